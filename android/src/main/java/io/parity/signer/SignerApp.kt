@@ -20,6 +20,9 @@ class SignerExceptionHandler : UncaughtExceptionHandler {
 		if (e is ErrorDisplayed.Str) {
 			Log.e(TAG, "Rust caused ErrorDisplay message was: ${e.s}")
 		}
+		if (e is ErrorDisplayed.Typed) {
+			Log.e(TAG, "Rust caused ErrorDisplay ${e.code} message was: ${e.message}")
+		}
 		val cause = e.cause
 		if (cause != null) {
 			uncaughtException(Thread.currentThread(), cause)
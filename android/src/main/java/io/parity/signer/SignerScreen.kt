@@ -50,6 +50,7 @@ fun ScreenSelector(
 		)
 		is ScreenData.Log -> HistoryScreen(screenData.f, button2)
 		is ScreenData.LogDetails -> LogDetails(screenData.f)
+		is ScreenData.LogSearch -> LogSearch(screenData.f, button2)
 		is ScreenData.ManageNetworks -> ManageNetworks(
 			screenData.f,
 			button2,
@@ -204,7 +205,7 @@ fun AlertSelector(
 			button = button1
 		)
 		is AlertData.Shield -> ShieldAlert(
-			// alert.f, // TODO: use this instead
+			shieldAlert = alert.f,
 			alertState = alertState,
 			button = button1,
 			acknowledgeWarning = acknowledgeWarning
@@ -5,16 +5,19 @@ import androidx.compose.runtime.State
 import io.parity.signer.components.AlertComponent
 import io.parity.signer.models.AlertState
 import io.parity.signer.uniffi.Action
+import io.parity.signer.uniffi.Exposure
+import io.parity.signer.uniffi.ExposureRecord
+import io.parity.signer.uniffi.ShieldAlert as ShieldAlertData
 
 @Composable
 fun ShieldAlert(
-	// shieldAlert: ShieldAlert, // TODO: use this instead of alertstate
+	shieldAlert: ShieldAlertData?,
 	alertState: State<AlertState?>,
 	button: (Action) -> Unit,
 	acknowledgeWarning: () -> Unit
 ) {
-	when (alertState.value) {
-		AlertState.Active -> {
+	when {
+		alertState.value == AlertState.Active -> {
 			AlertComponent(
 				show = true,
 				header = "Network connected!",
@@ -25,10 +28,11 @@ fun ShieldAlert(
 				showForward = false
 			)
 		}
-		AlertState.Past -> AlertComponent(
+		shieldAlert is ShieldAlertData.Past -> AlertComponent(
 			show = true,
-			header = "Network was connected!",
-			text = "Your Signer device has connected to a WiFi, tether or Bluetooth network since your last acknowledgement and should be considered unsafe to use. Please follow your security protocol",
+			header = "Signer was exposed!",
+			text = "Since your last acknowledgement your Signer device was exposed as listed below, and should be considered unsafe to use. Please follow your security protocol.\n\n" +
+				shieldAlert.exposures.joinToString("\n") { it.show() },
 			back = { button(Action.GO_BACK) },
 			forward = {
 				acknowledgeWarning()
@@ -37,7 +41,7 @@ fun ShieldAlert(
 			backText = "Back",
 			forwardText = "Acknowledge and reset"
 		)
-		AlertState.None -> AlertComponent(
+		alertState.value != null -> AlertComponent(
 			show = true,
 			header = "Signer is secure",
 			back = { button(Action.GO_BACK) },
@@ -58,3 +62,15 @@ fun ShieldAlert(
 		}
 	}
 }
+
+private fun ExposureRecord.show(): String {
+	val name = when (exposure) {
+		Exposure.DEVICE_WAS_ONLINE -> "Device was connected to network"
+		Exposure.USB_DEBUGGING -> "USB debugging was enabled"
+		Exposure.WIFI -> "Wi-Fi was enabled"
+		Exposure.BLUETOOTH -> "Bluetooth was enabled"
+		Exposure.SCREEN_RECORDING -> "Screen was recorded"
+		Exposure.BOOTLOADER_UNLOCKED -> "Bootloader was unlocked"
+	}
+	return firstSeen?.let { "$name, first seen $it" } ?: name
+}
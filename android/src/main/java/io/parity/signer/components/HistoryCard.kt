@@ -6,7 +6,14 @@ import androidx.compose.material.icons.outlined.Delete
 import androidx.compose.runtime.Composable
 import io.parity.signer.models.abbreviateString
 import io.parity.signer.models.encodeHex
+import io.parity.signer.models.render
 import io.parity.signer.uniffi.Event
+import io.parity.signer.uniffi.IdentityHistory
+import io.parity.signer.uniffi.NetworkSpecsAmendmentDisplay
+import io.parity.signer.uniffi.NetworkSpecsAmendmentTokensDisplay
+import io.parity.signer.uniffi.PolicyRule
+import io.parity.signer.uniffi.SecurityPolicy
+import io.parity.signer.uniffi.TokenSpecs
 import io.parity.signer.uniffi.ValidCurrentVerifier
 import io.parity.signer.uniffi.VerifierValue
 
@@ -68,7 +75,7 @@ fun HistoryCard(card: Event, timestamp: String) {
 				image = Icons.Default.Pattern,
 				line1 = timestamp,
 				line2 = "Key created",
-				line3 = card.identityHistory.seedName + card.identityHistory.path
+				line3 = card.identityHistory.show()
 			)
 		}
 		is Event.IdentityRemoved -> {
@@ -76,7 +83,7 @@ fun HistoryCard(card: Event, timestamp: String) {
 				image = Icons.Default.Delete,
 				line1 = timestamp,
 				line2 = "Key removed",
-				line3 = card.identityHistory.seedName + card.identityHistory.path
+				line3 = card.identityHistory.show()
 			)
 		}
 		is Event.SecretWasExported -> {
@@ -84,7 +91,7 @@ fun HistoryCard(card: Event, timestamp: String) {
 				image = Icons.Default.WbSunny,
 				line1 = timestamp,
 				line2 = "Secret was exported",
-				line3 = card.identityHistory.seedName + card.identityHistory.path
+				line3 = card.identityHistory.show()
 			)
 		}
 		is Event.MessageSignError -> {
@@ -292,6 +299,15 @@ fun HistoryCard(card: Event, timestamp: String) {
 				danger = true
 			)
 		}
+		is Event.WarningMessage -> {
+			HistoryCardTemplate(
+				image = Icons.Default.Warning,
+				line1 = timestamp,
+				line2 = "Warning!",
+				line3 = card.message.render(),
+				danger = true
+			)
+		}
 		is Event.WrongPassword -> {
 			HistoryCardTemplate(
 				image = Icons.Default.Warning,
@@ -301,5 +317,234 @@ fun HistoryCard(card: Event, timestamp: String) {
 				danger = true
 			)
 		}
+		is Event.HistoryAuditExported -> {
+			card.historyAuditExport.let {
+				HistoryCardTemplate(
+					image = Icons.Default.Verified,
+					line1 = timestamp,
+					line2 = "History audit exported",
+					line3 = "entries " + it.firstOrder + " to " + it.lastOrder
+				)
+			}
+		}
+		is Event.ContactAdded -> {
+			HistoryCardTemplate(
+				image = Icons.Default.PersonAdd,
+				line1 = timestamp,
+				line2 = "Contact added",
+				line3 = card.contactDisplay.name
+			)
+		}
+		is Event.ContactRemoved -> {
+			HistoryCardTemplate(
+				image = Icons.Default.PersonRemove,
+				line1 = timestamp,
+				line2 = "Contact removed",
+				line3 = card.contactDisplay.name
+			)
+		}
+		is Event.NetworkVerifierRotated -> {
+			card.networkVerifierRotationDisplay.let {
+				HistoryCardTemplate(
+					image = Icons.Default.Shield,
+					line1 = timestamp,
+					line2 = "Network verifier rotated",
+					line3 = it.oldVerifierValue.show() + " -> " + it.newVerifierValue.show() +
+						" for network with genesis hash " +
+						it.genesisHash.toUByteArray().toByteArray().encodeHex()
+				)
+			}
+		}
+		is Event.SecurityPolicySet -> {
+			HistoryCardTemplate(
+				image = Icons.Default.Policy,
+				line1 = timestamp,
+				line2 = "Security policy set",
+				line3 = card.securityPolicy.show()
+			)
+		}
+		is Event.SecurityPolicyViolation -> {
+			HistoryCardTemplate(
+				image = Icons.Default.Policy,
+				line1 = timestamp,
+				line2 = "Security policy violation attempted",
+				line3 = card.rule.show(),
+				danger = true
+			)
+		}
+		is Event.UsbDebuggingWasEnabled -> {
+			HistoryCardTemplate(
+				image = Icons.Default.Usb,
+				line1 = timestamp,
+				line2 = "USB debugging was enabled",
+				line3 = "",
+				danger = true
+			)
+		}
+		is Event.WifiWasEnabled -> {
+			HistoryCardTemplate(
+				image = Icons.Default.Wifi,
+				line1 = timestamp,
+				line2 = "Wi-Fi was enabled",
+				line3 = "",
+				danger = true
+			)
+		}
+		is Event.BluetoothWasEnabled -> {
+			HistoryCardTemplate(
+				image = Icons.Default.Bluetooth,
+				line1 = timestamp,
+				line2 = "Bluetooth was enabled",
+				line3 = "",
+				danger = true
+			)
+		}
+		is Event.ScreenWasRecorded -> {
+			HistoryCardTemplate(
+				image = Icons.Default.ScreenShare,
+				line1 = timestamp,
+				line2 = "Screen was recorded",
+				line3 = "",
+				danger = true
+			)
+		}
+		is Event.BootloaderWasUnlocked -> {
+			HistoryCardTemplate(
+				image = Icons.Default.LockOpen,
+				line1 = timestamp,
+				line2 = "Bootloader was unlocked",
+				line3 = "",
+				danger = true
+			)
+		}
+		is Event.DatabaseMigrated -> {
+			HistoryCardTemplate(
+				image = Icons.Default.Upgrade,
+				line1 = timestamp,
+				line2 = "Database migrated",
+				line3 = "schema version " + card.fromVersion.toString() +
+					" -> " + card.toVersion.toString()
+			)
+		}
+		is Event.DatabaseRepaired -> {
+			HistoryCardTemplate(
+				image = Icons.Default.Build,
+				line1 = timestamp,
+				line2 = "Database repaired",
+				line3 = card.repairs.joinToString("; ")
+			)
+		}
+		is Event.NetworkSpecsAmended -> {
+			card.networkSpecsAmendmentDisplay.let {
+				HistoryCardTemplate(
+					image = Icons.Default.Edit,
+					line1 = timestamp,
+					line2 = "Network specs amended: " + it.oldNetworkSpecs.title,
+					line3 = it.show()
+				)
+			}
+		}
+		is Event.KeyAnnotationChanged -> {
+			card.keyAnnotationHistory.let {
+				HistoryCardTemplate(
+					image = Icons.Default.Label,
+					line1 = timestamp,
+					line2 = "Key annotation changed",
+					line3 = it.seedName + it.path + if (it.annotation.label.isEmpty()) "" else " (" + it.annotation.label + ")"
+				)
+			}
+		}
+		is Event.NetworkSpecsTokensAdded -> {
+			card.networkSpecsTokensDisplay.let {
+				HistoryCardTemplate(
+					image = Icons.Default.QrCodeScanner,
+					line1 = timestamp,
+					line2 = "Network added",
+					line3 = it.networkSpecsDisplay.specs.title + it.tokens.showTokens()
+				)
+			}
+		}
+		is Event.NetworkSpecsTokensAmended -> {
+			card.networkSpecsAmendmentTokensDisplay.let {
+				HistoryCardTemplate(
+					image = Icons.Default.Edit,
+					line1 = timestamp,
+					line2 = "Network specs amended: " + it.networkSpecsAmendmentDisplay.oldNetworkSpecs.title,
+					line3 = it.show()
+				)
+			}
+		}
 	}
 }
+
+/**
+ * Key seed name and path, with user-defined label if any
+ */
+fun IdentityHistory.show(): String =
+	seedName + path + if (label.isEmpty()) "" else " ($label)"
+
+/**
+ * Abbreviated verifier public key
+ */
+fun VerifierValue.show(): String = when (this) {
+	is VerifierValue.Standard -> m.getOrElse(0) { "" }.abbreviateString(8)
+}
+
+/**
+ * Rules enforced by security policy
+ */
+fun SecurityPolicy.show(): String = listOfNotNull(
+	PolicyRule.UNSIGNED_UPDATE.takeIf { rejectUnsignedUpdates },
+	PolicyRule.VERIFIER_CHANGE.takeIf { forbidVerifierChange },
+	PolicyRule.SECRET_EXPORT.takeIf { forbidSecretExport },
+	PolicyRule.OUTDATED_METADATA.takeIf { forbidOutdatedMetadata },
+	PolicyRule.USER_COMMENT.takeIf { requireUserComment },
+).joinToString(", ") { it.show() }.ifEmpty { "nothing is enforced" }
+
+fun PolicyRule.show(): String = when (this) {
+	PolicyRule.UNSIGNED_UPDATE -> "unsigned updates are rejected"
+	PolicyRule.VERIFIER_CHANGE -> "verifier change is forbidden"
+	PolicyRule.SECRET_EXPORT -> "secret export is forbidden"
+	PolicyRule.OUTDATED_METADATA -> "outdated metadata is forbidden"
+	PolicyRule.USER_COMMENT -> "user comment is required"
+}
+
+/**
+ * Changed display specs of amended network
+ */
+fun NetworkSpecsAmendmentDisplay.show(): String = listOfNotNull(
+	specsChange("title", oldNetworkSpecs.title, newNetworkSpecs.title),
+	specsChange("unit", oldNetworkSpecs.unit, newNetworkSpecs.unit),
+	specsChange(
+		"decimals",
+		oldNetworkSpecs.decimals.toString(),
+		newNetworkSpecs.decimals.toString()
+	),
+	specsChange("logo", oldNetworkSpecs.logo, newNetworkSpecs.logo),
+	specsChange("color", oldNetworkSpecs.color, newNetworkSpecs.color),
+	specsChange(
+		"secondary color",
+		oldNetworkSpecs.secondaryColor,
+		newNetworkSpecs.secondaryColor
+	),
+).joinToString(", ")
+
+/**
+ * Changed display specs of amended network, with changed token list
+ */
+fun NetworkSpecsAmendmentTokensDisplay.show(): String = listOfNotNull(
+	networkSpecsAmendmentDisplay.show().ifEmpty { null },
+	specsChange("tokens", oldTokens.showList(), newTokens.showList()),
+).joinToString(", ")
+
+/**
+ * Network tokens other than the main one, if any
+ */
+fun List<TokenSpecs>.showTokens(): String =
+	if (isEmpty()) "" else " " + showList()
+
+private fun List<TokenSpecs>.showList(): String =
+	joinToString(", ", "[", "]") { it.unit + " (" + it.decimals + ")" }
+
+private fun specsChange(field: String, old: String, new: String): String? =
+	if (old == new) null else "$field: $old -> $new"
@@ -9,6 +9,7 @@ import androidx.compose.material.icons.outlined.Delete
 import androidx.compose.runtime.Composable
 import io.parity.signer.models.abbreviateString
 import io.parity.signer.models.encodeHex
+import io.parity.signer.models.render
 import io.parity.signer.uniffi.Event
 import io.parity.signer.uniffi.MEventMaybeDecoded
 import io.parity.signer.uniffi.ValidCurrentVerifier
@@ -82,7 +83,7 @@ fun HistoryCardExtended(
 					image = Icons.Default.Pattern,
 					line1 = timestamp,
 					line2 = "Key created",
-					line3 = it.seedName + it.path
+					line3 = it.show()
 				)
 			}
 		}
@@ -92,7 +93,7 @@ fun HistoryCardExtended(
 					image = Icons.Default.Delete,
 					line1 = timestamp,
 					line2 = "Key removed",
-					line3 = it.seedName + it.path
+					line3 = it.show()
 				)
 			}
 		}
@@ -102,7 +103,7 @@ fun HistoryCardExtended(
 					image = Icons.Default.WbSunny,
 					line1 = timestamp,
 					line2 = "Secret was exported",
-					line3 = it.seedName + it.path
+					line3 = it.show()
 				)
 			}
 		}
@@ -324,6 +325,15 @@ fun HistoryCardExtended(
 				danger = true
 			)
 		}
+		is Event.WarningMessage -> {
+			HistoryCardTemplate(
+				image = Icons.Default.Warning,
+				line1 = timestamp,
+				line2 = "Warning!",
+				line3 = eventVal.message.render(),
+				danger = true
+			)
+		}
 		is Event.WrongPassword -> {
 			HistoryCardTemplate(
 				image = Icons.Default.Warning,
@@ -333,5 +343,162 @@ fun HistoryCardExtended(
 				danger = true
 			)
 		}
+		is Event.HistoryAuditExported -> {
+			eventVal.historyAuditExport.let {
+				HistoryCardTemplate(
+					image = Icons.Default.Verified,
+					line1 = timestamp,
+					line2 = "History audit exported",
+					line3 = "entries " + it.firstOrder + " to " + it.lastOrder
+				)
+			}
+		}
+		is Event.ContactAdded -> {
+			HistoryCardTemplate(
+				image = Icons.Default.PersonAdd,
+				line1 = timestamp,
+				line2 = "Contact added",
+				line3 = eventVal.contactDisplay.name
+			)
+		}
+		is Event.ContactRemoved -> {
+			HistoryCardTemplate(
+				image = Icons.Default.PersonRemove,
+				line1 = timestamp,
+				line2 = "Contact removed",
+				line3 = eventVal.contactDisplay.name
+			)
+		}
+		is Event.NetworkVerifierRotated -> {
+			eventVal.networkVerifierRotationDisplay.let {
+				HistoryCardTemplate(
+					image = Icons.Default.Shield,
+					line1 = timestamp,
+					line2 = "Network verifier rotated",
+					line3 = it.oldVerifierValue.show() + " -> " + it.newVerifierValue.show() +
+						" for network with genesis hash " +
+						it.genesisHash.toUByteArray().toByteArray().encodeHex()
+				)
+			}
+		}
+		is Event.SecurityPolicySet -> {
+			HistoryCardTemplate(
+				image = Icons.Default.Policy,
+				line1 = timestamp,
+				line2 = "Security policy set",
+				line3 = eventVal.securityPolicy.show()
+			)
+		}
+		is Event.SecurityPolicyViolation -> {
+			HistoryCardTemplate(
+				image = Icons.Default.Policy,
+				line1 = timestamp,
+				line2 = "Security policy violation attempted",
+				line3 = eventVal.rule.show(),
+				danger = true
+			)
+		}
+		is Event.UsbDebuggingWasEnabled -> {
+			HistoryCardTemplate(
+				image = Icons.Default.Usb,
+				line1 = timestamp,
+				line2 = "USB debugging was enabled",
+				line3 = "",
+				danger = true
+			)
+		}
+		is Event.WifiWasEnabled -> {
+			HistoryCardTemplate(
+				image = Icons.Default.Wifi,
+				line1 = timestamp,
+				line2 = "Wi-Fi was enabled",
+				line3 = "",
+				danger = true
+			)
+		}
+		is Event.BluetoothWasEnabled -> {
+			HistoryCardTemplate(
+				image = Icons.Default.Bluetooth,
+				line1 = timestamp,
+				line2 = "Bluetooth was enabled",
+				line3 = "",
+				danger = true
+			)
+		}
+		is Event.ScreenWasRecorded -> {
+			HistoryCardTemplate(
+				image = Icons.Default.ScreenShare,
+				line1 = timestamp,
+				line2 = "Screen was recorded",
+				line3 = "",
+				danger = true
+			)
+		}
+		is Event.BootloaderWasUnlocked -> {
+			HistoryCardTemplate(
+				image = Icons.Default.LockOpen,
+				line1 = timestamp,
+				line2 = "Bootloader was unlocked",
+				line3 = "",
+				danger = true
+			)
+		}
+		is Event.DatabaseMigrated -> {
+			HistoryCardTemplate(
+				image = Icons.Default.Upgrade,
+				line1 = timestamp,
+				line2 = "Database migrated",
+				line3 = "schema version " + eventVal.fromVersion.toString() +
+					" -> " + eventVal.toVersion.toString()
+			)
+		}
+		is Event.DatabaseRepaired -> {
+			HistoryCardTemplate(
+				image = Icons.Default.Build,
+				line1 = timestamp,
+				line2 = "Database repaired",
+				line3 = eventVal.repairs.joinToString("; ")
+			)
+		}
+		is Event.NetworkSpecsAmended -> {
+			eventVal.networkSpecsAmendmentDisplay.let {
+				HistoryCardTemplate(
+					image = Icons.Default.Edit,
+					line1 = timestamp,
+					line2 = "Network specs amended: " + it.oldNetworkSpecs.title,
+					line3 = it.show()
+				)
+			}
+		}
+		is Event.KeyAnnotationChanged -> {
+			eventVal.keyAnnotationHistory.let {
+				HistoryCardTemplate(
+					image = Icons.Default.Label,
+					line1 = timestamp,
+					line2 = "Key annotation changed",
+					line3 = it.seedName + it.path + if (it.annotation.label.isEmpty()) "" else " (" + it.annotation.label + ")"
+				)
+			}
+		}
+		is Event.NetworkSpecsTokensAdded -> {
+			eventVal.networkSpecsTokensDisplay.let {
+				HistoryCardTemplate(
+					image = Icons.Default.QrCodeScanner,
+					line1 = timestamp,
+					line2 = "Network added",
+					line3 = it.networkSpecsDisplay.specs.title + it.tokens.showTokens()
+				)
+			}
+		}
+		is Event.NetworkSpecsTokensAmended -> {
+			eventVal.networkSpecsAmendmentTokensDisplay.let {
+				HistoryCardTemplate(
+					image = Icons.Default.Edit,
+					line1 = timestamp,
+					line2 = "Network specs amended: " + it.networkSpecsAmendmentDisplay.oldNetworkSpecs.title,
+					line3 = it.show()
+				)
+			}
+		}
 	}
 }
@@ -33,6 +33,7 @@ fun TransactionCard(card: TransactionCard) {
 			is Card.BitVecCard -> TCBitVec(bitVec = txCard.f)
 			is Card.BlockHashCard -> TCBlockHash(text = txCard.f)
 			is Card.CallCard -> TCMethod(payload = txCard.f)
+			is Card.ContactCard -> TCContact(contact = txCard.f)
 			is Card.DefaultCard -> Text(
 				txCard.f,
 				style = MaterialTheme.typography.body2,
@@ -46,6 +46,7 @@ fun TransactionPreviewField(
 			.padding(8.dp)
 	) {
 		transactionCards(this, cardSet.author)
+		transactionCards(this, cardSet.contacts)
 		transactionCards(this, cardSet.error)
 		transactionCards(
 			this,
@@ -58,7 +59,9 @@ fun TransactionPreviewField(
 		transactionCards(this, cardSet.message)
 		transactionCards(this, cardSet.meta)
 		transactionCards(this, cardSet.method)
+		transactionCards(this, cardSet.oldSpecs)
 		transactionCards(this, cardSet.newSpecs)
+		transactionCards(this, cardSet.newVerifier)
 		transactionCards(this, cardSet.verifier)
 		transactionCards(this, cardSet.warning)
 		transactionCards(
@@ -0,0 +1,30 @@
+package io.parity.signer.components.transactionCards
+
+import androidx.compose.foundation.layout.Column
+import androidx.compose.foundation.layout.Row
+import androidx.compose.material.MaterialTheme
+import androidx.compose.material.Text
+import androidx.compose.runtime.Composable
+import io.parity.signer.components.Identicon
+import io.parity.signer.ui.theme.Text400
+import io.parity.signer.ui.theme.Text600
+import io.parity.signer.uniffi.MscContact
+
+@Composable
+fun TCContact(contact: MscContact) {
+	Row {
+		Identicon(identicon = contact.identicon)
+		Column {
+			Text(
+				contact.name,
+				style = MaterialTheme.typography.body2,
+				color = MaterialTheme.colors.Text600
+			)
+			Text(
+				contact.base58,
+				style = MaterialTheme.typography.body2,
+				color = MaterialTheme.colors.Text400
+			)
+		}
+	}
+}
@@ -12,6 +12,7 @@ fun TCID(id: MscId) {
 	Row {
 		Identicon(identicon = id.identicon)
 		Column {
+			id.contactName?.let { Text(it) }
 			Text(id.base58)
 		}
 	}
@@ -0,0 +1,36 @@
+package io.parity.signer.models
+
+import io.parity.signer.uniffi.LocalisedMessage
+import io.parity.signer.uniffi.MessageArgValue
+import io.parity.signer.uniffi.englishMessageCatalog
+
+/**
+ * Message templates by identifier, fetched from backend once
+ */
+private val messageCatalog: Map<String, String> by lazy {
+	englishMessageCatalog().associate { it.id to it.template }
+}
+
+/**
+ * Render message from backend with English catalog;
+ * message with unknown identifier is shown as the identifier itself
+ */
+fun LocalisedMessage.render(): String {
+	val template = messageCatalog[id] ?: return id
+	return args.fold(template) { text, arg ->
+		text.replace("{" + arg.name + "}", arg.value.render())
+	}
+}
+
+private fun MessageArgValue.render(): String {
+	return when (this) {
+		is MessageArgValue.Text -> s
+		is MessageArgValue.Number -> n.toString()
+		is MessageArgValue.Hex -> h.toUByteArray().toByteArray().encodeHex()
+		is MessageArgValue.List -> if (items.isEmpty()) {
+			LocalisedMessage("list.none", listOf()).render()
+		} else {
+			items.joinToString(", ")
+		}
+	}
+}
@@ -3,7 +3,7 @@ package io.parity.signer.models
 import android.util.Log
 import android.widget.Toast
 import io.parity.signer.uniffi.Action
-import io.parity.signer.uniffi.backendAction
+import io.parity.signer.uniffi.backendSessionAction
 
 /**
  * This pretty much offloads all navigation to backend!
@@ -14,7 +14,9 @@ fun SignerDataModel.pushButton(
 	seedPhrase: String = ""
 ) {
 	try {
-		_actionResult.value = backendAction(button, details, seedPhrase)
+		_actionResult.value = navigationSession?.let {
+			backendSessionAction(it, button, details, seedPhrase)
+		}
 	} catch (e: java.lang.Exception) {
 		Log.e("Navigation error", e.toString())
 		Toast.makeText(context, e.toString(), Toast.LENGTH_SHORT).show()
@@ -5,8 +5,9 @@ import android.widget.Toast
 import io.parity.signer.components.SeedBoxStatus
 import io.parity.signer.uniffi.Action
 import io.parity.signer.uniffi.historySeedNameWasShown
-import io.parity.signer.uniffi.initNavigation
-import io.parity.signer.uniffi.updateSeedNames
+import io.parity.signer.uniffi.closeNavigationSession
+import io.parity.signer.uniffi.initNavigationSession
+import io.parity.signer.uniffi.updateSessionSeedNames
 
 /**
  * Refresh seed names list
@@ -17,9 +18,10 @@ import io.parity.signer.uniffi.updateSeedNames
 internal fun SignerDataModel.refreshSeedNames(init: Boolean = false) {
 	val allNames = sharedPreferences.all.keys.sorted().toTypedArray()
 	if (init) {
-		initNavigation(dbName, allNames.toList())
+		navigationSession?.let { closeNavigationSession(it) }
+		navigationSession = initNavigationSession(dbName, allNames.toList())
 	} else {
-		updateSeedNames(allNames.toList())
+		navigationSession?.let { updateSessionSeedNames(it, allNames.toList()) }
 	}
 	_seedNames.value = allNames
 }
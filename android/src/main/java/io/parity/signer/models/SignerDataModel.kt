@@ -80,6 +80,7 @@ class SignerDataModel : ViewModel() {
 			alertData = null,
 		)
 	)
+	internal var navigationSession: UInt? = null
 
 	// Data storage locations
 	internal var dbName: String = ""
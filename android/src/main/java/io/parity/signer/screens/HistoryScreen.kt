@@ -3,11 +3,14 @@ package io.parity.signer.screens
 import androidx.compose.foundation.clickable
 import androidx.compose.foundation.layout.Column
 import androidx.compose.foundation.layout.Row
+import androidx.compose.foundation.layout.padding
 import androidx.compose.foundation.lazy.LazyColumn
 import androidx.compose.foundation.lazy.items
 import androidx.compose.runtime.Composable
 import androidx.compose.ui.Modifier
+import androidx.compose.ui.unit.dp
 import io.parity.signer.components.HistoryCard
+import io.parity.signer.components.SmallButton
 import io.parity.signer.uniffi.Action
 import io.parity.signer.uniffi.MLog
 
@@ -19,6 +22,11 @@ fun HistoryScreen(
 	val history = mLog.log
 
 	Column {
+		Row(Modifier.padding(horizontal = 12.dp, vertical = 4.dp)) {
+			SmallButton(text = "Search log") {
+				button(Action.SEARCH_LOG, "")
+			}
+		}
 		LazyColumn {
 			for (record in history) {
 				val timestamp = record.timestamp
@@ -0,0 +1,119 @@
+package io.parity.signer.screens
+
+import androidx.compose.foundation.clickable
+import androidx.compose.foundation.layout.*
+import androidx.compose.foundation.lazy.LazyColumn
+import androidx.compose.foundation.lazy.items
+import androidx.compose.material.MaterialTheme
+import androidx.compose.material.Text
+import androidx.compose.runtime.Composable
+import androidx.compose.runtime.DisposableEffect
+import androidx.compose.runtime.mutableStateOf
+import androidx.compose.runtime.remember
+import androidx.compose.ui.Alignment
+import androidx.compose.ui.Modifier
+import androidx.compose.ui.focus.FocusRequester
+import androidx.compose.ui.platform.LocalFocusManager
+import androidx.compose.ui.unit.dp
+import io.parity.signer.components.BigButton
+import io.parity.signer.components.HeadingOverline
+import io.parity.signer.components.HistoryCard
+import io.parity.signer.components.SingleTextInput
+import io.parity.signer.components.SmallButton
+import io.parity.signer.ui.theme.Text600
+import io.parity.signer.uniffi.Action
+import io.parity.signer.uniffi.MLogSearch
+
+/**
+ * History log search with query like `kind=IdentityAdded;seed=Alice`,
+ * found entries are paged
+ */
+@Composable
+fun LogSearch(
+	mLogSearch: MLogSearch,
+	button: (action: Action, details: String) -> Unit
+) {
+	val query = remember { mutableStateOf(mLogSearch.query) }
+	val focusManager = LocalFocusManager.current
+	val focusRequester = remember { FocusRequester() }
+
+	Column(
+		modifier = Modifier.padding(horizontal = 12.dp)
+	) {
+		Row {
+			HeadingOverline("SEARCH LOG")
+			Spacer(Modifier.weight(1f))
+		}
+		SingleTextInput(
+			content = query,
+			update = {
+				query.value = it
+			},
+			onDone = {
+				button(Action.SEARCH_LOG, query.value)
+			},
+			capitalize = false,
+			focusManager = focusManager,
+			focusRequester = focusRequester
+		)
+		Text(
+			"kind, network, address, seed, verifier, text, from, to",
+			style = MaterialTheme.typography.caption,
+			color = MaterialTheme.colors.Text600
+		)
+		BigButton(
+			text = "Search",
+			action = {
+				focusManager.clearFocus()
+				button(Action.SEARCH_LOG, query.value)
+			}
+		)
+		Row(
+			verticalAlignment = Alignment.CenterVertically,
+			modifier = Modifier.padding(vertical = 8.dp)
+		) {
+			SmallButton(
+				text = "Previous",
+				isDisabled = mLogSearch.page == 0u
+			) { button(Action.PREVIOUS_UNIT, "") }
+			Spacer(Modifier.weight(1f))
+			Text(
+				if (mLogSearch.totalPages == 0u) "Nothing found"
+				else "Page " + (mLogSearch.page + 1u) + " out of " + mLogSearch.totalPages
+			)
+			Spacer(Modifier.weight(1f))
+			SmallButton(
+				text = "Next",
+				isDisabled = mLogSearch.page + 1u >= mLogSearch.totalPages
+			) { button(Action.NEXT_UNIT, "") }
+		}
+		LazyColumn {
+			for (record in mLogSearch.log) {
+				val timestamp = record.timestamp
+
+				this.items(
+					items = record.events,
+					key = { record.order.toString() + it.toString() }
+				) { item ->
+					Row(
+						Modifier.clickable {
+							button(
+								Action.SHOW_LOG_DETAILS,
+								record.order.toString()
+							)
+						}
+					) {
+						HistoryCard(
+							item,
+							timestamp
+						)
+					}
+				}
+			}
+		}
+	}
+
+	DisposableEffect(Unit) {
+		onDispose { focusManager.clearFocus() }
+	}
+}
@@ -26,10 +26,10 @@ struct ShieldAlertComponent: View {
                         message: { Localizable.networkConnectedMessage.text }
                     )
             } else {
-                if content == .past {
+                if case let .past(exposures)? = content {
                     Text("")
                         .alert(
-                            Localizable.networkWasConnected.key,
+                            Localizable.ShieldAlert.past.key,
                             isPresented: $show,
                             actions: {
                                 Button(Localizable.back.key) { navigationRequest(.init(action: .goBack)) }
@@ -37,7 +37,7 @@ struct ShieldAlertComponent: View {
                                     resetAlert()
                                 }
                             },
-                            message: { Localizable.networkWasConnectedMessage.text }
+                            message: { Text(pastMessage(exposures)) }
                         )
                 } else {
                     Text("")
@@ -55,6 +55,34 @@ struct ShieldAlertComponent: View {
     }
 }
 
+private func pastMessage(_ exposures: [ExposureRecord]) -> String {
+    ([Localizable.ShieldAlert.pastMessage.string] + exposures.map { record in
+        if let firstSeen = record.firstSeen {
+            return Localizable.ShieldAlert.firstSeen(record.exposure.label, firstSeen)
+        }
+        return record.exposure.label
+    }).joined(separator: "\n")
+}
+
+private extension Exposure {
+    var label: String {
+        switch self {
+        case .deviceWasOnline:
+            return Localizable.HistoryCard.deviceConnected.string
+        case .usbDebugging:
+            return Localizable.HistoryCard.usbDebuggingWasEnabled.string
+        case .wifi:
+            return Localizable.HistoryCard.wifiWasEnabled.string
+        case .bluetooth:
+            return Localizable.HistoryCard.bluetoothWasEnabled.string
+        case .screenRecording:
+            return Localizable.HistoryCard.screenWasRecorded.string
+        case .bootloaderUnlocked:
+            return Localizable.HistoryCard.bootloaderWasUnlocked.string
+        }
+    }
+}
+
 // struct ShieldAlert_Previews: PreviewProvider {
 // static var previews: some View {
 // ShieldAlert()
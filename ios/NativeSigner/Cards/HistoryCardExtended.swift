@@ -30,16 +30,34 @@ struct HistoryCardExtended: View {
              .typesRemoved,
              .userEntry,
              .warning,
+             .warningMessage,
              .wrongPassword,
              .messageSignError,
-             .messageSigned:
+             .messageSigned,
+             .historyAuditExported,
+             .contactAdded,
+             .contactRemoved,
+             .networkVerifierRotated,
+             .securityPolicySet,
+             .securityPolicyViolation,
+             .usbDebuggingWasEnabled,
+             .wifiWasEnabled,
+             .bluetoothWasEnabled,
+             .screenWasRecorded,
+             .bootloaderWasUnlocked,
+             .databaseMigrated,
+             .databaseRepaired,
+             .networkSpecsAmended,
+             .keyAnnotationChanged,
+             .networkSpecsTokensAdded,
+             .networkSpecsTokensAmended:
             HistoryCard(event: event.event)
         case let .identityAdded(value):
             HistoryCardTemplate(
                 image: .init(.aqi, variant: .medium),
                 danger: false,
                 line1: Localizable.keyCreated.string,
-                line2: value.seedName + value.path + " in network with hash " +
+                line2: value.displayName + " in network with hash " +
                     value.networkGenesisHash.formattedAsString
             )
         case let .identityRemoved(value):
@@ -47,7 +65,7 @@ struct HistoryCardExtended: View {
                 image: .init(.xmark, variants: [.rectangle, .portrait]),
                 danger: false,
                 line1: Localizable.keyRemoved.string,
-                line2: value.seedName + value.path + " in network with hash " +
+                line2: value.displayName + " in network with hash " +
                     value.networkGenesisHash.formattedAsString
             )
         case let .secretWasExported(value):
@@ -55,7 +73,7 @@ struct HistoryCardExtended: View {
                 image: .init(.eye, variants: [.trianglebadge, .exclamationmark, .fill]),
                 danger: true,
                 line1: Localizable.secretWasExported.string,
-                line2: value.seedName + value.path + " in network with hash " +
+                line2: value.displayName + " in network with hash " +
                     value.networkGenesisHash.formattedAsString
             )
         case let .networkVerifierSet(value):
@@ -26,6 +26,8 @@ struct TransactionCardSelector: View {
                 TCBlockHash(text: text)
             case let .callCard(value):
                 TCCall(value: value)
+            case let .contactCard(value):
+                TCContact(value: value)
             case let .defaultCard(text):
                 TCDefault(content: text)
             case let .derivationsCard(value):
@@ -76,7 +78,7 @@ struct TransactionCardSelector: View {
                 TCVarName(text: text)
             case let .verifierCard(value):
                 TCVerifier(value: value)
-            case let .warningCard(text):
+            case let .warningCard(text, _):
                 TCWarning(text: text)
             case let .networkGenesisHashCard(text):
                 TCGenesisHash(content: text)
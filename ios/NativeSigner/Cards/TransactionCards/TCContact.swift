@@ -0,0 +1,22 @@
+//
+//  TCContact.swift
+//  NativeSigner
+//
+
+import SwiftUI
+
+struct TCContact: View {
+    var value: MscContact
+    var body: some View {
+        HStack {
+            Identicon(identicon: value.identicon)
+            VStack(alignment: .leading) {
+                Text(value.name)
+                    .foregroundColor(Asset.text600.swiftUIColor).font(Fontstyle.body2.base)
+                Text(value.base58)
+                    .foregroundColor(Asset.text400.swiftUIColor).font(Fontstyle.body2.crypto)
+            }
+            Spacer()
+        }
+    }
+}
@@ -12,8 +12,14 @@ struct TCID: View {
     var body: some View {
         HStack {
             Identicon(identicon: value.identicon)
-            Text(value.base58)
-                .foregroundColor(Asset.text600.swiftUIColor).font(Fontstyle.body2.crypto)
+            VStack(alignment: .leading) {
+                if let contactName = value.contactName {
+                    Text(contactName)
+                        .foregroundColor(Asset.text600.swiftUIColor).font(Fontstyle.body2.base)
+                }
+                Text(value.base58)
+                    .foregroundColor(Asset.text600.swiftUIColor).font(Fontstyle.body2.crypto)
+            }
             Spacer()
         }
     }
@@ -54,7 +54,7 @@ struct HistoryCard: View {
                 timestamp: timestamp,
                 danger: false,
                 line1: Localizable.HistoryCard.keysCreated.string,
-                line2: value.seedName + value.path
+                line2: value.displayName
             )
         case let .identityRemoved(value):
             HistoryCardTemplate(
@@ -62,7 +62,7 @@ struct HistoryCard: View {
                 timestamp: timestamp,
                 danger: false,
                 line1: Localizable.HistoryCard.keysRemoved.string,
-                line2: value.seedName + value.path
+                line2: value.displayName
             )
         case let .secretWasExported(value):
             HistoryCardTemplate(
@@ -70,7 +70,7 @@ struct HistoryCard: View {
                 timestamp: timestamp,
                 danger: true,
                 line1: Localizable.HistoryCard.secretWasExported.string,
-                line2: value.seedName + value.path
+                line2: value.displayName
             )
         case let .metadataAdded(value):
             HistoryCardTemplate(
@@ -223,6 +223,13 @@ struct HistoryCard: View {
                 danger: true,
                 line1: Localizable.HistoryCard.warning(text)
             )
+        case let .warningMessage(message):
+            HistoryCardTemplate(
+                image: .init(.exclamationmark, variants: [.triangle, .fill]),
+                timestamp: timestamp,
+                danger: true,
+                line1: Localizable.HistoryCard.warning(message.rendered)
+            )
         case .wrongPassword:
             HistoryCardTemplate(
                 image: .init(.exclamationmark, variants: [.triangle, .fill]),
@@ -247,12 +254,228 @@ struct HistoryCard: View {
                 line1: Localizable.HistoryCard.messageSigned.string,
                 line2: value.userComment
             )
+        case let .historyAuditExported(value):
+            HistoryCardTemplate(
+                image: .init(.signature),
+                timestamp: timestamp,
+                danger: false,
+                line1: Localizable.HistoryCard.historyAuditExported.string,
+                line2: Localizable.HistoryCard.historyAuditRange(
+                    String(value.firstOrder),
+                    String(value.lastOrder)
+                )
+            )
+        case let .contactAdded(value):
+            HistoryCardTemplate(
+                image: .init(.person, variants: [.crop, .circle, .badge, .plus]),
+                timestamp: timestamp,
+                danger: false,
+                line1: Localizable.HistoryCard.contactAdded.string,
+                line2: value.contactDisplay.name
+            )
+        case let .contactRemoved(value):
+            HistoryCardTemplate(
+                image: .init(.person, variants: [.crop, .circle, .badge, .minus]),
+                timestamp: timestamp,
+                danger: false,
+                line1: Localizable.HistoryCard.contactRemoved.string,
+                line2: value.contactDisplay.name
+            )
+        case let .networkVerifierRotated(value):
+            HistoryCardTemplate(
+                image: .init(.checkmark, variant: .shield),
+                timestamp: timestamp,
+                danger: false,
+                line1: Localizable.HistoryCard.networkVerifierRotated.string,
+                line2: value.oldVerifierValue.show() + " -> " + value.newVerifierValue.show() +
+                    " for network with genesis hash " + value.genesisHash.formattedAsString
+            )
+        case let .securityPolicySet(value):
+            HistoryCardTemplate(
+                image: .init(.lock, variant: .shield),
+                timestamp: timestamp,
+                danger: false,
+                line1: Localizable.HistoryCard.securityPolicySet.string,
+                line2: value.show()
+            )
+        case let .securityPolicyViolation(rule):
+            HistoryCardTemplate(
+                image: .init(.exclamationmark, variant: .shield),
+                timestamp: timestamp,
+                danger: true,
+                line1: Localizable.HistoryCard.securityPolicyViolation.string,
+                line2: rule.show()
+            )
+        case .usbDebuggingWasEnabled:
+            HistoryCardTemplate(
+                image: .init(.xmark, variants: [.shield, .fill]),
+                timestamp: timestamp,
+                danger: true,
+                line1: Localizable.HistoryCard.usbDebuggingWasEnabled.string
+            )
+        case .wifiWasEnabled:
+            HistoryCardTemplate(
+                image: .init(.xmark, variants: [.shield, .fill]),
+                timestamp: timestamp,
+                danger: true,
+                line1: Localizable.HistoryCard.wifiWasEnabled.string
+            )
+        case .bluetoothWasEnabled:
+            HistoryCardTemplate(
+                image: .init(.xmark, variants: [.shield, .fill]),
+                timestamp: timestamp,
+                danger: true,
+                line1: Localizable.HistoryCard.bluetoothWasEnabled.string
+            )
+        case .screenWasRecorded:
+            HistoryCardTemplate(
+                image: .init(.xmark, variants: [.shield, .fill]),
+                timestamp: timestamp,
+                danger: true,
+                line1: Localizable.HistoryCard.screenWasRecorded.string
+            )
+        case .bootloaderWasUnlocked:
+            HistoryCardTemplate(
+                image: .init(.xmark, variants: [.shield, .fill]),
+                timestamp: timestamp,
+                danger: true,
+                line1: Localizable.HistoryCard.bootloaderWasUnlocked.string
+            )
+        case let .databaseMigrated(fromVersion, toVersion):
+            HistoryCardTemplate(
+                image: .init(.arrow, variants: [.up, .circle]),
+                timestamp: timestamp,
+                danger: false,
+                line1: Localizable.HistoryCard.databaseMigrated.string,
+                line2: Localizable.HistoryCard.databaseMigratedVersions(String(fromVersion), String(toVersion))
+            )
+        case let .databaseRepaired(repairs):
+            HistoryCardTemplate(
+                image: .init(.wrench),
+                timestamp: timestamp,
+                danger: false,
+                line1: Localizable.HistoryCard.databaseRepaired.string,
+                line2: repairs.joined(separator: "; ")
+            )
+        case let .networkSpecsAmended(value):
+            HistoryCardTemplate(
+                image: .init(.doc, variant: .text),
+                timestamp: timestamp,
+                danger: false,
+                line1: Localizable.HistoryCard.networkSpecsAmended(value.oldNetworkSpecs.title),
+                line2: value.show()
+            )
+        case let .keyAnnotationChanged(value):
+            HistoryCardTemplate(
+                image: .init(.tag),
+                timestamp: timestamp,
+                danger: false,
+                line1: Localizable.HistoryCard.keyAnnotationChanged.string,
+                line2: value.seedName + value.path +
+                    (value.annotation.label.isEmpty ? "" : " (\(value.annotation.label))")
+            )
+        case let .networkSpecsTokensAdded(value):
+            HistoryCardTemplate(
+                image: .init(.plus, variant: .viewfinder),
+                timestamp: timestamp,
+                danger: false,
+                line1: Localizable.HistoryCard.networkAdded.string,
+                line2: value.networkSpecsDisplay.specs.title + value.tokens.show()
+            )
+        case let .networkSpecsTokensAmended(value):
+            HistoryCardTemplate(
+                image: .init(.doc, variant: .text),
+                timestamp: timestamp,
+                danger: false,
+                line1: Localizable.HistoryCard
+                    .networkSpecsAmended(value.networkSpecsAmendmentDisplay.oldNetworkSpecs.title),
+                line2: value.show()
+            )
         }
     }
 }
 
+extension IdentityHistory {
+    /// Key seed name and path, with user-defined label if any
+    var displayName: String {
+        seedName + path + (label.isEmpty ? "" : " (\(label))")
+    }
+}
+
 // struct HistoryCard_Previews: PreviewProvider {
 // static var previews: some View {
 // HistoryCard()
 // }
 // }
+
+extension SecurityPolicy {
+    /// Rules enforced by security policy
+    func show() -> String {
+        let rules: [PolicyRule] = [
+            rejectUnsignedUpdates ? .unsignedUpdate : nil,
+            forbidVerifierChange ? .verifierChange : nil,
+            forbidSecretExport ? .secretExport : nil,
+            forbidOutdatedMetadata ? .outdatedMetadata : nil,
+            requireUserComment ? .userComment : nil
+        ].compactMap { $0 }
+        guard !rules.isEmpty else { return Localizable.PolicyRule.nothing.string }
+        return rules.map { $0.show() }.joined(separator: ", ")
+    }
+}
+
+extension PolicyRule {
+    func show() -> String {
+        switch self {
+        case .unsignedUpdate:
+            return Localizable.PolicyRule.unsignedUpdate.string
+        case .verifierChange:
+            return Localizable.PolicyRule.verifierChange.string
+        case .secretExport:
+            return Localizable.PolicyRule.secretExport.string
+        case .outdatedMetadata:
+            return Localizable.PolicyRule.outdatedMetadata.string
+        case .userComment:
+            return Localizable.PolicyRule.userComment.string
+        }
+    }
+}
+
+extension NetworkSpecsAmendmentDisplay {
+    /// Changed display specs of amended network
+    func show() -> String {
+        let changes: [(String, String, String)] = [
+            ("title", oldNetworkSpecs.title, newNetworkSpecs.title),
+            ("unit", oldNetworkSpecs.unit, newNetworkSpecs.unit),
+            ("decimals", String(oldNetworkSpecs.decimals), String(newNetworkSpecs.decimals)),
+            ("logo", oldNetworkSpecs.logo, newNetworkSpecs.logo),
+            ("color", oldNetworkSpecs.color, newNetworkSpecs.color),
+            ("secondary color", oldNetworkSpecs.secondaryColor, newNetworkSpecs.secondaryColor)
+        ]
+        return changes
+            .filter { $0.1 != $0.2 }
+            .map { "\($0.0): \($0.1) -> \($0.2)" }
+            .joined(separator: ", ")
+    }
+}
+
+extension NetworkSpecsAmendmentTokensDisplay {
+    /// Changed display specs of amended network, with changed token list
+    func show() -> String {
+        var changes = [networkSpecsAmendmentDisplay.show()].filter { !$0.isEmpty }
+        if oldTokens != newTokens {
+            changes.append("tokens: \(oldTokens.showList()) -> \(newTokens.showList())")
+        }
+        return changes.joined(separator: ", ")
+    }
+}
+
+extension Array where Element == TokenSpecs {
+    /// Network tokens other than the main one, if any
+    func show() -> String {
+        isEmpty ? "" : " " + showList()
+    }
+
+    func showList() -> String {
+        "[" + map { "\($0.unit) (\($0.decimals))" }.joined(separator: ", ") + "]"
+    }
+}
@@ -23,14 +23,37 @@ protocol BackendNavigationPerforming: AnyObject {
 /// This adapters acts as a wrapper for public function for navigation
 final class BackendNavigationAdapter: BackendNavigationPerforming {
     func performBackend(action: Action, details: String, seedPhrase: String) -> ActionResult? {
-        guard let result = try? backendAction(
-            action: action,
-            details: details,
-            seedPhrase: seedPhrase
-        ) else { return nil }
+        guard let session = NavigationSession.handle,
+              let result = try? backendSessionAction(
+                  session: session,
+                  action: action,
+                  details: details,
+                  seedPhrase: seedPhrase
+              ) else { return nil }
         if case let .sufficientCryptoReady(value) = result.modalData {
             print(value)
         }
         return result
     }
 }
+
+/// Navigation session of the app in Rust backend, there is no global navigation state there
+enum NavigationSession {
+    /// Handle of the current session, `nil` if not started yet
+    private(set) static var handle: UInt32?
+
+    /// Starts new navigation session, closing the previous one
+    static func start(dbName: String, seedNames: [String]) throws {
+        if let handle = handle {
+            try? closeNavigationSession(session: handle)
+            self.handle = nil
+        }
+        handle = try initNavigationSession(dbname: dbName, seedNames: seedNames)
+    }
+
+    /// Synchronizes seed names of the current session
+    static func updateSeedNames(_ seedNames: [String]) {
+        guard let handle = handle else { return }
+        try? updateSessionSeedNames(session: handle, seedNames: seedNames)
+    }
+}
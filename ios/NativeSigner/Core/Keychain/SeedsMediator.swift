@@ -96,7 +96,7 @@ final class SeedsMediator: SeedsMediating {
             if let authenticated = payload.authenticated {
                 signerDataModel.authenticated = authenticated
             }
-            updateSeedNames(seedNames: seedNames)
+            NavigationSession.updateSeedNames(seedNames)
         case .failure:
             signerDataModel.authenticated = false
         }
@@ -114,7 +114,7 @@ final class SeedsMediator: SeedsMediating {
         case .success:
             seedNames.append(seedName)
             seedNames.sort()
-            updateSeedNames(seedNames: seedNames)
+            NavigationSession.updateSeedNames(seedNames)
             signerDataModel.navigation.perform(navigation: .init(
                 action: .goForward,
                 details: createRoots ? Constants.true : Constants.false,
@@ -188,7 +188,7 @@ final class SeedsMediator: SeedsMediating {
             seedNames = seedNames
                 .filter { $0 != seedName }
                 .sorted()
-            updateSeedNames(seedNames: seedNames)
+            NavigationSession.updateSeedNames(seedNames)
             signerDataModel.navigation.perform(navigation: .init(action: .removeSeed), skipDebounce: true)
         case .failure: ()
             // We should inform user with some dedicated UI state for that error, maybe just system alert
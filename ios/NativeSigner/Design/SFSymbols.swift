@@ -13,6 +13,7 @@ import SwiftUI
 enum SFSymbols: String {
     case airplane
     case aqi
+    case arrow
     case clear
     case circle
     case checkmark
@@ -25,14 +26,17 @@ enum SFSymbols: String {
     case iphoneArrow = "iphone.and.arrow"
     case lock
     case minus
+    case person
     case plus
     case rectangle
     case signature
     case square
     case shield
+    case tag
     case trash
     case viewfinder
     case wifi
+    case wrench
     case xmark
 }
 
@@ -40,7 +44,9 @@ enum SFSymbols: String {
 ///
 /// Refer to https://developer.apple.com/sf-symbols/ for currently available combinations
 enum SFSymbolVariant: String {
+    case badge
     case circle
+    case crop
     case down
     case exclamationmark
     case fill
@@ -49,7 +55,9 @@ enum SFSymbolVariant: String {
     case grid
     case medium
     case magnifyingglass
+    case minus
     case oneByTwo = "1x2"
+    case plus
     case portrait
     case rectangle
     case shield
@@ -58,6 +66,7 @@ enum SFSymbolVariant: String {
     case text
     case triangle
     case trianglebadge
+    case up
     case viewfinder
 }
 
@@ -10,7 +10,11 @@ import Foundation
 /// Maybe this could show errors?
 extension ErrorDisplayed {
     func show() {
-        guard case let .Str(payload) = self else { return }
-        print(payload)
+        switch self {
+        case let .Str(payload):
+            print(payload)
+        case let .Typed(code, _, message):
+            print("\(code): \(message)")
+        }
     }
 }
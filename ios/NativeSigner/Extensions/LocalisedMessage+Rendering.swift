@@ -0,0 +1,39 @@
+//
+//  LocalisedMessage+Rendering.swift
+//  NativeSigner
+//
+
+import Foundation
+
+/// Message templates by identifier, fetched from Rust backend once
+private let messageCatalog: [String: String] = Dictionary(
+    englishMessageCatalog().map { ($0.id, $0.template) },
+    uniquingKeysWith: { first, _ in first }
+)
+
+extension LocalisedMessage {
+    /// Renders message from Rust backend with English catalog;
+    /// message with unknown identifier is shown as the identifier itself
+    var rendered: String {
+        guard let template = messageCatalog[id] else { return id }
+        return args.reduce(template) { text, arg in
+            text.replacingOccurrences(of: "{\(arg.name)}", with: arg.value.rendered)
+        }
+    }
+}
+
+private extension MessageArgValue {
+    var rendered: String {
+        switch self {
+        case let .text(value):
+            return value
+        case let .number(value):
+            return String(value)
+        case let .hex(value):
+            return value.map { String(format: "%02x", $0) }.joined()
+        case let .list(items):
+            guard !items.isEmpty else { return LocalisedMessage(id: "list.none", args: []).rendered }
+            return items.joined(separator: ", ")
+        }
+    }
+}
@@ -93,7 +93,7 @@ final class SignerDataModel: ObservableObject {
             // if self.canaryDead {
             // device_was_online(nil, self.dbName)
             // }
-            initNavigation(dbname: dbName, seedNames: seedsMediator.seedNames)
+            try NavigationSession.start(dbName: dbName, seedNames: seedsMediator.seedNames)
             totalRefresh()
             seedsMediator.refreshSeeds()
         } catch {
@@ -120,7 +120,7 @@ private extension SignerDataModel {
     func finaliseInitialisation() {
         guard onboardingDone else { return }
         seedsMediator.refreshSeeds()
-        initNavigation(dbname: dbName, seedNames: seedsMediator.seedNames)
+        try? NavigationSession.start(dbName: dbName, seedNames: seedsMediator.seedNames)
         totalRefresh()
     }
 }
@@ -137,7 +137,7 @@ extension SignerDataModel {
         databaseMediator.wipeDatabase()
         onboardingDone = false
         seedsMediator.seedNames = []
-        initNavigation(dbname: dbName, seedNames: seedsMediator.seedNames)
+        try? NavigationSession.start(dbName: dbName, seedNames: seedsMediator.seedNames)
     }
 }
 
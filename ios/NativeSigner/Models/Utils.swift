@@ -54,7 +54,7 @@ extension AttributedString {
 
 extension TransactionCardSet {
     func assemble() -> [TransactionCard] {
-        [author, error, extensions, importingDerivations, message, meta, method, newSpecs, verifier, warning, typesInfo]
+        [author, contacts, error, extensions, importingDerivations, message, meta, method, newSpecs, newVerifier, oldSpecs, verifier, warning, typesInfo]
             .compactMap { $0 }
             .flatMap { $0 }
             .sorted { $0.index < $1.index }
@@ -13,6 +13,14 @@ struct HistoryScreen: View {
     let navigationRequest: NavigationRequest
     var body: some View {
         ScrollView {
+            HStack {
+                Button(
+                    action: { navigationRequest(.init(action: .searchLog, details: "")) },
+                    label: { SmallButton(text: Localizable.HistoryScreen.searchLog.key) }
+                )
+                Spacer()
+            }
+            .padding(.horizontal, 8)
             LazyVStack(spacing: 8) {
                 ForEach(content.log, id: \.timestamp) { history in
                     ForEach(history.events, id: \.self) { event in
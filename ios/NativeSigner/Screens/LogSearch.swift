@@ -0,0 +1,94 @@
+//
+//  LogSearch.swift
+//  NativeSigner
+//
+
+import SwiftUI
+
+struct LogSearch: View {
+    let content: MLogSearch
+    let navigationRequest: NavigationRequest
+    @State private var query: String = ""
+    @FocusState private var queryFocused: Bool
+    var body: some View {
+        VStack(alignment: .leading) {
+            Localizable.LogSearch.header.text
+                .font(Fontstyle.overline.base)
+                .foregroundColor(Asset.text500.swiftUIColor)
+            ZStack {
+                RoundedRectangle(cornerRadius: 8)
+                    .stroke(Asset.border400.swiftUIColor)
+                    .frame(height: 39)
+                TextField(Localizable.LogSearch.header.string, text: $query, prompt: Localizable.LogSearch.prompt.text)
+                    .focused($queryFocused)
+                    .foregroundColor(Asset.text600.swiftUIColor)
+                    .font(Fontstyle.body2.base)
+                    .disableAutocorrection(true)
+                    .autocapitalization(.none)
+                    .keyboardType(.asciiCapable)
+                    .submitLabel(.search)
+                    .onSubmit {
+                        queryFocused = false
+                        navigationRequest(.init(action: .searchLog, details: query))
+                    }
+                    .onAppear(perform: {
+                        query = content.query
+                    })
+                    .padding(.horizontal, 8)
+            }
+            Localizable.LogSearch.hint.text
+                .font(Fontstyle.captionM.base)
+                .foregroundColor(Asset.text400.swiftUIColor)
+            BigButton(
+                text: Localizable.LogSearch.search.key,
+                action: {
+                    queryFocused = false
+                    navigationRequest(.init(action: .searchLog, details: query))
+                }
+            )
+            HStack {
+                Button(
+                    action: { navigationRequest(.init(action: .previousUnit)) },
+                    label: { SmallButton(text: Localizable.LogSearch.previous.key) }
+                ).disabled(content.page == 0)
+                Spacer()
+                if content.totalPages == 0 {
+                    Localizable.LogSearch.nothingFound.text
+                } else {
+                    Text(Localizable.LogSearch.page(String(content.page + 1), String(content.totalPages)))
+                }
+                Spacer()
+                Button(
+                    action: { navigationRequest(.init(action: .nextUnit)) },
+                    label: { SmallButton(text: Localizable.next.key) }
+                ).disabled(content.page + 1 >= content.totalPages)
+            }
+            .padding(.vertical, 8)
+            ScrollView {
+                LazyVStack(spacing: 8) {
+                    ForEach(content.log, id: \.order) { history in
+                        ForEach(history.events, id: \.self) { event in
+                            Button(
+                                action: {
+                                    navigationRequest(.init(action: .showLogDetails, details: String(history.order)))
+                                },
+                                label: {
+                                    HistoryCard(
+                                        event: event,
+                                        timestamp: history.timestamp.padding(
+                                            toLength: 16,
+                                            withPad: " ",
+                                            startingAt: 0
+                                        )
+                                    )
+                                    .foregroundColor(Asset.text400.swiftUIColor)
+                                }
+                            )
+                        }
+                    }
+                }
+            }
+        }
+        .padding(.horizontal, 8)
+    }
+}
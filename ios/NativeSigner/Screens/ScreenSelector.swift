@@ -50,6 +50,11 @@ struct ScreenSelector: View {
             )
         case let .logDetails(value):
             EventDetails(content: value)
+        case let .logSearch(value):
+            LogSearch(
+                content: value,
+                navigationRequest: navigationRequest
+            )
         case let .transaction(value):
             TransactionPreview(
                 content: value,
@@ -16,11 +16,13 @@
 //! sign updates for a given network
 //! - [`METATREE`], with network metadata
 //! - [`ADDRTREE`], with user addresses public information
-//! - [`SETTREE`], containing general verifier, types information, and Signer
-//! danger status
+//! - [`SETTREE`], containing general verifier, types information, Signer
+//! danger status, security policy and database schema version
 //! - [`TRANSACTION`], used to store temporarily transaction data while the
 //! user accepts or declines it
 //! - [`HISTORY`], with history log
+//! - [`CONTACTS`], with named recipient addresses known to the user
+//! - [`KEY_ANNOTATIONS`], with user labels, tags and notes for addresses
 //!
 //! Hot database contains following trees:
 //!
@@ -69,6 +71,11 @@ pub mod test_values;
 #[cfg(feature = "active")]
 pub const COLD_DB_NAME_RELEASE: &str = "../database/database_cold_release";
 
+/// Default path for the manifest of the cold database generated during the
+/// Signer build
+#[cfg(feature = "active")]
+pub const COLD_RELEASE_MANIFEST: &str = "../database/database_cold_release_manifest";
+
 /// Tree name for the tree storing the network specs in cold database
 pub const SPECSTREE: &[u8] = b"chainspecs";
 
@@ -87,7 +94,8 @@ pub const ADDRTREE: &[u8] = b"addresses";
 /// Tree name for the tree storing database settings
 ///
 /// In cold database, the settings tree contains general verifier, types
-/// information, Signer danger status.
+/// information, Signer danger status, Signer security policy and database
+/// schema version.
 ///
 /// In hot database, the settings tree contains types information.
 pub const SETTREE: &[u8] = b"settings";
@@ -98,6 +106,14 @@ pub const TRANSACTION: &[u8] = b"transaction";
 /// Tree name for the tree storing Signer history
 pub const HISTORY: &[u8] = b"history";
 
+/// Tree name for the tree storing named contacts, i.e. recipient addresses
+/// known to the user, in cold database
+pub const CONTACTS: &[u8] = b"contacts";
+
+/// Tree name for the tree storing user labels, tags and notes for addresses in
+/// cold database
+pub const KEY_ANNOTATIONS: &[u8] = b"key_annotations";
+
 /// Key in settings tree [`SETTREE`] for encoded types information
 pub const TYPES: &[u8] = b"types";
 
@@ -107,6 +123,21 @@ pub const GENERALVERIFIER: &[u8] = b"general_verifier";
 /// Key in settings tree [`SETTREE`] for Signer danger status
 pub const DANGER: &[u8] = b"dangerous_encounter";
 
+/// Key in settings tree [`SETTREE`] for Signer security policy
+pub const POLICY: &[u8] = b"security_policy";
+
+/// Key prefix in settings tree [`SETTREE`] for index of the last custom
+/// verifier rotation accepted for the network, followed by the network
+/// genesis hash
+pub const VERIFIER_ROTATION: &[u8] = b"verifier_rotation";
+
+/// Key in settings tree [`SETTREE`] for cold database schema version
+pub const SCHEMA_VERSION: &[u8] = b"schema_version";
+
+/// Key in settings tree [`SETTREE`] for history log hash chain head, order and
+/// hash of the latest history log entry
+pub const HISTORY_HEAD: &[u8] = b"history_head";
+
 /// Key in transactions tree [`TRANSACTION`] for updates data
 #[cfg(feature = "signer")]
 pub const STUB: &[u8] = b"stub";
@@ -128,6 +159,13 @@ pub const MAX_WORDS_DISPLAY: usize = 8;
 #[cfg(feature = "signer")]
 pub const HISTORY_PAGE_SIZE: usize = 20;
 
+/// Maximum size of update content after decompression, in bytes
+///
+/// Compressed updates announcing or producing on decompression larger content
+/// are rejected, so that a short payload could not be unpacked into unlimited
+/// amount of data.
+pub const MAX_DECOMPRESSED_SIZE: usize = 8 * 1024 * 1024;
+
 /// Default folder for hot database
 #[cfg(feature = "active")]
 pub const HOT_DB_NAME: &str = "../database/database_hot";
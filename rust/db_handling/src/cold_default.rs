@@ -8,7 +8,8 @@
 //! - `HISTORY` with Signer history log
 //! - `METATREE` with network metadata
 //! - `SETTREE` with settings: types information, Signer dangerous exposures
-//! record and Signer database general verifier
+//! record, Signer database general verifier, security policy and database
+//! schema version
 //! - `SPECSTREE` with network specs
 //! - `TRANSACTION` for temporary storage of the transaction data
 //! - `VERIFIERS` with network verifiers data
@@ -23,6 +24,10 @@
 //! - General verifier is set and this event is recorded in the history log. By
 //! default, Signer sets up Parity-associated key as a general verifier. This
 //! could be later on changed by the user.
+//! - Security policy is set, if the one other than default (nothing is
+//! forbidden) is requested, and this event is recorded in the history log.
+//! Security policy could be later on changed by an update signed by the general
+//! verifier.
 //!
 //! Signer then reads and updates the database as it operates.
 //!
@@ -40,14 +45,12 @@
 //! verified what information.
 #[cfg(any(feature = "active", feature = "signer"))]
 use parity_scale_codec::Encode;
-#[cfg(any(feature = "active", feature = "signer"))]
-use sled::Batch;
 use std::path::Path;
 
 #[cfg(feature = "active")]
-use constants::{DANGER, TYPES};
+use constants::{DANGER, SCHEMA_VERSION, TYPES};
 #[cfg(any(feature = "active", feature = "signer"))]
-use constants::{GENERALVERIFIER, HISTORY};
+use constants::{GENERALVERIFIER, HISTORY, POLICY};
 
 #[cfg(feature = "active")]
 use definitions::{
@@ -55,7 +58,7 @@ use definitions::{
     keyring::{MetaKey, NetworkSpecsKey},
 };
 #[cfg(any(feature = "active", feature = "signer"))]
-use definitions::{history::Event, network_specs::Verifier};
+use definitions::{history::Event, network_specs::Verifier, policy::SecurityPolicy};
 
 #[cfg(feature = "signer")]
 use defaults::default_general_verifier;
@@ -65,10 +68,15 @@ use defaults::{default_chainspecs, default_types_content, default_verifiers, rel
 use defaults::{nav_test_metadata, test_metadata};
 
 #[cfg(feature = "test")]
-use crate::identities::generate_test_identities;
+use crate::identities::generate_test_identities_in;
+#[cfg(feature = "active")]
+use crate::migrations::LIVE_SCHEMA_VERSION;
 #[cfg(any(feature = "active", feature = "signer"))]
 use crate::{
-    db_transactions::TrDbCold, helpers::make_batch_clear_tree, manage_history::events_in_batch,
+    db_transactions::TrDbCold,
+    helpers::make_batch_clear_tree_in,
+    manage_history::events_in_batch_in,
+    storage::{Batch, SledStorage, Storage},
 };
 
 use crate::Result;
@@ -127,7 +135,7 @@ fn default_cold_network_specs() -> Batch {
     let mut batch = Batch::default();
     for x in default_chainspecs().iter() {
         let network_specs_key = NetworkSpecsKey::from_parts(&x.genesis_hash, &x.encryption);
-        batch.insert(network_specs_key.key(), x.encode());
+        batch.insert(network_specs_key.key(), x.store());
     }
     batch
 }
@@ -136,8 +144,8 @@ fn default_cold_network_specs() -> Batch {
 /// database.
 ///
 /// Adds default entries: types information
-/// [`ContentLoadTypes`](definitions::qr_transfers::ContentLoadTypes) and danger
-/// record [`DangerRecord`].
+/// [`ContentLoadTypes`](definitions::qr_transfers::ContentLoadTypes), danger
+/// record [`DangerRecord`] and current database schema version.
 ///
 /// Note that the general verifier is **not** set up here.
 ///
@@ -150,6 +158,7 @@ fn default_cold_settings_init_later() -> Result<Batch> {
     let types_prep = default_types_content()?;
     batch.insert(TYPES, types_prep.store());
     batch.insert(DANGER, DangerRecord::safe().store());
+    batch.insert(SCHEMA_VERSION, LIVE_SCHEMA_VERSION.encode());
     Ok(batch)
 }
 
@@ -186,14 +195,23 @@ where
     P: AsRef<Path>,
 {
     if std::fs::remove_dir_all(&db_path).is_ok() {}
+    cold_database_no_init_in(&SledStorage::open(db_path)?, purpose)
+}
+
+/// Same as [`cold_database_no_init`], for the database in given [`Storage`].
+///
+/// Storage is expected to be empty.
+#[cfg(any(feature = "active", feature = "test"))]
+fn cold_database_no_init_in<S>(storage: &S, purpose: Purpose) -> Result<()>
+where
+    S: Storage,
+{
     TrDbCold::new()
         .set_metadata(default_cold_metadata(purpose)?) // set default metadata
         .set_network_specs(default_cold_network_specs()) // set default network specs
         .set_settings(default_cold_settings_init_later()?) // set default types and danger status, no general verifier yet
         .set_verifiers(default_cold_verifiers()) // set default verifiers
-        .apply(&db_path)?;
-
-    Ok(())
+        .apply_in(storage)
 }
 
 /// Initiate cold database and set up the database general verifier to given
@@ -211,26 +229,69 @@ where
 pub fn init_db<P>(db_path: P, general_verifier: Verifier) -> Result<()>
 where
     P: AsRef<Path>,
+{
+    init_db_with_policy(db_path, general_verifier, SecurityPolicy::default())
+}
+
+/// Same as [`init_db`], for the database in given [`Storage`].
+pub fn init_db_in<S>(storage: &S, general_verifier: Verifier) -> Result<()>
+where
+    S: Storage,
+{
+    init_db_with_policy_in(storage, general_verifier, SecurityPolicy::default())
+}
+
+/// Initiate cold database with given general verifier [`Verifier`] and
+/// [`SecurityPolicy`].
+///
+/// Same as [`init_db`], with security policy additionally stored in `SETTREE`
+/// under key `POLICY`. Non-default policy is recorded in the history log as
+/// `Event::SecurityPolicySet`.
+pub fn init_db_with_policy<P>(
+    db_path: P,
+    general_verifier: Verifier,
+    security_policy: SecurityPolicy,
+) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    init_db_with_policy_in(
+        &SledStorage::open(db_path)?,
+        general_verifier,
+        security_policy,
+    )
+}
+
+/// Same as [`init_db_with_policy`], for the database in given [`Storage`].
+pub fn init_db_with_policy_in<S>(
+    storage: &S,
+    general_verifier: Verifier,
+    security_policy: SecurityPolicy,
+) -> Result<()>
+where
+    S: Storage,
 {
     let mut settings_batch = Batch::default();
     settings_batch.insert(GENERALVERIFIER, general_verifier.encode());
+    settings_batch.insert(POLICY, security_policy.encode());
 
-    let clear_history_batch = make_batch_clear_tree(&db_path, HISTORY)?;
-    let events = vec![
+    let clear_history_batch = make_batch_clear_tree_in(storage, HISTORY)?;
+    let mut events = vec![
         Event::DatabaseInitiated,
         Event::GeneralVerifierSet {
             verifier: general_verifier,
         },
     ];
+    if security_policy != SecurityPolicy::default() {
+        events.push(Event::SecurityPolicySet { security_policy });
+    }
     let start_zero = true;
-    let history_batch = events_in_batch(&db_path, start_zero, clear_history_batch, events)?;
+    let history_batch = events_in_batch_in(storage, start_zero, clear_history_batch, events)?;
 
     TrDbCold::new()
         .set_history(history_batch) // set *start* history
         .set_settings(settings_batch) // set general_verifier
-        .apply(&db_path)?;
-
-    Ok(())
+        .apply_in(storage)
 }
 
 /// Initiate Signer database with default general verifier (Parity-associated
@@ -246,6 +307,37 @@ where
     init_db(db_path, default_general_verifier())
 }
 
+/// Same as [`signer_init_with_cert`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn signer_init_with_cert_in<S>(storage: &S) -> Result<()>
+where
+    S: Storage,
+{
+    init_db_in(storage, default_general_verifier())
+}
+
+/// Initiate Signer database with default general verifier (Parity-associated
+/// key) and given [`SecurityPolicy`].
+///
+/// Function is applied during the initial start of the Signer, if the policy
+/// is provisioned by the organisation deploying the device.
+#[cfg(feature = "signer")]
+pub fn signer_init_with_policy<P>(db_path: P, security_policy: SecurityPolicy) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    init_db_with_policy(db_path, default_general_verifier(), security_policy)
+}
+
+/// Same as [`signer_init_with_policy`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn signer_init_with_policy_in<S>(storage: &S, security_policy: SecurityPolicy) -> Result<()>
+where
+    S: Storage,
+{
+    init_db_with_policy_in(storage, default_general_verifier(), security_policy)
+}
+
 /// Initiate Signer database with general verifier set up to `Verifier(None)`.
 ///
 /// Function is applied during `Remove general certificate` procedure.
@@ -257,6 +349,15 @@ where
     init_db(db_path, Verifier { v: None })
 }
 
+/// Same as [`signer_init_no_cert`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn signer_init_no_cert_in<S>(storage: &S) -> Result<()>
+where
+    S: Storage,
+{
+    init_db_in(storage, Verifier { v: None })
+}
+
 /// Generate initiated test cold database with no network-associated data.
 ///
 /// Function wipes everything in the database directory and loads into database
@@ -268,10 +369,22 @@ where
     P: AsRef<Path>,
 {
     if std::fs::remove_dir_all(&db_path).is_ok() {}
+    populate_cold_no_networks_in(&SledStorage::open(db_path)?, general_verifier)
+}
+
+/// Same as [`populate_cold_no_networks`], for the database in given
+/// [`Storage`].
+///
+/// Storage is expected to be empty.
+#[cfg(feature = "test")]
+pub fn populate_cold_no_networks_in<S>(storage: &S, general_verifier: Verifier) -> Result<()>
+where
+    S: Storage,
+{
     TrDbCold::new()
         .set_settings(default_cold_settings_init_later()?) // set general verifier and load default types
-        .apply(&db_path)?;
-    init_db(&db_path, general_verifier)
+        .apply_in(storage)?;
+    init_db_in(storage, general_verifier)
 }
 
 /// Generate initiated test cold database without network metadata.
@@ -290,12 +403,24 @@ where
     P: AsRef<Path>,
 {
     if std::fs::remove_dir_all(&db_path).is_ok() {}
+    populate_cold_no_metadata_in(&SledStorage::open(db_path)?, general_verifier)
+}
+
+/// Same as [`populate_cold_no_metadata`], for the database in given
+/// [`Storage`].
+///
+/// Storage is expected to be empty.
+#[cfg(feature = "test")]
+pub fn populate_cold_no_metadata_in<S>(storage: &S, general_verifier: Verifier) -> Result<()>
+where
+    S: Storage,
+{
     TrDbCold::new()
         .set_network_specs(default_cold_network_specs()) // set default network specs
         .set_settings(default_cold_settings_init_later()?) // set general verifier and load default types
         .set_verifiers(default_cold_verifiers()) // set default verifiers
-        .apply(&db_path)?;
-    init_db(&db_path, general_verifier)
+        .apply_in(storage)?;
+    init_db_in(storage, general_verifier)
 }
 
 /// Generate initiated test cold database with default content, and create in it
@@ -305,9 +430,21 @@ pub fn populate_cold<P>(db_path: P, general_verifier: Verifier) -> Result<()>
 where
     P: AsRef<Path>,
 {
-    cold_database_no_init(&db_path, Purpose::Test)?;
-    init_db(&db_path, general_verifier)?;
-    generate_test_identities(&db_path)
+    if std::fs::remove_dir_all(&db_path).is_ok() {}
+    populate_cold_in(&SledStorage::open(db_path)?, general_verifier)
+}
+
+/// Same as [`populate_cold`], for the database in given [`Storage`].
+///
+/// Storage is expected to be empty.
+#[cfg(feature = "test")]
+pub fn populate_cold_in<S>(storage: &S, general_verifier: Verifier) -> Result<()>
+where
+    S: Storage,
+{
+    cold_database_no_init_in(storage, Purpose::Test)?;
+    init_db_in(storage, general_verifier)?;
+    generate_test_identities_in(storage)
 }
 
 /// Generate **not initiated** release cold database.
@@ -319,6 +456,17 @@ where
     cold_database_no_init(db_path, Purpose::Release)
 }
 
+/// Same as [`populate_cold_release`], for the database in given [`Storage`].
+///
+/// Storage is expected to be empty.
+#[cfg(feature = "active")]
+pub fn populate_cold_release_in<S>(storage: &S) -> Result<()>
+where
+    S: Storage,
+{
+    cold_database_no_init_in(storage, Purpose::Release)
+}
+
 /// Generate **not initiated** test cold database for `navigator` testing.
 #[cfg(feature = "test")]
 pub fn populate_cold_nav_test<P>(db_path: P) -> Result<()>
@@ -327,3 +475,14 @@ where
 {
     cold_database_no_init(db_path, Purpose::TestNavigator)
 }
+
+/// Same as [`populate_cold_nav_test`], for the database in given [`Storage`].
+///
+/// Storage is expected to be empty.
+#[cfg(feature = "test")]
+pub fn populate_cold_nav_test_in<S>(storage: &S) -> Result<()>
+where
+    S: Storage,
+{
+    cold_database_no_init_in(storage, Purpose::TestNavigator)
+}
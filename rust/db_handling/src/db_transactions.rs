@@ -1,7 +1,7 @@
 //! Atomic transactions in cold and hot databases
 //!
 //! Additions and removals of entries in cold and hot database occur through
-//! atomic [transactions](sled::transaction).
+//! atomic transactions, applied by the database [`Storage`].
 //! Each tree gets updated with its own [`Batch`], updates occur within a single
 //! transaction.
 //!
@@ -11,46 +11,57 @@ use std::path::Path;
 // TODO this is a temporary solution, the data eventually could be stored in
 // `navigator` state.
 #[cfg(feature = "signer")]
-use parity_scale_codec::{Decode, Encode};
-use sled::{transaction::TransactionResult, Batch, Transactional};
+use parity_scale_codec::Decode;
+use parity_scale_codec::Encode;
 #[cfg(feature = "signer")]
 use sp_runtime::MultiSigner;
 
 #[cfg(feature = "active")]
 use constants::{ADDRESS_BOOK, META_HISTORY, SPECSTREEPREP};
-use constants::{ADDRTREE, HISTORY, METATREE, SETTREE, SPECSTREE, TRANSACTION, VERIFIERS};
+use constants::{
+    ADDRTREE, CONTACTS, HISTORY, HISTORY_HEAD, KEY_ANNOTATIONS, METATREE, SETTREE, SPECSTREE,
+    TRANSACTION, VERIFIERS,
+};
 #[cfg(feature = "signer")]
-use constants::{DRV, GENERALVERIFIER, SIGN, STUB, TYPES};
+use constants::{DRV, GENERALVERIFIER, POLICY, SIGN, STUB, TYPES};
 
 #[cfg(feature = "signer")]
 use definitions::{
     helpers::multisigner_to_public,
     history::{
-        Event, IdentityHistory, MetaValuesDisplay, NetworkSpecsDisplay, NetworkVerifierDisplay,
-        SignDisplay, SignMessageDisplay, TypesDisplay,
+        ContactDisplay, Event, EventProof, IdentityHistory, MetaValuesDisplay,
+        NetworkSpecsAmendmentTokensDisplay, NetworkSpecsDisplay, NetworkSpecsTokensDisplay,
+        NetworkVerifierDisplay, NetworkVerifierRotationDisplay, SignDisplay, SignMessageDisplay,
+        TypesDisplay,
     },
     keyring::{AddressKey, MetaKey, NetworkSpecsKey, VerifierKey},
     metadata::MetaValues,
+    metadata_proof::MetadataProof,
     network_specs::{
         CurrentVerifier, NetworkSpecs, NetworkSpecsToSend, ValidCurrentVerifier, Verifier,
         VerifierValue,
     },
+    policy::SecurityPolicy,
     qr_transfers::ContentLoadTypes,
-    users::AddressDetails,
+    users::{AddressDetails, ContactDetails, ContactEntry},
 };
 
-use crate::helpers::{open_db, open_tree};
+use crate::manage_history::history_head_after_in;
+use crate::storage::{Batch, SledStorage, Storage};
 #[cfg(feature = "signer")]
 use crate::Error;
 use crate::Result;
 #[cfg(feature = "signer")]
 use crate::{
-    helpers::{make_batch_clear_tree, verify_checksum},
-    manage_history::events_to_batch,
+    helpers::{
+        get_address_details_in, make_batch_clear_tree_in, verifier_rotation_key, verify_checksum,
+    },
+    manage_history::{events_to_batch_in, events_with_proofs_to_batch_in, timestamp_now},
+    storage::sled_entry,
 };
 
 /// Cold database transaction data containing [`Batch`] elements that will be
-/// applied to each [`Tree`](sled::Tree).
+/// applied to each database tree.
 ///
 /// Cold database tree names and content information could be found in
 /// [`constants`] crate. All trees are routinely updated as Signer is used.
@@ -67,9 +78,15 @@ pub struct TrDbCold {
     /// `Batch` to be applied to [`ADDRTREE`] tree
     for_addresses: Batch,
 
+    /// `Batch` to be applied to [`CONTACTS`] tree
+    for_contacts: Batch,
+
     /// `Batch` to be applied to [`HISTORY`] tree
     for_history: Batch,
 
+    /// `Batch` to be applied to [`KEY_ANNOTATIONS`] tree
+    for_key_annotations: Batch,
+
     /// `Batch` to be applied to [`METATREE`] tree
     for_metadata: Batch,
 
@@ -91,7 +108,9 @@ impl TrDbCold {
     pub fn new() -> Self {
         Self {
             for_addresses: Batch::default(),
+            for_contacts: Batch::default(),
             for_history: Batch::default(),
+            for_key_annotations: Batch::default(),
             for_metadata: Batch::default(),
             for_network_specs: Batch::default(),
             for_settings: Batch::default(),
@@ -107,6 +126,13 @@ impl TrDbCold {
         self
     }
 
+    /// Set `for_contacts` field in [`TrDbCold`] with `Batch` that will be
+    /// applied to [`CONTACTS`] tree.
+    pub fn set_contacts(mut self, for_contacts: Batch) -> Self {
+        self.for_contacts = for_contacts;
+        self
+    }
+
     /// Set `for_history` field in [`TrDbCold`] with `Batch` that will be
     /// applied to [`HISTORY`] tree.
     pub fn set_history(mut self, for_history: Batch) -> Self {
@@ -114,6 +140,13 @@ impl TrDbCold {
         self
     }
 
+    /// Set `for_key_annotations` field in [`TrDbCold`] with `Batch` that will
+    /// be applied to [`KEY_ANNOTATIONS`] tree.
+    pub fn set_key_annotations(mut self, for_key_annotations: Batch) -> Self {
+        self.for_key_annotations = for_key_annotations;
+        self
+    }
+
     /// Set `for_metadata` field in [`TrDbCold`] with `Batch` that will be
     /// applied to [`METATREE`] tree.
     pub fn set_metadata(mut self, for_metadata: Batch) -> Self {
@@ -157,52 +190,35 @@ impl TrDbCold {
     where
         P: AsRef<Path>,
     {
-        let database = open_db(&db_path)?;
-        let addresses = open_tree(&database, ADDRTREE)?;
-        let history = open_tree(&database, HISTORY)?;
-        let metadata = open_tree(&database, METATREE)?;
-        let network_specs = open_tree(&database, SPECSTREE)?;
-        let settings = open_tree(&database, SETTREE)?;
-        let transaction = open_tree(&database, TRANSACTION)?;
-        let verifiers = open_tree(&database, VERIFIERS)?;
-        let s = (
-            &addresses,
-            &history,
-            &metadata,
-            &network_specs,
-            &settings,
-            &transaction,
-            &verifiers,
-        );
-        let res: TransactionResult<(), sled::Error> = s.transaction(
-            |(
-                tx_addresses,
-                tx_history,
-                tx_metadata,
-                tx_network_specs,
-                tx_settings,
-                tx_transaction,
-                tx_verifiers,
-            )| {
-                tx_addresses.apply_batch(&self.for_addresses)?;
-                tx_addresses.flush();
-                tx_history.apply_batch(&self.for_history)?;
-                tx_history.flush();
-                tx_metadata.apply_batch(&self.for_metadata)?;
-                tx_metadata.flush();
-                tx_network_specs.apply_batch(&self.for_network_specs)?;
-                tx_network_specs.flush();
-                tx_settings.apply_batch(&self.for_settings)?;
-                tx_settings.flush();
-                tx_transaction.apply_batch(&self.for_transaction)?;
-                tx_transaction.flush();
-                tx_verifiers.apply_batch(&self.for_verifiers)?;
-                tx_verifiers.flush();
-                Ok(())
-            },
-        );
+        self.apply_in(&SledStorage::open(db_path)?)
+    }
 
-        Ok(res?)
+    /// Same as [`apply`](Self::apply), for the database in given [`Storage`].
+    ///
+    /// History log head stored in [`SETTREE`] is updated in the same
+    /// transaction, if the history log is changed.
+    pub fn apply_in<S>(&self, storage: &S) -> Result<()>
+    where
+        S: Storage,
+    {
+        let mut for_settings = self.for_settings.clone();
+        if !self.for_history.is_empty() {
+            match history_head_after_in(storage, &self.for_history)? {
+                Some(head) => for_settings.insert(HISTORY_HEAD, head.encode()),
+                None => for_settings.remove(HISTORY_HEAD),
+            }
+        }
+        storage.apply(&[
+            (ADDRTREE, &self.for_addresses),
+            (CONTACTS, &self.for_contacts),
+            (HISTORY, &self.for_history),
+            (KEY_ANNOTATIONS, &self.for_key_annotations),
+            (METATREE, &self.for_metadata),
+            (SPECSTREE, &self.for_network_specs),
+            (SETTREE, &for_settings),
+            (TRANSACTION, &self.for_transaction),
+            (VERIFIERS, &self.for_verifiers),
+        ])
     }
 }
 
@@ -214,7 +230,7 @@ impl Default for TrDbCold {
 }
 
 /// Hot database transaction data containing [`Batch`] elements that will be
-/// applied to each [`Tree`](sled::Tree).
+/// applied to each database tree.
 ///
 /// Hot database tree names and content information could be found in
 /// [`constants`] crate.
@@ -301,43 +317,21 @@ impl TrDbHot {
     where
         P: AsRef<Path>,
     {
-        let database = open_db(&db_path)?;
-        let address_book = open_tree(&database, ADDRESS_BOOK)?;
-        let metadata = open_tree(&database, METATREE)?;
-        let meta_history = open_tree(&database, META_HISTORY)?;
-        let network_specs_prep = open_tree(&database, SPECSTREEPREP)?;
-        let settings = open_tree(&database, SETTREE)?;
-        let s = (
-            &address_book,
-            &metadata,
-            &meta_history,
-            &network_specs_prep,
-            &settings,
-        );
-
-        let res: TransactionResult<(), sled::Error> = s.transaction(
-            |(
-                tx_address_book,
-                tx_metadata,
-                tx_meta_history,
-                tx_network_specs_prep,
-                tx_settings,
-            )| {
-                tx_address_book.apply_batch(&self.for_address_book)?;
-                tx_address_book.flush();
-                tx_metadata.apply_batch(&self.for_metadata)?;
-                tx_metadata.flush();
-                tx_meta_history.apply_batch(&self.for_meta_history)?;
-                tx_meta_history.flush();
-                tx_network_specs_prep.apply_batch(&self.for_network_specs_prep)?;
-                tx_network_specs_prep.flush();
-                tx_settings.apply_batch(&self.for_settings)?;
-                tx_settings.flush();
-                Ok(())
-            },
-        );
+        self.apply_in(&SledStorage::open(db_path)?)
+    }
 
-        Ok(res?)
+    /// Same as [`apply`](Self::apply), for the database in given [`Storage`].
+    pub fn apply_in<S>(&self, storage: &S) -> Result<()>
+    where
+        S: Storage,
+    {
+        storage.apply(&[
+            (ADDRESS_BOOK, &self.for_address_book),
+            (METATREE, &self.for_metadata),
+            (META_HISTORY, &self.for_meta_history),
+            (SPECSTREEPREP, &self.for_network_specs_prep),
+            (SETTREE, &self.for_settings),
+        ])
     }
 }
 
@@ -412,7 +406,7 @@ impl BatchStub {
 }
 
 /// Draft for cold database atomic transaction, constructed for Signer update
-/// transaction (`add_specs`, `load_metadata`, `load_types`).
+/// transaction (`add_specs`, `load_metadata`, `load_types`, `add_contacts`).
 ///
 /// [`TrDbColdStub`] is stored SCALE-encoded in [`TRANSACTION`] tree
 /// of the cold database under key [`STUB`] while the update is considered by
@@ -426,6 +420,7 @@ impl BatchStub {
 /// database trees:
 ///
 /// - [`ADDRTREE`]
+/// - [`CONTACTS`]
 /// - [`METATREE`]
 /// - [`SPECSTREE`]
 /// - [`SETTREE`]
@@ -440,6 +435,9 @@ pub struct TrDbColdStub {
     /// `BatchStub` to be transformed into `Batch` for [`ADDRTREE`] tree.
     addresses_stub: BatchStub,
 
+    /// `BatchStub` to be transformed into `Batch` for [`CONTACTS`] tree.
+    contacts_stub: BatchStub,
+
     /// `Vec<Event>` to be entered into [`HISTORY`] tree, the
     /// [`Entry`](definitions::history::Entry) with a timestamp is generated
     /// only when the payload is approved by the user.
@@ -464,6 +462,7 @@ impl TrDbColdStub {
     pub fn new() -> Self {
         Self {
             addresses_stub: BatchStub::empty(),
+            contacts_stub: BatchStub::empty(),
             history_stub: Vec::new(),
             metadata_stub: BatchStub::empty(),
             network_specs_stub: BatchStub::empty(),
@@ -484,15 +483,20 @@ impl TrDbColdStub {
     where
         P: AsRef<Path>,
     {
-        let stub_encoded = {
-            let database = open_db(&db_path)?;
-            verify_checksum(&database, checksum)?;
-            let transaction = open_tree(&database, TRANSACTION)?;
-            transaction.get(STUB)?.ok_or(Error::Stub)?
-        };
+        Self::from_storage_in(&SledStorage::open(db_path)?, checksum)
+    }
+
+    /// Same as [`from_storage`](Self::from_storage), for the database in given
+    /// [`Storage`].
+    pub fn from_storage_in<S>(storage: &S, checksum: u32) -> Result<Self>
+    where
+        S: Storage,
+    {
+        verify_checksum(storage, checksum)?;
+        let stub_encoded = storage.get(TRANSACTION, STUB)?.ok_or(Error::Stub)?;
         TrDbCold::new()
-            .set_transaction(make_batch_clear_tree(&db_path, TRANSACTION)?) // clear transaction tree
-            .apply(&db_path)?;
+            .set_transaction(make_batch_clear_tree_in(storage, TRANSACTION)?) // clear transaction tree
+            .apply_in(storage)?;
         Ok(Self::decode(&mut &stub_encoded[..])?)
     }
 
@@ -507,13 +511,21 @@ impl TrDbColdStub {
     where
         P: AsRef<Path>,
     {
-        let mut transaction_batch = make_batch_clear_tree(&db_path, TRANSACTION)?;
+        self.store_and_get_checksum_in(&SledStorage::open(db_path)?)
+    }
+
+    /// Same as [`store_and_get_checksum`](Self::store_and_get_checksum), for
+    /// the database in given [`Storage`].
+    pub fn store_and_get_checksum_in<S>(&self, storage: &S) -> Result<u32>
+    where
+        S: Storage,
+    {
+        let mut transaction_batch = make_batch_clear_tree_in(storage, TRANSACTION)?;
         transaction_batch.insert(STUB, self.encode());
         TrDbCold::new()
             .set_transaction(transaction_batch) // clear transaction tree
-            .apply(&db_path)?;
-        let database = open_db(&db_path)?;
-        Ok(database.checksum()?)
+            .apply_in(storage)?;
+        storage.checksum()
     }
 
     /// Add new [`Event`] in `history_stub` field of the [`TrDbColdStub`]
@@ -568,7 +580,8 @@ impl TrDbColdStub {
     /// - Add a (key, value) pair to the network specs additions queue in
     /// `network_specs_stub`. Key is [`NetworkSpecsKey`] in key form, value is
     /// SCALE-encoded [`NetworkSpecs`].
-    /// - Add corresponding `Event::NetworkSpecsAdded(_)` into `history_stub`.
+    /// - Add corresponding `Event::NetworkSpecsTokensAdded(_)` into
+    /// `history_stub`.
     /// - Add root address for the network if the [`AddressDetails`] entry with
     /// matching [`Encryption`](definitions::crypto::Encryption) already exists,
     /// i.e. add (key, value) pair to the address additions queue in
@@ -589,54 +602,67 @@ impl TrDbColdStub {
     ) -> Result<Self>
     where
         P: AsRef<Path>,
+    {
+        self.add_network_specs_in(
+            network_specs_to_send,
+            valid_current_verifier,
+            general_verifier,
+            &SledStorage::open(db_path)?,
+        )
+    }
+
+    /// Same as [`add_network_specs`](Self::add_network_specs), for the
+    /// database in given [`Storage`].
+    pub fn add_network_specs_in<S>(
+        mut self,
+        network_specs_to_send: &NetworkSpecsToSend,
+        valid_current_verifier: &ValidCurrentVerifier,
+        general_verifier: &Verifier,
+        storage: &S,
+    ) -> Result<Self>
+    where
+        S: Storage,
     {
         let network_specs_key = NetworkSpecsKey::from_parts(
             &network_specs_to_send.genesis_hash,
             &network_specs_to_send.encryption,
         );
-        let order = {
-            let database = open_db(&db_path)?;
-            let chainspecs = open_tree(&database, SPECSTREE)?;
-            chainspecs.len()
-        } as u8;
+        let order = storage.len(SPECSTREE)? as u8;
         let network_specs = network_specs_to_send.to_store(order);
         self.network_specs_stub = self
             .network_specs_stub
-            .new_addition(network_specs_key.key(), network_specs.encode());
-        self.history_stub.push(Event::NetworkSpecsAdded {
-            network_specs_display: NetworkSpecsDisplay::get(
+            .new_addition(network_specs_key.key(), network_specs.store());
+        self.history_stub.push(Event::NetworkSpecsTokensAdded {
+            network_specs_tokens_display: NetworkSpecsTokensDisplay::get(
                 &network_specs,
                 valid_current_verifier,
                 general_verifier,
             ),
         });
+        for (address_key_vec, address_entry) in storage.iter(ADDRTREE)?.into_iter().map(sled_entry)
         {
-            let database = open_db(&db_path)?;
-            let identities = open_tree(&database, ADDRTREE)?;
-            for (address_key_vec, address_entry) in identities.iter().flatten() {
-                let address_key = AddressKey::from_ivec(&address_key_vec);
-                let (multisigner, mut address_details) =
-                    AddressDetails::process_entry_with_key_checked(&address_key, address_entry)?;
-                if address_details.is_root()
-                    && (address_details.encryption == network_specs.encryption)
-                    && !address_details.network_id.contains(&network_specs_key)
-                {
-                    address_details
-                        .network_id
-                        .push(network_specs_key.to_owned());
-                    self.addresses_stub = self
-                        .addresses_stub
-                        .new_addition(address_key.key(), address_details.encode());
-                    self.history_stub.push(Event::IdentityAdded {
-                        identity_history: IdentityHistory::get(
-                            &address_details.seed_name,
-                            &address_details.encryption,
-                            &multisigner_to_public(&multisigner),
-                            &address_details.path,
-                            network_specs.genesis_hash,
-                        ),
-                    });
-                }
+            let address_key = AddressKey::from_ivec(&address_key_vec);
+            let (multisigner, mut address_details) =
+                AddressDetails::process_entry_with_key_checked(&address_key, address_entry)?;
+            if address_details.is_root()
+                && (address_details.encryption == network_specs.encryption)
+                && !address_details.network_id.contains(&network_specs_key)
+            {
+                address_details
+                    .network_id
+                    .push(network_specs_key.to_owned());
+                self.addresses_stub = self
+                    .addresses_stub
+                    .new_addition(address_key.key(), address_details.store());
+                self.history_stub.push(Event::IdentityAdded {
+                    identity_history: IdentityHistory::get(
+                        &address_details.seed_name,
+                        &address_details.encryption,
+                        &multisigner_to_public(&multisigner),
+                        &address_details.path,
+                        network_specs.genesis_hash,
+                    ),
+                });
             }
         }
         Ok(self)
@@ -726,6 +752,31 @@ impl TrDbColdStub {
         self
     }
 
+    /// Prepare adding named contacts [`ContactEntry`] received as
+    /// `add_contacts` update into the cold database:
+    ///
+    /// - Add (key, value) pairs to the contacts additions queue in
+    /// `contacts_stub`. Key is contact account id and the value is
+    /// SCALE-encoded [`ContactDetails`].
+    /// - Add corresponding `Event::ContactAdded(_)` into `history_stub` for
+    /// each contact.
+    pub fn add_contacts(mut self, contacts: &[ContactEntry], verifier: &Verifier) -> Self {
+        for contact in contacts.iter() {
+            self.contacts_stub = self.contacts_stub.new_addition(
+                contact.account_id.to_vec(),
+                ContactDetails {
+                    name: contact.name.to_owned(),
+                    verifier: verifier.to_owned(),
+                }
+                .encode(),
+            );
+            self.history_stub.push(Event::ContactAdded {
+                contact_display: ContactDisplay::get(contact, verifier),
+            });
+        }
+        self
+    }
+
     /// Prepare adding new network verifier [`ValidCurrentVerifier`] into the
     /// cold database:
     ///
@@ -754,6 +805,96 @@ impl TrDbColdStub {
         self
     }
 
+    /// Prepare rotating custom network verifier in the cold database, with
+    /// network specs and metadata kept intact:
+    ///
+    /// - Add a (key, value) pair to the verifiers additions queue in
+    /// `verifiers_stub`. Key is [`VerifierKey`] and the value is SCALE-encoded
+    /// [`ValidCurrentVerifier::Custom`] with new verifier value.
+    /// - Add a (key, value) pair to the settings additions queue in
+    /// `settings_stub`. Key is
+    /// [`VERIFIER_ROTATION`](constants::VERIFIER_ROTATION) followed by the
+    /// genesis hash and the value is SCALE-encoded rotation index.
+    /// - Add corresponding `Event::NetworkVerifierRotated(_)` into
+    /// `history_stub`.
+    pub fn rotate_network_verifier(
+        mut self,
+        verifier_key: &VerifierKey,
+        old_verifier_value: &VerifierValue,
+        new_verifier_value: &VerifierValue,
+        rotation_index: u32,
+    ) -> Self {
+        self.settings_stub = self
+            .settings_stub
+            .new_addition(verifier_rotation_key(verifier_key), rotation_index.encode());
+        self.verifiers_stub = self.verifiers_stub.new_addition(
+            verifier_key.key(),
+            CurrentVerifier::Valid(ValidCurrentVerifier::Custom {
+                v: Verifier {
+                    v: Some(new_verifier_value.to_owned()),
+                },
+            })
+            .encode(),
+        );
+        self.history_stub.push(Event::NetworkVerifierRotated {
+            network_verifier_rotation_display: NetworkVerifierRotationDisplay::get(
+                verifier_key,
+                old_verifier_value,
+                new_verifier_value,
+            ),
+        });
+        self
+    }
+
+    /// Prepare amending display specs of a known network, received as
+    /// `amend_specs` update, into the cold database:
+    ///
+    /// - Add a (key, value) pair to the network specs additions queue in
+    /// `network_specs_stub`. Key is [`NetworkSpecsKey`] and the value is
+    /// SCALE-encoded amended [`NetworkSpecs`], with `order` of the old ones.
+    /// - Add corresponding `Event::NetworkSpecsTokensAmended(_)` into
+    /// `history_stub`.
+    ///
+    /// Network addresses and metadata are not affected.
+    pub fn amend_network_specs(
+        mut self,
+        old_network_specs: &NetworkSpecs,
+        new_network_specs_to_send: &NetworkSpecsToSend,
+    ) -> Self {
+        let network_specs_key = NetworkSpecsKey::from_parts(
+            &old_network_specs.genesis_hash,
+            &old_network_specs.encryption,
+        );
+        let new_network_specs = new_network_specs_to_send.to_store(old_network_specs.order);
+        self.network_specs_stub = self
+            .network_specs_stub
+            .new_addition(network_specs_key.key(), new_network_specs.store());
+        self.history_stub.push(Event::NetworkSpecsTokensAmended {
+            network_specs_amendment_tokens_display: NetworkSpecsAmendmentTokensDisplay::get(
+                old_network_specs,
+                &new_network_specs,
+            ),
+        });
+        self
+    }
+
+    /// Prepare setting new Signer [`SecurityPolicy`] received as
+    /// `security_policy` update into the cold database:
+    ///
+    /// - Add a (key, value) pair to the settings additions queue in
+    /// `settings_stub`. Key is [`POLICY`] and the value is SCALE-encoded
+    /// [`SecurityPolicy`].
+    /// - Add corresponding `Event::SecurityPolicySet(_)` into `history_stub`.
+    pub fn new_security_policy(mut self, security_policy: &SecurityPolicy) -> Self {
+        self.settings_stub = self
+            .settings_stub
+            .new_addition(POLICY.to_vec(), security_policy.encode());
+        self.history_stub.push(Event::SecurityPolicySet {
+            security_policy: security_policy.to_owned(),
+        });
+        self
+    }
+
     /// Transform [`TrDbColdStub`] into [`TrDbCold`] and apply to the database
     /// with a given name, in a single transaction.
     ///
@@ -766,17 +907,26 @@ impl TrDbColdStub {
     where
         P: AsRef<Path>,
     {
-        let for_transaction = make_batch_clear_tree(&db_path, TRANSACTION)?;
+        self.apply_in(&SledStorage::open(db_path)?)
+    }
+
+    /// Same as [`apply`](Self::apply), for the database in given [`Storage`].
+    pub fn apply_in<S>(self, storage: &S) -> Result<()>
+    where
+        S: Storage,
+    {
+        let for_transaction = make_batch_clear_tree_in(storage, TRANSACTION)?;
         TrDbCold {
             for_addresses: self.addresses_stub.make_batch(),
-            for_history: events_to_batch(&db_path, self.history_stub)?,
+            for_contacts: self.contacts_stub.make_batch(),
+            for_history: events_to_batch_in(storage, self.history_stub)?,
             for_metadata: self.metadata_stub.make_batch(),
             for_network_specs: self.network_specs_stub.make_batch(),
             for_settings: self.settings_stub.make_batch(),
             for_transaction,
             for_verifiers: self.verifiers_stub.make_batch(),
         }
-        .apply(db_path)
+        .apply_in(storage)
     }
 }
 
@@ -804,7 +954,8 @@ impl Default for TrDbColdStub {
 /// [`TrDbColdSign`] contains:
 ///
 /// - [`SignContent`] with data to sign
-/// - name of the network in which the transaction is made
+/// - name of the network in which the transaction is made, and its
+/// [`NetworkSpecsKey`]
 /// - derivation path of the address used, whether the address has password,
 /// corresponding [`MultiSigner`] value
 /// - relevant history [`Event`] set: warnings that were shown during the
@@ -818,6 +969,10 @@ pub struct TrDbColdSign {
     /// name of the network in which the transaction is made
     network_name: String,
 
+    /// [`NetworkSpecsKey`] of the network in which the transaction is made,
+    /// used to record the address key usage
+    network_specs_key: NetworkSpecsKey,
+
     /// derivation path of the address by which the transaction was generated
     path: String,
 
@@ -840,7 +995,9 @@ pub struct TrDbColdSign {
 ///
 /// Mortal signable transactions have prelude `53xx00`, immortal have prelude
 /// `53xx02`. Signable transactions consist of method with call details and
-/// extensions.
+/// extensions. Transactions with prelude `53xx06` also carry the metadata
+/// proof they were decoded with, it is stored in the history log together
+/// with the transaction, so that the transaction could be decoded later.
 ///
 /// Messages contain SCALE-encoded text messages.
 #[cfg(feature = "signer")]
@@ -853,6 +1010,12 @@ pub enum SignContent {
 
         /// extensions as raw data
         extensions: Vec<u8>,
+
+        /// transaction nonce, if found in parsed extensions
+        nonce: Option<u64>,
+
+        /// metadata proof, for `53xx06` transaction
+        metadata_proof: Option<MetadataProof>,
     },
 
     /// `53xx03` text message
@@ -866,13 +1029,15 @@ impl TrDbColdSign {
     /// Required input:
     ///
     /// - [`SignContent`] with data to sign
-    /// - name of the network in which the transaction is made
+    /// - name of the network in which the transaction is made, and its
+    /// [`NetworkSpecsKey`]
     /// - derivation path of the address used, whether the address has password,
     /// corresponding [`MultiSigner`] value
     /// - relevant history [`Event`] set
     pub fn generate(
         content: SignContent,
         network_name: &str,
+        network_specs_key: &NetworkSpecsKey,
         path: &str,
         has_pwd: bool,
         multisigner: &MultiSigner,
@@ -881,6 +1046,7 @@ impl TrDbColdSign {
         Self {
             content,
             network_name: network_name.to_string(),
+            network_specs_key: network_specs_key.to_owned(),
             path: path.to_string(),
             has_pwd,
             multisigner: multisigner.to_owned(),
@@ -901,15 +1067,17 @@ impl TrDbColdSign {
     where
         P: AsRef<Path>,
     {
-        let sign_encoded = {
-            let database = open_db(&db_path)?;
-            verify_checksum(&database, checksum)?;
-            let transaction = open_tree(&database, TRANSACTION)?;
-            match transaction.get(SIGN)? {
-                Some(a) => a,
-                None => return Err(Error::Sign),
-            }
-        };
+        Self::from_storage_in(&SledStorage::open(db_path)?, checksum)
+    }
+
+    /// Same as [`from_storage`](Self::from_storage), for the database in given
+    /// [`Storage`].
+    pub fn from_storage_in<S>(storage: &S, checksum: u32) -> Result<Self>
+    where
+        S: Storage,
+    {
+        verify_checksum(storage, checksum)?;
+        let sign_encoded = storage.get(TRANSACTION, SIGN)?.ok_or(Error::Sign)?;
         Ok(Self::decode(&mut &sign_encoded[..])?)
     }
 
@@ -944,13 +1112,21 @@ impl TrDbColdSign {
     where
         P: AsRef<Path>,
     {
-        let mut transaction_batch = make_batch_clear_tree(&db_path, TRANSACTION)?;
+        self.store_and_get_checksum_in(&SledStorage::open(db_path)?)
+    }
+
+    /// Same as [`store_and_get_checksum`](Self::store_and_get_checksum), for
+    /// the database in given [`Storage`].
+    pub fn store_and_get_checksum_in<S>(&self, storage: &S) -> Result<u32>
+    where
+        S: Storage,
+    {
+        let mut transaction_batch = make_batch_clear_tree_in(storage, TRANSACTION)?;
         transaction_batch.insert(SIGN, self.encode());
         TrDbCold::new()
             .set_transaction(transaction_batch) // clear transaction tree
-            .apply(&db_path)?;
-        let database = open_db(&db_path)?;
-        Ok(database.checksum()?)
+            .apply_in(storage)?;
+        storage.checksum()
     }
 
     /// Use [`TrDbColdSign`] to add history log data into the cold database.
@@ -976,26 +1152,56 @@ impl TrDbColdSign {
     /// of wrong password entry.
     ///
     /// If the password entered is correct, the [`TRANSACTION`] tree gets
-    /// cleared.
+    /// cleared, and the usage statistics of the signing address key in the
+    /// [`ADDRTREE`] tree are updated.
     pub fn apply<P>(self, wrong_password: bool, user_comment: &str, db_path: P) -> Result<u32>
     where
         P: AsRef<Path>,
+    {
+        self.apply_in(wrong_password, user_comment, &SledStorage::open(db_path)?)
+    }
+
+    /// Same as [`apply`](Self::apply), for the database in given [`Storage`].
+    pub fn apply_in<S>(self, wrong_password: bool, user_comment: &str, storage: &S) -> Result<u32>
+    where
+        S: Storage,
     {
         let signed_by = VerifierValue::Standard {
             m: self.multisigner(),
         };
+        let address_key = AddressKey::from_multisigner(&self.multisigner);
         let mut history = self.history;
+        let mut metadata_proofs = Vec::new();
+        let mut for_addresses = Batch::default();
         let mut for_transaction = Batch::default();
         match self.content {
-            SignContent::Transaction { method, extensions } => {
+            SignContent::Transaction {
+                method,
+                extensions,
+                nonce,
+                metadata_proof,
+            } => {
                 let transaction = [method.encode(), extensions].concat();
                 let sign_display =
                     SignDisplay::get(&transaction, &self.network_name, &signed_by, user_comment);
+                if let Some(metadata_proof) = metadata_proof {
+                    metadata_proofs.push(EventProof {
+                        event_index: history.len() as u32,
+                        metadata_proof,
+                    })
+                }
                 if wrong_password {
                     history.push(Event::TransactionSignError { sign_display })
                 } else {
                     history.push(Event::TransactionSigned { sign_display });
-                    for_transaction = make_batch_clear_tree(&db_path, TRANSACTION)?;
+                    for_transaction = make_batch_clear_tree_in(storage, TRANSACTION)?;
+                    let mut address_details = get_address_details_in(storage, &address_key)?;
+                    address_details.record_transaction(
+                        &self.network_specs_key,
+                        nonce,
+                        &timestamp_now()?,
+                    );
+                    for_addresses.insert(address_key.key(), address_details.store());
                 }
             }
             SignContent::Message(message) => {
@@ -1009,16 +1215,23 @@ impl TrDbColdSign {
                     history.push(Event::MessageSigned {
                         sign_message_display,
                     });
-                    for_transaction = make_batch_clear_tree(&db_path, TRANSACTION)?;
+                    for_transaction = make_batch_clear_tree_in(storage, TRANSACTION)?;
+                    let mut address_details = get_address_details_in(storage, &address_key)?;
+                    address_details.record_message(&self.network_specs_key, &timestamp_now()?);
+                    for_addresses.insert(address_key.key(), address_details.store());
                 }
             }
         }
         TrDbCold::new()
-            .set_history(events_to_batch(&db_path, history)?)
+            .set_addresses(for_addresses)
+            .set_history(events_with_proofs_to_batch_in(
+                storage,
+                history,
+                metadata_proofs,
+            )?)
             .set_transaction(for_transaction)
-            .apply(&db_path)?;
-        let database = open_db(&db_path)?;
-        Ok(database.checksum()?)
+            .apply_in(storage)?;
+        storage.checksum()
     }
 }
 
@@ -1074,18 +1287,22 @@ impl TrDbColdDerivations {
     where
         P: AsRef<Path>,
     {
-        let drv_encoded = {
-            let database = open_db(&db_path)?;
-            verify_checksum(&database, checksum)?;
-            let transaction = open_tree(&database, TRANSACTION)?;
-            match transaction.get(DRV)? {
-                Some(a) => a,
-                None => return Err(Error::DerivationsNotFound),
-            }
-        };
+        Self::from_storage_in(&SledStorage::open(db_path)?, checksum)
+    }
+
+    /// Same as [`from_storage`](Self::from_storage), for the database in given
+    /// [`Storage`].
+    pub fn from_storage_in<S>(storage: &S, checksum: u32) -> Result<Self>
+    where
+        S: Storage,
+    {
+        verify_checksum(storage, checksum)?;
+        let drv_encoded = storage
+            .get(TRANSACTION, DRV)?
+            .ok_or(Error::DerivationsNotFound)?;
         TrDbCold::new()
-            .set_transaction(make_batch_clear_tree(&db_path, TRANSACTION)?) // clear transaction tree
-            .apply(&db_path)?;
+            .set_transaction(make_batch_clear_tree_in(storage, TRANSACTION)?) // clear transaction tree
+            .apply_in(storage)?;
         Ok(Self::decode(&mut &drv_encoded[..])?)
     }
 
@@ -1110,13 +1327,20 @@ impl TrDbColdDerivations {
     where
         P: AsRef<Path>,
     {
-        let mut transaction_batch = make_batch_clear_tree(&db_path, TRANSACTION)?;
+        self.store_and_get_checksum_in(&SledStorage::open(db_path)?)
+    }
+
+    /// Same as [`store_and_get_checksum`](Self::store_and_get_checksum), for
+    /// the database in given [`Storage`].
+    pub fn store_and_get_checksum_in<S>(&self, storage: &S) -> Result<u32>
+    where
+        S: Storage,
+    {
+        let mut transaction_batch = make_batch_clear_tree_in(storage, TRANSACTION)?;
         transaction_batch.insert(DRV, self.encode());
         TrDbCold::new()
             .set_transaction(transaction_batch) // clear transaction tree
-            .apply(&db_path)?;
-        let database = open_db(&db_path)?;
-
-        Ok(database.checksum()?)
+            .apply_in(storage)?;
+        storage.checksum()
     }
 }
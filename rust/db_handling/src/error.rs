@@ -4,6 +4,7 @@ use definitions::{
     crypto::Encryption,
     helpers::multisigner_to_public,
     keyring::{AddressKey, NetworkSpecsKey, VerifierKey},
+    policy::PolicyRule,
     users::AddressDetails,
 };
 use sp_core::H256;
@@ -98,6 +99,36 @@ pub enum Error {
     #[error("Unable to decode history entry for order {0}")]
     HistoryEntryNotFound(u32),
 
+    /// History log hash chain is broken: [`Entry`](definitions::history::Entry)
+    /// with given order is missing or does not contain the hash of the
+    /// previous entry.
+    ///
+    /// This indicates that the history log was tampered with.
+    #[error("History log hash chain is broken at order {order}.")]
+    HistoryChainBroken { order: u32 },
+
+    /// History log search query received from the frontend has a part that
+    /// could not be interpreted.
+    #[error("Unable to interpret history search query part {part}.")]
+    HistoryQueryInvalid { part: String },
+
+    /// History log has no entries with orders in the range requested for
+    /// audit report.
+    #[error("History log has no entries with orders from {first_order} to {last_order}.")]
+    HistoryAuditRangeEmpty { first_order: u32, last_order: u32 },
+
+    /// Contact name entered by user is empty.
+    #[error("Contact name could not be empty.")]
+    ContactNameEmpty,
+
+    /// Contact address entered by user is not a valid SS58 address.
+    #[error("Contact address {address} is not a valid SS58 address.")]
+    ContactAddressInvalid { address: String },
+
+    /// Contact to be removed is not in the database.
+    #[error("Could not find contact with account id {}.", hex::encode(.account_id))]
+    ContactNotFound { account_id: [u8; 32] },
+
     /// Database has two seed addresses (i.e. with empty derivation path and no
     /// password) for same seed name and [`Encryption`]
     ///
@@ -263,6 +294,10 @@ pub enum Error {
     #[error("No valid derivations found to generate ContentDerivations.")]
     NoValidDerivationToExport,
 
+    /// Structured derivations import source could not be read.
+    #[error("Derivations import source could not be read.")]
+    DerivationsImportNotReadable,
+
     /// Temporary database entry in `TRANSACTION` tree of the Signer database
     /// under the key `DRV`, used to store the derivation import data.
     ///
@@ -388,6 +423,27 @@ pub enum Error {
     hex::encode(multisigner_to_public(.multisigner)),
     )]
     NoSeedForKeyPair { multisigner: MultiSigner },
+
+    /// Action is forbidden by the
+    /// [`SecurityPolicy`](definitions::policy::SecurityPolicy) stored in
+    /// `SETTREE` tree of the Signer database under key `POLICY`.
+    ///
+    /// The attempt is recorded in the history log as
+    /// `Event::SecurityPolicyViolation`.
+    #[error("Forbidden by Signer security policy: {}.", .0.show())]
+    PolicyViolation(PolicyRule),
+
+    /// Cold database schema version, stored in `SETTREE` tree of the database
+    /// under key `SCHEMA_VERSION`, is newer than the latest version supported
+    /// by this Signer.
+    ///
+    /// Database was made or migrated by a newer Signer version, and could not
+    /// be operated safely.
+    #[error(
+        "Database schema version {found} is newer than the version {supported} \
+        supported by this Signer. Please update the Signer."
+    )]
+    SchemaVersionTooNew { found: u32, supported: u32 },
 }
 
 /// DB handling result.
@@ -0,0 +1,494 @@
+//! Cold database consistency check
+//!
+//! Cold database trees refer to each other: [`AddressDetails`] refer to
+//! network specs in `SPECSTREE` by [`NetworkSpecsKey`], network specs need
+//! verifier entry in `VERIFIERS`, metadata in `METATREE` is kept only for
+//! known network names, key annotations are kept only for the addresses in
+//! `ADDRTREE`, history log entries are hash-chained. Regular database
+//! operations keep these relations, but the database could still get
+//! inconsistent, for example if the app was killed during an update made by
+//! older Signer versions.
+//!
+//! [`check_db`] decodes every database entry with the same checked
+//! constructors used elsewhere, verifies all relations, and reports found
+//! problems as [`DbIssue`] set.
+//!
+//! Some issues have safe repair, that removes only the dangling data and
+//! never touches the seeds, verifiers or network specs. [`repair_db`] applies
+//! all safe repairs in a single [`TrDbCold`] transaction, and records them in
+//! the history log as `Event::DatabaseRepaired`.
+use parity_scale_codec::Decode;
+use std::{collections::HashSet, path::Path};
+
+use constants::{
+    ADDRTREE, CONTACTS, DANGER, GENERALVERIFIER, HISTORY, KEY_ANNOTATIONS, METATREE, POLICY,
+    SCHEMA_VERSION, SETTREE, SPECSTREE, TRANSACTION, TYPES, VERIFIERS,
+};
+use definitions::{
+    danger::DangerRecord,
+    history::{Entry, Event},
+    keyring::{AddressKey, MetaKey, NetworkSpecsKey, Order, VerifierKey},
+    metadata::MetaValues,
+    network_specs::{CurrentVerifier, NetworkSpecs, Verifier},
+    policy::SecurityPolicy,
+    types::TypeEntry,
+    users::{AddressDetails, ContactDetails, KeyAnnotation},
+};
+
+use crate::{
+    db_transactions::TrDbCold,
+    manage_history::{events_to_batch_in, verify_history_chain_in},
+    storage::{Batch, SledStorage, Storage},
+    Error, Result,
+};
+
+/// Problem found in the cold database
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DbIssue {
+    /// Entry could not be decoded, or its key does not match its content
+    UndecodableEntry {
+        /// tree name
+        tree: String,
+
+        /// hexadecimal entry key
+        key: String,
+
+        /// decoding error
+        error: String,
+    },
+
+    /// Required `SETTREE` entry is missing
+    MissingSetting { key: String },
+
+    /// Network specs have no verifier entry in `VERIFIERS` tree
+    SpecsWithoutVerifier {
+        network_specs_key: NetworkSpecsKey,
+        name: String,
+    },
+
+    /// Verifier entry has no network specs with its genesis hash
+    ///
+    /// Verifier entry stays in the database when the network is removed, so
+    /// that the network could not be added back with weaker verifier. This is
+    /// reported for information and never repaired automatically.
+    VerifierWithoutSpecs { verifier_key: VerifierKey },
+
+    /// Address refers to network specs that are not in `SPECSTREE`
+    AddressUnknownNetwork {
+        address_key: AddressKey,
+        network_specs_key: NetworkSpecsKey,
+    },
+
+    /// Metadata for network name with no network specs
+    MetadataUnknownNetwork { name: String, version: u32 },
+
+    /// Key annotation for address that is not in `ADDRTREE`
+    AnnotationUnknownAddress { address_key: AddressKey },
+
+    /// Temporary entry left in `TRANSACTION` tree
+    TransactionLeftover { key: Vec<u8> },
+
+    /// History log hash chain is broken starting from entry with given order
+    HistoryChainBroken { order: u32 },
+}
+
+impl DbIssue {
+    /// Issue identifier, stable across versions.
+    pub fn id(&self) -> &'static str {
+        match self {
+            DbIssue::UndecodableEntry { .. } => "undecodable_entry",
+            DbIssue::MissingSetting { .. } => "missing_setting",
+            DbIssue::SpecsWithoutVerifier { .. } => "specs_without_verifier",
+            DbIssue::VerifierWithoutSpecs { .. } => "verifier_without_specs",
+            DbIssue::AddressUnknownNetwork { .. } => "address_unknown_network",
+            DbIssue::MetadataUnknownNetwork { .. } => "metadata_unknown_network",
+            DbIssue::AnnotationUnknownAddress { .. } => "annotation_unknown_address",
+            DbIssue::TransactionLeftover { .. } => "transaction_leftover",
+            DbIssue::HistoryChainBroken { .. } => "history_chain_broken",
+        }
+    }
+
+    /// Issue description, for display.
+    pub fn show(&self) -> String {
+        match self {
+            DbIssue::UndecodableEntry { tree, key, error } => {
+                format!("entry {key} in tree {tree} could not be decoded: {error}")
+            }
+            DbIssue::MissingSetting { key } => format!("settings entry {key} is missing"),
+            DbIssue::SpecsWithoutVerifier {
+                network_specs_key,
+                name,
+            } => format!(
+                "network specs {} for {name} have no verifier entry",
+                hex::encode(network_specs_key.key())
+            ),
+            DbIssue::VerifierWithoutSpecs { verifier_key } => format!(
+                "verifier entry for genesis hash {} has no network specs",
+                hex::encode(verifier_key.genesis_hash())
+            ),
+            DbIssue::AddressUnknownNetwork {
+                address_key,
+                network_specs_key,
+            } => format!(
+                "address {} refers to unknown network specs {}",
+                hex::encode(address_key.key()),
+                hex::encode(network_specs_key.key())
+            ),
+            DbIssue::MetadataUnknownNetwork { name, version } => {
+                format!("metadata {name}{version} has no network specs")
+            }
+            DbIssue::AnnotationUnknownAddress { address_key } => format!(
+                "key annotation for unknown address {}",
+                hex::encode(address_key.key())
+            ),
+            DbIssue::TransactionLeftover { key } => format!(
+                "temporary transaction entry {} was left behind",
+                String::from_utf8_lossy(key)
+            ),
+            DbIssue::HistoryChainBroken { order } => {
+                format!("history log hash chain is broken at entry {order}")
+            }
+        }
+    }
+
+    /// Description of the safe repair, if there is one.
+    pub fn repair(&self) -> Option<&'static str> {
+        match self {
+            DbIssue::AddressUnknownNetwork { .. } => Some(
+                "remove unknown network from the address, \
+                and the address itself if it has no networks left",
+            ),
+            DbIssue::MetadataUnknownNetwork { .. } => Some("remove metadata entry"),
+            DbIssue::AnnotationUnknownAddress { .. } => Some("remove key annotation"),
+            DbIssue::TransactionLeftover { .. } => Some("remove temporary transaction entry"),
+            DbIssue::UndecodableEntry { .. }
+            | DbIssue::MissingSetting { .. }
+            | DbIssue::SpecsWithoutVerifier { .. }
+            | DbIssue::VerifierWithoutSpecs { .. }
+            | DbIssue::HistoryChainBroken { .. } => None,
+        }
+    }
+
+    /// Issue in displayable form, for the interface.
+    pub fn display(&self) -> DbIssueDisplay {
+        DbIssueDisplay {
+            id: self.id().to_string(),
+            description: self.show(),
+            repair: self.repair().map(|x| x.to_string()),
+        }
+    }
+}
+
+/// [`DbIssue`] in displayable form
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DbIssueDisplay {
+    pub id: String,
+    pub description: String,
+    pub repair: Option<String>,
+}
+
+/// Check the consistency of the cold database.
+///
+/// Result is the list of found issues, empty for the consistent database.
+pub fn check_db<P>(db_path: P) -> Result<Vec<DbIssue>>
+where
+    P: AsRef<Path>,
+{
+    check_db_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`check_db`], for the database in given [`Storage`].
+pub fn check_db_in<S>(storage: &S) -> Result<Vec<DbIssue>>
+where
+    S: Storage,
+{
+    let mut issues: Vec<DbIssue> = Vec::new();
+
+    check_settings(storage, &mut issues)?;
+
+    let mut network_specs_set: Vec<(NetworkSpecsKey, NetworkSpecs)> = Vec::new();
+    for (network_specs_key_vec, entry) in storage.iter(SPECSTREE)? {
+        let network_specs_key =
+            NetworkSpecsKey::from_ivec(&network_specs_key_vec.as_slice().into());
+        match NetworkSpecs::from_entry_with_key_checked(&network_specs_key, entry.into()) {
+            Ok(network_specs) => network_specs_set.push((network_specs_key, network_specs)),
+            Err(e) => issues.push(undecodable(SPECSTREE, &network_specs_key_vec, e)),
+        }
+    }
+
+    for (verifier_key_vec, entry) in storage.iter(VERIFIERS)? {
+        match VerifierKey::from_ivec(&verifier_key_vec.as_slice().into()) {
+            Ok(verifier_key) => {
+                if let Err(e) = <CurrentVerifier>::decode(&mut &entry[..]) {
+                    issues.push(undecodable(VERIFIERS, &verifier_key_vec, e));
+                }
+                if !network_specs_set
+                    .iter()
+                    .any(|(_, specs)| specs.genesis_hash == verifier_key.genesis_hash())
+                {
+                    issues.push(DbIssue::VerifierWithoutSpecs { verifier_key })
+                }
+            }
+            Err(e) => issues.push(undecodable(VERIFIERS, &verifier_key_vec, e)),
+        }
+    }
+
+    for (network_specs_key, network_specs) in network_specs_set.iter() {
+        let verifier_key = VerifierKey::from_parts(network_specs.genesis_hash);
+        if storage.get(VERIFIERS, &verifier_key.key())?.is_none() {
+            issues.push(DbIssue::SpecsWithoutVerifier {
+                network_specs_key: network_specs_key.to_owned(),
+                name: network_specs.name.to_string(),
+            })
+        }
+    }
+
+    let network_names: HashSet<&str> = network_specs_set
+        .iter()
+        .map(|(_, specs)| specs.name.as_str())
+        .collect();
+    for (meta_key_vec, entry) in storage.iter(METATREE)? {
+        match MetaValues::from_entry_checked((meta_key_vec.as_slice().into(), entry.into())) {
+            Ok(meta_values) => {
+                if !network_names.contains(meta_values.name.as_str()) {
+                    issues.push(DbIssue::MetadataUnknownNetwork {
+                        name: meta_values.name,
+                        version: meta_values.version,
+                    })
+                }
+            }
+            Err(e) => issues.push(undecodable(METATREE, &meta_key_vec, e)),
+        }
+    }
+
+    // all address keys, including the ones with undecodable details
+    let mut address_keys: HashSet<Vec<u8>> = HashSet::new();
+    for (address_key_vec, entry) in storage.iter(ADDRTREE)? {
+        let address_key = AddressKey::from_ivec(&address_key_vec.as_slice().into());
+        match AddressDetails::from_entry_with_key_checked(&address_key, entry.into()) {
+            Ok(address_details) => {
+                for network_specs_key in address_details.network_id.into_iter() {
+                    if !network_specs_set
+                        .iter()
+                        .any(|(key, _)| key == &network_specs_key)
+                    {
+                        issues.push(DbIssue::AddressUnknownNetwork {
+                            address_key: address_key.to_owned(),
+                            network_specs_key,
+                        })
+                    }
+                }
+            }
+            Err(e) => issues.push(undecodable(ADDRTREE, &address_key_vec, e)),
+        }
+        address_keys.insert(address_key.key());
+    }
+
+    for (address_key_vec, entry) in storage.iter(KEY_ANNOTATIONS)? {
+        match KeyAnnotation::process_entry((address_key_vec.as_slice().into(), entry.into())) {
+            Ok((address_key, _)) => {
+                if !address_keys.contains(&address_key.key()) {
+                    issues.push(DbIssue::AnnotationUnknownAddress { address_key })
+                }
+            }
+            Err(e) => issues.push(undecodable(KEY_ANNOTATIONS, &address_key_vec, e)),
+        }
+    }
+
+    for (account_id, entry) in storage.iter(CONTACTS)? {
+        if let Err(e) = ContactDetails::process_entry((account_id.as_slice().into(), entry.into()))
+        {
+            issues.push(undecodable(CONTACTS, &account_id, e))
+        }
+    }
+
+    let mut history_decodes = true;
+    for (order_encoded, entry) in storage.iter(HISTORY)? {
+        if let Err(e) = Order::from_ivec(&order_encoded.as_slice().into()) {
+            issues.push(undecodable(HISTORY, &order_encoded, e));
+            history_decodes = false
+        } else if let Err(e) = Entry::from_stored(&entry) {
+            issues.push(undecodable(HISTORY, &order_encoded, e));
+            history_decodes = false
+        }
+    }
+
+    // hash chain is walked only if all history log entries decode,
+    // undecodable entries are already reported
+    if history_decodes {
+        match verify_history_chain_in(storage) {
+            Ok(()) => (),
+            Err(Error::HistoryChainBroken { order }) => {
+                issues.push(DbIssue::HistoryChainBroken { order })
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    for (key, _) in storage.iter(TRANSACTION)? {
+        issues.push(DbIssue::TransactionLeftover { key })
+    }
+
+    Ok(issues)
+}
+
+/// Check `SETTREE` entries.
+///
+/// Danger record is always required. General verifier is set up together with
+/// the history log start, and is required only in the initiated database, i.e.
+/// with non-empty history log. Other settings could be missing and are only
+/// checked to decode.
+fn check_settings<S>(storage: &S, issues: &mut Vec<DbIssue>) -> Result<()>
+where
+    S: Storage,
+{
+    match storage.get(SETTREE, GENERALVERIFIER)? {
+        Some(encoded) => {
+            if let Err(e) = <Verifier>::decode(&mut &encoded[..]) {
+                issues.push(undecodable(SETTREE, GENERALVERIFIER, e))
+            }
+        }
+        None => {
+            if storage.len(HISTORY)? != 0 {
+                issues.push(missing_setting(GENERALVERIFIER))
+            }
+        }
+    }
+    match storage.get(SETTREE, DANGER)? {
+        Some(encoded) => {
+            if let Err(e) = DangerRecord::from_ivec(&encoded.into()).exposures() {
+                issues.push(undecodable(SETTREE, DANGER, e))
+            }
+        }
+        None => issues.push(missing_setting(DANGER)),
+    }
+    if let Some(encoded) = storage.get(SETTREE, TYPES)? {
+        if let Err(e) = <Vec<TypeEntry>>::decode(&mut &encoded[..]) {
+            issues.push(undecodable(SETTREE, TYPES, e))
+        }
+    }
+    if let Some(encoded) = storage.get(SETTREE, POLICY)? {
+        if let Err(e) = <SecurityPolicy>::decode(&mut &encoded[..]) {
+            issues.push(undecodable(SETTREE, POLICY, e))
+        }
+    }
+    if let Some(encoded) = storage.get(SETTREE, SCHEMA_VERSION)? {
+        if let Err(e) = <u32>::decode(&mut &encoded[..]) {
+            issues.push(undecodable(SETTREE, SCHEMA_VERSION, e))
+        }
+    }
+    Ok(())
+}
+
+fn undecodable<E>(tree: &[u8], key: &[u8], error: E) -> DbIssue
+where
+    E: ToString,
+{
+    DbIssue::UndecodableEntry {
+        tree: String::from_utf8_lossy(tree).to_string(),
+        key: hex::encode(key),
+        error: error.to_string(),
+    }
+}
+
+fn missing_setting(key: &[u8]) -> DbIssue {
+    DbIssue::MissingSetting {
+        key: String::from_utf8_lossy(key).to_string(),
+    }
+}
+
+/// Check the cold database and apply all safe repairs.
+///
+/// Repairs are applied in a single [`TrDbCold`] transaction, together with
+/// `Event::DatabaseRepaired` history log entry.
+///
+/// Result is the list of repaired issues. Issues with no safe repair are left
+/// as is and could be found by running [`check_db`] again.
+///
+/// Should be called only when there is no transaction in progress, as it
+/// clears `TRANSACTION` tree.
+pub fn repair_db<P>(db_path: P) -> Result<Vec<DbIssue>>
+where
+    P: AsRef<Path>,
+{
+    repair_db_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`repair_db`], for the database in given [`Storage`].
+pub fn repair_db_in<S>(storage: &S) -> Result<Vec<DbIssue>>
+where
+    S: Storage,
+{
+    let repaired: Vec<DbIssue> = check_db_in(storage)?
+        .into_iter()
+        .filter(|issue| issue.repair().is_some())
+        .collect();
+    if repaired.is_empty() {
+        return Ok(repaired);
+    }
+
+    let mut address_batch = Batch::default();
+    let mut key_annotations_batch = Batch::default();
+    let mut meta_batch = Batch::default();
+    let mut transaction_batch = Batch::default();
+
+    // unknown networks to remove, collected for each address
+    let mut unknown_networks: Vec<(AddressKey, Vec<NetworkSpecsKey>)> = Vec::new();
+
+    for issue in repaired.iter() {
+        match issue {
+            DbIssue::AddressUnknownNetwork {
+                address_key,
+                network_specs_key,
+            } => match unknown_networks
+                .iter_mut()
+                .find(|(key, _)| key == address_key)
+            {
+                Some((_, network_specs_keys)) => {
+                    network_specs_keys.push(network_specs_key.to_owned())
+                }
+                None => unknown_networks
+                    .push((address_key.to_owned(), vec![network_specs_key.to_owned()])),
+            },
+            DbIssue::MetadataUnknownNetwork { name, version } => {
+                meta_batch.remove(MetaKey::from_parts(name, *version).key())
+            }
+            DbIssue::AnnotationUnknownAddress { address_key } => {
+                key_annotations_batch.remove(address_key.key())
+            }
+            DbIssue::TransactionLeftover { key } => transaction_batch.remove(key),
+            _ => (),
+        }
+    }
+
+    for (address_key, network_specs_keys) in unknown_networks.into_iter() {
+        if let Some(entry) = storage.get(ADDRTREE, &address_key.key())? {
+            let mut address_details =
+                AddressDetails::from_entry_with_key_checked(&address_key, entry.into())?;
+            address_details
+                .network_id
+                .retain(|key| !network_specs_keys.contains(key));
+            address_details
+                .usage
+                .retain(|key_usage| !network_specs_keys.contains(&key_usage.network_specs_key));
+            if address_details.network_id.is_empty() {
+                address_batch.remove(address_key.key());
+                key_annotations_batch.remove(address_key.key())
+            } else {
+                address_batch.insert(address_key.key(), address_details.store())
+            }
+        }
+    }
+
+    let events = vec![Event::DatabaseRepaired {
+        repairs: repaired.iter().map(|issue| issue.show()).collect(),
+    }];
+    TrDbCold::new()
+        .set_addresses(address_batch) // upd addresses
+        .set_history(events_to_batch_in(storage, events)?) // add corresponding history
+        .set_key_annotations(key_annotations_batch) // upd key annotations
+        .set_metadata(meta_batch) // upd metadata
+        .set_transaction(transaction_batch) // clear leftover transaction entries
+        .apply_in(storage)?;
+    Ok(repaired)
+}
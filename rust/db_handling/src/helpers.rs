@@ -1,24 +1,36 @@
 //! Common helper functions for database operations
 
 use parity_scale_codec::Decode;
-#[cfg(any(feature = "active", feature = "signer"))]
+#[cfg(feature = "signer")]
 use parity_scale_codec::Encode;
-use sled::{open, Batch, Db, Tree};
+use sled::{open, Db, Tree};
+#[cfg(feature = "signer")]
+use sp_core::{
+    crypto::{AccountId32, Ss58Codec},
+    H256,
+};
 #[cfg(feature = "signer")]
-use sp_core::H256;
+use sp_runtime::MultiSigner;
 use std::path::Path;
 
 #[cfg(feature = "signer")]
-use constants::{ADDRTREE, DANGER, GENERALVERIFIER, VERIFIERS};
+use constants::VERIFIER_ROTATION;
+#[cfg(feature = "signer")]
+use constants::{ADDRTREE, CONTACTS, DANGER, GENERALVERIFIER, KEY_ANNOTATIONS, POLICY, VERIFIERS};
 use constants::{METATREE, SETTREE, SPECSTREE, TYPES};
 
 #[cfg(feature = "signer")]
 use definitions::{
-    danger::DangerRecord,
+    danger::{DangerRecord, ExposureRecord},
     helpers::multisigner_to_public,
-    history::{Event, IdentityHistory, MetaValuesDisplay, NetworkSpecsDisplay, TypesDisplay},
+    history::{
+        ContactDisplay, Event, IdentityHistory, KeyAnnotationHistory, MetaValuesDisplay,
+        NetworkSpecsDisplay, TypesDisplay,
+    },
     keyring::{NetworkSpecsKey, VerifierKey},
     network_specs::{CurrentVerifier, ValidCurrentVerifier, Verifier},
+    policy::{PolicyRule, SecurityPolicy},
+    users::{ContactDetails, ContactEntry, KeyAnnotation},
 };
 use definitions::{
     keyring::MetaKey, metadata::MetaValues, network_specs::NetworkSpecs,
@@ -30,21 +42,27 @@ use definitions::{
     users::AddressDetails,
 };
 
-use crate::{Error, Result};
+use crate::{migrations::check_schema_version, Error, Result};
 
 #[cfg(any(feature = "active", feature = "signer"))]
 use crate::db_transactions::TrDbCold;
 #[cfg(feature = "signer")]
-use crate::manage_history::events_to_batch;
+use crate::manage_history::{enter_events_in, events_to_batch_in};
+use crate::storage::{sled_entry, Batch, SledStorage, Storage};
 
 /// Open a database.
 ///
 /// Wrapper for [`open`] from [`sled`]. Input is database location path.
+///
+/// Databases with schema version newer than this Signer supports are refused,
+/// see [`migrations`](crate::migrations).
 pub fn open_db<P>(db_path: P) -> Result<Db>
 where
     P: AsRef<Path>,
 {
-    Ok(open(db_path)?)
+    let database = open(db_path)?;
+    check_schema_version(&database)?;
+    Ok(database)
 }
 
 /// Open a tree in the database.
@@ -60,10 +78,16 @@ pub fn make_batch_clear_tree<P>(db_path: P, tree_name: &[u8]) -> Result<Batch>
 where
     P: AsRef<Path>,
 {
-    let database = open_db(&db_path)?;
-    let tree = open_tree(&database, tree_name)?;
+    make_batch_clear_tree_in(&SledStorage::open(db_path)?, tree_name)
+}
+
+/// Same as [`make_batch_clear_tree`], for the database in given [`Storage`].
+pub fn make_batch_clear_tree_in<S>(storage: &S, tree_name: &[u8]) -> Result<Batch>
+where
+    S: Storage,
+{
     let mut out = Batch::default();
-    for (key, _) in tree.iter().flatten() {
+    for (key, _) in storage.iter(tree_name)? {
         out.remove(key)
     }
     Ok(out)
@@ -77,11 +101,17 @@ pub fn get_all_networks<P>(db_path: P) -> Result<Vec<NetworkSpecs>>
 where
     P: AsRef<Path>,
 {
-    let database = open_db(&db_path)?;
-    let chainspecs = open_tree(&database, SPECSTREE)?;
+    get_all_networks_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`get_all_networks`], for the database in given [`Storage`].
+pub fn get_all_networks_in<S>(storage: &S) -> Result<Vec<NetworkSpecs>>
+where
+    S: Storage,
+{
     let mut out: Vec<NetworkSpecs> = Vec::new();
-    for x in chainspecs.iter().flatten() {
-        out.push(NetworkSpecs::from_entry_checked(x)?)
+    for x in storage.iter(SPECSTREE)? {
+        out.push(NetworkSpecs::from_entry_checked(sled_entry(x))?)
     }
     Ok(out)
 }
@@ -103,10 +133,21 @@ pub fn try_get_valid_current_verifier<P>(
 where
     P: AsRef<Path>,
 {
-    let general_verifier = get_general_verifier(&db_path)?;
-    let database = open_db(&db_path)?;
-    let verifiers = open_tree(&database, VERIFIERS)?;
-    match verifiers.get(verifier_key.key())? {
+    try_get_valid_current_verifier_in(verifier_key, &SledStorage::open(db_path)?)
+}
+
+/// Same as [`try_get_valid_current_verifier`], for the database in given
+/// [`Storage`].
+#[cfg(feature = "signer")]
+pub fn try_get_valid_current_verifier_in<S>(
+    verifier_key: &VerifierKey,
+    storage: &S,
+) -> Result<Option<ValidCurrentVerifier>>
+where
+    S: Storage,
+{
+    let general_verifier = get_general_verifier_in(storage)?;
+    match storage.get(VERIFIERS, &verifier_key.key())? {
         // verifier entry is found
         Some(verifier_encoded) => {
             match <CurrentVerifier>::decode(&mut &verifier_encoded[..])? {
@@ -142,7 +183,7 @@ where
             // If the genesis hash is found in network specs, but no verifier
             // entry exists, it indicated that the database is corrupted.
             if let Some(specs_invariants) =
-                genesis_hash_in_specs(verifier_key.genesis_hash(), &database)?
+                genesis_hash_in_specs(verifier_key.genesis_hash(), storage)?
             {
                 return Err(Error::UnexpectedGenesisHash {
                     name: specs_invariants.name,
@@ -167,7 +208,20 @@ pub fn get_valid_current_verifier<P>(
 where
     P: AsRef<Path>,
 {
-    try_get_valid_current_verifier(verifier_key, db_path)?
+    get_valid_current_verifier_in(verifier_key, &SledStorage::open(db_path)?)
+}
+
+/// Same as [`get_valid_current_verifier`], for the database in given
+/// [`Storage`].
+#[cfg(feature = "signer")]
+pub fn get_valid_current_verifier_in<S>(
+    verifier_key: &VerifierKey,
+    storage: &S,
+) -> Result<ValidCurrentVerifier>
+where
+    S: Storage,
+{
+    try_get_valid_current_verifier_in(verifier_key, storage)?
         .ok_or_else(|| Error::NoValidCurrentVerifier(verifier_key.clone()))
 }
 
@@ -201,14 +255,18 @@ pub struct SpecsInvariants {
 /// could be a part of the network metadata, and therefore must not depend on
 /// encryption used.
 #[cfg(feature = "signer")]
-pub fn genesis_hash_in_specs(genesis_hash: H256, database: &Db) -> Result<Option<SpecsInvariants>> {
-    let chainspecs = open_tree(database, SPECSTREE)?;
+pub fn genesis_hash_in_specs<S>(genesis_hash: H256, storage: &S) -> Result<Option<SpecsInvariants>>
+where
+    S: Storage,
+{
     let mut specs_set: Vec<(NetworkSpecsKey, NetworkSpecs)> = Vec::new();
     let mut found_permanent_specs: Option<(u16, String)> = None;
-    for (network_specs_key_vec, network_specs_encoded) in chainspecs.iter().flatten() {
-        let network_specs_key = NetworkSpecsKey::from_ivec(&network_specs_key_vec);
-        let network_specs =
-            NetworkSpecs::from_entry_with_key_checked(&network_specs_key, network_specs_encoded)?;
+    for (network_specs_key_vec, network_specs_encoded) in storage.iter(SPECSTREE)? {
+        let network_specs_key = NetworkSpecsKey::from_ivec(&network_specs_key_vec.into());
+        let network_specs = NetworkSpecs::from_entry_with_key_checked(
+            &network_specs_key,
+            network_specs_encoded.into(),
+        )?;
         if network_specs.genesis_hash.as_bytes() == &genesis_hash[..] {
             found_permanent_specs = match found_permanent_specs {
                 Some((base58prefix, name)) => {
@@ -256,14 +314,104 @@ pub fn get_general_verifier<P>(db_path: P) -> Result<Verifier>
 where
     P: AsRef<Path>,
 {
-    let database = open_db(&db_path)?;
-    let settings = open_tree(&database, SETTREE)?;
-    let verifier_encoded = settings
-        .get(GENERALVERIFIER)?
+    get_general_verifier_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`get_general_verifier`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn get_general_verifier_in<S>(storage: &S) -> Result<Verifier>
+where
+    S: Storage,
+{
+    let verifier_encoded = storage
+        .get(SETTREE, GENERALVERIFIER)?
         .ok_or(Error::GeneralVerifierNotFound)?;
     Ok(<Verifier>::decode(&mut &verifier_encoded[..])?)
 }
 
+/// Get index of the last custom verifier rotation accepted for the network.
+///
+/// Index is stored in `SETTREE` tree under key `VERIFIER_ROTATION` followed by
+/// the network genesis hash. Networks without accepted rotations have no
+/// record, and index `0` is used.
+#[cfg(feature = "signer")]
+pub fn get_verifier_rotation_index<P>(verifier_key: &VerifierKey, db_path: P) -> Result<u32>
+where
+    P: AsRef<Path>,
+{
+    get_verifier_rotation_index_in(verifier_key, &SledStorage::open(db_path)?)
+}
+
+/// Same as [`get_verifier_rotation_index`], for the database in given
+/// [`Storage`].
+#[cfg(feature = "signer")]
+pub fn get_verifier_rotation_index_in<S>(verifier_key: &VerifierKey, storage: &S) -> Result<u32>
+where
+    S: Storage,
+{
+    match storage.get(SETTREE, &verifier_rotation_key(verifier_key))? {
+        Some(index_encoded) => Ok(<u32>::decode(&mut &index_encoded[..])?),
+        None => Ok(0),
+    }
+}
+
+/// Key in `SETTREE` tree for index of the last custom verifier rotation
+/// accepted for the network.
+#[cfg(feature = "signer")]
+pub(crate) fn verifier_rotation_key(verifier_key: &VerifierKey) -> Vec<u8> {
+    [VERIFIER_ROTATION, &verifier_key.key()].concat()
+}
+
+/// Get [`SecurityPolicy`] from the Signer database.
+///
+/// Security policy is stored in `SETTREE` tree under key `POLICY`. Databases
+/// initiated without explicit policy have no record, and the default policy
+/// (nothing is forbidden) is used.
+#[cfg(feature = "signer")]
+pub fn get_security_policy<P>(db_path: P) -> Result<SecurityPolicy>
+where
+    P: AsRef<Path>,
+{
+    get_security_policy_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`get_security_policy`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn get_security_policy_in<S>(storage: &S) -> Result<SecurityPolicy>
+where
+    S: Storage,
+{
+    match storage.get(SETTREE, POLICY)? {
+        Some(policy_encoded) => Ok(<SecurityPolicy>::decode(&mut &policy_encoded[..])?),
+        None => Ok(SecurityPolicy::default()),
+    }
+}
+
+/// Check that the action is allowed by the Signer [`SecurityPolicy`].
+///
+/// If the policy forbids the action, `Event::SecurityPolicyViolation` is
+/// entered into the history log and [`Error::PolicyViolation`] is produced.
+#[cfg(feature = "signer")]
+pub fn enforce_policy<P>(rule: PolicyRule, db_path: P) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    enforce_policy_in(rule, &SledStorage::open(db_path)?)
+}
+
+/// Same as [`enforce_policy`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn enforce_policy_in<S>(rule: PolicyRule, storage: &S) -> Result<()>
+where
+    S: Storage,
+{
+    if get_security_policy_in(storage)?.forbids(rule) {
+        enter_events_in(storage, vec![Event::SecurityPolicyViolation { rule }])?;
+        return Err(Error::PolicyViolation(rule));
+    }
+    Ok(())
+}
+
 /// Try to get types information from the database.
 ///
 /// If no types information is found, result is `Ok(None)`.
@@ -271,10 +419,16 @@ pub fn try_get_types<P>(db_path: P) -> Result<Option<Vec<TypeEntry>>>
 where
     P: AsRef<Path>,
 {
-    let database = open_db(&db_path)?;
-    let settings = open_tree(&database, SETTREE)?;
-    let res = settings
-        .get(TYPES)?
+    try_get_types_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`try_get_types`], for the database in given [`Storage`].
+pub fn try_get_types_in<S>(storage: &S) -> Result<Option<Vec<TypeEntry>>>
+where
+    S: Storage,
+{
+    let res = storage
+        .get(SETTREE, TYPES)?
         .map(|types_info_encoded| <Vec<TypeEntry>>::decode(&mut &types_info_encoded[..]))
         .transpose()?;
 
@@ -298,6 +452,14 @@ where
     try_get_types(db_path)?.ok_or(Error::TypesNotFound)
 }
 
+/// Same as [`get_types`], for the database in given [`Storage`].
+pub fn get_types_in<S>(storage: &S) -> Result<Vec<TypeEntry>>
+where
+    S: Storage,
+{
+    try_get_types_in(storage)?.ok_or(Error::TypesNotFound)
+}
+
 /// Get types information as [`ContentLoadTypes`] from the database.
 ///
 /// Function prepares types information in qr payload format.
@@ -311,7 +473,15 @@ pub fn prep_types<P>(db_path: P) -> Result<ContentLoadTypes>
 where
     P: AsRef<Path>,
 {
-    Ok(ContentLoadTypes::generate(&get_types(db_path)?))
+    prep_types_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`prep_types`], for the database in given [`Storage`].
+pub fn prep_types_in<S>(storage: &S) -> Result<ContentLoadTypes>
+where
+    S: Storage,
+{
+    Ok(ContentLoadTypes::generate(&get_types_in(storage)?))
 }
 
 /// Try to get network specs [`NetworkSpecs`] from the Signer database.
@@ -326,12 +496,25 @@ pub fn try_get_network_specs<P>(
 where
     P: AsRef<Path>,
 {
-    let database = open_db(&db_path)?;
-    let chainspecs = open_tree(&database, SPECSTREE)?;
-    Ok(chainspecs
-        .get(network_specs_key.key())?
+    try_get_network_specs_in(&SledStorage::open(db_path)?, network_specs_key)
+}
+
+/// Same as [`try_get_network_specs`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn try_get_network_specs_in<S>(
+    storage: &S,
+    network_specs_key: &NetworkSpecsKey,
+) -> Result<Option<NetworkSpecs>>
+where
+    S: Storage,
+{
+    Ok(storage
+        .get(SPECSTREE, &network_specs_key.key())?
         .map(|network_specs_encoded| {
-            NetworkSpecs::from_entry_with_key_checked(network_specs_key, network_specs_encoded)
+            NetworkSpecs::from_entry_with_key_checked(
+                network_specs_key,
+                network_specs_encoded.into(),
+            )
         })
         .transpose()?)
 }
@@ -349,6 +532,19 @@ where
         .ok_or_else(|| Error::NetworkSpecsNotFound(network_specs_key.clone()))
 }
 
+/// Same as [`get_network_specs`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn get_network_specs_in<S>(
+    storage: &S,
+    network_specs_key: &NetworkSpecsKey,
+) -> Result<NetworkSpecs>
+where
+    S: Storage,
+{
+    try_get_network_specs_in(storage, network_specs_key)?
+        .ok_or_else(|| Error::NetworkSpecsNotFound(network_specs_key.clone()))
+}
+
 /// Try to get [`AddressDetails`] from the Signer database, using
 /// [`AddressKey`].
 ///
@@ -361,14 +557,24 @@ pub fn try_get_address_details<P>(
 where
     P: AsRef<Path>,
 {
-    let database = open_db(&db_path)?;
-    let identities = open_tree(&database, ADDRTREE)?;
-    identities
-        .get(address_key.key())?
+    try_get_address_details_in(&SledStorage::open(db_path)?, address_key)
+}
+
+/// Same as [`try_get_address_details`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn try_get_address_details_in<S>(
+    storage: &S,
+    address_key: &AddressKey,
+) -> Result<Option<AddressDetails>>
+where
+    S: Storage,
+{
+    storage
+        .get(ADDRTREE, &address_key.key())?
         .map(|address_details_encoded| -> Result<AddressDetails> {
             Ok(AddressDetails::from_entry_with_key_checked(
                 address_key,
-                address_details_encoded,
+                address_details_encoded.into(),
             )?)
         })
         .transpose()
@@ -387,22 +593,33 @@ where
         .ok_or_else(|| Error::AddressNotFound(address_key.clone()))
 }
 
+/// Same as [`get_address_details`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn get_address_details_in<S>(storage: &S, address_key: &AddressKey) -> Result<AddressDetails>
+where
+    S: Storage,
+{
+    try_get_address_details_in(storage, address_key)?
+        .ok_or_else(|| Error::AddressNotFound(address_key.clone()))
+}
+
 /// Get [`MetaValues`] set from Signer database, for networks with a given name.
 ///
 /// The resulting set could be an empty one. It is used to display metadata
 /// available for the network and to find the metadata to be deleted, when the
 /// network gets deleted.
 #[cfg(feature = "signer")]
-pub(crate) fn get_meta_values_by_name<P>(db_path: P, network_name: &str) -> Result<Vec<MetaValues>>
+pub(crate) fn get_meta_values_by_name_in<S>(
+    storage: &S,
+    network_name: &str,
+) -> Result<Vec<MetaValues>>
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
-    let database = open_db(&db_path)?;
-    let metadata = open_tree(&database, METATREE)?;
     let mut out: Vec<MetaValues> = Vec::new();
     let meta_key_prefix = MetaKeyPrefix::from_name(network_name);
-    for x in metadata.scan_prefix(meta_key_prefix.prefix()).flatten() {
-        let meta_values = MetaValues::from_entry_checked(x)?;
+    for x in storage.scan_prefix(METATREE, &meta_key_prefix.prefix())? {
+        let meta_values = MetaValues::from_entry_checked(sled_entry(x))?;
         if meta_values.name == network_name {
             out.push(meta_values)
         }
@@ -422,16 +639,31 @@ pub fn try_get_meta_values_by_name_version<P>(
 where
     P: AsRef<Path>,
 {
-    let database = open_db(&db_path)?;
-    let metadata = open_tree(&database, METATREE)?;
+    try_get_meta_values_by_name_version_in(
+        &SledStorage::open(db_path)?,
+        network_name,
+        network_version,
+    )
+}
+
+/// Same as [`try_get_meta_values_by_name_version`], for the database in given
+/// [`Storage`].
+pub fn try_get_meta_values_by_name_version_in<S>(
+    storage: &S,
+    network_name: &str,
+    network_version: u32,
+) -> Result<Option<MetaValues>>
+where
+    S: Storage,
+{
     let meta_key = MetaKey::from_parts(network_name, network_version);
-    Ok(metadata
-        .get(meta_key.key())?
+    Ok(storage
+        .get(METATREE, &meta_key.key())?
         .map(|meta| -> Result<_> {
             Ok(Some(MetaValues::from_entry_name_version_checked(
                 network_name,
                 network_version,
-                meta,
+                meta.into(),
             )?))
         })
         .transpose()?
@@ -451,7 +683,20 @@ pub fn get_meta_values_by_name_version<P>(
 where
     P: AsRef<Path>,
 {
-    try_get_meta_values_by_name_version(db_path, network_name, network_version)?.ok_or(
+    get_meta_values_by_name_version_in(&SledStorage::open(db_path)?, network_name, network_version)
+}
+
+/// Same as [`get_meta_values_by_name_version`], for the database in given
+/// [`Storage`].
+pub fn get_meta_values_by_name_version_in<S>(
+    storage: &S,
+    network_name: &str,
+    network_version: u32,
+) -> Result<MetaValues>
+where
+    S: Storage,
+{
+    try_get_meta_values_by_name_version_in(storage, network_name, network_version)?.ok_or(
         Error::MetaValuesNotFound {
             name: network_name.to_owned(),
             version: network_version,
@@ -471,26 +716,34 @@ where
 pub fn transfer_metadata_to_cold<P>(hot_db_path: P, cold_db_path: P) -> Result<()>
 where
     P: AsRef<Path>,
+{
+    transfer_metadata_to_cold_in(
+        &SledStorage::open(hot_db_path)?,
+        &SledStorage::open(cold_db_path)?,
+    )
+}
+
+/// Same as [`transfer_metadata_to_cold`], for the databases in given
+/// [`Storage`]s.
+#[cfg(feature = "active")]
+pub fn transfer_metadata_to_cold_in<H, C>(hot_storage: &H, cold_storage: &C) -> Result<()>
+where
+    H: Storage,
+    C: Storage,
 {
     let mut for_metadata = Batch::default();
-    {
-        let database_hot = open_db(&hot_db_path)?;
-        let metadata_hot = open_tree(&database_hot, METATREE)?;
-        let database_cold = open_db(&cold_db_path)?;
-        let chainspecs_cold = open_tree(&database_cold, SPECSTREE)?;
-        for x in chainspecs_cold.iter().flatten() {
-            let network_specs = NetworkSpecs::from_entry_checked(x)?;
-            for (key, value) in metadata_hot
-                .scan_prefix(MetaKeyPrefix::from_name(&network_specs.name).prefix())
-                .flatten()
-            {
-                for_metadata.insert(key, value)
-            }
+    for x in cold_storage.iter(SPECSTREE)? {
+        let network_specs = NetworkSpecs::from_entry_checked(sled_entry(x))?;
+        for (key, value) in hot_storage.scan_prefix(
+            METATREE,
+            &MetaKeyPrefix::from_name(&network_specs.name).prefix(),
+        )? {
+            for_metadata.insert(key, value)
         }
     }
     TrDbCold::new()
         .set_metadata(for_metadata)
-        .apply(&cold_db_path)
+        .apply_in(cold_storage)
 }
 
 /// Remove the network from the database.
@@ -533,18 +786,28 @@ where
 pub fn remove_network<P>(network_specs_key: &NetworkSpecsKey, db_path: P) -> Result<()>
 where
     P: AsRef<Path>,
+{
+    remove_network_in(network_specs_key, &SledStorage::open(db_path)?)
+}
+
+/// Same as [`remove_network`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn remove_network_in<S>(network_specs_key: &NetworkSpecsKey, storage: &S) -> Result<()>
+where
+    S: Storage,
 {
     let mut address_batch = Batch::default();
+    let mut key_annotations_batch = Batch::default();
     let mut meta_batch = Batch::default();
     let mut network_specs_batch = Batch::default();
     let mut verifiers_batch = Batch::default();
     let mut events: Vec<Event> = Vec::new();
 
-    let general_verifier = get_general_verifier(&db_path)?;
-    let network_specs = get_network_specs(&db_path, network_specs_key)?;
+    let general_verifier = get_general_verifier_in(storage)?;
+    let network_specs = get_network_specs_in(storage, network_specs_key)?;
 
     let verifier_key = VerifierKey::from_parts(network_specs.genesis_hash);
-    let valid_current_verifier = get_valid_current_verifier(&verifier_key, &db_path)?;
+    let valid_current_verifier = get_valid_current_verifier_in(&verifier_key, storage)?;
 
     // modify verifier as needed
     if let ValidCurrentVerifier::Custom { ref v } = valid_current_verifier {
@@ -558,7 +821,7 @@ where
     }
 
     // scan through metadata tree to mark for removal all networks with target name
-    for meta_values in get_meta_values_by_name(&db_path, &network_specs.name)?.iter() {
+    for meta_values in get_meta_values_by_name_in(storage, &network_specs.name)?.iter() {
         let meta_key = MetaKey::from_parts(&meta_values.name, meta_values.version);
         meta_batch.remove(meta_key.key());
         events.push(Event::MetadataRemoved {
@@ -567,13 +830,9 @@ where
     }
 
     {
-        let database = open_db(&db_path)?;
-        let chainspecs = open_tree(&database, SPECSTREE)?;
-        let identities = open_tree(&database, ADDRTREE)?;
-
         // scan through chainspecs tree to mark for removal all networks with target genesis hash
         let mut keys_to_wipe: Vec<NetworkSpecsKey> = Vec::new();
-        for (network_specs_key_vec, entry) in chainspecs.iter().flatten() {
+        for (network_specs_key_vec, entry) in storage.iter(SPECSTREE)?.into_iter().map(sled_entry) {
             let x_network_specs_key = NetworkSpecsKey::from_ivec(&network_specs_key_vec);
             let mut x_network_specs =
                 NetworkSpecs::from_entry_with_key_checked(&x_network_specs_key, entry)?;
@@ -589,12 +848,12 @@ where
                 keys_to_wipe.push(x_network_specs_key);
             } else if x_network_specs.order > network_specs.order {
                 x_network_specs.order -= 1;
-                network_specs_batch.insert(x_network_specs_key.key(), x_network_specs.encode());
+                network_specs_batch.insert(x_network_specs_key.key(), x_network_specs.store());
             }
         }
 
         // scan through address tree to clean up the network_key(s) from identities
-        for (address_key_vec, entry) in identities.iter().flatten() {
+        for (address_key_vec, entry) in storage.iter(ADDRTREE)?.into_iter().map(sled_entry) {
             let address_key = AddressKey::from_ivec(&address_key_vec);
             let (multisigner, mut address_details) =
                 AddressDetails::process_entry_checked((address_key_vec, entry))?;
@@ -613,22 +872,30 @@ where
                         .into_iter()
                         .filter(|id| id != key)
                         .collect();
+                    address_details
+                        .usage
+                        .retain(|key_usage| &key_usage.network_specs_key != key);
                 }
             }
             if address_details.network_id.is_empty() {
-                address_batch.remove(address_key.key())
+                if storage.get(KEY_ANNOTATIONS, &address_key.key())?.is_some() {
+                    events.push(key_annotation_removed_event(&multisigner, &address_details));
+                }
+                address_batch.remove(address_key.key());
+                key_annotations_batch.remove(address_key.key())
             } else {
-                address_batch.insert(address_key.key(), address_details.encode())
+                address_batch.insert(address_key.key(), address_details.store())
             }
         }
     }
     TrDbCold::new()
         .set_addresses(address_batch) // upd addresses
-        .set_history(events_to_batch(&db_path, events)?) // add corresponding history
+        .set_history(events_to_batch_in(storage, events)?) // add corresponding history
+        .set_key_annotations(key_annotations_batch) // upd key annotations
         .set_metadata(meta_batch) // upd metadata
         .set_network_specs(network_specs_batch) // upd network_specs
         .set_verifiers(verifiers_batch) // upd network_verifiers
-        .apply(&db_path)
+        .apply_in(storage)
 }
 
 /// Remove the network metadata entry from the database.
@@ -651,16 +918,33 @@ pub fn remove_metadata<P>(
 where
     P: AsRef<Path>,
 {
-    let network_specs = get_network_specs(&db_path, network_specs_key)?;
+    remove_metadata_in(
+        network_specs_key,
+        network_version,
+        &SledStorage::open(db_path)?,
+    )
+}
+
+/// Same as [`remove_metadata`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn remove_metadata_in<S>(
+    network_specs_key: &NetworkSpecsKey,
+    network_version: u32,
+    storage: &S,
+) -> Result<()>
+where
+    S: Storage,
+{
+    let network_specs = get_network_specs_in(storage, network_specs_key)?;
     let meta_key = MetaKey::from_parts(&network_specs.name, network_version);
     let mut meta_batch = Batch::default();
     meta_batch.remove(meta_key.key());
 
     let meta_values =
-        get_meta_values_by_name_version(&db_path, &network_specs.name, network_version)?;
+        get_meta_values_by_name_version_in(storage, &network_specs.name, network_version)?;
     let meta_values_display = MetaValuesDisplay::get(&meta_values);
-    let history_batch = events_to_batch(
-        &db_path,
+    let history_batch = events_to_batch_in(
+        storage,
         vec![Event::MetadataRemoved {
             meta_values_display,
         }],
@@ -668,7 +952,7 @@ where
     TrDbCold::new()
         .set_metadata(meta_batch) // remove metadata
         .set_history(history_batch) // add corresponding history
-        .apply(&db_path)
+        .apply_in(storage)
 }
 
 /// User-initiated removal of the types information from the Signer database.
@@ -690,21 +974,324 @@ where
 pub fn remove_types_info<P>(db_path: P) -> Result<()>
 where
     P: AsRef<Path>,
+{
+    remove_types_info_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`remove_types_info`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn remove_types_info_in<S>(storage: &S) -> Result<()>
+where
+    S: Storage,
 {
     let mut settings_batch = Batch::default();
     settings_batch.remove(TYPES);
     let events: Vec<Event> = vec![Event::TypesRemoved {
         types_display: TypesDisplay::get(
-            &ContentLoadTypes::generate(&get_types(&db_path)?),
-            &get_general_verifier(&db_path)?,
+            &ContentLoadTypes::generate(&get_types_in(storage)?),
+            &get_general_verifier_in(storage)?,
         ),
     }];
     TrDbCold::new()
-        .set_history(events_to_batch(&db_path, events)?)
+        .set_history(events_to_batch_in(storage, events)?)
         // add history
         .set_settings(settings_batch)
         // upd settings
-        .apply(&db_path)
+        .apply_in(storage)
+}
+
+/// Get all named contacts from the database, as account id and
+/// [`ContactDetails`] tuples.
+#[cfg(feature = "signer")]
+pub fn get_contacts<P>(db_path: P) -> Result<Vec<([u8; 32], ContactDetails)>>
+where
+    P: AsRef<Path>,
+{
+    get_contacts_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`get_contacts`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn get_contacts_in<S>(storage: &S) -> Result<Vec<([u8; 32], ContactDetails)>>
+where
+    S: Storage,
+{
+    let mut out = Vec::new();
+    for x in storage.iter(CONTACTS)? {
+        out.push(ContactDetails::process_entry(sled_entry(x))?);
+    }
+    Ok(out)
+}
+
+/// Get account id from contact SS58 address, in any network format.
+#[cfg(feature = "signer")]
+fn contact_account_id(address: &str) -> Result<[u8; 32]> {
+    AccountId32::from_ss58check(address)
+        .map(<[u8; 32]>::from)
+        .map_err(|_| Error::ContactAddressInvalid {
+            address: address.to_string(),
+        })
+}
+
+/// Add named contact entered manually by the user.
+///
+/// Contact address is SS58 address, in any network format. If the contact
+/// with same account id is already in the database, it gets renamed.
+///
+/// Manually entered contacts have no verifier, and are recorded in history
+/// log as `Event::ContactAdded(_)` with `Verifier { v: None }`.
+#[cfg(feature = "signer")]
+pub fn add_contact<P>(db_path: P, name: &str, address: &str) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    add_contact_in(&SledStorage::open(db_path)?, name, address)
+}
+
+/// Same as [`add_contact`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn add_contact_in<S>(storage: &S, name: &str, address: &str) -> Result<()>
+where
+    S: Storage,
+{
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(Error::ContactNameEmpty);
+    }
+    let contact = ContactEntry {
+        account_id: contact_account_id(address)?,
+        name: name.to_string(),
+    };
+    let verifier = Verifier { v: None };
+    let mut contacts_batch = Batch::default();
+    contacts_batch.insert(
+        contact.account_id.to_vec(),
+        ContactDetails {
+            name: contact.name.to_owned(),
+            verifier: verifier.to_owned(),
+        }
+        .encode(),
+    );
+    let events = vec![Event::ContactAdded {
+        contact_display: ContactDisplay::get(&contact, &verifier),
+    }];
+    TrDbCold::new()
+        .set_contacts(contacts_batch) // add contact
+        .set_history(events_to_batch_in(storage, events)?) // add history
+        .apply_in(storage)
+}
+
+/// Remove named contact by its SS58 address, in any network format.
+///
+/// Removal is recorded in history log as `Event::ContactRemoved(_)`.
+#[cfg(feature = "signer")]
+pub fn remove_contact<P>(db_path: P, address: &str) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    remove_contact_in(&SledStorage::open(db_path)?, address)
+}
+
+/// Same as [`remove_contact`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn remove_contact_in<S>(storage: &S, address: &str) -> Result<()>
+where
+    S: Storage,
+{
+    let account_id = contact_account_id(address)?;
+    let contact_details = {
+        let contact_details_encoded = storage
+            .get(CONTACTS, &account_id)?
+            .ok_or(Error::ContactNotFound { account_id })?;
+        ContactDetails::decode(&mut &contact_details_encoded[..])?
+    };
+    let mut contacts_batch = Batch::default();
+    contacts_batch.remove(account_id.to_vec());
+    let events = vec![Event::ContactRemoved {
+        contact_display: ContactDisplay::get(
+            &ContactEntry {
+                account_id,
+                name: contact_details.name,
+            },
+            &contact_details.verifier,
+        ),
+    }];
+    TrDbCold::new()
+        .set_contacts(contacts_batch) // remove contact
+        .set_history(events_to_batch_in(storage, events)?) // add history
+        .apply_in(storage)
+}
+
+/// Get [`KeyAnnotation`] for the address key from the database, if the user
+/// has annotated the key.
+#[cfg(feature = "signer")]
+pub fn try_get_key_annotation<P>(
+    db_path: P,
+    address_key: &AddressKey,
+) -> Result<Option<KeyAnnotation>>
+where
+    P: AsRef<Path>,
+{
+    try_get_key_annotation_in(&SledStorage::open(db_path)?, address_key)
+}
+
+/// Same as [`try_get_key_annotation`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn try_get_key_annotation_in<S>(
+    storage: &S,
+    address_key: &AddressKey,
+) -> Result<Option<KeyAnnotation>>
+where
+    S: Storage,
+{
+    storage
+        .get(KEY_ANNOTATIONS, &address_key.key())?
+        .map(|key_annotation_encoded| -> Result<KeyAnnotation> {
+            Ok(KeyAnnotation::decode(&mut &key_annotation_encoded[..])?)
+        })
+        .transpose()
+}
+
+/// Get all address key annotations from the database, as [`AddressKey`] and
+/// [`KeyAnnotation`] tuples.
+#[cfg(feature = "signer")]
+pub fn get_key_annotations<P>(db_path: P) -> Result<Vec<(AddressKey, KeyAnnotation)>>
+where
+    P: AsRef<Path>,
+{
+    get_key_annotations_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`get_key_annotations`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn get_key_annotations_in<S>(storage: &S) -> Result<Vec<(AddressKey, KeyAnnotation)>>
+where
+    S: Storage,
+{
+    let mut out = Vec::new();
+    for x in storage.iter(KEY_ANNOTATIONS)? {
+        out.push(KeyAnnotation::process_entry(sled_entry(x))?);
+    }
+    Ok(out)
+}
+
+/// Modify [`KeyAnnotation`] of the address key and store it in the database.
+///
+/// Address key must be in the database. Annotation left with no content is
+/// removed from the database. Annotation change is recorded in the history
+/// log.
+#[cfg(feature = "signer")]
+fn update_key_annotation<S, F>(storage: &S, multisigner: &MultiSigner, update: F) -> Result<()>
+where
+    S: Storage,
+    F: FnOnce(&mut KeyAnnotation),
+{
+    let address_key = AddressKey::from_multisigner(multisigner);
+    let address_details = get_address_details_in(storage, &address_key)?;
+    let old_key_annotation = try_get_key_annotation_in(storage, &address_key)?.unwrap_or_default();
+    let mut key_annotation = old_key_annotation.to_owned();
+    update(&mut key_annotation);
+    if key_annotation == old_key_annotation {
+        return Ok(());
+    }
+    let mut key_annotations_batch = Batch::default();
+    if key_annotation.is_empty() {
+        key_annotations_batch.remove(address_key.key())
+    } else {
+        key_annotations_batch.insert(address_key.key(), key_annotation.encode())
+    }
+    let events = vec![Event::KeyAnnotationChanged {
+        key_annotation_history: KeyAnnotationHistory::get(
+            &address_details.seed_name,
+            &address_details.encryption,
+            &multisigner_to_public(multisigner),
+            &address_details.path,
+            &key_annotation,
+        ),
+    }];
+    TrDbCold::new()
+        .set_history(events_to_batch_in(storage, events)?) // add corresponding history
+        .set_key_annotations(key_annotations_batch) // upd key annotation
+        .apply_in(storage)
+}
+
+/// [`Event::KeyAnnotationChanged`] with empty annotation, recording that the
+/// annotation of the address key is removed together with the address key.
+#[cfg(feature = "signer")]
+pub(crate) fn key_annotation_removed_event(
+    multisigner: &MultiSigner,
+    address_details: &AddressDetails,
+) -> Event {
+    Event::KeyAnnotationChanged {
+        key_annotation_history: KeyAnnotationHistory::get(
+            &address_details.seed_name,
+            &address_details.encryption,
+            &multisigner_to_public(multisigner),
+            &address_details.path,
+            &KeyAnnotation::default(),
+        ),
+    }
+}
+
+/// Set user label for the address key. Empty label removes the existing one.
+#[cfg(feature = "signer")]
+pub fn set_key_label<P>(db_path: P, multisigner: &MultiSigner, label: &str) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    set_key_label_in(&SledStorage::open(db_path)?, multisigner, label)
+}
+
+/// Same as [`set_key_label`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn set_key_label_in<S>(storage: &S, multisigner: &MultiSigner, label: &str) -> Result<()>
+where
+    S: Storage,
+{
+    update_key_annotation(storage, multisigner, |key_annotation| {
+        key_annotation.label = label.trim().to_string()
+    })
+}
+
+/// Set user tags for the address key from comma-separated input, replacing
+/// the existing ones. Empty input removes all tags.
+#[cfg(feature = "signer")]
+pub fn set_key_tags<P>(db_path: P, multisigner: &MultiSigner, tags: &str) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    set_key_tags_in(&SledStorage::open(db_path)?, multisigner, tags)
+}
+
+/// Same as [`set_key_tags`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn set_key_tags_in<S>(storage: &S, multisigner: &MultiSigner, tags: &str) -> Result<()>
+where
+    S: Storage,
+{
+    update_key_annotation(storage, multisigner, |key_annotation| {
+        key_annotation.tags = KeyAnnotation::tags_from_str(tags)
+    })
+}
+
+/// Set user notes for the address key. Empty notes remove the existing ones.
+#[cfg(feature = "signer")]
+pub fn set_key_notes<P>(db_path: P, multisigner: &MultiSigner, notes: &str) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    set_key_notes_in(&SledStorage::open(db_path)?, multisigner, notes)
+}
+
+/// Same as [`set_key_notes`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn set_key_notes_in<S>(storage: &S, multisigner: &MultiSigner, notes: &str) -> Result<()>
+where
+    S: Storage,
+{
+    update_key_annotation(storage, multisigner, |key_annotation| {
+        key_annotation.notes = notes.trim().to_string()
+    })
 }
 
 /// Modify existing batch for [`ADDRTREE`](constants::ADDRTREE) with incoming
@@ -712,7 +1299,7 @@ where
 #[cfg(any(feature = "active", feature = "signer"))]
 pub(crate) fn upd_id_batch(mut batch: Batch, adds: Vec<(AddressKey, AddressDetails)>) -> Batch {
     for (address_key, address_details) in adds.iter() {
-        batch.insert(address_key.key(), address_details.encode());
+        batch.insert(address_key.key(), address_details.store());
     }
     batch
 }
@@ -723,25 +1310,74 @@ pub(crate) fn upd_id_batch(mut batch: Batch, adds: Vec<(AddressKey, AddressDetai
 /// [`TRANSACTION`](constants::TRANSACTION) tree of the database.
 // TODO Goes obsolete if the temporary storage goes.
 #[cfg(feature = "signer")]
-pub(crate) fn verify_checksum(database: &Db, checksum: u32) -> Result<()> {
-    let real_checksum = database.checksum()?;
+pub(crate) fn verify_checksum<S>(storage: &S, checksum: u32) -> Result<()>
+where
+    S: Storage,
+{
+    let real_checksum = storage.checksum()?;
     if checksum != real_checksum {
         return Err(Error::ChecksumMismatch);
     }
     Ok(())
 }
 
+/// Get [`DangerRecord`] from the Signer database.
+///
+/// Missing `DangerRecord` always indicates the database corruption.
+#[cfg(feature = "signer")]
+pub(crate) fn get_danger_record<P>(db_path: P) -> Result<DangerRecord>
+where
+    P: AsRef<Path>,
+{
+    get_danger_record_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`get_danger_record`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub(crate) fn get_danger_record_in<S>(storage: &S) -> Result<DangerRecord>
+where
+    S: Storage,
+{
+    let a = storage
+        .get(SETTREE, DANGER)?
+        .ok_or(Error::DangerStatusNotFound)?;
+    Ok(DangerRecord::from_ivec(&a.into()))
+}
+
 /// Get the danger status from the Signer database.
 ///
-/// Currently, the only flag contributing to the danger status is whether the
-/// device was online. This may change eventually.
+/// Signer is in danger if there is any exposure in the [`DangerRecord`].
 #[cfg(feature = "signer")]
 pub fn get_danger_status<P>(db_path: P) -> Result<bool>
 where
     P: AsRef<Path>,
 {
-    let database = open_db(&db_path)?;
-    let settings = open_tree(&database, SETTREE)?;
-    let a = settings.get(DANGER)?.ok_or(Error::DangerStatusNotFound)?;
-    Ok(DangerRecord::from_ivec(&a).device_was_online()?)
+    get_danger_status_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`get_danger_status`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn get_danger_status_in<S>(storage: &S) -> Result<bool>
+where
+    S: Storage,
+{
+    Ok(!get_danger_record_in(storage)?.is_safe()?)
+}
+
+/// Get exposures recorded in the [`DangerRecord`] of the Signer database.
+#[cfg(feature = "signer")]
+pub fn get_danger_exposures<P>(db_path: P) -> Result<Vec<ExposureRecord>>
+where
+    P: AsRef<Path>,
+{
+    get_danger_exposures_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`get_danger_exposures`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn get_danger_exposures_in<S>(storage: &S) -> Result<Vec<ExposureRecord>>
+where
+    S: Storage,
+{
+    Ok(get_danger_record_in(storage)?.exposures()?)
 }
@@ -16,13 +16,13 @@
 //! - [`SPECSTREEPREP`](constants::SPECSTREEPREP) with network specs entries
 //! [`NetworkSpecsToSend`](definitions::network_specs::NetworkSpecsToSend)
 use parity_scale_codec::Encode;
-use sled::Batch;
 
 use constants::TYPES;
 use defaults::{default_address_book, default_chainspecs_to_send, default_types_content};
 use definitions::keyring::{AddressBookKey, NetworkSpecsKey};
 
 use crate::db_transactions::TrDbHot;
+use crate::storage::{Batch, SledStorage, Storage};
 use crate::Result;
 
 /// Make [`Batch`] with default
@@ -49,7 +49,7 @@ fn default_hot_network_specs_prep() -> Result<Batch> {
     let mut batch = Batch::default();
     for x in default_chainspecs_to_send().iter() {
         let network_specs_key = NetworkSpecsKey::from_parts(&x.genesis_hash, &x.encryption);
-        batch.insert(network_specs_key.key(), x.encode());
+        batch.insert(network_specs_key.key(), x.store());
     }
     Ok(batch)
 }
@@ -82,9 +82,19 @@ where
     P: AsRef<std::path::Path>,
 {
     if std::fs::remove_dir_all(&db_path).is_ok() {}
+    reset_hot_database_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`reset_hot_database`], for the database in given [`Storage`].
+///
+/// Storage is expected to be empty.
+pub fn reset_hot_database_in<S>(storage: &S) -> Result<()>
+where
+    S: Storage,
+{
     TrDbHot::new()
         .set_address_book(default_hot_address_book()?) // set default address book
         .set_network_specs_prep(default_hot_network_specs_prep()?) // set default network specs
         .set_settings(default_hot_settings()?) // load default types
-        .apply(&db_path)
+        .apply_in(storage)
 }
@@ -33,11 +33,7 @@
 #[cfg(feature = "signer")]
 use bip39::{Language, Mnemonic, MnemonicType};
 use lazy_static::lazy_static;
-#[cfg(feature = "signer")]
-use parity_scale_codec::Encode;
 use regex::Regex;
-#[cfg(feature = "signer")]
-use sled::Batch;
 use sp_core::H256;
 #[cfg(any(feature = "active", feature = "signer"))]
 use sp_core::{ecdsa, ed25519, sr25519, Pair};
@@ -70,7 +66,8 @@ use definitions::{
 #[cfg(feature = "signer")]
 use definitions::{
     helpers::{make_identicon_from_multisigner, print_multisigner_as_base58},
-    navigation::{Address, MKeyDetails, MSCNetworkInfo},
+    navigation::{Address, MKeyDetails, MKeyUsage, MSCNetworkInfo},
+    policy::PolicyRule,
 };
 #[cfg(feature = "signer")]
 use qrcode_static::{png_qr_from_string, DataType};
@@ -78,14 +75,19 @@ use qrcode_static::{png_qr_from_string, DataType};
 #[cfg(any(feature = "active", feature = "signer"))]
 use crate::{
     db_transactions::TrDbCold,
-    helpers::{get_all_networks, make_batch_clear_tree, open_db, open_tree, upd_id_batch},
-    manage_history::events_to_batch,
+    helpers::{get_all_networks_in, make_batch_clear_tree_in, upd_id_batch},
+    manage_history::events_to_batch_in,
+    storage::{sled_entry, SledStorage, Storage},
 };
 #[cfg(feature = "signer")]
 use crate::{
     db_transactions::TrDbColdDerivations,
-    helpers::{get_address_details, get_network_specs},
-    interface_signer::addresses_set_seed_name_network,
+    helpers::{
+        enforce_policy_in, get_address_details_in, get_network_specs_in,
+        key_annotation_removed_event, try_get_key_annotation_in,
+    },
+    interface_signer::addresses_set_seed_name_network_in,
+    storage::Batch,
 };
 use crate::{Error, Result};
 
@@ -98,14 +100,12 @@ lazy_static! {
 
 /// Get all existing addresses from the database.
 #[cfg(any(feature = "active", feature = "signer"))]
-pub(crate) fn get_all_addresses<P>(db_path: P) -> Result<Vec<(MultiSigner, AddressDetails)>>
+pub(crate) fn get_all_addresses_in<S>(storage: &S) -> Result<Vec<(MultiSigner, AddressDetails)>>
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
-    let database = open_db(&db_path)?;
-    let identities = open_tree(&database, ADDRTREE)?;
     let mut out: Vec<(MultiSigner, AddressDetails)> = Vec::new();
-    for (address_key_vec, address_entry) in identities.iter().flatten() {
+    for (address_key_vec, address_entry) in storage.iter(ADDRTREE)?.into_iter().map(sled_entry) {
         let address_key = AddressKey::from_ivec(&address_key_vec);
         let (multisigner, address_details) =
             AddressDetails::process_entry_with_key_checked(&address_key, address_entry)?;
@@ -123,7 +123,20 @@ pub fn get_addresses_by_seed_name<P>(
 where
     P: AsRef<Path>,
 {
-    Ok(get_all_addresses(&db_path)?
+    get_addresses_by_seed_name_in(&SledStorage::open(db_path)?, seed_name)
+}
+
+/// Same as [`get_addresses_by_seed_name`], for the database in given
+/// [`Storage`].
+#[cfg(any(feature = "active", feature = "signer"))]
+pub fn get_addresses_by_seed_name_in<S>(
+    storage: &S,
+    seed_name: &str,
+) -> Result<Vec<(MultiSigner, AddressDetails)>>
+where
+    S: Storage,
+{
+    Ok(get_all_addresses_in(storage)?
         .into_iter()
         .filter(|(_, address_details)| address_details.seed_name == seed_name)
         .collect())
@@ -179,16 +192,16 @@ pub(crate) fn is_potentially_exposed(
 ///
 /// Input set is already filtered by seed name elsewhere.
 #[cfg(any(feature = "active", feature = "signer"))]
-fn has_parent_with_exposed_secret<P>(
+fn has_parent_with_exposed_secret<S>(
     new_cropped_path: &str,
     new_is_passworded: bool,
     seed_name: &str,
-    db_path: P,
+    storage: &S,
 ) -> Result<bool>
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
-    Ok(get_addresses_by_seed_name(db_path, seed_name)?
+    Ok(get_addresses_by_seed_name_in(storage, seed_name)?
         .iter()
         .any(|(_, address_details)| {
             address_details.secret_exposed
@@ -227,7 +240,7 @@ fn exposed_set(
 /// Data associated with address generation
 #[cfg(any(feature = "active", feature = "signer"))]
 pub(crate) struct PrepData {
-    /// information to be added into [`Batch`](sled::Batch) for [`ADDRTREE`]
+    /// information to be added into [`Batch`](crate::storage::Batch) for [`ADDRTREE`]
     /// update
     pub(crate) address_prep: Vec<(AddressKey, AddressDetails)>,
 
@@ -252,7 +265,7 @@ pub(crate) struct PrepData {
 /// The output [`PrepData`] contains:
 ///
 /// - `address_prep` set of `(AddressKey, AddressDetails)` values that
-/// eventually will be transformed into [`Batch`](sled::Batch) to update the
+/// eventually will be transformed into [`Batch`](crate::storage::Batch) to update the
 /// `ADDRTREE` of the cold database
 /// - output `Event` set that will be eventually added into `HISTORY` as a part
 /// of single `Entry`(definitions::history::Entry)
@@ -274,8 +287,8 @@ pub(crate) struct PrepData {
 /// [`sp_core::crypto`]. Combined secret string is then zeroized here regardless
 /// of the address generation success.
 #[cfg(any(feature = "active", feature = "signer"))]
-pub(crate) fn create_address<P>(
-    db_path: P,
+pub(crate) fn create_address_in<S>(
+    storage: &S,
     input_batch_prep: &[(AddressKey, AddressDetails)],
     path: &str,
     network_specs: &NetworkSpecs,
@@ -283,7 +296,7 @@ pub(crate) fn create_address<P>(
     seed_phrase: &str,
 ) -> Result<PrepData>
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
     // Check that the seed phrase is not empty.
     // In upstream, empty seed phrase means default Alice seed phrase.
@@ -424,16 +437,16 @@ where
             // `secret_exposed` flag will not be changed by extending key to
             // another network.
             let secret_exposed =
-                has_parent_with_exposed_secret(cropped_path, has_pwd, seed_name, &db_path)?;
+                has_parent_with_exposed_secret(cropped_path, has_pwd, seed_name, storage)?;
 
             // check if the `AddressKey` is already in the database
-            let database = open_db(&db_path)?;
-            let identities = open_tree(&database, ADDRTREE)?;
-            match identities.get(address_key.key()) {
+            match storage.get(ADDRTREE, &address_key.key())? {
                 // `AddressKey` is in the database
-                Ok(Some(address_entry)) => {
-                    let mut address_details =
-                        AddressDetails::from_entry_with_key_checked(&address_key, address_entry)?;
+                Some(address_entry) => {
+                    let mut address_details = AddressDetails::from_entry_with_key_checked(
+                        &address_key,
+                        address_entry.into(),
+                    )?;
 
                     // Even though the public key and `Encryption` are same
                     // (resulting in the same `AddressKey`), the path in
@@ -486,7 +499,7 @@ where
 
                 // `AddressKey` is not in the database either.
                 // Make altogether new entry.
-                Ok(None) => {
+                None => {
                     let address_details = AddressDetails {
                         seed_name: seed_name.to_string(),
                         path: cropped_path.to_string(),
@@ -494,6 +507,7 @@ where
                         network_id: vec![network_specs_key],
                         encryption: network_specs.encryption.to_owned(),
                         secret_exposed,
+                        usage: Vec::new(),
                     };
                     address_prep.push((address_key, address_details));
                     Ok(PrepData {
@@ -501,9 +515,6 @@ where
                         history_prep,
                     })
                 }
-
-                // database error
-                Err(e) => Err(e.into()),
             }
         }
     }
@@ -522,7 +533,7 @@ where
 /// The output [`PrepData`] contains:
 ///
 /// - `address_prep` set of `(AddressKey, AddressDetails)` values that
-/// eventually will be transformed into [`Batch`](sled::Batch) to update the
+/// eventually will be transformed into [`Batch`](crate::storage::Batch) to update the
 /// `ADDRTREE` of the cold database
 /// - output `Event` set that will be eventually added into `HISTORY` as a part
 /// of single `Entry`(definitions::history::Entry)
@@ -535,14 +546,14 @@ where
 /// This function inputs secret seed phrase as `&str`. It is passed as `&str`
 /// into `create_address` and used there.
 #[cfg(any(feature = "active", feature = "signer"))]
-fn populate_addresses<P>(
-    db_path: P,
+fn populate_addresses_in<S>(
+    storage: &S,
     seed_name: &str,
     seed_phrase: &str,
     make_seed_keys: bool,
 ) -> Result<PrepData>
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
     // Set of `(AddressKey, AddressDetails)` to be added into the database.
     // Set is updated and is used on each iteration of `create_address` to check
@@ -556,15 +567,15 @@ where
     // Collect all networks known to Signer.
     // Note: networks with all `Encryption` variants are used here if they are
     // in the Signer database.
-    let specs_set = get_all_networks(&db_path)?;
+    let specs_set = get_all_networks_in(storage)?;
 
     for network_specs in specs_set.iter() {
         // Make seed keys if requested.
         // Seed keys **must** be possible to generate,
         // if a seed key has a collision with some other key, it is an error
         if make_seed_keys {
-            let prep_data = create_address(
-                &db_path,
+            let prep_data = create_address_in(
+                storage,
                 &address_prep,
                 "",
                 network_specs,
@@ -578,8 +589,8 @@ where
         // make keys with default derivation if possible;
         // key with default derivation may collide with some other key,
         // this should not prevent generating a seed;
-        if let Ok(prep_data) = create_address(
-            &db_path,
+        if let Ok(prep_data) = create_address_in(
+            storage,
             &address_prep,
             &network_specs.path_id,
             network_specs,
@@ -617,17 +628,36 @@ pub fn try_create_seed<P>(
 ) -> Result<()>
 where
     P: AsRef<Path>,
+{
+    try_create_seed_in(
+        seed_name,
+        seed_phrase,
+        make_seed_keys,
+        &SledStorage::open(db_path)?,
+    )
+}
+
+/// Same as [`try_create_seed`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn try_create_seed_in<S>(
+    seed_name: &str,
+    seed_phrase: &str,
+    make_seed_keys: bool,
+    storage: &S,
+) -> Result<()>
+where
+    S: Storage,
 {
     let mut events: Vec<Event> = vec![Event::SeedCreated {
         seed_created: seed_name.to_string(),
     }];
 
-    let prep_data = populate_addresses(&db_path, seed_name, seed_phrase, make_seed_keys)?;
+    let prep_data = populate_addresses_in(storage, seed_name, seed_phrase, make_seed_keys)?;
     events.extend_from_slice(&prep_data.history_prep);
     TrDbCold::new()
         .set_addresses(upd_id_batch(Batch::default(), prep_data.address_prep)) // add addresses just made in populate_addresses
-        .set_history(events_to_batch(&db_path, events)?) // add corresponding history
-        .apply(&db_path)
+        .set_history(events_to_batch_in(storage, events)?) // add corresponding history
+        .apply_in(storage)
 }
 
 /// Remove address from the Signer database.
@@ -648,7 +678,20 @@ pub fn remove_key<P>(
 where
     P: AsRef<Path>,
 {
-    remove_keys_set(&db_path, &[multisigner.to_owned()], network_specs_key)
+    remove_key_in(&SledStorage::open(db_path)?, multisigner, network_specs_key)
+}
+
+/// Same as [`remove_key`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn remove_key_in<S>(
+    storage: &S,
+    multisigner: &MultiSigner,
+    network_specs_key: &NetworkSpecsKey,
+) -> Result<()>
+where
+    S: Storage,
+{
+    remove_keys_set_in(storage, &[multisigner.to_owned()], network_specs_key)
 }
 
 /// Remove a set of addresses within a single network from the Signer database.
@@ -669,14 +712,28 @@ pub fn remove_keys_set<P>(
 ) -> Result<()>
 where
     P: AsRef<Path>,
+{
+    remove_keys_set_in(&SledStorage::open(db_path)?, multiselect, network_specs_key)
+}
+
+/// Same as [`remove_keys_set`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn remove_keys_set_in<S>(
+    storage: &S,
+    multiselect: &[MultiSigner],
+    network_specs_key: &NetworkSpecsKey,
+) -> Result<()>
+where
+    S: Storage,
 {
     let mut id_batch = Batch::default();
+    let mut key_annotations_batch = Batch::default();
     let mut events: Vec<Event> = Vec::new();
-    let network_specs = get_network_specs(&db_path, network_specs_key)?;
+    let network_specs = get_network_specs_in(storage, network_specs_key)?;
     for multisigner in multiselect.iter() {
         let public_key = multisigner_to_public(multisigner);
         let address_key = AddressKey::from_multisigner(multisigner);
-        let mut address_details = get_address_details(&db_path, &address_key)?;
+        let mut address_details = get_address_details_in(storage, &address_key)?;
         let identity_history = IdentityHistory::get(
             &address_details.seed_name,
             &network_specs.encryption,
@@ -690,16 +747,24 @@ where
             .into_iter()
             .filter(|id| id != network_specs_key)
             .collect();
+        address_details
+            .usage
+            .retain(|key_usage| &key_usage.network_specs_key != network_specs_key);
         if address_details.network_id.is_empty() {
-            id_batch.remove(address_key.key())
+            if try_get_key_annotation_in(storage, &address_key)?.is_some() {
+                events.push(key_annotation_removed_event(multisigner, &address_details));
+            }
+            id_batch.remove(address_key.key());
+            key_annotations_batch.remove(address_key.key())
         } else {
-            id_batch.insert(address_key.key(), address_details.encode())
+            id_batch.insert(address_key.key(), address_details.store())
         }
     }
     TrDbCold::new()
         .set_addresses(id_batch) // modify existing address entries
-        .set_history(events_to_batch(&db_path, events)?) // add corresponding history
-        .apply(&db_path)
+        .set_history(events_to_batch_in(storage, events)?) // add corresponding history
+        .set_key_annotations(key_annotations_batch) // remove annotations of removed keys
+        .apply_in(storage)
 }
 
 /// Add a set of new derived addresses: N+1, N+2, etc into Signer database.
@@ -767,11 +832,32 @@ pub fn create_increment_set<P>(
 ) -> Result<()>
 where
     P: AsRef<Path>,
+{
+    create_increment_set_in(
+        increment,
+        multisigner,
+        network_specs_key,
+        seed_phrase,
+        &SledStorage::open(db_path)?,
+    )
+}
+
+/// Same as [`create_increment_set`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn create_increment_set_in<S>(
+    increment: u32,
+    multisigner: &MultiSigner,
+    network_specs_key: &NetworkSpecsKey,
+    seed_phrase: &str,
+    storage: &S,
+) -> Result<()>
+where
+    S: Storage,
 {
     let address_details =
-        get_address_details(&db_path, &AddressKey::from_multisigner(multisigner))?;
+        get_address_details_in(storage, &AddressKey::from_multisigner(multisigner))?;
     let existing_identities =
-        addresses_set_seed_name_network(&db_path, &address_details.seed_name, network_specs_key)?;
+        addresses_set_seed_name_network_in(storage, &address_details.seed_name, network_specs_key)?;
     let mut last_index = 0;
     for (_, details) in existing_identities.iter() {
         if let Some(("", suffix)) = details.path.split_once(&address_details.path) {
@@ -782,13 +868,13 @@ where
             }
         }
     }
-    let network_specs = get_network_specs(&db_path, network_specs_key)?;
+    let network_specs = get_network_specs_in(storage, network_specs_key)?;
     let mut identity_adds: Vec<(AddressKey, AddressDetails)> = Vec::new();
     let mut current_events: Vec<Event> = Vec::new();
     for i in 0..increment {
         let path = address_details.path.to_string() + "//" + &(last_index + i).to_string();
-        let prep_data = create_address(
-            &db_path,
+        let prep_data = create_address_in(
+            storage,
             &identity_adds,
             &path,
             &network_specs,
@@ -801,8 +887,8 @@ where
     let id_batch = upd_id_batch(Batch::default(), identity_adds);
     TrDbCold::new()
         .set_addresses(id_batch) // add created addresses
-        .set_history(events_to_batch(&db_path, current_events)?) // add corresponding history
-        .apply(&db_path)
+        .set_history(events_to_batch_in(storage, current_events)?) // add corresponding history
+        .apply_in(storage)
 }
 
 /// Check derivation format and determine if it has a password.
@@ -873,6 +959,25 @@ pub fn derivation_check<P>(
 ) -> Result<DerivationCheck>
 where
     P: AsRef<Path>,
+{
+    derivation_check_in(
+        seed_name,
+        path,
+        network_specs_key,
+        &SledStorage::open(db_path)?,
+    )
+}
+
+/// Same as [`derivation_check`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn derivation_check_in<S>(
+    seed_name: &str,
+    path: &str,
+    network_specs_key: &NetworkSpecsKey,
+    storage: &S,
+) -> Result<DerivationCheck>
+where
+    S: Storage,
 {
     match is_passworded(path) {
         // Proposed derivation has password, no checks could be made, proceed.
@@ -882,7 +987,7 @@ where
         // coincidence.
         Ok(false) => {
             let mut found_exact = None;
-            for (multisigner, address_details) in get_all_addresses(db_path)?.into_iter() {
+            for (multisigner, address_details) in get_all_addresses_in(storage)?.into_iter() {
                 if (address_details.seed_name == seed_name) // seed name
                     && (address_details.path == path) // derivation path, cropped part without password
                     && (address_details.network_id.contains(network_specs_key)) // in this network
@@ -954,7 +1059,28 @@ pub fn try_create_address<P>(
 where
     P: AsRef<Path>,
 {
-    match derivation_check(seed_name, path, network_specs_key, &db_path)? {
+    try_create_address_in(
+        seed_name,
+        seed_phrase,
+        path,
+        network_specs_key,
+        &SledStorage::open(db_path)?,
+    )
+}
+
+/// Same as [`try_create_address`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn try_create_address_in<S>(
+    seed_name: &str,
+    seed_phrase: &str,
+    path: &str,
+    network_specs_key: &NetworkSpecsKey,
+    storage: &S,
+) -> Result<()>
+where
+    S: Storage,
+{
+    match derivation_check_in(seed_name, path, network_specs_key, storage)? {
         // UI should prevent user from getting into `try_create_address` if
         // derivation has a bad format
         DerivationCheck::BadFormat => Err(Error::InvalidDerivation(path.to_string())),
@@ -976,9 +1102,9 @@ where
         // coincidence (for passworded derivation) could not have been checked
         // preliminarily and would result in `create_address` errors here.
         _ => {
-            let network_specs = get_network_specs(&db_path, network_specs_key)?;
-            let prep_data = create_address(
-                &db_path,
+            let network_specs = get_network_specs_in(storage, network_specs_key)?;
+            let prep_data = create_address_in(
+                storage,
                 &Vec::new(), // a single address is created, no data to check against here
                 path,
                 &network_specs,
@@ -988,8 +1114,8 @@ where
             let id_batch = upd_id_batch(Batch::default(), prep_data.address_prep);
             TrDbCold::new()
                 .set_addresses(id_batch) // add created address
-                .set_history(events_to_batch(&db_path, prep_data.history_prep)?) // add corresponding history
-                .apply(&db_path)
+                .set_history(events_to_batch_in(storage, prep_data.history_prep)?) // add corresponding history
+                .apply_in(storage)
         }
     }
 }
@@ -1006,15 +1132,25 @@ where
 pub fn generate_test_identities<P>(db_path: P) -> Result<()>
 where
     P: AsRef<Path>,
+{
+    generate_test_identities_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`generate_test_identities`], for the database in given
+/// [`Storage`].
+#[cfg(feature = "active")]
+pub fn generate_test_identities_in<S>(storage: &S) -> Result<()>
+where
+    S: Storage,
 {
     // clear the tree
-    let entry_batch = make_batch_clear_tree(&db_path, ADDRTREE)?;
+    let entry_batch = make_batch_clear_tree_in(storage, ADDRTREE)?;
 
     // make a record that the tree was wiped
     let mut events = vec![Event::IdentitiesWiped];
 
     // data for adding seed addresses and addresses with default derivation path
-    let prep_data = populate_addresses(&db_path, "Alice", ALICE_SEED_PHRASE, true)?;
+    let prep_data = populate_addresses_in(storage, "Alice", ALICE_SEED_PHRASE, true)?;
 
     // Address preparation set, to be used as following `create_address` input.
     // Alice addresses are known and good, so checking them for collisions is
@@ -1024,12 +1160,12 @@ where
     // update events
     events.extend_from_slice(&prep_data.history_prep);
 
-    for network_specs in get_all_networks(&db_path)?.iter() {
+    for network_specs in get_all_networks_in(storage)?.iter() {
         if (network_specs.name == "westend") && (network_specs.encryption == Encryption::Sr25519) {
             // data for adding address with `//Alice` derivation path in Westend
             // network
-            let prep_data = create_address(
-                &db_path,
+            let prep_data = create_address_in(
+                storage,
                 &address_prep, // address
                 "//Alice",
                 network_specs,
@@ -1043,8 +1179,8 @@ where
 
     TrDbCold::new()
         .set_addresses(upd_id_batch(entry_batch, address_prep)) // add created addresses
-        .set_history(events_to_batch(&db_path, events)?) // add corresponding history
-        .apply(&db_path)
+        .set_history(events_to_batch_in(storage, events)?) // add corresponding history
+        .apply_in(storage)
 }
 
 /// Remove all addresses associated with given seed name from the Signer
@@ -1056,12 +1192,22 @@ where
 pub fn remove_seed<P>(db_path: P, seed_name: &str) -> Result<()>
 where
     P: AsRef<Path>,
+{
+    remove_seed_in(&SledStorage::open(db_path)?, seed_name)
+}
+
+/// Same as [`remove_seed`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn remove_seed_in<S>(storage: &S, seed_name: &str) -> Result<()>
+where
+    S: Storage,
 {
     // `Batch` to use
     let mut identity_batch = Batch::default();
+    let mut key_annotations_batch = Batch::default();
 
     // All addresses with given seed name from the database
-    let id_set = get_addresses_by_seed_name(&db_path, seed_name)?;
+    let id_set = get_addresses_by_seed_name_in(storage, seed_name)?;
     if id_set.is_empty() {
         return Err(Error::NoKnownSeeds);
     }
@@ -1076,6 +1222,7 @@ where
 
         // removal of all addresses corresponging to `AddressKey`
         identity_batch.remove(address_key.key());
+        key_annotations_batch.remove(address_key.key());
 
         let public_key = multisigner_to_public(multisigner);
         for network_specs_key in address_details.network_id.iter() {
@@ -1090,11 +1237,15 @@ where
             // separate `Event` for each `NetworkSpecsKey` from `network_id` set
             events.push(Event::IdentityRemoved { identity_history });
         }
+        if try_get_key_annotation_in(storage, &address_key)?.is_some() {
+            events.push(key_annotation_removed_event(multisigner, address_details));
+        }
     }
     TrDbCold::new()
         .set_addresses(identity_batch) // modify addresses
-        .set_history(events_to_batch(&db_path, events)?) // add corresponding history
-        .apply(&db_path)
+        .set_history(events_to_batch_in(storage, events)?) // add corresponding history
+        .set_key_annotations(key_annotations_batch) // remove annotations of removed keys
+        .apply_in(storage)
 }
 
 /// Create a set of addresses using imported derivations set for user-selected
@@ -1127,9 +1278,28 @@ pub fn import_derivations<P>(
 ) -> Result<()>
 where
     P: AsRef<Path>,
+{
+    import_derivations_in(
+        checksum,
+        seed_name,
+        seed_phrase,
+        &SledStorage::open(db_path)?,
+    )
+}
+
+/// Same as [`import_derivations`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn import_derivations_in<S>(
+    checksum: u32,
+    seed_name: &str,
+    seed_phrase: &str,
+    storage: &S,
+) -> Result<()>
+where
+    S: Storage,
 {
     // derivations data retrieved from the database
-    let content_derivations = TrDbColdDerivations::from_storage(&db_path, checksum)?;
+    let content_derivations = TrDbColdDerivations::from_storage_in(storage, checksum)?;
 
     // [`NetworkSpecs`] for the network in which the addresses are generated
     let network_specs = content_derivations.network_specs();
@@ -1143,7 +1313,7 @@ where
 
     for path in content_derivations.checked_derivations().iter() {
         // try creating address for each of the derivations
-        match create_address(&db_path, &adds, path, network_specs, seed_name, seed_phrase) {
+        match create_address_in(storage, &adds, path, network_specs, seed_name, seed_phrase) {
             // success, updating address preparation set and `Event` set
             Ok(prep_data) => {
                 adds = prep_data.address_prep;
@@ -1159,9 +1329,9 @@ where
     }
     TrDbCold::new()
         .set_addresses(upd_id_batch(Batch::default(), adds)) // modify addresses data
-        .set_history(events_to_batch(&db_path, events)?) // add corresponding history
-        .set_transaction(make_batch_clear_tree(&db_path, TRANSACTION)?) // clear transaction tree
-        .apply(&db_path)
+        .set_history(events_to_batch_in(storage, events)?) // add corresponding history
+        .set_transaction(make_batch_clear_tree_in(storage, TRANSACTION)?) // clear transaction tree
+        .apply_in(storage)
 }
 
 /// Check derivations before offering user to import them.
@@ -1245,6 +1415,205 @@ pub fn prepare_derivations_import(
     ))
 }
 
+/// Format of the structured derivations import source.
+#[cfg(feature = "active")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DerivationsFormat {
+    /// JSON array of objects, each with `path` string and `networks` array of
+    /// address book titles, for example:
+    ///
+    /// `[{"path": "//polkadot//0", "networks": ["polkadot", "kusama"]}]`
+    Json,
+
+    /// Lines `<path>,<address book title>[,<address book title>...]`, optional
+    /// header line starting with `path,` is skipped.
+    ///
+    /// Derivation path is the first field, so passwords containing commas
+    /// could not be used in this format.
+    Csv,
+}
+
+/// Reason why a row of the structured derivations import source was rejected.
+#[cfg(feature = "active")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DerivationRejection {
+    /// Row could not be read, for example JSON array element is not an object,
+    /// or `path` field is missing or is not a string.
+    BadFormat,
+
+    /// Derivation path is empty.
+    EmptyPath,
+
+    /// Derivation path has invalid format.
+    InvalidPath,
+
+    /// Row has no target networks.
+    NoNetworks,
+
+    /// Same derivation is already in import for the network.
+    Duplicate { network: String },
+}
+
+#[cfg(feature = "active")]
+impl std::fmt::Display for DerivationRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DerivationRejection::BadFormat => write!(f, "row could not be read"),
+            DerivationRejection::EmptyPath => write!(f, "empty derivation path"),
+            DerivationRejection::InvalidPath => write!(f, "invalid derivation path"),
+            DerivationRejection::NoNetworks => write!(f, "no target networks"),
+            DerivationRejection::Duplicate { network } => {
+                write!(f, "derivation is already in import for {}", network)
+            }
+        }
+    }
+}
+
+/// Rejected row of the structured derivations import source.
+///
+/// Row content is not kept, as it may contain derivation password.
+#[cfg(feature = "active")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RejectedDerivation {
+    /// Row number, starting from 1: line number in CSV source, array element
+    /// number in JSON source.
+    pub row: usize,
+
+    /// Rejection reason
+    pub reason: DerivationRejection,
+}
+
+/// Derivations from the structured import source, grouped by target network.
+#[cfg(feature = "active")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DerivationsImport {
+    /// Network address book titles with derivations to be imported, in order
+    /// of first appearance in the source.
+    pub networks: Vec<(String, Vec<String>)>,
+
+    /// All rejected rows.
+    pub rejected: Vec<RejectedDerivation>,
+}
+
+#[cfg(feature = "active")]
+impl DerivationsImport {
+    /// Add derivation for the network, rejecting duplicates.
+    fn add(&mut self, path: &str, network: &str) -> std::result::Result<(), DerivationRejection> {
+        match self.networks.iter_mut().find(|(title, _)| title == network) {
+            Some((_, derivations)) => {
+                if derivations.iter().any(|x| x == path) {
+                    return Err(DerivationRejection::Duplicate {
+                        network: network.to_string(),
+                    });
+                }
+                derivations.push(path.to_string())
+            }
+            None => self
+                .networks
+                .push((network.to_string(), vec![path.to_string()])),
+        }
+        Ok(())
+    }
+
+    /// Process single row, with derivation path and target networks.
+    fn process_row(&mut self, row: usize, path: &str, networks: &[String]) {
+        if path.is_empty() {
+            self.rejected.push(RejectedDerivation {
+                row,
+                reason: DerivationRejection::EmptyPath,
+            });
+            return;
+        }
+        if REG_PATH.captures(path).is_none() {
+            self.rejected.push(RejectedDerivation {
+                row,
+                reason: DerivationRejection::InvalidPath,
+            });
+            return;
+        }
+        if networks.is_empty() {
+            self.rejected.push(RejectedDerivation {
+                row,
+                reason: DerivationRejection::NoNetworks,
+            });
+            return;
+        }
+        for network in networks.iter() {
+            if let Err(reason) = self.add(path, network) {
+                self.rejected.push(RejectedDerivation { row, reason })
+            }
+        }
+    }
+}
+
+/// Prepare derivations import from the structured source with per-network
+/// targets.
+///
+/// Each row of the source names a derivation path and one or more target
+/// networks, by network address book title. Every rejected row is reported in
+/// [`DerivationsImport`] with a reason, valid rows are grouped by network, so
+/// that one [`ContentDerivations`] could be generated for each network.
+///
+/// Source that could not be read at all, for example JSON that is not an
+/// array, results in an error. Source with no valid derivations is not an
+/// error here, so that the caller could report the rejected rows first.
+#[cfg(feature = "active")]
+pub fn prepare_structured_derivations_import(
+    content: &str,
+    format: DerivationsFormat,
+) -> Result<DerivationsImport> {
+    let mut derivations_import = DerivationsImport {
+        networks: Vec::new(),
+        rejected: Vec::new(),
+    };
+    match format {
+        DerivationsFormat::Json => {
+            let rows = match serde_json::from_str::<serde_json::Value>(content) {
+                Ok(serde_json::Value::Array(rows)) => rows,
+                _ => return Err(Error::DerivationsImportNotReadable),
+            };
+            for (i, x) in rows.iter().enumerate() {
+                let row = i + 1;
+                let path = x.get("path").and_then(|a| a.as_str());
+                let networks: Option<Vec<String>> = match x.get("networks") {
+                    Some(serde_json::Value::Array(a)) => a
+                        .iter()
+                        .map(|b| b.as_str().map(|c| c.to_string()))
+                        .collect(),
+                    None => Some(Vec::new()),
+                    _ => None,
+                };
+                match (path, networks) {
+                    (Some(path), Some(networks)) => {
+                        derivations_import.process_row(row, path, &networks)
+                    }
+                    _ => derivations_import.rejected.push(RejectedDerivation {
+                        row,
+                        reason: DerivationRejection::BadFormat,
+                    }),
+                }
+            }
+        }
+        DerivationsFormat::Csv => {
+            for (i, line) in content.lines().enumerate() {
+                let row = i + 1;
+                let line = line.trim();
+                if line.is_empty() || (row == 1 && line.starts_with("path,")) {
+                    continue;
+                }
+                let mut fields = line.split(',').map(|a| a.trim());
+                let path = fields.next().unwrap_or_default();
+                let networks: Vec<String> = fields
+                    .filter(|a| !a.is_empty())
+                    .map(|a| a.to_string())
+                    .collect();
+                derivations_import.process_row(row, path, &networks)
+            }
+        }
+    }
+    Ok(derivations_import)
+}
+
 #[cfg(feature = "signer")]
 fn prepare_secret_key_for_export(
     multisigner: &MultiSigner,
@@ -1294,6 +1663,9 @@ fn prepare_secret_key_for_export(
 /// The QR code here contains sensitive information, and is made with special
 /// coloration, so that the difference with safe QR codes is immediately visible
 /// on screen.
+///
+/// Export is rejected if forbidden by the Signer
+/// [`SecurityPolicy`](definitions::policy::SecurityPolicy).
 #[cfg(feature = "signer")]
 pub fn export_secret_key<P>(
     db_path: P,
@@ -1306,12 +1678,36 @@ pub fn export_secret_key<P>(
 where
     P: AsRef<Path>,
 {
+    export_secret_key_in(
+        &SledStorage::open(db_path)?,
+        public_key,
+        expected_seed_name,
+        network_specs_key_hex,
+        seed_phrase,
+        key_password,
+    )
+}
+
+/// Same as [`export_secret_key`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn export_secret_key_in<S>(
+    storage: &S,
+    public_key: &str,
+    expected_seed_name: &str,
+    network_specs_key_hex: &str,
+    seed_phrase: &str,
+    mut key_password: Option<String>,
+) -> Result<MKeyDetails>
+where
+    S: Storage,
+{
+    enforce_policy_in(PolicyRule::SecretExport, storage)?;
     let public_key = &unhex(public_key)?;
     let network_specs_key = &NetworkSpecsKey::from_hex(network_specs_key_hex)?;
-    let network_specs = get_network_specs(&db_path, network_specs_key)?;
+    let network_specs = get_network_specs_in(storage, network_specs_key)?;
     let multisigner = &get_multisigner(public_key, &network_specs.encryption)?;
     let address_key = AddressKey::from_multisigner(multisigner);
-    let address_details = get_address_details(&db_path, &address_key)?;
+    let address_details = get_address_details_in(storage, &address_key)?;
     if address_details.seed_name != expected_seed_name {
         return Err(Error::SeedNameNotMatching {
             address_key,
@@ -1333,7 +1729,11 @@ where
         seed_name: address_details.seed_name.to_string(),
         multiselect: None,
         secret_exposed: true,
+        annotation: try_get_key_annotation_in(storage, &address_key)?,
     };
+    let usage = address_details
+        .usage(network_specs_key)
+        .map(MKeyUsage::from);
 
     let network_info = MSCNetworkInfo {
         network_title: network_specs.title,
@@ -1341,7 +1741,7 @@ where
         network_specs_key: network_specs_key_hex.to_owned(),
     };
 
-    let database_addresses = get_addresses_by_seed_name(&db_path, expected_seed_name)?;
+    let database_addresses = get_addresses_by_seed_name_in(storage, expected_seed_name)?;
 
     let exposed_addresses = exposed_set(
         &address_details.path,
@@ -1356,12 +1756,12 @@ where
         new_address_details.secret_exposed = true;
         identity_batch.insert(
             AddressKey::from_multisigner(&x_multisigner).key(),
-            new_address_details.encode(),
+            new_address_details.store(),
         )
     }
 
-    let history_batch = events_to_batch(
-        &db_path,
+    let history_batch = events_to_batch_in(
+        storage,
         vec![Event::SecretWasExported {
             identity_history: IdentityHistory::get(
                 &address_details.seed_name,
@@ -1385,7 +1785,7 @@ where
     if let Err(e) = TrDbCold::new()
         .set_addresses(identity_batch) // modify addresses
         .set_history(history_batch) // add corresponding history
-        .apply(&db_path)
+        .apply_in(storage)
     {
         qr.zeroize();
         return Err(e);
@@ -1396,6 +1796,7 @@ where
         pubkey: hex::encode(public_key),
         network_info,
         address,
+        usage,
     })
 }
 
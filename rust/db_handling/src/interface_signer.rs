@@ -1,26 +1,30 @@
 //! Utils to communicate with the Signer frontend
 use bip39::{Language, Mnemonic};
 use hex;
-use parity_scale_codec::Encode;
 use plot_icon::EMPTY_PNG;
-use sp_core::{blake2_256, sr25519, Pair};
+use sp_core::{
+    blake2_256,
+    crypto::{AccountId32, Ss58Codec},
+    sr25519, Pair,
+};
 use sp_runtime::MultiSigner;
 use std::collections::HashMap;
 use std::path::Path;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
-use constants::{HISTORY, MAX_WORDS_DISPLAY, TRANSACTION};
+use constants::{MAX_WORDS_DISPLAY, TRANSACTION};
 use definitions::{
     helpers::{
-        make_identicon_from_multisigner, multisigner_to_encryption, multisigner_to_public,
-        pic_meta, print_multisigner_as_base58,
+        make_identicon_from_account, make_identicon_from_multisigner, multisigner_to_encryption,
+        multisigner_to_public, pic_meta, print_multisigner_as_base58,
     },
     keyring::{AddressKey, NetworkSpecsKey, VerifierKey},
     navigation::{
         Address, DerivationCheck as NavDerivationCheck, DerivationDestination, DerivationEntry,
-        DerivationPack, MBackup, MDeriveKey, MKeyDetails, MKeysCard, MMMNetwork, MMNetwork,
-        MManageMetadata, MMetadataRecord, MNetworkDetails, MNetworkMenu, MNewSeedBackup, MRawKey,
-        MSCNetworkInfo, MSeedKeyCard, MTypesInfo, MVerifier, Network, SeedNameCard,
+        DerivationPack, MBackup, MDeriveKey, MKeyDetails, MKeyUsage, MKeysCard, MMMNetwork,
+        MMNetwork, MManageMetadata, MMetadataRecord, MNetworkDetails, MNetworkMenu, MNewSeedBackup,
+        MRawKey, MSCContact, MSCNetworkInfo, MSeedKeyCard, MTypesInfo, MVerifier, Network,
+        SeedNameCard,
     },
     network_specs::{NetworkSpecs, ValidCurrentVerifier},
     qr_transfers::ContentLoadTypes,
@@ -29,15 +33,17 @@ use definitions::{
 use qrcode_static::{png_qr_from_string, DataType};
 
 use crate::helpers::{
-    get_address_details, get_all_networks, get_general_verifier, get_meta_values_by_name,
-    get_meta_values_by_name_version, get_network_specs, make_batch_clear_tree, open_db, open_tree,
-    try_get_types,
+    get_address_details_in, get_all_networks_in, get_contacts_in, get_general_verifier_in,
+    get_key_annotations_in, get_meta_values_by_name_in, get_meta_values_by_name_version_in,
+    get_network_specs_in, make_batch_clear_tree_in, try_get_key_annotation_in, try_get_types_in,
 };
 use crate::identities::{
-    derivation_check, generate_random_phrase, get_addresses_by_seed_name, get_all_addresses,
-    DerivationCheck,
+    derivation_check_in, generate_random_phrase, get_addresses_by_seed_name_in,
+    get_all_addresses_in, DerivationCheck,
 };
-use crate::{db_transactions::TrDbCold, helpers::get_valid_current_verifier};
+use crate::manage_history::history_chain_head_in;
+use crate::storage::{SledStorage, Storage};
+use crate::{db_transactions::TrDbCold, helpers::get_valid_current_verifier_in};
 use crate::{Error, Result};
 
 /// Return a `Vec` with all seed names with seed key identicons if seed key is
@@ -58,10 +64,22 @@ pub fn get_all_seed_names_with_identicons<P>(
 ) -> Result<Vec<SeedNameCard>>
 where
     P: AsRef<Path>,
+{
+    get_all_seed_names_with_identicons_in(&SledStorage::open(db_path)?, names_phone_knows)
+}
+
+/// Same as [`get_all_seed_names_with_identicons`], for the database in given
+/// [`Storage`].
+pub fn get_all_seed_names_with_identicons_in<S>(
+    storage: &S,
+    names_phone_knows: &[String],
+) -> Result<Vec<SeedNameCard>>
+where
+    S: Storage,
 {
     let mut data_set: HashMap<String, Vec<MultiSigner>> = HashMap::new();
     let mut derivation_count: HashMap<String, u32> = HashMap::new();
-    for (multisigner, address_details) in get_all_addresses(&db_path)?.into_iter() {
+    for (multisigner, address_details) in get_all_addresses_in(storage)?.into_iter() {
         if address_details.is_root() {
             // found a seed key; could be any of the supported encryptions;
             match data_set.get(&address_details.seed_name) {
@@ -155,7 +173,15 @@ pub fn print_all_identities<P>(db_path: P) -> Result<Vec<MRawKey>>
 where
     P: AsRef<Path>,
 {
-    Ok(get_all_addresses(db_path)?
+    print_all_identities_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`print_all_identities`], for the database in given [`Storage`].
+pub fn print_all_identities_in<S>(storage: &S) -> Result<Vec<MRawKey>>
+where
+    S: Storage,
+{
+    Ok(get_all_addresses_in(storage)?
         .into_iter()
         .map(|(multisigner, address_details)| {
             let address_key = AddressKey::from_multisigner(&multisigner); // to click
@@ -192,8 +218,30 @@ pub fn print_identities_for_seed_name_and_network<P>(
 where
     P: AsRef<Path>,
 {
-    let network_specs = get_network_specs(&db_path, network_specs_key)?;
-    let identities = addresses_set_seed_name_network(&db_path, seed_name, network_specs_key)?;
+    print_identities_for_seed_name_and_network_in(
+        &SledStorage::open(db_path)?,
+        seed_name,
+        network_specs_key,
+        swiped_key,
+        multiselect,
+    )
+}
+
+/// Same as [`print_identities_for_seed_name_and_network`], for the database in given
+/// [`Storage`].
+pub fn print_identities_for_seed_name_and_network_in<S>(
+    storage: &S,
+    seed_name: &str,
+    network_specs_key: &NetworkSpecsKey,
+    swiped_key: Option<MultiSigner>,
+    multiselect: Vec<MultiSigner>,
+) -> Result<(MSeedKeyCard, Vec<MKeysCard>, String, String)>
+where
+    S: Storage,
+{
+    let network_specs = get_network_specs_in(storage, network_specs_key)?;
+    let identities = addresses_set_seed_name_network_in(storage, seed_name, network_specs_key)?;
+    let key_annotations = get_key_annotations_in(storage)?;
     let mut root_id = None;
     let mut other_id: Vec<(MultiSigner, AddressDetails, Vec<u8>, bool, bool)> = Vec::new();
     for (multisigner, address_details) in identities.into_iter() {
@@ -236,15 +284,29 @@ where
     let set: Vec<_> = other_id
         .into_iter()
         .map(
-            |(multisigner, address_details, identicon, swiped, multiselect)| MKeysCard {
-                address_key: hex::encode(AddressKey::from_multisigner(&multisigner).key()),
-                base58: print_multisigner_as_base58(&multisigner, Some(network_specs.base58prefix)),
-                identicon,
-                has_pwd: address_details.has_pwd,
-                path: address_details.path,
-                swiped,
-                multiselect,
-                secret_exposed: address_details.secret_exposed,
+            |(multisigner, address_details, identicon, swiped, multiselect)| {
+                let address_key = AddressKey::from_multisigner(&multisigner);
+                let annotation = key_annotations
+                    .iter()
+                    .find(|(annotated_key, _)| annotated_key == &address_key)
+                    .map(|(_, key_annotation)| key_annotation.to_owned());
+                MKeysCard {
+                    address_key: hex::encode(address_key.key()),
+                    base58: print_multisigner_as_base58(
+                        &multisigner,
+                        Some(network_specs.base58prefix),
+                    ),
+                    identicon,
+                    has_pwd: address_details.has_pwd,
+                    path: address_details.path,
+                    swiped,
+                    multiselect,
+                    secret_exposed: address_details.secret_exposed,
+                    usage: address_details
+                        .usage(network_specs_key)
+                        .map(MKeyUsage::from),
+                    annotation,
+                }
             },
         )
         .collect();
@@ -262,7 +324,20 @@ pub fn addresses_set_seed_name_network<P>(
 where
     P: AsRef<Path>,
 {
-    Ok(get_addresses_by_seed_name(db_path, seed_name)?
+    addresses_set_seed_name_network_in(&SledStorage::open(db_path)?, seed_name, network_specs_key)
+}
+
+/// Same as [`addresses_set_seed_name_network`], for the database in given
+/// [`Storage`].
+pub fn addresses_set_seed_name_network_in<S>(
+    storage: &S,
+    seed_name: &str,
+    network_specs_key: &NetworkSpecsKey,
+) -> Result<Vec<(MultiSigner, AddressDetails)>>
+where
+    S: Storage,
+{
+    Ok(get_addresses_by_seed_name_in(storage, seed_name)?
         .into_iter()
         .filter(|(_, address_details)| address_details.network_id.contains(network_specs_key))
         .collect())
@@ -277,7 +352,19 @@ pub fn show_all_networks_with_flag<P>(
 where
     P: AsRef<Path>,
 {
-    let mut networks: Vec<_> = get_all_networks(db_path)?
+    show_all_networks_with_flag_in(&SledStorage::open(db_path)?, network_specs_key)
+}
+
+/// Same as [`show_all_networks_with_flag`], for the database in given
+/// [`Storage`].
+pub fn show_all_networks_with_flag_in<S>(
+    storage: &S,
+    network_specs_key: &NetworkSpecsKey,
+) -> Result<MNetworkMenu>
+where
+    S: Storage,
+{
+    let mut networks: Vec<_> = get_all_networks_in(storage)?
         .into_iter()
         .map(|network| {
             let network_specs_key_current =
@@ -298,7 +385,15 @@ pub fn show_all_networks<P>(db_path: P) -> Result<Vec<MMNetwork>>
 where
     P: AsRef<Path>,
 {
-    let networks = get_all_networks(db_path)?;
+    show_all_networks_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`show_all_networks`], for the database in given [`Storage`].
+pub fn show_all_networks_in<S>(storage: &S) -> Result<Vec<MMNetwork>>
+where
+    S: Storage,
+{
+    let networks = get_all_networks_in(storage)?;
     let mut networks = networks
         .into_iter()
         .map(|n| MMNetwork {
@@ -323,7 +418,15 @@ pub fn first_network<P>(db_path: P) -> Result<NetworkSpecs>
 where
     P: AsRef<Path>,
 {
-    let mut networks = get_all_networks(db_path)?;
+    first_network_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`first_network`], for the database in given [`Storage`].
+pub fn first_network_in<S>(storage: &S) -> Result<NetworkSpecs>
+where
+    S: Storage,
+{
+    let mut networks = get_all_networks_in(storage)?;
     if networks.is_empty() {
         return Err(Error::NoNetworksAvailable);
     }
@@ -350,9 +453,27 @@ pub fn export_key<P>(
 where
     P: AsRef<Path>,
 {
-    let network_specs = get_network_specs(&db_path, network_specs_key)?;
+    export_key_in(
+        &SledStorage::open(db_path)?,
+        multisigner,
+        expected_seed_name,
+        network_specs_key,
+    )
+}
+
+/// Same as [`export_key`], for the database in given [`Storage`].
+pub fn export_key_in<S>(
+    storage: &S,
+    multisigner: &MultiSigner,
+    expected_seed_name: &str,
+    network_specs_key: &NetworkSpecsKey,
+) -> Result<MKeyDetails>
+where
+    S: Storage,
+{
+    let network_specs = get_network_specs_in(storage, network_specs_key)?;
     let address_key = AddressKey::from_multisigner(multisigner);
-    let address_details = get_address_details(&db_path, &address_key)?;
+    let address_details = get_address_details_in(storage, &address_key)?;
     if address_details.seed_name != expected_seed_name {
         return Err(Error::SeedNameNotMatching {
             address_key,
@@ -381,6 +502,9 @@ where
             });
         }
     };
+    let usage = address_details
+        .usage(network_specs_key)
+        .map(MKeyUsage::from);
     let address = Address {
         base58,
         path: address_details.path,
@@ -389,6 +513,7 @@ where
         seed_name: address_details.seed_name,
         multiselect: None,
         secret_exposed: address_details.secret_exposed,
+        annotation: try_get_key_annotation_in(storage, &address_key)?,
     };
 
     let network_info = MSCNetworkInfo {
@@ -402,6 +527,7 @@ where
         pubkey: hex::encode(public_key),
         network_info,
         address,
+        usage,
     })
 }
 
@@ -413,14 +539,22 @@ pub fn backup_prep<P>(db_path: P, seed_name: &str) -> Result<MBackup>
 where
     P: AsRef<Path>,
 {
-    let networks = get_all_networks(&db_path)?;
+    backup_prep_in(&SledStorage::open(db_path)?, seed_name)
+}
+
+/// Same as [`backup_prep`], for the database in given [`Storage`].
+pub fn backup_prep_in<S>(storage: &S, seed_name: &str) -> Result<MBackup>
+where
+    S: Storage,
+{
+    let networks = get_all_networks_in(storage)?;
     if networks.is_empty() {
         return Err(Error::NoNetworksAvailable);
     }
     let mut derivations = Vec::new();
     for network in networks.into_iter() {
-        let id_set: Vec<_> = addresses_set_seed_name_network(
-            &db_path,
+        let id_set: Vec<_> = addresses_set_seed_name_network_in(
+            storage,
             seed_name,
             &NetworkSpecsKey::from_parts(&network.genesis_hash, &network.encryption),
         )?
@@ -471,7 +605,29 @@ pub fn derive_prep<P>(
 where
     P: AsRef<Path>,
 {
-    let network_specs = get_network_specs(&db_path, network_specs_key)?;
+    derive_prep_in(
+        &SledStorage::open(db_path)?,
+        seed_name,
+        network_specs_key,
+        collision,
+        suggest,
+        keyboard,
+    )
+}
+
+/// Same as [`derive_prep`], for the database in given [`Storage`].
+pub fn derive_prep_in<S>(
+    storage: &S,
+    seed_name: &str,
+    network_specs_key: &NetworkSpecsKey,
+    collision: Option<(MultiSigner, AddressDetails)>,
+    suggest: &str,
+    keyboard: bool,
+) -> Result<MDeriveKey>
+where
+    S: Storage,
+{
+    let network_specs = get_network_specs_in(storage, network_specs_key)?;
 
     let derivation_check = match collision {
         Some((multisigner, address_details)) => {
@@ -481,6 +637,8 @@ where
             let has_pwd = address_details.has_pwd;
             let identicon = make_identicon_from_multisigner(&multisigner);
             let seed_name = seed_name.to_string();
+            let annotation =
+                try_get_key_annotation_in(storage, &AddressKey::from_multisigner(&multisigner))?;
             let collision = Address {
                 base58,
                 path,
@@ -489,6 +647,7 @@ where
                 seed_name,
                 multiselect: None,
                 secret_exposed: address_details.secret_exposed,
+                annotation,
             };
 
             NavDerivationCheck {
@@ -496,7 +655,7 @@ where
                 ..Default::default()
             }
         }
-        None => dynamic_path_check_unhexed(&db_path, seed_name, suggest, network_specs_key),
+        None => dynamic_path_check_unhexed(storage, seed_name, suggest, network_specs_key),
     };
 
     Ok(MDeriveKey {
@@ -531,9 +690,28 @@ pub fn dynamic_path_check<P>(
 ) -> NavDerivationCheck
 where
     P: AsRef<Path>,
+{
+    match SledStorage::open(db_path) {
+        Ok(storage) => dynamic_path_check_in(&storage, seed_name, path, network_specs_key_hex),
+        Err(e) => NavDerivationCheck {
+            error: Some(e.to_string()),
+            ..Default::default()
+        },
+    }
+}
+
+/// Same as [`dynamic_path_check`], for the database in given [`Storage`].
+pub fn dynamic_path_check_in<S>(
+    storage: &S,
+    seed_name: &str,
+    path: &str,
+    network_specs_key_hex: &str,
+) -> NavDerivationCheck
+where
+    S: Storage,
 {
     match NetworkSpecsKey::from_hex(network_specs_key_hex) {
-        Ok(key) => dynamic_path_check_unhexed(db_path, seed_name, path, &key),
+        Ok(key) => dynamic_path_check_unhexed(storage, seed_name, path, &key),
         Err(e) => NavDerivationCheck {
             error: Some(e.to_string()),
             ..Default::default()
@@ -541,17 +719,18 @@ where
     }
 }
 
-fn dynamic_path_check_unhexed<P>(
-    db_path: P,
+fn dynamic_path_check_unhexed<S>(
+    storage: &S,
     seed_name: &str,
     path: &str,
     network_specs_key: &NetworkSpecsKey,
 ) -> NavDerivationCheck
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
-    match get_network_specs(&db_path, network_specs_key) {
-        Ok(network_specs) => match derivation_check(seed_name, path, network_specs_key, &db_path) {
+    match get_network_specs_in(storage, network_specs_key) {
+        Ok(network_specs) => match derivation_check_in(seed_name, path, network_specs_key, storage)
+        {
             Ok(DerivationCheck::BadFormat) => NavDerivationCheck {
                 button_good: false,
                 ..Default::default()
@@ -570,19 +749,31 @@ where
                 let address_base58 =
                     print_multisigner_as_base58(&multisigner, Some(network_specs.base58prefix));
                 let identicon = make_identicon_from_multisigner(&multisigner);
-                let collision_display = Address {
-                    base58: address_base58,
-                    path: address_details.path,
-                    has_pwd: address_details.has_pwd,
-                    identicon,
-                    seed_name: seed_name.to_string(),
-                    multiselect: None,
-                    secret_exposed: address_details.secret_exposed,
-                };
-                NavDerivationCheck {
-                    button_good: false,
-                    collision: Some(collision_display),
-                    ..Default::default()
+                match try_get_key_annotation_in(
+                    storage,
+                    &AddressKey::from_multisigner(&multisigner),
+                ) {
+                    Ok(annotation) => {
+                        let collision_display = Address {
+                            base58: address_base58,
+                            path: address_details.path,
+                            has_pwd: address_details.has_pwd,
+                            identicon,
+                            seed_name: seed_name.to_string(),
+                            multiselect: None,
+                            secret_exposed: address_details.secret_exposed,
+                            annotation,
+                        };
+                        NavDerivationCheck {
+                            button_good: false,
+                            collision: Some(collision_display),
+                            ..Default::default()
+                        }
+                    }
+                    Err(e) => NavDerivationCheck {
+                        error: Some(e.to_string()),
+                        ..Default::default()
+                    },
                 }
             }
             Err(e) => NavDerivationCheck {
@@ -605,6 +796,17 @@ pub fn network_details_by_key<P>(
 ) -> Result<MNetworkDetails>
 where
     P: AsRef<Path>,
+{
+    network_details_by_key_in(&SledStorage::open(db_path)?, network_specs_key)
+}
+
+/// Same as [`network_details_by_key`], for the database in given [`Storage`].
+pub fn network_details_by_key_in<S>(
+    storage: &S,
+    network_specs_key: &NetworkSpecsKey,
+) -> Result<MNetworkDetails>
+where
+    S: Storage,
 {
     let NetworkSpecs {
         base58prefix,
@@ -618,12 +820,13 @@ where
         path_id,
         secondary_color,
         title,
+        tokens: _,
         unit,
-    } = get_network_specs(&db_path, network_specs_key)?;
+    } = get_network_specs_in(storage, network_specs_key)?;
     let verifier_key = VerifierKey::from_parts(genesis_hash);
-    let general_verifier = get_general_verifier(&db_path)?;
-    let current_verifier = get_valid_current_verifier(&verifier_key, &db_path)?;
-    let meta: Vec<_> = get_meta_values_by_name(&db_path, &name)?
+    let general_verifier = get_general_verifier_in(storage)?;
+    let current_verifier = get_valid_current_verifier_in(&verifier_key, storage)?;
+    let meta: Vec<_> = get_meta_values_by_name_in(storage, &name)?
         .into_iter()
         .map(|m| {
             let meta_hash = blake2_256(&m.meta);
@@ -672,10 +875,26 @@ pub fn metadata_details<P>(
 where
     P: AsRef<Path>,
 {
-    let network_specs = get_network_specs(&db_path, network_specs_key)?;
+    metadata_details_in(
+        &SledStorage::open(db_path)?,
+        network_specs_key,
+        network_version,
+    )
+}
+
+/// Same as [`metadata_details`], for the database in given [`Storage`].
+pub fn metadata_details_in<S>(
+    storage: &S,
+    network_specs_key: &NetworkSpecsKey,
+    network_version: u32,
+) -> Result<MManageMetadata>
+where
+    S: Storage,
+{
+    let network_specs = get_network_specs_in(storage, network_specs_key)?;
     let meta_values =
-        get_meta_values_by_name_version(&db_path, &network_specs.name, network_version)?;
-    let networks: Vec<_> = get_all_networks(&db_path)?
+        get_meta_values_by_name_version_in(storage, &network_specs.name, network_version)?;
+    let networks: Vec<_> = get_all_networks_in(storage)?
         .into_iter()
         .filter(|a| a.name == network_specs.name)
         .map(|network| MMMNetwork {
@@ -705,7 +924,15 @@ pub fn show_types_status<P>(db_path: P) -> Result<MTypesInfo>
 where
     P: AsRef<Path>,
 {
-    match try_get_types(&db_path)? {
+    show_types_status_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`show_types_status`], for the database in given [`Storage`].
+pub fn show_types_status_in<S>(storage: &S) -> Result<MTypesInfo>
+where
+    S: Storage,
+{
+    match try_get_types_in(storage)? {
         Some(a) => {
             let (types_hash, types_id_pic) = ContentLoadTypes::generate(&a).show();
             Ok(MTypesInfo {
@@ -722,6 +949,37 @@ where
     }
 }
 
+/// Make named contacts list display, sorted by contact name.
+///
+/// Contact addresses are shown in default SS58 format, as contacts are not
+/// associated with any particular network.
+pub fn show_contacts<P>(db_path: P) -> Result<Vec<MSCContact>>
+where
+    P: AsRef<Path>,
+{
+    show_contacts_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`show_contacts`], for the database in given [`Storage`].
+pub fn show_contacts_in<S>(storage: &S) -> Result<Vec<MSCContact>>
+where
+    S: Storage,
+{
+    let mut out: Vec<MSCContact> = get_contacts_in(storage)?
+        .into_iter()
+        .map(|(account_id, contact_details)| {
+            let account_id = AccountId32::new(account_id);
+            MSCContact {
+                name: contact_details.name,
+                base58: account_id.to_ss58check(),
+                identicon: make_identicon_from_account(account_id),
+            }
+        })
+        .collect();
+    out.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(out)
+}
+
 /// Generate new random seed phrase, make identicon for `sr25519` public key,
 /// and send to Signer screen.
 pub fn print_new_seed(seed_name: &str) -> Result<MNewSeedBackup> {
@@ -736,15 +994,22 @@ pub fn print_new_seed(seed_name: &str) -> Result<MNewSeedBackup> {
     })
 }
 
-/// Get database history tree checksum to be displayed in log screen.
+/// Get database history log hash chain head, i.e. the hash of the latest
+/// history entry, to be displayed in log screen.
 pub fn history_hex_checksum<P>(db_path: P) -> Result<String>
 where
     P: AsRef<Path>,
 {
-    let database = open_db(&db_path)?;
-    let history = open_tree(&database, HISTORY)?;
-    let checksum = history.checksum()?;
-    Ok(hex::encode(checksum.encode()).to_uppercase())
+    history_hex_checksum_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`history_hex_checksum`], for the database in given [`Storage`].
+pub fn history_hex_checksum_in<S>(storage: &S) -> Result<String>
+where
+    S: Storage,
+{
+    let head = history_chain_head_in(storage)?;
+    Ok(hex::encode(head).to_uppercase())
 }
 
 /// Clear transaction tree of the database.
@@ -754,10 +1019,18 @@ where
 pub fn purge_transactions<P>(db_path: P) -> Result<()>
 where
     P: AsRef<Path>,
+{
+    purge_transactions_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`purge_transactions`], for the database in given [`Storage`].
+pub fn purge_transactions_in<S>(storage: &S) -> Result<()>
+where
+    S: Storage,
 {
     TrDbCold::new()
-        .set_transaction(make_batch_clear_tree(&db_path, TRANSACTION)?) // clear transaction
-        .apply(&db_path)
+        .set_transaction(make_batch_clear_tree_in(storage, TRANSACTION)?) // clear transaction
+        .apply_in(storage)
 }
 
 /// Get possible options of English `bip39` words that start with user-entered
@@ -12,6 +12,8 @@
 //! - contains Signer interface interactions, allowing exports of data to the
 //! interface
 //! - deals with address generation for Signer
+//! - abstracts the database storage, with on-disk and in-memory backends
+//! - checks the cold database consistency and repairs the found issues
 //!
 //! # Features
 //!
@@ -40,6 +42,8 @@ pub mod cold_default;
 
 pub mod db_transactions;
 
+pub mod fsck;
+
 pub mod helpers;
 
 #[cfg(feature = "active")]
@@ -52,6 +56,13 @@ pub mod interface_signer;
 
 pub mod manage_history;
 
+pub mod migrations;
+
+#[cfg(feature = "active")]
+pub mod release_manifest;
+
+pub mod storage;
+
 mod error;
 
 pub use error::{Error, Result};
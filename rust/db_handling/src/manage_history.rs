@@ -6,34 +6,43 @@
 //! Each history log [`Entry`] contains [`Event`] set and a timestamp. Database
 //! key for [`Entry`] value is [`Order`], SCALE-encoded number of the entry.
 //!
-//! In addition to keeping the log, Signer also displays [`HISTORY`] tree
-//! checksum for user to possibly keep the track of.
+//! Entries are hash-chained, each [`Entry`] contains the hash of the previous
+//! one, see [`verify_history_chain`]. In addition to keeping the log, Signer
+//! also displays the chain head, i.e. the hash of the latest [`Entry`], for
+//! user to possibly keep the track of.
 use std::path::Path;
 // TODO: substantial part of this will go obsolete with interface updates;
 // some functions are not called at the moment from the user interface - kept
 // for now in case they make a return, commented.
-#[cfg(feature = "signer")]
 use parity_scale_codec::Decode;
-use parity_scale_codec::Encode;
-use sled::Batch;
+use sp_core::H256;
 
 #[cfg(feature = "signer")]
 use constants::DANGER;
-use constants::HISTORY;
+use constants::{HISTORY, HISTORY_HEAD, SETTREE};
 use definitions::{
-    history::{Entry, Event},
+    history::{entry_hash, Entry, EntryLink, Event, EventProof},
     keyring::Order,
 };
 
 #[cfg(feature = "signer")]
-use definitions::danger::DangerRecord;
+use definitions::{
+    crypto::Encryption,
+    danger::{DangerRecord, Exposure},
+    helpers::{multisigner_to_encryption, multisigner_to_public, unhex},
+    history::{AuditFormat, NetworkSpecsAmendmentTokensDisplay, NetworkSpecsTokensDisplay},
+    keyring::{AddressKey, NetworkSpecsKey},
+    network_specs::{ValidCurrentVerifier, Verifier, VerifierValue},
+};
+#[cfg(feature = "signer")]
+use sp_runtime::MultiSigner;
 
-use crate::helpers::{open_db, open_tree};
 #[cfg(feature = "signer")]
-use crate::Error;
-use crate::Result;
+use crate::helpers::{get_danger_record_in, try_get_key_annotation_in, try_get_network_specs_in};
+use crate::storage::{Batch, SledStorage, Storage};
 #[cfg(feature = "signer")]
-use crate::{db_transactions::TrDbCold, helpers::make_batch_clear_tree};
+use crate::{db_transactions::TrDbCold, helpers::make_batch_clear_tree_in};
+use crate::{Error, Result};
 
 /// Print total number of pages, for maximum [`HISTORY_PAGE_SIZE`](constants::HISTORY_PAGE_SIZE) number of
 /// entries per page.
@@ -41,10 +50,19 @@ use crate::{db_transactions::TrDbCold, helpers::make_batch_clear_tree};
 pub fn history_total_pages<P>(db_path: P) -> Result<u32>
 where
     P: AsRef<Path>,
+{
+    history_total_pages_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`history_total_pages`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn history_total_pages_in<S>(storage: &S) -> Result<u32>
+where
+    S: Storage,
 {
     use constants::HISTORY_PAGE_SIZE;
 
-    let history = get_history(db_path)?;
+    let history = get_history_in(storage)?;
     let total_pages = {
         if history.len() % HISTORY_PAGE_SIZE == 0 {
             history.len() / HISTORY_PAGE_SIZE
@@ -61,12 +79,20 @@ pub fn get_history<P>(db_path: P) -> Result<Vec<(Order, Entry)>>
 where
     P: AsRef<Path>,
 {
-    let database = open_db(&db_path)?;
-    let history = open_tree(&database, HISTORY)?;
+    get_history_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`get_history`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn get_history_in<S>(storage: &S) -> Result<Vec<(Order, Entry)>>
+where
+    S: Storage,
+{
     let mut out: Vec<(Order, Entry)> = Vec::new();
-    for (order_encoded, history_entry_encoded) in history.iter().flatten() {
-        let order = Order::from_ivec(&order_encoded)?;
-        let history_entry = <Entry>::decode(&mut &history_entry_encoded[..])?;
+    for (order_encoded, history_entry_encoded) in storage.iter(HISTORY)? {
+        let order = Order::from_ivec(&order_encoded.into())?;
+        let mut history_entry = Entry::from_stored(&history_entry_encoded)?;
+        fill_key_labels(storage, &mut history_entry)?;
         out.push((order, history_entry));
     }
     out.sort_by(|a, b| b.0.stamp().cmp(&a.0.stamp()));
@@ -80,30 +106,950 @@ pub fn get_history_entry_by_order<P>(order: u32, db_path: P) -> Result<Entry>
 where
     P: AsRef<Path>,
 {
-    let database = open_db(&db_path)?;
-    let history = open_tree(&database, HISTORY)?;
+    get_history_entry_by_order_in(order, &SledStorage::open(db_path)?)
+}
+
+/// Same as [`get_history_entry_by_order`], for the database in given
+/// [`Storage`].
+#[cfg(feature = "signer")]
+pub fn get_history_entry_by_order_in<S>(order: u32, storage: &S) -> Result<Entry>
+where
+    S: Storage,
+{
     let mut found = None;
     let order = Order::from_number(order);
-    for (order_encoded, history_entry_encoded) in history.iter().flatten() {
-        let order_found = Order::from_ivec(&order_encoded)?;
+    for (order_encoded, history_entry_encoded) in storage.iter(HISTORY)? {
+        let order_found = Order::from_ivec(&order_encoded.into())?;
         if order_found == order {
-            found = Some(<Entry>::decode(&mut &history_entry_encoded[..])?);
+            let mut history_entry = Entry::from_stored(&history_entry_encoded)?;
+            fill_key_labels(storage, &mut history_entry)?;
+            found = Some(history_entry);
             break;
         }
     }
     found.ok_or_else(|| Error::HistoryEntryNotFound(order.stamp()))
 }
 
+/// Fill in the labels of the address keys in identity-related events of the
+/// history log [`Entry`], from the current key annotations.
+///
+/// Labels are not stored in the history log, see
+/// [`IdentityHistory`](definitions::history::IdentityHistory).
+#[cfg(feature = "signer")]
+fn fill_key_labels<S>(storage: &S, entry: &mut Entry) -> Result<()>
+where
+    S: Storage,
+{
+    for event in entry.events.iter_mut() {
+        if let Event::IdentityAdded { identity_history }
+        | Event::IdentityRemoved { identity_history }
+        | Event::SecretWasExported { identity_history } = event
+        {
+            if let Ok(address_key) =
+                AddressKey::from_parts(&identity_history.public_key, &identity_history.encryption)
+            {
+                if let Some(key_annotation) = try_get_key_annotation_in(storage, &address_key)? {
+                    identity_history.label = key_annotation.label;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Get the hash of the latest history log [`Entry`], i.e. the head of the
+/// history log hash chain.
+///
+/// If the history log is empty, the result is zero hash.
+pub fn history_chain_head<P>(db_path: P) -> Result<H256>
+where
+    P: AsRef<Path>,
+{
+    history_chain_head_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`history_chain_head`], for the database in given [`Storage`].
+pub fn history_chain_head_in<S>(storage: &S) -> Result<H256>
+where
+    S: Storage,
+{
+    Ok(history_head_in(storage)?
+        .map(|(_, hash)| hash)
+        .unwrap_or_default())
+}
+
+/// Order and hash of the latest history log [`Entry`], `None` if the history
+/// log is empty.
+///
+/// Head is stored in [`SETTREE`] tree under [`HISTORY_HEAD`] key, and is
+/// updated with each [`TrDbCold`](crate::db_transactions::TrDbCold) that
+/// changes the history log, see [`history_head_after_in`]. Databases that have
+/// no head stored yet get it from the [`HISTORY`] tree.
+pub(crate) fn history_head_in<S>(storage: &S) -> Result<Option<(u32, H256)>>
+where
+    S: Storage,
+{
+    if let Some(head_encoded) = storage.get(SETTREE, HISTORY_HEAD)? {
+        return Ok(Some(<(u32, H256)>::decode(&mut &head_encoded[..])?));
+    }
+    let mut head: Option<(u32, H256)> = None;
+    for (order_encoded, history_entry_encoded) in storage.iter(HISTORY)? {
+        let order = Order::from_ivec(&order_encoded.into())?.stamp();
+        if head.map(|(a, _)| a < order).unwrap_or(true) {
+            head = Some((order, entry_hash(&history_entry_encoded)))
+        }
+    }
+    Ok(head)
+}
+
+/// History log head after the [`Batch`] is applied to the [`HISTORY`] tree.
+///
+/// New head is the latest entry added by the [`Batch`], if it is not before
+/// the current head. Batch that removes the current head, as when the log is
+/// cleared and started anew with the genesis marker, always gets the new head
+/// from its own entries. Batch that only rewrites entries before the head
+/// keeps the current head.
+///
+/// `None` means that the head could not be found from the [`Batch`] alone, and
+/// should be found from the [`HISTORY`] tree instead.
+pub(crate) fn history_head_after_in<S>(
+    storage: &S,
+    for_history: &Batch,
+) -> Result<Option<(u32, H256)>>
+where
+    S: Storage,
+{
+    let mut head = history_head_in(storage)?;
+    let mut added: Option<(u32, H256)> = None;
+    for (order_encoded, history_entry_encoded) in for_history.changes() {
+        let order = <u32>::decode(&mut &order_encoded[..])?;
+        match history_entry_encoded {
+            Some(history_entry_encoded) => {
+                if added.map(|(a, _)| a < order).unwrap_or(true) {
+                    added = Some((order, entry_hash(history_entry_encoded)))
+                }
+            }
+            None => {
+                if head.map(|(a, _)| a == order).unwrap_or(false) {
+                    head = None
+                }
+            }
+        }
+    }
+    Ok(match (head, added) {
+        (Some((head_order, _)), Some((added_order, _))) if added_order < head_order => head,
+        (_, Some(added)) => Some(added),
+        (head, None) => head,
+    })
+}
+
+/// Walk the history log hash chain and check that each [`Entry`] contains the
+/// hash of the previous one.
+///
+/// The entry with [`Order`] `0` is the genesis marker of the log, its link is
+/// [`EntryLink::Genesis`], signing off the log that was cleared when the
+/// current log was started. Genesis marker link is not checked, but no other
+/// entry could have it.
+///
+/// Entries stored before the history log was hash-chained are chained by the
+/// database migration, see [`migrations`](crate::migrations). Only the first
+/// entry of the log could have no link, all entries after it must be linked.
+///
+/// The last entry must be the history log head stored in the database, so
+/// that the entries could not be removed from the end of the log unnoticed.
+///
+/// Missing, edited, unlinked or reordered entries result in
+/// [`Error::HistoryChainBroken`](crate::Error::HistoryChainBroken) with the
+/// first order for which the link is broken.
+pub fn verify_history_chain<P>(db_path: P) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    verify_history_chain_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`verify_history_chain`], for the database in given [`Storage`].
+pub fn verify_history_chain_in<S>(storage: &S) -> Result<()>
+where
+    S: Storage,
+{
+    // key labels are not a part of the stored entry, and are not needed here
+    let mut history: Vec<(Order, Entry)> = Vec::new();
+    for (order_encoded, history_entry_encoded) in storage.iter(HISTORY)? {
+        history.push((
+            Order::from_ivec(&order_encoded.into())?,
+            Entry::from_stored(&history_entry_encoded)?,
+        ));
+    }
+    history.sort_by_key(|(order, _)| order.stamp());
+    let mut previous: Option<H256> = None;
+    for (expected, (order, entry)) in history.iter().enumerate() {
+        let order = order.stamp();
+        if order != expected as u32 {
+            return Err(Error::HistoryChainBroken {
+                order: expected as u32,
+            });
+        }
+        let link_valid = match (&entry.link, previous) {
+            (Some(EntryLink::Genesis { .. }), None) => true,
+            (Some(EntryLink::Previous { previous_hash }), Some(hash)) => previous_hash == &hash,
+            (None, None) => true,
+            _ => false,
+        };
+        if !link_valid {
+            return Err(Error::HistoryChainBroken { order });
+        }
+        previous = Some(entry.hash());
+    }
+    if let Some(head_encoded) = storage.get(SETTREE, HISTORY_HEAD)? {
+        let (head_order, head_hash) = <(u32, H256)>::decode(&mut &head_encoded[..])?;
+        let entries = history.len() as u32;
+        if head_order.saturating_add(1) != entries {
+            return Err(Error::HistoryChainBroken {
+                order: head_order.saturating_add(1).min(entries),
+            });
+        }
+        if previous != Some(head_hash) {
+            return Err(Error::HistoryChainBroken { order: head_order });
+        }
+    }
+    Ok(())
+}
+
+/// Filter for history log search
+///
+/// All set filter fields must match for an [`Event`] to be found. Entries are
+/// shown with only the matching events.
+#[cfg(feature = "signer")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HistoryFilter {
+    /// event kinds, as in [`Event::kind`], any of them matches
+    pub event_kinds: Vec<String>,
+
+    /// network of the event
+    pub network_specs_key: Option<NetworkSpecsKey>,
+
+    /// address that was signing, or was added, removed or exported
+    pub address_key: Option<AddressKey>,
+
+    /// seed name
+    pub seed_name: Option<String>,
+
+    /// public key of general, network or types verifier
+    pub verifier: Option<Vec<u8>>,
+
+    /// case-insensitive text in user comment or user log entry
+    pub text: Option<String>,
+
+    /// earliest timestamp, or its prefix, inclusive
+    pub from: Option<String>,
+
+    /// latest timestamp, or its prefix, inclusive
+    pub to: Option<String>,
+}
+
+#[cfg(feature = "signer")]
+impl HistoryFilter {
+    /// Get [`HistoryFilter`] from the query string received from the frontend.
+    ///
+    /// Query consists of `name=value` parts separated by `;`, with names:
+    ///
+    /// - `kind`: event kind, could be repeated
+    /// - `network`: hexadecimal [`NetworkSpecsKey`]
+    /// - `address`: hexadecimal [`AddressKey`]
+    /// - `seed`: seed name
+    /// - `verifier`: hexadecimal verifier public key
+    /// - `text`: text in user comment or user log entry
+    /// - `from`, `to`: timestamp range, e.g. `2022-10-01` or
+    /// `2022-10-01 12:00`
+    ///
+    /// Empty query is an empty filter, i.e. all entries are found.
+    pub fn from_query(query: &str) -> Result<Self> {
+        let mut filter = Self::default();
+        for part in query.split(';').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+            let invalid = || Error::HistoryQueryInvalid {
+                part: part.to_string(),
+            };
+            let (name, value) = part.split_once('=').ok_or_else(invalid)?;
+            let value = value.trim();
+            match name.trim() {
+                "kind" => filter.event_kinds.push(value.to_string()),
+                "network" => filter.network_specs_key = Some(NetworkSpecsKey::from_hex(value)?),
+                "address" => filter.address_key = Some(AddressKey::from_hex(value)?),
+                "seed" => filter.seed_name = Some(value.to_string()),
+                "verifier" => filter.verifier = Some(unhex(value)?),
+                "text" => filter.text = Some(value.to_lowercase()),
+                "from" => filter.from = Some(value.to_string()),
+                "to" => filter.to = Some(value.to_string()),
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(filter)
+    }
+
+    /// Check if the entry timestamp is within the filter range.
+    fn timestamp_matches(&self, timestamp: &str) -> bool {
+        if let Some(ref from) = self.from {
+            if timestamp < from.as_str() {
+                return false;
+            }
+        }
+        if let Some(ref to) = self.to {
+            if timestamp.get(..to.len()).unwrap_or(timestamp) > to.as_str() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Page of history log search results
+#[cfg(feature = "signer")]
+#[derive(Debug)]
+pub struct HistorySearchPage {
+    /// found entries with matching events only, latest first
+    pub entries: Vec<(Order, Entry)>,
+
+    /// total number of pages, for maximum
+    /// [`HISTORY_PAGE_SIZE`](constants::HISTORY_PAGE_SIZE) entries per page
+    pub total_pages: u32,
+}
+
+/// Search history log with [`HistoryFilter`].
+///
+/// Found entries are paged same way as the whole log, `page` starts from `0`.
+#[cfg(feature = "signer")]
+pub fn search_history<P>(db_path: P, filter: &HistoryFilter, page: u32) -> Result<HistorySearchPage>
+where
+    P: AsRef<Path>,
+{
+    search_history_in(&SledStorage::open(db_path)?, filter, page)
+}
+
+/// Same as [`search_history`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn search_history_in<S>(
+    storage: &S,
+    filter: &HistoryFilter,
+    page: u32,
+) -> Result<HistorySearchPage>
+where
+    S: Storage,
+{
+    use constants::HISTORY_PAGE_SIZE;
+
+    let network = match filter.network_specs_key {
+        Some(ref network_specs_key) => {
+            let (genesis_hash, encryption) = network_specs_key.genesis_hash_encryption()?;
+            let name = try_get_network_specs_in(storage, network_specs_key)?.map(|x| x.name);
+            Some((genesis_hash, encryption, name))
+        }
+        None => None,
+    };
+    let address = match filter.address_key {
+        Some(ref address_key) => Some(address_key.multi_signer()?),
+        None => None,
+    };
+
+    let mut found: Vec<(Order, Entry)> = Vec::new();
+    for (order, mut entry) in get_history_in(storage)?.into_iter() {
+        if !filter.timestamp_matches(&entry.timestamp) {
+            continue;
+        }
+        entry.events.retain(|event| {
+            (filter.event_kinds.is_empty()
+                || filter.event_kinds.iter().any(|kind| kind == event.kind()))
+                && network
+                    .as_ref()
+                    .map(|(genesis_hash, encryption, name)| {
+                        event_network_matches(event, genesis_hash, encryption, name.as_deref())
+                    })
+                    .unwrap_or(true)
+                && address
+                    .as_ref()
+                    .map(|multisigner| event_address_matches(event, multisigner))
+                    .unwrap_or(true)
+                && filter
+                    .seed_name
+                    .as_ref()
+                    .map(|seed_name| event_seed_name(event) == Some(seed_name.as_str()))
+                    .unwrap_or(true)
+                && filter
+                    .verifier
+                    .as_ref()
+                    .map(|public| event_verifier_matches(event, public))
+                    .unwrap_or(true)
+                && filter
+                    .text
+                    .as_ref()
+                    .map(|text| {
+                        event_user_text(event)
+                            .map(|x| x.to_lowercase().contains(text))
+                            .unwrap_or(false)
+                    })
+                    .unwrap_or(true)
+        });
+        if !entry.events.is_empty() {
+            found.push((order, entry))
+        }
+    }
+
+    let total_pages = ((found.len() + HISTORY_PAGE_SIZE - 1) / HISTORY_PAGE_SIZE) as u32;
+    let entries = found
+        .into_iter()
+        .skip(page as usize * HISTORY_PAGE_SIZE)
+        .take(HISTORY_PAGE_SIZE)
+        .collect();
+    Ok(HistorySearchPage {
+        entries,
+        total_pages,
+    })
+}
+
+/// Check if the [`Event`] is related to the network.
+///
+/// Events that record only network name (metadata and signing events) are
+/// matched by name, if network specs are still in the database.
+#[cfg(feature = "signer")]
+fn event_network_matches(
+    event: &Event,
+    genesis_hash: &H256,
+    encryption: &Encryption,
+    name: Option<&str>,
+) -> bool {
+    match event {
+        Event::MetadataAdded {
+            meta_values_display,
+        }
+        | Event::MetadataRemoved {
+            meta_values_display,
+        } => Some(meta_values_display.name.as_str()) == name,
+        Event::MetadataSigned { meta_values_export } => {
+            Some(meta_values_export.name.as_str()) == name
+        }
+        Event::NetworkSpecsAdded {
+            network_specs_display,
+        }
+        | Event::NetworkSpecsRemoved {
+            network_specs_display,
+        } => {
+            &network_specs_display.specs.genesis_hash == genesis_hash
+                && &network_specs_display.specs.encryption == encryption
+        }
+        Event::NetworkSpecsSigned {
+            network_specs_export,
+        } => {
+            &network_specs_export.specs_to_send.genesis_hash == genesis_hash
+                && &network_specs_export.specs_to_send.encryption == encryption
+        }
+        Event::NetworkVerifierSet {
+            network_verifier_display,
+        } => &network_verifier_display.genesis_hash == genesis_hash,
+        Event::NetworkVerifierRotated {
+            network_verifier_rotation_display,
+        } => &network_verifier_rotation_display.genesis_hash == genesis_hash,
+        Event::TransactionSigned { sign_display }
+        | Event::TransactionSignError { sign_display } => {
+            let (_, network_name, sign_encryption) = sign_display.transaction_network_encryption();
+            Some(network_name.as_str()) == name && &sign_encryption == encryption
+        }
+        Event::MessageSigned {
+            sign_message_display,
+        }
+        | Event::MessageSignError {
+            sign_message_display,
+        } => {
+            let VerifierValue::Standard { m } = &sign_message_display.signed_by;
+            Some(sign_message_display.network_name.as_str()) == name
+                && &multisigner_to_encryption(m) == encryption
+        }
+        Event::IdentityAdded { identity_history }
+        | Event::IdentityRemoved { identity_history }
+        | Event::SecretWasExported { identity_history } => {
+            &identity_history.network_genesis_hash == genesis_hash
+                && &identity_history.encryption == encryption
+        }
+        Event::NetworkSpecsTokensAdded {
+            network_specs_tokens_display,
+        } => {
+            let specs = &network_specs_tokens_display.network_specs_display.specs;
+            &specs.genesis_hash == genesis_hash && &specs.encryption == encryption
+        }
+        Event::NetworkSpecsAmended {
+            network_specs_amendment_display,
+        }
+        | Event::NetworkSpecsTokensAmended {
+            network_specs_amendment_tokens_display:
+                NetworkSpecsAmendmentTokensDisplay {
+                    network_specs_amendment_display,
+                    ..
+                },
+        } => {
+            let specs = &network_specs_amendment_display.old_network_specs;
+            &specs.genesis_hash == genesis_hash && &specs.encryption == encryption
+        }
+        Event::GeneralVerifierSet { .. }
+        | Event::TypesAdded { .. }
+        | Event::TypesRemoved { .. }
+        | Event::TypesSigned { .. }
+        | Event::IdentitiesWiped
+        | Event::DeviceWasOnline
+        | Event::ResetDangerRecord
+        | Event::SeedCreated { .. }
+        | Event::SeedRemoved { .. }
+        | Event::SeedNameWasShown { .. }
+        | Event::Warning { .. }
+        | Event::WrongPassword
+        | Event::UserEntry { .. }
+        | Event::SystemEntry { .. }
+        | Event::HistoryCleared
+        | Event::DatabaseInitiated
+        | Event::ContactAdded { .. }
+        | Event::ContactRemoved { .. }
+        | Event::WarningMessage { .. }
+        | Event::SecurityPolicySet { .. }
+        | Event::SecurityPolicyViolation { .. }
+        | Event::UsbDebuggingWasEnabled
+        | Event::WifiWasEnabled
+        | Event::BluetoothWasEnabled
+        | Event::ScreenWasRecorded
+        | Event::BootloaderWasUnlocked
+        | Event::DatabaseMigrated { .. }
+        | Event::DatabaseRepaired { .. }
+        | Event::HistoryAuditExported { .. }
+        | Event::KeyAnnotationChanged { .. } => false,
+    }
+}
+
+/// Check if the [`Event`] is related to the address.
+#[cfg(feature = "signer")]
+fn event_address_matches(event: &Event, multisigner: &MultiSigner) -> bool {
+    let signed_by = match event {
+        Event::MetadataSigned { meta_values_export } => &meta_values_export.signed_by,
+        Event::NetworkSpecsSigned {
+            network_specs_export,
+        } => &network_specs_export.signed_by,
+        Event::TypesSigned { types_export } => &types_export.signed_by,
+        Event::HistoryAuditExported {
+            history_audit_export,
+        } => &history_audit_export.signed_by,
+        Event::TransactionSigned { sign_display }
+        | Event::TransactionSignError { sign_display } => &sign_display.signed_by,
+        Event::MessageSigned {
+            sign_message_display,
+        }
+        | Event::MessageSignError {
+            sign_message_display,
+        } => &sign_message_display.signed_by,
+        Event::IdentityAdded { identity_history }
+        | Event::IdentityRemoved { identity_history }
+        | Event::SecretWasExported { identity_history } => {
+            return identity_history.public_key == multisigner_to_public(multisigner)
+                && identity_history.encryption == multisigner_to_encryption(multisigner)
+        }
+        Event::KeyAnnotationChanged {
+            key_annotation_history,
+        } => {
+            return key_annotation_history.public_key == multisigner_to_public(multisigner)
+                && key_annotation_history.encryption == multisigner_to_encryption(multisigner)
+        }
+        Event::MetadataAdded { .. }
+        | Event::MetadataRemoved { .. }
+        | Event::NetworkSpecsAdded { .. }
+        | Event::NetworkSpecsRemoved { .. }
+        | Event::NetworkVerifierSet { .. }
+        | Event::GeneralVerifierSet { .. }
+        | Event::TypesAdded { .. }
+        | Event::TypesRemoved { .. }
+        | Event::IdentitiesWiped
+        | Event::DeviceWasOnline
+        | Event::ResetDangerRecord
+        | Event::SeedCreated { .. }
+        | Event::SeedRemoved { .. }
+        | Event::SeedNameWasShown { .. }
+        | Event::Warning { .. }
+        | Event::WrongPassword
+        | Event::UserEntry { .. }
+        | Event::SystemEntry { .. }
+        | Event::HistoryCleared
+        | Event::DatabaseInitiated
+        | Event::ContactAdded { .. }
+        | Event::ContactRemoved { .. }
+        | Event::WarningMessage { .. }
+        | Event::NetworkVerifierRotated { .. }
+        | Event::SecurityPolicySet { .. }
+        | Event::SecurityPolicyViolation { .. }
+        | Event::UsbDebuggingWasEnabled
+        | Event::WifiWasEnabled
+        | Event::BluetoothWasEnabled
+        | Event::ScreenWasRecorded
+        | Event::BootloaderWasUnlocked
+        | Event::DatabaseMigrated { .. }
+        | Event::DatabaseRepaired { .. }
+        | Event::NetworkSpecsAmended { .. }
+        | Event::NetworkSpecsTokensAdded { .. }
+        | Event::NetworkSpecsTokensAmended { .. } => return false,
+    };
+    let VerifierValue::Standard { m } = signed_by;
+    m == multisigner
+}
+
+/// Seed name recorded in the [`Event`], if any.
+#[cfg(feature = "signer")]
+fn event_seed_name(event: &Event) -> Option<&str> {
+    match event {
+        Event::IdentityAdded { identity_history }
+        | Event::IdentityRemoved { identity_history }
+        | Event::SecretWasExported { identity_history } => Some(&identity_history.seed_name),
+        Event::KeyAnnotationChanged {
+            key_annotation_history,
+        } => Some(&key_annotation_history.seed_name),
+        Event::SeedCreated { seed_created } => Some(seed_created),
+        Event::SeedRemoved { seed_name } => Some(seed_name),
+        Event::SeedNameWasShown {
+            seed_name_was_shown,
+        } => Some(seed_name_was_shown),
+        Event::MetadataAdded { .. }
+        | Event::MetadataRemoved { .. }
+        | Event::MetadataSigned { .. }
+        | Event::NetworkSpecsAdded { .. }
+        | Event::NetworkSpecsRemoved { .. }
+        | Event::NetworkSpecsSigned { .. }
+        | Event::NetworkVerifierSet { .. }
+        | Event::GeneralVerifierSet { .. }
+        | Event::TypesAdded { .. }
+        | Event::TypesRemoved { .. }
+        | Event::TypesSigned { .. }
+        | Event::TransactionSigned { .. }
+        | Event::TransactionSignError { .. }
+        | Event::MessageSigned { .. }
+        | Event::MessageSignError { .. }
+        | Event::IdentitiesWiped
+        | Event::DeviceWasOnline
+        | Event::ResetDangerRecord
+        | Event::Warning { .. }
+        | Event::WrongPassword
+        | Event::UserEntry { .. }
+        | Event::SystemEntry { .. }
+        | Event::HistoryCleared
+        | Event::DatabaseInitiated
+        | Event::ContactAdded { .. }
+        | Event::ContactRemoved { .. }
+        | Event::WarningMessage { .. }
+        | Event::NetworkVerifierRotated { .. }
+        | Event::SecurityPolicySet { .. }
+        | Event::SecurityPolicyViolation { .. }
+        | Event::UsbDebuggingWasEnabled
+        | Event::WifiWasEnabled
+        | Event::BluetoothWasEnabled
+        | Event::ScreenWasRecorded
+        | Event::BootloaderWasUnlocked
+        | Event::DatabaseMigrated { .. }
+        | Event::DatabaseRepaired { .. }
+        | Event::NetworkSpecsAmended { .. }
+        | Event::HistoryAuditExported { .. }
+        | Event::NetworkSpecsTokensAdded { .. }
+        | Event::NetworkSpecsTokensAmended { .. } => None,
+    }
+}
+
+/// Check if the [`Event`] records a verifier with given public key.
+#[cfg(feature = "signer")]
+fn event_verifier_matches(event: &Event, public: &[u8]) -> bool {
+    let verifier_value_matches = |verifier_value: &VerifierValue| match verifier_value {
+        VerifierValue::Standard { m } => multisigner_to_public(m) == public,
+    };
+    let verifier_matches = |verifier: &Verifier| match verifier.v {
+        Some(ref verifier_value) => verifier_value_matches(verifier_value),
+        None => false,
+    };
+    let valid_current_verifier_matches =
+        |valid_current_verifier: &ValidCurrentVerifier| match valid_current_verifier {
+            ValidCurrentVerifier::General => false,
+            ValidCurrentVerifier::Custom { v } => verifier_matches(v),
+        };
+    match event {
+        Event::GeneralVerifierSet { verifier } => verifier_matches(verifier),
+        Event::NetworkVerifierSet {
+            network_verifier_display,
+        } => {
+            valid_current_verifier_matches(&network_verifier_display.valid_current_verifier)
+                || verifier_matches(&network_verifier_display.general_verifier)
+        }
+        Event::NetworkSpecsAdded {
+            network_specs_display,
+        }
+        | Event::NetworkSpecsRemoved {
+            network_specs_display,
+        }
+        | Event::NetworkSpecsTokensAdded {
+            network_specs_tokens_display:
+                NetworkSpecsTokensDisplay {
+                    network_specs_display,
+                    ..
+                },
+        } => {
+            valid_current_verifier_matches(&network_specs_display.valid_current_verifier)
+                || verifier_matches(&network_specs_display.general_verifier)
+        }
+        Event::TypesAdded { types_display } | Event::TypesRemoved { types_display } => {
+            verifier_matches(&types_display.verifier)
+        }
+        Event::ContactAdded { contact_display } | Event::ContactRemoved { contact_display } => {
+            verifier_matches(&contact_display.verifier)
+        }
+        Event::NetworkVerifierRotated {
+            network_verifier_rotation_display,
+        } => {
+            verifier_value_matches(&network_verifier_rotation_display.old_verifier_value)
+                || verifier_value_matches(&network_verifier_rotation_display.new_verifier_value)
+        }
+        Event::MetadataAdded { .. }
+        | Event::MetadataRemoved { .. }
+        | Event::MetadataSigned { .. }
+        | Event::NetworkSpecsSigned { .. }
+        | Event::TypesSigned { .. }
+        | Event::TransactionSigned { .. }
+        | Event::TransactionSignError { .. }
+        | Event::MessageSigned { .. }
+        | Event::MessageSignError { .. }
+        | Event::IdentityAdded { .. }
+        | Event::IdentityRemoved { .. }
+        | Event::IdentitiesWiped
+        | Event::DeviceWasOnline
+        | Event::ResetDangerRecord
+        | Event::SeedCreated { .. }
+        | Event::SeedRemoved { .. }
+        | Event::SeedNameWasShown { .. }
+        | Event::SecretWasExported { .. }
+        | Event::Warning { .. }
+        | Event::WrongPassword
+        | Event::UserEntry { .. }
+        | Event::SystemEntry { .. }
+        | Event::HistoryCleared
+        | Event::DatabaseInitiated
+        | Event::WarningMessage { .. }
+        | Event::SecurityPolicySet { .. }
+        | Event::SecurityPolicyViolation { .. }
+        | Event::UsbDebuggingWasEnabled
+        | Event::WifiWasEnabled
+        | Event::BluetoothWasEnabled
+        | Event::ScreenWasRecorded
+        | Event::BootloaderWasUnlocked
+        | Event::DatabaseMigrated { .. }
+        | Event::DatabaseRepaired { .. }
+        | Event::NetworkSpecsAmended { .. }
+        | Event::HistoryAuditExported { .. }
+        | Event::KeyAnnotationChanged { .. }
+        | Event::NetworkSpecsTokensAmended { .. } => false,
+    }
+}
+
+/// User-entered text in the [`Event`], if any.
+///
+/// For events related to the address key this is the key label.
+#[cfg(feature = "signer")]
+fn event_user_text(event: &Event) -> Option<&str> {
+    match event {
+        Event::TransactionSigned { sign_display }
+        | Event::TransactionSignError { sign_display } => Some(&sign_display.user_comment),
+        Event::MessageSigned {
+            sign_message_display,
+        }
+        | Event::MessageSignError {
+            sign_message_display,
+        } => Some(&sign_message_display.user_comment),
+        Event::UserEntry { user_entry } => Some(user_entry),
+        Event::IdentityAdded { identity_history }
+        | Event::IdentityRemoved { identity_history }
+        | Event::SecretWasExported { identity_history } => Some(&identity_history.label),
+        Event::KeyAnnotationChanged {
+            key_annotation_history,
+        } => Some(&key_annotation_history.annotation.label),
+        Event::MetadataAdded { .. }
+        | Event::MetadataRemoved { .. }
+        | Event::MetadataSigned { .. }
+        | Event::NetworkSpecsAdded { .. }
+        | Event::NetworkSpecsRemoved { .. }
+        | Event::NetworkSpecsSigned { .. }
+        | Event::NetworkVerifierSet { .. }
+        | Event::GeneralVerifierSet { .. }
+        | Event::TypesAdded { .. }
+        | Event::TypesRemoved { .. }
+        | Event::TypesSigned { .. }
+        | Event::IdentitiesWiped
+        | Event::DeviceWasOnline
+        | Event::ResetDangerRecord
+        | Event::SeedCreated { .. }
+        | Event::SeedRemoved { .. }
+        | Event::SeedNameWasShown { .. }
+        | Event::Warning { .. }
+        | Event::WrongPassword
+        | Event::SystemEntry { .. }
+        | Event::HistoryCleared
+        | Event::DatabaseInitiated
+        | Event::ContactAdded { .. }
+        | Event::ContactRemoved { .. }
+        | Event::WarningMessage { .. }
+        | Event::NetworkVerifierRotated { .. }
+        | Event::SecurityPolicySet { .. }
+        | Event::SecurityPolicyViolation { .. }
+        | Event::UsbDebuggingWasEnabled
+        | Event::WifiWasEnabled
+        | Event::BluetoothWasEnabled
+        | Event::ScreenWasRecorded
+        | Event::BootloaderWasUnlocked
+        | Event::DatabaseMigrated { .. }
+        | Event::DatabaseRepaired { .. }
+        | Event::NetworkSpecsAmended { .. }
+        | Event::HistoryAuditExported { .. }
+        | Event::NetworkSpecsTokensAdded { .. }
+        | Event::NetworkSpecsTokensAmended { .. } => None,
+    }
+}
+
+/// Make history log audit report for entries with [`Order`] from
+/// `first_order` to `last_order` inclusive.
+///
+/// Report lists each [`Entry`] with its order, timestamp, own hash and the
+/// hash of the previous entry, so that the report could be matched with the
+/// history chain head shown in Signer, and all entry events with details, see
+/// [`Event::audit_fields`].
+///
+/// In [`AuditFormat::Json`] report is a JSON object with `entries` array. In
+/// [`AuditFormat::Csv`] report is a table with header and one row per event,
+/// event details are joined as `name=value` pairs separated by `; `.
+#[cfg(feature = "signer")]
+pub fn history_audit_report<P>(
+    db_path: P,
+    first_order: u32,
+    last_order: u32,
+    format: AuditFormat,
+) -> Result<Vec<u8>>
+where
+    P: AsRef<Path>,
+{
+    history_audit_report_in(
+        &SledStorage::open(db_path)?,
+        first_order,
+        last_order,
+        format,
+    )
+}
+
+/// Same as [`history_audit_report`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn history_audit_report_in<S>(
+    storage: &S,
+    first_order: u32,
+    last_order: u32,
+    format: AuditFormat,
+) -> Result<Vec<u8>>
+where
+    S: Storage,
+{
+    let mut history = get_history_in(storage)?;
+    history.retain(|(order, _)| (first_order..=last_order).contains(&order.stamp()));
+    history.reverse();
+    if history.is_empty() {
+        return Err(Error::HistoryAuditRangeEmpty {
+            first_order,
+            last_order,
+        });
+    }
+    let report = match format {
+        AuditFormat::Json => {
+            let entries: Vec<serde_json::Value> = history
+                .iter()
+                .map(|(order, entry)| {
+                    let events: Vec<serde_json::Value> = entry
+                        .events
+                        .iter()
+                        .map(|event| {
+                            let (name, fields) = event.audit_fields();
+                            let details: serde_json::Map<String, serde_json::Value> = fields
+                                .into_iter()
+                                .map(|(field, value)| (field.to_string(), value.into()))
+                                .collect();
+                            serde_json::json!({"event": name, "details": details})
+                        })
+                        .collect();
+                    serde_json::json!({
+                        "order": order.stamp(),
+                        "timestamp": entry.timestamp,
+                        "previous_hash": hex::encode(entry.previous_hash()),
+                        "hash": hex::encode(entry.hash()),
+                        "events": events,
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "first_order": first_order,
+                "last_order": last_order,
+                "entries": entries,
+            })
+            .to_string()
+        }
+        AuditFormat::Csv => {
+            let mut out = String::from("order,timestamp,previous_hash,hash,event,details\n");
+            for (order, entry) in history.iter() {
+                let hash = hex::encode(entry.hash());
+                for event in entry.events.iter() {
+                    let (name, fields) = event.audit_fields();
+                    let details = fields
+                        .iter()
+                        .map(|(field, value)| format!("{}={}", field, value))
+                        .collect::<Vec<String>>()
+                        .join("; ");
+                    out.push_str(&format!(
+                        "{},{},{},{},{},{}\n",
+                        order.stamp(),
+                        csv_field(&entry.timestamp),
+                        hex::encode(entry.previous_hash()),
+                        hash,
+                        name,
+                        csv_field(&details),
+                    ))
+                }
+            }
+            out
+        }
+    };
+    Ok(report.into_bytes())
+}
+
+/// Quote CSV field if it contains separators, quotes or line breaks.
+#[cfg(feature = "signer")]
+fn csv_field(field: &str) -> String {
+    if field.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
 /// Clear Signer history and make a log [`Entry`] that history was cleared.
+///
+/// New log starts with genesis marker [`Entry`], that signs off the cleared log
+/// with its head hash and number of entries, see [`EntryLink::Genesis`].
 #[cfg(feature = "signer")]
 pub fn clear_history<P>(db_path: P) -> Result<()>
 where
     P: AsRef<Path>,
 {
-    let batch = make_batch_clear_tree(&db_path, HISTORY)?;
+    clear_history_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`clear_history`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn clear_history_in<S>(storage: &S) -> Result<()>
+where
+    S: Storage,
+{
+    let batch = make_batch_clear_tree_in(storage, HISTORY)?;
     let events = vec![Event::HistoryCleared];
-    let for_history = events_in_batch(&db_path, true, batch, events)?;
-    TrDbCold::new().set_history(for_history).apply(&db_path)?;
+    let for_history = events_in_batch_in(storage, true, batch, events)?;
+    TrDbCold::new().set_history(for_history).apply_in(storage)?;
 
     Ok(())
 }
@@ -114,7 +1060,42 @@ pub fn events_to_batch<P>(db_path: P, events: Vec<Event>) -> Result<Batch>
 where
     P: AsRef<Path>,
 {
-    events_in_batch(db_path, false, Batch::default(), events)
+    events_to_batch_in(&SledStorage::open(db_path)?, events)
+}
+
+/// Same as [`events_to_batch`], for the database in given [`Storage`].
+pub fn events_to_batch_in<S>(storage: &S, events: Vec<Event>) -> Result<Batch>
+where
+    S: Storage,
+{
+    events_in_batch_in(storage, false, Batch::default(), events)
+}
+
+/// Timestamp [`Event`] set with metadata proofs of the transactions signed
+/// with proof, and make with it a new [`Batch`], that could be applied to the
+/// [`HISTORY`] tree.
+pub fn events_with_proofs_to_batch<P>(
+    db_path: P,
+    events: Vec<Event>,
+    metadata_proofs: Vec<EventProof>,
+) -> Result<Batch>
+where
+    P: AsRef<Path>,
+{
+    events_with_proofs_to_batch_in(&SledStorage::open(db_path)?, events, metadata_proofs)
+}
+
+/// Same as [`events_with_proofs_to_batch`], for the database in given
+/// [`Storage`].
+pub fn events_with_proofs_to_batch_in<S>(
+    storage: &S,
+    events: Vec<Event>,
+    metadata_proofs: Vec<EventProof>,
+) -> Result<Batch>
+where
+    S: Storage,
+{
+    entry_in_batch_in(storage, false, Batch::default(), events, metadata_proofs)
 }
 
 /// Timestamp [`Event`] set and add it to existing [`Batch`], that could be
@@ -123,30 +1104,71 @@ where
 /// Note that existing [`Batch`] must contain no [`Entry`] additions, only
 /// removals are possible. Only one [`Event`] set, transformed into [`Entry`]
 /// with a single timestamp could be added in a single database transaction.
-pub(crate) fn events_in_batch<P>(
-    db_path: P,
+pub(crate) fn events_in_batch_in<S>(
+    storage: &S,
+    start_zero: bool,
+    out_prep: Batch,
+    events: Vec<Event>,
+) -> Result<Batch>
+where
+    S: Storage,
+{
+    entry_in_batch_in(storage, start_zero, out_prep, events, Vec::new())
+}
+
+/// Timestamp [`Event`] set with its metadata proofs and add it to existing
+/// [`Batch`].
+fn entry_in_batch_in<S>(
+    storage: &S,
     start_zero: bool,
     mut out_prep: Batch,
     events: Vec<Event>,
+    metadata_proofs: Vec<EventProof>,
 ) -> Result<Batch>
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
-    let database = open_db(&db_path)?;
-    let history = open_tree(&database, HISTORY)?;
-    let order = {
-        if start_zero {
-            Order::from_number(0u32)
+    let (entries, head) = match history_head_in(storage)? {
+        Some((order, hash)) => (order + 1, hash),
+        None => (0, H256::zero()),
+    };
+    // when the log starts anew, the new genesis entry signs off the log being
+    // cleared
+    let (order, link) = {
+        if start_zero || entries == 0 {
+            (
+                Order::from_number(0u32),
+                EntryLink::Genesis {
+                    cleared_head: head,
+                    cleared_entries: entries,
+                },
+            )
         } else {
-            Order::from_number(history.len() as u32)
+            (
+                Order::from_number(entries),
+                EntryLink::Previous {
+                    previous_hash: head,
+                },
+            )
         }
     };
+    let timestamp = timestamp_now()?;
+    let history_entry = Entry {
+        timestamp,
+        events,
+        link: Some(link),
+        metadata_proofs,
+    };
+    out_prep.insert(order.store(), history_entry.store());
+    Ok(out_prep)
+}
+
+/// Current UTC time, formatted as in history log [`Entry`] timestamps.
+pub(crate) fn timestamp_now() -> Result<String> {
     let timestamp = time::OffsetDateTime::now_utc().format(&time::macros::format_description!(
         "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond]"
     ))?;
-    let history_entry = Entry { timestamp, events };
-    out_prep.insert(order.store(), history_entry.encode());
-    Ok(out_prep)
+    Ok(timestamp)
 }
 
 /// Enter [`Event`] set into the database as a single database transaction.
@@ -154,10 +1176,19 @@ where
 pub fn enter_events<P>(db_path: P, events: Vec<Event>) -> Result<()>
 where
     P: AsRef<Path>,
+{
+    enter_events_in(&SledStorage::open(db_path)?, events)
+}
+
+/// Same as [`enter_events`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn enter_events_in<S>(storage: &S, events: Vec<Event>) -> Result<()>
+where
+    S: Storage,
 {
     TrDbCold::new()
-        .set_history(events_to_batch(&db_path, events)?)
-        .apply(&db_path)
+        .set_history(events_to_batch_in(storage, events)?)
+        .apply_in(storage)
 }
 
 /// Enter user-generated [`Event`] into the database.
@@ -165,11 +1196,20 @@ where
 pub fn history_entry_user<P>(db_path: P, string_from_user: &str) -> Result<()>
 where
     P: AsRef<Path>,
+{
+    history_entry_user_in(&SledStorage::open(db_path)?, string_from_user)
+}
+
+/// Same as [`history_entry_user`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn history_entry_user_in<S>(storage: &S, string_from_user: &str) -> Result<()>
+where
+    S: Storage,
 {
     let events = vec![Event::UserEntry {
         user_entry: string_from_user.to_string(),
     }];
-    enter_events(db_path, events)
+    enter_events_in(storage, events)
 }
 
 /// Enter system-generated [`Event`] into the database.
@@ -178,40 +1218,94 @@ where
 pub fn history_entry_system<P>(db_path: P, event: Event) -> Result<()>
 where
     P: AsRef<Path>,
+{
+    history_entry_system_in(&SledStorage::open(db_path)?, event)
+}
+
+/// Same as [`history_entry_system`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn history_entry_system_in<S>(storage: &S, event: Event) -> Result<()>
+where
+    S: Storage,
 {
     // let events = vec![Event::SystemEntry(string_from_system)];
     let events = vec![event];
-    enter_events(db_path, events)
+    enter_events_in(storage, events)
 }
 
 /// Process the fact that the Signer device was online.
 ///
-/// - Add history log entry with `Event::DeviceWasOnline`.
+/// Same as [`device_exposed`] with [`Exposure::DeviceWasOnline`].
+#[cfg(feature = "signer")]
+pub fn device_was_online<P>(db_path: P) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    device_was_online_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`device_was_online`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn device_was_online_in<S>(storage: &S) -> Result<()>
+where
+    S: Storage,
+{
+    device_exposed_in(storage, Exposure::DeviceWasOnline)
+}
+
+/// Process the fact that the Signer device was exposed.
+///
+/// - Add history log entry with [`Event`] corresponding to the exposure,
+/// e.g. `Event::DeviceWasOnline`.
 /// - Update [`DangerRecord`] stored in [`SETTREE`](constants::SETTREE) with
-/// `device_was_online = true` flag.
+/// the exposure, if it is not yet there, marking the time it was first seen.
 ///
 /// Unacknowledged non-safe [`DangerRecord`] block the use of Signer in the
 /// frontend.
 #[cfg(feature = "signer")]
-pub fn device_was_online<P>(db_path: P) -> Result<()>
+pub fn device_exposed<P>(db_path: P, exposure: Exposure) -> Result<()>
 where
     P: AsRef<Path>,
 {
-    let events = vec![Event::DeviceWasOnline];
+    device_exposed_in(&SledStorage::open(db_path)?, exposure)
+}
+
+/// Same as [`device_exposed`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn device_exposed_in<S>(storage: &S, exposure: Exposure) -> Result<()>
+where
+    S: Storage,
+{
+    let danger_record =
+        get_danger_record_in(storage)?.with_exposure(exposure, &timestamp_now()?)?;
+    let events = vec![exposure_event(exposure)];
     let mut settings_batch = Batch::default();
-    settings_batch.insert(DANGER, DangerRecord::set_was_online().store());
+    settings_batch.insert(DANGER, danger_record.store());
     TrDbCold::new()
-        .set_history(events_to_batch(&db_path, events)?)
+        .set_history(events_to_batch_in(storage, events)?)
         .set_settings(settings_batch)
-        .apply(&db_path)
+        .apply_in(storage)
 }
 
-/// Acknowledge that the Signer device was online and reset the
+/// History log [`Event`] for the exposure.
+#[cfg(feature = "signer")]
+fn exposure_event(exposure: Exposure) -> Event {
+    match exposure {
+        Exposure::DeviceWasOnline => Event::DeviceWasOnline,
+        Exposure::UsbDebugging => Event::UsbDebuggingWasEnabled,
+        Exposure::Wifi => Event::WifiWasEnabled,
+        Exposure::Bluetooth => Event::BluetoothWasEnabled,
+        Exposure::ScreenRecording => Event::ScreenWasRecorded,
+        Exposure::BootloaderUnlocked => Event::BootloaderWasUnlocked,
+    }
+}
+
+/// Acknowledge that the Signer device was exposed and reset the
 /// [`DangerRecord`] back to safe.
 ///
 /// - Add history log entry with `Event::ResetDangerRecord`.
 /// - Reset [`DangerRecord`] stored in [`SETTREE`](constants::SETTREE) to
-/// `safe`, i.e. with `device_was_online = false` flag.
+/// `safe`, i.e. with no exposures.
 ///
 /// Acknowledged and reset [`DangerRecord`] allow to resume the use of Signer in
 /// the frontend. Use it wisely.
@@ -219,14 +1313,24 @@ where
 pub fn reset_danger_status_to_safe<P>(db_path: P) -> Result<()>
 where
     P: AsRef<Path>,
+{
+    reset_danger_status_to_safe_in(&SledStorage::open(db_path)?)
+}
+
+/// Same as [`reset_danger_status_to_safe`], for the database in given
+/// [`Storage`].
+#[cfg(feature = "signer")]
+pub fn reset_danger_status_to_safe_in<S>(storage: &S) -> Result<()>
+where
+    S: Storage,
 {
     let events = vec![Event::ResetDangerRecord];
     let mut settings_batch = Batch::default();
     settings_batch.insert(DANGER, DangerRecord::safe().store());
     TrDbCold::new()
-        .set_history(events_to_batch(&db_path, events)?)
+        .set_history(events_to_batch_in(storage, events)?)
         .set_settings(settings_batch)
-        .apply(&db_path)
+        .apply_in(storage)
 }
 
 /// Record in history log that certain seed was shown on Signer screen,
@@ -237,9 +1341,18 @@ where
 pub fn seed_name_was_shown<P>(db_path: P, seed_name_was_shown: String) -> Result<()>
 where
     P: AsRef<Path>,
+{
+    seed_name_was_shown_in(&SledStorage::open(db_path)?, seed_name_was_shown)
+}
+
+/// Same as [`seed_name_was_shown`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn seed_name_was_shown_in<S>(storage: &S, seed_name_was_shown: String) -> Result<()>
+where
+    S: Storage,
 {
     let events = vec![Event::SeedNameWasShown {
         seed_name_was_shown,
     }];
-    enter_events(db_path, events)
+    enter_events_in(storage, events)
 }
@@ -0,0 +1,298 @@
+//! Cold database schema versioning and migrations
+//!
+//! Cold database schema version is stored in `SETTREE` tree under key
+//! `SCHEMA_VERSION`, as SCALE-encoded `u32`. Databases made before the schema
+//! versioning was introduced have no version entry and are of version `0`.
+//!
+//! Database of version `n` is brought to version `n + 1` by the [`Migration`]
+//! with index `n` in [`MIGRATIONS`] list. Each migration is applied as a single
+//! [`TrDbCold`] transaction, that also updates the stored schema version and
+//! adds `Event::DatabaseMigrated` into the history log.
+//!
+//! Databases made by the Signer before the schema versioning (version `0`)
+//! have legacy danger record, no security policy record, history log entries
+//! that are not hash-chained and address details without key usage
+//! statistics. All of these are brought to the current format.
+//!
+//! Database with schema version newer than [`LIVE_SCHEMA_VERSION`] is not
+//! operated by this Signer, and is refused by
+//! [`open_db`](crate::helpers::open_db).
+use parity_scale_codec::Decode;
+#[cfg(feature = "signer")]
+use parity_scale_codec::Encode;
+use sled::{open, Db};
+#[cfg(feature = "signer")]
+use sp_core::H256;
+use std::path::Path;
+
+#[cfg(feature = "signer")]
+use constants::{ADDRTREE, DANGER, HISTORY, POLICY};
+use constants::{SCHEMA_VERSION, SETTREE};
+#[cfg(feature = "signer")]
+use definitions::{
+    history::{EntryLink, Event},
+    users::AddressDetails,
+};
+
+#[cfg(feature = "signer")]
+use crate::{
+    db_transactions::TrDbCold,
+    helpers::{get_danger_record_in, get_security_policy_in},
+    manage_history::{events_in_batch_in, events_to_batch_in, get_history_in},
+    storage::{Batch, MemoryStorage, SledStorage},
+};
+use crate::{helpers::open_tree, storage::Storage, Error, Result};
+
+/// Schema version of the cold databases made by this Signer.
+pub const LIVE_SCHEMA_VERSION: u32 = 4;
+
+/// Cold database migration, from the schema version equal to its index in
+/// [`MIGRATIONS`] to the next one.
+#[cfg(feature = "signer")]
+pub struct Migration {
+    /// Short description of the changes, for migration report
+    pub description: &'static str,
+
+    /// Prepare database transaction for the migration.
+    ///
+    /// Input `Batch` for `SETTREE` tree already has the new schema version,
+    /// migration adds its own settings changes into it. Input `Event` is the
+    /// `Event::DatabaseMigrated` to be added into the history log. Migrations
+    /// that do not rewrite `HISTORY` tree add it with `with_history_entry`,
+    /// migrations that do must add it after the rewritten entries, so that
+    /// the new entry links to the rewritten log head.
+    prepare: fn(&dyn Storage, Batch, Event) -> Result<TrDbCold>,
+}
+
+/// Cold database migrations, ordered by schema version.
+#[cfg(feature = "signer")]
+pub const MIGRATIONS: [Migration; LIVE_SCHEMA_VERSION as usize] = [
+    Migration {
+        description: "danger record stored in versioned format",
+        prepare: migrate_danger_record,
+    },
+    Migration {
+        description: "security policy stored explicitly",
+        prepare: migrate_security_policy,
+    },
+    Migration {
+        description: "history log entries hash-chained",
+        prepare: migrate_history_chain,
+    },
+    Migration {
+        description: "address details stored with key usage statistics",
+        prepare: migrate_address_details,
+    },
+];
+
+/// Report on the migration that was applied, or would have been applied in
+/// dry-run mode.
+#[cfg(feature = "signer")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub description: String,
+}
+
+/// Schema version of the opened database, `0` if no version is recorded.
+fn schema_version(database: &Db) -> Result<u32> {
+    let settings = open_tree(database, SETTREE)?;
+    decode_schema_version(settings.get(SCHEMA_VERSION)?.as_deref())
+}
+
+/// Decode stored schema version, `0` if no version is recorded.
+fn decode_schema_version(version_encoded: Option<&[u8]>) -> Result<u32> {
+    match version_encoded {
+        Some(mut version_encoded) => Ok(<u32>::decode(&mut version_encoded)?),
+        None => Ok(0),
+    }
+}
+
+/// Check that the opened database schema version is supported by this Signer.
+pub(crate) fn check_schema_version(database: &Db) -> Result<()> {
+    let found = schema_version(database)?;
+    if found > LIVE_SCHEMA_VERSION {
+        return Err(Error::SchemaVersionTooNew {
+            found,
+            supported: LIVE_SCHEMA_VERSION,
+        });
+    }
+    Ok(())
+}
+
+/// Get schema version of the cold database.
+///
+/// Version is read also for the databases newer than this Signer supports.
+pub fn get_schema_version<P>(db_path: P) -> Result<u32>
+where
+    P: AsRef<Path>,
+{
+    schema_version(&open(db_path)?)
+}
+
+/// Same as [`get_schema_version`], for the database in given [`Storage`].
+pub fn get_schema_version_in<S>(storage: &S) -> Result<u32>
+where
+    S: Storage,
+{
+    decode_schema_version(storage.get(SETTREE, SCHEMA_VERSION)?.as_deref())
+}
+
+/// Bring the cold database to [`LIVE_SCHEMA_VERSION`].
+///
+/// Pending migrations are applied in order, each as a separate database
+/// transaction, so that an interrupted migration leaves the database in a
+/// consistent state of some intermediate version.
+///
+/// In dry-run mode, the transactions are prepared against the current
+/// database content, but not applied.
+///
+/// Result is the list of the applied (or pending, in dry-run mode)
+/// migrations, empty if the database is already up to date.
+#[cfg(feature = "signer")]
+pub fn migrate_db<P>(db_path: P, dry_run: bool) -> Result<Vec<MigrationReport>>
+where
+    P: AsRef<Path>,
+{
+    migrate_db_in(&SledStorage::open(db_path)?, dry_run)
+}
+
+/// Same as [`migrate_db`], for the database in given [`Storage`].
+#[cfg(feature = "signer")]
+pub fn migrate_db_in<S>(storage: &S, dry_run: bool) -> Result<Vec<MigrationReport>>
+where
+    S: Storage,
+{
+    let current_version = get_schema_version_in(storage)?;
+    if current_version > LIVE_SCHEMA_VERSION {
+        return Err(Error::SchemaVersionTooNew {
+            found: current_version,
+            supported: LIVE_SCHEMA_VERSION,
+        });
+    }
+
+    let mut reports = Vec::new();
+    for (from_version, migration) in MIGRATIONS.iter().enumerate().skip(current_version as usize) {
+        let from_version = from_version as u32;
+        let to_version = from_version + 1;
+        let mut settings = Batch::default();
+        settings.insert(SCHEMA_VERSION, to_version.encode());
+        let transaction = (migration.prepare)(
+            storage,
+            settings,
+            Event::DatabaseMigrated {
+                from_version,
+                to_version,
+            },
+        )?;
+        if !dry_run {
+            transaction.apply_in(storage)?;
+        }
+        reports.push(MigrationReport {
+            from_version,
+            to_version,
+            description: migration.description.to_string(),
+        });
+    }
+    Ok(reports)
+}
+
+/// Add `Event::DatabaseMigrated` into the history log, for migrations that do
+/// not rewrite `HISTORY` tree.
+#[cfg(feature = "signer")]
+fn with_history_entry(
+    storage: &dyn Storage,
+    transaction: TrDbCold,
+    migrated: Event,
+) -> Result<TrDbCold> {
+    Ok(transaction.set_history(events_to_batch_in(&storage, vec![migrated])?))
+}
+
+/// Rewrite legacy danger record, with single `device_was_online` flag, in the
+/// versioned format.
+#[cfg(feature = "signer")]
+fn migrate_danger_record(
+    storage: &dyn Storage,
+    mut settings: Batch,
+    migrated: Event,
+) -> Result<TrDbCold> {
+    settings.insert(DANGER, get_danger_record_in(&storage)?.upgraded()?.store());
+    with_history_entry(storage, TrDbCold::new().set_settings(settings), migrated)
+}
+
+/// Store security policy in force, so that the databases initiated before the
+/// security policy was introduced have explicit default policy record.
+#[cfg(feature = "signer")]
+fn migrate_security_policy(
+    storage: &dyn Storage,
+    mut settings: Batch,
+    migrated: Event,
+) -> Result<TrDbCold> {
+    settings.insert(POLICY, get_security_policy_in(&storage)?.encode());
+    with_history_entry(storage, TrDbCold::new().set_settings(settings), migrated)
+}
+
+/// Hash-chain history log entries stored before the history log was
+/// hash-chained.
+///
+/// The first entry of the log becomes the genesis marker (with no cleared
+/// log), each of the others links to the entry before it. Entries that are
+/// already chained are linked anew as well, since the hashes of the legacy
+/// entries before them change. Genesis marker that is already in place is
+/// kept as is.
+///
+/// `Event::DatabaseMigrated` entry is chained after the rewritten entries.
+#[cfg(feature = "signer")]
+fn migrate_history_chain(
+    storage: &dyn Storage,
+    settings: Batch,
+    migrated: Event,
+) -> Result<TrDbCold> {
+    let mut history = get_history_in(&storage)?;
+    history.reverse();
+    let mut for_history = Batch::default();
+    let mut previous: Option<H256> = None;
+    for (order, mut entry) in history.into_iter() {
+        entry.link = Some(match (previous, entry.link) {
+            (None, Some(genesis @ EntryLink::Genesis { .. })) => genesis,
+            (None, _) => EntryLink::Genesis {
+                cleared_head: H256::zero(),
+                cleared_entries: 0,
+            },
+            (Some(previous_hash), _) => EntryLink::Previous { previous_hash },
+        });
+        previous = Some(entry.hash());
+        for_history.insert(order.store(), entry.store());
+    }
+
+    // new entry is chained against the rewritten log
+    let rewritten = MemoryStorage::new();
+    rewritten.apply(&[(HISTORY, &for_history)])?;
+    let for_history = events_in_batch_in(&rewritten, false, for_history, vec![migrated])?;
+    Ok(TrDbCold::new()
+        .set_history(for_history)
+        .set_settings(settings))
+}
+
+/// Rewrite address details stored before the key usage statistics were
+/// introduced, with empty usage statistics.
+#[cfg(feature = "signer")]
+fn migrate_address_details(
+    storage: &dyn Storage,
+    settings: Batch,
+    migrated: Event,
+) -> Result<TrDbCold> {
+    let mut for_addresses = Batch::default();
+    for (address_key, address_details_encoded) in storage.iter(ADDRTREE)? {
+        let address_details = AddressDetails::from_stored(&address_details_encoded)?;
+        for_addresses.insert(address_key, address_details.store());
+    }
+    with_history_entry(
+        storage,
+        TrDbCold::new()
+            .set_addresses(for_addresses)
+            .set_settings(settings),
+        migrated,
+    )
+}
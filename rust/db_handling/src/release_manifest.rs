@@ -0,0 +1,114 @@
+//! Manifest of the cold release database
+//!
+//! Cold release database is generated on the active side and is copied
+//! verbatim into Signer during the build. The bytes of the sled database depend
+//! on the insertion order and on the sled internals, and could not be compared
+//! directly with the approved release content.
+//!
+//! [`ReleaseManifest`] lists every entry of [`SPECSTREE`], [`VERIFIERS`],
+//! [`METATREE`] and [`SETTREE`] trees of the cold release database, with the
+//! hexadecimal key and the `blake2_256` hash of the entry value. Trees are
+//! listed in fixed order, entries within each tree are sorted by key, so that
+//! the manifest depends only on the database content.
+//!
+//! Manifest text has a line `<tree name> <key hex> <value hash hex>` for each
+//! entry, followed by the line `digest <digest hex>`. Digest is `blake2_256`
+//! hash of all entry lines, including the line breaks, and could be signed to
+//! approve the release database content.
+use constants::{METATREE, SETTREE, SPECSTREE, VERIFIERS};
+use sp_core::blake2_256;
+use std::path::Path;
+
+use crate::storage::{SledStorage, Storage};
+use crate::Result;
+
+/// Trees of the cold release database covered by the manifest, in the order
+/// they are listed.
+const MANIFEST_TREES: [&[u8]; 4] = [SPECSTREE, VERIFIERS, METATREE, SETTREE];
+
+/// Single cold release database entry, as recorded in manifest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestEntry {
+    /// tree name
+    pub tree: String,
+
+    /// entry key
+    pub key: Vec<u8>,
+
+    /// `blake2_256` hash of the entry value
+    pub value_hash: [u8; 32],
+}
+
+impl ManifestEntry {
+    /// Manifest line for the entry.
+    pub fn line(&self) -> String {
+        format!(
+            "{} {} {}",
+            self.tree,
+            hex::encode(&self.key),
+            hex::encode(self.value_hash)
+        )
+    }
+}
+
+/// Manifest of the cold release database.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReleaseManifest {
+    entries: Vec<ManifestEntry>,
+}
+
+impl ReleaseManifest {
+    /// Collect manifest from the cold database at given path.
+    pub fn from_cold_db<P>(db_path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Self::from_cold_db_in(&SledStorage::open(db_path)?)
+    }
+
+    /// Same as [`from_cold_db`](Self::from_cold_db), for the database in given
+    /// [`Storage`].
+    pub fn from_cold_db_in<S>(storage: &S) -> Result<Self>
+    where
+        S: Storage,
+    {
+        let mut entries: Vec<ManifestEntry> = Vec::new();
+        for tree_name in MANIFEST_TREES {
+            for (key, value) in storage.iter(tree_name)? {
+                entries.push(ManifestEntry {
+                    tree: String::from_utf8_lossy(tree_name).to_string(),
+                    key,
+                    value_hash: blake2_256(&value),
+                })
+            }
+        }
+        Ok(Self { entries })
+    }
+
+    /// Manifest entries.
+    pub fn entries(&self) -> &[ManifestEntry] {
+        &self.entries
+    }
+
+    /// Manifest digest, `blake2_256` hash of all entry lines.
+    pub fn digest(&self) -> [u8; 32] {
+        blake2_256(self.entries_text().as_bytes())
+    }
+
+    /// Manifest in text form, as written into manifest file.
+    pub fn to_text(&self) -> String {
+        format!(
+            "{}digest {}\n",
+            self.entries_text(),
+            hex::encode(self.digest())
+        )
+    }
+
+    /// Entry lines, each followed by a line break.
+    fn entries_text(&self) -> String {
+        self.entries
+            .iter()
+            .map(|x| format!("{}\n", x.line()))
+            .collect()
+    }
+}
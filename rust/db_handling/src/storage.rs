@@ -0,0 +1,290 @@
+//! Storage backends for the databases
+//!
+//! Database content is organized in named trees, each with binary keys and
+//! binary values. Access to the database goes through [`Storage`] trait, so
+//! that the database functions do not depend on the storage engine.
+//!
+//! Two storage backends are available:
+//!
+//! - [`SledStorage`], on-disk [`sled`] database, used by Signer and by the
+//! active side tools
+//! - [`MemoryStorage`], in-memory database, used mostly in tests
+//!
+//! Database functions with `db_path` input operate on [`SledStorage`] opened
+//! at this path, and have equivalents with `_in` suffix, that operate on any
+//! [`Storage`].
+//!
+//! Keys and values are plain bytes, independent of the storage engine.
+//!
+//! Changes are made through [`Batch`] sets, with all tree batches applied in a
+//! single atomic transaction.
+use sled::{transaction::TransactionResult, Db, IVec, Transactional, Tree};
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap},
+    hash::{Hash, Hasher},
+    path::Path,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use crate::helpers::{open_db, open_tree};
+use crate::Result;
+
+/// Database entry, binary key and binary value.
+pub type StorageEntry = (Vec<u8>, Vec<u8>);
+
+/// Database entry as `IVec` pair, as expected by the entry processing in
+/// `definitions`.
+pub(crate) fn sled_entry((key, value): StorageEntry) -> (IVec, IVec) {
+    (key.into(), value.into())
+}
+
+/// Set of changes to be applied to a single tree.
+///
+/// Same as [`sled::Batch`], the last change for the key is the one that gets
+/// applied.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Batch {
+    /// `Some(_)` for the value to be inserted, `None` for removal
+    writes: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+}
+
+impl Batch {
+    /// Set a key to a new value.
+    pub fn insert<K, V>(&mut self, key: K, value: V)
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.writes
+            .insert(key.as_ref().to_vec(), Some(value.as_ref().to_vec()));
+    }
+
+    /// Remove a key.
+    pub fn remove<K>(&mut self, key: K)
+    where
+        K: AsRef<[u8]>,
+    {
+        self.writes.insert(key.as_ref().to_vec(), None);
+    }
+
+    /// Check if [`Batch`] makes no changes.
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+
+    /// Changes made by [`Batch`], ordered by key, with `None` for removal.
+    pub(crate) fn changes(&self) -> impl Iterator<Item = (&[u8], Option<&[u8]>)> {
+        self.writes
+            .iter()
+            .map(|(key, value)| (&key[..], value.as_deref()))
+    }
+}
+
+impl From<&Batch> for sled::Batch {
+    fn from(batch: &Batch) -> Self {
+        let mut out = sled::Batch::default();
+        for (key, value) in batch.writes.iter() {
+            match value {
+                Some(value) => out.insert(&key[..], &value[..]),
+                None => out.remove(&key[..]),
+            }
+        }
+        out
+    }
+}
+
+/// Database storage backend.
+pub trait Storage {
+    /// Get value for a key in the tree, `None` if the key is not in the tree.
+    fn get(&self, tree_name: &[u8], key: &[u8]) -> Result<Option<Vec<u8>>>;
+
+    /// Get all entries in the tree with the keys starting with given prefix,
+    /// ordered by key.
+    fn scan_prefix(&self, tree_name: &[u8], prefix: &[u8]) -> Result<Vec<StorageEntry>>;
+
+    /// Apply batches to corresponding trees, in a single atomic transaction.
+    fn apply(&self, batches: &[(&[u8], &Batch)]) -> Result<()>;
+
+    /// Checksum of the whole storage content.
+    fn checksum(&self) -> Result<u32>;
+
+    /// Get all entries in the tree, ordered by key.
+    fn iter(&self, tree_name: &[u8]) -> Result<Vec<StorageEntry>> {
+        self.scan_prefix(tree_name, &[])
+    }
+
+    /// Number of entries in the tree.
+    fn len(&self, tree_name: &[u8]) -> Result<usize> {
+        Ok(self.iter(tree_name)?.len())
+    }
+}
+
+/// Reference to a storage is a storage too, so that storage trait objects
+/// could be used with the functions generic over [`Storage`].
+impl<T> Storage for &T
+where
+    T: Storage + ?Sized,
+{
+    fn get(&self, tree_name: &[u8], key: &[u8]) -> Result<Option<Vec<u8>>> {
+        (**self).get(tree_name, key)
+    }
+
+    fn scan_prefix(&self, tree_name: &[u8], prefix: &[u8]) -> Result<Vec<StorageEntry>> {
+        (**self).scan_prefix(tree_name, prefix)
+    }
+
+    fn apply(&self, batches: &[(&[u8], &Batch)]) -> Result<()> {
+        (**self).apply(batches)
+    }
+
+    fn checksum(&self) -> Result<u32> {
+        (**self).checksum()
+    }
+
+    fn iter(&self, tree_name: &[u8]) -> Result<Vec<StorageEntry>> {
+        (**self).iter(tree_name)
+    }
+
+    fn len(&self, tree_name: &[u8]) -> Result<usize> {
+        (**self).len(tree_name)
+    }
+}
+
+/// On-disk storage, [`sled`] database.
+pub struct SledStorage {
+    database: Db,
+}
+
+impl SledStorage {
+    /// Open [`sled`] database at given path.
+    ///
+    /// Same as [`open_db`], the database with schema version newer than
+    /// supported is refused.
+    pub fn open<P>(db_path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(Self {
+            database: open_db(db_path)?,
+        })
+    }
+
+    /// Underlying [`sled`] database.
+    pub fn database(&self) -> &Db {
+        &self.database
+    }
+}
+
+impl Storage for SledStorage {
+    fn get(&self, tree_name: &[u8], key: &[u8]) -> Result<Option<Vec<u8>>> {
+        Ok(open_tree(&self.database, tree_name)?
+            .get(key)?
+            .map(|value| value.to_vec()))
+    }
+
+    fn scan_prefix(&self, tree_name: &[u8], prefix: &[u8]) -> Result<Vec<StorageEntry>> {
+        open_tree(&self.database, tree_name)?
+            .scan_prefix(prefix)
+            .map(|entry| {
+                let (key, value) = entry?;
+                Ok((key.to_vec(), value.to_vec()))
+            })
+            .collect()
+    }
+
+    fn apply(&self, batches: &[(&[u8], &Batch)]) -> Result<()> {
+        let trees = batches
+            .iter()
+            .map(|(tree_name, _)| open_tree(&self.database, tree_name))
+            .collect::<Result<Vec<Tree>>>()?;
+        let sled_batches: Vec<sled::Batch> = batches
+            .iter()
+            .map(|(_, batch)| sled::Batch::from(*batch))
+            .collect();
+        let res: TransactionResult<(), sled::Error> = trees.as_slice().transaction(|tx_trees| {
+            for (tx_tree, batch) in tx_trees.iter().zip(sled_batches.iter()) {
+                tx_tree.apply_batch(batch)?;
+                tx_tree.flush();
+            }
+            Ok(())
+        });
+        Ok(res?)
+    }
+
+    fn checksum(&self) -> Result<u32> {
+        Ok(self.database.checksum()?)
+    }
+
+    fn len(&self, tree_name: &[u8]) -> Result<usize> {
+        Ok(open_tree(&self.database, tree_name)?.len())
+    }
+}
+
+/// In-memory trees, by tree name.
+type MemoryTrees = BTreeMap<Vec<u8>, BTreeMap<Vec<u8>, Vec<u8>>>;
+
+/// In-memory storage.
+///
+/// Content is lost when [`MemoryStorage`] is dropped.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    trees: Mutex<MemoryTrees>,
+}
+
+impl MemoryStorage {
+    /// Construct new empty [`MemoryStorage`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn trees(&self) -> MutexGuard<MemoryTrees> {
+        self.trees.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn get(&self, tree_name: &[u8], key: &[u8]) -> Result<Option<Vec<u8>>> {
+        Ok(self
+            .trees()
+            .get(tree_name)
+            .and_then(|tree| tree.get(key))
+            .cloned())
+    }
+
+    fn scan_prefix(&self, tree_name: &[u8], prefix: &[u8]) -> Result<Vec<StorageEntry>> {
+        Ok(self
+            .trees()
+            .get(tree_name)
+            .map(|tree| {
+                tree.range(prefix.to_vec()..)
+                    .take_while(|(key, _)| key.starts_with(prefix))
+                    .map(|(key, value)| (key.to_owned(), value.to_owned()))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    fn apply(&self, batches: &[(&[u8], &Batch)]) -> Result<()> {
+        // all batches are applied under the same lock, and applying could not
+        // fail midway
+        let mut trees = self.trees();
+        for (tree_name, batch) in batches.iter() {
+            let tree = trees.entry(tree_name.to_vec()).or_default();
+            for (key, value) in batch.writes.iter() {
+                match value {
+                    Some(value) => tree.insert(key.to_owned(), value.to_owned()),
+                    None => tree.remove(key),
+                };
+            }
+        }
+        Ok(())
+    }
+
+    /// Checksum of the content, only meaningful for comparison with other
+    /// checksums of the same storage within the process.
+    fn checksum(&self) -> Result<u32> {
+        let mut hasher = DefaultHasher::new();
+        self.trees().hash(&mut hasher);
+        Ok(hasher.finish() as u32)
+    }
+}
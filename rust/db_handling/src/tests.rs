@@ -4,7 +4,11 @@ use pretty_assertions::assert_eq;
 use constants::ALICE_SEED_PHRASE;
 
 use crate::{
-    identities::{check_derivation_set, generate_random_phrase, is_passworded},
+    identities::{
+        check_derivation_set, generate_random_phrase, is_passworded,
+        prepare_structured_derivations_import, DerivationRejection, DerivationsFormat,
+        RejectedDerivation,
+    },
     interface_signer::{guess, SeedDraft},
 };
 
@@ -207,3 +211,88 @@ fn checking_derivation_set() {
     assert!(check_derivation_set(&["no_path_at_all".to_string()]).is_err());
     assert!(check_derivation_set(&["///".to_string()]).is_err());
 }
+
+#[test]
+fn structured_derivations_json() {
+    let content = r#"[
+        {"path": "//polkadot//0", "networks": ["polkadot", "kusama"]},
+        {"path": "//secret///password", "networks": ["kusama"]},
+        {"path": "no_path_at_all", "networks": ["polkadot"]},
+        {"path": "//lonely", "networks": []},
+        {"networks": ["polkadot"]},
+        {"path": "//polkadot//0", "networks": ["kusama"]},
+        {"path": "", "networks": ["polkadot"]}
+    ]"#;
+    let out = prepare_structured_derivations_import(content, DerivationsFormat::Json).unwrap();
+    assert_eq!(
+        out.networks,
+        vec![
+            ("polkadot".to_string(), vec!["//polkadot//0".to_string()]),
+            (
+                "kusama".to_string(),
+                vec![
+                    "//polkadot//0".to_string(),
+                    "//secret///password".to_string()
+                ]
+            ),
+        ]
+    );
+    assert_eq!(
+        out.rejected,
+        vec![
+            RejectedDerivation {
+                row: 3,
+                reason: DerivationRejection::InvalidPath
+            },
+            RejectedDerivation {
+                row: 4,
+                reason: DerivationRejection::NoNetworks
+            },
+            RejectedDerivation {
+                row: 5,
+                reason: DerivationRejection::BadFormat
+            },
+            RejectedDerivation {
+                row: 6,
+                reason: DerivationRejection::Duplicate {
+                    network: "kusama".to_string()
+                }
+            },
+            RejectedDerivation {
+                row: 7,
+                reason: DerivationRejection::EmptyPath
+            },
+        ]
+    );
+    assert!(prepare_structured_derivations_import("{}", DerivationsFormat::Json).is_err());
+}
+
+#[test]
+fn structured_derivations_csv() {
+    let content = "path,networks\n//1,polkadot,westend\n\nbad,polkadot\n//2\n,polkadot\n";
+    let out = prepare_structured_derivations_import(content, DerivationsFormat::Csv).unwrap();
+    assert_eq!(
+        out.networks,
+        vec![
+            ("polkadot".to_string(), vec!["//1".to_string()]),
+            ("westend".to_string(), vec!["//1".to_string()]),
+        ]
+    );
+    assert_eq!(
+        out.rejected,
+        vec![
+            RejectedDerivation {
+                row: 4,
+                reason: DerivationRejection::InvalidPath
+            },
+            RejectedDerivation {
+                row: 5,
+                reason: DerivationRejection::NoNetworks
+            },
+            RejectedDerivation {
+                row: 6,
+                reason: DerivationRejection::EmptyPath
+            },
+        ]
+    );
+}
@@ -1,7 +1,7 @@
 #[cfg(feature = "test")]
-use pretty_assertions::{assert_eq, assert_ne};
+use parity_scale_codec::Encode;
 #[cfg(feature = "test")]
-use sled::{open, Batch, Db, Tree};
+use pretty_assertions::{assert_eq, assert_ne};
 #[cfg(feature = "test")]
 use sp_core::sr25519::Public;
 #[cfg(feature = "test")]
@@ -18,7 +18,8 @@ use constants::{
         alice_sr_secret_abracadabra, alice_sr_westend, alice_westend_root_qr,
         alice_westend_secret_qr, empty_png, types_known, westend_9000, westend_9010,
     },
-    ADDRTREE, ALICE_SEED_PHRASE, METATREE, SPECSTREE,
+    ADDRTREE, ALICE_SEED_PHRASE, DANGER, HISTORY, KEY_ANNOTATIONS, METATREE, POLICY,
+    SCHEMA_VERSION, SETTREE, SPECSTREE, STUB, TRANSACTION,
 };
 #[cfg(feature = "test")]
 use db_handling::Error;
@@ -27,74 +28,92 @@ use defaults::default_chainspecs;
 #[cfg(feature = "test")]
 use definitions::{
     crypto::Encryption,
+    danger::Exposure,
     history::{
-        all_events_preview, Entry, Event, IdentityHistory, MetaValuesDisplay, MetaValuesExport,
-        NetworkSpecsDisplay, NetworkSpecsExport, NetworkVerifierDisplay, SignDisplay,
-        SignMessageDisplay, TypesDisplay, TypesExport,
+        all_events_preview, AuditFormat, ContactDisplay, Entry, EntryLink, Event, IdentityHistory,
+        KeyAnnotationHistory, MetaValuesDisplay, MetaValuesExport, NetworkSpecsAmendmentDisplay,
+        NetworkSpecsAmendmentTokensDisplay, NetworkSpecsDisplay, NetworkSpecsExport,
+        NetworkSpecsTokensDisplay, NetworkVerifierDisplay, SignDisplay, SignMessageDisplay,
+        TypesDisplay, TypesExport,
     },
-    keyring::{AddressKey, MetaKey, MetaKeyPrefix, NetworkSpecsKey, VerifierKey},
+    keyring::{AddressKey, MetaKey, MetaKeyPrefix, NetworkSpecsKey, Order, VerifierKey},
+    localisation::LocalisedMessage,
     metadata::MetaValues,
+    metadata_proof::{ExtraInfo, ExtrinsicInfo, MetadataProof, TypeRef},
     navigation::{
         Address, DerivationCheck as NavDerivationCheck, DerivationDestination, DerivationEntry,
         DerivationPack, MBackup, MDeriveKey, MKeyDetails, MKeysCard, MMMNetwork, MMNetwork,
         MManageMetadata, MMetadataRecord, MNetworkDetails, MNetworkMenu, MRawKey, MSCNetworkInfo,
         MSeedKeyCard, MTypesInfo, MVerifier, Network, NetworkSpecsToSend, SeedNameCard,
     },
-    network_specs::{NetworkSpecs, ValidCurrentVerifier, Verifier, VerifierValue},
-    users::AddressDetails,
+    network_specs::{NetworkSpecs, TokenSpecs, ValidCurrentVerifier, Verifier, VerifierValue},
+    users::{AddressDetails, ContactEntry, KeyAnnotation},
 };
 
 #[cfg(feature = "test")]
 use db_handling::{
     cold_default::{
-        populate_cold, populate_cold_no_metadata, signer_init_no_cert, signer_init_with_cert,
+        init_db_in, populate_cold, populate_cold_in, populate_cold_no_metadata_in,
+        populate_cold_release_in, signer_init_no_cert, signer_init_no_cert_in,
+        signer_init_with_cert_in,
     },
-    db_transactions::TrDbCold,
+    db_transactions::{SignContent, TrDbCold, TrDbColdSign},
     default_cold_release, default_hot,
+    fsck::{check_db, check_db_in, repair_db_in, DbIssue},
     helpers::{
-        get_danger_status, get_general_verifier, open_db, open_tree, remove_metadata,
-        remove_network, remove_types_info, transfer_metadata_to_cold,
-        try_get_valid_current_verifier,
+        add_contact_in, get_all_networks, get_all_networks_in, get_contacts_in,
+        get_danger_exposures, get_danger_exposures_in, get_danger_status_in,
+        get_general_verifier_in, get_key_annotations_in, open_db, open_tree, remove_contact_in,
+        remove_metadata_in, remove_network_in, remove_types_info_in, set_key_label_in,
+        set_key_notes_in, set_key_tags_in, transfer_metadata_to_cold, try_get_key_annotation_in,
+        try_get_valid_current_verifier_in,
     },
     identities::{
-        create_increment_set, derivation_check, export_secret_key, get_addresses_by_seed_name,
-        remove_key, remove_seed, try_create_address, try_create_seed, DerivationCheck,
+        create_increment_set_in, derivation_check_in, export_secret_key_in,
+        get_addresses_by_seed_name, get_addresses_by_seed_name_in, remove_key_in, remove_seed_in,
+        try_create_address_in, try_create_seed_in, DerivationCheck,
     },
     interface_signer::{
-        addresses_set_seed_name_network, backup_prep, derive_prep, dynamic_path_check, export_key,
-        first_network, get_all_seed_names_with_identicons, metadata_details,
-        network_details_by_key, print_all_identities, print_identities_for_seed_name_and_network,
-        show_all_networks, show_all_networks_with_flag, show_types_status,
+        addresses_set_seed_name_network_in, backup_prep_in, derive_prep_in, dynamic_path_check_in,
+        export_key_in, first_network_in, get_all_seed_names_with_identicons_in,
+        metadata_details_in, network_details_by_key_in, print_all_identities_in,
+        print_identities_for_seed_name_and_network_in, show_all_networks_in,
+        show_all_networks_with_flag_in, show_contacts_in, show_types_status_in,
     },
     manage_history::{
-        device_was_online, enter_events, get_history, get_history_entry_by_order,
-        reset_danger_status_to_safe,
+        clear_history_in, device_exposed_in, device_was_online_in, enter_events, enter_events_in,
+        get_history, get_history_entry_by_order_in, get_history_in, history_audit_report_in,
+        history_chain_head_in, reset_danger_status_to_safe_in, search_history_in,
+        verify_history_chain, verify_history_chain_in, HistoryFilter, HistorySearchPage,
     },
+    migrations::{
+        get_schema_version, get_schema_version_in, migrate_db, migrate_db_in, LIVE_SCHEMA_VERSION,
+    },
+    storage::{Batch, MemoryStorage, Storage},
 };
 use definitions::helpers::multisigner_to_public;
 
 #[cfg(feature = "test")]
 #[test]
 fn print_seed_names() {
-    let dbname = "for_tests/print_seed_names";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
-    let cards = get_all_seed_names_with_identicons(dbname, &[String::from("Alice")]).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let cards = get_all_seed_names_with_identicons_in(&storage, &[String::from("Alice")]).unwrap();
     let expected_cards = vec![SeedNameCard {
         seed_name: "Alice".to_string(),
         identicon: alice_sr_root().to_vec(),
         derived_keys_count: 4, // "//westend", "//kusama", "//polkadot", "//Alice"
     }];
     assert!(cards == expected_cards, "\nReceived: \n{:?}", cards);
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn print_seed_names_with_orphan() {
-    let dbname = "for_tests/print_seed_names_with_orphan";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
-    let cards = get_all_seed_names_with_identicons(
-        dbname,
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let cards = get_all_seed_names_with_identicons_in(
+        &storage,
         &[String::from("Alice"), String::from("BobGhost")],
     )
     .unwrap();
@@ -112,15 +131,14 @@ fn print_seed_names_with_orphan() {
         },
     ];
     assert!(cards == expected_cards, "\nReceived: \n{:?}", cards);
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn print_all_ids() {
-    let dbname = "for_tests/print_all_ids";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
-    let keys = print_all_identities(dbname).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let keys = print_all_identities_in(&storage).unwrap();
 
     let expected_keys = vec![
         MRawKey {
@@ -181,16 +199,15 @@ fn print_all_ids() {
     ];
 
     assert_eq!(keys, expected_keys);
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn print_ids_seed_name_network() {
-    let dbname = "for_tests/print_ids_seed_name_network";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
-    let cards = print_identities_for_seed_name_and_network(
-        dbname,
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let cards = print_identities_for_seed_name_and_network_in(
+        &storage,
         "Alice",
         &NetworkSpecsKey::from_parts(
             &H256::from_str("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e")
@@ -223,6 +240,8 @@ fn print_ids_seed_name_network() {
                 swiped: false,
                 multiselect: false,
                 secret_exposed: false,
+                usage: None,
+                annotation: None,
             },
             MKeysCard {
                 address_key: "01d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
@@ -234,21 +253,22 @@ fn print_ids_seed_name_network() {
                 swiped: false,
                 multiselect: false,
                 secret_exposed: false,
+                usage: None,
+                annotation: None,
             },
         ],
     );
     // TODO: "network":{"title":"Westend","logo":"westend"}"#;
     assert_eq!((cards.0, cards.1), expected_cards);
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn print_show_all_networks_flag_westend() {
-    let dbname = "for_tests/show_all_networks_flag_westend";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
-    let menu = show_all_networks_with_flag(
-        dbname,
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let menu = show_all_networks_with_flag_in(
+        &storage,
         &NetworkSpecsKey::from_parts(
             &H256::from_str("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e")
                 .unwrap(),
@@ -285,15 +305,14 @@ fn print_show_all_networks_flag_westend() {
         ],
     };
     assert_eq!(menu, expected_menu);
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn show_all_networks_no_flag() {
-    let dbname = "for_tests/show_all_networks_no_flag";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
-    let networks = show_all_networks(dbname).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let networks = show_all_networks_in(&storage).unwrap();
     let expected_networks = vec![
         MMNetwork {
             key: "0191b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3".to_string(),
@@ -315,31 +334,29 @@ fn show_all_networks_no_flag() {
         },
     ];
     assert_eq!(networks, expected_networks);
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn first_standard_network() {
-    let dbname = "for_tests/first_standard_network";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
-    let specs = first_network(dbname).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let specs = first_network_in(&storage).unwrap();
     assert_eq!(specs.name, "polkadot");
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn export_alice_westend() {
-    let dbname = "for_tests/export_alice_westend";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
     let public: [u8; 32] =
         hex::decode("46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a")
             .unwrap()
             .try_into()
             .unwrap();
-    let key = export_key(
-        dbname,
+    let key = export_key_in(
+        &storage,
         &MultiSigner::Sr25519(Public::from_raw(public)),
         "Alice",
         &NetworkSpecsKey::from_parts(
@@ -360,6 +377,7 @@ fn export_alice_westend() {
             has_pwd: false,
             multiselect: None,
             secret_exposed: false,
+            annotation: None,
         },
         network_info: MSCNetworkInfo {
             network_title: "Westend".to_string(),
@@ -367,17 +385,17 @@ fn export_alice_westend() {
             network_specs_key: "01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"
                 .to_string(),
         },
+        usage: None,
     };
     assert_eq!(key, expected_key);
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn backup_prep_alice() {
-    let dbname = "for_tests/backup_prep_alice";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
-    let backup = backup_prep(dbname, "Alice").unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let backup = backup_prep_in(&storage, "Alice").unwrap();
     let expected_backup = MBackup {
         seed_name: "Alice".to_string(),
         derivations: vec![
@@ -433,16 +451,15 @@ fn backup_prep_alice() {
         ],
     };
     assert_eq!(backup, expected_backup);
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn derive_prep_alice() {
-    let dbname = "for_tests/derive_prep_alice";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
-    let key = derive_prep(
-        dbname,
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let key = derive_prep_in(
+        &storage,
         "Alice",
         &NetworkSpecsKey::from_parts(
             &H256::from_str("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e")
@@ -469,14 +486,13 @@ fn derive_prep_alice() {
         },
     };
     assert_eq!(key, expected_key);
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn derive_prep_alice_collided() {
-    let dbname = "for_tests/derive_prep_alice_collided";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
     let network_specs_key = NetworkSpecsKey::from_parts(
         &H256::from_str("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e")
             .unwrap(),
@@ -484,7 +500,7 @@ fn derive_prep_alice_collided() {
     );
     let mut collision = None;
     for (multisigner, address_details) in
-        addresses_set_seed_name_network(dbname, "Alice", &network_specs_key)
+        addresses_set_seed_name_network_in(&storage, "Alice", &network_specs_key)
             .unwrap()
             .into_iter()
     {
@@ -497,8 +513,8 @@ fn derive_prep_alice_collided() {
         Some(a) => a,
         None => panic!("Did not create address?"),
     };
-    let key = derive_prep(
-        dbname,
+    let key = derive_prep_in(
+        &storage,
         "Alice",
         &network_specs_key,
         Some(collision),
@@ -525,35 +541,35 @@ fn derive_prep_alice_collided() {
                 seed_name: "Alice".to_string(),
                 multiselect: None,
                 secret_exposed: false,
+                annotation: None,
             }),
             error: None,
         },
     };
     assert_eq!(key, expected_key);
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn derive_prep_alice_collided_with_password() {
-    let dbname = "for_tests/derive_prep_alice_collided_with_password";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
     let network_specs_key = NetworkSpecsKey::from_parts(
         &H256::from_str("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e")
             .unwrap(),
         &Encryption::Sr25519,
     );
-    try_create_address(
+    try_create_address_in(
         "Alice",
         ALICE_SEED_PHRASE,
         "//secret///abracadabra",
         &network_specs_key,
-        dbname,
+        &storage,
     )
     .unwrap();
     let mut collision = None;
     for (multisigner, address_details) in
-        addresses_set_seed_name_network(dbname, "Alice", &network_specs_key)
+        addresses_set_seed_name_network_in(&storage, "Alice", &network_specs_key)
             .unwrap()
             .into_iter()
     {
@@ -566,8 +582,8 @@ fn derive_prep_alice_collided_with_password() {
         Some(a) => a,
         None => panic!("Did not create address?"),
     };
-    let key = derive_prep(
-        dbname,
+    let key = derive_prep_in(
+        &storage,
         "Alice",
         &network_specs_key,
         Some(collision),
@@ -594,21 +610,21 @@ fn derive_prep_alice_collided_with_password() {
                 seed_name: "Alice".to_string(),
                 multiselect: None,
                 secret_exposed: false,
+                annotation: None,
             }),
             error: None,
         },
     };
     assert_eq!(key, expected_key);
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn westend_network_details() {
-    let dbname = "for_tests/westend_network_details";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
-    let details = network_details_by_key(
-        dbname,
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let details = network_details_by_key_in(
+        &storage,
         &NetworkSpecsKey::from_parts(
             &H256::from_str("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e")
                 .unwrap(),
@@ -658,16 +674,15 @@ fn westend_network_details() {
         ],
     };
     assert_eq!(details, expected_details);
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn westend_9010_metadata_details() {
-    let dbname = "for_tests/westend_9010_metadata_details";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
-    let network = metadata_details(
-        dbname,
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let network = metadata_details_in(
+        &storage,
         &NetworkSpecsKey::from_parts(
             &H256::from_str("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e")
                 .unwrap(),
@@ -689,16 +704,15 @@ fn westend_9010_metadata_details() {
         }],
     };
     assert_eq!(network, expected_network);
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn types_status_and_history() {
-    let dbname = "for_tests/types_status_and_history";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
 
-    let types = show_types_status(dbname).unwrap();
+    let types = show_types_status_in(&storage).unwrap();
     let mut expected_types = MTypesInfo {
         types_on_file: true,
         types_hash: Some(
@@ -708,15 +722,15 @@ fn types_status_and_history() {
     };
     assert_eq!(types, expected_types);
 
-    remove_types_info(dbname).unwrap();
-    let types = show_types_status(dbname).unwrap();
+    remove_types_info_in(&storage).unwrap();
+    let types = show_types_status_in(&storage).unwrap();
     expected_types.types_on_file = false;
     expected_types.types_hash = None;
     expected_types.types_id_pic = None;
 
     assert_eq!(types, expected_types);
 
-    let history_printed = get_history(dbname).unwrap();
+    let history_printed = get_history_in(&storage).unwrap();
     let expected_element = Event::TypesRemoved {
         types_display: TypesDisplay {
             types_hash: H256::from_str(
@@ -729,17 +743,15 @@ fn types_status_and_history() {
     assert!(history_printed
         .iter()
         .any(|h| h.1.events.contains(&expected_element)));
-
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn path_is_known() {
-    let dbname = "for_tests/path_is_known";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
-    let check = dynamic_path_check(
-        dbname,
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let check = dynamic_path_check_in(
+        &storage,
         "Alice",
         "//Alice",
         "01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e",
@@ -755,20 +767,20 @@ fn path_is_known() {
             seed_name: "Alice".to_string(),
             multiselect: None,
             secret_exposed: false,
+            annotation: None,
         }),
         error: None,
     };
     assert_eq!(check, expected_check);
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn path_is_unknown() {
-    let dbname = "for_tests/path_is_unknown";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
-    let check = dynamic_path_check(
-        dbname,
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let check = dynamic_path_check_in(
+        &storage,
         "Alice",
         "//secret",
         "01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e",
@@ -780,16 +792,15 @@ fn path_is_unknown() {
         error: None,
     };
     assert_eq!(check, expected_check);
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn path_is_unknown_passworded() {
-    let dbname = "for_tests/path_is_unknown_passworded";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
-    let check = dynamic_path_check(
-        dbname,
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let check = dynamic_path_check_in(
+        &storage,
         "Alice",
         "//secret///abracadabra",
         "01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e",
@@ -801,45 +812,522 @@ fn path_is_unknown_passworded() {
         error: None,
     };
     assert_eq!(check, expected_check);
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn get_danger_status_properly() {
-    let dbname = "for_tests/get_danger_status_properly";
-    default_cold_release(Some(PathBuf::from(dbname))).unwrap();
-    signer_init_no_cert(dbname).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_release_in(&storage).unwrap();
+    signer_init_no_cert_in(&storage).unwrap();
     assert!(
-        !get_danger_status(dbname).unwrap(),
+        !get_danger_status_in(&storage).unwrap(),
         "Expected danger status = false after the database initiation."
     );
-    device_was_online(dbname).unwrap();
+    device_was_online_in(&storage).unwrap();
     assert!(
-        get_danger_status(dbname).unwrap(),
+        get_danger_status_in(&storage).unwrap(),
         "Expected danger status = true after the reported exposure."
     );
-    reset_danger_status_to_safe(dbname).unwrap();
+    reset_danger_status_to_safe_in(&storage).unwrap();
     assert!(
-        !get_danger_status(dbname).unwrap(),
+        !get_danger_status_in(&storage).unwrap(),
         "Expected danger status = false after the danger reset."
     );
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn danger_exposures_recorded() {
+    let storage = MemoryStorage::new();
+    populate_cold_release_in(&storage).unwrap();
+    signer_init_no_cert_in(&storage).unwrap();
+    device_exposed_in(&storage, Exposure::UsbDebugging).unwrap();
+    device_was_online_in(&storage).unwrap();
+    device_exposed_in(&storage, Exposure::UsbDebugging).unwrap();
+    let exposures: Vec<Exposure> = get_danger_exposures_in(&storage)
+        .unwrap()
+        .into_iter()
+        .map(|exposure_record| {
+            assert!(exposure_record.first_seen.is_some());
+            exposure_record.exposure
+        })
+        .collect();
+    assert_eq!(
+        exposures,
+        vec![Exposure::UsbDebugging, Exposure::DeviceWasOnline]
+    );
+    let entries: Vec<Entry> = get_history_in(&storage)
+        .unwrap()
+        .into_iter()
+        .map(|(_, entry)| entry)
+        .collect();
+    assert!(entries_contain_event(
+        &entries,
+        &Event::UsbDebuggingWasEnabled
+    ));
+    assert!(entries_contain_event(&entries, &Event::DeviceWasOnline));
+    reset_danger_status_to_safe_in(&storage).unwrap();
+    assert!(get_danger_exposures_in(&storage).unwrap().is_empty());
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn schema_migrations() {
+    let dbname = "for_tests/schema_migrations";
+    default_cold_release(Some(PathBuf::from(dbname))).unwrap();
+    signer_init_no_cert(dbname).unwrap();
+    assert_eq!(get_schema_version(dbname).unwrap(), LIVE_SCHEMA_VERSION);
+    assert!(migrate_db(dbname, false).unwrap().is_empty());
+
+    // database as it was before the schema versioning
+    let mut settings_batch = Batch::default();
+    settings_batch.remove(SCHEMA_VERSION);
+    settings_batch.remove(POLICY);
+    settings_batch.insert(DANGER, true.encode());
+    TrDbCold::new()
+        .set_settings(settings_batch)
+        .apply(dbname)
+        .unwrap();
+    assert_eq!(get_schema_version(dbname).unwrap(), 0);
+
+    let reports = migrate_db(dbname, true).unwrap();
+    assert_eq!(
+        reports
+            .iter()
+            .map(|report| (report.from_version, report.to_version))
+            .collect::<Vec<_>>(),
+        vec![(0, 1), (1, 2), (2, 3), (3, 4)]
+    );
+    assert_eq!(get_schema_version(dbname).unwrap(), 0);
+
+    assert_eq!(migrate_db(dbname, false).unwrap(), reports);
+    assert_eq!(get_schema_version(dbname).unwrap(), LIVE_SCHEMA_VERSION);
+    {
+        let database = open_db(dbname).unwrap();
+        let settings = open_tree(&database, SETTREE).unwrap();
+        assert!(settings.get(POLICY).unwrap().is_some());
+    }
+    let exposures = get_danger_exposures(dbname).unwrap();
+    assert_eq!(exposures.len(), 1);
+    assert_eq!(exposures[0].exposure, Exposure::DeviceWasOnline);
+    assert!(exposures[0].first_seen.is_none());
+    let entries: Vec<Entry> = get_history(dbname)
+        .unwrap()
+        .into_iter()
+        .map(|(_, entry)| entry)
+        .collect();
+    assert!(entries_contain_event(
+        &entries,
+        &Event::DatabaseMigrated {
+            from_version: 3,
+            to_version: 4,
+        }
+    ));
+    verify_history_chain(dbname).unwrap();
+
+    // database from a newer Signer is refused
+    let mut settings_batch = Batch::default();
+    settings_batch.insert(SCHEMA_VERSION, (LIVE_SCHEMA_VERSION + 1).encode());
+    TrDbCold::new()
+        .set_settings(settings_batch)
+        .apply(dbname)
+        .unwrap();
+    assert!(matches!(
+        open_db(dbname),
+        Err(Error::SchemaVersionTooNew { found, supported })
+            if found == LIVE_SCHEMA_VERSION + 1 && supported == LIVE_SCHEMA_VERSION
+    ));
+    assert!(migrate_db(dbname, true).is_err());
     fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
-fn display_general_verifier_properly() {
-    let dbname = "for_tests/display_general_verifier_properly";
+fn schema_migrations_in_memory() {
+    let storage = MemoryStorage::new();
+    populate_cold_release_in(&storage).unwrap();
+    signer_init_no_cert_in(&storage).unwrap();
+    assert_eq!(
+        get_schema_version_in(&storage).unwrap(),
+        LIVE_SCHEMA_VERSION
+    );
+
+    let mut settings_batch = Batch::default();
+    settings_batch.remove(SCHEMA_VERSION);
+    settings_batch.remove(POLICY);
+    settings_batch.insert(DANGER, true.encode());
+    TrDbCold::new()
+        .set_settings(settings_batch)
+        .apply_in(&storage)
+        .unwrap();
+
+    let reports = migrate_db_in(&storage, true).unwrap();
+    assert_eq!(reports.len(), LIVE_SCHEMA_VERSION as usize);
+    assert_eq!(get_schema_version_in(&storage).unwrap(), 0);
+    assert_eq!(migrate_db_in(&storage, false).unwrap(), reports);
+    assert_eq!(
+        get_schema_version_in(&storage).unwrap(),
+        LIVE_SCHEMA_VERSION
+    );
+    assert!(storage.get(SETTREE, POLICY).unwrap().is_some());
+    verify_history_chain_in(&storage).unwrap();
+
+    let mut settings_batch = Batch::default();
+    settings_batch.insert(SCHEMA_VERSION, (LIVE_SCHEMA_VERSION + 1).encode());
+    TrDbCold::new()
+        .set_settings(settings_batch)
+        .apply_in(&storage)
+        .unwrap();
+    assert!(matches!(
+        migrate_db_in(&storage, true),
+        Err(Error::SchemaVersionTooNew { found, supported })
+            if found == LIVE_SCHEMA_VERSION + 1 && supported == LIVE_SCHEMA_VERSION
+    ));
+}
+
+/// Rewrite database in the format of the baseline Signer, before the schema
+/// versioning was introduced: no schema version and no security policy
+/// records, legacy danger record, history log entries without links and
+/// address details without key usage statistics.
+#[cfg(feature = "test")]
+fn rewrite_as_baseline(dbname: &str) {
+    let mut settings_batch = Batch::default();
+    settings_batch.remove(SCHEMA_VERSION);
+    settings_batch.remove(POLICY);
+    settings_batch.insert(DANGER, true.encode());
+    let mut history_batch = Batch::default();
+    for (order, entry) in get_history(dbname).unwrap() {
+        history_batch.insert(order.store(), (&entry.timestamp, &entry.events).encode());
+    }
+    let mut addresses_batch = Batch::default();
+    for (multisigner, address_details) in get_addresses_by_seed_name(dbname, "Alice").unwrap() {
+        addresses_batch.insert(
+            AddressKey::from_multisigner(&multisigner).key(),
+            (
+                &address_details.seed_name,
+                &address_details.path,
+                address_details.has_pwd,
+                &address_details.network_id,
+                &address_details.encryption,
+                address_details.secret_exposed,
+            )
+                .encode(),
+        );
+    }
+    TrDbCold::new()
+        .set_settings(settings_batch)
+        .set_history(history_batch)
+        .set_addresses(addresses_batch)
+        .apply(dbname)
+        .unwrap();
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn migrate_baseline_database() {
+    let dbname = "for_tests/migrate_baseline_database";
+    populate_cold(dbname, Verifier { v: None }).unwrap();
+    enter_events(
+        dbname,
+        vec![Event::UserEntry {
+            user_entry: "Before the upgrade".to_string(),
+        }],
+    )
+    .unwrap();
+    let known_addresses = get_addresses_by_seed_name(dbname, "Alice").unwrap();
+    let known_history = get_history(dbname).unwrap();
+
+    rewrite_as_baseline(dbname);
+    assert_eq!(get_schema_version(dbname).unwrap(), 0);
+    assert!(get_history(dbname)
+        .unwrap()
+        .iter()
+        .all(|(_, entry)| entry.link.is_none()));
+    open_db(dbname).unwrap();
+
+    let reports = migrate_db(dbname, false).unwrap();
+    assert_eq!(reports.len(), LIVE_SCHEMA_VERSION as usize);
+    assert_eq!(get_schema_version(dbname).unwrap(), LIVE_SCHEMA_VERSION);
+    assert!(check_db(dbname).unwrap().is_empty());
+
+    // history log is chained from the genesis marker, with all baseline
+    // entries kept and migrations recorded after them
+    verify_history_chain(dbname).unwrap();
+    let history = get_history(dbname).unwrap();
+    assert_eq!(
+        history.len(),
+        known_history.len() + LIVE_SCHEMA_VERSION as usize
+    );
+    for (order, entry) in known_history.iter() {
+        let (_, migrated) = history
+            .iter()
+            .find(|(migrated_order, _)| migrated_order.stamp() == order.stamp())
+            .unwrap();
+        assert_eq!(migrated.timestamp, entry.timestamp);
+        assert_eq!(migrated.events, entry.events);
+    }
+    let (_, genesis) = history.last().unwrap();
+    assert_eq!(
+        genesis.link,
+        Some(EntryLink::Genesis {
+            cleared_head: H256::zero(),
+            cleared_entries: 0,
+        })
+    );
+    let (_, head) = history.first().unwrap();
+    assert_eq!(
+        head.events,
+        vec![Event::DatabaseMigrated {
+            from_version: LIVE_SCHEMA_VERSION - 1,
+            to_version: LIVE_SCHEMA_VERSION,
+        }]
+    );
+
+    // address details are stored in the current format
+    assert_eq!(
+        get_addresses_by_seed_name(dbname, "Alice").unwrap(),
+        known_addresses
+    );
+    {
+        let database = open_db(dbname).unwrap();
+        let identities = open_tree(&database, ADDRTREE).unwrap();
+        for (multisigner, address_details) in known_addresses.iter() {
+            let stored = identities
+                .get(AddressKey::from_multisigner(multisigner).key())
+                .unwrap()
+                .unwrap();
+            assert_eq!(stored.to_vec(), address_details.store());
+        }
+    }
+
+    let exposures = get_danger_exposures(dbname).unwrap();
+    assert_eq!(exposures.len(), 1);
+    assert_eq!(exposures[0].exposure, Exposure::DeviceWasOnline);
+    fs::remove_dir_all(dbname).unwrap();
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn memory_storage_batches() {
+    let storage = MemoryStorage::new();
+    let mut batch = Batch::default();
+    batch.insert(b"ab", b"1");
+    batch.insert(b"ac", b"2");
+    batch.insert(b"b", b"3");
+    batch.remove(b"ac");
+    storage.apply(&[(SETTREE, &batch)]).unwrap();
+    assert_eq!(
+        storage.scan_prefix(SETTREE, b"a").unwrap(),
+        vec![(b"ab".to_vec(), b"1".to_vec())]
+    );
+    assert_eq!(storage.len(SETTREE).unwrap(), 2);
+    assert!(storage.get(SETTREE, b"ac").unwrap().is_none());
+    assert!(storage.iter(HISTORY).unwrap().is_empty());
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn memory_storage_matches_sled() {
+    let dbname = "for_tests/memory_storage_matches_sled";
     default_cold_release(Some(PathBuf::from(dbname))).unwrap();
     signer_init_no_cert(dbname).unwrap();
-    let verifier = get_general_verifier(dbname).unwrap();
+
+    let storage = MemoryStorage::new();
+    populate_cold_release_in(&storage).unwrap();
+    init_db_in(&storage, Verifier { v: None }).unwrap();
+
+    assert_eq!(
+        get_all_networks_in(&storage).unwrap(),
+        get_all_networks(dbname).unwrap()
+    );
+    assert_eq!(
+        get_general_verifier_in(&storage).unwrap(),
+        Verifier { v: None }
+    );
+    assert!(get_danger_exposures_in(&storage).unwrap().is_empty());
+    let history = get_history_in(&storage).unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(
+        history[0].1.events,
+        vec![
+            Event::DatabaseInitiated,
+            Event::GeneralVerifierSet {
+                verifier: Verifier { v: None }
+            }
+        ]
+    );
+
+    let checksum = storage.checksum().unwrap();
+    enter_events_in(
+        &storage,
+        vec![Event::UserEntry {
+            user_entry: "in memory".to_string(),
+        }],
+    )
+    .unwrap();
+    assert_ne!(storage.checksum().unwrap(), checksum);
+    assert_eq!(get_history_in(&storage).unwrap().len(), 2);
+    fs::remove_dir_all(dbname).unwrap();
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn check_and_repair_db() {
+    let storage = MemoryStorage::new();
+    populate_cold_release_in(&storage).unwrap();
+    init_db_in(&storage, Verifier { v: None }).unwrap();
+    assert!(check_db_in(&storage).unwrap().is_empty());
+
+    let westend_specs = get_all_networks_in(&storage)
+        .unwrap()
+        .into_iter()
+        .find(|specs| specs.name == "westend")
+        .unwrap();
+    let westend_specs_key =
+        NetworkSpecsKey::from_parts(&westend_specs.genesis_hash, &westend_specs.encryption);
+    let address_key =
+        AddressKey::from_multisigner(&MultiSigner::Sr25519(Public::from_raw([1; 32])));
+    let address_details = AddressDetails {
+        seed_name: "Alice".to_string(),
+        path: "//westend".to_string(),
+        has_pwd: false,
+        network_id: vec![westend_specs_key.to_owned()],
+        encryption: Encryption::Sr25519,
+        secret_exposed: false,
+        usage: Vec::new(),
+    };
+    let key_annotation = KeyAnnotation {
+        label: "westend key".to_string(),
+        ..Default::default()
+    };
+
+    // westend specs removed on their own, leaving behind the address,
+    // metadata and verifier; temporary transaction entry left as well
+    let mut address_batch = Batch::default();
+    address_batch.insert(address_key.key(), address_details.store());
+    let mut key_annotations_batch = Batch::default();
+    key_annotations_batch.insert(address_key.key(), key_annotation.encode());
+    let mut network_specs_batch = Batch::default();
+    network_specs_batch.remove(westend_specs_key.key());
+    let mut transaction_batch = Batch::default();
+    transaction_batch.insert(STUB, b"leftover");
+    TrDbCold::new()
+        .set_addresses(address_batch)
+        .set_key_annotations(key_annotations_batch)
+        .set_network_specs(network_specs_batch)
+        .set_transaction(transaction_batch)
+        .apply_in(&storage)
+        .unwrap();
+
+    let issues = check_db_in(&storage).unwrap();
+    assert!(issues.contains(&DbIssue::AddressUnknownNetwork {
+        address_key: address_key.to_owned(),
+        network_specs_key: westend_specs_key,
+    }));
+    assert!(issues.contains(&DbIssue::VerifierWithoutSpecs {
+        verifier_key: VerifierKey::from_parts(westend_specs.genesis_hash),
+    }));
+    assert!(issues.contains(&DbIssue::TransactionLeftover { key: STUB.to_vec() }));
+    assert!(issues.iter().any(
+        |issue| matches!(issue, DbIssue::MetadataUnknownNetwork { name, .. } if name == "westend")
+    ));
+
+    let repaired = repair_db_in(&storage).unwrap();
+    assert_eq!(repaired.len(), issues.len() - 1);
+    assert_eq!(
+        check_db_in(&storage).unwrap(),
+        vec![DbIssue::VerifierWithoutSpecs {
+            verifier_key: VerifierKey::from_parts(westend_specs.genesis_hash),
+        }]
+    );
+    assert!(storage.get(ADDRTREE, &address_key.key()).unwrap().is_none());
+    assert!(storage
+        .get(KEY_ANNOTATIONS, &address_key.key())
+        .unwrap()
+        .is_none());
+    assert!(storage.iter(TRANSACTION).unwrap().is_empty());
+    let history = get_history_in(&storage).unwrap();
+    assert!(matches!(
+        &history[0].1.events[..],
+        [Event::DatabaseRepaired { repairs }] if repairs.len() == repaired.len()
+    ));
+
+    // nothing left to repair
+    assert!(repair_db_in(&storage).unwrap().is_empty());
+
+    // edited history log entry breaks the chain at the entry after it
+    let (order, mut entry) = history.last().unwrap().to_owned();
+    entry.events.push(Event::UserEntry {
+        user_entry: "inserted".to_string(),
+    });
+    let mut history_batch = Batch::default();
+    history_batch.insert(order.store(), entry.store());
+    TrDbCold::new()
+        .set_history(history_batch)
+        .apply_in(&storage)
+        .unwrap();
+    let issues = check_db_in(&storage).unwrap();
+    assert!(issues.contains(&DbIssue::HistoryChainBroken { order: 1 }));
+    assert_eq!(
+        DbIssue::HistoryChainBroken { order: 1 }.display().repair,
+        None
+    );
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn check_db_reports_stripped_history_links() {
+    let storage = MemoryStorage::new();
+    populate_cold_release_in(&storage).unwrap();
+    init_db_in(&storage, Verifier { v: None }).unwrap();
+    enter_events_in(
+        &storage,
+        vec![Event::UserEntry {
+            user_entry: "chained".to_string(),
+        }],
+    )
+    .unwrap();
+    assert!(check_db_in(&storage).unwrap().is_empty());
+
+    // entry stored the way it was before the history was hash-chained
+    let (order, entry) = get_history_in(&storage).unwrap().remove(0);
+    let mut history_batch = Batch::default();
+    history_batch.insert(order.store(), (&entry.timestamp, &entry.events).encode());
+    TrDbCold::new()
+        .set_history(history_batch)
+        .apply_in(&storage)
+        .unwrap();
+    assert_eq!(
+        check_db_in(&storage).unwrap(),
+        vec![DbIssue::HistoryChainBroken {
+            order: order.stamp()
+        }]
+    );
+
+    // stripped link has no safe repair
+    assert!(repair_db_in(&storage).unwrap().is_empty());
+    assert_eq!(
+        check_db_in(&storage).unwrap(),
+        vec![DbIssue::HistoryChainBroken {
+            order: order.stamp()
+        }]
+    );
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn display_general_verifier_properly() {
+    let storage = MemoryStorage::new();
+    populate_cold_release_in(&storage).unwrap();
+    signer_init_no_cert_in(&storage).unwrap();
+    let verifier = get_general_verifier_in(&storage).unwrap();
 
     let expected_verifier = Verifier { v: None };
     assert_eq!(verifier, expected_verifier);
 
-    signer_init_with_cert(dbname).unwrap();
-    let verifier = get_general_verifier(dbname).unwrap();
+    signer_init_with_cert_in(&storage).unwrap();
+    let verifier = get_general_verifier_in(&storage).unwrap();
     let expected_verifier = Verifier {
         v: Some(VerifierValue::Standard {
             m: MultiSigner::Sr25519(
@@ -853,53 +1341,45 @@ fn display_general_verifier_properly() {
         }),
     };
     assert_eq!(verifier, expected_verifier);
-
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn find_westend_verifier() {
-    let dbname = "for_tests/find_westend_verifier";
-    populate_cold_no_metadata(dbname, Verifier { v: None }).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_no_metadata_in(&storage, Verifier { v: None }).unwrap();
     let verifier_key = VerifierKey::from_parts(
         H256::from_str("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e").unwrap(),
     );
-    let westend_verifier = try_get_valid_current_verifier(&verifier_key, dbname).unwrap();
+    let westend_verifier = try_get_valid_current_verifier_in(&verifier_key, &storage).unwrap();
     assert_eq!(westend_verifier, Some(ValidCurrentVerifier::General));
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn not_find_mock_verifier() {
-    let dbname = "for_tests/not_find_mock_verifier";
-    populate_cold_no_metadata(dbname, Verifier { v: None }).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_no_metadata_in(&storage, Verifier { v: None }).unwrap();
     let verifier_key = VerifierKey::from_parts(
         H256::from_str("62bacaaa3d9bb01313bb882c23615aae6509ab2ef1e7e807581ee0b74c77416b").unwrap(),
     );
-    match try_get_valid_current_verifier(&verifier_key, dbname) {
+    match try_get_valid_current_verifier_in(&verifier_key, &storage) {
         Ok(Some(_)) => panic!("Found network key that should not be in database."),
         Ok(None) => (),
         Err(e) => panic!("Error looking for mock verifier: {}", e),
     }
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn test_generate_default_addresses_for_alice() {
-    let dbname = "for_tests/test_generate_default_addresses_for_Alice";
-    populate_cold_no_metadata(dbname, Verifier { v: None }).unwrap();
-    try_create_seed("Alice", ALICE_SEED_PHRASE, true, dbname).unwrap();
-    {
-        let database = open_db(dbname).unwrap();
-        let addresses = open_tree(&database, ADDRTREE).unwrap();
-        assert_eq!(addresses.len(), 4);
-    }
+    let storage = MemoryStorage::new();
+    populate_cold_no_metadata_in(&storage, Verifier { v: None }).unwrap();
+    try_create_seed_in("Alice", ALICE_SEED_PHRASE, true, &storage).unwrap();
+    assert_eq!(storage.len(ADDRTREE).unwrap(), 4);
     let chainspecs = default_chainspecs();
-    let default_addresses = addresses_set_seed_name_network(
-        dbname,
+    let default_addresses = addresses_set_seed_name_network_in(
+        &storage,
         "Alice",
         &NetworkSpecsKey::from_parts(&chainspecs[0].genesis_hash, &Encryption::Sr25519),
     )
@@ -939,6 +1419,7 @@ fn test_generate_default_addresses_for_alice() {
                 ],
                 encryption: Encryption::Sr25519,
                 secret_exposed: false,
+                usage: Vec::new(),
             },
         ),
         (
@@ -961,64 +1442,62 @@ fn test_generate_default_addresses_for_alice() {
                 .unwrap()],
                 encryption: Encryption::Sr25519,
                 secret_exposed: false,
+                usage: Vec::new(),
             },
         ),
     ];
 
     assert_eq!(default_addresses, expected_default_addresses);
 
-    let database: Db = open(dbname).unwrap();
-    let identities: Tree = database.open_tree(ADDRTREE).unwrap();
     let test_key = AddressKey::from_parts(
         &hex::decode("46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a").unwrap(),
         &Encryption::Sr25519,
     )
     .unwrap();
-    assert!(identities.contains_key(test_key.key()).unwrap());
-    fs::remove_dir_all(dbname).unwrap();
+    assert!(storage.get(ADDRTREE, &test_key.key()).unwrap().is_some());
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn test_derive() {
-    let dbname = "for_tests/test_derive";
-    populate_cold_no_metadata(dbname, Verifier { v: None }).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_no_metadata_in(&storage, Verifier { v: None }).unwrap();
     let specs = default_chainspecs();
     println!("[0]: {:?}, [1]: {:?}", specs[0].name, specs[1].name);
     let seed_name = "Alice";
     let network_id_0 = NetworkSpecsKey::from_parts(&specs[0].genesis_hash, &specs[0].encryption);
     let network_id_1 = NetworkSpecsKey::from_parts(&specs[1].genesis_hash, &specs[1].encryption);
 
-    try_create_seed(seed_name, ALICE_SEED_PHRASE, true, dbname).unwrap();
-    try_create_address(
+    try_create_seed_in(seed_name, ALICE_SEED_PHRASE, true, &storage).unwrap();
+    try_create_address_in(
         seed_name,
         ALICE_SEED_PHRASE,
         "//Alice",
         &network_id_0,
-        dbname,
+        &storage,
     )
     .unwrap();
-    try_create_address(
+    try_create_address_in(
         seed_name,
         ALICE_SEED_PHRASE,
         "//Alice",
         &network_id_1,
-        dbname,
+        &storage,
     )
     .unwrap();
-    try_create_address(
+    try_create_address_in(
         seed_name,
         ALICE_SEED_PHRASE,
         "//Alice/1",
         &network_id_0,
-        dbname,
+        &storage,
     )
     .unwrap();
 
     let both_networks = vec![network_id_0.to_owned(), network_id_1];
     let only_one_network = vec![network_id_0];
 
-    let identities = get_addresses_by_seed_name(dbname, seed_name).unwrap();
+    let identities = get_addresses_by_seed_name_in(&storage, seed_name).unwrap();
     println!("{:?}", identities);
     let mut flag0 = false;
     let mut flag1 = false;
@@ -1028,34 +1507,34 @@ fn test_derive() {
     }
     assert!(flag0, "Something is wrong with //Alice");
     assert!(flag1, "Something is wrong with //Alice/1");
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn test_identity_deletion() {
-    let dbname = "for_tests/test_identity_deletion";
-    populate_cold_no_metadata(dbname, Verifier { v: None }).unwrap();
-    try_create_seed("Alice", ALICE_SEED_PHRASE, true, dbname).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_no_metadata_in(&storage, Verifier { v: None }).unwrap();
+    try_create_seed_in("Alice", ALICE_SEED_PHRASE, true, &storage).unwrap();
     let chainspecs = default_chainspecs();
     let network_specs_key_0 =
         NetworkSpecsKey::from_parts(&chainspecs[0].genesis_hash, &Encryption::Sr25519);
     let network_specs_key_1 =
         NetworkSpecsKey::from_parts(&chainspecs[1].genesis_hash, &Encryption::Sr25519);
-    let mut identities = addresses_set_seed_name_network(dbname, "Alice", &network_specs_key_0)
-        .expect("Alice should have some addresses by default");
+    let mut identities =
+        addresses_set_seed_name_network_in(&storage, "Alice", &network_specs_key_0)
+            .expect("Alice should have some addresses by default");
     println!("{:?}", identities);
     let (key0, _) = identities.remove(0); //TODO: this should be root key
     let (key1, _) = identities.remove(0); //TODO: this should be network-specific key
-    remove_key(dbname, &key0, &network_specs_key_0).expect("delete an address");
-    remove_key(dbname, &key1, &network_specs_key_0).expect("delete another address");
-    let identities = addresses_set_seed_name_network(dbname, "Alice", &network_specs_key_0)
+    remove_key_in(&storage, &key0, &network_specs_key_0).expect("delete an address");
+    remove_key_in(&storage, &key1, &network_specs_key_0).expect("delete another address");
+    let identities = addresses_set_seed_name_network_in(&storage, "Alice", &network_specs_key_0)
         .expect("Alice still should have some addresses after deletion of two");
     for (address_key, _) in identities {
         assert_ne!(address_key, key0);
         assert_ne!(address_key, key1);
     }
-    let identities = addresses_set_seed_name_network(dbname, "Alice", &network_specs_key_1)
+    let identities = addresses_set_seed_name_network_in(&storage, "Alice", &network_specs_key_1)
         .expect("Alice still should have some addresses after deletion of two");
     let mut flag_to_check_key0_remains = false;
     for (address_key, _) in identities {
@@ -1068,16 +1547,15 @@ fn test_identity_deletion() {
         flag_to_check_key0_remains,
         "An address that should have only lost network was removed entirely"
     );
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn history_with_identities() {
-    let dbname = "for_tests/history_with_identities";
-    default_cold_release(Some(PathBuf::from(dbname))).unwrap();
-    signer_init_with_cert(dbname).unwrap();
-    let history_printed = get_history(dbname).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_release_in(&storage).unwrap();
+    signer_init_with_cert_in(&storage).unwrap();
+    let history_printed = get_history_in(&storage).unwrap();
     let element1 = Event::DatabaseInitiated;
     let element2 = Event::GeneralVerifierSet {
         verifier: Verifier {
@@ -1101,8 +1579,8 @@ fn history_with_identities() {
     assert!(history_printed
         .iter()
         .any(|e| e.1.events.contains(&element2)));
-    try_create_seed("Alice", ALICE_SEED_PHRASE, true, dbname).unwrap();
-    let history_printed_after_create_seed: Vec<_> = get_history(dbname)
+    try_create_seed_in("Alice", ALICE_SEED_PHRASE, true, &storage).unwrap();
+    let history_printed_after_create_seed: Vec<_> = get_history_in(&storage)
         .unwrap()
         .into_iter()
         .map(|e| e.1)
@@ -1125,6 +1603,7 @@ fn history_with_identities() {
                     "91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3",
                 )
                 .unwrap(),
+                label: String::new(),
             },
         },
         Event::IdentityAdded {
@@ -1140,6 +1619,7 @@ fn history_with_identities() {
                     "91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3",
                 )
                 .unwrap(),
+                label: String::new(),
             },
         },
         Event::IdentityAdded {
@@ -1155,6 +1635,7 @@ fn history_with_identities() {
                     "b0a8d493285c2df73290dfb7e61f870f17b41801197a149ca93654499ea3dafe",
                 )
                 .unwrap(),
+                label: String::new(),
             },
         },
         Event::IdentityAdded {
@@ -1170,6 +1651,7 @@ fn history_with_identities() {
                     "b0a8d493285c2df73290dfb7e61f870f17b41801197a149ca93654499ea3dafe",
                 )
                 .unwrap(),
+                label: String::new(),
             },
         },
         Event::IdentityAdded {
@@ -1185,6 +1667,7 @@ fn history_with_identities() {
                     "e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e",
                 )
                 .unwrap(),
+                label: String::new(),
             },
         },
         Event::IdentityAdded {
@@ -1200,6 +1683,7 @@ fn history_with_identities() {
                     "e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e",
                 )
                 .unwrap(),
+                label: String::new(),
             },
         },
     ];
@@ -1220,22 +1704,20 @@ fn history_with_identities() {
             i
         );
     }
-
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn remove_seed_history() {
-    let dbname = "for_tests/remove_seed_history";
+    let storage = MemoryStorage::new();
     let seed_name = "Alice";
-    default_cold_release(Some(PathBuf::from(dbname))).unwrap();
+    populate_cold_release_in(&storage).unwrap();
 
-    try_create_seed(seed_name, ALICE_SEED_PHRASE, true, dbname).unwrap();
-    assert!(remove_seed(dbname, "Wrong seed name").is_err());
-    remove_seed(dbname, seed_name).unwrap();
+    try_create_seed_in(seed_name, ALICE_SEED_PHRASE, true, &storage).unwrap();
+    assert!(remove_seed_in(&storage, "Wrong seed name").is_err());
+    remove_seed_in(&storage, seed_name).unwrap();
 
-    let history_printed: Vec<_> = get_history(dbname)
+    let history_printed: Vec<_> = get_history_in(&storage)
         .unwrap()
         .into_iter()
         .map(|e| e.1)
@@ -1246,16 +1728,15 @@ fn remove_seed_history() {
             seed_name: seed_name.to_string(),
         }
     ));
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
-fn get_multisigner_path_set(dbname: &str) -> Vec<(MultiSigner, String)> {
-    let db = open_db(dbname).unwrap();
-    let identities = open_tree(&db, ADDRTREE).unwrap();
+fn get_multisigner_path_set<S: Storage>(storage: &S) -> Vec<(MultiSigner, String)> {
     let mut multisigner_path_set: Vec<(MultiSigner, String)> = Vec::new();
-    for a in identities.iter().flatten() {
-        let (multisigner, address_details) = AddressDetails::process_entry_checked(a).unwrap();
+    for (address_key, address_entry) in storage.iter(ADDRTREE).unwrap() {
+        let (multisigner, address_details) =
+            AddressDetails::process_entry_checked((address_key.into(), address_entry.into()))
+                .unwrap();
         multisigner_path_set.push((multisigner, address_details.path.to_string()))
     }
     multisigner_path_set
@@ -1264,33 +1745,36 @@ fn get_multisigner_path_set(dbname: &str) -> Vec<(MultiSigner, String)> {
 #[cfg(feature = "test")]
 #[test]
 fn increment_identities_1() {
-    let dbname = "for_tests/increment_identities_1";
-    populate_cold_no_metadata(dbname, Verifier { v: None }).unwrap();
-    {
-        let db = open_db(dbname).unwrap();
-        let identities = open_tree(&db, ADDRTREE).unwrap();
-        assert!(identities.is_empty());
-    }
+    let storage = MemoryStorage::new();
+    populate_cold_no_metadata_in(&storage, Verifier { v: None }).unwrap();
+    assert_eq!(storage.len(ADDRTREE).unwrap(), 0);
     let chainspecs = default_chainspecs();
     let network_id_0 =
         NetworkSpecsKey::from_parts(&chainspecs[0].genesis_hash, &Encryption::Sr25519);
-    try_create_address("Alice", ALICE_SEED_PHRASE, "//Alice", &network_id_0, dbname).unwrap();
-    let multisigner_path_set = get_multisigner_path_set(dbname);
+    try_create_address_in(
+        "Alice",
+        ALICE_SEED_PHRASE,
+        "//Alice",
+        &network_id_0,
+        &storage,
+    )
+    .unwrap();
+    let multisigner_path_set = get_multisigner_path_set(&storage);
     assert!(
         multisigner_path_set.len() == 1,
         "Wrong number of identities: {:?}",
         multisigner_path_set
     );
     println!("{}", multisigner_path_set[0].1);
-    create_increment_set(
+    create_increment_set_in(
         4,
         &multisigner_path_set[0].0,
         &network_id_0,
         ALICE_SEED_PHRASE,
-        dbname,
+        &storage,
     )
     .unwrap();
-    let multisigner_path_set = get_multisigner_path_set(dbname);
+    let multisigner_path_set = get_multisigner_path_set(&storage);
     assert!(
         multisigner_path_set.len() == 5,
         "Wrong number of identities after increment: {:?}",
@@ -1304,32 +1788,34 @@ fn increment_identities_1() {
     assert!(path_set.contains(&String::from("//Alice//1")));
     assert!(path_set.contains(&String::from("//Alice//2")));
     assert!(path_set.contains(&String::from("//Alice//3")));
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn increment_identities_2() {
-    let dbname = "for_tests/increment_identities_2";
-    populate_cold_no_metadata(dbname, Verifier { v: None }).unwrap();
-    {
-        let db = open_db(dbname).unwrap();
-        let identities = open_tree(&db, ADDRTREE).unwrap();
-        assert!(identities.is_empty());
-    }
+    let storage = MemoryStorage::new();
+    populate_cold_no_metadata_in(&storage, Verifier { v: None }).unwrap();
+    assert_eq!(storage.len(ADDRTREE).unwrap(), 0);
     let chainspecs = default_chainspecs();
     let network_id_0 =
         NetworkSpecsKey::from_parts(&chainspecs[0].genesis_hash, &Encryption::Sr25519);
-    try_create_address("Alice", ALICE_SEED_PHRASE, "//Alice", &network_id_0, dbname).unwrap();
-    try_create_address(
+    try_create_address_in(
+        "Alice",
+        ALICE_SEED_PHRASE,
+        "//Alice",
+        &network_id_0,
+        &storage,
+    )
+    .unwrap();
+    try_create_address_in(
         "Alice",
         ALICE_SEED_PHRASE,
         "//Alice//1",
         &network_id_0,
-        dbname,
+        &storage,
     )
     .unwrap();
-    let multisigner_path_set = get_multisigner_path_set(dbname);
+    let multisigner_path_set = get_multisigner_path_set(&storage);
     let alice_multisigner_path = multisigner_path_set
         .iter()
         .find(|(_, path)| path == "//Alice")
@@ -1339,15 +1825,15 @@ fn increment_identities_2() {
         "Wrong number of identities: {:?}",
         multisigner_path_set
     );
-    create_increment_set(
+    create_increment_set_in(
         3,
         &alice_multisigner_path.0,
         &network_id_0,
         ALICE_SEED_PHRASE,
-        dbname,
+        &storage,
     )
     .unwrap();
-    let multisigner_path_set = get_multisigner_path_set(dbname);
+    let multisigner_path_set = get_multisigner_path_set(&storage);
     assert!(
         multisigner_path_set.len() == 5,
         "Wrong number of identities after increment: {:?}",
@@ -1360,32 +1846,34 @@ fn increment_identities_2() {
     assert!(path_set.contains(&String::from("//Alice//2")));
     assert!(path_set.contains(&String::from("//Alice//3")));
     assert!(path_set.contains(&String::from("//Alice//4")));
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn increment_identities_3() {
-    let dbname = "for_tests/increment_identities_3";
-    populate_cold_no_metadata(dbname, Verifier { v: None }).unwrap();
-    {
-        let db = open_db(dbname).unwrap();
-        let identities = open_tree(&db, ADDRTREE).unwrap();
-        assert!(identities.is_empty());
-    }
+    let storage = MemoryStorage::new();
+    populate_cold_no_metadata_in(&storage, Verifier { v: None }).unwrap();
+    assert_eq!(storage.len(ADDRTREE).unwrap(), 0);
     let chainspecs = default_chainspecs();
     let network_id_0 =
         NetworkSpecsKey::from_parts(&chainspecs[0].genesis_hash, &Encryption::Sr25519);
-    try_create_address("Alice", ALICE_SEED_PHRASE, "//Alice", &network_id_0, dbname).unwrap();
-    try_create_address(
+    try_create_address_in(
+        "Alice",
+        ALICE_SEED_PHRASE,
+        "//Alice",
+        &network_id_0,
+        &storage,
+    )
+    .unwrap();
+    try_create_address_in(
         "Alice",
         ALICE_SEED_PHRASE,
         "//Alice//1",
         &network_id_0,
-        dbname,
+        &storage,
     )
     .unwrap();
-    let multisigner_path_set = get_multisigner_path_set(dbname);
+    let multisigner_path_set = get_multisigner_path_set(&storage);
     let alice_multisigner_path = multisigner_path_set
         .iter()
         .find(|(_, path)| path == "//Alice//1")
@@ -1395,15 +1883,15 @@ fn increment_identities_3() {
         "Wrong number of identities: {:?}",
         multisigner_path_set
     );
-    create_increment_set(
+    create_increment_set_in(
         3,
         &alice_multisigner_path.0,
         &network_id_0,
         ALICE_SEED_PHRASE,
-        dbname,
+        &storage,
     )
     .unwrap();
-    let multisigner_path_set = get_multisigner_path_set(dbname);
+    let multisigner_path_set = get_multisigner_path_set(&storage);
     assert!(
         multisigner_path_set.len() == 5,
         "Wrong number of identities after increment: {:?}",
@@ -1416,29 +1904,41 @@ fn increment_identities_3() {
     assert!(path_set.contains(&String::from("//Alice//1//0")));
     assert!(path_set.contains(&String::from("//Alice//1//1")));
     assert!(path_set.contains(&String::from("//Alice//1//2")));
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn creating_derivation_1() {
-    let dbname = "for_tests/creating_derivation_1";
-    populate_cold_no_metadata(dbname, Verifier { v: None }).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_no_metadata_in(&storage, Verifier { v: None }).unwrap();
     let chainspecs = default_chainspecs();
     let network_id_0 =
         NetworkSpecsKey::from_parts(&chainspecs[0].genesis_hash, &Encryption::Sr25519);
     assert!(
-        try_create_address("Alice", ALICE_SEED_PHRASE, "//Alice", &network_id_0, dbname).is_ok(),
+        try_create_address_in(
+            "Alice",
+            ALICE_SEED_PHRASE,
+            "//Alice",
+            &network_id_0,
+            &storage
+        )
+        .is_ok(),
         "Should be able to create //Alice derivation."
     );
     if let DerivationCheck::NoPassword(Some(_)) =
-        derivation_check("Alice", "//Alice", &network_id_0, dbname).unwrap()
+        derivation_check_in("Alice", "//Alice", &network_id_0, &storage).unwrap()
     {
         println!("Found existing");
     } else {
         panic!("Derivation should already exist.");
     }
-    match try_create_address("Alice", ALICE_SEED_PHRASE, "//Alice", &network_id_0, dbname) {
+    match try_create_address_in(
+        "Alice",
+        ALICE_SEED_PHRASE,
+        "//Alice",
+        &network_id_0,
+        &storage,
+    ) {
         Ok(()) => panic!("Should NOT be able to create //Alice derivation again."),
         Err(e) => {
             if let Error::DerivationExists {
@@ -1464,30 +1964,29 @@ fn creating_derivation_1() {
             }
         }
     }
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn creating_derivation_2() {
-    let dbname = "for_tests/creating_derivation_2";
-    populate_cold_no_metadata(dbname, Verifier { v: None }).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_no_metadata_in(&storage, Verifier { v: None }).unwrap();
     let chainspecs = default_chainspecs();
     let network_id_0 =
         NetworkSpecsKey::from_parts(&chainspecs[0].genesis_hash, &Encryption::Sr25519);
     assert!(
-        try_create_address(
+        try_create_address_in(
             "Alice",
             ALICE_SEED_PHRASE,
             "//Alice///secret",
             &network_id_0,
-            dbname
+            &storage
         )
         .is_ok(),
         "Should be able to create //Alice/// secret derivation."
     );
     if let DerivationCheck::NoPassword(None) =
-        derivation_check("Alice", "//Alice", &network_id_0, dbname).unwrap()
+        derivation_check_in("Alice", "//Alice", &network_id_0, &storage).unwrap()
     {
         println!("It did well.");
     } else {
@@ -1496,26 +1995,39 @@ fn creating_derivation_2() {
         );
     }
     assert!(
-        try_create_address("Alice", ALICE_SEED_PHRASE, "//Alice", &network_id_0, dbname).is_ok(),
+        try_create_address_in(
+            "Alice",
+            ALICE_SEED_PHRASE,
+            "//Alice",
+            &network_id_0,
+            &storage
+        )
+        .is_ok(),
         "Should be able to create //Alice derivation."
     );
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn creating_derivation_3() {
-    let dbname = "for_tests/creating_derivation_3";
-    populate_cold_no_metadata(dbname, Verifier { v: None }).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_no_metadata_in(&storage, Verifier { v: None }).unwrap();
     let chainspecs = default_chainspecs();
     let network_id_0 =
         NetworkSpecsKey::from_parts(&chainspecs[0].genesis_hash, &Encryption::Sr25519);
     assert!(
-        try_create_address("Alice", ALICE_SEED_PHRASE, "//Alice", &network_id_0, dbname).is_ok(),
+        try_create_address_in(
+            "Alice",
+            ALICE_SEED_PHRASE,
+            "//Alice",
+            &network_id_0,
+            &storage
+        )
+        .is_ok(),
         "Should be able to create //Alice derivation."
     );
     if let DerivationCheck::Password =
-        derivation_check("Alice", "//Alice///secret", &network_id_0, dbname).unwrap()
+        derivation_check_in("Alice", "//Alice///secret", &network_id_0, &storage).unwrap()
     {
         println!("It did well.");
     } else {
@@ -1524,91 +2036,89 @@ fn creating_derivation_3() {
         );
     }
     assert!(
-        try_create_address(
+        try_create_address_in(
             "Alice",
             ALICE_SEED_PHRASE,
             "//Alice///secret",
             &network_id_0,
-            dbname
+            &storage
         )
         .is_ok(),
         "Should be able to create //Alice///secret derivation."
     );
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn creating_derivation_4() {
-    let dbname = "for_tests/creating_derivation_4";
-    populate_cold_no_metadata(dbname, Verifier { v: None }).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_no_metadata_in(&storage, Verifier { v: None }).unwrap();
     let chainspecs = default_chainspecs();
     let network_id_0 =
         NetworkSpecsKey::from_parts(&chainspecs[0].genesis_hash, &Encryption::Sr25519);
     assert!(
-        try_create_address(
+        try_create_address_in(
             "Alice",
             ALICE_SEED_PHRASE,
             "//Alice///secret1",
             &network_id_0,
-            dbname
+            &storage
         )
         .is_ok(),
         "Should be able to create //Alice///secret1 derivation."
     );
     if let DerivationCheck::Password =
-        derivation_check("Alice", "//Alice///secret2", &network_id_0, dbname).unwrap()
+        derivation_check_in("Alice", "//Alice///secret2", &network_id_0, &storage).unwrap()
     {
         println!("It did well.");
     } else {
         panic!("Existing derivation has different password.");
     }
     assert!(
-        try_create_address(
+        try_create_address_in(
             "Alice",
             ALICE_SEED_PHRASE,
             "//Alice///secret2",
             &network_id_0,
-            dbname
+            &storage
         )
         .is_ok(),
         "Should be able to create //Alice///secret2 derivation."
     );
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn creating_derivation_5() {
-    let dbname = "for_tests/creating_derivation_5";
-    populate_cold_no_metadata(dbname, Verifier { v: None }).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_no_metadata_in(&storage, Verifier { v: None }).unwrap();
     let chainspecs = default_chainspecs();
     let network_id_0 =
         NetworkSpecsKey::from_parts(&chainspecs[0].genesis_hash, &Encryption::Sr25519);
     assert!(
-        try_create_address(
+        try_create_address_in(
             "Alice",
             ALICE_SEED_PHRASE,
             "//Alice///secret",
             &network_id_0,
-            dbname
+            &storage
         )
         .is_ok(),
         "Should be able to create //Alice derivation."
     );
     if let DerivationCheck::Password =
-        derivation_check("Alice", "//Alice///secret", &network_id_0, dbname).unwrap()
+        derivation_check_in("Alice", "//Alice///secret", &network_id_0, &storage).unwrap()
     {
         println!("It did well.");
     } else {
         panic!("Derivation exists, but has password.");
     }
-    match try_create_address(
+    match try_create_address_in(
         "Alice",
         ALICE_SEED_PHRASE,
         "//Alice///secret",
         &network_id_0,
-        dbname,
+        &storage,
     ) {
         Ok(()) => panic!("Should NOT be able to create //Alice///secret derivation again."),
         Err(e) => {
@@ -1635,7 +2145,6 @@ fn creating_derivation_5() {
             }
         }
     }
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
@@ -1717,11 +2226,14 @@ fn test_metadata_transfer() {
 #[cfg(feature = "test")]
 #[test]
 fn test_all_events() {
-    let dbname = "for_tests/test_all_events";
-    populate_cold_no_metadata(dbname, Verifier { v: None }).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_no_metadata_in(&storage, Verifier { v: None }).unwrap();
     let events = all_events_preview();
-    enter_events(dbname, events).unwrap();
-    let entries: Vec<_> = get_history(dbname)
+    for event in events.iter() {
+        assert_eq!(event.kind(), event.audit_fields().0);
+    }
+    enter_events_in(&storage, events).unwrap();
+    let entries: Vec<_> = get_history_in(&storage)
         .unwrap()
         .into_iter()
         .map(|(_, a)| a)
@@ -1800,6 +2312,7 @@ fn test_all_events() {
                     path_id: "//westend".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "Westend".to_string(),
+                    tokens: Vec::new(),
                     unit: "WND".to_string(),
                 },
                 valid_current_verifier: ValidCurrentVerifier::General,
@@ -1837,6 +2350,7 @@ fn test_all_events() {
                     path_id: "//westend".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "Westend".to_string(),
+                    tokens: Vec::new(),
                     unit: "WND".to_string(),
                 },
                 valid_current_verifier: ValidCurrentVerifier::General,
@@ -1872,6 +2386,7 @@ fn test_all_events() {
                     path_id: "//westend".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "Westend".to_string(),
+                    tokens: Vec::new(),
                     unit: "WND".to_string(),
                 },
                 signed_by: VerifierValue::Standard {
@@ -2109,7 +2624,8 @@ fn test_all_events() {
                 network_genesis_hash: H256::from_str(
                     "e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"
                 )
-                .unwrap()
+                .unwrap(),
+                label: String::new(),
             }
         }
     ));
@@ -2128,7 +2644,8 @@ fn test_all_events() {
                 network_genesis_hash: H256::from_str(
                     "e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"
                 )
-                .unwrap()
+                .unwrap(),
+                label: String::new(),
             }
         }
     ));
@@ -2154,6 +2671,12 @@ fn test_all_events() {
             warning: "Received network information is not verified.".to_string()
         }
     ));
+    assert!(entries_contain_event(
+        &entries,
+        &Event::WarningMessage {
+            message: LocalisedMessage::new("warning.not_verified")
+        }
+    ));
     assert!(entries_contain_event(&entries, &Event::WrongPassword));
     assert!(entries_contain_event(
         &entries,
@@ -2170,16 +2693,14 @@ fn test_all_events() {
 
     assert!(entries_contain_event(&entries, &Event::HistoryCleared));
     assert!(entries_contain_event(&entries, &Event::DatabaseInitiated));
-
-    std::fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn print_single_event() {
-    let dbname = "for_tests/print_single_event";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
-    let entry = get_history_entry_by_order(0, dbname).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let entry = get_history_entry_by_order_in(0, &storage).unwrap();
     let expected_events = vec![
         Event::DatabaseInitiated,
         Event::GeneralVerifierSet {
@@ -2188,16 +2709,12 @@ fn print_single_event() {
     ];
 
     assert_eq!(entry.events, expected_events);
-
-    std::fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
-fn check_for_network(name: &str, version: u32, dbname: &str) -> bool {
-    let database: Db = open(dbname).unwrap();
-    let metadata: Tree = database.open_tree(METATREE).unwrap();
+fn check_for_network<S: Storage>(name: &str, version: u32, storage: &S) -> bool {
     let meta_key = MetaKey::from_parts(name, version);
-    metadata.contains_key(meta_key.key()).unwrap()
+    storage.get(METATREE, &meta_key.key()).unwrap().is_some()
 }
 
 #[cfg(feature = "test")]
@@ -2208,41 +2725,42 @@ fn entries_contain_event(entries: &[Entry], event: &Event) -> bool {
 #[cfg(feature = "test")]
 #[test]
 fn remove_all_westend() {
-    let dbname = "for_tests/remove_all_westend";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
 
     let genesis_hash = "e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e";
     let network_specs_key =
         NetworkSpecsKey::from_parts(&H256::from_str(genesis_hash).unwrap(), &Encryption::Sr25519);
-    remove_network(&network_specs_key, dbname).unwrap();
+    remove_network_in(&network_specs_key, &storage).unwrap();
 
-    {
-        let database: Db = open(dbname).unwrap();
-        let chainspecs: Tree = database.open_tree(SPECSTREE).unwrap();
+    assert!(
+        storage
+            .get(SPECSTREE, &network_specs_key.key())
+            .unwrap()
+            .is_none(),
+        "Westend network specs were not deleted"
+    );
+    let prefix_meta = MetaKeyPrefix::from_name("westend");
+    assert!(
+        storage
+            .scan_prefix(METATREE, &prefix_meta.prefix())
+            .unwrap()
+            .is_empty(),
+        "Some westend metadata was not deleted"
+    );
+    for (key, value) in storage.iter(ADDRTREE).unwrap() {
+        let (_, address_details) =
+            AddressDetails::process_entry_checked((key.into(), value.into())).unwrap();
         assert!(
-            chainspecs.get(&network_specs_key.key()).unwrap() == None,
-            "Westend network specs were not deleted"
+            !address_details.network_id.contains(&network_specs_key),
+            "Some westend identities still remain."
         );
-        let metadata: Tree = database.open_tree(METATREE).unwrap();
-        let prefix_meta = MetaKeyPrefix::from_name("westend");
         assert!(
-            metadata.scan_prefix(&prefix_meta.prefix()).next() == None,
-            "Some westend metadata was not deleted"
+            !address_details.network_id.is_empty(),
+            "Did not remove address key entried with no network keys associated"
         );
-        let identities: Tree = database.open_tree(ADDRTREE).unwrap();
-        for a in identities.iter().flatten() {
-            let (_, address_details) = AddressDetails::process_entry_checked(a).unwrap();
-            assert!(
-                !address_details.network_id.contains(&network_specs_key),
-                "Some westend identities still remain."
-            );
-            assert!(
-                !address_details.network_id.is_empty(),
-                "Did not remove address key entried with no network keys associated"
-            );
-        }
     }
-    let history: Vec<_> = get_history(dbname)
+    let history: Vec<_> = get_history_in(&storage)
         .unwrap()
         .into_iter()
         .map(|e| e.1)
@@ -2268,6 +2786,7 @@ fn remove_all_westend() {
                     path_id: "//westend".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "Westend".to_string(),
+                    tokens: Vec::new(),
                     unit: "WND".to_string(),
                 },
                 valid_current_verifier: ValidCurrentVerifier::General,
@@ -2317,7 +2836,8 @@ fn remove_all_westend() {
                 network_genesis_hash: H256::from_str(
                     "e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"
                 )
-                .unwrap()
+                .unwrap(),
+                label: String::new(),
             },
         }
     ));
@@ -2336,7 +2856,8 @@ fn remove_all_westend() {
                 network_genesis_hash: H256::from_str(
                     "e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"
                 )
-                .unwrap()
+                .unwrap(),
+                label: String::new(),
             },
         }
     ));
@@ -2355,62 +2876,61 @@ fn remove_all_westend() {
                 network_genesis_hash: H256::from_str(
                     "e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"
                 )
-                .unwrap()
+                .unwrap(),
+                label: String::new(),
             },
         }
     ));
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn remove_westend_9010() {
-    let dbname = "for_tests/remove_westend_9010";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
     let genesis_hash = "e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e";
     let network_specs_key =
         NetworkSpecsKey::from_parts(&H256::from_str(genesis_hash).unwrap(), &Encryption::Sr25519);
     let network_version = 9010;
     assert!(
-        check_for_network("westend", network_version, dbname),
+        check_for_network("westend", network_version, &storage),
         "No westend 9010 to begin with."
     );
-    remove_metadata(&network_specs_key, network_version, dbname).unwrap();
+    remove_metadata_in(&network_specs_key, network_version, &storage).unwrap();
     assert!(
-        !check_for_network("westend", network_version, dbname),
+        !check_for_network("westend", network_version, &storage),
         "Westend 9010 not removed."
     );
-    fs::remove_dir_all(dbname).unwrap();
 }
 
 #[cfg(feature = "test")]
 #[test]
 fn test_export_secret_key() {
-    let dbname = "for_tests/export_alice_secret";
-    populate_cold(dbname, Verifier { v: None }).unwrap();
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
     let specs = default_chainspecs();
     let spec = specs.iter().find(|spec| spec.name == "westend").unwrap();
     let network_id = NetworkSpecsKey::from_parts(&spec.genesis_hash, &spec.encryption);
     let seed_name = "Alice";
 
     let (derivation_path, child_path) = ("//Alice", "//Alice//1");
-    try_create_address(
+    try_create_address_in(
         seed_name,
         ALICE_SEED_PHRASE,
         child_path,
         &network_id,
-        dbname,
+        &storage,
     )
     .unwrap();
     let identities: Vec<(MultiSigner, AddressDetails)> =
-        get_addresses_by_seed_name(dbname, seed_name).unwrap();
+        get_addresses_by_seed_name_in(&storage, seed_name).unwrap();
 
     let (derivation_multisigner, _) = identities
         .iter()
         .find(|(_, a)| a.path == derivation_path)
         .unwrap();
-    let secret_key = export_secret_key(
-        dbname,
+    let secret_key = export_secret_key_in(
+        &storage,
         hex::encode(multisigner_to_public(derivation_multisigner)).as_str(),
         seed_name,
         &hex::encode(network_id.key()),
@@ -2423,12 +2943,703 @@ fn test_export_secret_key() {
     assert!(secret_key.address.secret_exposed);
 
     let identities: Vec<(MultiSigner, AddressDetails)> =
-        get_addresses_by_seed_name(dbname, seed_name).unwrap();
+        get_addresses_by_seed_name_in(&storage, seed_name).unwrap();
     let (_, child_address) = identities
         .iter()
         .find(|(_, a)| a.path == child_path)
         .unwrap();
     assert!(child_address.secret_exposed);
+}
 
-    fs::remove_dir_all(dbname).unwrap();
+#[cfg(feature = "test")]
+#[test]
+fn history_hash_chain() {
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    for user_entry in ["one", "two", "three"] {
+        enter_events_in(
+            &storage,
+            vec![Event::UserEntry {
+                user_entry: user_entry.to_string(),
+            }],
+        )
+        .unwrap();
+    }
+    verify_history_chain_in(&storage).unwrap();
+
+    let entry_2 = get_history_entry_by_order_in(2, &storage).unwrap();
+    let entry_3 = get_history_entry_by_order_in(3, &storage).unwrap();
+    assert_eq!(
+        entry_3.link,
+        Some(EntryLink::Previous {
+            previous_hash: entry_2.hash()
+        })
+    );
+    assert_eq!(history_chain_head_in(&storage).unwrap(), entry_3.hash());
+
+    // cleared history starts with genesis marker signing off the cleared log
+    let cleared_head = history_chain_head_in(&storage).unwrap();
+    let cleared_entries = get_history_in(&storage).unwrap().len() as u32;
+    clear_history_in(&storage).unwrap();
+    let genesis = get_history_entry_by_order_in(0, &storage).unwrap();
+    assert_eq!(genesis.events, vec![Event::HistoryCleared]);
+    assert_eq!(
+        genesis.link,
+        Some(EntryLink::Genesis {
+            cleared_head,
+            cleared_entries
+        })
+    );
+    verify_history_chain_in(&storage).unwrap();
+
+    enter_events_in(
+        &storage,
+        vec![Event::UserEntry {
+            user_entry: "four".to_string(),
+        }],
+    )
+    .unwrap();
+    enter_events_in(
+        &storage,
+        vec![Event::UserEntry {
+            user_entry: "five".to_string(),
+        }],
+    )
+    .unwrap();
+    verify_history_chain_in(&storage).unwrap();
+
+    // silently edited entry breaks the link to the next entry
+    {
+        let mut entry_1 = get_history_entry_by_order_in(1, &storage).unwrap();
+        entry_1.events = vec![Event::UserEntry {
+            user_entry: "edited".to_string(),
+        }];
+        let mut history_batch = Batch::default();
+        history_batch.insert(Order::from_number(1).store(), entry_1.store());
+        storage.apply(&[(HISTORY, &history_batch)]).unwrap();
+    }
+    if let Error::HistoryChainBroken { order } = verify_history_chain_in(&storage).unwrap_err() {
+        assert_eq!(order, 2);
+    } else {
+        panic!("Expected broken history chain.")
+    }
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn transaction_signed_with_proof_in_history() {
+    let storage = MemoryStorage::new();
+    populate_cold_no_metadata_in(&storage, Verifier { v: None }).unwrap();
+
+    // proof content is not checked when the transaction is logged
+    let metadata_proof = MetadataProof {
+        leaves: Vec::new(),
+        leaf_indices: Vec::new(),
+        nodes: Vec::new(),
+        extrinsic: ExtrinsicInfo {
+            version: 4,
+            address_ty: TypeRef::Void,
+            call_ty: TypeRef::Void,
+            signature_ty: TypeRef::Void,
+            signed_extensions: Vec::new(),
+        },
+        extra: ExtraInfo {
+            spec_version: 9000,
+            spec_name: "westend".to_string(),
+            base58prefix: 42,
+            decimals: 12,
+            unit: "WND".to_string(),
+        },
+    };
+    let westend_genesis_hash =
+        H256::from_str("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e").unwrap();
+    let sign = TrDbColdSign::generate(
+        SignContent::Transaction {
+            method: vec![4, 0],
+            extensions: Vec::new(),
+            nonce: None,
+            metadata_proof: Some(metadata_proof.to_owned()),
+        },
+        "westend",
+        &NetworkSpecsKey::from_parts(&westend_genesis_hash, &Encryption::Sr25519),
+        "//westend",
+        false,
+        &MultiSigner::Sr25519(Public::from_raw([1; 32])),
+        vec![Event::UserEntry {
+            user_entry: "before signing".to_string(),
+        }],
+    );
+
+    // wrong password entry is logged with the transaction as well
+    sign.apply_in(true, "", &storage).unwrap();
+    let (_, entry) = get_history_in(&storage).unwrap().remove(0);
+    assert!(matches!(
+        &entry.events[..],
+        [Event::UserEntry { .. }, Event::TransactionSignError { .. }]
+    ));
+    assert_eq!(entry.metadata_proof(0), None);
+    assert_eq!(entry.metadata_proof(1), Some(&metadata_proof));
+    assert_eq!(
+        Entry::from_stored(&entry.store()).unwrap().metadata_proofs,
+        entry.metadata_proofs
+    );
+    verify_history_chain_in(&storage).unwrap();
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn history_hash_chain_rejects_stripped_links() {
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    enter_events_in(
+        &storage,
+        vec![Event::UserEntry {
+            user_entry: "chained".to_string(),
+        }],
+    )
+    .unwrap();
+    verify_history_chain_in(&storage).unwrap();
+    let entries = get_history_in(&storage).unwrap().len() as u32;
+    let expect_broken = |expected: u32| {
+        if let Error::HistoryChainBroken { order } = verify_history_chain_in(&storage).unwrap_err()
+        {
+            assert_eq!(order, expected);
+        } else {
+            panic!("Expected broken history chain.")
+        }
+    };
+
+    // entry removed from the end of the log does not match the stored head
+    let last_order = Order::from_number(entries - 1).store();
+    let last = storage.get(HISTORY, &last_order).unwrap().unwrap();
+    let mut history_batch = Batch::default();
+    history_batch.remove(&last_order);
+    storage.apply(&[(HISTORY, &history_batch)]).unwrap();
+    expect_broken(entries - 1);
+    let mut history_batch = Batch::default();
+    history_batch.insert(&last_order, last);
+    storage.apply(&[(HISTORY, &history_batch)]).unwrap();
+    verify_history_chain_in(&storage).unwrap();
+
+    // entry with stripped link is not accepted after the first entry
+    let strip_link = |order: &Order, entry: &Entry| {
+        let mut history_batch = Batch::default();
+        history_batch.insert(order.store(), (&entry.timestamp, &entry.events).encode());
+        TrDbCold::new()
+            .set_history(history_batch)
+            .apply_in(&storage)
+            .unwrap();
+    };
+    let history = get_history_in(&storage).unwrap();
+    let (order, entry) = history
+        .iter()
+        .find(|(order, _)| order.stamp() == 2)
+        .unwrap();
+    strip_link(order, entry);
+    assert!(get_history_entry_by_order_in(2, &storage)
+        .unwrap()
+        .link
+        .is_none());
+    expect_broken(2);
+
+    // log with all links stripped is not accepted either
+    for (order, entry) in history.iter() {
+        strip_link(order, entry);
+    }
+    assert!(get_history_in(&storage)
+        .unwrap()
+        .iter()
+        .all(|(_, entry)| entry.link.is_none()));
+    expect_broken(1);
+
+    // stored entry with data left after the link is not accepted
+    let mut stored = history[0].1.store();
+    stored.push(0);
+    assert!(Entry::from_stored(&stored).is_err());
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn history_audit_report_formats() {
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    enter_events_in(
+        &storage,
+        vec![Event::UserEntry {
+            user_entry: "audit, with \"quotes\"".to_string(),
+        }],
+    )
+    .unwrap();
+    let entry_1 = get_history_entry_by_order_in(1, &storage).unwrap();
+    let entry_2 = get_history_entry_by_order_in(2, &storage).unwrap();
+
+    let json_report = history_audit_report_in(&storage, 2, 5, AuditFormat::Json).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json_report).unwrap();
+    assert_eq!(json["first_order"], 2);
+    assert_eq!(json["last_order"], 5);
+    let entries = json["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["order"], 2);
+    assert_eq!(entries[0]["timestamp"], entry_2.timestamp.as_str());
+    assert_eq!(
+        entries[0]["previous_hash"],
+        hex::encode(entry_1.hash()).as_str()
+    );
+    assert_eq!(entries[0]["hash"], hex::encode(entry_2.hash()).as_str());
+    assert_eq!(
+        entries[0]["events"],
+        serde_json::json!([{"event": "UserEntry", "details": {"user_entry": "audit, with \"quotes\""}}])
+    );
+
+    let csv_report = history_audit_report_in(&storage, 2, 2, AuditFormat::Csv).unwrap();
+    let csv_expected = format!(
+        "order,timestamp,previous_hash,hash,event,details\n\
+        2,{},{},{},UserEntry,\"user_entry=audit, with \"\"quotes\"\"\"\n",
+        entry_2.timestamp,
+        hex::encode(entry_1.hash()),
+        hex::encode(entry_2.hash()),
+    );
+    assert_eq!(String::from_utf8(csv_report).unwrap(), csv_expected);
+
+    // each event of an entry gets its own row
+    let csv_report = history_audit_report_in(&storage, 0, 0, AuditFormat::Csv).unwrap();
+    let csv_report = String::from_utf8(csv_report).unwrap();
+    let rows: Vec<&str> = csv_report.lines().collect();
+    assert_eq!(rows.len(), 3);
+    assert!(rows[1].ends_with(",DatabaseInitiated,"));
+    assert!(rows[2].ends_with(",GeneralVerifierSet,verifier=none"));
+
+    if let Error::HistoryAuditRangeEmpty {
+        first_order,
+        last_order,
+    } = history_audit_report_in(&storage, 10, 20, AuditFormat::Json).unwrap_err()
+    {
+        assert_eq!((first_order, last_order), (10, 20));
+    } else {
+        panic!("Expected empty audit range.")
+    }
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn history_keeps_network_tokens() {
+    let storage = MemoryStorage::new();
+    populate_cold_no_metadata_in(&storage, Verifier { v: None }).unwrap();
+    let mut network_specs = get_all_networks_in(&storage).unwrap().remove(0);
+    network_specs.tokens = vec![TokenSpecs {
+        asset_id: Some(1984),
+        decimals: 6,
+        unit: "USDt".to_string(),
+    }];
+    let mut amended_specs = network_specs.to_owned();
+    amended_specs.tokens[0].decimals = 8;
+    let events = vec![
+        Event::NetworkSpecsTokensAdded {
+            network_specs_tokens_display: NetworkSpecsTokensDisplay::get(
+                &network_specs,
+                &ValidCurrentVerifier::General,
+                &Verifier { v: None },
+            ),
+        },
+        Event::NetworkSpecsTokensAmended {
+            network_specs_amendment_tokens_display: NetworkSpecsAmendmentTokensDisplay::get(
+                &network_specs,
+                &amended_specs,
+            ),
+        },
+    ];
+    enter_events_in(&storage, events.to_owned()).unwrap();
+    let entries: Vec<_> = get_history_in(&storage)
+        .unwrap()
+        .into_iter()
+        .map(|(_, a)| a)
+        .collect();
+    for event in events.iter() {
+        assert!(entries_contain_event(&entries, event));
+    }
+
+    let (_, added) = events[0].audit_fields();
+    assert!(added.contains(&("tokens", "[USDt (6)]".to_string())));
+    let (_, amended) = events[1].audit_fields();
+    assert!(amended.contains(&("changes", "tokens: [USDt (6)] -> [USDt (8)]".to_string())));
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn history_search_filters() {
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    enter_events_in(
+        &storage,
+        vec![Event::UserEntry {
+            user_entry: "Remember this Kusama moment".to_string(),
+        }],
+    )
+    .unwrap();
+    let all_events = |page: &HistorySearchPage| -> Vec<Event> {
+        page.entries
+            .iter()
+            .flat_map(|(_, entry)| entry.events.to_vec())
+            .collect()
+    };
+
+    let filter = HistoryFilter::from_query("kind=IdentityAdded; seed=Alice").unwrap();
+    let found = search_history_in(&storage, &filter, 0).unwrap();
+    assert_eq!(found.total_pages, 1);
+    let events = all_events(&found);
+    assert!(!events.is_empty());
+    assert!(events
+        .iter()
+        .all(|event| matches!(event, Event::IdentityAdded { identity_history } if identity_history.seed_name == "Alice")));
+
+    let kusama_genesis_hash =
+        H256::from_str("b0a8d493285c2df73290dfb7e61f870f17b41801197a149ca93654499ea3dafe").unwrap();
+    let kusama_sr25519 = NetworkSpecsKey::from_parts(&kusama_genesis_hash, &Encryption::Sr25519);
+    let filter = HistoryFilter::from_query(&format!(
+        "kind=IdentityAdded;network={}",
+        hex::encode(kusama_sr25519.key())
+    ))
+    .unwrap();
+    let events = all_events(&search_history_in(&storage, &filter, 0).unwrap());
+    assert!(!events.is_empty());
+    assert!(events.iter().all(|event| matches!(event, Event::IdentityAdded { identity_history } if identity_history.network_genesis_hash == kusama_genesis_hash)));
+
+    // network specs amendments are found by network as well
+    let kusama_specs = get_all_networks_in(&storage)
+        .unwrap()
+        .into_iter()
+        .find(|specs| specs.genesis_hash == kusama_genesis_hash)
+        .unwrap();
+    let mut amended_specs = kusama_specs.to_owned();
+    amended_specs.title = "Kusama amended".to_string();
+    let amendment = Event::NetworkSpecsAmended {
+        network_specs_amendment_display: NetworkSpecsAmendmentDisplay::get(
+            &kusama_specs,
+            &amended_specs,
+        ),
+    };
+    enter_events_in(&storage, vec![amendment.to_owned()]).unwrap();
+    let filter = HistoryFilter::from_query(&format!(
+        "kind=NetworkSpecsAmended;network={}",
+        hex::encode(kusama_sr25519.key())
+    ))
+    .unwrap();
+    assert_eq!(
+        all_events(&search_history_in(&storage, &filter, 0).unwrap()),
+        vec![amendment]
+    );
+
+    let alice_root = AddressKey::from_parts(
+        &hex::decode("46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a").unwrap(),
+        &Encryption::Sr25519,
+    )
+    .unwrap();
+    let filter =
+        HistoryFilter::from_query(&format!("address={}", hex::encode(alice_root.key()))).unwrap();
+    let events = all_events(&search_history_in(&storage, &filter, 0).unwrap());
+    assert_eq!(events.len(), 3);
+    assert!(events
+        .iter()
+        .all(|event| matches!(event, Event::IdentityAdded { identity_history } if identity_history.path.is_empty())));
+
+    let filter = HistoryFilter::from_query("text=kusama moment").unwrap();
+    let found = search_history_in(&storage, &filter, 0).unwrap();
+    assert_eq!(found.entries.len(), 1);
+    assert_eq!(found.entries[0].0.stamp(), 2);
+    assert_eq!(
+        found.entries[0].1.events,
+        vec![Event::UserEntry {
+            user_entry: "Remember this Kusama moment".to_string()
+        }]
+    );
+
+    let filter = HistoryFilter::from_query("from=2999").unwrap();
+    let found = search_history_in(&storage, &filter, 0).unwrap();
+    assert!(found.entries.is_empty());
+    assert_eq!(found.total_pages, 0);
+
+    if let Error::HistoryQueryInvalid { part } = HistoryFilter::from_query("color=red").unwrap_err()
+    {
+        assert_eq!(part, "color=red");
+    } else {
+        panic!("Expected invalid query.")
+    }
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn contacts_add_and_remove() {
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let bob_base58 = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+    let bob_account_id: [u8; 32] =
+        hex::decode("8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    assert!(get_contacts_in(&storage).unwrap().is_empty());
+
+    add_contact_in(&storage, "  Bob ", bob_base58).unwrap();
+    let contacts = get_contacts_in(&storage).unwrap();
+    assert_eq!(contacts.len(), 1);
+    assert_eq!(contacts[0].0, bob_account_id);
+    assert_eq!(contacts[0].1.name, "Bob");
+    assert_eq!(contacts[0].1.verifier, Verifier { v: None });
+    let shown = show_contacts_in(&storage).unwrap();
+    assert_eq!(shown.len(), 1);
+    assert_eq!(shown[0].name, "Bob");
+    assert_eq!(shown[0].base58, bob_base58);
+
+    let bob_display = ContactDisplay::get(
+        &ContactEntry {
+            account_id: bob_account_id,
+            name: "Bob".to_string(),
+        },
+        &Verifier { v: None },
+    );
+    let history: Vec<Entry> = get_history_in(&storage)
+        .unwrap()
+        .into_iter()
+        .map(|(_, entry)| entry)
+        .collect();
+    assert!(entries_contain_event(
+        &history,
+        &Event::ContactAdded {
+            contact_display: bob_display.to_owned()
+        }
+    ));
+
+    // same account in polkadot format renames the contact
+    add_contact_in(
+        &storage,
+        "Robert",
+        "14E5nqKAp3oAJcmzgZhUD2RcptBeUBScxKHgJKU4HPNcKVf3",
+    )
+    .unwrap();
+    let contacts = get_contacts_in(&storage).unwrap();
+    assert_eq!(contacts.len(), 1);
+    assert_eq!(contacts[0].1.name, "Robert");
+
+    remove_contact_in(&storage, bob_base58).unwrap();
+    assert!(get_contacts_in(&storage).unwrap().is_empty());
+    let history: Vec<Entry> = get_history_in(&storage)
+        .unwrap()
+        .into_iter()
+        .map(|(_, entry)| entry)
+        .collect();
+    assert!(history.iter().any(|entry| entry
+        .events
+        .iter()
+        .any(|event| matches!(event, Event::ContactRemoved { contact_display } if contact_display.name == "Robert"))));
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn contacts_errors() {
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let bob_base58 = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+
+    if let Error::ContactNameEmpty = add_contact_in(&storage, "   ", bob_base58).unwrap_err() {
+    } else {
+        panic!("Expected empty name error.")
+    }
+
+    if let Error::ContactAddressInvalid { address } =
+        add_contact_in(&storage, "Bob", "not an address").unwrap_err()
+    {
+        assert_eq!(address, "not an address");
+    } else {
+        panic!("Expected invalid address error.")
+    }
+
+    if let Error::ContactNotFound { .. } = remove_contact_in(&storage, bob_base58).unwrap_err() {
+    } else {
+        panic!("Expected contact not found error.")
+    }
+    assert!(get_contacts_in(&storage).unwrap().is_empty());
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn key_annotations_set_and_removed() {
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let alice_root = AddressKey::from_parts(
+        &hex::decode("46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a").unwrap(),
+        &Encryption::Sr25519,
+    )
+    .unwrap();
+    let multisigner = alice_root.multi_signer().unwrap();
+    assert!(try_get_key_annotation_in(&storage, &alice_root)
+        .unwrap()
+        .is_none());
+
+    set_key_label_in(&storage, &multisigner, "  Treasury ").unwrap();
+    set_key_tags_in(&storage, &multisigner, "cold, savings,, cold ").unwrap();
+    set_key_notes_in(&storage, &multisigner, "Do not use for staking").unwrap();
+    let key_annotation = try_get_key_annotation_in(&storage, &alice_root)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        key_annotation,
+        KeyAnnotation {
+            label: "Treasury".to_string(),
+            tags: vec!["cold".to_string(), "savings".to_string()],
+            notes: "Do not use for staking".to_string(),
+        }
+    );
+    assert!(key_annotation.matches("SAVINGS"));
+    assert!(key_annotation.matches("staking"));
+    assert!(!key_annotation.matches("polkadot"));
+    assert_eq!(
+        get_key_annotations_in(&storage).unwrap(),
+        vec![(alice_root.to_owned(), key_annotation)]
+    );
+
+    // annotation with all content cleared is removed
+    set_key_label_in(&storage, &multisigner, "").unwrap();
+    set_key_tags_in(&storage, &multisigner, " ").unwrap();
+    set_key_notes_in(&storage, &multisigner, "").unwrap();
+    assert!(get_key_annotations_in(&storage).unwrap().is_empty());
+
+    // annotations of removed keys are removed too
+    set_key_label_in(&storage, &multisigner, "Treasury").unwrap();
+    remove_seed_in(&storage, "Alice").unwrap();
+    assert!(get_key_annotations_in(&storage).unwrap().is_empty());
+    if let Error::AddressNotFound(address_key) =
+        set_key_label_in(&storage, &multisigner, "Treasury").unwrap_err()
+    {
+        assert_eq!(address_key, alice_root);
+    } else {
+        panic!("Expected address not found error.")
+    }
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn key_annotations_in_history() {
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let alice_root_public =
+        hex::decode("46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a").unwrap();
+    let alice_root = AddressKey::from_parts(&alice_root_public, &Encryption::Sr25519).unwrap();
+    let multisigner = alice_root.multi_signer().unwrap();
+    let key_annotation_changed = |label: &str| Event::KeyAnnotationChanged {
+        key_annotation_history: KeyAnnotationHistory::get(
+            "Alice",
+            &Encryption::Sr25519,
+            &alice_root_public,
+            "",
+            &KeyAnnotation {
+                label: label.to_string(),
+                ..Default::default()
+            },
+        ),
+    };
+    let all_events = || -> Vec<Event> {
+        get_history_in(&storage)
+            .unwrap()
+            .into_iter()
+            .flat_map(|(_, entry)| entry.events)
+            .collect()
+    };
+
+    set_key_label_in(&storage, &multisigner, "Treasury").unwrap();
+    // no change, nothing recorded
+    set_key_label_in(&storage, &multisigner, "Treasury").unwrap();
+    let events = all_events();
+    assert_eq!(
+        events
+            .iter()
+            .filter(|event| matches!(event, Event::KeyAnnotationChanged { .. }))
+            .collect::<Vec<_>>(),
+        vec![&key_annotation_changed("Treasury")]
+    );
+
+    // identity events of the annotated key are shown with current label
+    assert!(events.iter().any(|event| matches!(
+        event,
+        Event::IdentityAdded { identity_history }
+            if identity_history.public_key == alice_root_public
+                && identity_history.label == "Treasury"
+    )));
+    assert!(events.iter().all(|event| match event {
+        Event::IdentityAdded { identity_history } =>
+            identity_history.public_key == alice_root_public || identity_history.label.is_empty(),
+        _ => true,
+    }));
+    let found = search_history_in(
+        &storage,
+        &HistoryFilter::from_query("text=treasury").unwrap(),
+        0,
+    )
+    .unwrap();
+    let found_events: Vec<Event> = found
+        .entries
+        .into_iter()
+        .flat_map(|(_, entry)| entry.events)
+        .collect();
+    assert!(found_events.contains(&key_annotation_changed("Treasury")));
+    assert!(found_events
+        .iter()
+        .any(|event| matches!(event, Event::IdentityAdded { .. })));
+
+    // labels are not stored, history chain is intact
+    verify_history_chain_in(&storage).unwrap();
+
+    // annotation removed together with the key is recorded
+    remove_seed_in(&storage, "Alice").unwrap();
+    let latest_entry = get_history_in(&storage).unwrap().remove(0).1;
+    assert!(latest_entry.events.contains(&key_annotation_changed("")));
+    verify_history_chain_in(&storage).unwrap();
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn address_details_stored_without_usage() {
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let known = get_addresses_by_seed_name_in(&storage, "Alice").unwrap();
+    assert!(!known.is_empty());
+
+    // rewrite addresses the way they were stored before the key usage
+    // statistics were introduced
+    let mut address_batch = Batch::default();
+    for (multisigner, address_details) in known.iter() {
+        let legacy = (
+            &address_details.seed_name,
+            &address_details.path,
+            address_details.has_pwd,
+            &address_details.network_id,
+            &address_details.encryption,
+            address_details.secret_exposed,
+        )
+            .encode();
+        address_batch.insert(AddressKey::from_multisigner(multisigner).key(), legacy);
+    }
+    storage.apply(&[(ADDRTREE, &address_batch)]).unwrap();
+    assert_eq!(
+        get_addresses_by_seed_name_in(&storage, "Alice").unwrap(),
+        known,
+        "Legacy address details are read with empty usage set."
+    );
+
+    let (multisigner, mut address_details) = known[0].to_owned();
+    let network_specs_key = address_details.network_id[0].to_owned();
+    address_details.record_transaction(&network_specs_key, Some(5), "2022-10-18");
+    let stored = address_details.store();
+    assert_eq!(
+        AddressDetails::from_stored(&stored).unwrap(),
+        address_details
+    );
+
+    let mut stored_with_leftover = stored;
+    stored_with_leftover.push(0);
+    assert!(AddressDetails::from_stored(&stored_with_leftover).is_err());
+    assert!(AddressDetails::process_entry_with_key_checked(
+        &AddressKey::from_multisigner(&multisigner),
+        address_details.store().into(),
+    )
+    .is_ok());
 }
@@ -203,6 +203,7 @@ pub fn default_chainspecs() -> Vec<NetworkSpecs> {
             path_id: x.path_id.to_string(),
             secondary_color: x.secondary_color.to_string(),
             title: x.title.to_string(),
+            tokens: Vec::new(),
             unit: x.unit.to_string(),
         };
         out.push(new);
@@ -240,6 +241,7 @@ pub fn default_chainspecs_to_send() -> Vec<NetworkSpecsToSend> {
             path_id: x.path_id.to_string(),
             secondary_color: x.secondary_color.to_string(),
             title: x.title.to_string(),
+            tokens: Vec::new(),
             unit: x.unit.to_string(),
         };
         out.push(new);
@@ -1,81 +1,243 @@
-//! Record of the Signer exposure to dangerous events  
+//! Record of the Signer exposure to dangerous events
 //!
 //! Signer potentially dangerous exposures are recorded in `SETTREE` as
 //! encoded [`DangerRecord`] under key `DANGER`.
 //!
-//! Signer should stay offline (i.e. air-gapped) throughout its usage.
+//! Signer should stay offline (i.e. air-gapped) throughout its usage. Native
+//! side of the Signer also detects other conditions compromising the device,
+//! all listed in [`Exposure`].
 //!
-//! In case Signer finds itself online, it records this in the database
-//! danger record and generates log entry in `HISTORY` tree.
+//! In case Signer detects an exposure, it records this in the database
+//! danger record, together with the time the exposure was first seen, and
+//! generates log entry in `HISTORY` tree.
 //!
 //! [`DangerRecord`] could be reset only by designated reset function, and the
-//! fact of the reset is also recorded in the history log.  
+//! fact of the reset is also recorded in the history log.
+//!
+//! Danger record is versioned. Legacy danger record, with single
+//! `device_was_online` flag, is read as the current version, with
+//! [`Exposure::DeviceWasOnline`] having unknown first-seen time.
 use parity_scale_codec::{Decode, Encode};
-#[cfg(feature = "signer")]
 use sled::IVec;
 
-#[cfg(feature = "signer")]
 use crate::error::Result;
 
+/// Current version of the [`DangerRecord`] format
+const DANGER_RECORD_VERSION: u8 = 1;
+
 /// Danger status in the Signer database
 ///
 /// Indicates if the Signer has a record of unsafe exposure.
 pub struct DangerRecord(Vec<u8>);
 
-/// Decoded `DangerRecord` content, struct with boolean fields corresponding
-/// to different exposure modes inside  
-///
-/// Currently contains only `device_was_online` flag
+/// Kind of the Signer exposure
+#[derive(Decode, Encode, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Exposure {
+    /// Signer was online, i.e. the air-gap was broken
+    DeviceWasOnline,
+
+    /// USB debugging was enabled
+    UsbDebugging,
+
+    /// Wi-Fi radio was enabled
+    Wifi,
+
+    /// Bluetooth radio was enabled
+    Bluetooth,
+
+    /// Screen was recorded
+    ScreenRecording,
+
+    /// Device bootloader was unlocked
+    BootloaderUnlocked,
+}
+
+impl Exposure {
+    /// Exposure description, for display.
+    pub fn show(&self) -> &'static str {
+        match self {
+            Exposure::DeviceWasOnline => "device was online",
+            Exposure::UsbDebugging => "USB debugging was enabled",
+            Exposure::Wifi => "Wi-Fi was enabled",
+            Exposure::Bluetooth => "Bluetooth was enabled",
+            Exposure::ScreenRecording => "screen was recorded",
+            Exposure::BootloaderUnlocked => "bootloader was unlocked",
+        }
+    }
+}
+
+/// Exposure in [`DangerRecord`], with the time it was first seen
+#[derive(Decode, Encode, PartialEq, Eq, Debug, Clone)]
+pub struct ExposureRecord {
+    /// exposure kind
+    pub exposure: Exposure,
+
+    /// timestamp of the first detection since the last danger record reset,
+    /// formatted as in history log entries; `None` for the exposures migrated
+    /// from the legacy danger record
+    pub first_seen: Option<String>,
+}
+
+/// Decoded `DangerRecord` content, set of exposures with their first-seen
+/// timestamps
 #[derive(Decode, Encode)]
 struct DecodedDangerRecord {
+    exposures: Vec<ExposureRecord>,
+}
+
+/// Decoded legacy `DangerRecord` content
+///
+/// Legacy record contains only `device_was_online` flag, and has no version.
+#[derive(Decode, Encode)]
+struct DecodedDangerRecordLegacy {
     device_was_online: bool,
 }
 
 impl DangerRecord {
-    /// Set danger record to "safe".  
+    /// Set danger record to "safe".
     ///
-    /// Switch all exposure flags to `false`.  
+    /// Danger record contains no exposures.
     pub fn safe() -> Self {
-        Self(
-            DecodedDangerRecord {
-                device_was_online: false,
-            }
-            .encode(),
-        )
+        Self::from_exposures(Vec::new())
+    }
+
+    /// Make danger record with given exposures, in the current format.
+    fn from_exposures(exposures: Vec<ExposureRecord>) -> Self {
+        let mut out = vec![DANGER_RECORD_VERSION];
+        out.extend_from_slice(&DecodedDangerRecord { exposures }.encode());
+        Self(out)
     }
 
-    /// Set `device_was_online` exposure flag to `true`.  
+    /// Add exposure to the danger record.
+    ///
+    /// If the exposure is already in the record, it is kept with its original
+    /// first-seen timestamp. Resulting danger record is in the current format.
     ///
-    /// Having `device_was_online` flag `true` makes danger record "not safe".  
+    /// Having any exposure makes danger record "not safe".
     #[cfg(feature = "signer")]
-    pub fn set_was_online() -> Self {
-        Self(
-            DecodedDangerRecord {
-                device_was_online: true,
-            }
-            .encode(),
-        )
+    pub fn with_exposure(&self, exposure: Exposure, timestamp: &str) -> Result<Self> {
+        let mut exposures = self.exposures()?;
+        if !exposures.iter().any(|x| x.exposure == exposure) {
+            exposures.push(ExposureRecord {
+                exposure,
+                first_seen: Some(timestamp.to_string()),
+            })
+        }
+        Ok(Self::from_exposures(exposures))
+    }
+
+    /// Danger record with the same exposures, in the current format.
+    ///
+    /// Could result in error if the `DangerRecord` content is corrupted.
+    pub fn upgraded(&self) -> Result<Self> {
+        Ok(Self::from_exposures(self.exposures()?))
     }
 
     /// Get `DangerRecord` with content from the encoded value,
-    /// as it is stored in the database.  
+    /// as it is stored in the database.
     ///
     /// Infallible, as the validity of the value is not checked.
-    #[cfg(feature = "signer")]
     pub fn from_ivec(ivec: &IVec) -> Self {
         Self(ivec.to_vec())
     }
 
-    /// Get the value of `device_was_online` flag from `DangerRecord`.  
+    /// Get exposures recorded in `DangerRecord`.
     ///
-    /// Could result in error if the `DangerRecord` content is corrupted.  
-    #[cfg(feature = "signer")]
-    pub fn device_was_online(&self) -> Result<bool> {
-        Ok(<DecodedDangerRecord>::decode(&mut &self.0[..])?.device_was_online)
+    /// Legacy danger record is processed as well.
+    ///
+    /// Could result in error if the `DangerRecord` content is corrupted.
+    pub fn exposures(&self) -> Result<Vec<ExposureRecord>> {
+        // legacy danger record is a single encoded `bool`
+        if self.0.len() == 1 {
+            let legacy = <DecodedDangerRecordLegacy>::decode(&mut &self.0[..])?;
+            let mut exposures = Vec::new();
+            if legacy.device_was_online {
+                exposures.push(ExposureRecord {
+                    exposure: Exposure::DeviceWasOnline,
+                    first_seen: None,
+                })
+            }
+            return Ok(exposures);
+        }
+        match self.0.split_first() {
+            Some((&DANGER_RECORD_VERSION, encoded)) => {
+                Ok(<DecodedDangerRecord>::decode(&mut &encoded[..])?.exposures)
+            }
+            _ => Err(parity_scale_codec::Error::from("unknown danger record version").into()),
+        }
+    }
+
+    /// Check if `DangerRecord` has no exposures.
+    ///
+    /// Could result in error if the `DangerRecord` content is corrupted.
+    pub fn is_safe(&self) -> Result<bool> {
+        Ok(self.exposures()?.is_empty())
     }
 
-    /// Transform `DangerRecord` into `Vec<u8>` to put in the database.  
+    /// Transform `DangerRecord` into `Vec<u8>` to put in the database.
     pub fn store(&self) -> Vec<u8> {
         self.0.to_vec()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_danger_record_migrates() {
+        let legacy = DangerRecord(
+            DecodedDangerRecordLegacy {
+                device_was_online: true,
+            }
+            .encode(),
+        );
+        assert_eq!(
+            legacy.exposures().unwrap(),
+            vec![ExposureRecord {
+                exposure: Exposure::DeviceWasOnline,
+                first_seen: None,
+            }]
+        );
+        let updated = legacy
+            .with_exposure(Exposure::Wifi, "2022-10-01 12:00:00.000000")
+            .unwrap();
+        assert_eq!(
+            updated.exposures().unwrap(),
+            vec![
+                ExposureRecord {
+                    exposure: Exposure::DeviceWasOnline,
+                    first_seen: None,
+                },
+                ExposureRecord {
+                    exposure: Exposure::Wifi,
+                    first_seen: Some("2022-10-01 12:00:00.000000".to_string()),
+                },
+            ]
+        );
+
+        let legacy_safe = DangerRecord(
+            DecodedDangerRecordLegacy {
+                device_was_online: false,
+            }
+            .encode(),
+        );
+        assert!(legacy_safe.is_safe().unwrap());
+    }
+
+    #[test]
+    fn exposure_first_seen_is_kept() {
+        let record = DangerRecord::safe()
+            .with_exposure(Exposure::UsbDebugging, "2022-10-01 12:00:00.000000")
+            .unwrap()
+            .with_exposure(Exposure::UsbDebugging, "2022-10-02 12:00:00.000000")
+            .unwrap();
+        assert_eq!(
+            record.exposures().unwrap(),
+            vec![ExposureRecord {
+                exposure: Exposure::UsbDebugging,
+                first_seen: Some("2022-10-01 12:00:00.000000".to_string()),
+            }]
+        );
+    }
+}
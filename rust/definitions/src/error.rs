@@ -91,6 +91,22 @@ pub enum Error {
     #[error(transparent)]
     MetadataError(#[from] MetadataError),
 
+    #[error(transparent)]
+    MetadataProofError(#[from] MetadataProofError),
+
+    /// Compressed update content declares or produces on decompression more
+    /// data than allowed.
+    #[error("Compressed update content exceeds decompression limit of {limit} bytes.")]
+    DecompressionLimit { limit: usize },
+
+    /// Compressed update content produced on decompression data with length
+    /// different from the declared one.
+    #[error(
+        "Compressed update content was declared to have length {declared}, \
+        decompressed content has length {found}."
+    )]
+    DecompressedLengthMismatch { declared: u32, found: usize },
+
     /// Network name and/or network version in [`MetaKey`] do not match the
     /// network name and network version from `Version` constant, `System`
     /// pallet of the metadata stored under this [`MetaKey`].
@@ -115,7 +131,7 @@ pub enum Error {
 /// All variants could be encountered both on the active side
 /// (when checking the message content while signing it)
 /// and on the Signer side (when processing the received messages)
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "test", derive(VariantCount))]
 pub enum TransferContent {
     /// `add_specs` message content
@@ -126,6 +142,18 @@ pub enum TransferContent {
 
     /// `load_types` message content
     LoadTypes,
+
+    /// `add_contacts` message content
+    AddContacts,
+
+    /// `rotate_verifier` message content
+    RotateVerifier,
+
+    /// `security_policy` message content
+    SecurityPolicy,
+
+    /// `amend_specs` message content
+    AmendSpecs,
 }
 
 impl TransferContent {
@@ -135,6 +163,10 @@ impl TransferContent {
             TransferContent::AddSpecs => "`add_specs`",
             TransferContent::LoadMeta => "`load_meta`",
             TransferContent::LoadTypes => "`load_types`",
+            TransferContent::AddContacts => "`add_contacts`",
+            TransferContent::RotateVerifier => "`rotate_verifier`",
+            TransferContent::SecurityPolicy => "`security_policy`",
+            TransferContent::AmendSpecs => "`amend_specs`",
         };
         format!("Payload could not be decoded as {}.", insert)
     }
@@ -188,3 +220,72 @@ pub enum MetadataError {
     #[error("metadata body is not decodable")]
     UnableToDecode,
 }
+
+/// Errors in partial metadata proofs
+///
+/// See [`metadata_proof`](crate::metadata_proof) for details.
+#[derive(Debug, thiserror::Error)]
+#[cfg_attr(feature = "test", derive(VariantCount))]
+pub enum MetadataProofError {
+    /// Metadata proof has no types.
+    #[error("metadata proof has no types")]
+    NoTypes,
+
+    /// Metadata proof has number of leaf indices different from the number
+    /// of types.
+    #[error("metadata proof has {leaves} types and {leaf_indices} leaf indices")]
+    LeafIndicesMismatch { leaves: u32, leaf_indices: u32 },
+
+    /// Metadata proof refers to more types than Signer is ready to restore.
+    #[error("metadata proof refers to {type_count} types, allowed are at most {limit}")]
+    TooManyTypes { type_count: u32, limit: u32 },
+
+    /// Metadata proof has types with the same id that could not be merged
+    /// into a single type.
+    #[error("metadata proof has conflicting types with id {0}")]
+    TypeConflict(u32),
+
+    /// Metadata proof has too few tree nodes to restore the types tree root.
+    #[error("metadata proof has too few tree nodes")]
+    NodesMissing,
+
+    /// Metadata proof has tree nodes not used in restoring the types tree
+    /// root.
+    #[error("metadata proof has unused tree nodes")]
+    NodesUnused,
+
+    /// Metadata proof has types not used in restoring the types tree root,
+    /// because the leaf indices are not in depth-first order.
+    #[error("metadata proof has unused types")]
+    LeavesUnused,
+
+    /// Enum variant index in metadata proof does not fit into `u8`.
+    #[error("metadata proof has enum variant index {0} out of range")]
+    VariantIndexOutOfRange(u32),
+
+    /// Metadata type could not be resolved in types registry.
+    #[error("metadata has no type with id {0}")]
+    TypeNotResolved(u32),
+
+    /// Metadata extrinsic type has no type parameter needed for metadata
+    /// digest.
+    #[error("metadata extrinsic type has no {0} type parameter")]
+    NoExtrinsicTypeParam(String),
+
+    /// Metadata has compact of a type that is not a single unsigned integer.
+    #[error("metadata type with id {0} is unsupported compact")]
+    UnsupportedCompact(u32),
+
+    /// Metadata has bit sequence with unsupported bit store or bit order type.
+    #[error("metadata type with id {0} is unsupported bit sequence")]
+    UnsupportedBitSequence(u32),
+
+    /// Metadata has no pallet with requested index, or pallet has no calls.
+    #[error("metadata has no calls in pallet with index {0}")]
+    NoCallsInPallet(u8),
+
+    /// Pallet calls type in metadata could not be resolved as an enum with
+    /// requested call index.
+    #[error("metadata has no call with index {call_index} in pallet with index {pallet_index}")]
+    CallNotFound { pallet_index: u8, call_index: u8 },
+}
@@ -12,6 +12,12 @@ pub enum GeneralVerifierForContent {
 
     /// Types information.
     Types,
+
+    /// Named contacts.
+    Contacts,
+
+    /// Signer security policy.
+    SecurityPolicy,
 }
 
 /// Errors in transaction parsing
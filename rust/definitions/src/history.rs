@@ -17,8 +17,15 @@
 //! User can clear history log at any time. This indeed will remove all history
 //! entries, and the log will then start with `Entry` containing
 //! `Event::HistoryCleared`.
-use parity_scale_codec::{Decode, Encode};
-use sp_core::{blake2_256, H256};
+//!
+//! Log entries are hash-chained: each [`Entry`] contains the hash of the
+//! previous entry, so that any edited or removed entry breaks the chain. The
+//! first entry of the log, with `Order` `0`, is the genesis marker. It signs
+//! off the log that was cleared when the new log was started, with the hash of
+//! the last cleared entry and the number of cleared entries, see
+//! [`EntryLink::Genesis`].
+use parity_scale_codec::{Decode, DecodeAll, Encode};
+use sp_core::{blake2_256, ecdsa, ed25519, sr25519, Pair, H256};
 use sp_runtime::MultiSigner;
 #[cfg(feature = "signer")]
 use std::convert::TryInto;
@@ -26,13 +33,18 @@ use std::convert::TryInto;
 use variant_count::VariantCount;
 
 use crate::{
-    crypto::Encryption,
+    crypto::{Encryption, SufficientCrypto},
+    error::Result,
     keyring::VerifierKey,
+    localisation::LocalisedMessage,
     metadata::MetaValues,
+    metadata_proof::MetadataProof,
     network_specs::{
-        NetworkSpecs, NetworkSpecsToSend, ValidCurrentVerifier, Verifier, VerifierValue,
+        NetworkSpecs, NetworkSpecsToSend, TokenSpecs, ValidCurrentVerifier, Verifier, VerifierValue,
     },
+    policy::{PolicyRule, SecurityPolicy},
     qr_transfers::ContentLoadTypes,
+    users::{ContactEntry, KeyAnnotation},
 };
 
 /// Event content for importing or removing metadata of a known network
@@ -101,6 +113,10 @@ impl MetaValuesExport {
 }
 
 /// Event content for importing or removing network specs  
+///
+/// Network specs here carry no token list, see
+/// [`network_specs`](crate::network_specs) module documentation. Network
+/// specs are imported with token list in [`NetworkSpecsTokensDisplay`].  
 #[derive(Debug, Decode, Encode, PartialEq, Eq, Clone)]
 pub struct NetworkSpecsDisplay {
     pub specs: NetworkSpecs,
@@ -125,6 +141,36 @@ impl NetworkSpecsDisplay {
     }
 }
 
+/// Event content for importing network specs, with network token list  
+///
+/// Token list is kept next to [`NetworkSpecsDisplay`], so that the history
+/// log shows with which token decimals the network balances are displayed.  
+#[derive(Debug, Decode, Encode, PartialEq, Eq, Clone)]
+pub struct NetworkSpecsTokensDisplay {
+    pub network_specs_display: NetworkSpecsDisplay,
+    pub tokens: Vec<TokenSpecs>,
+}
+
+impl NetworkSpecsTokensDisplay {
+    /// Generate [`NetworkSpecsTokensDisplay`] from [`NetworkSpecs`],
+    /// network-associated [`ValidCurrentVerifier`], and
+    /// general verifier [`Verifier`]
+    pub fn get(
+        specs: &NetworkSpecs,
+        valid_current_verifier: &ValidCurrentVerifier,
+        general_verifier: &Verifier,
+    ) -> Self {
+        Self {
+            network_specs_display: NetworkSpecsDisplay::get(
+                specs,
+                valid_current_verifier,
+                general_verifier,
+            ),
+            tokens: specs.tokens.to_vec(),
+        }
+    }
+}
+
 /// Event content for generating [`SufficientCrypto`](crate::crypto::SufficientCrypto)
 /// QR code for `add_specs` message  
 ///
@@ -173,6 +219,81 @@ impl NetworkVerifierDisplay {
     }
 }
 
+/// Event content for rotating custom network verifier
+///
+/// Rotation is endorsed by the old verifier, i.e. the `rotate_verifier` update
+/// is signed by `old_verifier_value`.
+#[derive(Debug, Decode, Encode, PartialEq, Eq, Clone)]
+pub struct NetworkVerifierRotationDisplay {
+    pub genesis_hash: H256,
+    pub old_verifier_value: VerifierValue,
+    pub new_verifier_value: VerifierValue,
+}
+
+impl NetworkVerifierRotationDisplay {
+    /// Generate [`NetworkVerifierRotationDisplay`] from [`VerifierKey`] and
+    /// old and new network verifier [`VerifierValue`]  
+    pub fn get(
+        verifier_key: &VerifierKey,
+        old_verifier_value: &VerifierValue,
+        new_verifier_value: &VerifierValue,
+    ) -> Self {
+        Self {
+            genesis_hash: verifier_key.genesis_hash(),
+            old_verifier_value: old_verifier_value.to_owned(),
+            new_verifier_value: new_verifier_value.to_owned(),
+        }
+    }
+}
+
+/// Event content for amending display specs of a known network
+///
+/// Amendment keeps network addresses and metadata, and changes only the
+/// specs fields that are used for display.
+#[derive(Debug, Decode, Encode, PartialEq, Eq, Clone)]
+pub struct NetworkSpecsAmendmentDisplay {
+    pub old_network_specs: NetworkSpecs,
+    pub new_network_specs: NetworkSpecs,
+}
+
+impl NetworkSpecsAmendmentDisplay {
+    /// Generate [`NetworkSpecsAmendmentDisplay`] from old and new
+    /// [`NetworkSpecs`]
+    pub fn get(old_network_specs: &NetworkSpecs, new_network_specs: &NetworkSpecs) -> Self {
+        Self {
+            old_network_specs: old_network_specs.to_owned(),
+            new_network_specs: new_network_specs.to_owned(),
+        }
+    }
+}
+
+/// Event content for amending display specs of a known network, with old and
+/// new network token lists
+///
+/// Network specs in [`NetworkSpecsAmendmentDisplay`] carry no token list, see
+/// [`network_specs`](crate::network_specs) module documentation.
+#[derive(Debug, Decode, Encode, PartialEq, Eq, Clone)]
+pub struct NetworkSpecsAmendmentTokensDisplay {
+    pub network_specs_amendment_display: NetworkSpecsAmendmentDisplay,
+    pub old_tokens: Vec<TokenSpecs>,
+    pub new_tokens: Vec<TokenSpecs>,
+}
+
+impl NetworkSpecsAmendmentTokensDisplay {
+    /// Generate [`NetworkSpecsAmendmentTokensDisplay`] from old and new
+    /// [`NetworkSpecs`]
+    pub fn get(old_network_specs: &NetworkSpecs, new_network_specs: &NetworkSpecs) -> Self {
+        Self {
+            network_specs_amendment_display: NetworkSpecsAmendmentDisplay::get(
+                old_network_specs,
+                new_network_specs,
+            ),
+            old_tokens: old_network_specs.tokens.to_vec(),
+            new_tokens: new_network_specs.tokens.to_vec(),
+        }
+    }
+}
+
 /// Event content for importing or removing types information
 ///
 /// Contains hash of SCALE-encoded types data and types information [`Verifier`].
@@ -200,6 +321,31 @@ impl TypesDisplay {
     }
 }
 
+/// Event content for adding or removing named contact
+///
+/// Contains contact account id, contact name, and [`Verifier`] of the
+/// `add_contacts` update that brought the contact into Signer.
+/// `Verifier { v: None }` is used for unverified updates and for contacts
+/// entered manually.
+#[derive(Debug, Decode, Encode, PartialEq, Eq, Clone)]
+pub struct ContactDisplay {
+    pub account_id: Vec<u8>,
+    pub name: String,
+    pub verifier: Verifier,
+}
+
+impl ContactDisplay {
+    /// Generate [`ContactDisplay`] from [`ContactEntry`] and contact
+    /// [`Verifier`]  
+    pub fn get(contact: &ContactEntry, verifier: &Verifier) -> Self {
+        Self {
+            account_id: contact.account_id.to_vec(),
+            name: contact.name.to_owned(),
+            verifier: verifier.to_owned(),
+        }
+    }
+}
+
 /// Event content for generating [`SufficientCrypto`](crate::crypto::SufficientCrypto)
 /// QR code for `load_types` message  
 ///
@@ -231,6 +377,46 @@ impl TypesExport {
     }
 }
 
+/// Event content for exporting signed history log audit report
+///
+/// Records the range of history log entries in the report, report format,
+/// hash of the signed content and the key that signed the report.
+#[derive(Debug, Decode, Encode, PartialEq, Eq, Clone)]
+pub struct HistoryAuditExport {
+    /// order of the first entry in the report
+    pub first_order: u32,
+    /// order of the last entry in the report
+    pub last_order: u32,
+    /// report format
+    pub format: AuditFormat,
+    /// hash of [`SignedHistoryAudit::signed_content`]
+    pub report_hash: H256,
+    /// [`VerifierValue`] of address used for signing
+    pub signed_by: VerifierValue,
+}
+
+impl HistoryAuditExport {
+    /// Generate [`HistoryAuditExport`] from the report range and
+    /// [`SignedHistoryAudit`].
+    pub fn get(
+        first_order: u32,
+        last_order: u32,
+        signed_history_audit: &SignedHistoryAudit,
+    ) -> Self {
+        Self {
+            first_order,
+            last_order,
+            format: signed_history_audit.format,
+            report_hash: blake2_256(&SignedHistoryAudit::signed_content(
+                signed_history_audit.format,
+                &signed_history_audit.report,
+            ))
+            .into(),
+            signed_by: signed_history_audit.sufficient_crypto.verifier_value(),
+        }
+    }
+}
+
 /// Event content for address generation or removal.
 #[derive(Debug, Decode, Encode, PartialEq, Eq, Clone)]
 pub struct IdentityHistory {
@@ -244,6 +430,13 @@ pub struct IdentityHistory {
     pub path: String,
     /// - genesis hash of the network within which the address is  
     pub network_genesis_hash: H256,
+    /// - user-defined label of the address key  
+    ///
+    /// Label is not recorded in the history log, it is filled in from the
+    /// current key annotations when the log is read. Label changes are
+    /// recorded separately as [`Event::KeyAnnotationChanged`].
+    #[codec(skip)]
+    pub label: String,
 }
 
 impl IdentityHistory {
@@ -260,6 +453,7 @@ impl IdentityHistory {
             public_key,
             path,
             network_genesis_hash,
+            label: String::new(),
         }
     }
 
@@ -277,6 +471,44 @@ impl IdentityHistory {
             public_key: public_key.to_vec(),
             path: path.to_string(),
             network_genesis_hash,
+            label: String::new(),
+        }
+    }
+}
+
+/// Event content for changing user-defined annotation of the address key.
+///
+/// Contains the annotation set by user. Annotation with no content means that
+/// the annotation was removed, by user or together with the address key.
+#[derive(Debug, Decode, Encode, PartialEq, Eq, Clone)]
+pub struct KeyAnnotationHistory {
+    /// The name of the seed.
+    pub seed_name: String,
+    /// [`Encryption`] scheme of the seed.
+    pub encryption: Encryption,
+    /// Public key.
+    pub public_key: Vec<u8>,
+    /// - path with soft (`/`) and hard (`//`) derivations only, **without** password  
+    pub path: String,
+    /// - new annotation of the address key  
+    pub annotation: KeyAnnotation,
+}
+
+impl KeyAnnotationHistory {
+    /// Generate [`KeyAnnotationHistory`] from parts  
+    pub fn get(
+        seed_name: &str,
+        encryption: &Encryption,
+        public_key: &[u8],
+        path: &str,
+        annotation: &KeyAnnotation,
+    ) -> Self {
+        Self {
+            seed_name: seed_name.to_string(),
+            encryption: encryption.to_owned(),
+            public_key: public_key.to_vec(),
+            path: path.to_string(),
+            annotation: annotation.to_owned(),
         }
     }
 }
@@ -411,6 +643,9 @@ pub enum Event {
     },
 
     /// Network specs were added
+    ///
+    /// Recorded before the token lists were kept in the history log, new
+    /// entries have [`Event::NetworkSpecsTokensAdded`] instead.
     NetworkSpecsAdded {
         network_specs_display: NetworkSpecsDisplay,
     },
@@ -491,7 +726,10 @@ pub enum Event {
     /// User has generated QR code for secret key export
     SecretWasExported { identity_history: IdentityHistory },
 
-    /// A warning was produces and displayed to user
+    /// A warning was produces and displayed to user, as English text
+    ///
+    /// Recorded by older Signer versions, new warnings are recorded as
+    /// [`Event::WarningMessage`].
     Warning { warning: String },
 
     /// User has entered wrong password
@@ -508,18 +746,725 @@ pub enum Event {
 
     /// Database was initiated
     DatabaseInitiated,
+
+    /// Named contact was added or renamed
+    ContactAdded { contact_display: ContactDisplay },
+
+    /// Named contact was removed
+    ContactRemoved { contact_display: ContactDisplay },
+
+    /// A warning was produced and displayed to user
+    WarningMessage { message: LocalisedMessage },
+
+    /// Custom network verifier was rotated, with endorsement of the old
+    /// verifier
+    NetworkVerifierRotated {
+        network_verifier_rotation_display: NetworkVerifierRotationDisplay,
+    },
+
+    /// Signer security policy was set up
+    SecurityPolicySet { security_policy: SecurityPolicy },
+
+    /// User attempted an action forbidden by the Signer security policy
+    SecurityPolicyViolation { rule: PolicyRule },
+
+    /// USB debugging was enabled on Signer device
+    UsbDebuggingWasEnabled,
+
+    /// Wi-Fi radio was enabled on Signer device
+    WifiWasEnabled,
+
+    /// Bluetooth radio was enabled on Signer device
+    BluetoothWasEnabled,
+
+    /// Signer screen was recorded
+    ScreenWasRecorded,
+
+    /// Signer device bootloader was unlocked
+    BootloaderWasUnlocked,
+
+    /// Cold database was migrated to newer schema version
+    DatabaseMigrated { from_version: u32, to_version: u32 },
+
+    /// Cold database inconsistencies were repaired, with descriptions of the
+    /// applied repairs
+    DatabaseRepaired { repairs: Vec<String> },
+
+    /// Display specs of a known network were amended
+    ///
+    /// Recorded before the token lists were kept in the history log, new
+    /// entries have [`Event::NetworkSpecsTokensAmended`] instead.
+    NetworkSpecsAmended {
+        network_specs_amendment_display: NetworkSpecsAmendmentDisplay,
+    },
+
+    /// Signed history log audit report was exported
+    HistoryAuditExported {
+        history_audit_export: HistoryAuditExport,
+    },
+
+    /// User-defined label, tags or notes of the address key were changed
+    KeyAnnotationChanged {
+        key_annotation_history: KeyAnnotationHistory,
+    },
+
+    /// Network specs were added, with network token list
+    NetworkSpecsTokensAdded {
+        network_specs_tokens_display: NetworkSpecsTokensDisplay,
+    },
+
+    /// Display specs of a known network were amended, with network token
+    /// lists
+    NetworkSpecsTokensAmended {
+        network_specs_amendment_tokens_display: NetworkSpecsAmendmentTokensDisplay,
+    },
+}
+
+impl Event {
+    /// Event kind, i.e. the name of the [`Event`] variant, as used in the
+    /// history log search and audit report.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Event::MetadataAdded { .. } => "MetadataAdded",
+            Event::MetadataRemoved { .. } => "MetadataRemoved",
+            Event::MetadataSigned { .. } => "MetadataSigned",
+            Event::NetworkSpecsAdded { .. } => "NetworkSpecsAdded",
+            Event::NetworkSpecsRemoved { .. } => "NetworkSpecsRemoved",
+            Event::NetworkSpecsSigned { .. } => "NetworkSpecsSigned",
+            Event::NetworkVerifierSet { .. } => "NetworkVerifierSet",
+            Event::GeneralVerifierSet { .. } => "GeneralVerifierSet",
+            Event::TypesAdded { .. } => "TypesAdded",
+            Event::TypesRemoved { .. } => "TypesRemoved",
+            Event::TypesSigned { .. } => "TypesSigned",
+            Event::TransactionSigned { .. } => "TransactionSigned",
+            Event::TransactionSignError { .. } => "TransactionSignError",
+            Event::MessageSigned { .. } => "MessageSigned",
+            Event::MessageSignError { .. } => "MessageSignError",
+            Event::IdentityAdded { .. } => "IdentityAdded",
+            Event::IdentityRemoved { .. } => "IdentityRemoved",
+            Event::IdentitiesWiped => "IdentitiesWiped",
+            Event::DeviceWasOnline => "DeviceWasOnline",
+            Event::ResetDangerRecord => "ResetDangerRecord",
+            Event::SeedCreated { .. } => "SeedCreated",
+            Event::SeedRemoved { .. } => "SeedRemoved",
+            Event::SeedNameWasShown { .. } => "SeedNameWasShown",
+            Event::SecretWasExported { .. } => "SecretWasExported",
+            Event::Warning { .. } => "Warning",
+            Event::WrongPassword => "WrongPassword",
+            Event::UserEntry { .. } => "UserEntry",
+            Event::SystemEntry { .. } => "SystemEntry",
+            Event::HistoryCleared => "HistoryCleared",
+            Event::DatabaseInitiated => "DatabaseInitiated",
+            Event::ContactAdded { .. } => "ContactAdded",
+            Event::ContactRemoved { .. } => "ContactRemoved",
+            Event::WarningMessage { .. } => "WarningMessage",
+            Event::NetworkVerifierRotated { .. } => "NetworkVerifierRotated",
+            Event::SecurityPolicySet { .. } => "SecurityPolicySet",
+            Event::SecurityPolicyViolation { .. } => "SecurityPolicyViolation",
+            Event::UsbDebuggingWasEnabled => "UsbDebuggingWasEnabled",
+            Event::WifiWasEnabled => "WifiWasEnabled",
+            Event::BluetoothWasEnabled => "BluetoothWasEnabled",
+            Event::ScreenWasRecorded => "ScreenWasRecorded",
+            Event::BootloaderWasUnlocked => "BootloaderWasUnlocked",
+            Event::DatabaseMigrated { .. } => "DatabaseMigrated",
+            Event::DatabaseRepaired { .. } => "DatabaseRepaired",
+            Event::NetworkSpecsAmended { .. } => "NetworkSpecsAmended",
+            Event::HistoryAuditExported { .. } => "HistoryAuditExported",
+            Event::KeyAnnotationChanged { .. } => "KeyAnnotationChanged",
+            Event::NetworkSpecsTokensAdded { .. } => "NetworkSpecsTokensAdded",
+            Event::NetworkSpecsTokensAmended { .. } => "NetworkSpecsTokensAmended",
+        }
+    }
+}
+
+#[cfg(feature = "signer")]
+impl Event {
+    /// Event name and event details as `(field name, value)` pairs, for
+    /// history log audit report.
+    ///
+    /// Public keys, hashes and raw transactions are hexadecimal.
+    pub fn audit_fields(&self) -> (&'static str, Vec<(&'static str, String)>) {
+        match self {
+            Event::MetadataAdded {
+                meta_values_display,
+            } => ("MetadataAdded", meta_values_display.audit_fields()),
+            Event::MetadataRemoved {
+                meta_values_display,
+            } => ("MetadataRemoved", meta_values_display.audit_fields()),
+            Event::MetadataSigned { meta_values_export } => (
+                "MetadataSigned",
+                vec![
+                    ("name", meta_values_export.name.to_string()),
+                    ("version", meta_values_export.version.to_string()),
+                    ("meta_hash", hex::encode(meta_values_export.meta_hash)),
+                    ("signed_by", meta_values_export.signed_by.show_error()),
+                ],
+            ),
+            Event::NetworkSpecsAdded {
+                network_specs_display,
+            } => ("NetworkSpecsAdded", network_specs_display.audit_fields()),
+            Event::NetworkSpecsRemoved {
+                network_specs_display,
+            } => ("NetworkSpecsRemoved", network_specs_display.audit_fields()),
+            Event::NetworkSpecsSigned {
+                network_specs_export,
+            } => (
+                "NetworkSpecsSigned",
+                vec![
+                    ("name", network_specs_export.specs_to_send.name.to_string()),
+                    (
+                        "genesis_hash",
+                        hex::encode(network_specs_export.specs_to_send.genesis_hash),
+                    ),
+                    (
+                        "encryption",
+                        network_specs_export.specs_to_send.encryption.show(),
+                    ),
+                    ("signed_by", network_specs_export.signed_by.show_error()),
+                ],
+            ),
+            Event::NetworkVerifierSet {
+                network_verifier_display,
+            } => (
+                "NetworkVerifierSet",
+                vec![
+                    (
+                        "genesis_hash",
+                        hex::encode(network_verifier_display.genesis_hash),
+                    ),
+                    (
+                        "verifier",
+                        show_valid_current_verifier(
+                            &network_verifier_display.valid_current_verifier,
+                        ),
+                    ),
+                    (
+                        "general_verifier",
+                        network_verifier_display.general_verifier.show_error(),
+                    ),
+                ],
+            ),
+            Event::GeneralVerifierSet { verifier } => (
+                "GeneralVerifierSet",
+                vec![("verifier", verifier.show_error())],
+            ),
+            Event::TypesAdded { types_display } => ("TypesAdded", types_display.audit_fields()),
+            Event::TypesRemoved { types_display } => ("TypesRemoved", types_display.audit_fields()),
+            Event::TypesSigned { types_export } => (
+                "TypesSigned",
+                vec![
+                    ("types_hash", hex::encode(types_export.types_hash)),
+                    ("signed_by", types_export.signed_by.show_error()),
+                ],
+            ),
+            Event::TransactionSigned { sign_display } => {
+                ("TransactionSigned", sign_display.audit_fields())
+            }
+            Event::TransactionSignError { sign_display } => {
+                ("TransactionSignError", sign_display.audit_fields())
+            }
+            Event::MessageSigned {
+                sign_message_display,
+            } => ("MessageSigned", sign_message_display.audit_fields()),
+            Event::MessageSignError {
+                sign_message_display,
+            } => ("MessageSignError", sign_message_display.audit_fields()),
+            Event::IdentityAdded { identity_history } => {
+                ("IdentityAdded", identity_history.audit_fields())
+            }
+            Event::IdentityRemoved { identity_history } => {
+                ("IdentityRemoved", identity_history.audit_fields())
+            }
+            Event::IdentitiesWiped => ("IdentitiesWiped", Vec::new()),
+            Event::DeviceWasOnline => ("DeviceWasOnline", Vec::new()),
+            Event::ResetDangerRecord => ("ResetDangerRecord", Vec::new()),
+            Event::SeedCreated { seed_created } => {
+                ("SeedCreated", vec![("seed_name", seed_created.to_string())])
+            }
+            Event::SeedRemoved { seed_name } => {
+                ("SeedRemoved", vec![("seed_name", seed_name.to_string())])
+            }
+            Event::SeedNameWasShown {
+                seed_name_was_shown,
+            } => (
+                "SeedNameWasShown",
+                vec![("seed_name", seed_name_was_shown.to_string())],
+            ),
+            Event::SecretWasExported { identity_history } => {
+                ("SecretWasExported", identity_history.audit_fields())
+            }
+            Event::Warning { warning } => ("Warning", vec![("warning", warning.to_string())]),
+            Event::WrongPassword => ("WrongPassword", Vec::new()),
+            Event::UserEntry { user_entry } => {
+                ("UserEntry", vec![("user_entry", user_entry.to_string())])
+            }
+            Event::SystemEntry { system_entry } => (
+                "SystemEntry",
+                vec![("system_entry", system_entry.to_string())],
+            ),
+            Event::HistoryCleared => ("HistoryCleared", Vec::new()),
+            Event::DatabaseInitiated => ("DatabaseInitiated", Vec::new()),
+            Event::ContactAdded { contact_display } => {
+                ("ContactAdded", contact_display.audit_fields())
+            }
+            Event::ContactRemoved { contact_display } => {
+                ("ContactRemoved", contact_display.audit_fields())
+            }
+            Event::WarningMessage { message } => (
+                "WarningMessage",
+                vec![
+                    ("warning_id", message.id.to_string()),
+                    ("warning", message.show()),
+                ],
+            ),
+            Event::NetworkVerifierRotated {
+                network_verifier_rotation_display,
+            } => (
+                "NetworkVerifierRotated",
+                vec![
+                    (
+                        "genesis_hash",
+                        hex::encode(network_verifier_rotation_display.genesis_hash),
+                    ),
+                    (
+                        "old_verifier",
+                        network_verifier_rotation_display
+                            .old_verifier_value
+                            .show_error(),
+                    ),
+                    (
+                        "new_verifier",
+                        network_verifier_rotation_display
+                            .new_verifier_value
+                            .show_error(),
+                    ),
+                ],
+            ),
+            Event::SecurityPolicySet { security_policy } => (
+                "SecurityPolicySet",
+                std::iter::once(("index", security_policy.index.to_string()))
+                    .chain(
+                        PolicyRule::ALL
+                            .iter()
+                            .map(|rule| (rule.id(), security_policy.forbids(*rule).to_string())),
+                    )
+                    .collect(),
+            ),
+            Event::SecurityPolicyViolation { rule } => (
+                "SecurityPolicyViolation",
+                vec![("rule", rule.id().to_string())],
+            ),
+            Event::UsbDebuggingWasEnabled => ("UsbDebuggingWasEnabled", Vec::new()),
+            Event::WifiWasEnabled => ("WifiWasEnabled", Vec::new()),
+            Event::BluetoothWasEnabled => ("BluetoothWasEnabled", Vec::new()),
+            Event::ScreenWasRecorded => ("ScreenWasRecorded", Vec::new()),
+            Event::BootloaderWasUnlocked => ("BootloaderWasUnlocked", Vec::new()),
+            Event::DatabaseMigrated {
+                from_version,
+                to_version,
+            } => (
+                "DatabaseMigrated",
+                vec![
+                    ("from_version", from_version.to_string()),
+                    ("to_version", to_version.to_string()),
+                ],
+            ),
+            Event::DatabaseRepaired { repairs } => {
+                ("DatabaseRepaired", vec![("repairs", repairs.join("; "))])
+            }
+            Event::NetworkSpecsAmended {
+                network_specs_amendment_display,
+            } => (
+                "NetworkSpecsAmended",
+                network_specs_amendment_display.audit_fields(),
+            ),
+            Event::HistoryAuditExported {
+                history_audit_export,
+            } => (
+                "HistoryAuditExported",
+                vec![
+                    ("first_order", history_audit_export.first_order.to_string()),
+                    ("last_order", history_audit_export.last_order.to_string()),
+                    ("format", format!("{:?}", history_audit_export.format)),
+                    ("report_hash", hex::encode(history_audit_export.report_hash)),
+                    ("signed_by", history_audit_export.signed_by.show_error()),
+                ],
+            ),
+            Event::KeyAnnotationChanged {
+                key_annotation_history,
+            } => (
+                "KeyAnnotationChanged",
+                key_annotation_history.audit_fields(),
+            ),
+            Event::NetworkSpecsTokensAdded {
+                network_specs_tokens_display,
+            } => (
+                "NetworkSpecsTokensAdded",
+                network_specs_tokens_display.audit_fields(),
+            ),
+            Event::NetworkSpecsTokensAmended {
+                network_specs_amendment_tokens_display,
+            } => (
+                "NetworkSpecsTokensAmended",
+                network_specs_amendment_tokens_display.audit_fields(),
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "signer")]
+impl MetaValuesDisplay {
+    fn audit_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("name", self.name.to_string()),
+            ("version", self.version.to_string()),
+            ("meta_hash", hex::encode(self.meta_hash)),
+        ]
+    }
+}
+
+#[cfg(feature = "signer")]
+impl NetworkSpecsDisplay {
+    fn audit_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("name", self.specs.name.to_string()),
+            ("genesis_hash", hex::encode(self.specs.genesis_hash)),
+            ("encryption", self.specs.encryption.show()),
+            (
+                "verifier",
+                show_valid_current_verifier(&self.valid_current_verifier),
+            ),
+            ("general_verifier", self.general_verifier.show_error()),
+        ]
+    }
+}
+
+#[cfg(feature = "signer")]
+impl NetworkSpecsTokensDisplay {
+    fn audit_fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = self.network_specs_display.audit_fields();
+        fields.push(("tokens", crate::network_specs::show_tokens(&self.tokens)));
+        fields
+    }
+}
+
+#[cfg(feature = "signer")]
+impl NetworkSpecsAmendmentDisplay {
+    fn audit_fields(&self) -> Vec<(&'static str, String)> {
+        let changes = self
+            .old_network_specs
+            .to_send()
+            .display_changes(&self.new_network_specs.to_send())
+            .into_iter()
+            .map(|(field, old, new)| format!("{}: {} -> {}", field, old, new))
+            .collect::<Vec<String>>();
+        vec![
+            ("name", self.new_network_specs.name.to_string()),
+            (
+                "genesis_hash",
+                hex::encode(self.new_network_specs.genesis_hash),
+            ),
+            ("encryption", self.new_network_specs.encryption.show()),
+            ("changes", changes.join("; ")),
+        ]
+    }
+}
+
+#[cfg(feature = "signer")]
+impl NetworkSpecsAmendmentTokensDisplay {
+    fn audit_fields(&self) -> Vec<(&'static str, String)> {
+        // token lists are put back into network specs, so that the token
+        // changes are listed with the other changes
+        let mut network_specs_amendment_display = self.network_specs_amendment_display.to_owned();
+        network_specs_amendment_display.old_network_specs.tokens = self.old_tokens.to_vec();
+        network_specs_amendment_display.new_network_specs.tokens = self.new_tokens.to_vec();
+        network_specs_amendment_display.audit_fields()
+    }
+}
+
+#[cfg(feature = "signer")]
+impl TypesDisplay {
+    fn audit_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("types_hash", hex::encode(self.types_hash)),
+            ("verifier", self.verifier.show_error()),
+        ]
+    }
+}
+
+#[cfg(feature = "signer")]
+impl SignDisplay {
+    fn audit_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("transaction", hex::encode(&self.transaction)),
+            ("network_name", self.network_name.to_string()),
+            ("signed_by", self.signed_by.show_error()),
+            ("user_comment", self.user_comment.to_string()),
+        ]
+    }
+}
+
+#[cfg(feature = "signer")]
+impl SignMessageDisplay {
+    fn audit_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("message", self.message.to_string()),
+            ("network_name", self.network_name.to_string()),
+            ("signed_by", self.signed_by.show_error()),
+            ("user_comment", self.user_comment.to_string()),
+        ]
+    }
+}
+
+#[cfg(feature = "signer")]
+impl IdentityHistory {
+    fn audit_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("seed_name", self.seed_name.to_string()),
+            ("encryption", self.encryption.show()),
+            ("public_key", hex::encode(&self.public_key)),
+            ("path", self.path.to_string()),
+            ("genesis_hash", hex::encode(self.network_genesis_hash)),
+        ]
+    }
+}
+
+#[cfg(feature = "signer")]
+impl KeyAnnotationHistory {
+    fn audit_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("seed_name", self.seed_name.to_string()),
+            ("encryption", self.encryption.show()),
+            ("public_key", hex::encode(&self.public_key)),
+            ("path", self.path.to_string()),
+            ("label", self.annotation.label.to_string()),
+            ("tags", self.annotation.tags.join(",")),
+            ("notes", self.annotation.notes.to_string()),
+        ]
+    }
+}
+
+#[cfg(feature = "signer")]
+impl ContactDisplay {
+    fn audit_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("account_id", hex::encode(&self.account_id)),
+            ("name", self.name.to_string()),
+            ("verifier", self.verifier.show_error()),
+        ]
+    }
+}
+
+/// Display [`ValidCurrentVerifier`] in human-readable format, for history log
+/// audit report.
+#[cfg(feature = "signer")]
+fn show_valid_current_verifier(valid_current_verifier: &ValidCurrentVerifier) -> String {
+    match valid_current_verifier {
+        ValidCurrentVerifier::General => String::from("general"),
+        ValidCurrentVerifier::Custom { v } => v.show_error(),
+    }
+}
+
+/// Link of history log [`Entry`] to the log before it
+#[derive(Debug, Decode, Encode, Clone, PartialEq, Eq)]
+pub enum EntryLink {
+    /// Hash of the previous entry of the log
+    Previous { previous_hash: H256 },
+
+    /// Genesis marker of the log, the entry with `Order` `0`
+    ///
+    /// Genesis marker signs off the log that was cleared when the new log was
+    /// started: it records the hash of the last cleared entry and the number
+    /// of cleared entries. For a newly initiated database the hash is zero
+    /// hash, and there are no cleared entries.
+    Genesis {
+        cleared_head: H256,
+        cleared_entries: u32,
+    },
 }
 
 /// History log individual entry
 ///
-/// Contains timestamp and set of simultaneously occurred events `Vec<Event>`.
+/// Contains timestamp, set of simultaneously occurred events `Vec<Event>` and
+/// the link to the previous entry.
 ///
-/// `Entry` is stored SCALE-encoded in the `HISTORY` tree of the cold database,
-/// under key `Order`.
-#[derive(Debug, Decode, Encode, Clone)]
+/// `Entry` is stored in the `HISTORY` tree of the cold database, under key
+/// `Order`, as SCALE-encoded timestamp and events, followed by SCALE-encoded
+/// [`EntryLink`]. Entries stored before the history log was hash-chained
+/// have no link.
+///
+/// Entry with transaction signed with metadata proof has the proof stored
+/// after the link, as SCALE-encoded [`EventProof`] set, so that the
+/// transaction could be decoded later without network metadata. Proofs
+/// are a part of the entry hash.
+#[derive(Debug, Clone)]
 pub struct Entry {
     pub timestamp: String,
     pub events: Vec<Event>, // events already in showable form
+    pub link: Option<EntryLink>,
+    pub metadata_proofs: Vec<EventProof>,
+}
+
+/// Metadata proof of the transaction in history log [`Entry`] event
+#[derive(Debug, Clone, Decode, Encode, PartialEq)]
+pub struct EventProof {
+    /// Index of `Event::TransactionSigned` or `Event::TransactionSignError`
+    /// in the entry events
+    pub event_index: u32,
+
+    /// Metadata proof the transaction was decoded with
+    pub metadata_proof: MetadataProof,
+}
+
+impl Entry {
+    /// Transform [`Entry`] into `Vec<u8>` to put in the database.
+    ///
+    /// Metadata proofs are stored only for the entries with link.
+    pub fn store(&self) -> Vec<u8> {
+        let mut out = (&self.timestamp, &self.events).encode();
+        if let Some(ref link) = self.link {
+            link.encode_to(&mut out);
+            if !self.metadata_proofs.is_empty() {
+                self.metadata_proofs.encode_to(&mut out);
+            }
+        }
+        out
+    }
+
+    /// Get [`Entry`] from the value stored in the database.
+    ///
+    /// Values stored before the history log was hash-chained are processed
+    /// as well, and have no link. Only the non-empty metadata proof set could
+    /// follow the link, any other data left after the link is an error.
+    pub fn from_stored(mut entry_encoded: &[u8]) -> Result<Self> {
+        let timestamp = String::decode(&mut entry_encoded)?;
+        let events = <Vec<Event>>::decode(&mut entry_encoded)?;
+        let link = if entry_encoded.is_empty() {
+            None
+        } else {
+            Some(EntryLink::decode(&mut entry_encoded)?)
+        };
+        let metadata_proofs = if entry_encoded.is_empty() {
+            Vec::new()
+        } else {
+            let metadata_proofs = <Vec<EventProof>>::decode_all(&mut entry_encoded)?;
+            if metadata_proofs.is_empty() {
+                return Err(parity_scale_codec::Error::from(
+                    "empty metadata proof set is not stored",
+                )
+                .into());
+            }
+            metadata_proofs
+        };
+        Ok(Self {
+            timestamp,
+            events,
+            link,
+            metadata_proofs,
+        })
+    }
+
+    /// Metadata proof of the transaction in the event with given index, if
+    /// the transaction was signed with proof.
+    pub fn metadata_proof(&self, event_index: usize) -> Option<&MetadataProof> {
+        self.metadata_proofs
+            .iter()
+            .find(|x| x.event_index as usize == event_index)
+            .map(|x| &x.metadata_proof)
+    }
+
+    /// Hash of the [`Entry`], as it is stored in the database.
+    ///
+    /// Next entry in the history log links to this hash.
+    pub fn hash(&self) -> H256 {
+        entry_hash(&self.store())
+    }
+
+    /// Hash that the [`Entry`] links to: the hash of the previous entry, or,
+    /// for genesis marker, the hash of the last cleared entry.
+    ///
+    /// Entries without link result in zero hash.
+    pub fn previous_hash(&self) -> H256 {
+        match self.link {
+            Some(EntryLink::Previous { previous_hash }) => previous_hash,
+            Some(EntryLink::Genesis { cleared_head, .. }) => cleared_head,
+            None => H256::zero(),
+        }
+    }
+}
+
+/// Hash of SCALE-encoded [`Entry`], as it is stored in the database.
+pub fn entry_hash(entry_encoded: &[u8]) -> H256 {
+    blake2_256(entry_encoded).into()
+}
+
+/// Format of the history log audit report
+#[derive(Clone, Copy, Debug, Decode, Encode, PartialEq, Eq)]
+pub enum AuditFormat {
+    /// JSON object with history entries and their events
+    Json,
+
+    /// CSV table with one row per event
+    Csv,
+}
+
+/// Opening tag of the signed history log audit content
+///
+/// Audit report is signed wrapped in tags, so that the signature could not be
+/// passed for a signature of a transaction, a message or an update payload.
+pub const AUDIT_CONTENT_PREFIX: &[u8] = b"<Bytes><SignerHistoryAudit>";
+
+/// Closing tag of the signed history log audit content
+pub const AUDIT_CONTENT_SUFFIX: &[u8] = b"</SignerHistoryAudit></Bytes>";
+
+/// History log audit report signed by one of Signer keys
+///
+/// Report covers a range of history log entries, and is exported from Signer
+/// as a multiframe QR code, SCALE-encoded. Signature in [`SufficientCrypto`]
+/// is made for [`SignedHistoryAudit::signed_content`] and could be checked on
+/// the hot side with [`SignedHistoryAudit::is_valid`].
+#[derive(Debug, Decode, Encode, Clone)]
+pub struct SignedHistoryAudit {
+    /// report format
+    pub format: AuditFormat,
+
+    /// report text in given format
+    pub report: Vec<u8>,
+
+    /// public key and signature of the key that signed the report
+    pub sufficient_crypto: SufficientCrypto,
+}
+
+impl SignedHistoryAudit {
+    /// Content that gets signed for the report in given format: SCALE-encoded
+    /// format and the report, wrapped in [`AUDIT_CONTENT_PREFIX`] and
+    /// [`AUDIT_CONTENT_SUFFIX`].
+    pub fn signed_content(format: AuditFormat, report: &[u8]) -> Vec<u8> {
+        [
+            AUDIT_CONTENT_PREFIX,
+            &format.encode(),
+            report,
+            AUDIT_CONTENT_SUFFIX,
+        ]
+        .concat()
+    }
+
+    /// Check that the signature in [`SufficientCrypto`] is valid for the
+    /// report.
+    pub fn is_valid(&self) -> bool {
+        let signed_content = Self::signed_content(self.format, &self.report);
+        match &self.sufficient_crypto {
+            SufficientCrypto::Ed25519 { public, signature } => {
+                ed25519::Pair::verify(signature, &signed_content, public)
+            }
+            SufficientCrypto::Sr25519 { public, signature } => {
+                sr25519::Pair::verify(signature, &signed_content, public)
+            }
+            SufficientCrypto::Ecdsa { public, signature } => {
+                ecdsa::Pair::verify(signature, &signed_content, public)
+            }
+        }
+    }
 }
 
 /// Test function generating a set of all possible events
@@ -563,6 +1508,7 @@ pub fn all_events_preview() -> Vec<Event> {
         path_id: String::from("//westend"),
         secondary_color: String::from("#262626"),
         title: String::from("Westend"),
+        tokens: Vec::new(),
         unit: String::from("WND"),
     };
     vec![
@@ -694,5 +1640,110 @@ pub fn all_events_preview() -> Vec<Event> {
         },
         Event::HistoryCleared,
         Event::DatabaseInitiated,
+        Event::ContactAdded {
+            contact_display: ContactDisplay::get(
+                &ContactEntry {
+                    account_id: public,
+                    name: String::from("Exchange deposit"),
+                },
+                &verifier,
+            ),
+        },
+        Event::ContactRemoved {
+            contact_display: ContactDisplay::get(
+                &ContactEntry {
+                    account_id: public,
+                    name: String::from("Exchange deposit"),
+                },
+                &Verifier { v: None },
+            ),
+        },
+        Event::WarningMessage {
+            message: LocalisedMessage::new("warning.not_verified"),
+        },
+        Event::NetworkVerifierRotated {
+            network_verifier_rotation_display: NetworkVerifierRotationDisplay::get(
+                &VerifierKey::from_parts(network_specs.genesis_hash),
+                &verifier_value,
+                &VerifierValue::Standard {
+                    m: MultiSigner::Sr25519(sp_core::sr25519::Public::from_raw([1u8; 32])),
+                },
+            ),
+        },
+        Event::SecurityPolicySet {
+            security_policy: SecurityPolicy {
+                index: 1,
+                reject_unsigned_updates: true,
+                require_user_comment: true,
+                ..Default::default()
+            },
+        },
+        Event::SecurityPolicyViolation {
+            rule: PolicyRule::SecretExport,
+        },
+        Event::UsbDebuggingWasEnabled,
+        Event::WifiWasEnabled,
+        Event::BluetoothWasEnabled,
+        Event::ScreenWasRecorded,
+        Event::BootloaderWasUnlocked,
+        Event::DatabaseMigrated {
+            from_version: 0,
+            to_version: 1,
+        },
+        Event::DatabaseRepaired {
+            repairs: vec!["leftover transaction entry removed".to_string()],
+        },
+        Event::NetworkSpecsAmended {
+            network_specs_amendment_display: NetworkSpecsAmendmentDisplay::get(
+                &network_specs,
+                &NetworkSpecs {
+                    unit: String::from("DOT"),
+                    decimals: 10,
+                    ..network_specs.to_owned()
+                },
+            ),
+        },
+        Event::HistoryAuditExported {
+            history_audit_export: HistoryAuditExport {
+                first_order: 0,
+                last_order: 1,
+                format: AuditFormat::Csv,
+                report_hash: H256::from([3u8; 32]),
+                signed_by: verifier_value.to_owned(),
+            },
+        },
+        Event::KeyAnnotationChanged {
+            key_annotation_history: KeyAnnotationHistory::get(
+                "Alice",
+                &Encryption::Sr25519,
+                &public,
+                "//westend",
+                &KeyAnnotation {
+                    label: String::from("Treasury"),
+                    tags: vec![String::from("team")],
+                    notes: String::from("multisig member"),
+                },
+            ),
+        },
+        Event::NetworkSpecsTokensAdded {
+            network_specs_tokens_display: NetworkSpecsTokensDisplay::get(
+                &network_specs,
+                &valid_current_verifier,
+                &verifier,
+            ),
+        },
+        Event::NetworkSpecsTokensAmended {
+            network_specs_amendment_tokens_display: NetworkSpecsAmendmentTokensDisplay::get(
+                &network_specs,
+                &NetworkSpecs {
+                    tokens: vec![TokenSpecs {
+                        asset_id: Some(1984),
+                        decimals: 6,
+                        unit: String::from("USDt"),
+                    }],
+                    ..network_specs.to_owned()
+                },
+            ),
+        },
     ]
 }
@@ -354,7 +354,6 @@ impl Order {
     ///
     /// If `Order` could not be decoded, i.e. entry is corrupted, produces an
     /// error.  
-    #[cfg(feature = "signer")]
     pub fn from_ivec(ivec: &IVec) -> Result<Self> {
         Ok(Self(<u32>::decode(&mut &ivec[..])?))
     }
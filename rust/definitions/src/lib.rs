@@ -31,10 +31,16 @@ pub mod history;
 
 pub mod keyring;
 
+pub mod localisation;
+
 pub mod metadata;
 
+pub mod metadata_proof;
+
 pub mod network_specs;
 
+pub mod policy;
+
 pub mod qr_transfers;
 
 pub mod types;
@@ -0,0 +1,292 @@
+//! Localisation-ready messages
+//!
+//! Texts shown to the user, such as transaction card warnings and warnings
+//! recorded in history log, are produced in Rust as [`LocalisedMessage`]:
+//! stable message identifier with named typed arguments. Frontends could
+//! render the message in any language using own templates, and never need to
+//! parse English text.
+//!
+//! Message templates are kept in a [`Catalog`]. Template refers to message
+//! arguments by name in curly brackets, for example
+//! `"Transaction uses outdated runtime version {used_version}."`.
+//! [`English`] catalog is built in, and is used as a fallback for any
+//! message identifier missing in other catalog.
+//!
+//! Message identifiers are stable, and must not be changed or reused once
+//! released, since messages are stored in history log.
+use parity_scale_codec::{Decode, Encode};
+use std::collections::HashMap;
+
+/// Message with stable identifier and named typed arguments
+#[derive(Decode, Encode, PartialEq, Eq, Debug, Clone)]
+pub struct LocalisedMessage {
+    /// Message identifier, key in [`Catalog`]
+    pub id: String,
+
+    /// Message arguments
+    pub args: Vec<MessageArg>,
+}
+
+/// Named message argument
+#[derive(Decode, Encode, PartialEq, Eq, Debug, Clone)]
+pub struct MessageArg {
+    pub name: String,
+    pub value: MessageArgValue,
+}
+
+/// Typed value of message argument
+#[derive(Decode, Encode, PartialEq, Eq, Debug, Clone)]
+pub enum MessageArgValue {
+    /// Text not subject to translation, such as network name or address
+    Text { s: String },
+
+    /// Number, could be formatted according to the locale
+    Number { n: u64 },
+
+    /// Hash or public key, shown as hexadecimal
+    Hex { h: Vec<u8> },
+
+    /// List of texts not subject to translation; empty list is shown as
+    /// `list.none` message
+    List { items: Vec<String> },
+}
+
+/// Message template, for catalog export
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct MessageTemplate {
+    pub id: String,
+    pub template: String,
+}
+
+/// Message template set for a language
+pub trait Catalog {
+    /// Template for message identifier, if known
+    fn template(&self, id: &str) -> Option<&str>;
+}
+
+/// Built-in English catalog, used as a fallback for all other catalogs
+pub struct English;
+
+impl Catalog for English {
+    fn template(&self, id: &str) -> Option<&str> {
+        ENGLISH
+            .iter()
+            .find(|(template_id, _)| *template_id == id)
+            .map(|(_, template)| *template)
+    }
+}
+
+/// Catalog with templates, for example loaded from translation file
+impl Catalog for HashMap<String, String> {
+    fn template(&self, id: &str) -> Option<&str> {
+        self.get(id).map(|template| template.as_str())
+    }
+}
+
+impl LocalisedMessage {
+    /// Message without arguments
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Add argument to the message
+    pub fn with(mut self, name: &str, value: MessageArgValue) -> Self {
+        self.args.push(MessageArg {
+            name: name.to_string(),
+            value,
+        });
+        self
+    }
+
+    /// Add text argument to the message
+    pub fn with_text(self, name: &str, s: &str) -> Self {
+        self.with(name, MessageArgValue::Text { s: s.to_string() })
+    }
+
+    /// Add number argument to the message
+    pub fn with_number<N: Into<u64>>(self, name: &str, n: N) -> Self {
+        self.with(name, MessageArgValue::Number { n: n.into() })
+    }
+
+    /// Add hexadecimal argument to the message
+    pub fn with_hex<H: AsRef<[u8]>>(self, name: &str, h: H) -> Self {
+        self.with(
+            name,
+            MessageArgValue::Hex {
+                h: h.as_ref().to_vec(),
+            },
+        )
+    }
+
+    /// Add list argument to the message
+    pub fn with_list(self, name: &str, items: Vec<String>) -> Self {
+        self.with(name, MessageArgValue::List { items })
+    }
+
+    /// Render message with templates from the catalog, falling back to
+    /// [`English`] template. Message with unknown identifier is rendered as
+    /// the identifier itself.
+    pub fn render<C: Catalog>(&self, catalog: &C) -> String {
+        let template = match catalog
+            .template(&self.id)
+            .or_else(|| English.template(&self.id))
+        {
+            Some(template) => template,
+            None => return self.id.to_string(),
+        };
+        let mut out = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            match after
+                .find('}')
+                .and_then(|end| self.arg(&after[..end]).map(|value| (end, value)))
+            {
+                Some((end, value)) => {
+                    out.push_str(&value.render(catalog));
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    out.push('{');
+                    rest = after;
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+
+    /// Render message in English
+    pub fn show(&self) -> String {
+        self.render(&English)
+    }
+
+    fn arg(&self, name: &str) -> Option<&MessageArgValue> {
+        self.args
+            .iter()
+            .find(|arg| arg.name == name)
+            .map(|arg| &arg.value)
+    }
+}
+
+impl MessageArgValue {
+    fn render<C: Catalog>(&self, catalog: &C) -> String {
+        match self {
+            MessageArgValue::Text { s } => s.to_string(),
+            MessageArgValue::Number { n } => n.to_string(),
+            MessageArgValue::Hex { h } => hex::encode(h),
+            MessageArgValue::List { items } => {
+                if items.is_empty() {
+                    LocalisedMessage::new("list.none").render(catalog)
+                } else {
+                    items.join(", ")
+                }
+            }
+        }
+    }
+}
+
+/// All English message templates
+pub fn english_catalog() -> Vec<MessageTemplate> {
+    ENGLISH
+        .iter()
+        .map(|(id, template)| MessageTemplate {
+            id: id.to_string(),
+            template: template.to_string(),
+        })
+        .collect()
+}
+
+/// English message templates, `(identifier, template)`
+pub const ENGLISH: &[(&str, &str)] = &[
+    ("list.none", "none"),
+    ("warning.author_not_found", "Transaction author public key not found."),
+    ("warning.newer_version", "Transaction uses outdated runtime version {used_version}. Latest known available version is {latest_version}."),
+    ("warning.no_network_id", "Public key is on record, but not associated with the network used."),
+    ("warning.not_verified", "Received network information is not verified."),
+    ("warning.updating_types", "Updating types (really rare operation)."),
+    ("warning.types_not_verified", "Received types information is not verified."),
+    ("warning.general_verifier_appeared", "Received message is verified by a new general verifier. Currently no general verifier is set, and proceeding will update the general verifier to the received value. All previously acquired information associated with general verifier will be purged. Affected network specs entries: {network_specs}; affected metadata entries: {metadata}."),
+    ("warning.general_verifier_appeared_types", "Received message is verified by a new general verifier. Currently no general verifier is set, and proceeding will update the general verifier to the received value. All previously acquired information associated with general verifier will be purged. Affected network specs entries: {network_specs}; affected metadata entries: {metadata}. Types information is purged."),
+    ("warning.verifier_changing_to_general", "Received message is verified by the general verifier. Current verifier for network with genesis hash {genesis_hash} is a custom one, and proceeding will update the network verifier to general. All previously acquired information associated with former custom verifier will be purged. Affected network specs entries: {network_specs}; affected metadata entries: {metadata}."),
+    ("warning.verifier_changing_to_custom", "Received message is verified. Currently no verifier is set for network with genesis hash {genesis_hash}. Proceeding will update the network verifier to custom verifier. All previously acquired network information that was received unverified will be purged. Affected network specs entries: {network_specs}; affected metadata entries: {metadata}."),
+    ("warning.verifier_general_super", "Received message is verified. Currently no verifier is set for network with genesis hash {genesis_hash} and no general verifier is set. Proceeding will update the network verifier to general. All previously acquired network information that was received unverified will be purged. Affected network specs entries: {network_specs}; affected metadata entries: {metadata}."),
+    ("warning.types_already_there", "Received types information is identical to the one that was in the database."),
+    ("warning.network_specs_already_there", "Received network specs information for {title} is same as the one already in the database."),
+    ("warning.metadata_extensions_incomplete", "Received metadata has incomplete set of signed extensions. As a result, Signer may be unable to parse signable transactions using this metadata."),
+    ("warning.contacts_not_verified", "Received contacts are not verified."),
+    ("warning.contact_lookalike", "Address {address} looks similar to the address of contact {contact_name}, but is different. Make sure the address is the intended one."),
+    ("warning.nonce_replay", "Transaction nonce {nonce} is not higher than nonce {last_nonce} of a transaction already signed with this key in this network. This could be a replay or a duplicate of an already signed transaction."),
+    ("warning.verifier_rotation", "Received message replaces the custom verifier of network {name} with genesis hash {genesis_hash}, and is endorsed by the current network verifier. Network specs and metadata are kept. After proceeding, updates for this network will be accepted only if signed by the new verifier."),
+    ("warning.security_policy", "Received message replaces Signer security policy, and is signed by the general verifier. After proceeding, following rules will be enforced: {rules}."),
+    ("warning.specs_amendment", "Received message amends network specs of {name}, and is signed by the current network verifier. Following fields will be changed: {changes}. Addresses and metadata of the network are kept."),
+    ("card.pallet", "pallet: {pallet}"),
+    ("card.method", "method: {method}"),
+    ("card.varname", "varname: {varname}"),
+    ("card.default", "default: {value}"),
+    ("card.text", "text: {text}"),
+    ("card.id", "Id: {address}"),
+    ("card.none", "none: "),
+    ("card.identity_field", "identity_field: {field}"),
+    ("card.bitvec", "bitvec: {bitvec}"),
+    ("card.balance", "balance: {number} {units}"),
+    ("card.field_name", "field_name: {name}"),
+    ("card.field_number", "field_number: {number}"),
+    ("card.enum_variant_name", "enum_variant_name: {name}"),
+    ("card.era_immortal", "era: Immortal"),
+    ("card.era_mortal", "era: Mortal, phase: {phase}, period: {period}"),
+    ("card.nonce", "nonce: {nonce}"),
+    ("card.block_hash", "block_hash: {block_hash}"),
+    ("card.tip", "tip: {number} {units}"),
+    ("card.network", "network: {name}{version}"),
+    ("card.tx_version", "tx_version: {tx_version}"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_with_fallback() {
+        let message = LocalisedMessage::new("warning.newer_version")
+            .with_number("used_version", 9010u32)
+            .with_number("latest_version", 9123u32);
+        assert_eq!(
+            message.show(),
+            "Transaction uses outdated runtime version 9010. Latest known available version is 9123."
+        );
+
+        let mut german = HashMap::new();
+        german.insert(
+            "warning.newer_version".to_string(),
+            "Transaktion verwendet veraltete Laufzeitversion {used_version}. Neueste bekannte Version ist {latest_version}.".to_string(),
+        );
+        german.insert("list.none".to_string(), "keine".to_string());
+        assert_eq!(
+            message.render(&german),
+            "Transaktion verwendet veraltete Laufzeitversion 9010. Neueste bekannte Version ist 9123."
+        );
+
+        let message = LocalisedMessage::new("warning.verifier_changing_to_custom")
+            .with_hex("genesis_hash", [1u8, 2])
+            .with_list("network_specs", vec!["Westend".to_string()])
+            .with_list("metadata", Vec::new());
+        assert_eq!(
+            message.render(&german),
+            "Received message is verified. Currently no verifier is set for network with genesis hash 0102. Proceeding will update the network verifier to custom verifier. All previously acquired network information that was received unverified will be purged. Affected network specs entries: Westend; affected metadata entries: keine."
+        );
+
+        assert_eq!(
+            LocalisedMessage::new("warning.unknown").show(),
+            "warning.unknown"
+        );
+        assert_eq!(
+            LocalisedMessage::new("card.pallet").show(),
+            "pallet: {pallet}"
+        );
+    }
+}
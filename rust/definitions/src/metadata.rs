@@ -323,6 +323,108 @@ pub fn runtime_metadata_from_slice(meta: &[u8]) -> Result<RuntimeMetadata> {
     Ok(RuntimeMetadata::decode(&mut &meta[4..]).map_err(|_| MetadataError::UnableToDecode)?)
 }
 
+/// Display rule for signed extension of the transaction with `V14` metadata
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ExtensionRule {
+    /// Transaction nonce
+    Nonce,
+
+    /// Tip, in the network main token
+    Tip,
+
+    /// Tip, in the asset selected by the extension `asset_id` field, or in the
+    /// network main token if no asset is selected
+    AssetTip,
+
+    /// Network metadata version, in additional signed data
+    SpecVersion,
+
+    /// Transaction version, in additional signed data
+    TxVersion,
+
+    /// Network genesis hash, in additional signed data
+    GenesisHash,
+
+    /// Block hash, in additional signed data of the mortality extension
+    BlockHash,
+
+    /// Metadata digest, in additional signed data, see
+    /// [`metadata_proof`](crate::metadata_proof)
+    MetadataHash,
+}
+
+/// Signed extension with known display rule
+pub struct KnownExtension {
+    /// Extension identifier, as it appears in the metadata
+    pub identifier: &'static str,
+
+    /// Display rule for the extension
+    pub rule: ExtensionRule,
+}
+
+/// Signed extensions known to the Signer
+///
+/// Extensions not on the list are decoded and displayed as generic types.
+///
+/// The list is fixed at compile time and is deliberately not pluggable. It
+/// decides how the signed data are shown to the user, and Signer receives
+/// nothing over the air gap that could be trusted to change it: a runtime
+/// registry loaded with updates would let the update author hide or relabel
+/// parts of the transaction being signed. Custom extension of some network
+/// is recognized by adding it here, with one of existing [`ExtensionRule`],
+/// in a Signer release.
+pub const KNOWN_EXTENSIONS: &[KnownExtension] = &[
+    KnownExtension {
+        identifier: "CheckSpecVersion",
+        rule: ExtensionRule::SpecVersion,
+    },
+    KnownExtension {
+        identifier: "CheckVersion",
+        rule: ExtensionRule::SpecVersion,
+    },
+    KnownExtension {
+        identifier: "CheckTxVersion",
+        rule: ExtensionRule::TxVersion,
+    },
+    KnownExtension {
+        identifier: "CheckGenesis",
+        rule: ExtensionRule::GenesisHash,
+    },
+    KnownExtension {
+        identifier: "CheckMortality",
+        rule: ExtensionRule::BlockHash,
+    },
+    KnownExtension {
+        identifier: "CheckEra",
+        rule: ExtensionRule::BlockHash,
+    },
+    KnownExtension {
+        identifier: "CheckNonce",
+        rule: ExtensionRule::Nonce,
+    },
+    KnownExtension {
+        identifier: "ChargeTransactionPayment",
+        rule: ExtensionRule::Tip,
+    },
+    KnownExtension {
+        identifier: "ChargeAssetTxPayment",
+        rule: ExtensionRule::AssetTip,
+    },
+    KnownExtension {
+        identifier: "CheckMetadataHash",
+        rule: ExtensionRule::MetadataHash,
+    },
+];
+
+/// Display rule for signed extension with given identifier, if the extension
+/// is known.
+pub fn extension_rule(identifier: &str) -> Option<ExtensionRule> {
+    KNOWN_EXTENSIONS
+        .iter()
+        .find(|x| x.identifier == identifier)
+        .map(|x| x.rule)
+}
+
 /// Checks if the `v14` metadata has all signed extensions required for transaction decoding.
 /// True if extensions are incomplete.
 ///
@@ -331,19 +433,22 @@ pub fn runtime_metadata_from_slice(meta: &[u8]) -> Result<RuntimeMetadata> {
 /// However, we can not simply forbid the loading of the metadata without required set of
 /// signed extensions into Signer.
 ///
+/// Extensions are matched by their [`ExtensionRule`], so that the extensions
+/// with legacy identifiers are also accepted.
+///
 /// This function should be used for warnings only on `generate_message` side and during metadata
 /// loading into Signer.
 fn need_v14_warning(metadata_v14: &RuntimeMetadataV14) -> bool {
-    let mut signed_extensions = HashMap::new();
+    let mut found_rules = HashMap::new();
     for x in metadata_v14.extrinsic.signed_extensions.iter() {
-        let count = signed_extensions
-            .entry(x.identifier.to_string())
-            .or_insert(0);
-        *count += 1;
-    }
-    !(signed_extensions.get("CheckSpecVersion") == Some(&1)
-        && signed_extensions.get("CheckGenesis") == Some(&1)
-        && signed_extensions.get("CheckMortality") == Some(&1)) // no warning needed if each one encountered, and only once
+        if let Some(rule) = extension_rule(&x.identifier) {
+            let count = found_rules.entry(rule).or_insert(0);
+            *count += 1;
+        }
+    }
+    !(found_rules.get(&ExtensionRule::SpecVersion) == Some(&1)
+        && found_rules.get(&ExtensionRule::GenesisHash) == Some(&1)
+        && found_rules.get(&ExtensionRule::BlockHash) == Some(&1)) // no warning needed if each one encountered, and only once
 }
 
 /// Metadata as checked [`RuntimeMetadata`](https://docs.rs/frame-metadata/15.0.0/frame_metadata/enum.RuntimeMetadata.html)
@@ -785,6 +890,34 @@ mod tests {
         )
     }
 
+    #[test]
+    fn legacy_extension_identifiers() {
+        let filename = String::from("for_tests/westend9150");
+        let meta = read_to_string(&filename).unwrap();
+        let meta_vec = hex::decode(meta.trim()).unwrap();
+        let mut metadata_v14 = match runtime_metadata_from_slice(&meta_vec).unwrap() {
+            RuntimeMetadata::V14(a) => a,
+            _ => panic!("Expected V14 metadata in westend9150."),
+        };
+        for x in metadata_v14.extrinsic.signed_extensions.iter_mut() {
+            if x.identifier == "CheckMortality" {
+                x.identifier = String::from("CheckEra");
+            }
+        }
+        assert!(
+            !need_v14_warning(&metadata_v14),
+            "Expected legacy `CheckEra` extension to be accepted."
+        );
+        metadata_v14
+            .extrinsic
+            .signed_extensions
+            .retain(|x| x.identifier != "CheckEra");
+        assert!(
+            need_v14_warning(&metadata_v14),
+            "Expected incomplete extensions warning without mortality extension."
+        )
+    }
+
     #[test]
     fn unwasm_westend9150() {
         let filename = "for_tests/westend_runtime-v9150.compact.compressed.wasm";
@@ -0,0 +1,1244 @@
+//! Partial metadata proofs
+//!
+//! Transaction parsing normally requires full network metadata for the
+//! network version in transaction extensions, loaded into Signer beforehand
+//! through `load_metadata` update.
+//!
+//! Alternatively, transaction could carry [`MetadataProof`] with only the
+//! types needed to decode this particular transaction. Proof types are
+//! checked by restoring from the proof the metadata digest of
+//! [RFC-0078](https://polkadot-fellows.github.io/RFCs/approved/0078-merkleized-metadata.html),
+//! and comparing it with the metadata hash that the transaction commits to
+//! in `CheckMetadataHash` extension. Runtimes with this extension check the
+//! same digest, and reject transactions signed with any other metadata.
+//!
+//! Metadata digest is built as follows:
+//!
+//! - types reachable from the extrinsic address, call and signature types,
+//! and from the signed extensions, are converted into [`ProofType`]:
+//! primitives, compacts and types with no data are inlined as [`TypeRef`],
+//! each enum variant is a separate type, and types get new ids following
+//! the order of their ids in metadata
+//! - each [`ProofType`] is a types tree leaf, leaf hash is `blake3` hash of
+//! the SCALE-encoded type, leaves are ordered by type id and variant index
+//! - types tree is a complete binary tree: node `i` has children `2i + 1` and
+//! `2i + 2`, node hash is `blake3` hash of concatenated child hashes, and
+//! leaves are the last nodes of the tree
+//! - digest is `blake3` hash of SCALE-encoded `MetadataDigest::V1` with types
+//! tree root, `blake3` hash of SCALE-encoded [`ExtrinsicInfo`], and
+//! [`ExtraInfo`]
+//!
+//! The digest construction is pinned by a known-answer test with the digest
+//! of full `westend9150` metadata, calculated with the reference
+//! implementation `merkleized-metadata`.
+//!
+//! Proof has the leaves needed for decoding, their tree node indices, and
+//! the hashes of the tree nodes needed to restore the types tree root, in
+//! depth-first order. All types absent from the proof are replaced in the
+//! restored metadata with placeholder enums with no variants and with path
+//! [`OMITTED_TYPE_PATH`], decoding of such type always fails.
+//!
+//! Transaction with metadata proof has prelude `53xx06`. Position of the
+//! metadata hash in transaction extensions is not taken from the proof, see
+//! `transaction_parsing` crate.
+use frame_metadata::v14::{
+    ExtrinsicMetadata, PalletCallMetadata, PalletMetadata, RuntimeMetadataV14,
+    SignedExtensionMetadata,
+};
+use parity_scale_codec::{Compact, Decode, Encode};
+use scale_info::{form::PortableForm, PortableRegistry, Type, TypeDef, TypeDefPrimitive};
+use sp_core::H256;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::error::{MetadataProofError, Result};
+
+/// Path of the placeholder types in metadata restored from the proof
+pub const OMITTED_TYPE_PATH: [&str; 2] = ["metadata_proof", "OmittedType"];
+
+/// Maximum number of types in metadata restored from the proof
+pub const TYPE_COUNT_LIMIT: u32 = 65536;
+
+/// Variant index of `MetadataDigest::V1` in RFC-0078
+pub const DIGEST_VERSION: u8 = 1;
+
+/// Reference to a type
+///
+/// Primitives, compacts and types with no data are inlined, all other types
+/// are referred to by id.
+#[derive(Clone, Copy, Debug, Decode, Encode, Eq, Ord, PartialEq, PartialOrd)]
+pub enum TypeRef {
+    Bool,
+    Char,
+    Str,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    I256,
+    CompactU8,
+    CompactU16,
+    CompactU32,
+    CompactU64,
+    CompactU128,
+    CompactU256,
+
+    /// Type with no data: empty struct, empty tuple, or enum with no variants
+    Void,
+
+    /// Type with given id
+    ById(Compact<u32>),
+}
+
+impl TypeRef {
+    /// Id of the referred type, if the type is not inlined.
+    pub fn id(&self) -> Option<u32> {
+        match self {
+            TypeRef::ById(id) => Some(id.0),
+            _ => None,
+        }
+    }
+}
+
+/// Struct field or enum variant field
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+pub struct ProofField {
+    /// Field name, if the field is named
+    pub name: Option<String>,
+
+    /// Field type
+    pub ty: TypeRef,
+
+    /// Field type name, as it appears in the source code
+    pub type_name: Option<String>,
+}
+
+/// Enum variant
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+pub struct ProofVariant {
+    /// Variant name
+    pub name: String,
+
+    /// Variant fields
+    pub fields: Vec<ProofField>,
+
+    /// Variant index, first byte of the encoded enum
+    pub index: Compact<u32>,
+}
+
+/// Array with fixed length
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+pub struct ProofArray {
+    /// Number of array elements
+    pub len: u32,
+
+    /// Array element type
+    pub type_param: TypeRef,
+}
+
+/// Bit sequence
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+pub struct ProofBitSequence {
+    /// Number of bytes in bit store element
+    pub num_bytes: u8,
+
+    /// Bit order is `Lsb0`
+    pub least_significant_bit_first: bool,
+}
+
+/// Type definition
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+pub enum ProofTypeDef {
+    Composite(Vec<ProofField>),
+
+    /// Single variant of an enum, each variant is a separate tree leaf
+    Enumeration(ProofVariant),
+    Sequence(TypeRef),
+    Array(ProofArray),
+    Tuple(Vec<TypeRef>),
+    BitSequence(ProofBitSequence),
+}
+
+impl ProofTypeDef {
+    /// References to other types.
+    fn type_refs(&self) -> Vec<TypeRef> {
+        match self {
+            ProofTypeDef::Composite(fields)
+            | ProofTypeDef::Enumeration(ProofVariant { fields, .. }) => {
+                fields.iter().map(|field| field.ty).collect()
+            }
+            ProofTypeDef::Sequence(type_param)
+            | ProofTypeDef::Array(ProofArray { type_param, .. }) => vec![*type_param],
+            ProofTypeDef::Tuple(fields) => fields.to_owned(),
+            ProofTypeDef::BitSequence(_) => Vec::new(),
+        }
+    }
+}
+
+/// Type, a leaf of the types tree
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+pub struct ProofType {
+    /// Type path, empty for built-in types
+    pub path: Vec<String>,
+
+    /// Type definition
+    pub type_def: ProofTypeDef,
+
+    /// Type id
+    pub type_id: Compact<u32>,
+}
+
+impl ProofType {
+    /// Hash of the types tree leaf.
+    pub fn hash(&self) -> H256 {
+        blake3_hash(&self.encode())
+    }
+}
+
+/// Signed extension information
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+pub struct SignedExtensionInfo {
+    /// Extension identifier
+    pub identifier: String,
+
+    /// Type of the extension data
+    pub included_in_extrinsic: TypeRef,
+
+    /// Type of the extension additional signed data
+    pub included_in_signed_data: TypeRef,
+}
+
+/// Extrinsic information, committed in metadata digest in full
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+pub struct ExtrinsicInfo {
+    /// Extrinsic version
+    pub version: u8,
+
+    /// Type of the transaction author address
+    pub address_ty: TypeRef,
+
+    /// Type of the call
+    pub call_ty: TypeRef,
+
+    /// Type of the transaction signature
+    pub signature_ty: TypeRef,
+
+    /// Signed extensions, in order of appearance in the transaction
+    pub signed_extensions: Vec<SignedExtensionInfo>,
+}
+
+impl ExtrinsicInfo {
+    /// References to other types.
+    fn type_refs(&self) -> Vec<TypeRef> {
+        let mut type_refs = vec![self.address_ty, self.call_ty, self.signature_ty];
+        for x in self.signed_extensions.iter() {
+            type_refs.push(x.included_in_extrinsic);
+            type_refs.push(x.included_in_signed_data);
+        }
+        type_refs
+    }
+}
+
+/// Network information committed in metadata digest
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq)]
+pub struct ExtraInfo {
+    /// Network metadata version
+    pub spec_version: u32,
+
+    /// Network name, as it appears in the metadata
+    pub spec_name: String,
+
+    /// Network-specific prefix for address representation
+    pub base58prefix: u16,
+
+    /// Order of magnitude, by which the token unit exceeds the balance integer
+    /// unit
+    pub decimals: u8,
+
+    /// Main token unit
+    pub unit: String,
+}
+
+/// Partial metadata proof
+#[derive(Clone, Debug, Decode, Encode, PartialEq)]
+pub struct MetadataProof {
+    /// Types tree leaves needed for decoding, in depth-first order
+    pub leaves: Vec<ProofType>,
+
+    /// Tree node indices of the leaves
+    pub leaf_indices: Vec<u32>,
+
+    /// Hashes of tree nodes needed to restore the types tree root, in
+    /// depth-first order
+    pub nodes: Vec<H256>,
+
+    /// Extrinsic information
+    pub extrinsic: ExtrinsicInfo,
+
+    /// Network information
+    pub extra: ExtraInfo,
+}
+
+impl MetadataProof {
+    /// Generate proof with types needed to decode call with given pallet and
+    /// call index.
+    ///
+    /// Other calls of the pallet are not decodable with the proof.
+    pub fn for_call(
+        meta_v14: &RuntimeMetadataV14,
+        extra: ExtraInfo,
+        pallet_index: u8,
+        call_index: u8,
+    ) -> Result<Self> {
+        let type_information = TypeInformation::from_metadata(meta_v14)?;
+        let (pallet_leaf, pallet_variant) = type_information
+            .variant(type_information.extrinsic.call_ty, pallet_index)
+            .ok_or(MetadataProofError::NoCallsInPallet(pallet_index))?;
+        let calls_ty = pallet_variant
+            .fields
+            .first()
+            .map(|field| field.ty)
+            .ok_or(MetadataProofError::NoCallsInPallet(pallet_index))?;
+        let (call_leaf, call_variant) = type_information.variant(calls_ty, call_index).ok_or(
+            MetadataProofError::CallNotFound {
+                pallet_index,
+                call_index,
+            },
+        )?;
+
+        // call enums are needed only for the variants of this call, all
+        // types of the call fields are needed in full
+        let mut leaves = BTreeSet::from([pallet_leaf, call_leaf]);
+        type_information.collect_leaves(
+            call_variant.fields.iter().map(|field| field.ty).collect(),
+            &mut leaves,
+        );
+        Ok(type_information.proof(&leaves, extra))
+    }
+
+    /// Restore metadata digest from the proof.
+    pub fn digest(&self) -> Result<H256> {
+        self.check_leaves()?;
+        let mut leaves = &self.leaves[..];
+        let mut leaf_indices = &self.leaf_indices[..];
+        let mut nodes = &self.nodes[..];
+        let root = restore_node(0, &mut leaves, &mut leaf_indices, &mut nodes)?;
+        if !leaves.is_empty() {
+            return Err(MetadataProofError::LeavesUnused.into());
+        }
+        if !nodes.is_empty() {
+            return Err(MetadataProofError::NodesUnused.into());
+        }
+        Ok(digest_from_root(&root, &self.extrinsic, &self.extra))
+    }
+
+    /// Restore metadata from the proof.
+    ///
+    /// Types absent from the proof are replaced with placeholders. Types
+    /// inlined in the proof get new ids after all proof type ids. Pallets are
+    /// restored from the call enum variants in the proof. Extrinsic type and
+    /// runtime type are not in the proof, and are set to empty tuple.
+    pub fn runtime_metadata(&self) -> Result<RuntimeMetadataV14> {
+        self.check_leaves()?;
+        let mut proof_types: BTreeMap<u32, Vec<&ProofType>> = BTreeMap::new();
+        let mut type_refs = self.extrinsic.type_refs();
+        for leaf in self.leaves.iter() {
+            proof_types.entry(leaf.type_id.0).or_default().push(leaf);
+            type_refs.push(TypeRef::ById(leaf.type_id));
+            type_refs.extend(leaf.type_def.type_refs());
+        }
+        let type_count = type_refs
+            .iter()
+            .filter_map(|x| x.id())
+            .map(|id| id.saturating_add(1))
+            .max()
+            .unwrap_or_default();
+        if type_count > TYPE_COUNT_LIMIT {
+            return Err(MetadataProofError::TooManyTypes {
+                type_count,
+                limit: TYPE_COUNT_LIMIT,
+            }
+            .into());
+        }
+
+        let mut registry = RegistryBuilder::new(type_count);
+        let mut all_types = Vec::new();
+        for id in 0..type_count {
+            let encoded_type = match proof_types.get(&id) {
+                Some(leaves) => registry.proof_type(id, leaves)?,
+                None => registry_type(
+                    id,
+                    omitted_type_path(),
+                    RegistryTypeDef::Variant(Vec::new()),
+                ),
+            };
+            all_types.push(encoded_type);
+        }
+        let pallets = match self
+            .extrinsic
+            .call_ty
+            .id()
+            .and_then(|id| proof_types.get(&id))
+        {
+            Some(leaves) => restore_pallets(leaves)?,
+            None => Vec::new(),
+        };
+        let signed_extensions = self
+            .extrinsic
+            .signed_extensions
+            .iter()
+            .map(|x| SignedExtensionMetadata {
+                identifier: x.identifier.to_string(),
+                ty: registry.type_ref(x.included_in_extrinsic).into(),
+                additional_signed: registry.type_ref(x.included_in_signed_data).into(),
+            })
+            .collect();
+        let void = registry.type_ref(TypeRef::Void);
+        all_types.extend(registry.synthetic);
+
+        let encoded_registry =
+            [Compact(all_types.len() as u32).encode(), all_types.concat()].concat();
+        let types = PortableRegistry::decode(&mut &encoded_registry[..])?;
+        Ok(RuntimeMetadataV14 {
+            types,
+            pallets,
+            extrinsic: ExtrinsicMetadata {
+                ty: void.into(),
+                version: self.extrinsic.version,
+                signed_extensions,
+            },
+            ty: void.into(),
+        })
+    }
+
+    /// Check that the proof has leaves, with a tree node index for each leaf.
+    fn check_leaves(&self) -> Result<()> {
+        if self.leaves.is_empty() {
+            return Err(MetadataProofError::NoTypes.into());
+        }
+        if self.leaves.len() != self.leaf_indices.len() {
+            return Err(MetadataProofError::LeafIndicesMismatch {
+                leaves: self.leaves.len() as u32,
+                leaf_indices: self.leaf_indices.len() as u32,
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Metadata digest of full metadata.
+pub fn metadata_digest(meta_v14: &RuntimeMetadataV14, extra: ExtraInfo) -> Result<H256> {
+    let type_information = TypeInformation::from_metadata(meta_v14)?;
+    let root = tree_nodes(&type_information.leaves)[0];
+    Ok(digest_from_root(&root, &type_information.extrinsic, &extra))
+}
+
+/// Check if the type is a placeholder for type omitted in metadata proof.
+pub fn is_omitted_type(ty: &Type<PortableForm>) -> bool {
+    ty.path().segments() == OMITTED_TYPE_PATH
+}
+
+/// All types of full metadata, converted for the types tree.
+struct TypeInformation {
+    /// Extrinsic information
+    extrinsic: ExtrinsicInfo,
+
+    /// Types tree leaves, in order
+    leaves: Vec<ProofType>,
+}
+
+impl TypeInformation {
+    fn from_metadata(meta_v14: &RuntimeMetadataV14) -> Result<Self> {
+        // metadata `V14` has extrinsic address, call and signature types only
+        // as type parameters of the extrinsic type
+        let extrinsic_ty = resolve(meta_v14, meta_v14.extrinsic.ty.id())?;
+        let type_param = |name: &str| {
+            extrinsic_ty
+                .type_params()
+                .iter()
+                .find(|x| x.name() == name)
+                .and_then(|x| x.ty())
+                .map(|x| x.id())
+                .ok_or_else(|| MetadataProofError::NoExtrinsicTypeParam(name.to_string()))
+        };
+        let address_ty = type_param("Address")?;
+        let call_ty = type_param("Call")?;
+        let signature_ty = type_param("Signature")?;
+
+        let mut roots = vec![address_ty, call_ty, signature_ty];
+        for x in meta_v14.extrinsic.signed_extensions.iter() {
+            roots.push(x.ty.id());
+            roots.push(x.additional_signed.id());
+        }
+        let mut ids = BTreeSet::new();
+        collect_types(meta_v14, roots, &mut ids)?;
+        let mut new_ids = BTreeMap::new();
+        for id in ids.into_iter() {
+            if !is_inlined(resolve(meta_v14, id)?) {
+                new_ids.insert(id, new_ids.len() as u32);
+            }
+        }
+
+        let extrinsic = ExtrinsicInfo {
+            version: meta_v14.extrinsic.version,
+            address_ty: type_ref(meta_v14, &new_ids, address_ty)?,
+            call_ty: type_ref(meta_v14, &new_ids, call_ty)?,
+            signature_ty: type_ref(meta_v14, &new_ids, signature_ty)?,
+            signed_extensions: meta_v14
+                .extrinsic
+                .signed_extensions
+                .iter()
+                .map(|x| {
+                    Ok(SignedExtensionInfo {
+                        identifier: x.identifier.to_string(),
+                        included_in_extrinsic: type_ref(meta_v14, &new_ids, x.ty.id())?,
+                        included_in_signed_data: type_ref(
+                            meta_v14,
+                            &new_ids,
+                            x.additional_signed.id(),
+                        )?,
+                    })
+                })
+                .collect::<Result<Vec<SignedExtensionInfo>>>()?,
+        };
+        let mut leaves = Vec::new();
+        for (id, new_id) in new_ids.iter() {
+            leaves.extend(proof_types(meta_v14, &new_ids, *id, *new_id)?);
+        }
+        Ok(Self { extrinsic, leaves })
+    }
+
+    /// Leaf of the enum variant with given index.
+    fn variant(&self, ty: TypeRef, index: u8) -> Option<(usize, &ProofVariant)> {
+        let id = ty.id()?;
+        self.leaves
+            .iter()
+            .enumerate()
+            .find_map(|(i, leaf)| match leaf.type_def {
+                ProofTypeDef::Enumeration(ref variant)
+                    if leaf.type_id.0 == id && variant.index.0 == index as u32 =>
+                {
+                    Some((i, variant))
+                }
+                _ => None,
+            })
+    }
+
+    /// Collect leaves of given types and all types they refer to.
+    fn collect_leaves(&self, mut roots: Vec<TypeRef>, leaves: &mut BTreeSet<usize>) {
+        let mut ids = BTreeSet::new();
+        while let Some(type_ref) = roots.pop() {
+            let id = match type_ref.id() {
+                Some(id) => id,
+                None => continue,
+            };
+            if !ids.insert(id) {
+                continue;
+            }
+            for (i, leaf) in self.leaves.iter().enumerate() {
+                if leaf.type_id.0 == id {
+                    leaves.insert(i);
+                    roots.extend(leaf.type_def.type_refs());
+                }
+            }
+        }
+    }
+
+    /// Generate proof with given leaves.
+    fn proof(&self, leaves: &BTreeSet<usize>, extra: ExtraInfo) -> MetadataProof {
+        let nodes = tree_nodes(&self.leaves);
+        let first_leaf = self.leaves.len() - 1;
+        let mut leaf_indices: Vec<u32> = leaves.iter().map(|i| (first_leaf + i) as u32).collect();
+
+        // in complete binary tree the leaves on the lowest level are the
+        // leftmost ones
+        leaf_indices.sort_by_key(|i| (std::cmp::Reverse(level(u64::from(*i))), *i));
+        let mut proof_nodes = Vec::new();
+        collect_proof_nodes(&nodes, 0, &leaf_indices, &mut proof_nodes);
+        MetadataProof {
+            leaves: leaf_indices
+                .iter()
+                .map(|i| self.leaves[*i as usize - first_leaf].to_owned())
+                .collect(),
+            leaf_indices,
+            nodes: proof_nodes,
+            extrinsic: self.extrinsic.to_owned(),
+            extra,
+        }
+    }
+}
+
+fn resolve(meta_v14: &RuntimeMetadataV14, id: u32) -> Result<&Type<PortableForm>> {
+    meta_v14
+        .types
+        .resolve(id)
+        .ok_or_else(|| MetadataProofError::TypeNotResolved(id).into())
+}
+
+/// Check if the type is inlined as [`TypeRef`] and is not a tree leaf.
+fn is_inlined(ty: &Type<PortableForm>) -> bool {
+    match ty.type_def() {
+        TypeDef::Primitive(_) | TypeDef::Compact(_) => true,
+        TypeDef::Composite(x) => x.fields().is_empty(),
+        TypeDef::Variant(x) => x.variants().is_empty(),
+        TypeDef::Tuple(x) => x.fields().is_empty(),
+        _ => false,
+    }
+}
+
+/// Collect ids of given types and all types they refer to.
+///
+/// Types inside compacts and bit sequences are not collected, as they are
+/// inlined.
+fn collect_types(
+    meta_v14: &RuntimeMetadataV14,
+    mut roots: Vec<u32>,
+    ids: &mut BTreeSet<u32>,
+) -> Result<()> {
+    while let Some(id) = roots.pop() {
+        if !ids.insert(id) {
+            continue;
+        }
+        match resolve(meta_v14, id)?.type_def() {
+            TypeDef::Composite(x) => roots.extend(x.fields().iter().map(|f| f.ty().id())),
+            TypeDef::Variant(x) => roots.extend(
+                x.variants()
+                    .iter()
+                    .flat_map(|v| v.fields().iter().map(|f| f.ty().id())),
+            ),
+            TypeDef::Sequence(x) => roots.push(x.type_param().id()),
+            TypeDef::Array(x) => roots.push(x.type_param().id()),
+            TypeDef::Tuple(x) => roots.extend(x.fields().iter().map(|f| f.id())),
+            TypeDef::Primitive(_) | TypeDef::Compact(_) | TypeDef::BitSequence(_) => (),
+        }
+    }
+    Ok(())
+}
+
+/// Collect primitives found in the type with given id and in all types it
+/// refers to.
+fn collect_primitives(
+    meta_v14: &RuntimeMetadataV14,
+    id: u32,
+    visited: &mut BTreeSet<u32>,
+    found: &mut Vec<TypeDefPrimitive>,
+) -> Result<()> {
+    if !visited.insert(id) {
+        return Ok(());
+    }
+    let inner: Vec<u32> = match resolve(meta_v14, id)?.type_def() {
+        TypeDef::Composite(x) => x.fields().iter().map(|f| f.ty().id()).collect(),
+        TypeDef::Variant(x) => x
+            .variants()
+            .iter()
+            .flat_map(|v| v.fields().iter().map(|f| f.ty().id()))
+            .collect(),
+        TypeDef::Sequence(x) => vec![x.type_param().id()],
+        TypeDef::Array(x) => vec![x.type_param().id()],
+        TypeDef::Tuple(x) => x.fields().iter().map(|f| f.id()).collect(),
+        TypeDef::Primitive(x) => {
+            found.push(x.to_owned());
+            Vec::new()
+        }
+        TypeDef::Compact(x) => vec![x.type_param().id()],
+        TypeDef::BitSequence(x) => vec![x.bit_order_type().id(), x.bit_store_type().id()],
+    };
+    for inner_id in inner.into_iter() {
+        collect_primitives(meta_v14, inner_id, visited, found)?;
+    }
+    Ok(())
+}
+
+/// Reference to the type with given id.
+fn type_ref(
+    meta_v14: &RuntimeMetadataV14,
+    new_ids: &BTreeMap<u32, u32>,
+    id: u32,
+) -> Result<TypeRef> {
+    let ty = resolve(meta_v14, id)?;
+    let type_ref = match ty.type_def() {
+        TypeDef::Primitive(x) => match x {
+            TypeDefPrimitive::Bool => TypeRef::Bool,
+            TypeDefPrimitive::Char => TypeRef::Char,
+            TypeDefPrimitive::Str => TypeRef::Str,
+            TypeDefPrimitive::U8 => TypeRef::U8,
+            TypeDefPrimitive::U16 => TypeRef::U16,
+            TypeDefPrimitive::U32 => TypeRef::U32,
+            TypeDefPrimitive::U64 => TypeRef::U64,
+            TypeDefPrimitive::U128 => TypeRef::U128,
+            TypeDefPrimitive::U256 => TypeRef::U256,
+            TypeDefPrimitive::I8 => TypeRef::I8,
+            TypeDefPrimitive::I16 => TypeRef::I16,
+            TypeDefPrimitive::I32 => TypeRef::I32,
+            TypeDefPrimitive::I64 => TypeRef::I64,
+            TypeDefPrimitive::I128 => TypeRef::I128,
+            TypeDefPrimitive::I256 => TypeRef::I256,
+        },
+        TypeDef::Compact(x) => {
+            // compact of a wrapper struct is a compact of the wrapped
+            // primitive
+            let mut found = Vec::new();
+            collect_primitives(
+                meta_v14,
+                x.type_param().id(),
+                &mut BTreeSet::new(),
+                &mut found,
+            )?;
+            match found[..] {
+                [] => TypeRef::Void,
+                [TypeDefPrimitive::U8] => TypeRef::CompactU8,
+                [TypeDefPrimitive::U16] => TypeRef::CompactU16,
+                [TypeDefPrimitive::U32] => TypeRef::CompactU32,
+                [TypeDefPrimitive::U64] => TypeRef::CompactU64,
+                [TypeDefPrimitive::U128] => TypeRef::CompactU128,
+                _ => return Err(MetadataProofError::UnsupportedCompact(id).into()),
+            }
+        }
+        _ if is_inlined(ty) => TypeRef::Void,
+        _ => match new_ids.get(&id) {
+            Some(new_id) => TypeRef::ById(Compact(*new_id)),
+            None => return Err(MetadataProofError::TypeNotResolved(id).into()),
+        },
+    };
+    Ok(type_ref)
+}
+
+fn proof_fields(
+    meta_v14: &RuntimeMetadataV14,
+    new_ids: &BTreeMap<u32, u32>,
+    fields: &[scale_info::Field<PortableForm>],
+) -> Result<Vec<ProofField>> {
+    fields
+        .iter()
+        .map(|field| {
+            Ok(ProofField {
+                name: field.name().map(|x| x.to_string()),
+                ty: type_ref(meta_v14, new_ids, field.ty().id())?,
+                type_name: field.type_name().map(|x| x.to_string()),
+            })
+        })
+        .collect()
+}
+
+/// Tree leaves for the type with given id, one leaf for each enum variant.
+fn proof_types(
+    meta_v14: &RuntimeMetadataV14,
+    new_ids: &BTreeMap<u32, u32>,
+    id: u32,
+    new_id: u32,
+) -> Result<Vec<ProofType>> {
+    let ty = resolve(meta_v14, id)?;
+    let path = ty.path().segments().to_vec();
+    let type_id = Compact(new_id);
+    let type_def = match ty.type_def() {
+        TypeDef::Composite(x) => {
+            ProofTypeDef::Composite(proof_fields(meta_v14, new_ids, x.fields())?)
+        }
+        TypeDef::Variant(x) => {
+            let mut variants: Vec<&scale_info::Variant<PortableForm>> =
+                x.variants().iter().collect();
+            variants.sort_by_key(|variant| variant.index());
+            return variants
+                .into_iter()
+                .map(|variant| {
+                    Ok(ProofType {
+                        path: path.to_owned(),
+                        type_def: ProofTypeDef::Enumeration(ProofVariant {
+                            name: variant.name().to_string(),
+                            fields: proof_fields(meta_v14, new_ids, variant.fields())?,
+                            index: Compact(variant.index() as u32),
+                        }),
+                        type_id,
+                    })
+                })
+                .collect();
+        }
+        TypeDef::Sequence(x) => {
+            ProofTypeDef::Sequence(type_ref(meta_v14, new_ids, x.type_param().id())?)
+        }
+        TypeDef::Array(x) => ProofTypeDef::Array(ProofArray {
+            len: x.len(),
+            type_param: type_ref(meta_v14, new_ids, x.type_param().id())?,
+        }),
+        TypeDef::Tuple(x) => ProofTypeDef::Tuple(
+            x.fields()
+                .iter()
+                .map(|f| type_ref(meta_v14, new_ids, f.id()))
+                .collect::<Result<Vec<TypeRef>>>()?,
+        ),
+        TypeDef::BitSequence(x) => {
+            let mut found = Vec::new();
+            collect_primitives(
+                meta_v14,
+                x.bit_store_type().id(),
+                &mut BTreeSet::new(),
+                &mut found,
+            )?;
+            let num_bytes = match found[..] {
+                [TypeDefPrimitive::U8] => 1,
+                [TypeDefPrimitive::U16] => 2,
+                [TypeDefPrimitive::U32] => 4,
+                [TypeDefPrimitive::U64] => 8,
+                _ => return Err(MetadataProofError::UnsupportedBitSequence(id).into()),
+            };
+            let bit_order = resolve(meta_v14, x.bit_order_type().id())?
+                .path()
+                .segments()
+                .iter()
+                .find(|x| *x == "Lsb0" || *x == "Msb0")
+                .ok_or(MetadataProofError::UnsupportedBitSequence(id))?;
+            ProofTypeDef::BitSequence(ProofBitSequence {
+                num_bytes,
+                least_significant_bit_first: bit_order == "Lsb0",
+            })
+        }
+        TypeDef::Primitive(_) | TypeDef::Compact(_) => return Ok(Vec::new()),
+    };
+    Ok(vec![ProofType {
+        path,
+        type_def,
+        type_id,
+    }])
+}
+
+fn blake3_hash(data: &[u8]) -> H256 {
+    H256(blake3::hash(data).into())
+}
+
+fn node_hash(left: &H256, right: &H256) -> H256 {
+    blake3_hash(&[left.as_bytes(), right.as_bytes()].concat())
+}
+
+fn digest_from_root(root: &H256, extrinsic: &ExtrinsicInfo, extra: &ExtraInfo) -> H256 {
+    let extrinsic_hash = blake3_hash(&extrinsic.encode());
+    blake3_hash(&(DIGEST_VERSION, root, extrinsic_hash, extra).encode())
+}
+
+/// Hashes of all types tree nodes, node `i` has children `2i + 1` and
+/// `2i + 2`.
+///
+/// Tree with no leaves has zero root hash.
+fn tree_nodes(leaves: &[ProofType]) -> Vec<H256> {
+    if leaves.is_empty() {
+        return vec![H256::zero()];
+    }
+    let first_leaf = leaves.len() - 1;
+    let mut nodes = vec![H256::zero(); first_leaf];
+    nodes.extend(leaves.iter().map(|leaf| leaf.hash()));
+    for i in (0..first_leaf).rev() {
+        nodes[i] = node_hash(&nodes[2 * i + 1], &nodes[2 * i + 2]);
+    }
+    nodes
+}
+
+/// Level of the tree node, root is on level 0.
+fn level(node: u64) -> u32 {
+    63 - (node + 1).leading_zeros()
+}
+
+/// Check if the tree node `other` is in the subtree of the tree node `node`.
+fn is_in_subtree(node: u64, other: u64) -> bool {
+    if node > other {
+        return false;
+    }
+    (node + 1) == (other + 1) >> (level(other) - level(node))
+}
+
+/// Collect hashes of the tree nodes not calculable from the leaves with given
+/// tree node indices, in depth-first order.
+fn collect_proof_nodes(
+    nodes: &[H256],
+    node: u64,
+    leaf_indices: &[u32],
+    proof_nodes: &mut Vec<H256>,
+) {
+    if leaf_indices.iter().any(|i| u64::from(*i) == node) {
+        return;
+    }
+    if leaf_indices
+        .iter()
+        .any(|i| is_in_subtree(node, u64::from(*i)))
+    {
+        collect_proof_nodes(nodes, 2 * node + 1, leaf_indices, proof_nodes);
+        collect_proof_nodes(nodes, 2 * node + 2, leaf_indices, proof_nodes);
+    } else {
+        proof_nodes.push(nodes[node as usize]);
+    }
+}
+
+/// Restore hash of the tree node, using proof leaves and nodes in
+/// depth-first order.
+fn restore_node(
+    node: u64,
+    leaves: &mut &[ProofType],
+    leaf_indices: &mut &[u32],
+    nodes: &mut &[H256],
+) -> Result<H256> {
+    if let (Some(leaf), Some(leaf_index)) = (leaves.first(), leaf_indices.first()) {
+        let leaf_index = u64::from(*leaf_index);
+        if leaf_index == node {
+            *leaves = &leaves[1..];
+            *leaf_indices = &leaf_indices[1..];
+            return Ok(leaf.hash());
+        }
+        if is_in_subtree(node, leaf_index) {
+            let left = restore_node(2 * node + 1, leaves, leaf_indices, nodes)?;
+            let right = restore_node(2 * node + 2, leaves, leaf_indices, nodes)?;
+            return Ok(node_hash(&left, &right));
+        }
+    }
+    let (hash, remaining) = nodes
+        .split_first()
+        .ok_or(MetadataProofError::NodesMissing)?;
+    *nodes = remaining;
+    Ok(*hash)
+}
+
+/// Pallets with calls, restored from the call enum variants.
+fn restore_pallets(call_leaves: &[&ProofType]) -> Result<Vec<PalletMetadata<PortableForm>>> {
+    let mut pallets = Vec::new();
+    for leaf in call_leaves.iter() {
+        if let ProofTypeDef::Enumeration(ref variant) = leaf.type_def {
+            pallets.push(PalletMetadata {
+                name: variant.name.to_string(),
+                storage: None,
+                calls: variant
+                    .fields
+                    .first()
+                    .and_then(|field| field.ty.id())
+                    .map(|ty| PalletCallMetadata { ty: ty.into() }),
+                event: None,
+                constants: Vec::new(),
+                error: None,
+                index: variant_index(variant)?,
+            });
+        }
+    }
+    pallets.sort_by_key(|pallet| pallet.index);
+    Ok(pallets)
+}
+
+fn variant_index(variant: &ProofVariant) -> Result<u8> {
+    u8::try_from(variant.index.0)
+        .map_err(|_| MetadataProofError::VariantIndexOutOfRange(variant.index.0).into())
+}
+
+fn omitted_type_path() -> Vec<String> {
+    OMITTED_TYPE_PATH.iter().map(|x| x.to_string()).collect()
+}
+
+/// Type definition, encoded same way as `TypeDef` in types registry.
+#[derive(Encode)]
+enum RegistryTypeDef {
+    Composite(Vec<RegistryField>),
+    Variant(Vec<RegistryVariant>),
+    Sequence(Compact<u32>),
+    Array(u32, Compact<u32>),
+    Tuple(Vec<Compact<u32>>),
+    Primitive(u8),
+    Compact(Compact<u32>),
+    BitSequence(Compact<u32>, Compact<u32>),
+}
+
+/// Field, encoded same way as `Field` in types registry.
+#[derive(Encode)]
+struct RegistryField {
+    name: Option<String>,
+    ty: Compact<u32>,
+    type_name: Option<String>,
+    docs: Vec<String>,
+}
+
+/// Enum variant, encoded same way as `Variant` in types registry.
+#[derive(Encode)]
+struct RegistryVariant {
+    name: String,
+    fields: Vec<RegistryField>,
+    index: u8,
+    docs: Vec<String>,
+}
+
+/// Encoded type of the types registry, with no type parameters and no docs.
+fn registry_type(id: u32, path: Vec<String>, type_def: RegistryTypeDef) -> Vec<u8> {
+    let no_type_params: Vec<u8> = Vec::new();
+    let no_docs: Vec<String> = Vec::new();
+    (Compact(id), path, no_type_params, type_def, no_docs).encode()
+}
+
+/// Builder for types of the registry restored from the proof that are not
+/// proof types: inlined types and bit orders.
+struct RegistryBuilder {
+    /// Id of the next added type
+    next_id: u32,
+
+    /// Added types, encoded
+    synthetic: Vec<Vec<u8>>,
+
+    /// Ids of added inlined types
+    inlined: BTreeMap<TypeRef, u32>,
+
+    /// Ids of added bit order types, by `least_significant_bit_first` flag
+    bit_orders: BTreeMap<bool, u32>,
+}
+
+impl RegistryBuilder {
+    fn new(type_count: u32) -> Self {
+        Self {
+            next_id: type_count,
+            synthetic: Vec::new(),
+            inlined: BTreeMap::new(),
+            bit_orders: BTreeMap::new(),
+        }
+    }
+
+    fn add(&mut self, path: Vec<String>, type_def: RegistryTypeDef) -> u32 {
+        let id = self.next_id;
+        self.synthetic.push(registry_type(id, path, type_def));
+        self.next_id += 1;
+        id
+    }
+
+    /// Registry id of the referred type, inlined type is added if needed.
+    fn type_ref(&mut self, type_ref: TypeRef) -> u32 {
+        if let Some(id) = type_ref.id() {
+            return id;
+        }
+        if let Some(id) = self.inlined.get(&type_ref) {
+            return *id;
+        }
+        let type_def = match type_ref {
+            TypeRef::CompactU8 => RegistryTypeDef::Compact(Compact(self.type_ref(TypeRef::U8))),
+            TypeRef::CompactU16 => RegistryTypeDef::Compact(Compact(self.type_ref(TypeRef::U16))),
+            TypeRef::CompactU32 => RegistryTypeDef::Compact(Compact(self.type_ref(TypeRef::U32))),
+            TypeRef::CompactU64 => RegistryTypeDef::Compact(Compact(self.type_ref(TypeRef::U64))),
+            TypeRef::CompactU128 => RegistryTypeDef::Compact(Compact(self.type_ref(TypeRef::U128))),
+            TypeRef::CompactU256 => RegistryTypeDef::Compact(Compact(self.type_ref(TypeRef::U256))),
+            TypeRef::Void => RegistryTypeDef::Tuple(Vec::new()),
+
+            // primitives go first in `TypeRef`, in the same order as in
+            // registry `TypeDefPrimitive`
+            primitive => RegistryTypeDef::Primitive(primitive.encode()[0]),
+        };
+        let id = self.add(Vec::new(), type_def);
+        self.inlined.insert(type_ref, id);
+        id
+    }
+
+    /// Registry id of the bit order type.
+    fn bit_order(&mut self, least_significant_bit_first: bool) -> u32 {
+        if let Some(id) = self.bit_orders.get(&least_significant_bit_first) {
+            return *id;
+        }
+        let ident = if least_significant_bit_first {
+            "Lsb0"
+        } else {
+            "Msb0"
+        };
+        let path = vec!["bitvec".to_string(), "order".to_string(), ident.to_string()];
+        let id = self.add(path, RegistryTypeDef::Composite(Vec::new()));
+        self.bit_orders.insert(least_significant_bit_first, id);
+        id
+    }
+
+    fn fields(&mut self, fields: &[ProofField]) -> Vec<RegistryField> {
+        fields
+            .iter()
+            .map(|field| RegistryField {
+                name: field.name.to_owned(),
+                ty: Compact(self.type_ref(field.ty)),
+                type_name: field.type_name.to_owned(),
+                docs: Vec::new(),
+            })
+            .collect()
+    }
+
+    /// Encoded registry type restored from the proof leaves with given id.
+    fn proof_type(&mut self, id: u32, leaves: &[&ProofType]) -> Result<Vec<u8>> {
+        let type_def = match leaves {
+            [leaf] if !matches!(leaf.type_def, ProofTypeDef::Enumeration(_)) => {
+                match leaf.type_def {
+                    ProofTypeDef::Composite(ref fields) => {
+                        RegistryTypeDef::Composite(self.fields(fields))
+                    }
+                    ProofTypeDef::Sequence(type_param) => {
+                        RegistryTypeDef::Sequence(Compact(self.type_ref(type_param)))
+                    }
+                    ProofTypeDef::Array(ref array) => {
+                        RegistryTypeDef::Array(array.len, Compact(self.type_ref(array.type_param)))
+                    }
+                    ProofTypeDef::Tuple(ref fields) => RegistryTypeDef::Tuple(
+                        fields.iter().map(|x| Compact(self.type_ref(*x))).collect(),
+                    ),
+                    ProofTypeDef::BitSequence(ref bit_sequence) => {
+                        let bit_store = match bit_sequence.num_bytes {
+                            1 => TypeRef::U8,
+                            2 => TypeRef::U16,
+                            4 => TypeRef::U32,
+                            8 => TypeRef::U64,
+                            _ => return Err(MetadataProofError::UnsupportedBitSequence(id).into()),
+                        };
+                        RegistryTypeDef::BitSequence(
+                            Compact(self.type_ref(bit_store)),
+                            Compact(self.bit_order(bit_sequence.least_significant_bit_first)),
+                        )
+                    }
+                    ProofTypeDef::Enumeration(_) => unreachable!("checked above"),
+                }
+            }
+            _ => {
+                let mut variants = Vec::new();
+                for leaf in leaves.iter() {
+                    match leaf.type_def {
+                        ProofTypeDef::Enumeration(ref variant) => variants.push(RegistryVariant {
+                            name: variant.name.to_string(),
+                            fields: self.fields(&variant.fields),
+                            index: variant_index(variant)?,
+                            docs: Vec::new(),
+                        }),
+                        _ => return Err(MetadataProofError::TypeConflict(id).into()),
+                    }
+                }
+                variants.sort_by_key(|variant| variant.index);
+                if variants.windows(2).any(|x| x[0].index == x[1].index) {
+                    return Err(MetadataProofError::TypeConflict(id).into());
+                }
+                RegistryTypeDef::Variant(variants)
+            }
+        };
+        Ok(registry_type(id, leaves[0].path.to_owned(), type_def))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "test")]
+mod tests {
+    use super::*;
+    use frame_metadata::RuntimeMetadata;
+    use std::fs::read_to_string;
+
+    use crate::metadata::{runtime_metadata_from_slice, MetaValues};
+
+    fn westend9150() -> RuntimeMetadataV14 {
+        let meta = read_to_string("for_tests/westend9150").unwrap();
+        let meta_values = MetaValues::from_str_metadata(meta.trim()).unwrap();
+        match runtime_metadata_from_slice(&meta_values.meta).unwrap() {
+            RuntimeMetadata::V14(meta_v14) => meta_v14,
+            _ => panic!("expected V14 metadata"),
+        }
+    }
+
+    fn extra() -> ExtraInfo {
+        ExtraInfo {
+            spec_version: 9150,
+            spec_name: "westend".to_string(),
+            base58prefix: 42,
+            decimals: 12,
+            unit: "WND".to_string(),
+        }
+    }
+
+    #[test]
+    fn proof_digest_matches_metadata_digest() {
+        let meta_v14 = westend9150();
+        let digest = metadata_digest(&meta_v14, extra()).unwrap();
+
+        // `Balances` pallet `transfer` call
+        let proof = MetadataProof::for_call(&meta_v14, extra(), 4, 0).unwrap();
+        assert_eq!(proof.digest().unwrap(), digest);
+
+        let restored = proof.runtime_metadata().unwrap();
+        let pallet = restored.pallets.iter().find(|x| x.index == 4).unwrap();
+        assert_eq!(pallet.name, "Balances");
+        let calls_ty = restored
+            .types
+            .resolve(pallet.calls.as_ref().unwrap().ty.id())
+            .unwrap();
+        match calls_ty.type_def() {
+            TypeDef::Variant(x) => {
+                assert_eq!(x.variants().len(), 1);
+                assert_eq!(x.variants()[0].name(), "transfer");
+            }
+            _ => panic!("expected enum"),
+        }
+        assert!(restored
+            .types
+            .types()
+            .iter()
+            .any(|x| is_omitted_type(x.ty())));
+
+        let proof_encoded = proof.encode();
+        let proof_decoded = MetadataProof::decode(&mut &proof_encoded[..]).unwrap();
+        assert_eq!(proof_decoded, proof);
+    }
+
+    #[test]
+    fn metadata_digest_known_answer() {
+        // calculated from the raw metadata with `merkleized-metadata` crate
+        assert_eq!(
+            hex::encode(metadata_digest(&westend9150(), extra()).unwrap()),
+            "4d467a9fcd0872a261576995c7f0085f9dffe4c01f95be7fc0928bc6f1eee056"
+        );
+    }
+
+    #[test]
+    fn tampered_proof() {
+        let meta_v14 = westend9150();
+        let digest = metadata_digest(&meta_v14, extra()).unwrap();
+        let proof = MetadataProof::for_call(&meta_v14, extra(), 4, 0).unwrap();
+
+        // type replaced with other type under the same id
+        let mut tampered = proof.clone();
+        tampered.leaves[0].path.push("Tampered".to_string());
+        assert_ne!(tampered.digest().unwrap(), digest);
+
+        // network information changed
+        let mut tampered = proof.clone();
+        tampered.extra.decimals = 10;
+        assert_ne!(tampered.digest().unwrap(), digest);
+
+        // extrinsic information changed
+        let mut tampered = proof.clone();
+        tampered.extrinsic.signed_extensions.pop();
+        assert_ne!(tampered.digest().unwrap(), digest);
+
+        let mut tampered = proof.clone();
+        tampered.nodes.pop();
+        assert!(matches!(
+            tampered.digest(),
+            Err(crate::error::Error::MetadataProofError(
+                MetadataProofError::NodesMissing
+            ))
+        ));
+
+        let mut tampered = proof.clone();
+        tampered.nodes.push(H256::zero());
+        assert!(matches!(
+            tampered.digest(),
+            Err(crate::error::Error::MetadataProofError(
+                MetadataProofError::NodesUnused
+            ))
+        ));
+
+        // leaves not in depth-first order
+        let mut tampered = proof.clone();
+        tampered.leaves.swap(0, 1);
+        tampered.leaf_indices.swap(0, 1);
+        assert!(!matches!(tampered.digest(), Ok(a) if a == digest));
+
+        let mut tampered = proof;
+        tampered.leaf_indices.pop();
+        assert!(matches!(
+            tampered.runtime_metadata(),
+            Err(crate::error::Error::MetadataProofError(
+                MetadataProofError::LeafIndicesMismatch { .. }
+            ))
+        ));
+    }
+}
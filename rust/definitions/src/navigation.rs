@@ -1,7 +1,13 @@
 use sp_core::H256;
 
 use crate::{
-    crypto::Encryption, history::Event, keyring::NetworkSpecsKey, network_specs::NetworkSpecs,
+    crypto::Encryption,
+    danger::ExposureRecord,
+    history::Event,
+    keyring::NetworkSpecsKey,
+    localisation::LocalisedMessage,
+    network_specs::NetworkSpecs,
+    users::{KeyAnnotation, KeyUsage},
 };
 
 pub use crate::network_specs::NetworkSpecsToSend;
@@ -44,12 +50,18 @@ pub enum TransactionAction {
 /// Is used for proper navigation. Variants:
 /// `AddSpecs` (with associated `NetworkSpecsKey`), `LoadMeta` (with associated
 /// `NetworkSpecsKey` for the first by order network using those metadata),
-/// and `LoadTypes`
+/// `LoadTypes`, `AddContacts`, `RotateVerifier` (with associated
+/// `NetworkSpecsKey` for the first by order network with rotated verifier),
+/// `SecurityPolicy` and `AmendSpecs` (with associated `NetworkSpecsKey`)
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub enum StubNav {
     AddSpecs { n: NetworkSpecsKey },
     LoadMeta { l: NetworkSpecsKey },
     LoadTypes,
+    AddContacts,
+    RotateVerifier { n: NetworkSpecsKey },
+    SecurityPolicy,
+    AmendSpecs { n: NetworkSpecsKey },
 }
 
 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -78,9 +90,13 @@ pub enum ScreenNameType {
     H4,
 }
 
+/// Shield alert content, when the Signer is in danger
+///
+/// `Past` contains all exposures recorded since the last danger record reset,
+/// with the time each was first seen.
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub enum ShieldAlert {
-    Past,
+    Past { exposures: Vec<ExposureRecord> },
 }
 
 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -116,6 +132,7 @@ pub enum ScreenData {
     Settings { f: MSettings },
     Log { f: MLog },
     LogDetails { f: MLogDetails },
+    LogSearch { f: MLogSearch },
     Transaction { f: MTransaction },
     SeedSelector { f: MSeeds },
     KeyDetails { f: MKeyDetails },
@@ -154,6 +171,29 @@ pub struct MKeysCard {
     pub swiped: bool,
     pub multiselect: bool,
     pub secret_exposed: bool,
+    pub usage: Option<MKeyUsage>,
+    pub annotation: Option<KeyAnnotation>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MKeyUsage {
+    pub transactions_signed: u32,
+    pub messages_signed: u32,
+    pub first_used: String,
+    pub last_used: String,
+    pub last_nonce: Option<u64>,
+}
+
+impl From<&KeyUsage> for MKeyUsage {
+    fn from(key_usage: &KeyUsage) -> Self {
+        MKeyUsage {
+            transactions_signed: key_usage.transactions_signed,
+            messages_signed: key_usage.messages_signed,
+            first_used: key_usage.first_used.to_owned(),
+            last_used: key_usage.last_used.to_owned(),
+            last_nonce: key_usage.last_nonce,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
@@ -203,6 +243,14 @@ pub struct MLog {
     pub log: Vec<History>,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MLogSearch {
+    pub query: String,
+    pub page: u32,
+    pub total_pages: u32,
+    pub log: Vec<History>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct MEventMaybeDecoded {
     pub event: Event,
@@ -236,6 +284,7 @@ pub struct TransactionCard {
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct TransactionCardSet {
     pub author: Option<Vec<TransactionCard>>,
+    pub contacts: Option<Vec<TransactionCard>>,
     pub error: Option<Vec<TransactionCard>>,
     pub extensions: Option<Vec<TransactionCard>>,
     pub importing_derivations: Option<Vec<TransactionCard>>,
@@ -243,6 +292,8 @@ pub struct TransactionCardSet {
     pub meta: Option<Vec<TransactionCard>>,
     pub method: Option<Vec<TransactionCard>>,
     pub new_specs: Option<Vec<TransactionCard>>,
+    pub new_verifier: Option<Vec<TransactionCard>>,
+    pub old_specs: Option<Vec<TransactionCard>>,
     pub verifier: Option<Vec<TransactionCard>>,
     pub warning: Option<Vec<TransactionCard>>,
     pub types_info: Option<Vec<TransactionCard>>,
@@ -274,6 +325,7 @@ pub struct MKeyDetails {
     pub pubkey: String,
     pub network_info: MSCNetworkInfo,
     pub address: Address,
+    pub usage: Option<MKeyUsage>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -314,6 +366,7 @@ pub struct Address {
     pub seed_name: String,
     pub multiselect: Option<bool>,
     pub secret_exposed: bool,
+    pub annotation: Option<KeyAnnotation>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -599,6 +652,14 @@ pub struct MSCFieldNumber {
 pub struct MSCId {
     pub base58: String,
     pub identicon: Vec<u8>,
+    pub contact_name: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MSCContact {
+    pub name: String,
+    pub base58: String,
+    pub identicon: Vec<u8>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -629,39 +690,109 @@ pub struct MSCTxSpecPlain {
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Card {
-    AuthorCard { f: Address },
-    AuthorPlainCard { f: MSCAuthorPlain },
-    AuthorPublicKeyCard { f: MVerifierDetails },
-    BalanceCard { f: MSCCurrency },
-    BitVecCard { f: String },
-    BlockHashCard { f: String },
-    CallCard { f: MSCCall },
-    DefaultCard { f: String },
-    DerivationsCard { f: Vec<String> },
-    EnumVariantNameCard { f: MSCEnumVariantName },
+    AuthorCard {
+        f: Address,
+    },
+    AuthorPlainCard {
+        f: MSCAuthorPlain,
+    },
+    AuthorPublicKeyCard {
+        f: MVerifierDetails,
+    },
+    BalanceCard {
+        f: MSCCurrency,
+    },
+    BitVecCard {
+        f: String,
+    },
+    BlockHashCard {
+        f: String,
+    },
+    CallCard {
+        f: MSCCall,
+    },
+    ContactCard {
+        f: MSCContact,
+    },
+    DefaultCard {
+        f: String,
+    },
+    DerivationsCard {
+        f: Vec<String>,
+    },
+    EnumVariantNameCard {
+        f: MSCEnumVariantName,
+    },
     EraImmortalCard,
-    EraMortalCard { f: MSCEraMortal },
-    ErrorCard { f: String },
-    FieldNameCard { f: MSCFieldName },
-    FieldNumberCard { f: MSCFieldNumber },
-    IdCard { f: MSCId },
-    IdentityFieldCard { f: String },
-    MetaCard { f: MMetadataRecord },
-    NameVersionCard { f: MSCNameVersion },
-    NetworkGenesisHashCard { f: String },
-    NetworkNameCard { f: String },
-    NetworkInfoCard { f: MSCNetworkInfo },
-    NewSpecsCard { f: NetworkSpecsToSend },
-    NonceCard { f: String },
+    EraMortalCard {
+        f: MSCEraMortal,
+    },
+    ErrorCard {
+        f: String,
+    },
+    FieldNameCard {
+        f: MSCFieldName,
+    },
+    FieldNumberCard {
+        f: MSCFieldNumber,
+    },
+    IdCard {
+        f: MSCId,
+    },
+    IdentityFieldCard {
+        f: String,
+    },
+    MetaCard {
+        f: MMetadataRecord,
+    },
+    NameVersionCard {
+        f: MSCNameVersion,
+    },
+    NetworkGenesisHashCard {
+        f: String,
+    },
+    NetworkNameCard {
+        f: String,
+    },
+    NetworkInfoCard {
+        f: MSCNetworkInfo,
+    },
+    NewSpecsCard {
+        f: NetworkSpecsToSend,
+    },
+    NonceCard {
+        f: String,
+    },
     NoneCard,
-    PalletCard { f: String },
-    TextCard { f: String },
-    TipCard { f: MSCCurrency },
-    TipPlainCard { f: String },
-    TxSpecCard { f: String },
-    TxSpecPlainCard { f: MSCTxSpecPlain },
-    TypesInfoCard { f: MTypesInfo },
-    VarNameCard { f: String },
-    VerifierCard { f: MVerifierDetails },
-    WarningCard { f: String },
+    PalletCard {
+        f: String,
+    },
+    TextCard {
+        f: String,
+    },
+    TipCard {
+        f: MSCCurrency,
+    },
+    TipPlainCard {
+        f: String,
+    },
+    TxSpecCard {
+        f: String,
+    },
+    TxSpecPlainCard {
+        f: MSCTxSpecPlain,
+    },
+    TypesInfoCard {
+        f: MTypesInfo,
+    },
+    VarNameCard {
+        f: String,
+    },
+    VerifierCard {
+        f: MVerifierDetails,
+    },
+    WarningCard {
+        f: String,
+        message: LocalisedMessage,
+    },
 }
@@ -225,6 +225,7 @@
 //! - `base58prefix`, network-associated base58 prefix  
 //! - `decimals`  
 //! - `name`, network name, as it appears in the network metadata  
+//! - `tokens`, network tokens other than the main one  
 //! - `unit`  
 //!
 //! However, if non-critical parameters have changes, Signer will permit the
@@ -261,6 +262,29 @@
 //! Sometimes the networks have several available decimals and units, or none at all.  
 //! This cases should be dealt with on case-by-case basis.  
 //!
+//! Networks with several tokens have main token `decimals` and `unit`, and the
+//! list of other network tokens as [`TokenSpecs`]. Balance-related values of
+//! `orml_tokens` or `pallet_assets` calls with known currency are displayed
+//! with the decimals and unit of the corresponding token.  
+//!
+//! Token list is not the part of the SCALE-encoded network specs. It is
+//! appended to the encoded specs in database entries and in `add_specs` and
+//! `amend_specs` payloads, only if the network has tokens other than the main
+//! one. Network specs encoded before the tokens were introduced are read with
+//! empty token list. Anything left after the token list is an error.  
+//!
+//! Network specs in history log events (`NetworkSpecsDisplay`,
+//! `NetworkSpecsAmendmentDisplay`, `NetworkSpecsExport`) are SCALE-encoded
+//! as a part of the entry, and carry no token list. This is deliberate, and
+//! is not fixed by a database migration: history log entries are hash-chained
+//! over their stored encoding, and re-encoding the events would change the
+//! hash of every entry, so that the chain heads and the audit reports signed
+//! earlier would no longer match, exactly as if the log was tampered with.
+//! Events recorded since the token lists are kept in the history log
+//! (`NetworkSpecsTokensDisplay`, `NetworkSpecsAmendmentTokensDisplay`) carry
+//! the token list next to the network specs, so that the log shows with which
+//! token decimals the network balances were displayed.  
+//!
 //! ## Examples: balance representation  
 //!
 //! Balance (`u64`) from transaction is decoded as `1`.
@@ -271,7 +295,7 @@
 //! Network `decimals` value is `12`, network `unit` is `WND`.
 //! The balance should be therefore represented as `10 uWND`.  
 
-use parity_scale_codec::{Decode, Encode};
+use parity_scale_codec::{Decode, DecodeAll, Encode};
 #[cfg(feature = "signer")]
 use plot_icon::EMPTY_PNG;
 use sled::IVec;
@@ -333,6 +357,12 @@ pub struct NetworkSpecs {
     /// Network title, as it appears in Signer menus.
     pub title: String,
 
+    /// Network tokens other than the main one.  
+    /// Token list is not SCALE-encoded with the specs, see
+    /// [`NetworkSpecs::store`].  
+    #[codec(skip)]
+    pub tokens: Vec<TokenSpecs>,
+
     /// Token name, to display balance-related values properly.  
     pub unit: String,
 }
@@ -378,10 +408,30 @@ pub struct NetworkSpecsToSend {
     /// Network title, as it appears in Signer menus.  
     pub title: String,
 
+    /// Network tokens other than the main one.  
+    /// Token list is not SCALE-encoded with the specs, see
+    /// [`NetworkSpecsToSend::store`].  
+    #[codec(skip)]
+    pub tokens: Vec<TokenSpecs>,
+
     /// Token name, to display balance-related values properly.  
     pub unit: String,
 }
 
+/// Network token other than the main one
+#[derive(Decode, Encode, PartialEq, Eq, Debug, Clone)]
+pub struct TokenSpecs {
+    /// Asset id, for tokens of `pallet_assets`.  
+    /// Tokens of `orml_tokens` are found by `unit`.  
+    pub asset_id: Option<u32>,
+
+    /// Order of magnitude, by which the token unit exceeds the balance integer unit.  
+    pub decimals: u8,
+
+    /// Token name, as it appears in currency id.  
+    pub unit: String,
+}
+
 /// Network parameters needed to decode and display transaction
 #[derive(Decode, Encode, PartialEq, Eq, Debug, Clone)]
 pub struct ShortSpecs {
@@ -399,6 +449,9 @@ pub struct ShortSpecs {
     /// Network name, as it appears in network metadata  
     pub name: String,
 
+    /// Network tokens other than the main one.  
+    pub tokens: Vec<TokenSpecs>,
+
     /// Token name, to display balance-related values properly.  
     pub unit: String,
 }
@@ -418,6 +471,7 @@ impl NetworkSpecs {
             path_id: self.path_id.to_string(),
             secondary_color: self.secondary_color.to_string(),
             title: self.title.to_string(),
+            tokens: self.tokens.to_vec(),
             unit: self.unit.to_string(),
         }
     }
@@ -430,10 +484,27 @@ impl NetworkSpecs {
             decimals: self.decimals,
             genesis_hash: self.genesis_hash,
             name: self.name.to_string(),
+            tokens: self.tokens.to_vec(),
             unit: self.unit.to_string(),
         }
     }
 
+    /// Transform [`NetworkSpecs`] into `Vec<u8>` to put in the cold database
+    ///
+    /// Token list, if not empty, is appended to the SCALE-encoded specs.  
+    pub fn store(&self) -> Vec<u8> {
+        encode_with_tokens(self, &self.tokens)
+    }
+
+    /// Get [`NetworkSpecs`] from the value stored in the cold database
+    ///
+    /// Values stored without token list are processed as well.  
+    pub fn from_stored(network_specs_encoded: &[u8]) -> Result<Self> {
+        let (mut network_specs, tokens) = decode_with_tokens::<Self>(network_specs_encoded)?;
+        network_specs.tokens = tokens;
+        Ok(network_specs)
+    }
+
     /// Gets [`NetworkSpecs`] from [`NetworkSpecsKey`] and associated value
     /// from cold database tree `SPECSTREE`  
     ///
@@ -444,7 +515,7 @@ impl NetworkSpecs {
         network_specs_encoded: IVec,
     ) -> Result<Self> {
         let (genesis_hash_vec, encryption) = network_specs_key.genesis_hash_encryption()?;
-        let network_specs = Self::decode(&mut &network_specs_encoded[..])?;
+        let network_specs = Self::from_stored(&network_specs_encoded)?;
         if &genesis_hash_vec[..] != network_specs.genesis_hash.as_bytes() {
             return Err(Error::SpecsGenesisHashMismatch {
                 network_specs_key: network_specs_key.to_owned(),
@@ -494,12 +565,93 @@ impl NetworkSpecsToSend {
             path_id: self.path_id.to_string(),
             secondary_color: self.secondary_color.to_string(),
             title: self.title.to_string(),
+            tokens: self.tokens.to_vec(),
             unit: self.unit.to_string(),
         }
     }
 
+    /// Transform [`NetworkSpecsToSend`] into `Vec<u8>` to put in the hot
+    /// database or in `add_specs` and `amend_specs` payloads
+    ///
+    /// Token list, if not empty, is appended to the SCALE-encoded specs.  
+    pub fn store(&self) -> Vec<u8> {
+        encode_with_tokens(self, &self.tokens)
+    }
+
+    /// Get [`NetworkSpecsToSend`] from the value stored in the hot database
+    /// or received in `add_specs` and `amend_specs` payloads
+    ///
+    /// Values stored without token list are processed as well.  
+    pub fn from_stored(network_specs_to_send_encoded: &[u8]) -> Result<Self> {
+        let (mut network_specs_to_send, tokens) =
+            decode_with_tokens::<Self>(network_specs_to_send_encoded)?;
+        network_specs_to_send.tokens = tokens;
+        Ok(network_specs_to_send)
+    }
+
+    /// Name of the first field that differs between `self` and `new` and
+    /// could not be changed without removing the network, if any
+    ///
+    /// Network `name`, `base58prefix` and `path_id` are used in addresses
+    /// and in metadata, so these could not be changed by an `amend_specs`
+    /// update.
+    pub fn fixed_field_changed(&self, new: &NetworkSpecsToSend) -> Option<&'static str> {
+        if self.name != new.name {
+            Some("name")
+        } else if self.base58prefix != new.base58prefix {
+            Some("base58prefix")
+        } else if self.path_id != new.path_id {
+            Some("path_id")
+        } else {
+            None
+        }
+    }
+
+    /// Display fields that differ between `self` and `new`, as
+    /// `(field name, old value, new value)`
+    ///
+    /// Only the fields that could be changed by an `amend_specs` update are
+    /// compared.
+    pub fn display_changes(&self, new: &NetworkSpecsToSend) -> Vec<(&'static str, String, String)> {
+        let mut out = Vec::new();
+        if self.unit != new.unit {
+            out.push(("unit", self.unit.to_string(), new.unit.to_string()));
+        }
+        if self.decimals != new.decimals {
+            out.push((
+                "decimals",
+                self.decimals.to_string(),
+                new.decimals.to_string(),
+            ));
+        }
+        if self.title != new.title {
+            out.push(("title", self.title.to_string(), new.title.to_string()));
+        }
+        if self.tokens != new.tokens {
+            out.push((
+                "tokens",
+                show_tokens(&self.tokens),
+                show_tokens(&new.tokens),
+            ));
+        }
+        if self.logo != new.logo {
+            out.push(("logo", self.logo.to_string(), new.logo.to_string()));
+        }
+        if self.color != new.color {
+            out.push(("color", self.color.to_string(), new.color.to_string()));
+        }
+        if self.secondary_color != new.secondary_color {
+            out.push((
+                "secondary_color",
+                self.secondary_color.to_string(),
+                new.secondary_color.to_string(),
+            ));
+        }
+        out
+    }
+
     /// Gets [`NetworkSpecsToSend`] from [`NetworkSpecsKey`] and associated
-    /// value from hot database tree `SPECSTREEPREP`  
+    /// value from hot database tree `SPECSTREEPREP`
     ///
     /// Checks that there is no genesis hash or encryption mismatch between
     /// key and specs content.  
@@ -509,7 +661,7 @@ impl NetworkSpecsToSend {
         network_specs_to_send_encoded: IVec,
     ) -> Result<Self> {
         let (genesis_hash_vec, encryption) = network_specs_key.genesis_hash_encryption()?;
-        let network_specs_to_send = Self::decode(&mut &network_specs_to_send_encoded[..])?;
+        let network_specs_to_send = Self::from_stored(&network_specs_to_send_encoded)?;
         if &genesis_hash_vec[..] != network_specs_to_send.genesis_hash.as_bytes() {
             return Err(Error::SpecsToSendGenesisHash {
                 network_specs_key: network_specs_key.to_owned(),
@@ -539,6 +691,53 @@ impl NetworkSpecsToSend {
     }
 }
 
+impl ShortSpecs {
+    /// Makes [`ShortSpecs`] to display balance-related values in the given
+    /// token
+    pub fn with_token(&self, token: &TokenSpecs) -> Self {
+        ShortSpecs {
+            base58prefix: self.base58prefix,
+            decimals: token.decimals,
+            genesis_hash: self.genesis_hash,
+            name: self.name.to_string(),
+            tokens: self.tokens.to_vec(),
+            unit: token.unit.to_string(),
+        }
+    }
+}
+
+/// SCALE-encoded value with the token list appended, if the token list is not
+/// empty
+fn encode_with_tokens<T: Encode>(value: &T, tokens: &[TokenSpecs]) -> Vec<u8> {
+    let mut out = value.encode();
+    if !tokens.is_empty() {
+        out.extend_from_slice(&tokens.encode());
+    }
+    out
+}
+
+/// Decode value and the token list appended to it, if there is any
+///
+/// Any data left after the token list is an error.
+fn decode_with_tokens<T: Decode>(mut encoded: &[u8]) -> Result<(T, Vec<TokenSpecs>)> {
+    let value = T::decode(&mut encoded)?;
+    let tokens = if encoded.is_empty() {
+        Vec::new()
+    } else {
+        <Vec<TokenSpecs>>::decode_all(&mut encoded)?
+    };
+    Ok((value, tokens))
+}
+
+/// Print token list, for display
+pub(crate) fn show_tokens(tokens: &[TokenSpecs]) -> String {
+    let printed: Vec<String> = tokens
+        .iter()
+        .map(|token| format!("{} ({})", token.unit, token.decimals))
+        .collect();
+    format!("[{}]", printed.join(", "))
+}
+
 /// Network properties that must be fetched with RPC call for properties
 /// in each compatible network
 #[derive(Decode, Encode, PartialEq, Eq, Debug)]
@@ -546,6 +745,7 @@ impl NetworkSpecsToSend {
 pub struct NetworkProperties {
     pub base58prefix: u16,
     pub decimals: u8,
+    pub tokens: Vec<TokenSpecs>,
     pub unit: String,
 }
 
@@ -653,3 +853,50 @@ pub enum ValidCurrentVerifier {
     /// Network has some other verifier, different from the general one
     Custom { v: Verifier },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn specs_with_tokens() -> NetworkSpecs {
+        NetworkSpecs {
+            base58prefix: 8,
+            color: "#000000".to_string(),
+            decimals: 12,
+            encryption: Encryption::Sr25519,
+            genesis_hash: H256::repeat_byte(1),
+            logo: "karura".to_string(),
+            name: "karura".to_string(),
+            order: 3,
+            path_id: "//karura".to_string(),
+            secondary_color: "#262626".to_string(),
+            title: "Karura".to_string(),
+            tokens: vec![TokenSpecs {
+                asset_id: None,
+                decimals: 12,
+                unit: "KSM".to_string(),
+            }],
+            unit: "KAR".to_string(),
+        }
+    }
+
+    #[test]
+    fn stored_specs_keep_tokens() {
+        let network_specs = specs_with_tokens();
+        assert_eq!(
+            NetworkSpecs::from_stored(&network_specs.store()).unwrap(),
+            network_specs
+        );
+
+        // specs encoded before the tokens were introduced
+        let legacy = NetworkSpecs::from_stored(&network_specs.encode()).unwrap();
+        assert!(legacy.tokens.is_empty());
+    }
+
+    #[test]
+    fn stored_specs_leftover_rejected() {
+        let mut stored = specs_with_tokens().store();
+        stored.push(0);
+        assert!(NetworkSpecs::from_stored(&stored).is_err());
+    }
+}
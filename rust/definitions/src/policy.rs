@@ -0,0 +1,121 @@
+//! Signer security policy
+//!
+//! Organisations could lock Signer devices down with [`SecurityPolicy`],
+//! stored in `SETTREE` as encoded [`SecurityPolicy`] under key `POLICY`.
+//!
+//! Policy is set up during the database initiation, or later through the
+//! `security_policy` update signed by the general verifier. Database without
+//! policy record behaves as with default [`SecurityPolicy`], i.e. with all
+//! rules switched off.
+//!
+//! Each policy carries an index, and `security_policy` update is accepted only
+//! if its index is greater than the index of the policy in force, so that
+//! signed policy updates could not be replayed.
+//!
+//! Action forbidden by the policy is rejected with a dedicated error, and the
+//! violation of [`PolicyRule`] is recorded in the history log.
+use parity_scale_codec::{Decode, Encode};
+
+/// Set of rules restricting the Signer behaviour
+///
+/// Each rule is switched off by default.
+#[derive(Decode, Encode, PartialEq, Eq, Debug, Clone, Default)]
+pub struct SecurityPolicy {
+    /// Policy index, increased with each policy update; default policy has
+    /// index `0`
+    pub index: u32,
+
+    /// Reject unsigned `add_specs`, `load_metadata`, `load_types` and
+    /// `add_contacts` updates
+    pub reject_unsigned_updates: bool,
+
+    /// Reject updates that set up or change the network verifier or the
+    /// general verifier, purging the data verified by the former verifier
+    pub forbid_verifier_change: bool,
+
+    /// Forbid the secret key export
+    pub forbid_secret_export: bool,
+
+    /// Forbid signing transactions parsed with metadata older than the latest
+    /// metadata known for the network
+    pub forbid_outdated_metadata: bool,
+
+    /// Require user comment on every signature
+    pub require_user_comment: bool,
+}
+
+/// Single rule of [`SecurityPolicy`]
+#[derive(Decode, Encode, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum PolicyRule {
+    /// [`SecurityPolicy::reject_unsigned_updates`]
+    UnsignedUpdate,
+
+    /// [`SecurityPolicy::forbid_verifier_change`]
+    VerifierChange,
+
+    /// [`SecurityPolicy::forbid_secret_export`]
+    SecretExport,
+
+    /// [`SecurityPolicy::forbid_outdated_metadata`]
+    OutdatedMetadata,
+
+    /// [`SecurityPolicy::require_user_comment`]
+    UserComment,
+}
+
+impl SecurityPolicy {
+    /// Check if the policy forbids action covered by the rule.
+    pub fn forbids(&self, rule: PolicyRule) -> bool {
+        match rule {
+            PolicyRule::UnsignedUpdate => self.reject_unsigned_updates,
+            PolicyRule::VerifierChange => self.forbid_verifier_change,
+            PolicyRule::SecretExport => self.forbid_secret_export,
+            PolicyRule::OutdatedMetadata => self.forbid_outdated_metadata,
+            PolicyRule::UserComment => self.require_user_comment,
+        }
+    }
+
+    /// Rules switched on in the policy.
+    pub fn rules(&self) -> Vec<PolicyRule> {
+        PolicyRule::ALL
+            .iter()
+            .copied()
+            .filter(|rule| self.forbids(*rule))
+            .collect()
+    }
+}
+
+impl PolicyRule {
+    /// All rules, in order of [`SecurityPolicy`] fields.
+    pub const ALL: [PolicyRule; 5] = [
+        PolicyRule::UnsignedUpdate,
+        PolicyRule::VerifierChange,
+        PolicyRule::SecretExport,
+        PolicyRule::OutdatedMetadata,
+        PolicyRule::UserComment,
+    ];
+
+    /// Stable rule identifier, same as the [`SecurityPolicy`] field name.
+    pub fn id(&self) -> &'static str {
+        match self {
+            PolicyRule::UnsignedUpdate => "reject_unsigned_updates",
+            PolicyRule::VerifierChange => "forbid_verifier_change",
+            PolicyRule::SecretExport => "forbid_secret_export",
+            PolicyRule::OutdatedMetadata => "forbid_outdated_metadata",
+            PolicyRule::UserComment => "require_user_comment",
+        }
+    }
+
+    /// Rule description, for errors.
+    pub fn show(&self) -> &'static str {
+        match self {
+            PolicyRule::UnsignedUpdate => "unsigned updates are rejected",
+            PolicyRule::VerifierChange => "network and general verifiers could not be changed",
+            PolicyRule::SecretExport => "secret keys could not be exported",
+            PolicyRule::OutdatedMetadata => {
+                "transactions could not be signed with outdated metadata"
+            }
+            PolicyRule::UserComment => "every signature requires a user comment",
+        }
+    }
+}
@@ -6,15 +6,27 @@
 //! - adding new networks,  
 //! - updating the metadata of existing networks,  
 //! - updating the types information,  
+//! - adding named contacts,  
+//! - rotating custom network verifier,  
+//! - setting up Signer security policy,  
+//! - amending display specs of already known networks,  
 //! - bulk-importing the derivations  
 //!
-//! QR codes for adding networks, loading metadata and updating types information
-//! all have similar structure:
+//! QR codes for adding networks, loading metadata, updating types information,
+//! adding contacts, rotating network verifier, setting up security policy and
+//! amending network specs all have similar structure:
 //! - prelude `53xxyy` where `xx` is the encryption type, and `yy` is the message type  
 //! - verifier public key (if the QR code is signed by verifier)  
 //! - content  
 //! - verifier signature (if the QR code is signed by verifier)  
 //!
+//! Update contents of `add_specs`, `load_metadata`, `load_types`,
+//! `add_contacts`, `rotate_verifier`, `security_policy` and `amend_specs` QR
+//! codes could also be sent compressed, as [`ContentCompressed`] with prelude
+//! `53xx82`. Signature in compressed updates is always produced for the
+//! uncompressed content, so the same signature could be used for both the
+//! compressed and the uncompressed update.
+//!
 //! QR codes for importing derivations are never signed and have structure:
 //! - prelude `53ffde`
 //! - content  
@@ -22,15 +34,18 @@
 //! This module deals with content part of QR codes.  
 
 use parity_scale_codec::{Decode, Encode};
+use std::io::Read;
 #[cfg(feature = "active")]
 use std::path::Path;
 
 use crate::crypto::Encryption;
-use crate::error::Result;
+use crate::error::{Error, Result, TransferContent};
 #[cfg(feature = "signer")]
 use crate::helpers::pic_types;
-use crate::network_specs::NetworkSpecsToSend;
+use crate::network_specs::{NetworkSpecsToSend, VerifierValue};
+use crate::policy::SecurityPolicy;
 use crate::types::TypeEntry;
+use crate::users::ContactEntry;
 use sp_core::H256;
 
 /// `load_metadata` QR code content  
@@ -107,20 +122,10 @@ impl ContentLoadMeta {
 #[derive(Decode, Encode)]
 pub struct ContentAddSpecs(Vec<u8>);
 
-#[derive(Decode, Encode)]
-struct DecodedContentAddSpecs {
-    specs: NetworkSpecsToSend,
-}
-
 impl ContentAddSpecs {
     /// Generate [`ContentAddSpecs`] from network specs [`NetworkSpecsToSend`].
     pub fn generate(specs: &NetworkSpecsToSend) -> Self {
-        Self(
-            DecodedContentAddSpecs {
-                specs: specs.to_owned(),
-            }
-            .encode(),
-        )
+        Self(specs.store())
     }
 
     /// Transform `&[u8]` slice into [`ContentAddSpecs`].
@@ -130,7 +135,7 @@ impl ContentAddSpecs {
 
     /// Get network specs [`NetworkSpecsToSend`] from [`ContentAddSpecs`].
     pub fn specs(&self) -> Result<NetworkSpecsToSend> {
-        Ok(<DecodedContentAddSpecs>::decode(&mut &self.0[..])?.specs)
+        NetworkSpecsToSend::from_stored(&self.0)
     }
 
     /// Write [`ContentAddSpecs`] into file that could be signed by the verifier.
@@ -241,6 +246,253 @@ impl ContentLoadTypes {
     }
 }
 
+/// `add_contacts` QR code content  
+///
+/// Messages `add_contacts` are used to add through air-gap named contacts,
+/// i.e. recipient addresses known to the user, for example exchange deposit
+/// addresses. Contacts with account id already known to the Signer get renamed.
+///
+/// Contacts are accepted only if signed by the general verifier, or unsigned
+/// if no general verifier is set up.  
+#[derive(Decode, Encode)]
+pub struct ContentAddContacts(Vec<u8>);
+
+#[derive(Decode, Encode)]
+struct DecodedContentAddContacts {
+    contacts: Vec<ContactEntry>,
+}
+
+impl ContentAddContacts {
+    /// Generate [`ContentAddContacts`] from contacts `&[ContactEntry]`.  
+    pub fn generate(contacts: &[ContactEntry]) -> Self {
+        Self(
+            DecodedContentAddContacts {
+                contacts: contacts.to_owned(),
+            }
+            .encode(),
+        )
+    }
+
+    /// Transform `&[u8]` slice into [`ContentAddContacts`].  
+    pub fn from_slice(slice: &[u8]) -> Self {
+        Self(slice.to_vec())
+    }
+
+    /// Get contacts `Vec<ContactEntry>` from [`ContentAddContacts`].  
+    pub fn contacts(&self) -> Result<Vec<ContactEntry>> {
+        Ok(<DecodedContentAddContacts>::decode(&mut &self.0[..])?.contacts)
+    }
+
+    /// Write [`ContentAddContacts`] into file that could be signed by the verifier.  
+    #[cfg(feature = "active")]
+    pub fn write<P>(&self, path: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(std::fs::write(path, &self.to_sign())?)
+    }
+
+    /// Transform [`ContentAddContacts`] into `Vec<u8>` that could be signed by the verifier.  
+    pub fn to_sign(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    /// Transform [`ContentAddContacts`] into `Vec<u8>` that is concatenated with
+    /// other parts of the QR code.  
+    ///
+    /// Same as in [`ContentLoadTypes`], already SCALE-encoded content is encoded
+    /// second time as an opaque `Vec<u8>`.  
+    pub fn to_transfer(&self) -> Vec<u8> {
+        self.encode()
+    }
+}
+
+/// `rotate_verifier` QR code content  
+///
+/// Messages `rotate_verifier` are used to replace custom network verifier
+/// with a new one, without purging the network specs and metadata already
+/// in the Signer database.
+///
+/// Rotation is accepted only if signed by the current custom verifier of the
+/// network. Signer never accepts unsigned rotations, or rotations signed by
+/// any other key, including previously rotated out network verifiers.
+///
+/// Rotation also names the outgoing verifier and carries the rotation index,
+/// that must be exactly the next one after the index of the last rotation
+/// accepted for the network (first rotation has index `1`). Thus an old
+/// rotation could not be replayed after the verifier was rotated back, i.e.
+/// `A -> B` rotation signed by `A` is not accepted again after `A -> B -> A`.  
+#[derive(Decode, Encode)]
+pub struct ContentRotateVerifier(Vec<u8>);
+
+/// Decoded [`ContentRotateVerifier`].  
+#[derive(Debug, Decode, Encode, PartialEq)]
+pub struct VerifierRotation {
+    /// network genesis hash
+    pub genesis_hash: H256,
+
+    /// current network verifier, to be rotated out
+    pub old_verifier_value: VerifierValue,
+
+    /// new network verifier
+    pub new_verifier_value: VerifierValue,
+
+    /// monotonic rotation index for the network
+    pub rotation_index: u32,
+}
+
+impl ContentRotateVerifier {
+    /// Generate [`ContentRotateVerifier`] from [`VerifierRotation`].  
+    pub fn generate(verifier_rotation: &VerifierRotation) -> Self {
+        Self(verifier_rotation.encode())
+    }
+
+    /// Transform `&[u8]` slice into [`ContentRotateVerifier`].  
+    pub fn from_slice(slice: &[u8]) -> Self {
+        Self(slice.to_vec())
+    }
+
+    /// Get [`VerifierRotation`] from [`ContentRotateVerifier`].  
+    pub fn verifier_rotation(&self) -> Result<VerifierRotation> {
+        Ok(<VerifierRotation>::decode(&mut &self.0[..])?)
+    }
+
+    /// Write [`ContentRotateVerifier`] into file that could be signed by the verifier.  
+    #[cfg(feature = "active")]
+    pub fn write<P>(&self, path: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(std::fs::write(path, &self.to_sign())?)
+    }
+
+    /// Transform [`ContentRotateVerifier`] into `Vec<u8>` that could be signed by the verifier.  
+    pub fn to_sign(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    /// Transform [`ContentRotateVerifier`] into `Vec<u8>` that is concatenated with
+    /// other parts of the QR code.  
+    ///
+    /// Same as in [`ContentLoadTypes`], already SCALE-encoded content is encoded
+    /// second time as an opaque `Vec<u8>`.  
+    pub fn to_transfer(&self) -> Vec<u8> {
+        self.encode()
+    }
+}
+
+/// `security_policy` QR code content  
+///
+/// Messages `security_policy` are used to set up through air-gap the Signer
+/// [`SecurityPolicy`], replacing the one already in the database.
+///
+/// Security policy is accepted only if signed by the general verifier.
+#[derive(Decode, Encode)]
+pub struct ContentSecurityPolicy(Vec<u8>);
+
+#[derive(Decode, Encode)]
+struct DecodedContentSecurityPolicy {
+    security_policy: SecurityPolicy,
+}
+
+impl ContentSecurityPolicy {
+    /// Generate [`ContentSecurityPolicy`] from [`SecurityPolicy`].  
+    pub fn generate(security_policy: &SecurityPolicy) -> Self {
+        Self(
+            DecodedContentSecurityPolicy {
+                security_policy: security_policy.to_owned(),
+            }
+            .encode(),
+        )
+    }
+
+    /// Transform `&[u8]` slice into [`ContentSecurityPolicy`].  
+    pub fn from_slice(slice: &[u8]) -> Self {
+        Self(slice.to_vec())
+    }
+
+    /// Get [`SecurityPolicy`] from [`ContentSecurityPolicy`].  
+    pub fn security_policy(&self) -> Result<SecurityPolicy> {
+        Ok(<DecodedContentSecurityPolicy>::decode(&mut &self.0[..])?.security_policy)
+    }
+
+    /// Write [`ContentSecurityPolicy`] into file that could be signed by the verifier.  
+    #[cfg(feature = "active")]
+    pub fn write<P>(&self, path: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(std::fs::write(path, &self.to_sign())?)
+    }
+
+    /// Transform [`ContentSecurityPolicy`] into `Vec<u8>` that could be signed by the verifier.  
+    pub fn to_sign(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    /// Transform [`ContentSecurityPolicy`] into `Vec<u8>` that is concatenated with
+    /// other parts of the QR code.  
+    ///
+    /// Same as in [`ContentLoadTypes`], already SCALE-encoded content is encoded
+    /// second time as an opaque `Vec<u8>`.  
+    pub fn to_transfer(&self) -> Vec<u8> {
+        self.encode()
+    }
+}
+
+/// `amend_specs` QR code content  
+///
+/// Messages `amend_specs` are used to change through air-gap the display specs
+/// (`unit`, `decimals`, `title`, `logo` and colors) of a network already known
+/// to the Signer, without removing the network and thus keeping its addresses
+/// and metadata.
+///
+/// Amended specs are accepted only if signed by the current network verifier.
+#[derive(Decode, Encode)]
+pub struct ContentAmendSpecs(Vec<u8>);
+
+impl ContentAmendSpecs {
+    /// Generate [`ContentAmendSpecs`] from amended network specs
+    /// [`NetworkSpecsToSend`].  
+    pub fn generate(specs: &NetworkSpecsToSend) -> Self {
+        Self(specs.store())
+    }
+
+    /// Transform `&[u8]` slice into [`ContentAmendSpecs`].  
+    pub fn from_slice(slice: &[u8]) -> Self {
+        Self(slice.to_vec())
+    }
+
+    /// Get amended network specs [`NetworkSpecsToSend`] from
+    /// [`ContentAmendSpecs`].  
+    pub fn specs(&self) -> Result<NetworkSpecsToSend> {
+        NetworkSpecsToSend::from_stored(&self.0)
+    }
+
+    /// Write [`ContentAmendSpecs`] into file that could be signed by the verifier.  
+    #[cfg(feature = "active")]
+    pub fn write<P>(&self, path: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(std::fs::write(path, &self.to_sign())?)
+    }
+
+    /// Transform [`ContentAmendSpecs`] into `Vec<u8>` that could be signed by the verifier.  
+    pub fn to_sign(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    /// Transform [`ContentAmendSpecs`] into `Vec<u8>` that is concatenated with
+    /// other parts of the QR code.  
+    ///
+    /// Same as in [`ContentAddSpecs`], already SCALE-encoded content is encoded
+    /// second time as an opaque `Vec<u8>`.  
+    pub fn to_transfer(&self) -> Vec<u8> {
+        self.encode()
+    }
+}
+
 /// Derivations import QR code content  
 ///
 /// Derivations import could be used to generate or to restore a set of
@@ -294,3 +546,229 @@ impl ContentDerivations {
         self.0.to_vec()
     }
 }
+
+/// Compression algorithm used in compressed update
+#[derive(Clone, Copy, Debug, Decode, Encode, PartialEq, Eq)]
+pub enum Compression {
+    /// `zstd` compression
+    Zstd,
+
+    /// `brotli` compression
+    Brotli,
+}
+
+impl Compression {
+    /// Display [`Compression`] in readable form
+    pub fn show(&self) -> String {
+        match &self {
+            Compression::Zstd => String::from("zstd"),
+            Compression::Brotli => String::from("brotli"),
+        }
+    }
+}
+
+/// Update content type wrapped into compressed update
+#[derive(Clone, Copy, Debug, Decode, Encode, PartialEq, Eq)]
+pub enum CompressedContentType {
+    /// [`ContentAddSpecs`] in `to_sign` form
+    AddSpecs,
+
+    /// [`ContentLoadMeta`] in `to_sign` form
+    LoadMeta,
+
+    /// [`ContentLoadTypes`] in `to_sign` form
+    LoadTypes,
+
+    /// [`ContentAddContacts`] in `to_sign` form
+    AddContacts,
+
+    /// [`ContentRotateVerifier`] in `to_sign` form
+    RotateVerifier,
+
+    /// [`ContentSecurityPolicy`] in `to_sign` form
+    SecurityPolicy,
+
+    /// [`ContentAmendSpecs`] in `to_sign` form
+    AmendSpecs,
+}
+
+impl From<CompressedContentType> for TransferContent {
+    fn from(content_type: CompressedContentType) -> Self {
+        match content_type {
+            CompressedContentType::AddSpecs => TransferContent::AddSpecs,
+            CompressedContentType::LoadMeta => TransferContent::LoadMeta,
+            CompressedContentType::LoadTypes => TransferContent::LoadTypes,
+            CompressedContentType::AddContacts => TransferContent::AddContacts,
+            CompressedContentType::RotateVerifier => TransferContent::RotateVerifier,
+            CompressedContentType::SecurityPolicy => TransferContent::SecurityPolicy,
+            CompressedContentType::AmendSpecs => TransferContent::AmendSpecs,
+        }
+    }
+}
+
+/// Compressed update QR code content  
+///
+/// Contains `add_specs`, `load_metadata`, `load_types`, `add_contacts`,
+/// `rotate_verifier`, `security_policy` or `amend_specs` update content in
+/// `to_sign` form, compressed with one of supported
+/// [`Compression`] algorithms. Metadata compresses well, so compressed `load_metadata`
+/// updates are considerably shorter than the raw ones.
+///
+/// Update signature, if any, is produced for uncompressed content, exactly as
+/// in uncompressed update.
+#[derive(Decode, Encode)]
+pub struct ContentCompressed(Vec<u8>);
+
+#[derive(Decode, Encode)]
+struct DecodedContentCompressed {
+    content_type: CompressedContentType,
+    compression: Compression,
+    uncompressed_length: u32,
+    compressed: Vec<u8>,
+}
+
+impl ContentCompressed {
+    /// Generate [`ContentCompressed`] from update content in `to_sign` form,
+    /// its [`CompressedContentType`], and [`Compression`] to use.
+    #[cfg(feature = "active")]
+    pub fn generate(
+        content_type: CompressedContentType,
+        compression: Compression,
+        to_sign: &[u8],
+    ) -> Result<Self> {
+        let compressed = match compression {
+            Compression::Zstd => zstd::stream::encode_all(to_sign, ZSTD_LEVEL)?,
+            Compression::Brotli => {
+                let mut compressed = Vec::new();
+                brotli::CompressorReader::new(
+                    to_sign,
+                    BROTLI_BUFFER_SIZE,
+                    BROTLI_QUALITY,
+                    BROTLI_LGWIN,
+                )
+                .read_to_end(&mut compressed)?;
+                compressed
+            }
+        };
+        Ok(Self(
+            DecodedContentCompressed {
+                content_type,
+                compression,
+                uncompressed_length: to_sign.len() as u32,
+                compressed,
+            }
+            .encode(),
+        ))
+    }
+
+    /// Transform `&[u8]` slice into [`ContentCompressed`].
+    pub fn from_slice(slice: &[u8]) -> Self {
+        Self(slice.to_vec())
+    }
+
+    /// Get [`CompressedContentType`] from [`ContentCompressed`].
+    pub fn content_type(&self) -> Result<CompressedContentType> {
+        Ok(<DecodedContentCompressed>::decode(&mut &self.0[..])?.content_type)
+    }
+
+    /// Get [`CompressedContentType`] and decompressed update content in
+    /// `to_sign` form from [`ContentCompressed`].
+    ///
+    /// Decompressed data is not allowed to exceed `limit` bytes, both as
+    /// declared in the content and as actually produced on decompression, so
+    /// that a small malicious payload could not expand into arbitrary large
+    /// data.
+    pub fn decompress(&self, limit: usize) -> Result<(CompressedContentType, Vec<u8>)> {
+        let a = <DecodedContentCompressed>::decode(&mut &self.0[..])?;
+        if a.uncompressed_length as usize > limit {
+            return Err(Error::DecompressionLimit { limit });
+        }
+        let decompressed = match a.compression {
+            Compression::Zstd => {
+                read_bounded(zstd::stream::read::Decoder::new(&a.compressed[..])?, limit)?
+            }
+            Compression::Brotli => read_bounded(
+                brotli::Decompressor::new(&a.compressed[..], BROTLI_BUFFER_SIZE),
+                limit,
+            )?,
+        };
+        if decompressed.len() != a.uncompressed_length as usize {
+            return Err(Error::DecompressedLengthMismatch {
+                declared: a.uncompressed_length,
+                found: decompressed.len(),
+            });
+        }
+        Ok((a.content_type, decompressed))
+    }
+
+    /// Transform [`ContentCompressed`] into `Vec<u8>` that is concatenated
+    /// with other parts of the QR code.
+    ///
+    /// Already SCALE-encoded content is encoded second time as an opaque
+    /// `Vec<u8>`, to have encoded piece length announced at the beginning of
+    /// the `u8` set, same as in [`ContentAddSpecs`] and [`ContentLoadTypes`].
+    pub fn to_transfer(&self) -> Vec<u8> {
+        self.encode()
+    }
+}
+
+/// `zstd` compression level used for updates, maximum non-ultra level
+#[cfg(feature = "active")]
+const ZSTD_LEVEL: i32 = 19;
+
+/// `brotli` internal buffer size
+const BROTLI_BUFFER_SIZE: usize = 4096;
+
+/// `brotli` compression quality used for updates, maximum quality
+#[cfg(feature = "active")]
+const BROTLI_QUALITY: u32 = 11;
+
+/// `brotli` base 2 logarithm of the sliding window size
+#[cfg(feature = "active")]
+const BROTLI_LGWIN: u32 = 22;
+
+/// Read all data from decompressing reader, but no more than `limit` bytes.
+fn read_bounded<R: Read>(reader: R, limit: usize) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    reader.take(limit as u64 + 1).read_to_end(&mut out)?;
+    if out.len() > limit {
+        return Err(Error::DecompressionLimit { limit });
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+#[cfg(feature = "test")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compressed_content_round_trip() {
+        let to_sign = [b"meta".to_vec(), vec![0; 10000]].concat();
+        for compression in [Compression::Zstd, Compression::Brotli] {
+            let content =
+                ContentCompressed::generate(CompressedContentType::LoadMeta, compression, &to_sign)
+                    .unwrap();
+            assert!(content.to_transfer().len() < to_sign.len());
+            let (content_type, decompressed) = content.decompress(to_sign.len()).unwrap();
+            assert_eq!(content_type, CompressedContentType::LoadMeta);
+            assert_eq!(decompressed, to_sign);
+        }
+    }
+
+    #[test]
+    fn compressed_content_over_limit() {
+        let to_sign = vec![0; 10000];
+        let content = ContentCompressed::generate(
+            CompressedContentType::LoadMeta,
+            Compression::Zstd,
+            &to_sign,
+        )
+        .unwrap();
+        let error = content.decompress(1000).unwrap_err();
+        if let Error::DecompressionLimit { limit: 1000 } = error {
+        } else {
+            panic!("Expected decompression limit error, received {:?}", error);
+        }
+    }
+}
@@ -4,11 +4,21 @@
 //! as a key and encoded [`AddressDetails`] as a value.
 //! [`AddressDetails`] contains non-secret information associated with address key.  
 //!
-//! `ADDRTREE` is operated mainly from within the Signer.
+//! `ADDRTREE` is operated mainly from within the Signer. [`AddressDetails`]
+//! also carry [`KeyUsage`] statistics, updated each time the address key signs
+//! a transaction or a message.
 //!
 //! Release and test versions of the cold database are generated on the Active side.
+//!
+//! Signer database also has a tree `CONTACTS` with account id of the named
+//! contact as a key and encoded [`ContactDetails`] as a value. Contacts are
+//! recipient addresses known to the user, they are not Signer keys.
+//!
+//! User-defined labels, tags and notes for the address keys are stored in a
+//! separate tree `KEY_ANNOTATIONS`, with [`AddressKey`] in key form as a key and
+//! encoded [`KeyAnnotation`] as a value.
 
-use parity_scale_codec::{Decode, Encode};
+use parity_scale_codec::{Decode, DecodeAll, Encode};
 use sled::IVec;
 use sp_runtime::MultiSigner;
 
@@ -17,13 +27,18 @@ use crate::{
     error::{Error, Result},
     helpers::multisigner_to_encryption,
     keyring::{AddressKey, NetworkSpecsKey},
+    network_specs::Verifier,
 };
 
 /// Address key associated non-secret information stored in Signer database  
 ///
 /// Info that should be available for any address key.  
 /// No secrets are stored there.  
-#[derive(Decode, PartialEq, Eq, Encode, Debug, Clone)]
+///
+/// [`AddressDetails`] are stored in the database as SCALE-encoded fields up
+/// to `secret_exposed`, followed by SCALE-encoded [`KeyUsage`] set. Values
+/// stored before the key usage statistics were introduced have no usage set.
+#[derive(PartialEq, Eq, Debug, Clone)]
 pub struct AddressDetails {
     /// seed name (as it is known to the Signer device)  
     pub seed_name: String,
@@ -43,9 +58,56 @@ pub struct AddressDetails {
 
     /// address, or its parent address, had or could have secret exposed
     pub secret_exposed: bool,
+
+    /// usage statistics of the address key, a separate entry for each network
+    /// in which the address key has signed anything  
+    pub usage: Vec<KeyUsage>,
 }
 
 impl AddressDetails {
+    /// Transform [`AddressDetails`] into `Vec<u8>` to put in the database.
+    pub fn store(&self) -> Vec<u8> {
+        let mut out = (
+            &self.seed_name,
+            &self.path,
+            self.has_pwd,
+            &self.network_id,
+            &self.encryption,
+            self.secret_exposed,
+        )
+            .encode();
+        self.usage.encode_to(&mut out);
+        out
+    }
+
+    /// Get [`AddressDetails`] from the value stored in the database.
+    ///
+    /// Values stored before the key usage statistics were introduced are
+    /// processed as well, and have empty usage set. Any data left after the
+    /// usage set is an error.
+    pub fn from_stored(mut address_details_encoded: &[u8]) -> Result<Self> {
+        let seed_name = String::decode(&mut address_details_encoded)?;
+        let path = String::decode(&mut address_details_encoded)?;
+        let has_pwd = bool::decode(&mut address_details_encoded)?;
+        let network_id = <Vec<NetworkSpecsKey>>::decode(&mut address_details_encoded)?;
+        let encryption = Encryption::decode(&mut address_details_encoded)?;
+        let secret_exposed = bool::decode(&mut address_details_encoded)?;
+        let usage = if address_details_encoded.is_empty() {
+            Vec::new()
+        } else {
+            <Vec<KeyUsage>>::decode_all(&mut address_details_encoded)?
+        };
+        Ok(Self {
+            seed_name,
+            path,
+            has_pwd,
+            network_id,
+            encryption,
+            secret_exposed,
+            usage,
+        })
+    }
+
     /// Gets ([`MultiSigner`](https://docs.rs/sp-runtime/6.0.0/sp_runtime/enum.MultiSigner.html),
     /// [`AddressDetails`]) tuple from [`AddressKey`] and associated value from
     /// database tree `ADDRTREE`.  
@@ -56,7 +118,7 @@ impl AddressDetails {
         address_details_encoded: IVec,
     ) -> Result<(MultiSigner, Self)> {
         let multisigner = address_key.multi_signer()?;
-        let address_details = AddressDetails::decode(&mut &address_details_encoded[..])?;
+        let address_details = AddressDetails::from_stored(&address_details_encoded)?;
         if multisigner_to_encryption(&multisigner) != address_details.encryption {
             return Err(Error::EncryptionMismatch {
                 address_key: address_key.to_owned(),
@@ -106,4 +168,193 @@ impl AddressDetails {
     pub fn is_root(&self) -> bool {
         (self.path.is_empty()) && (!self.has_pwd)
     }
+
+    /// Gets [`KeyUsage`] of the address key in the network with given
+    /// [`NetworkSpecsKey`], if the address key has signed anything in this
+    /// network.  
+    pub fn usage(&self, network_specs_key: &NetworkSpecsKey) -> Option<&KeyUsage> {
+        self.usage
+            .iter()
+            .find(|key_usage| &key_usage.network_specs_key == network_specs_key)
+    }
+
+    /// Records signed transaction in [`KeyUsage`] of the address key in the
+    /// network with given [`NetworkSpecsKey`].  
+    ///
+    /// Last nonce is updated only if the transaction nonce is known and is
+    /// higher than the one on record.  
+    pub fn record_transaction(
+        &mut self,
+        network_specs_key: &NetworkSpecsKey,
+        nonce: Option<u64>,
+        timestamp: &str,
+    ) {
+        let key_usage = self.usage_mut(network_specs_key, timestamp);
+        key_usage.transactions_signed = key_usage.transactions_signed.saturating_add(1);
+        if let Some(nonce) = nonce {
+            if key_usage
+                .last_nonce
+                .map_or(true, |last_nonce| nonce > last_nonce)
+            {
+                key_usage.last_nonce = Some(nonce);
+            }
+        }
+    }
+
+    /// Records signed message in [`KeyUsage`] of the address key in the
+    /// network with given [`NetworkSpecsKey`].  
+    pub fn record_message(&mut self, network_specs_key: &NetworkSpecsKey, timestamp: &str) {
+        let key_usage = self.usage_mut(network_specs_key, timestamp);
+        key_usage.messages_signed = key_usage.messages_signed.saturating_add(1);
+    }
+
+    /// Gets mutable [`KeyUsage`] for the network with given
+    /// [`NetworkSpecsKey`] with `last_used` timestamp updated, making new
+    /// entry if the address key was not used in this network before.  
+    fn usage_mut(&mut self, network_specs_key: &NetworkSpecsKey, timestamp: &str) -> &mut KeyUsage {
+        let position = match self
+            .usage
+            .iter()
+            .position(|key_usage| &key_usage.network_specs_key == network_specs_key)
+        {
+            Some(position) => position,
+            None => {
+                self.usage.push(KeyUsage {
+                    network_specs_key: network_specs_key.to_owned(),
+                    transactions_signed: 0,
+                    messages_signed: 0,
+                    first_used: timestamp.to_string(),
+                    last_used: timestamp.to_string(),
+                    last_nonce: None,
+                });
+                self.usage.len() - 1
+            }
+        };
+        let key_usage = &mut self.usage[position];
+        key_usage.last_used = timestamp.to_string();
+        key_usage
+    }
+}
+
+/// Usage statistics of an address key in a single network  
+///
+/// Updated each time the address key signs a transaction or a message.  
+#[derive(Decode, PartialEq, Eq, Encode, Debug, Clone)]
+pub struct KeyUsage {
+    /// network in which the address key was used  
+    pub network_specs_key: NetworkSpecsKey,
+
+    /// number of transactions signed  
+    pub transactions_signed: u32,
+
+    /// number of messages signed  
+    pub messages_signed: u32,
+
+    /// timestamp of the first signing  
+    pub first_used: String,
+
+    /// timestamp of the latest signing  
+    pub last_used: String,
+
+    /// highest nonce found in the signed transactions, if any  
+    pub last_nonce: Option<u64>,
+}
+
+/// Named contact, as it is received in `add_contacts` update or entered by the
+/// user  
+#[derive(Decode, PartialEq, Eq, Encode, Debug, Clone)]
+pub struct ContactEntry {
+    /// account id of the contact address  
+    pub account_id: [u8; 32],
+
+    /// contact name, as it is shown to the user  
+    pub name: String,
+}
+
+/// Named contact information stored in Signer database  
+///
+/// Contacts are used to annotate the addresses found in parsed transactions.  
+#[derive(Decode, PartialEq, Eq, Encode, Debug, Clone)]
+pub struct ContactDetails {
+    /// contact name, as it is shown to the user  
+    pub name: String,
+
+    /// verifier of the `add_contacts` update that brought the contact into
+    /// Signer, `Verifier { v: None }` for contacts entered manually  
+    pub verifier: Verifier,
+}
+
+impl ContactDetails {
+    /// Gets account id and [`ContactDetails`] tuple from database tree
+    /// `CONTACTS` (key, value) entry.  
+    pub fn process_entry(
+        (account_id, contact_details_encoded): (IVec, IVec),
+    ) -> Result<([u8; 32], Self)> {
+        let account_id: [u8; 32] = account_id
+            .as_ref()
+            .try_into()
+            .map_err(|_| Error::WrongPublicKeyLength)?;
+        let contact_details = ContactDetails::decode(&mut &contact_details_encoded[..])?;
+        Ok((account_id, contact_details))
+    }
+}
+
+/// User-defined label, tags and notes for an address key, stored in Signer
+/// database  
+///
+/// Annotation is not a part of [`AddressDetails`], and is kept only while
+/// the address key itself is in the database.  
+#[derive(Decode, PartialEq, Eq, Encode, Debug, Clone, Default)]
+pub struct KeyAnnotation {
+    /// short user-defined name of the address key  
+    pub label: String,
+
+    /// set of tags, without duplicates  
+    pub tags: Vec<String>,
+
+    /// free-text notes  
+    pub notes: String,
+}
+
+impl KeyAnnotation {
+    /// Gets [`KeyAnnotation`] from database tree `KEY_ANNOTATIONS`
+    /// (key, value) entry.  
+    pub fn process_entry(
+        (address_key_vec, key_annotation_encoded): (IVec, IVec),
+    ) -> Result<(AddressKey, Self)> {
+        let address_key = AddressKey::from_ivec(&address_key_vec);
+        let key_annotation = KeyAnnotation::decode(&mut &key_annotation_encoded[..])?;
+        Ok((address_key, key_annotation))
+    }
+
+    /// Makes tags set from comma-separated user input.  
+    ///
+    /// Tags are trimmed, empty tags and duplicates are dropped.  
+    pub fn tags_from_str(tags: &str) -> Vec<String> {
+        let mut out: Vec<String> = Vec::new();
+        for tag in tags.split(',').map(|tag| tag.trim()) {
+            if !tag.is_empty() && !out.iter().any(|known| known == tag) {
+                out.push(tag.to_string())
+            }
+        }
+        out
+    }
+
+    /// Checks if the annotation has no content and could be removed from the
+    /// database.  
+    pub fn is_empty(&self) -> bool {
+        self.label.is_empty() && self.tags.is_empty() && self.notes.is_empty()
+    }
+
+    /// Checks if the label, any of the tags, or the notes contain the search
+    /// query, case-insensitive.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        self.label.to_lowercase().contains(&query)
+            || self
+                .tags
+                .iter()
+                .any(|tag| tag.to_lowercase().contains(&query))
+            || self.notes.to_lowercase().contains(&query)
+    }
 }
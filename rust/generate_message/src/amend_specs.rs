@@ -0,0 +1,96 @@
+//! Network specs amendment update
+//!
+//! Signer can change display specs (`unit`, `decimals`, `title`, `tokens`,
+//! `logo` and colors) of a known network, keeping the network addresses and
+//! metadata, through updates with `0x53xxc5` prelude and
+//! [`ContentAmendSpecs`](definitions::qr_transfers::ContentAmendSpecs)
+//! content.
+//!
+//! Amendment is accepted by Signer only if signed by the current verifier of
+//! the network.
+use db_handling::{db_transactions::TrDbHot, storage::Batch};
+use definitions::{
+    keyring::NetworkSpecsKey, network_specs::TokenSpecs, qr_transfers::ContentAmendSpecs,
+};
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use crate::helpers::network_specs_from_title;
+use crate::interpret_specs::apply_asset_tokens;
+
+/// Display specs to amend, as received from the command line.
+///
+/// Fields set to `None` are kept unchanged.
+pub struct SpecsAmendment {
+    pub unit: Option<String>,
+    pub decimals: Option<u8>,
+    pub title: Option<String>,
+    pub logo: Option<String>,
+    pub color: Option<String>,
+    pub secondary_color: Option<String>,
+
+    /// Tokens of `pallet_assets`, added to network tokens or replacing the
+    /// tokens with same unit.
+    pub asset_tokens: Vec<TokenSpecs>,
+}
+
+/// Prepare `amend_specs` update payload for network with given address book
+/// title.
+///
+/// Amended network specs are also written into the
+/// [`SPECSTREEPREP`](constants::SPECSTREEPREP) tree of the hot database, so
+/// that the later `add_specs` payloads are consistent with the amendment.
+///
+/// Payload is written into `files_dir` as
+/// `sign_me_amend_specs_<network_name>_<encryption>`.
+pub fn gen_amend_specs<P, Q>(
+    title: &str,
+    amendment: SpecsAmendment,
+    db_path: P,
+    files_dir: Q,
+) -> Result<()>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let old_specs = network_specs_from_title(title, &db_path)?;
+    let mut new_specs = old_specs.to_owned();
+    if let Some(unit) = amendment.unit {
+        new_specs.unit = unit;
+    }
+    if let Some(decimals) = amendment.decimals {
+        new_specs.decimals = decimals;
+    }
+    if let Some(title) = amendment.title {
+        new_specs.title = title;
+    }
+    if let Some(logo) = amendment.logo {
+        new_specs.logo = logo;
+    }
+    if let Some(color) = amendment.color {
+        new_specs.color = color;
+    }
+    if let Some(secondary_color) = amendment.secondary_color {
+        new_specs.secondary_color = secondary_color;
+    }
+    apply_asset_tokens(&mut new_specs.tokens, &amendment.asset_tokens);
+    if old_specs.display_changes(&new_specs).is_empty() {
+        return Err(Error::NoSpecsAmendment(title.to_string()));
+    }
+
+    let mut network_specs_prep_batch = Batch::default();
+    network_specs_prep_batch.insert(
+        NetworkSpecsKey::from_parts(&new_specs.genesis_hash, &new_specs.encryption).key(),
+        new_specs.store(),
+    );
+    TrDbHot::new()
+        .set_network_specs_prep(network_specs_prep_batch)
+        .apply(&db_path)?;
+
+    ContentAmendSpecs::generate(&new_specs).write(files_dir.as_ref().join(format!(
+        "sign_me_amend_specs_{}_{}",
+        new_specs.name,
+        new_specs.encryption.show()
+    )))?;
+    Ok(())
+}
@@ -0,0 +1,155 @@
+//! Complete signed update from Signer-produced `SufficientCrypto`
+use definitions::{
+    crypto::SufficientCrypto,
+    helpers::unhex,
+    qr_transfers::{
+        ContentAddContacts, ContentAddSpecs, ContentAmendSpecs, ContentLoadMeta, ContentLoadTypes,
+        ContentRotateVerifier, ContentSecurityPolicy,
+    },
+};
+use parity_scale_codec::{Decode, Encode};
+use sp_core::{ecdsa, ed25519, sr25519, Pair};
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use crate::make_message::make_message;
+use crate::parser::{Assemble, Make, Msg, Signature, Sufficient, Verifier};
+
+/// Generate signed update from [`SufficientCrypto`] and matching payload.
+///
+/// All payloads ready for signing in `files_dir` are checked, exactly one of
+/// them is expected to have valid signature in [`SufficientCrypto`]. The
+/// update is then generated same way as in `sign` command.
+pub fn assemble_message(assemble: Assemble) -> Result<()> {
+    let sufficient_crypto_encoded = match (&assemble.sufficient_hex, &assemble.sufficient_qr) {
+        (Some(hex), None) => unhex(hex)?,
+        (None, Some(path)) => read_qr(path)?,
+        f => panic!("mutually exclusive flags: {:?}", f),
+    };
+    let sufficient_crypto = <SufficientCrypto>::decode(&mut &sufficient_crypto_encoded[..])?;
+
+    let mut found: Vec<(Msg, String)> = Vec::new();
+    for entry in std::fs::read_dir(&assemble.files_dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let msg = match msg_from_file_name(&file_name) {
+            Some(a) => a,
+            None => continue,
+        };
+        let payload = std::fs::read(entry.path())?;
+        if is_signed(&sufficient_crypto, &to_sign(&msg, &payload)) {
+            found.push((msg, file_name))
+        }
+    }
+
+    let (msg, payload) = match found.len() {
+        0 => {
+            return Err(Error::NoPayloadForSignature {
+                files_dir: assemble.files_dir.to_string_lossy().to_string(),
+            })
+        }
+        1 => found.remove(0),
+        _ => {
+            return Err(Error::SeveralPayloadsForSignature {
+                payloads: found.into_iter().map(|(_, file_name)| file_name).collect(),
+            })
+        }
+    };
+    println!("Payload {} matches provided signature.", payload);
+
+    make_message(Make {
+        msg,
+        payload: payload.into(),
+        goal: assemble.goal,
+        verifier: Verifier {
+            verifier_alice: None,
+            verifier_hex: None,
+            verifier_file: None,
+        },
+        signature: Signature {
+            signature_hex: None,
+            signature_file: None,
+        },
+        sufficient: Sufficient {
+            sufficient_hex: Some(hex::encode(sufficient_crypto.encode())),
+            sufficient_file: None,
+        },
+        crypto: None,
+        compress: assemble.compress,
+        name: assemble.name,
+        files_dir: assemble.files_dir,
+        export_dir: assemble.export_dir,
+    })
+}
+
+/// Payload type, from payload file name as generated by `add-specs`,
+/// `load-metadata`, `load-types`, `add-contacts`, `rotate-verifier` and
+/// `security-policy` commands.
+fn msg_from_file_name(file_name: &str) -> Option<Msg> {
+    if file_name.starts_with("sign_me_add_specs") {
+        Some(Msg::AddSpecs)
+    } else if file_name.starts_with("sign_me_load_metadata") {
+        Some(Msg::LoadMetadata)
+    } else if file_name.starts_with("sign_me_load_types") {
+        Some(Msg::LoadTypes)
+    } else if file_name.starts_with("sign_me_add_contacts") {
+        Some(Msg::AddContacts)
+    } else if file_name.starts_with("sign_me_rotate_verifier") {
+        Some(Msg::RotateVerifier)
+    } else if file_name.starts_with("sign_me_security_policy") {
+        Some(Msg::SecurityPolicy)
+    } else if file_name.starts_with("sign_me_amend_specs") {
+        Some(Msg::AmendSpecs)
+    } else {
+        None
+    }
+}
+
+/// Bytes that are signed for the payload.
+fn to_sign(msg: &Msg, payload: &[u8]) -> Vec<u8> {
+    match msg {
+        Msg::AddSpecs => ContentAddSpecs::from_slice(payload).to_sign(),
+        Msg::LoadMetadata => ContentLoadMeta::from_slice(payload).to_sign(),
+        Msg::LoadTypes => ContentLoadTypes::from_slice(payload).to_sign(),
+        Msg::AddContacts => ContentAddContacts::from_slice(payload).to_sign(),
+        Msg::RotateVerifier => ContentRotateVerifier::from_slice(payload).to_sign(),
+        Msg::SecurityPolicy => ContentSecurityPolicy::from_slice(payload).to_sign(),
+        Msg::AmendSpecs => ContentAmendSpecs::from_slice(payload).to_sign(),
+    }
+}
+
+/// Check that signature in [`SufficientCrypto`] is valid for the message.
+fn is_signed(sufficient_crypto: &SufficientCrypto, message: &[u8]) -> bool {
+    match sufficient_crypto {
+        SufficientCrypto::Ed25519 { public, signature } => {
+            ed25519::Pair::verify(signature, message, public)
+        }
+        SufficientCrypto::Sr25519 { public, signature } => {
+            sr25519::Pair::verify(signature, message, public)
+        }
+        SufficientCrypto::Ecdsa { public, signature } => {
+            ecdsa::Pair::verify(signature, message, public)
+        }
+    }
+}
+
+/// Read static QR code image produced by the Signer.
+///
+/// `SufficientCrypto` QR code is always a static one, with raw bytes content.
+fn read_qr<P>(path: P) -> Result<Vec<u8>>
+where
+    P: AsRef<Path>,
+{
+    let not_decoded = || Error::QrNotDecoded {
+        path: path.as_ref().to_string_lossy().to_string(),
+    };
+    let image = image::open(&path).map_err(|_| not_decoded())?.to_luma8();
+    let mut qr_decoder = quircs::Quirc::new();
+    let codes = qr_decoder.identify(image.width() as usize, image.height() as usize, &image);
+    for code in codes.flatten() {
+        if let Ok(decoded) = code.decode() {
+            return Ok(decoded.payload);
+        }
+    }
+    Err(not_decoded())
+}
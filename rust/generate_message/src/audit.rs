@@ -0,0 +1,47 @@
+//! Verify signed history log audit report exported from Signer
+use definitions::{
+    helpers::{multisigner_to_encryption, multisigner_to_public, unhex},
+    history::{AuditFormat, SignedHistoryAudit},
+    network_specs::VerifierValue,
+};
+use parity_scale_codec::Decode;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+
+/// Verify [`SignedHistoryAudit`] from file with hexadecimal payload, as it is
+/// decoded from the Signer multiframe QR code.
+///
+/// Key that signed the report is printed. If signature is valid and `output`
+/// path is provided, the report text is written there.
+pub fn verify_audit<P, Q>(payload_file: P, output: Option<Q>) -> Result<()>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let hex_payload = std::fs::read_to_string(payload_file)?;
+    let payload = unhex(hex_payload.trim())?;
+    let signed_history_audit = <SignedHistoryAudit>::decode(&mut &payload[..])?;
+
+    let VerifierValue::Standard { m } = signed_history_audit.sufficient_crypto.verifier_value();
+    let signed_by = format!(
+        "public key: {}, encryption: {}",
+        hex::encode(multisigner_to_public(&m)),
+        multisigner_to_encryption(&m).show()
+    );
+    if !signed_history_audit.is_valid() {
+        return Err(Error::AuditSignatureInvalid { signed_by });
+    }
+    let format = match signed_history_audit.format {
+        AuditFormat::Json => "json",
+        AuditFormat::Csv => "csv",
+    };
+    println!(
+        "Audit report in {} format has valid signature, signed by {}.",
+        format, signed_by
+    );
+    if let Some(output) = output {
+        std::fs::write(output, &signed_history_audit.report)?;
+    }
+    Ok(())
+}
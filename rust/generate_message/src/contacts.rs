@@ -0,0 +1,76 @@
+//! Named contacts update
+//!
+//! Signer can accept named contacts, i.e. recipient addresses known to the
+//! user, through updates with `0x53xxc2` prelude and
+//! [`ContentAddContacts`](definitions::qr_transfers::ContentAddContacts)
+//! content. Contacts are used in Signer to annotate addresses in parsed
+//! transactions and to warn about lookalike addresses.
+//!
+//! Contacts are accepted by Signer if signed by its general verifier, or
+//! unsigned if no general verifier is set.
+use definitions::{qr_transfers::ContentAddContacts, users::ContactEntry};
+use sp_core::crypto::{AccountId32, Ss58Codec};
+use std::path::Path;
+
+use crate::error::{Error, Result};
+
+/// Optional header line in contacts source file.
+const CONTACTS_HEADER: &str = "name,address";
+
+/// Prepare `add_contacts` update payload from contacts source file.
+///
+/// Source file has one contact per line, contact name and contact SS58
+/// address, in any network format, separated by the last comma in line.
+/// Empty lines and optional header line are skipped.
+///
+/// Payload is written into `files_dir` as `sign_me_add_contacts`.
+pub fn gen_add_contacts<P, Q>(source: P, files_dir: Q) -> Result<()>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let content = std::fs::read_to_string(source)?;
+    let contacts = contacts_from_source(&content)?;
+    ContentAddContacts::generate(&contacts)
+        .write(files_dir.as_ref().join("sign_me_add_contacts"))?;
+    Ok(())
+}
+
+/// Get contacts from contacts source file content.
+fn contacts_from_source(content: &str) -> Result<Vec<ContactEntry>> {
+    let mut contacts: Vec<ContactEntry> = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || (i == 0 && line == CONTACTS_HEADER) {
+            continue;
+        }
+        let invalid = |reason: &str| Error::ContactInvalid {
+            line: i + 1,
+            reason: reason.to_string(),
+        };
+        let (name, address) = line
+            .rsplit_once(',')
+            .ok_or_else(|| invalid("no comma separating name and address"))?;
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(invalid("empty contact name"));
+        }
+        let account_id = AccountId32::from_ss58check(address.trim())
+            .map_err(|_| invalid("address is not a valid SS58 address"))?;
+        let account_id = <[u8; 32]>::from(account_id);
+        if contacts
+            .iter()
+            .any(|contact| contact.account_id == account_id)
+        {
+            return Err(invalid("duplicated address"));
+        }
+        contacts.push(ContactEntry {
+            account_id,
+            name: name.to_string(),
+        });
+    }
+    if contacts.is_empty() {
+        return Err(Error::NoContacts);
+    }
+    Ok(contacts)
+}
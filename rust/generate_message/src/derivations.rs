@@ -16,16 +16,23 @@
 //! and plain text input of derivations with each valid derivation on its own
 //! line.
 //!
+//! Structured derivations import source, JSON or CSV, names target networks
+//! for each derivation, and a separate derivations import is generated for
+//! each network, see [`prepare_structured_derivations_import`].
+//!
 //! Example command line to generate derivations import QR:
 //!
 //! `$ cargo run derivations -qr --title westend-sr25519 -payload
 //! my_westend_set.txt`
-use db_handling::identities::prepare_derivations_import;
+use db_handling::identities::{
+    prepare_derivations_import, prepare_structured_derivations_import, DerivationsFormat,
+};
+use definitions::qr_transfers::ContentDerivations;
 use qrcode_rtx::make_pretty_qr;
 
 use crate::error::{Error, Result};
 use crate::helpers::get_address_book_entry;
-use crate::parser::{Derivations, Goal};
+use crate::parser::{Derivations, DerivationsInput, Goal};
 
 /// Generate derivations import QR code and/or text file
 ///
@@ -38,17 +45,64 @@ use crate::parser::{Derivations, Goal};
 /// title, for example `westend-sr25519` or `westend-ed25519`. It goes into
 /// `title` field of [`Derivations`], and is processed to get network encryption
 /// and genesis hash.
+///
+/// For structured derivations source, JSON or CSV, the networks are
+/// indicated by address book titles in the source itself, and one derivations
+/// import is generated for each network. All rejected source rows are printed
+/// with rejection reason.
 pub fn process_derivations(x: Derivations) -> Result<()> {
-    // get network information from the database, by network address book title
-    let address_book_entry = get_address_book_entry(&x.title, x.db)?;
+    let content = x.content()?;
+    let format = match x.format {
+        DerivationsInput::Plain => {
+            let title = x.title.ok_or(Error::DerivationsTitleRequired)?;
 
-    // prepare `ContentDerivations`
-    let content = prepare_derivations_import(
-        &address_book_entry.encryption,
-        address_book_entry.genesis_hash,
-        &x.derivations,
-    )?;
+            // get network information from the database, by network address
+            // book title
+            let address_book_entry = get_address_book_entry(&title, &x.db)?;
+
+            // prepare `ContentDerivations`
+            let content = prepare_derivations_import(
+                &address_book_entry.encryption,
+                address_book_entry.genesis_hash,
+                &content,
+            )?;
+            return export_derivations(&content, &title, &x.goal);
+        }
+        DerivationsInput::Json => DerivationsFormat::Json,
+        DerivationsInput::Csv => DerivationsFormat::Csv,
+    };
+
+    let derivations_import = prepare_structured_derivations_import(&content, format)?;
+    for x in derivations_import.rejected.iter() {
+        println!("Rejected row {}: {}.", x.row, x.reason);
+    }
+    if derivations_import.networks.is_empty() {
+        return Err(db_handling::Error::NoValidDerivationToExport.into());
+    }
+
+    // all networks must be known to the hot database before any export is made
+    let mut set: Vec<(String, ContentDerivations, usize)> = Vec::new();
+    for (title, derivations) in derivations_import.networks.iter() {
+        let address_book_entry = get_address_book_entry(title, &x.db)?;
+        set.push((
+            title.to_string(),
+            ContentDerivations::generate(
+                &address_book_entry.encryption,
+                address_book_entry.genesis_hash,
+                derivations,
+            ),
+            derivations.len(),
+        ))
+    }
+    for (title, content, number) in set.iter() {
+        println!("Found and used {} valid derivations for {}.", number, title);
+        export_derivations(content, title, &x.goal)?;
+    }
+    Ok(())
+}
 
+/// Export derivations import for the network with given address book title.
+fn export_derivations(content: &ContentDerivations, title: &str, goal: &Goal) -> Result<()> {
     // prelude, always identical
     let prelude = hex::decode("53ffde").expect("known static value");
 
@@ -56,8 +110,8 @@ pub fn process_derivations(x: Derivations) -> Result<()> {
     let complete_message = [prelude, content.to_transfer()].concat();
 
     // output file name in `/generate_message/` folder
-    let output_name = format!("derivations-{}", x.title);
-    match x.goal {
+    let output_name = format!("derivations-{}", title);
+    match goal {
         Goal::Qr => make_pretty_qr(&complete_message, &output_name).map_err(Error::Qr)?,
         Goal::Text => std::fs::write(
             &format!("{}.txt", output_name),
@@ -18,6 +18,9 @@ pub enum Error {
     #[error(transparent)]
     Definitions(#[from] definitions::error::Error),
 
+    #[error(transparent)]
+    Defaults(#[from] defaults::Error),
+
     #[error(transparent)]
     Sled(#[from] sled::Error),
 
@@ -328,6 +331,81 @@ pub enum Error {
 
     #[error("Unexpected signature length.")]
     UnexpectedSignatureLength,
+
+    /// Derivations in `plain` format are imported for a single network, that
+    /// must be specified with `--title` key.
+    #[error("Network address book title is required for plain text derivations.")]
+    DerivationsTitleRequired,
+
+    /// QR code image could not be decoded as a static QR code.
+    #[error("Could not read static QR code from {path}.")]
+    QrNotDecoded {
+        /// path to QR code image
+        path: String,
+    },
+
+    /// No payload ready for signing matches the provided `SufficientCrypto`.
+    #[error("No payload in {files_dir} matches provided signature.")]
+    NoPayloadForSignature {
+        /// folder with payloads ready for signing
+        files_dir: String,
+    },
+
+    /// More than one payload ready for signing matches the provided
+    /// `SufficientCrypto`.
+    #[error("Several payloads match provided signature: {}.", .payloads.join(", "))]
+    SeveralPayloadsForSignature {
+        /// matching payload file names
+        payloads: Vec<String>,
+    },
+
+    /// Cold release database content does not match the manifest.
+    #[error(
+        "Cold release database does not match manifest: \
+        {missing} line(s) not in manifest, {unexpected} manifest line(s) not in database."
+    )]
+    ColdReleaseManifestMismatch {
+        /// number of lines computed from the database, but not found in the
+        /// manifest
+        missing: usize,
+
+        /// number of lines found in the manifest, but not computed from the
+        /// database
+        unexpected: usize,
+    },
+
+    /// Cold database has consistency issues that were not repaired.
+    #[error("Cold database has {0} unrepaired consistency issue(s).")]
+    ColdDbIssues(usize),
+
+    /// Signature in history log audit report exported from Signer is not
+    /// valid for the report content.
+    #[error("Audit report signature is not valid. Report is signed by {signed_by}.")]
+    AuditSignatureInvalid {
+        /// public key and encryption of the key that declared the signature
+        signed_by: String,
+    },
+
+    /// Line in contacts source file could not be interpreted as contact name
+    /// and SS58 address.
+    #[error("Contacts source line {line} could not be interpreted: {reason}.")]
+    ContactInvalid {
+        /// line number, starting from 1
+        line: usize,
+
+        /// rejection reason
+        reason: String,
+    },
+
+    /// Contacts source file has no contacts.
+    #[error("No contacts found in contacts source file.")]
+    NoContacts,
+    /// Command `amend-specs` does not change any of the network specs.
+    #[error(
+        "No changes in network specs of {0}. At least one of unit, decimals, \
+        title, logo or colors must be amended."
+    )]
+    NoSpecsAmendment(String),
 }
 
 /// Errors on the active side with network specs received through RPC call
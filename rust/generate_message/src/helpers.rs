@@ -1,7 +1,6 @@
 //! Helpers
 use parity_scale_codec::Encode;
 use serde_json::{map::Map, value::Value};
-use sled::Batch;
 use sp_core::H256;
 use std::path::Path;
 use std::{cmp::Ordering, convert::TryInto};
@@ -10,19 +9,22 @@ use constants::{ADDRESS_BOOK, COLOR, METATREE, META_HISTORY, SECONDARY_COLOR, SP
 use db_handling::{
     db_transactions::TrDbHot,
     helpers::{make_batch_clear_tree, open_db, open_tree},
+    storage::Batch,
 };
 use definitions::{
     crypto::Encryption,
     helpers::unhex,
     keyring::{AddressBookKey, MetaKey, NetworkSpecsKey},
     metadata::{AddressBookEntry, MetaHistoryEntry, MetaValues},
-    network_specs::NetworkSpecsToSend,
+    network_specs::{NetworkSpecsToSend, TokenSpecs},
     qr_transfers::{ContentAddSpecs, ContentLoadMeta},
 };
 
 use crate::error::{Changed, Error, NotHexActive, Result, SpecsError};
 use crate::fetch_metadata::{fetch_info, fetch_info_with_network_specs, fetch_meta_at_block};
-use crate::interpret_specs::{check_specs, interpret_properties, TokenFetch};
+use crate::interpret_specs::{
+    apply_asset_tokens, check_specs, interpret_properties, other_tokens, TokenFetch,
+};
 use crate::parser::Token;
 
 /// Get [`AddressBookEntry`] from the database for given address book title.
@@ -133,7 +135,7 @@ where
     let mut network_specs_prep_batch = Batch::default();
     network_specs_prep_batch.insert(
         NetworkSpecsKey::from_parts(&network_specs.genesis_hash, &network_specs.encryption).key(),
-        network_specs.encode(),
+        network_specs.store(),
     );
     let address_book_new_key = AddressBookKey::from_title(&format!(
         "{}-{}",
@@ -689,6 +691,7 @@ pub fn specs_agnostic(
         path_id: format!("//{}", fetch.meta_values.name),
         secondary_color: SECONDARY_COLOR.to_string(),
         title,
+        tokens: new_properties.tokens,
         unit: new_properties.unit,
     })
 }
@@ -882,7 +885,7 @@ fn known_specs_processing(
                 });
             }
         }
-        TokenFetch::Array { .. } => {
+        TokenFetch::Array { tokens, .. } => {
             // override is allowed
             if let Some(token) = optional_token_override {
                 if specs.decimals != token.decimals {
@@ -894,6 +897,21 @@ fn known_specs_processing(
                     update_done = true;
                 }
             }
+
+            // fetched tokens other than the main one are kept in specs,
+            // together with `pallet_assets` tokens set earlier by user
+            let mut fetched_tokens = other_tokens(&tokens, &specs.unit);
+            let asset_tokens: Vec<TokenSpecs> = specs
+                .tokens
+                .iter()
+                .filter(|token| token.asset_id.is_some())
+                .cloned()
+                .collect();
+            apply_asset_tokens(&mut fetched_tokens, &asset_tokens);
+            if specs.tokens != fetched_tokens {
+                specs.tokens = fetched_tokens;
+                update_done = true;
+            }
         }
         TokenFetch::None => {
             // only decimals `0` possible, check that decimals value did not
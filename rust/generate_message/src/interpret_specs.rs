@@ -19,21 +19,28 @@
 //! network is not suitable for use in Signer either.
 //!
 //! Some networks support more than one token, in this case RPC returns an array
-//! of decimals and an array of units, that must have equal length. No mechanism
-//! to automatically choose a token from the fetched set is currently present.
+//! of decimals and an array of units, that must have equal length. By default,
+//! the first fetched token becomes the main network token, and the other
+//! fetched tokens are kept in network specs `tokens` list, so that the Signer
+//! could display balances of these tokens in `orml_tokens` transactions.
 //!
-//! Some other networks have no token at all.
+//! Asset ids of `pallet_assets` tokens are not fetched via RPC call, and are
+//! entered by user in command line sequence with `--token-asset-id` instead.
+//! Asset tokens are kept in the database through later specs fetches.
 //!
-//! For networks with token set or no token, decimals default to `0` and units
-//! default to `UNIT`. For networks with token set there could be a token
-//! override applied by user in command line sequence, to set the decimals and
-//! units manually. Token override is not supported for networks that have a
+//! Some other networks have no token at all. For networks with no token,
+//! decimals default to `0` and units default to `UNIT`.
+//!
+//! For networks with token set there could be a token override applied by user
+//! in command line sequence, to set the main token decimals and units manually.
+//! Fetched tokens with unit different from the override are kept as other
+//! network tokens. Token override is not supported for networks that have a
 //! single token or no token.
 //!
 //! Command line with token override:
 //!
 //! `$ cargo run add-specs -d -u <url_address> --encryption sr25519 --token-decimals <decimals> --token-unit <unit>`
-use definitions::network_specs::NetworkProperties;
+use definitions::network_specs::{NetworkProperties, TokenSpecs};
 use serde_json::{map::Map, value::Value};
 use std::convert::TryInto;
 
@@ -57,24 +64,38 @@ pub fn interpret_properties(
 ) -> Result<NetworkProperties> {
     let base58prefix = base58prefix(x, optional_prefix_from_meta)?;
 
-    let (decimals, unit) = match token(x)? {
+    let (decimals, unit, tokens) = match token(x)? {
         TokenFetch::Single(token) => {
             // single unit value and single decimals value, override impossible
             if optional_token_override.is_some() {
                 return Err(SpecsError::OverrideIgnoredSingle)?;
             }
-            (token.decimals, token.unit)
+            (token.decimals, token.unit, Vec::new())
         }
-        TokenFetch::Array { decimals, unit } => {
-            if let Some(token_override) = optional_token_override {
-                // token override possible and invoked by the user
-                println!("Network supports several tokens. An array of tokenDecimals {} and an array of tokenSymbol {} were fetched. Through override, the decimals value will be set to {} and unit value will be set to {}. To improve this behavior, please file a ticket.", decimals, unit, token_override.decimals, token_override.unit);
-                (token_override.decimals, token_override.unit)
-            } else {
-                // token override is possible, but not called for by the user
-                println!("Network supports several tokens. An array of tokenDecimals {} and an array of tokenSymbol {} were fetched. By default, decimals value will be set to 0, and unit value will be set to UNIT. To override, use --token-decimals <value_decimals> --token-unit <value_unit>. To improve this behavior, please file a ticket.", decimals, unit);
-                (0, String::from("UNIT"))
-            }
+        TokenFetch::Array {
+            decimals,
+            unit,
+            tokens,
+        } => {
+            let main_token = match optional_token_override {
+                Some(token_override) => {
+                    // token override possible and invoked by the user
+                    println!("Network supports several tokens. An array of tokenDecimals {} and an array of tokenSymbol {} were fetched. Through override, the main token decimals value will be set to {} and unit value will be set to {}. Other fetched tokens are kept in network specs.", decimals, unit, token_override.decimals, token_override.unit);
+                    token_override
+                }
+                None => {
+                    // token override is possible, but not called for by the
+                    // user, first fetched token is the main one
+                    let first = &tokens[0];
+                    println!("Network supports several tokens. An array of tokenDecimals {} and an array of tokenSymbol {} were fetched. By default, the main token decimals value will be set to {} and unit value will be set to {}. Other fetched tokens are kept in network specs. To override the main token, use --token-decimals <value_decimals> --token-unit <value_unit>.", decimals, unit, first.decimals, first.unit);
+                    Token {
+                        decimals: first.decimals,
+                        unit: first.unit.to_string(),
+                    }
+                }
+            };
+            let fetched_tokens = other_tokens(&tokens, &main_token.unit);
+            (main_token.decimals, main_token.unit, fetched_tokens)
         }
         TokenFetch::None => {
             // override impossible
@@ -82,16 +103,64 @@ pub fn interpret_properties(
                 return Err(SpecsError::OverrideIgnoredNone)?;
             }
             println!("Network has no token. By default, decimals value will be set to 0, and unit value will be set to UNIT. To improve this behavior, please file a ticket.");
-            (0, String::from("UNIT"))
+            (0, String::from("UNIT"), Vec::new())
         }
     };
     Ok(NetworkProperties {
         base58prefix,
         decimals,
+        tokens,
         unit,
     })
 }
 
+/// Fetched tokens other than the main network token, to be stored in network
+/// specs `tokens` list
+///
+/// Function inputs:
+///
+/// - fetched token set
+/// - unit of the main network token
+pub fn other_tokens(tokens: &[Token], main_unit: &str) -> Vec<TokenSpecs> {
+    tokens
+        .iter()
+        .filter(|token| token.unit != main_unit)
+        .map(|token| TokenSpecs {
+            asset_id: None,
+            decimals: token.decimals,
+            unit: token.unit.to_string(),
+        })
+        .collect()
+}
+
+/// Apply `pallet_assets` tokens entered by user to network specs `tokens` list
+///
+/// Asset token replaces the listed token with the same unit, other asset
+/// tokens are added to the list.
+///
+/// Output is flag indicating if the list has been changed.
+pub fn apply_asset_tokens(tokens: &mut Vec<TokenSpecs>, asset_tokens: &[TokenSpecs]) -> bool {
+    let mut changed = false;
+    for asset_token in asset_tokens {
+        match tokens
+            .iter_mut()
+            .find(|token| token.unit == asset_token.unit)
+        {
+            Some(token) => {
+                if token != asset_token {
+                    *token = asset_token.clone();
+                    changed = true;
+                }
+            }
+            None => {
+                tokens.push(asset_token.clone());
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
 /// Process base58 prefix
 ///
 /// Function inputs:
@@ -176,7 +245,16 @@ pub enum TokenFetch {
     /// Equal size arrays for decimals and units.
     ///
     /// Token override could be applied.
-    Array { decimals: String, unit: String },
+    Array {
+        /// Printed decimals array, to display to user
+        decimals: String,
+
+        /// Printed units array, to display to user
+        unit: String,
+
+        /// Fetched tokens, in order of fetching
+        tokens: Vec<Token>,
+    },
 
     /// No decimals and no unit.
     None,
@@ -189,8 +267,8 @@ enum DecimalsFetch {
 
     /// Decimals array.
     ///
-    /// Associated data is printed array to display to user and array size.
-    Array(String, usize),
+    /// Associated data is printed array to display to user and array values.
+    Array(String, Vec<u8>),
 
     /// No decimals.
     None,
@@ -203,8 +281,8 @@ enum UnitFetch {
 
     /// Units array.
     ///
-    /// Associated data is printed array to display to user and array size.
-    Array(String, usize),
+    /// Associated data is printed array to display to user and array values.
+    Array(String, Vec<String>),
 
     /// No unit.
     None,
@@ -274,8 +352,20 @@ fn decimals(x: &Map<String, Value>) -> Result<DecimalsFetch> {
                         })?
                     }
                 } else {
-                    // decimals are an array with more than one element
-                    Ok(DecimalsFetch::Array(a.to_string(), b.len()))
+                    // decimals are an array with more than one element, each
+                    // element must be a number that fits into `u8`
+                    let mut decimals_set: Vec<u8> = Vec::with_capacity(b.len());
+                    for c in b.iter() {
+                        match c.as_u64().map(|d| d.try_into()) {
+                            Some(Ok(f)) => decimals_set.push(f),
+                            _ => {
+                                return Err(SpecsError::DecimalsFormatNotSupported {
+                                    value: a.to_string(),
+                                })?
+                            }
+                        }
+                    }
+                    Ok(DecimalsFetch::Array(a.to_string(), decimals_set))
                 }
             }
 
@@ -322,8 +412,20 @@ fn unit(x: &Map<String, Value>) -> Result<UnitFetch> {
                         })?
                     }
                 } else {
-                    // units are an array with more than one element
-                    Ok(UnitFetch::Array(a.to_string(), b.len()))
+                    // units are an array with more than one element, each
+                    // element must be a `String`
+                    let mut unit_set = Vec::with_capacity(b.len());
+                    for c in b.iter() {
+                        match c {
+                            Value::String(d) => unit_set.push(d.to_string()),
+                            _ => {
+                                return Err(SpecsError::UnitFormatNotSupported {
+                                    value: a.to_string(),
+                                })?
+                            }
+                        }
+                    }
+                    Ok(UnitFetch::Array(a.to_string(), unit_set))
                 }
             }
 
@@ -352,13 +454,22 @@ fn token(x: &Map<String, Value>) -> Result<TokenFetch> {
             UnitFetch::Array(..) => Err(SpecsError::UnitsArrayDecimalsNot)?,
             UnitFetch::None => Err(SpecsError::DecimalsNoUnit(decimals.to_string()))?,
         },
-        DecimalsFetch::Array(decimals, decimals_array_size) => match unit_fetch {
+        DecimalsFetch::Array(decimals, decimals_set) => match unit_fetch {
             UnitFetch::Single(_) => Err(SpecsError::DecimalsArrayUnitsNot)?,
-            UnitFetch::Array(unit, unit_array_size) => {
-                if decimals_array_size != unit_array_size {
+            UnitFetch::Array(unit, unit_set) => {
+                if decimals_set.len() != unit_set.len() {
                     Err(SpecsError::DecimalsUnitsArrayLength { decimals, unit })?
                 } else {
-                    Ok(TokenFetch::Array { decimals, unit })
+                    let tokens = decimals_set
+                        .into_iter()
+                        .zip(unit_set.into_iter())
+                        .map(|(decimals, unit)| Token { decimals, unit })
+                        .collect();
+                    Ok(TokenFetch::Array {
+                        decimals,
+                        unit,
+                        tokens,
+                    })
                 }
             }
             UnitFetch::None => Err(SpecsError::DecimalsNoUnit(decimals))?,
@@ -474,15 +585,23 @@ mod tests {
         );
         let properties = properties_maybe.unwrap();
         assert!(
-            properties.decimals == 0,
+            properties.decimals == 8,
             "Wrong decimals, got: {}",
             properties.decimals
         );
         assert!(
-            properties.unit == "UNIT",
+            properties.unit == "WND",
             "Wrong unit, got: {}",
             properties.unit
         );
+        assert_eq!(
+            properties.tokens,
+            vec![TokenSpecs {
+                asset_id: None,
+                decimals: 10,
+                unit: "NWND".to_string(),
+            }]
+        );
     }
 
     #[test]
@@ -521,6 +640,14 @@ mod tests {
             "Wrong unit, got: {}",
             properties.unit
         );
+        assert_eq!(
+            properties.tokens,
+            vec![TokenSpecs {
+                asset_id: None,
+                decimals: 10,
+                unit: "NWND".to_string(),
+            }]
+        );
     }
 
     #[test]
@@ -617,4 +744,29 @@ mod tests {
             panic!("Wrong error in mock specs:\n{:?}", properties_error);
         }
     }
+
+    #[test]
+    /// Asset tokens replace listed tokens with same unit
+    fn asset_tokens() {
+        let mut tokens = vec![TokenSpecs {
+            asset_id: None,
+            decimals: 6,
+            unit: "USDt".to_string(),
+        }];
+        let asset_tokens = [
+            TokenSpecs {
+                asset_id: Some(1984),
+                decimals: 6,
+                unit: "USDt".to_string(),
+            },
+            TokenSpecs {
+                asset_id: Some(1337),
+                decimals: 6,
+                unit: "USDC".to_string(),
+            },
+        ];
+        assert!(apply_asset_tokens(&mut tokens, &asset_tokens));
+        assert_eq!(tokens, asset_tokens);
+        assert!(!apply_asset_tokens(&mut tokens, &asset_tokens));
+    }
 }
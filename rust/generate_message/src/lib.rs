@@ -25,14 +25,22 @@
 //! - `load-types`, to load types information (it is used to support the
 //! transactions parsing in networks with legacy metadata, `RuntimeMetadata`
 //! version below `V14`)
+//! - `add-contacts`, to add into the Signer named contacts, i.e. recipient
+//! addresses known to the user, used to annotate addresses in transactions
+//! - `rotate-verifier`, to replace custom network verifier with a new one,
+//! keeping network specs and metadata in the Signer
+//! - `security-policy`, to replace the Signer security policy
+//! - `amend-specs`, to change display specs of a known network, keeping its
+//! addresses and metadata in the Signer
 //!
 //! Updates are assembled as `Vec<u8>` and could be transformed into:
 //!
 //! - `PNG` QR codes, static or dynamic multiframe depending on the data size
 //! - hex-encoded string (for tests)
 //!
-//! Information in `add-specs`, `load-metadata` and `load-types` could be either
-//! signed or unsigned. Using signed updates is strongly encouraged.
+//! Information in `add-specs`, `load-metadata`, `load-types` and
+//! `add-contacts` could be either signed or unsigned. Using signed updates is strongly encouraged.
+//! Updates `rotate-verifier` and `security-policy` must always be signed.
 //!
 //! Update has following general structure:
 //!
@@ -95,8 +103,50 @@
 //!         <td>SCALE encoded <code>Vec&ltTypeEntry&gt</code></td>
 //!         <td>double SCALE encoded <code>Vec&ltTypeEntry&gt</code></td>
 //!     </tr>
+//!     <tr>
+//!         <td><code>add_contacts</code></td>
+//!         <td><code>ContentAddContacts</code></td>
+//!         <td>SCALE encoded <code>Vec&ltContactEntry&gt</code></td>
+//!         <td>double SCALE encoded <code>Vec&ltContactEntry&gt</code></td>
+//!     </tr>
+//!     <tr>
+//!         <td><code>rotate_verifier</code></td>
+//!         <td><code>ContentRotateVerifier</code></td>
+//!         <td>SCALE encoded <code>VerifierRotation</code>: network genesis hash,
+//! current and new <code>VerifierValue</code>, and rotation index</td>
+//!         <td>SCALE encoded <code>Vec&ltu8&gt</code> of data signed</td>
+//!     </tr>
+//!     <tr>
+//!         <td><code>security_policy</code></td>
+//!         <td><code>ContentSecurityPolicy</code></td>
+//!         <td>SCALE encoded <code>SecurityPolicy</code></td>
+//!         <td>SCALE encoded <code>Vec&ltu8&gt</code> of data signed</td>
+//!     </tr>
+//!     <tr>
+//!         <td><code>amend_specs</code></td>
+//!         <td><code>ContentAmendSpecs</code></td>
+//!         <td>SCALE encoded amended <code>NetworkSpecsToSend</code></td>
+//!         <td>double SCALE encoded amended <code>NetworkSpecsToSend</code></td>
+//!     </tr>
+//!     <tr>
+//!         <td>compressed <code>add_specs</code>, <code>load_metadata</code>,
+//! <code>load_types</code>, <code>add_contacts</code>, <code>rotate_verifier</code>,
+//! <code>security_policy</code> or <code>amend_specs</code></td>
+//!         <td><code>ContentCompressed</code></td>
+//!         <td>same as in uncompressed update</td>
+//!         <td>double SCALE encoded compressed content, with content type,
+//! compression algorithm and uncompressed length</td>
+//!     </tr>
 //! </table>
 //!
+//! Payload codes are `c1` for `add_specs`, `80` for `load_metadata`, `81` for
+//! `load_types`, `c2` for `add_contacts`, `c3` for `rotate_verifier`, `c4` for
+//! `security_policy`, `c5` for `amend_specs` and `82` for compressed update of
+//! any of these types. Content of compressed updates could be compressed with
+//! `zstd` or `brotli`. Since the signature is always produced for uncompressed
+//! content, the same signature is valid for compressed and uncompressed
+//! update.
+//!
 //! Note that the update payloads are build in such a way that the length of
 //! the payload always could be easily found, thus allowing to separate update
 //! payload, signature and reserved tail in Signer when accepting the update.
@@ -253,6 +303,44 @@
 //! Block hashes could be useful should silent metadata updates (metadata change
 //! with no version bump) happen again.
 //!
+//! ## Display types information from `SETTREE` tree of the hot database
+//!
+//! `$ cargo run show types`
+//!
+//! Prints types information hash, calculated same way as in Signer, and the
+//! names of all types in the database. Types information is compared with the
+//! default types set from `defaults` crate, types added, missing or changed
+//! compared to the default set are listed.
+//!
+//! ## Display details of a metadata entry from the hot database
+//!
+//! `$ cargo run show metadata-details <NAME> <VERSION>`
+//!
+//! Prints for the metadata entry with given network name and version:
+//!
+//! - hexadecimal metadata hash
+//! - metadata size in bytes
+//! - base58 prefix from the metadata, if any
+//! - `RuntimeMetadata` version
+//! - signed extensions identifiers
+//! - pallets with their indices and call names
+//!
+//! ### Example
+//!
+//! `$ cargo run show metadata-details westend 9230`
+//!
+//! ## Check the hot database for inconsistencies
+//!
+//! `$ cargo run show audit`
+//!
+//! Reports:
+//!
+//! - address book entries with no metadata in the database
+//! - [`META_HISTORY`](constants::META_HISTORY) entries with no corresponding
+//! metadata entry
+//! - network specs with base58 prefix different from the one in the network
+//! metadata
+//!
 //! ## Prepare `add_specs` update payload
 //!
 //! `$ cargo run add-specs [OPTIONS] <-d|-f|-k|-p|-t> <--all|--name <NAME>|--url <ADDRESS>>`
@@ -333,6 +421,12 @@
 //! By default, if no token override in provided, such networks have `0u8` decimals
 //! and `UNIT` unit set up.
 //!
+//! Tokens of `pallet_assets` could be added for a network processed with the
+//! database update (`-p` or `-t` setting keys), with key `--token-asset-id`
+//! followed by `<asset_id>:<decimals>:<unit>`. Key could be repeated. Asset
+//! tokens are kept in the database and in later `add_specs` payloads of the
+//! network.
+//!
 //! Title override could be used when processing an individual network, to set
 //! the title under which the network will be displayed in Signer, should the
 //! `add-specs` payload be accepted. Non-default networks, if the title override
@@ -461,6 +555,10 @@
 //!
 //! `$ cargo run add-specs -d -u wss://acala.polkawallet.io --encryption sr25519 --token-decimals 12 --token-unit ACA --title Acala`
 //!
+//! Add `pallet_assets` token to a known network:
+//!
+//! `$ cargo run add-specs -p --name statemine-sr25519 --token-asset-id 1984:6:USDt`
+//!
 //! ## Prepare `load_metadata` update payload
 //!
 //! `$ cargo run load-metadata [OPTIONS] <-d|-f|-k|-p|-t>`
@@ -682,10 +780,84 @@
 //! (optionally) be signed and later be transformed into `load_types` update QR.
 //! Output file name is `sign_me_load_types`.
 //!
+//! ## Prepare `add_contacts` update payload
+//!
+//! `$ cargo run add-contacts --source <path>`
+//!
+//! Source is a text file with one contact per line, contact name and contact
+//! SS58 address separated by the last comma in line, for example
+//! `Exchange deposit,5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY`. Empty
+//! lines and optional header line `name,address` are skipped.
+//!
+//! A file is generated in dedicated [`FOLDER`](constants::FOLDER) to
+//! (optionally) be signed and later be transformed into `add_contacts` update
+//! QR. Output file name is `sign_me_add_contacts`.
+//!
+//! Signer accepts `add_contacts` updates signed by its general verifier, or
+//! unsigned ones if no general verifier is set.
+//!
+//! ## Prepare `rotate_verifier` update payload
+//!
+//! `$ cargo run rotate-verifier --genesis-hash <hex> --old-verifier <hex>
+//! --old-encryption <encryption> --rotation-index <index> --new-verifier <hex>
+//! --encryption <encryption>`
+//!
+//! Current and new verifier public keys are given as hexadecimal strings, with
+//! encryption `ed25519`, `sr25519` or `ecdsa`.
+//!
+//! Rotation index is the next one after the index of the last rotation for the
+//! network, starting from `1` for the first rotation. Signer accepts each
+//! rotation index only once and only in order, so that old rotations could not
+//! be replayed after the verifier was rotated back.
+//!
+//! A file is generated in dedicated [`FOLDER`](constants::FOLDER) to be signed
+//! and later be transformed into `rotate_verifier` update QR. Output file name
+//! is `sign_me_rotate_verifier`.
+//!
+//! Signer accepts `rotate_verifier` updates only if signed by the current
+//! custom verifier of the network. Network specs and metadata are kept, and
+//! all further updates for the network must be signed by the new verifier.
+//!
+//! ## Prepare `security_policy` update payload
+//!
+//! `$ cargo run security-policy --index <index> [--reject-unsigned-updates]
+//! [--forbid-verifier-change] [--forbid-secret-export]
+//! [--forbid-outdated-metadata] [--require-user-comment]`
+//!
+//! Each flag switches on the corresponding rule, rules not mentioned are
+//! switched off. Policy index must be greater than the index of the policy
+//! currently in force in Signer, otherwise the update is rejected as replayed.
+//!
+//! A file is generated in dedicated [`FOLDER`](constants::FOLDER) to be signed
+//! and later be transformed into `security_policy` update QR. Output file name
+//! is `sign_me_security_policy`.
+//!
+//! Signer accepts `security_policy` updates only if signed by its general
+//! verifier.
+//!
+//! ## Prepare `amend_specs` update payload
+//!
+//! `$ cargo run amend-specs --network <address_book_title> [--unit <unit>]
+//! [--decimals <decimals>] [--title <title>] [--logo <logo>]
+//! [--color <color>] [--secondary-color <color>]`
+//!
+//! Network specs are taken from the hot database entry with given address
+//! book title, and only the fields mentioned are changed. At least one field
+//! must be changed. Amended specs replace the ones in the hot database.
+//!
+//! A file is generated in dedicated [`FOLDER`](constants::FOLDER) to be signed
+//! and later be transformed into `amend_specs` update QR. Output file name is
+//! `sign_me_amend_specs_<network_name>_<encryption>`.
+//!
+//! Signer accepts `amend_specs` updates only if signed by the current verifier
+//! of the network. Network name, base58 prefix and path could not be amended.
+//! Network addresses and metadata are kept.
+//!
 //! ## Generate update QR and/or hexadecimal string file
 //!
-//! Raw `[u8]` update payloads, as prepared by `add_specs`, `load_metadata` or
-//! `load_types` commands get transformed into update QR codes (to be scanned
+//! Raw `[u8]` update payloads, as prepared by `add_specs`, `load_metadata`,
+//! `load_types`, `add_contacts`, `rotate_verifier`, `security_policy` or
+//! `amend_specs` commands get transformed into update QR codes (to be scanned
 //! into the Signer) or textfiles with hexadecimal data (for tests).
 //!
 //! There are two commands for generating updates: `make` and `sign`.
@@ -735,6 +907,22 @@
 //!         <td><code>load-types</code></td>
 //!         <td><code>load_types</code></td>
 //!     </tr>
+//!     <tr>
+//!         <td><code>add-contacts</code></td>
+//!         <td><code>add_contacts</code></td>
+//!     </tr>
+//!     <tr>
+//!         <td><code>rotate-verifier</code></td>
+//!         <td><code>rotate_verifier</code></td>
+//!     </tr>
+//!     <tr>
+//!         <td><code>security-policy</code></td>
+//!         <td><code>security_policy</code></td>
+//!     </tr>
+//!     <tr>
+//!         <td><code>amend-specs</code></td>
+//!         <td><code>amend_specs_&ltnetwork_name&gt-&ltnetwork_encryption&gt</code></td>
+//!     </tr>
 //! </table>
 //!
 //! Names for Alice-signed updates have additional tail
@@ -742,6 +930,9 @@
 //!
 //! Names for unsigned updates have additional tail `_unsigned`.
 //!
+//! Names for compressed updates have additional tail `_<compression>` before
+//! the signature-related tail.
+//!
 //! ### `make` command
 //!
 //! `$ cargo run make <keys> <arguments>`
@@ -786,6 +977,9 @@
 //! - Optional key `-name` followed by path override for export file in
 //! dedicated [`EXPORT_FOLDER`](constants::EXPORT_FOLDER)
 //!
+//! - Optional key `--compress` followed by compression algorithm, `zstd` or
+//! `brotli`, to produce compressed update
+//!
 //! ### `sign` command
 //!
 //! `$ cargo run make <keys> <arguments>`
@@ -898,6 +1092,29 @@
 //! 0146ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47aceef7c58b5f952b6233b8aba5beb6f0000c8ca7f7cc16b7ada7cd45026fc3f3ec2289dd90dab0dfac38dfe3be843231443ddd30a3f3bbabb5cefcd2bbcef908c
 //! --msg load-metadata --payload sign_me_load_metadata_westendV9200`
 //!
+//! ## Complete signed update from `SufficientCrypto`
+//!
+//! `$ cargo run assemble <--sufficient-hex <HEX>|--sufficient-qr <QR_PATH>>
+//! [OPTIONS]`
+//!
+//! [`SufficientCrypto`](definitions::crypto::SufficientCrypto) produced by
+//! the Signer is taken either as a hexadecimal string or as a QR code image.
+//!
+//! Payloads ready for signing in [`FOLDER`](constants::FOLDER), or in the
+//! folder provided with `--files-dir` key, are checked one by one, and the
+//! payload for which the `SufficientCrypto` signature is valid is used to
+//! generate the signed update, same way as in `sign` command. Payload type is
+//! determined from the payload file name, as generated by `add-specs`,
+//! `load-metadata` and `load-types` commands. It is an error if none or more
+//! than one payload matches the signature.
+//!
+//! Keys `--goal`, `--compress`, `--name` and `--export-dir` are same as in
+//! `make` command.
+//!
+//! Example:
+//!
+//! `$ cargo run assemble --goal qr --sufficient-qr sufficient_crypto.png`
+//!
 //! ## Remove a single metadata entry from the `METATREE`
 //!
 //! `$ cargo run remove --name <network_name> --version <metadata_version>`
@@ -968,6 +1185,10 @@
 //! started. This will be done only in Signer itself. Before initialization,
 //! the cold release database could not be used by Signer.
 //!
+//! Manifest of the generated database is written at path provided with
+//! `--manifest` key or, if no path is given, at default path
+//! [`COLD_RELEASE_MANIFEST`](constants::COLD_RELEASE_MANIFEST).
+//!
 //! ## Transfer metadata from hot database to cold release database
 //!
 //! `$ cargo run transfer_meta_to_cold_release <optional path>`
@@ -980,6 +1201,50 @@
 //! database, i.e. the ones having
 //! [`NetworkSpecs`](definitions::network_specs::NetworkSpecs) entry in
 //! [`SPECSTREE`](constants::SPECSTREE).
+//!
+//! Manifest of the updated database is written same way as for
+//! `make-cold-release` command.
+//!
+//! ## Verify cold release database against the manifest
+//!
+//! `$ cargo run verify-cold-release --cold-db <COLD_DB_PATH> --manifest <MANIFEST_PATH>`
+//!
+//! Manifest lists every entry of [`SPECSTREE`](constants::SPECSTREE),
+//! [`VERIFIERS`](constants::VERIFIERS), [`METATREE`](constants::METATREE) and
+//! [`SETTREE`](constants::SETTREE) trees of the cold release database, with
+//! `blake2_256` hash of the entry value, and an overall digest that could be
+//! signed to approve the release. Unlike the sled database bytes, the manifest
+//! depends only on the database content.
+//!
+//! Command recomputes the manifest from the database and compares it with the
+//! manifest file, printing all lines that differ. Any difference results in an
+//! error.
+//!
+//! ## Check cold database consistency
+//!
+//! `$ cargo run check-cold-db --cold-db <COLD_DB_PATH> [--repair]`
+//!
+//! Every cold database entry is decoded, and the references between the
+//! trees are checked: addresses must refer to known network specs, network
+//! specs must have verifier entry, metadata and key annotations must belong
+//! to known networks and addresses, and no temporary transaction entries
+//! should be left.
+//!
+//! All found issues are printed. With `--repair` flag the safe repairs are
+//! applied. Any issue left unrepaired results in an error.
+
+//! ## Verify history log audit report exported from Signer
+//!
+//! `$ cargo run verify-audit --payload-file <PAYLOAD_FILE> --output <OUTPUT>`
+//!
+//! Signer could export a range of its history log as audit report in JSON or
+//! CSV format, signed by one of Signer keys, as a multiframe QR code.
+//! `<PAYLOAD_FILE>` is a file with hexadecimal string decoded from that QR
+//! code.
+//!
+//! Command checks the report signature and prints the key that signed the
+//! report. If the signature is valid and optional `<OUTPUT>` is provided,
+//! report text is written into `<OUTPUT>` file.
 
 //! ## Make derivations import QR and/or hexadecimal string file
 //!
@@ -999,8 +1264,20 @@
 //! suitable derivations will be processed. Processed derivations are also
 //! printed for user to check.
 //!
+//! - Key `--derivations-file` followed by path to the file with derivations,
+//! could be used instead of `--derivations`.
+//!
 //! - Key `--title` followed by network address book title, to indicate to
-//! which network the derivations belong.
+//! which network the derivations belong. Required for `plain` format.
+//!
+//! - Optional key `--format` followed by derivations format: `plain` (default)
+//! for derivations each on its own line, `json` or `csv` for structured
+//! derivations with target networks. JSON format is an array of objects with
+//! `path` derivation and `networks` array of address book titles, CSV format
+//! has lines `<path>,<address book title>[,<address book title>...]`.
+//! Structured derivations are grouped by network, and a separate derivations
+//! import is generated for each network. Each rejected row is printed with the
+//! rejection reason.
 //!
 //! Output file is in `/generate_message/` folder, file name would be
 //! `derivations-<address_book_title>`.
@@ -1064,11 +1341,21 @@
 #![deny(unused)]
 #![deny(rustdoc::broken_intra_doc_links)]
 
+use constants::COLD_DB_NAME_RELEASE;
 use db_handling::{
     default_cold_release, default_hot,
     helpers::{prep_types, transfer_metadata_to_cold},
 };
+use definitions::{policy::SecurityPolicy, qr_transfers::ContentSecurityPolicy};
 
+mod amend_specs;
+use amend_specs::{gen_amend_specs, SpecsAmendment};
+mod assemble;
+use assemble::assemble_message;
+mod audit;
+use audit::verify_audit;
+mod contacts;
+use contacts::gen_add_contacts;
 mod derivations;
 use derivations::process_derivations;
 pub mod fetch_metadata;
@@ -1081,10 +1368,17 @@ mod make_message;
 use make_message::make_message;
 pub mod parser;
 use parser::{Command, Show};
+mod release;
+use release::{check_cold_db, verify_cold_release, write_release_manifest};
 mod remove;
 use remove::remove_info;
+mod rotate_verifier;
+use rotate_verifier::gen_rotate_verifier;
 mod show;
-use show::{check_file, show_block_history, show_metadata, show_networks, show_specs};
+use show::{
+    check_file, show_audit, show_block_history, show_metadata, show_metadata_details,
+    show_networks, show_specs, show_types,
+};
 mod specs;
 use specs::gen_add_specs;
 
@@ -1100,19 +1394,98 @@ pub fn full_run(command: Command) -> Result<()> {
             Show::Specs { s: title } => show_specs(title, db_path),
             Show::CheckFile { s: path } => check_file(path, db_path),
             Show::BlockHistory => show_block_history(db_path),
+            Show::Types => show_types(db_path),
+            Show::MetadataDetails { name, version } => {
+                show_metadata_details(name, version, db_path)
+            }
+            Show::Audit => show_audit(db_path),
         },
         Command::Specs { s: instruction } => gen_add_specs(instruction),
         Command::Load(instruction) => gen_load_meta(instruction),
         Command::Types { db_path, files_dir } => {
             Ok(prep_types(db_path)?.write(files_dir.join("sign_me_load_types"))?)
         }
+        Command::Contacts { source, files_dir } => gen_add_contacts(source, files_dir),
+        Command::RotateVerifier {
+            genesis_hash,
+            old_verifier,
+            old_encryption,
+            rotation_index,
+            new_verifier,
+            encryption,
+            files_dir,
+        } => gen_rotate_verifier(
+            &genesis_hash,
+            (&old_verifier, &old_encryption),
+            (&new_verifier, &encryption),
+            rotation_index,
+            files_dir,
+        ),
+        Command::SecurityPolicy {
+            index,
+            reject_unsigned_updates,
+            forbid_verifier_change,
+            forbid_secret_export,
+            forbid_outdated_metadata,
+            require_user_comment,
+            files_dir,
+        } => Ok(ContentSecurityPolicy::generate(&SecurityPolicy {
+            index,
+            reject_unsigned_updates,
+            forbid_verifier_change,
+            forbid_secret_export,
+            forbid_outdated_metadata,
+            require_user_comment,
+        })
+        .write(files_dir.join("sign_me_security_policy"))?),
+        Command::AmendSpecs {
+            network,
+            unit,
+            decimals,
+            title,
+            logo,
+            color,
+            secondary_color,
+            token_asset_id,
+            db_path,
+            files_dir,
+        } => gen_amend_specs(
+            &network,
+            SpecsAmendment {
+                unit,
+                decimals,
+                title,
+                logo,
+                color,
+                secondary_color,
+                asset_tokens: token_asset_id,
+            },
+            db_path,
+            files_dir,
+        ),
         Command::Sign(make) | Command::Make(make) => make_message(make),
+        Command::Assemble(assemble) => assemble_message(assemble),
         Command::Remove { r: info, db_path } => remove_info(info, db_path),
         Command::RestoreDefaults { db_path } => Ok(default_hot(Some(db_path))?),
-        Command::MakeColdRelease { path } => Ok(default_cold_release(path)?),
-        Command::TransferMetaToColdRelease { cold_db, hot_db } => {
-            Ok(transfer_metadata_to_cold(hot_db, cold_db)?)
+        Command::MakeColdRelease { path, manifest } => {
+            let path = path.unwrap_or_else(|| COLD_DB_NAME_RELEASE.into());
+            default_cold_release(Some(path.clone()))?;
+            write_release_manifest(path, manifest)
+        }
+        Command::TransferMetaToColdRelease {
+            cold_db,
+            hot_db,
+            manifest,
+        } => {
+            transfer_metadata_to_cold(hot_db, cold_db.clone())?;
+            write_release_manifest(cold_db, manifest)
         }
+        Command::VerifyColdRelease { cold_db, manifest } => verify_cold_release(cold_db, manifest),
+        Command::CheckColdDb { cold_db, repair } => check_cold_db(cold_db, repair),
+        Command::VerifyAudit {
+            payload_file,
+            output,
+        } => verify_audit(payload_file, output),
         Command::Derivations(x) => process_derivations(x),
         Command::Unwasm {
             filename,
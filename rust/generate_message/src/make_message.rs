@@ -3,7 +3,11 @@ use constants::ALICE_SEED_PHRASE;
 use definitions::{
     crypto::{Encryption, SufficientCrypto},
     metadata::MetaValues,
-    qr_transfers::{ContentAddSpecs, ContentLoadMeta, ContentLoadTypes},
+    qr_transfers::{
+        CompressedContentType, ContentAddContacts, ContentAddSpecs, ContentAmendSpecs,
+        ContentCompressed, ContentLoadMeta, ContentLoadTypes, ContentRotateVerifier,
+        ContentSecurityPolicy,
+    },
 };
 use qrcode_rtx::make_pretty_qr;
 use sp_core::{ecdsa, ed25519, sr25519, Pair};
@@ -29,42 +33,106 @@ pub fn make_message(make: Make) -> Result<()> {
     //
     // note that bytes signed and bytes added into concatenated update are not
     // necessarily the same
-    let (message_to_verify, message_to_transfer, name_stub, msg_type_code) = match make.msg {
-        Msg::LoadTypes => {
-            let content = ContentLoadTypes::from_slice(&vec);
-            content.types()?;
-            (
-                content.to_sign(),
-                content.to_transfer(),
-                String::from("load_types"),
-                "81",
-            )
-        }
-        Msg::LoadMetadata => {
-            let content = ContentLoadMeta::from_slice(&vec);
-            let meta = content.meta()?;
-            let meta_values = MetaValues::from_slice_metadata(&meta)?;
-            (
-                content.to_sign(),
-                content.to_transfer(),
-                format!("load_metadata_{}V{}", meta_values.name, meta_values.version),
-                "80",
-            )
-        }
-        Msg::AddSpecs => {
-            let content = ContentAddSpecs::from_slice(&vec);
-            let network_specs = content.specs()?;
-            (
-                content.to_sign(),
-                content.to_transfer(),
-                format!(
-                    "add_specs_{}-{}",
-                    network_specs.name,
-                    network_specs.encryption.show()
-                ),
-                "c1",
-            )
-        }
+    let (message_to_verify, message_to_transfer, name_stub, msg_type_code, content_type) =
+        match make.msg {
+            Msg::LoadTypes => {
+                let content = ContentLoadTypes::from_slice(&vec);
+                content.types()?;
+                (
+                    content.to_sign(),
+                    content.to_transfer(),
+                    String::from("load_types"),
+                    "81",
+                    CompressedContentType::LoadTypes,
+                )
+            }
+            Msg::LoadMetadata => {
+                let content = ContentLoadMeta::from_slice(&vec);
+                let meta = content.meta()?;
+                let meta_values = MetaValues::from_slice_metadata(&meta)?;
+                (
+                    content.to_sign(),
+                    content.to_transfer(),
+                    format!("load_metadata_{}V{}", meta_values.name, meta_values.version),
+                    "80",
+                    CompressedContentType::LoadMeta,
+                )
+            }
+            Msg::AddSpecs => {
+                let content = ContentAddSpecs::from_slice(&vec);
+                let network_specs = content.specs()?;
+                (
+                    content.to_sign(),
+                    content.to_transfer(),
+                    format!(
+                        "add_specs_{}-{}",
+                        network_specs.name,
+                        network_specs.encryption.show()
+                    ),
+                    "c1",
+                    CompressedContentType::AddSpecs,
+                )
+            }
+            Msg::AddContacts => {
+                let content = ContentAddContacts::from_slice(&vec);
+                content.contacts()?;
+                (
+                    content.to_sign(),
+                    content.to_transfer(),
+                    String::from("add_contacts"),
+                    "c2",
+                    CompressedContentType::AddContacts,
+                )
+            }
+            Msg::RotateVerifier => {
+                let content = ContentRotateVerifier::from_slice(&vec);
+                content.verifier_rotation()?;
+                (
+                    content.to_sign(),
+                    content.to_transfer(),
+                    String::from("rotate_verifier"),
+                    "c3",
+                    CompressedContentType::RotateVerifier,
+                )
+            }
+            Msg::SecurityPolicy => {
+                let content = ContentSecurityPolicy::from_slice(&vec);
+                content.security_policy()?;
+                (
+                    content.to_sign(),
+                    content.to_transfer(),
+                    String::from("security_policy"),
+                    "c4",
+                    CompressedContentType::SecurityPolicy,
+                )
+            }
+            Msg::AmendSpecs => {
+                let content = ContentAmendSpecs::from_slice(&vec);
+                let network_specs = content.specs()?;
+                (
+                    content.to_sign(),
+                    content.to_transfer(),
+                    format!(
+                        "amend_specs_{}-{}",
+                        network_specs.name,
+                        network_specs.encryption.show()
+                    ),
+                    "c5",
+                    CompressedContentType::AmendSpecs,
+                )
+            }
+        };
+
+    // compress the content if requested; signature is always produced and
+    // checked for uncompressed content
+    let (message_to_transfer, name_stub, msg_type_code) = match make.compress {
+        Some(compression) => (
+            ContentCompressed::generate(content_type, compression, &message_to_verify)?
+                .to_transfer(),
+            format!("{}_{}", name_stub, compression.show()),
+            "82",
+        ),
+        None => (message_to_transfer, name_stub, msg_type_code),
     };
 
     // adding signature (if any) and finalize the message and filename
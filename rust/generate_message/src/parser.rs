@@ -1,8 +1,10 @@
 //! Command line parser for the client
-use constants::{COLD_DB_NAME_RELEASE, EXPORT_FOLDER, FOLDER, HOT_DB_NAME};
+use constants::{COLD_DB_NAME_RELEASE, COLD_RELEASE_MANIFEST, EXPORT_FOLDER, FOLDER, HOT_DB_NAME};
 use definitions::{
     crypto::{Encryption, SufficientCrypto},
     helpers::unhex,
+    network_specs::TokenSpecs,
+    qr_transfers::Compression,
 };
 use sp_core::{ecdsa, ed25519, sr25519};
 use std::{convert::TryInto, path::PathBuf};
@@ -68,12 +70,152 @@ pub enum Command {
         files_dir: PathBuf,
     },
 
+    /// Prepare payload for add-contacts update
+    ///
+    /// Source file has one contact per line, contact name and SS58 address
+    /// separated by the last comma in line.
+    #[clap(name = "add-contacts")]
+    Contacts {
+        /// Path to contacts source file
+        #[clap(long, value_name = "SOURCE_PATH")]
+        source: PathBuf,
+
+        /// Folder to save payloads ready for signing
+        #[clap(long, value_name = "FOLDER_PATH", default_value = FOLDER)]
+        files_dir: PathBuf,
+    },
+
+    /// Prepare payload for rotate-verifier update
+    ///
+    /// Update must be signed by the current custom verifier of the network.
+    #[clap(name = "rotate-verifier")]
+    RotateVerifier {
+        /// Network genesis hash, hexadecimal
+        #[clap(long, value_name = "GENESIS_HASH")]
+        genesis_hash: String,
+
+        /// Current network verifier public key, hexadecimal
+        #[clap(long, value_name = "PUBLIC_KEY")]
+        old_verifier: String,
+
+        /// Current network verifier encryption
+        #[clap(long, value_parser = encryption_from_args)]
+        old_encryption: Encryption,
+
+        /// Rotation index, next after the last rotation for the network,
+        /// starting from 1
+        #[clap(long, value_name = "INDEX")]
+        rotation_index: u32,
+
+        /// New network verifier public key, hexadecimal
+        #[clap(long, value_name = "PUBLIC_KEY")]
+        new_verifier: String,
+
+        /// New network verifier encryption
+        #[clap(long, value_parser = encryption_from_args)]
+        encryption: Encryption,
+
+        /// Folder to save payloads ready for signing
+        #[clap(long, value_name = "FOLDER_PATH", default_value = FOLDER)]
+        files_dir: PathBuf,
+    },
+
+    /// Prepare payload for security-policy update
+    ///
+    /// Update must be signed by the Signer general verifier. Rules not
+    /// mentioned are switched off.
+    #[clap(name = "security-policy")]
+    SecurityPolicy {
+        /// Policy index, must be greater than the index of the policy in force
+        #[clap(long)]
+        index: u32,
+
+        /// Reject unsigned updates
+        #[clap(long)]
+        reject_unsigned_updates: bool,
+
+        /// Forbid changing network and general verifiers
+        #[clap(long)]
+        forbid_verifier_change: bool,
+
+        /// Forbid secret key export
+        #[clap(long)]
+        forbid_secret_export: bool,
+
+        /// Forbid signing with outdated metadata
+        #[clap(long)]
+        forbid_outdated_metadata: bool,
+
+        /// Require user comment on every signature
+        #[clap(long)]
+        require_user_comment: bool,
+
+        /// Folder to save payloads ready for signing
+        #[clap(long, value_name = "FOLDER_PATH", default_value = FOLDER)]
+        files_dir: PathBuf,
+    },
+
+    /// Prepare payload for amend-specs update
+    ///
+    /// Changes display specs of a network already known to the Signer,
+    /// keeping its addresses and metadata. Network specs in the hot database
+    /// are updated as well.
+    ///
+    /// Update must be signed by the current verifier of the network.
+    #[clap(name = "amend-specs")]
+    AmendSpecs {
+        /// Network address book title
+        #[clap(long, value_name = "ADDRESS_BOOK_TITLE")]
+        network: String,
+
+        /// New token unit
+        #[clap(long)]
+        unit: Option<String>,
+
+        /// New token decimals
+        #[clap(long)]
+        decimals: Option<u8>,
+
+        /// New network title, as displayed in the Signer
+        #[clap(long)]
+        title: Option<String>,
+
+        /// New network logo
+        #[clap(long)]
+        logo: Option<String>,
+
+        /// New network color
+        #[clap(long)]
+        color: Option<String>,
+
+        /// New network secondary color
+        #[clap(long)]
+        secondary_color: Option<String>,
+
+        /// Token of `pallet_assets` to add or replace, as
+        /// `<asset_id>:<decimals>:<unit>`
+        #[clap(long, value_name = "ASSET_ID:DECIMALS:UNIT", value_parser = asset_token_from_args)]
+        token_asset_id: Vec<TokenSpecs>,
+
+        /// Path to the hot database
+        #[clap(long = "hot-db-path", value_name = "HOT_DB_PATH", default_value = HOT_DB_NAME)]
+        db_path: PathBuf,
+
+        /// Folder to save payloads ready for signing
+        #[clap(long, value_name = "FOLDER_PATH", default_value = FOLDER)]
+        files_dir: PathBuf,
+    },
+
     /// Complete update generation according
     Make(Make),
 
     /// Sign
     Sign(Make),
 
+    /// Complete signed update from `SufficientCrypto` produced by the Signer,
+    /// searching for the signed payload among the payloads ready for signing
+    Assemble(Assemble),
+
     /// Remove all data associated with a network
     ///
     /// This will remove:
@@ -141,6 +283,10 @@ pub enum Command {
     MakeColdRelease {
         /// Path to release db
         path: Option<PathBuf>,
+
+        /// Path to write the release db manifest to
+        #[clap(long, value_name = "MANIFEST_PATH", default_value = COLD_RELEASE_MANIFEST)]
+        manifest: PathBuf,
     },
 
     /// Transfer metadata from hot database to release cold database
@@ -162,6 +308,52 @@ pub enum Command {
         /// Path to hot db
         #[clap(long, value_name = "HOT_DB_PATH", default_value = HOT_DB_NAME)]
         hot_db: PathBuf,
+
+        /// Path to write the release db manifest to
+        #[clap(long, value_name = "MANIFEST_PATH", default_value = COLD_RELEASE_MANIFEST)]
+        manifest: PathBuf,
+    },
+
+    /// Verify release cold database against the manifest
+    ///
+    /// Manifest is recomputed from the cold release database and compared with
+    /// the manifest file. All differences are printed.
+    VerifyColdRelease {
+        /// Path to release db
+        #[clap(long, value_name = "COLD_DB_PATH", default_value = COLD_DB_NAME_RELEASE)]
+        cold_db: PathBuf,
+
+        /// Path to the release db manifest
+        #[clap(long, value_name = "MANIFEST_PATH", default_value = COLD_RELEASE_MANIFEST)]
+        manifest: PathBuf,
+    },
+
+    /// Check cold database consistency
+    ///
+    /// All found issues are printed. Any issue left unrepaired results in an
+    /// error.
+    #[clap(name = "check-cold-db")]
+    CheckColdDb {
+        /// Path to cold db
+        #[clap(long, value_name = "COLD_DB_PATH", default_value = COLD_DB_NAME_RELEASE)]
+        cold_db: PathBuf,
+
+        /// Apply safe repairs
+        #[clap(long)]
+        repair: bool,
+    },
+
+    /// Verify signature of history log audit report exported from Signer
+    ///
+    /// Payload is hexadecimal string decoded from Signer audit QR code.
+    VerifyAudit {
+        /// File with hexadecimal payload
+        #[clap(long, value_name = "PAYLOAD_FILE")]
+        payload_file: PathBuf,
+
+        /// File to write the verified report into
+        #[clap(long, value_name = "OUTPUT")]
+        output: Option<PathBuf>,
     },
 
     /// Make derivations import QR and/or hexadecimal string file
@@ -285,6 +477,24 @@ pub enum Show {
 
     /// Show all entries from `META_HISTORY` tree
     BlockHistory,
+
+    /// Show types information and compare it with the default types set
+    Types,
+
+    /// Show pallets, calls, signed extensions and other details of the
+    /// metadata entry
+    MetadataDetails {
+        /// Network name
+        #[clap(value_name = "NAME")]
+        name: String,
+
+        /// Network version
+        #[clap(value_name = "VERSION")]
+        version: u32,
+    },
+
+    /// Check the hot database for inconsistencies
+    Audit,
 }
 
 /// Command details for `load-metadata`.
@@ -494,6 +704,10 @@ pub struct Make {
     #[clap(long, name = "crypto", value_parser = encryption_from_args)]
     pub crypto: Option<Encryption>,
 
+    /// compress update content with a specified algorithm
+    #[clap(long, name = "compress", value_parser = compression_from_args)]
+    pub compress: Option<Compression>,
+
     /// output name override
     #[clap(long, name = "name")]
     pub name: Option<PathBuf>,
@@ -566,6 +780,42 @@ impl Make {
     }
 }
 
+/// Data to process `assemble` command.
+#[derive(clap::Args, Debug)]
+#[clap(group(clap::ArgGroup::new("sufficient-crypto")
+                .required(true)
+                .args(&["sufficient-hex", "sufficient-qr"])
+        ))]
+pub struct Assemble {
+    /// `SufficientCrypto` in hex format as command line argument
+    #[clap(long, value_name = "HEX")]
+    pub sufficient_hex: Option<String>,
+
+    /// Path to `SufficientCrypto` QR code image
+    #[clap(long, value_name = "QR_PATH")]
+    pub sufficient_qr: Option<PathBuf>,
+
+    /// target output format
+    #[clap(long, name = "goal", value_parser, default_value_t = Goal::Both)]
+    pub goal: Goal,
+
+    /// compress update content with a specified algorithm
+    #[clap(long, name = "compress", value_parser = compression_from_args)]
+    pub compress: Option<Compression>,
+
+    /// output name override
+    #[clap(long, name = "name")]
+    pub name: Option<PathBuf>,
+
+    /// Folder with payloads ready for signing
+    #[clap(long, default_value = FOLDER)]
+    pub files_dir: PathBuf,
+
+    /// Folder to save completed update messages
+    #[clap(long, default_value = EXPORT_FOLDER)]
+    pub export_dir: PathBuf,
+}
+
 /// Target output format for `derivations`, `make` and `sign` commands.
 #[derive(clap::ValueEnum, Debug, Clone)]
 pub enum Goal {
@@ -667,6 +917,18 @@ pub enum Msg {
 
     /// `add-specs` payload
     AddSpecs,
+
+    /// `add-contacts` payload
+    AddContacts,
+
+    /// `rotate-verifier` payload
+    RotateVerifier,
+
+    /// `security-policy` payload
+    SecurityPolicy,
+
+    /// `amend-specs` payload
+    AmendSpecs,
 }
 
 /// Data to process `remove` command.
@@ -685,25 +947,75 @@ pub enum Remove {
 
 /// Data to process `derivations` command.
 #[derive(clap::Args, Clone, Debug)]
+#[clap(group(clap::ArgGroup::new("derivations-source")
+                .required(true)
+                .args(&["derivations", "derivations-file"])
+        ))]
 pub struct Derivations {
     /// Target output format
     #[clap(long, value_parser)]
     pub goal: Goal,
 
     /// Address book title for network in which addresses with imported
-    /// derivations will be made in Signer
+    /// derivations will be made in Signer, required for `plain` derivations
+    /// format
     #[clap(long)]
-    pub title: String,
+    pub title: Option<String>,
 
     /// Contents of the payload file
     #[clap(long)]
-    pub derivations: String,
+    pub derivations: Option<String>,
+
+    /// Path to the payload file
+    #[clap(long, value_name = "FILE")]
+    pub derivations_file: Option<PathBuf>,
+
+    /// Derivations format
+    #[clap(long, value_parser, default_value_t = DerivationsInput::Plain)]
+    pub format: DerivationsInput,
 
     /// Path to the hot database
     #[clap(long= "hot-db-path", value_name = "HOT_DB_PATH", default_value = HOT_DB_NAME)]
     pub db: PathBuf,
 }
 
+impl Derivations {
+    /// Derivations import source content.
+    pub fn content(&self) -> Result<String> {
+        match (&self.derivations, &self.derivations_file) {
+            (Some(content), None) => Ok(content.to_string()),
+            (None, Some(path)) => Ok(std::fs::read_to_string(path)?),
+            f => panic!("mutually exclusive flags: {:?}", f),
+        }
+    }
+}
+
+/// Derivations format for `derivations` command.
+#[derive(clap::ValueEnum, Clone, Debug)]
+pub enum DerivationsInput {
+    /// Plain text, each derivation on its own line, all derivations are for
+    /// the network from `--title`
+    Plain,
+
+    /// JSON array of objects with `path` and `networks` fields
+    Json,
+
+    /// Lines with derivation path followed by comma-separated network address
+    /// book titles
+    Csv,
+}
+
+impl std::fmt::Display for DerivationsInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            DerivationsInput::Plain => "plain",
+            DerivationsInput::Json => "json",
+            DerivationsInput::Csv => "csv",
+        };
+        write!(f, "{}", s)
+    }
+}
+
 /// Overrides for `add-specs` command.
 #[derive(Args, Debug)]
 pub struct Override {
@@ -739,6 +1051,16 @@ pub struct Override {
     /// arrays of equal size.
     #[clap(long)]
     pub token_unit: Option<String>,
+
+    /// Tokens of `pallet_assets` to display balance in network transactions,
+    /// as `<asset_id>:<decimals>:<unit>`.
+    ///
+    /// Asset ids are not fetched via RPC calls, and could be set only for
+    /// networks processed with the database update, i.e. not with `-f` or `-d`
+    /// setting keys. Asset token replaces the network token with same unit, if
+    /// there is one.
+    #[clap(long, value_name = "ASSET_ID:DECIMALS:UNIT", value_parser = asset_token_from_args)]
+    pub token_asset_id: Vec<TokenSpecs>,
 }
 
 impl Override {
@@ -762,10 +1084,33 @@ fn encryption_from_args(s: &str) -> std::result::Result<Encryption, &'static str
     }
 }
 
+fn asset_token_from_args(s: &str) -> std::result::Result<TokenSpecs, &'static str> {
+    let error = "unexpected asset token, expected `<asset_id>:<decimals>:<unit>`";
+    match s.splitn(3, ':').collect::<Vec<&str>>()[..] {
+        [asset_id, decimals, unit] if !unit.is_empty() => Ok(TokenSpecs {
+            asset_id: Some(asset_id.parse().map_err(|_| error)?),
+            decimals: decimals.parse().map_err(|_| error)?,
+            unit: unit.to_string(),
+        }),
+        _ => Err(error),
+    }
+}
+
+fn compression_from_args(s: &str) -> std::result::Result<Compression, &'static str> {
+    match s {
+        "zstd" => Ok(Compression::Zstd),
+        "brotli" => Ok(Compression::Brotli),
+        _ => Err("unexpected compression type, expected `zstd` or `brotli`"),
+    }
+}
+
 impl Override {
     /// Flag to indicate that no overrides were invoked.
     pub fn all_empty(&self) -> bool {
-        self.encryption.is_none() && self.title.is_none() && self.token().is_none()
+        self.encryption.is_none()
+            && self.title.is_none()
+            && self.token().is_none()
+            && self.token_asset_id.is_empty()
     }
 }
 
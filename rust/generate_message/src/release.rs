@@ -0,0 +1,97 @@
+//! Cold release database manifest
+//!
+//! Manifest lists every entry of the cold release database trees copied into
+//! Signer on build, with entry value hashes and an overall digest, see
+//! [`ReleaseManifest`]. Manifest is written each time the cold release
+//! database is generated or updated, and could be used to verify that the
+//! database bundled into Signer contains exactly the approved data.
+//!
+//! Cold database could also be checked for consistency before it is copied
+//! into Signer.
+use db_handling::{
+    fsck::{check_db, repair_db},
+    release_manifest::ReleaseManifest,
+};
+use std::path::Path;
+
+use crate::error::{Error, Result};
+
+/// Write manifest for the cold release database.
+pub fn write_release_manifest<P, Q>(db_path: P, manifest_path: Q) -> Result<()>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let manifest = ReleaseManifest::from_cold_db(db_path)?;
+    std::fs::write(&manifest_path, manifest.to_text())?;
+    println!(
+        "Cold release manifest with {} entries, digest {}",
+        manifest.entries().len(),
+        hex::encode(manifest.digest())
+    );
+    Ok(())
+}
+
+/// Recompute manifest for the cold release database and compare it with the
+/// manifest file.
+///
+/// All lines that differ are printed. Any difference results in an error.
+pub fn verify_cold_release<P, Q>(db_path: P, manifest_path: Q) -> Result<()>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let computed = ReleaseManifest::from_cold_db(db_path)?.to_text();
+    let on_file = std::fs::read_to_string(manifest_path)?;
+
+    let missing: Vec<&str> = computed
+        .lines()
+        .filter(|x| !on_file.lines().any(|y| &y == x))
+        .collect();
+    let unexpected: Vec<&str> = on_file
+        .lines()
+        .filter(|x| !computed.lines().any(|y| &y == x))
+        .collect();
+
+    if missing.is_empty() && unexpected.is_empty() {
+        println!("Cold release database matches the manifest.");
+        return Ok(());
+    }
+    for x in missing.iter() {
+        println!("not in manifest: {}", x);
+    }
+    for x in unexpected.iter() {
+        println!("not in database: {}", x);
+    }
+    Err(Error::ColdReleaseManifestMismatch {
+        missing: missing.len(),
+        unexpected: unexpected.len(),
+    })
+}
+
+/// Check cold database consistency, and optionally apply safe repairs.
+///
+/// All found issues are printed. Any issue left unrepaired results in an
+/// error.
+pub fn check_cold_db<P>(db_path: P, repair: bool) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    if repair {
+        for x in repair_db(&db_path)?.iter() {
+            println!("repaired: {}", x.show());
+        }
+    }
+    let issues = check_db(&db_path)?;
+    if issues.is_empty() {
+        println!("Cold database is consistent.");
+        return Ok(());
+    }
+    for x in issues.iter() {
+        match x.repair() {
+            Some(repair) => println!("{} (safe repair: {})", x.show(), repair),
+            None => println!("{}", x.show()),
+        }
+    }
+    Err(Error::ColdDbIssues(issues.len()))
+}
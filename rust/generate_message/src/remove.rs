@@ -59,9 +59,9 @@ use constants::{METATREE, META_HISTORY};
 use db_handling::{
     db_transactions::TrDbHot,
     helpers::{get_meta_values_by_name_version, open_db, open_tree},
+    storage::Batch,
 };
 use definitions::keyring::{AddressBookKey, MetaKey, MetaKeyPrefix, NetworkSpecsKey};
-use sled::Batch;
 use std::path::Path;
 
 use crate::error::Result;
@@ -0,0 +1,67 @@
+//! Network verifier rotation update
+//!
+//! Signer can replace custom network verifier with a new one, keeping the
+//! network specs and metadata, through updates with `0x53xxc3` prelude and
+//! [`ContentRotateVerifier`](definitions::qr_transfers::ContentRotateVerifier)
+//! content.
+//!
+//! Rotation is accepted by Signer only if signed by the current custom
+//! verifier of the network, named in the update as the outgoing one, and if
+//! the rotation index is the next one after the last rotation accepted for the
+//! network.
+use definitions::{
+    crypto::Encryption,
+    helpers::unhex,
+    network_specs::VerifierValue,
+    qr_transfers::{ContentRotateVerifier, VerifierRotation},
+};
+use sp_core::{ecdsa, ed25519, sr25519, H256};
+use std::{convert::TryInto, path::Path};
+
+use crate::error::{Error, Result};
+
+/// Prepare `rotate_verifier` update payload.
+///
+/// Genesis hash and verifier public keys are hexadecimal strings, with or
+/// without `0x` prefix.
+///
+/// Payload is written into `files_dir` as `sign_me_rotate_verifier`.
+pub fn gen_rotate_verifier<P>(
+    genesis_hash: &str,
+    old_verifier: (&str, &Encryption),
+    new_verifier: (&str, &Encryption),
+    rotation_index: u32,
+    files_dir: P,
+) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    let genesis_hash: [u8; 32] = unhex(genesis_hash)?
+        .try_into()
+        .map_err(|_| Error::UnexpectedGenesisHashFormat)?;
+    ContentRotateVerifier::generate(&VerifierRotation {
+        genesis_hash: H256::from(genesis_hash),
+        old_verifier_value: verifier_value(old_verifier.0, old_verifier.1)?,
+        new_verifier_value: verifier_value(new_verifier.0, new_verifier.1)?,
+        rotation_index,
+    })
+    .write(files_dir.as_ref().join("sign_me_rotate_verifier"))?;
+    Ok(())
+}
+
+fn verifier_value(public_key: &str, encryption: &Encryption) -> Result<VerifierValue> {
+    let public = unhex(public_key)?;
+    Ok(VerifierValue::Standard {
+        m: match encryption {
+            Encryption::Ed25519 => ed25519::Public::from_raw(into_pubkey(public)?).into(),
+            Encryption::Sr25519 => sr25519::Public::from_raw(into_pubkey(public)?).into(),
+            Encryption::Ecdsa => ecdsa::Public::from_raw(into_pubkey(public)?).into(),
+        },
+    })
+}
+
+fn into_pubkey<const N: usize>(public: Vec<u8>) -> Result<[u8; N]> {
+    public
+        .try_into()
+        .map_err(|e: Vec<_>| Error::PublicKeyWrongLength(N, e.len()))
+}
@@ -1,9 +1,19 @@
 //! Utils to display the database content and to verify default metadata files
-use db_handling::helpers::try_get_meta_values_by_name_version;
+use db_handling::helpers::{
+    get_meta_values_by_name_version, get_types, try_get_meta_values_by_name_version,
+};
+use defaults::default_types_vec;
 use definitions::{
-    metadata::{AddressBookEntry, MetaValues},
+    error::MetadataError,
+    metadata::{runtime_metadata_from_slice, AddressBookEntry, MetaValues},
     network_specs::NetworkSpecsToSend,
+    qr_transfers::ContentLoadTypes,
+};
+use frame_metadata::{
+    decode_different::{DecodeDifferent, DecodeDifferentStr},
+    RuntimeMetadata,
 };
+use scale_info::TypeDef;
 use sp_core::blake2_256;
 use std::path::Path;
 
@@ -213,6 +223,15 @@ where
         specs.title,
         specs.unit
     );
+    for token in specs.tokens.iter() {
+        match token.asset_id {
+            Some(asset_id) => println!(
+                "token: {}, decimals: {}, asset id: {}",
+                token.unit, token.decimals, asset_id
+            ),
+            None => println!("token: {}, decimals: {}", token.unit, token.decimals),
+        }
+    }
     Ok(())
 }
 
@@ -238,3 +257,279 @@ where
     }
     Ok(())
 }
+
+/// Show types information from [`SETTREE`](constants::SETTREE) tree and
+/// compare it with the types information from `defaults` crate.
+///
+/// Function prints the types information hash, calculated same way as in
+/// Signer, the names of all types in the database, and the differences with
+/// the default types set, if any.
+pub fn show_types<P>(db_path: P) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    let types = get_types(db_path)?;
+    println!(
+        "Database has types information for {} types, types hash {}:\n",
+        types.len(),
+        hash_string(&ContentLoadTypes::generate(&types).store())
+    );
+    for x in types.iter() {
+        println!("{}", x.name);
+    }
+
+    let default_types = default_types_vec()?;
+    let not_in_defaults: Vec<&str> = types
+        .iter()
+        .filter(|x| !default_types.iter().any(|y| y.name == x.name))
+        .map(|x| x.name.as_str())
+        .collect();
+    let missing_defaults: Vec<&str> = default_types
+        .iter()
+        .filter(|x| !types.iter().any(|y| y.name == x.name))
+        .map(|x| x.name.as_str())
+        .collect();
+    let different: Vec<&str> = types
+        .iter()
+        .filter(|x| {
+            default_types
+                .iter()
+                .any(|y| y.name == x.name && y.description != x.description)
+        })
+        .map(|x| x.name.as_str())
+        .collect();
+
+    println!();
+    if not_in_defaults.is_empty() && missing_defaults.is_empty() && different.is_empty() {
+        println!("Types information matches the default types set.");
+    } else {
+        print_type_names("Types not in the default types set", &not_in_defaults);
+        print_type_names("Default types missing in the database", &missing_defaults);
+        print_type_names("Types different from the default types set", &different);
+    }
+    Ok(())
+}
+
+/// Print non-empty set of type names, with a header.
+fn print_type_names(header: &str, names: &[&str]) {
+    if !names.is_empty() {
+        println!("{}: {}", header, names.join(", "));
+    }
+}
+
+/// Pallet information, as displayed in metadata details.
+struct PalletDetails {
+    index: u8,
+    name: String,
+    calls: Vec<String>,
+}
+
+/// Show details of the metadata entry with given network name and version
+/// from the hot database [`METATREE`](constants::METATREE) tree.
+///
+/// Function prints:
+///
+/// - network name and version
+/// - hexadecimal metadata hash
+/// - metadata size in bytes
+/// - base58 prefix from `SS58Prefix` constant of `System` pallet, if any
+/// - `RuntimeMetadata` version
+/// - signed extensions identifiers
+/// - pallets with their indices and call names
+///
+/// Metadata entry is expected to be in the database, not finding it results in
+/// an error.
+pub fn show_metadata_details<P>(name: String, version: u32, db_path: P) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    let meta_values = get_meta_values_by_name_version(db_path, &name, version)?;
+    let (runtime_version, pallets, extensions) =
+        metadata_details(&runtime_metadata_from_slice(&meta_values.meta)?)?;
+    let base58prefix = match meta_values.optional_base58prefix {
+        Some(a) => a.to_string(),
+        None => String::from("not in metadata"),
+    };
+    println!(
+        "{} {}\nmetadata hash: {}\nmetadata size: {} bytes\nbase58 prefix: {}\nruntime metadata version: {}\nsigned extensions: {}\npallets:",
+        meta_values.name,
+        meta_values.version,
+        hash_string(&meta_values.meta),
+        meta_values.meta.len(),
+        base58prefix,
+        runtime_version,
+        extensions.join(", "),
+    );
+    for x in pallets.iter() {
+        if x.calls.is_empty() {
+            println!("{} {}, no calls", x.index, x.name);
+        } else {
+            println!("{} {}, calls: {}", x.index, x.name, x.calls.join(", "));
+        }
+    }
+    Ok(())
+}
+
+/// Collect pallets and signed extensions identifiers from `V12` or `V13`
+/// metadata.
+///
+/// Legacy metadata versions have same field layout, but distinct module
+/// types, so the collecting is shared through a macro.
+macro_rules! legacy_metadata_details {
+    ($version:expr, $meta:expr) => {{
+        let mut pallets: Vec<PalletDetails> = Vec::new();
+        if let DecodeDifferent::Decoded(modules) = &$meta.modules {
+            for x in modules.iter() {
+                let calls = match &x.calls {
+                    Some(DecodeDifferent::Decoded(calls)) => {
+                        calls.iter().map(|call| decoded_str(&call.name)).collect()
+                    }
+                    _ => Vec::new(),
+                };
+                pallets.push(PalletDetails {
+                    index: x.index,
+                    name: decoded_str(&x.name),
+                    calls,
+                })
+            }
+        }
+        let extensions = $meta
+            .extrinsic
+            .signed_extensions
+            .iter()
+            .map(decoded_str)
+            .collect();
+        ($version, pallets, extensions)
+    }};
+}
+
+/// Collect `RuntimeMetadata` version, pallets and signed extensions
+/// identifiers from the metadata.
+fn metadata_details(
+    runtime_metadata: &RuntimeMetadata,
+) -> Result<(&'static str, Vec<PalletDetails>, Vec<String>)> {
+    match runtime_metadata {
+        RuntimeMetadata::V12(meta_v12) => Ok(legacy_metadata_details!("V12", meta_v12)),
+        RuntimeMetadata::V13(meta_v13) => Ok(legacy_metadata_details!("V13", meta_v13)),
+        RuntimeMetadata::V14(meta_v14) => {
+            let pallets = meta_v14
+                .pallets
+                .iter()
+                .map(|x| {
+                    let calls = match &x.calls {
+                        Some(calls) => match meta_v14
+                            .types
+                            .resolve(calls.ty.id())
+                            .map(|ty| ty.type_def())
+                        {
+                            Some(TypeDef::Variant(variants)) => variants
+                                .variants()
+                                .iter()
+                                .map(|variant| variant.name().to_string())
+                                .collect(),
+                            _ => Vec::new(),
+                        },
+                        None => Vec::new(),
+                    };
+                    PalletDetails {
+                        index: x.index,
+                        name: x.name.to_string(),
+                        calls,
+                    }
+                })
+                .collect();
+            let extensions = meta_v14
+                .extrinsic
+                .signed_extensions
+                .iter()
+                .map(|x| x.identifier.to_string())
+                .collect();
+            Ok(("V14", pallets, extensions))
+        }
+        _ => Err(MetadataError::VersionIncompatible.into()),
+    }
+}
+
+/// Get string from legacy metadata [`DecodeDifferentStr`].
+fn decoded_str(value: &DecodeDifferentStr) -> String {
+    match value {
+        DecodeDifferent::Encode(a) => a.to_string(),
+        DecodeDifferent::Decoded(a) => a.to_string(),
+    }
+}
+
+/// Check the hot database for inconsistencies.
+///
+/// Function reports:
+///
+/// - address book entries for which there is no metadata in
+/// [`METATREE`](constants::METATREE) tree
+/// - [`META_HISTORY`](constants::META_HISTORY) entries with no corresponding
+/// metadata in [`METATREE`](constants::METATREE) tree
+/// - network specs with base58 prefix different from the one in the network
+/// metadata
+///
+/// Neither of these makes the database unusable, but could indicate that the
+/// database needs updating or cleaning.
+pub fn show_audit<P>(db_path: P) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    let address_book_set = address_book_content(&db_path)?;
+    let meta_values_stamped_set = read_metadata_database(&db_path)?;
+    let meta_history_set = meta_history_content(&db_path)?;
+
+    let mut issues: Vec<String> = Vec::new();
+
+    for (title, address_book_entry) in address_book_set.iter() {
+        if !meta_values_stamped_set
+            .iter()
+            .any(|x| x.meta_values.name == address_book_entry.name)
+        {
+            issues.push(format!(
+                "address book entry {} has no metadata in the database",
+                title
+            ))
+        }
+        let network_specs = network_specs_from_entry(address_book_entry, &db_path)?;
+        for x in meta_values_stamped_set.iter() {
+            if x.meta_values.name != network_specs.name {
+                continue;
+            }
+            if let Some(base58prefix) = x.meta_values.optional_base58prefix {
+                if base58prefix != network_specs.base58prefix {
+                    issues.push(format!(
+                        "network specs for {} have base58 prefix {}, metadata {}{} has base58 prefix {}",
+                        title,
+                        network_specs.base58prefix,
+                        x.meta_values.name,
+                        x.meta_values.version,
+                        base58prefix,
+                    ))
+                }
+            }
+        }
+    }
+
+    for x in meta_history_set.iter() {
+        if !meta_values_stamped_set
+            .iter()
+            .any(|y| y.meta_values.name == x.name && y.meta_values.version == x.version)
+        {
+            issues.push(format!(
+                "block history entry for {}{} has no corresponding metadata in the database",
+                x.name, x.version
+            ))
+        }
+    }
+
+    if issues.is_empty() {
+        println!("No issues found in the hot database.");
+    } else {
+        println!("Hot database has following issues:\n");
+        for x in issues.iter() {
+            println!("{}", x);
+        }
+    }
+    Ok(())
+}
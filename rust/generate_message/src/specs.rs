@@ -3,7 +3,10 @@
 //! This module deals with processing command
 //!
 //! `$ cargo run add-specs <keys> <argument(s)>`
-use definitions::{crypto::Encryption, keyring::NetworkSpecsKey, metadata::AddressBookEntry};
+use definitions::{
+    crypto::Encryption, keyring::NetworkSpecsKey, metadata::AddressBookEntry,
+    network_specs::TokenSpecs,
+};
 use std::path::Path;
 
 use crate::error::{Error, Result};
@@ -13,6 +16,7 @@ use crate::helpers::{
     network_specs_from_title, specs_agnostic, try_get_network_specs_to_send, update_known_specs,
     update_modify_encryption_specs,
 };
+use crate::interpret_specs::apply_asset_tokens;
 use crate::parser::{Content, InstructionSpecs, Override, Set, Token};
 
 /// Process `add-specs` command according to the [`InstructionSpecs`] received
@@ -55,8 +59,9 @@ pub fn gen_add_specs(instruction: InstructionSpecs) -> Result<()> {
             // **will not** be added to the database.
             Content::Name { s: name } => {
                 // no fetch is done, there is no way to check the override is
-                // allowed
-                if instruction.over.token().is_some() {
+                // allowed; asset tokens are set only with database update
+                if instruction.over.token().is_some() || !instruction.over.token_asset_id.is_empty()
+                {
                     return Err(Error::NotSupported);
                 }
                 specs_f_n(
@@ -102,6 +107,10 @@ pub fn gen_add_specs(instruction: InstructionSpecs) -> Result<()> {
             //
             // In some cases the command may contain token override as well.
             Content::Address { s: address } => {
+                // asset tokens are set only with database update
+                if !instruction.over.token_asset_id.is_empty() {
+                    return Err(Error::NotSupported);
+                }
                 // not allowed to proceed without encryption override defined
                 if let Some(ref encryption) = instruction.over.encryption {
                     specs_d_u(
@@ -173,6 +182,7 @@ pub fn gen_add_specs(instruction: InstructionSpecs) -> Result<()> {
                         &address,
                         encryption.clone(),
                         instruction.over.token(),
+                        instruction.over.token_asset_id,
                         instruction.over.title,
                         false,
                         instruction.db,
@@ -230,6 +240,7 @@ pub fn gen_add_specs(instruction: InstructionSpecs) -> Result<()> {
                         &address,
                         encryption.clone(),
                         instruction.over.token(),
+                        instruction.over.token_asset_id,
                         instruction.over.title,
                         true,
                         instruction.db,
@@ -427,6 +438,10 @@ where
         )?,
     };
 
+    // asset tokens are applied after the fetched tokens are checked
+    let make_update = apply_asset_tokens(&mut network_specs_to_change.tokens, &over.token_asset_id)
+        || make_update;
+
     if make_update {
         db_upd_network(
             &address_book_entry.address,
@@ -472,6 +487,7 @@ fn specs_pt_u<P>(
     address: &str,
     encryption: Encryption,
     optional_token_override: Option<Token>,
+    asset_tokens: Vec<TokenSpecs>,
     optional_signer_title_override: Option<String>,
     printing: bool,
     db_path: P,
@@ -489,12 +505,13 @@ where
         });
     }
 
-    let specs = specs_agnostic(
+    let mut specs = specs_agnostic(
         address,
         encryption,
         optional_token_override,
         optional_signer_title_override,
     )?;
+    apply_asset_tokens(&mut specs.tokens, &asset_tokens);
 
     match genesis_hash_in_hot_db(specs.genesis_hash, &db_path)? {
         Some(address_book_entry) => Err(Error::UKeyHashInDb {
@@ -544,6 +561,7 @@ mod tests {
                     title: None,
                     token_unit: None,
                     token_decimals: None,
+                    token_asset_id: Vec::new(),
                 },
                 db: HOT_DB_NAME.into(),
                 files_dir: FOLDER.into(),
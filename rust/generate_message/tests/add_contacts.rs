@@ -0,0 +1,46 @@
+pub mod common;
+use crate::common::{assert_cmd_stdout, base_cmd};
+
+use definitions::{qr_transfers::ContentAddContacts, users::ContactEntry};
+use tempfile::tempdir;
+
+#[test]
+fn it_adds_contacts() {
+    let files_dir = tempdir().unwrap();
+    let source = files_dir.path().join("contacts.csv");
+    std::fs::write(
+        &source,
+        "name,address\nBob, the builder,5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty\n\n",
+    )
+    .unwrap();
+    let cmd = format!(
+        "add-contacts --source {} --files-dir {}",
+        source.to_string_lossy(),
+        files_dir.path().to_string_lossy()
+    );
+    assert_cmd_stdout(&cmd, "");
+
+    let result = std::fs::read(files_dir.path().join("sign_me_add_contacts")).unwrap();
+    let expected = ContentAddContacts::generate(&[ContactEntry {
+        account_id: [
+            142, 175, 4, 21, 22, 135, 115, 99, 38, 201, 254, 161, 126, 37, 252, 82, 135, 97, 54,
+            147, 201, 18, 144, 156, 178, 38, 170, 71, 148, 242, 106, 72,
+        ],
+        name: "Bob, the builder".to_string(),
+    }]);
+    assert_eq!(result, expected.to_sign());
+}
+
+#[test]
+fn it_rejects_invalid_contacts() {
+    let files_dir = tempdir().unwrap();
+    let source = files_dir.path().join("contacts.csv");
+    std::fs::write(&source, "Bob,not an address\n").unwrap();
+
+    base_cmd()
+        .args(&["add-contacts", "--source", source.to_str().unwrap()])
+        .args(&["--files-dir", files_dir.path().to_str().unwrap()])
+        .assert()
+        .failure();
+    assert!(!files_dir.path().join("sign_me_add_contacts").exists());
+}
@@ -0,0 +1,53 @@
+pub mod common;
+use crate::common::{assert_cmd_stdout, base_cmd, setup};
+
+use definitions::{network_specs::NetworkSpecsToSend, qr_transfers::ContentAmendSpecs};
+use generate_message::helpers::network_specs_from_title;
+use tempfile::tempdir;
+
+#[test]
+fn it_amends_specs() {
+    let files_dir = tempdir().unwrap();
+    setup(&files_dir);
+    let old_specs = network_specs_from_title("polkadot", &files_dir).unwrap();
+    let cmd = format!(
+        "amend-specs --network polkadot --unit DOTT --decimals 12 \
+        --hot-db-path {0} --files-dir {0}",
+        files_dir.path().to_string_lossy()
+    );
+    assert_cmd_stdout(&cmd, "");
+
+    let new_specs = NetworkSpecsToSend {
+        unit: String::from("DOTT"),
+        decimals: 12,
+        ..old_specs
+    };
+    let result = std::fs::read(
+        files_dir
+            .path()
+            .join("sign_me_amend_specs_polkadot_sr25519"),
+    )
+    .unwrap();
+    assert_eq!(result, ContentAmendSpecs::generate(&new_specs).to_sign());
+    assert_eq!(
+        network_specs_from_title("polkadot", &files_dir).unwrap(),
+        new_specs
+    );
+}
+
+#[test]
+fn it_rejects_empty_amendment() {
+    let files_dir = tempdir().unwrap();
+    setup(&files_dir);
+
+    base_cmd()
+        .args(&["amend-specs", "--network", "polkadot"])
+        .args(&["--hot-db-path", files_dir.path().to_str().unwrap()])
+        .args(&["--files-dir", files_dir.path().to_str().unwrap()])
+        .assert()
+        .failure();
+    assert!(!files_dir
+        .path()
+        .join("sign_me_amend_specs_polkadot_sr25519")
+        .exists());
+}
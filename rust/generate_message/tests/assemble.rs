@@ -0,0 +1,54 @@
+pub mod common;
+use crate::common::{assert_cmd_stdout, assert_files_eq, setup};
+
+use constants::ALICE_SEED_PHRASE;
+use definitions::{crypto::SufficientCrypto, qr_transfers::ContentLoadMeta};
+use parity_scale_codec::Encode;
+use sp_core::{ed25519, Pair};
+use tempfile::tempdir;
+
+#[test]
+fn it_assembles() {
+    let files_dir = tempdir().unwrap();
+    setup(&files_dir);
+    let cmd = format!(
+        "load-metadata -f -a --hot-db-path {0} --files-dir {0}",
+        files_dir.path().to_string_lossy()
+    );
+    assert_cmd_stdout(&cmd, "");
+
+    let payload =
+        std::fs::read(files_dir.path().join("sign_me_load_metadata_polkadotV30")).unwrap();
+    let ed25519_pair =
+        ed25519::Pair::from_string(&format!("{}//Alice", ALICE_SEED_PHRASE), None).unwrap();
+    let sufficient_crypto = SufficientCrypto::Ed25519 {
+        public: ed25519_pair.public(),
+        signature: ed25519_pair.sign(&ContentLoadMeta::from_slice(&payload).to_sign()),
+    };
+
+    let assemble_cmd = format!(
+        "assemble --goal text --sufficient-hex {1} --files-dir {0} --export-dir {0}",
+        files_dir.path().to_string_lossy(),
+        hex::encode(sufficient_crypto.encode())
+    );
+    assert_cmd_stdout(
+        &assemble_cmd,
+        "Payload sign_me_load_metadata_polkadotV30 matches provided signature.\n",
+    );
+
+    // ed25519 signature is deterministic, same update is made with Alice
+    // verifier directly
+    let sign_cmd = format!(
+        "make --goal text --crypto ed25519 --msg load-metadata --verifier-alice ed25519 \
+        --payload sign_me_load_metadata_polkadotV30 --files-dir {0} --export-dir {0}",
+        files_dir.path().to_string_lossy()
+    );
+    assert_cmd_stdout(&sign_cmd, "");
+
+    assert_files_eq(
+        files_dir.path().join("load_metadata_polkadotV30.txt"),
+        files_dir
+            .path()
+            .join("load_metadata_polkadotV30_Alice-ed25519.txt"),
+    );
+}
@@ -1,11 +1,11 @@
 use constants::TYPES;
+use db_handling::storage::Batch;
 use defaults::{default_types_content, test_metadata};
 use definitions::crypto::Encryption;
 use definitions::keyring::{AddressBookKey, MetaKey, NetworkSpecsKey};
 use definitions::metadata::AddressBookEntry;
 use definitions::network_specs::NetworkSpecsToSend;
 use parity_scale_codec::Encode;
-use sled::Batch;
 use sp_core::H256;
 use std::str::FromStr;
 
@@ -24,6 +24,7 @@ fn test_specs() -> NetworkSpecsToSend {
         path_id: String::from("//polkadot"),
         secondary_color: String::from("#262626"),
         title: String::from("Polkadot"),
+        tokens: Vec::new(),
         unit: String::from("DOT"),
     }
 }
@@ -60,7 +61,7 @@ pub fn network_specs_prep() -> Batch {
     let mut batch = Batch::default();
     batch.insert(
         NetworkSpecsKey::from_parts(&specs.genesis_hash, &specs.encryption).key(),
-        specs.encode(),
+        specs.store(),
     );
     batch
 }
@@ -1,5 +1,5 @@
 pub mod common;
-use crate::common::{assert_cmd_stdout, assert_files_eq, remove_if_exists, setup};
+use crate::common::{assert_cmd_stdout, assert_files_eq, base_cmd, remove_if_exists, setup};
 
 use std::path::PathBuf;
 use tempfile::tempdir;
@@ -19,3 +19,19 @@ fn it_derives() {
     assert_files_eq(&expected_file, &result_file);
     remove_if_exists(&result_file);
 }
+
+#[test]
+fn it_reports_rejected_derivations() {
+    let files_dir = tempdir().unwrap();
+    setup(&files_dir);
+    let derivations_file = files_dir.path().join("derivations.csv");
+    std::fs::write(&derivations_file, "//1\nbad,polkadot\n").unwrap();
+
+    base_cmd()
+        .args(&["derivations", "--goal", "text", "--format", "csv"])
+        .args(&["--derivations-file", derivations_file.to_str().unwrap()])
+        .args(&["--hot-db-path", files_dir.path().to_str().unwrap()])
+        .assert()
+        .failure()
+        .stdout("Rejected row 1: no target networks.\nRejected row 2: invalid derivation path.\n");
+}
@@ -0,0 +1,37 @@
+pub mod common;
+use crate::common::base_cmd;
+
+use tempfile::tempdir;
+
+#[test]
+fn it_verifies_cold_release() {
+    let tmp_dir = tempdir().unwrap();
+    let cold_db = tmp_dir.path().join("cold_release");
+    let manifest = tmp_dir.path().join("cold_release_manifest");
+
+    base_cmd()
+        .args(&["make-cold-release", cold_db.to_str().unwrap()])
+        .args(&["--manifest", manifest.to_str().unwrap()])
+        .assert()
+        .success();
+
+    base_cmd()
+        .arg("verify-cold-release")
+        .args(&["--cold-db", cold_db.to_str().unwrap()])
+        .args(&["--manifest", manifest.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout("Cold release database matches the manifest.\n");
+
+    let content = std::fs::read_to_string(&manifest).unwrap();
+    let mut lines: Vec<&str> = content.lines().collect();
+    lines.remove(0);
+    std::fs::write(&manifest, lines.join("\n")).unwrap();
+
+    base_cmd()
+        .arg("verify-cold-release")
+        .args(&["--cold-db", cold_db.to_str().unwrap()])
+        .args(&["--manifest", manifest.to_str().unwrap()])
+        .assert()
+        .failure();
+}
@@ -0,0 +1,70 @@
+pub mod common;
+use crate::common::{assert_cmd_stdout, base_cmd};
+
+use definitions::{
+    network_specs::VerifierValue,
+    qr_transfers::{ContentRotateVerifier, VerifierRotation},
+};
+use sp_core::{sr25519, H256};
+use std::str::FromStr;
+use tempfile::tempdir;
+
+#[test]
+fn it_rotates_verifier() {
+    let files_dir = tempdir().unwrap();
+    let cmd = format!(
+        "rotate-verifier --genesis-hash 0xe143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e \
+        --old-verifier d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d \
+        --old-encryption sr25519 --rotation-index 2 \
+        --new-verifier 8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48 \
+        --encryption sr25519 --files-dir {}",
+        files_dir.path().to_string_lossy()
+    );
+    assert_cmd_stdout(&cmd, "");
+
+    let result = std::fs::read(files_dir.path().join("sign_me_rotate_verifier")).unwrap();
+    let expected = ContentRotateVerifier::generate(&VerifierRotation {
+        genesis_hash: H256::from_str(
+            "e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e",
+        )
+        .unwrap(),
+        old_verifier_value: VerifierValue::Standard {
+            m: sr25519::Public::from_raw([
+                212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44,
+                133, 88, 133, 76, 205, 227, 154, 86, 132, 231, 165, 109, 162, 125,
+            ])
+            .into(),
+        },
+        new_verifier_value: VerifierValue::Standard {
+            m: sr25519::Public::from_raw([
+                142, 175, 4, 21, 22, 135, 115, 99, 38, 201, 254, 161, 126, 37, 252, 82, 135, 97,
+                54, 147, 201, 18, 144, 156, 178, 38, 170, 71, 148, 242, 106, 72,
+            ])
+            .into(),
+        },
+        rotation_index: 2,
+    });
+    assert_eq!(result, expected.to_sign());
+}
+
+#[test]
+fn it_rejects_short_verifier_key() {
+    let files_dir = tempdir().unwrap();
+
+    base_cmd()
+        .args(&["rotate-verifier", "--encryption", "sr25519"])
+        .args(&["--old-encryption", "sr25519", "--rotation-index", "1"])
+        .args(&[
+            "--old-verifier",
+            "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+        ])
+        .args(&[
+            "--genesis-hash",
+            "e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e",
+        ])
+        .args(&["--new-verifier", "8eaf0415"])
+        .args(&["--files-dir", files_dir.path().to_str().unwrap()])
+        .assert()
+        .failure();
+    assert!(!files_dir.path().join("sign_me_rotate_verifier").exists());
+}
@@ -1,5 +1,15 @@
-use crate::common::{assert_cmd_stdout, setup};
+use crate::common::{assert_cmd_stdout, base_cmd, setup};
+use db_handling::{db_transactions::TrDbHot, storage::Batch};
+use definitions::{
+    crypto::Encryption,
+    keyring::{MetaKey, NetworkSpecsKey},
+    network_specs::NetworkSpecsToSend,
+};
+use parity_scale_codec::Encode;
+use predicates::prelude::*;
+use sp_core::H256;
 use std::path::Path;
+use std::str::FromStr;
 use tempfile::tempdir;
 
 pub mod common;
@@ -74,3 +84,163 @@ unit: DOT\n",
         tmp_dir.path(),
     );
 }
+
+#[test]
+fn it_shows_audit() {
+    let tmp_dir = tempdir().unwrap();
+    run_cmd_test(
+        "show audit",
+        "No issues found in the hot database.\n",
+        tmp_dir.path(),
+    );
+}
+
+/// Set up the test hot database, apply `changes` and run `show audit`.
+fn run_audit_test<P>(changes: TrDbHot, output: &'static str, db_path: P)
+where
+    P: AsRef<Path>,
+{
+    setup(&db_path);
+    changes.apply(&db_path).unwrap();
+    assert_cmd_stdout(
+        &format!(
+            "show audit --hot-db-path {}",
+            db_path.as_ref().to_string_lossy()
+        ),
+        output,
+    );
+}
+
+#[test]
+fn it_shows_audit_missing_metadata() {
+    let tmp_dir = tempdir().unwrap();
+    let mut metadata = Batch::default();
+    metadata.remove(MetaKey::from_parts("polkadot", 30).key());
+    run_audit_test(
+        TrDbHot::new().set_metadata(metadata),
+        "Hot database has following issues:
+
+address book entry polkadot has no metadata in the database\n",
+        tmp_dir.path(),
+    );
+}
+
+#[test]
+fn it_shows_audit_orphaned_block_history() {
+    let tmp_dir = tempdir().unwrap();
+    let mut meta_history = Batch::default();
+    meta_history.insert(
+        MetaKey::from_parts("westend", 9999).key(),
+        H256::from_str("a0b1c2d3e4f5a0b1c2d3e4f5a0b1c2d3e4f5a0b1c2d3e4f5a0b1c2d3e4f5a0b1")
+            .unwrap()
+            .encode(),
+    );
+    run_audit_test(
+        TrDbHot::new().set_meta_history(meta_history),
+        "Hot database has following issues:
+
+block history entry for westend9999 has no corresponding metadata in the database\n",
+        tmp_dir.path(),
+    );
+}
+
+#[test]
+fn it_shows_audit_base58prefix_mismatch() {
+    let tmp_dir = tempdir().unwrap();
+    let genesis_hash =
+        H256::from_str("91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3").unwrap();
+    let specs = NetworkSpecsToSend {
+        base58prefix: 2,
+        color: String::from("#E6027A"),
+        decimals: 10,
+        encryption: Encryption::Sr25519,
+        genesis_hash,
+        logo: String::from("polkadot"),
+        name: String::from("polkadot"),
+        path_id: String::from("//polkadot"),
+        secondary_color: String::from("#262626"),
+        title: String::from("Polkadot"),
+        tokens: Vec::new(),
+        unit: String::from("DOT"),
+    };
+    let mut network_specs_prep = Batch::default();
+    network_specs_prep.insert(
+        NetworkSpecsKey::from_parts(&genesis_hash, &Encryption::Sr25519).key(),
+        specs.store(),
+    );
+    run_audit_test(
+        TrDbHot::new().set_network_specs_prep(network_specs_prep),
+        "Hot database has following issues:
+
+network specs for polkadot have base58 prefix 2, metadata polkadot30 has base58 prefix 0\n",
+        tmp_dir.path(),
+    );
+}
+
+#[test]
+fn it_shows_types() {
+    let tmp_dir = tempdir().unwrap();
+    setup(tmp_dir.path());
+    base_cmd()
+        .args(&[
+            "show",
+            "types",
+            "--hot-db-path",
+            &tmp_dir.path().to_string_lossy(),
+        ])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::starts_with("Database has types information for ").and(
+                predicate::str::ends_with("\nTypes information matches the default types set.\n"),
+            ),
+        );
+}
+
+#[test]
+fn it_shows_metadata_details() {
+    let tmp_dir = tempdir().unwrap();
+    setup(tmp_dir.path());
+    base_cmd()
+        .args(&[
+            "show",
+            "metadata-details",
+            "westend",
+            "9000",
+            "--hot-db-path",
+            &tmp_dir.path().to_string_lossy(),
+        ])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::starts_with(
+                "westend 9000
+metadata hash: e80237ad8b2e92b72fcf6beb8f0e4ba4a21043a7115c844d91d6c4f981e469ce
+metadata size: 139482 bytes
+",
+            )
+            .and(predicate::str::contains(
+                "runtime metadata version: V12
+signed extensions: CheckSpecVersion, CheckTxVersion, CheckGenesis, CheckMortality, CheckNonce, CheckWeight, ChargeTransactionPayment
+pallets:
+0 System, calls: ",
+            )),
+        );
+}
+
+#[test]
+fn it_fails_metadata_details_for_unknown_version() {
+    let tmp_dir = tempdir().unwrap();
+    setup(tmp_dir.path());
+    base_cmd()
+        .args(&[
+            "show",
+            "metadata-details",
+            "westend",
+            "9999",
+            "--hot-db-path",
+            &tmp_dir.path().to_string_lossy(),
+        ])
+        .assert()
+        .failure();
+}
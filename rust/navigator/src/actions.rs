@@ -3,8 +3,12 @@
 //use super::screens::Screen;
 //use crate::navstate::{Navstate, State};
 
+use std::str::FromStr;
+
+use crate::error::Error;
+
 ///All actions
-#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Action {
     Start,
     NavbarLog,
@@ -37,10 +41,15 @@ pub enum Action {
     ViewGeneralVerifier,
     ManageMetadata,
     RemoveKey,
+    SetKeyLabel,
+    SetKeyTags,
+    SetKeyNotes,
     RemoveSeed,
     ClearLog,
     CreateLogComment,
     ShowLogDetails,
+    SearchLog,
+    SearchKeys,
     Swipe,
     LongTap,
     SelectAll,
@@ -51,3 +60,62 @@ pub enum Action {
     PushWord,
     Nothing,
 }
+
+/// Get [`Action`] by its name, as it is spelled in the enum.
+impl FromStr for Action {
+    type Err = Error;
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "Start" => Ok(Action::Start),
+            "NavbarLog" => Ok(Action::NavbarLog),
+            "NavbarScan" => Ok(Action::NavbarScan),
+            "NavbarKeys" => Ok(Action::NavbarKeys),
+            "NavbarSettings" => Ok(Action::NavbarSettings),
+            "GoBack" => Ok(Action::GoBack),
+            "GoForward" => Ok(Action::GoForward),
+            "SelectSeed" => Ok(Action::SelectSeed),
+            "SelectKey" => Ok(Action::SelectKey),
+            "NewKey" => Ok(Action::NewKey),
+            "RightButtonAction" => Ok(Action::RightButtonAction),
+            "Shield" => Ok(Action::Shield),
+            "NewSeed" => Ok(Action::NewSeed),
+            "RecoverSeed" => Ok(Action::RecoverSeed),
+            "BackupSeed" => Ok(Action::BackupSeed),
+            "NetworkSelector" => Ok(Action::NetworkSelector),
+            "NextUnit" => Ok(Action::NextUnit),
+            "PreviousUnit" => Ok(Action::PreviousUnit),
+            "ChangeNetwork" => Ok(Action::ChangeNetwork),
+            "CheckPassword" => Ok(Action::CheckPassword),
+            "TransactionFetched" => Ok(Action::TransactionFetched),
+            "RemoveNetwork" => Ok(Action::RemoveNetwork),
+            "RemoveMetadata" => Ok(Action::RemoveMetadata),
+            "RemoveTypes" => Ok(Action::RemoveTypes),
+            "SignNetworkSpecs" => Ok(Action::SignNetworkSpecs),
+            "SignMetadata" => Ok(Action::SignMetadata),
+            "SignTypes" => Ok(Action::SignTypes),
+            "ManageNetworks" => Ok(Action::ManageNetworks),
+            "ViewGeneralVerifier" => Ok(Action::ViewGeneralVerifier),
+            "ManageMetadata" => Ok(Action::ManageMetadata),
+            "RemoveKey" => Ok(Action::RemoveKey),
+            "SetKeyLabel" => Ok(Action::SetKeyLabel),
+            "SetKeyTags" => Ok(Action::SetKeyTags),
+            "SetKeyNotes" => Ok(Action::SetKeyNotes),
+            "RemoveSeed" => Ok(Action::RemoveSeed),
+            "ClearLog" => Ok(Action::ClearLog),
+            "CreateLogComment" => Ok(Action::CreateLogComment),
+            "ShowLogDetails" => Ok(Action::ShowLogDetails),
+            "SearchLog" => Ok(Action::SearchLog),
+            "SearchKeys" => Ok(Action::SearchKeys),
+            "Swipe" => Ok(Action::Swipe),
+            "LongTap" => Ok(Action::LongTap),
+            "SelectAll" => Ok(Action::SelectAll),
+            "ExportMultiSelect" => Ok(Action::ExportMultiSelect),
+            "Increment" => Ok(Action::Increment),
+            "ShowDocuments" => Ok(Action::ShowDocuments),
+            "TextEntry" => Ok(Action::TextEntry),
+            "PushWord" => Ok(Action::PushWord),
+            "Nothing" => Ok(Action::Nothing),
+            _ => Err(Error::UnknownAction(name.to_string())),
+        }
+    }
+}
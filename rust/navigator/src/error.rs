@@ -13,4 +13,35 @@ pub enum Error {
 
     #[error("DB not initialized.")]
     DbNotInitialized,
+
+    #[error("Navigation session {0} not found.")]
+    SessionNotFound(u32),
+
+    #[error("Unknown action {0}.")]
+    UnknownAction(String),
+
+    #[cfg(any(test, feature = "scenario"))]
+    #[error("Scenario could not be parsed. {0}")]
+    ScenarioFormat(String),
+
+    #[cfg(any(test, feature = "scenario"))]
+    #[error("No golden snapshot at {0}. Run with UPDATE_SCENARIO_SNAPSHOTS=1 to record it.")]
+    ScenarioSnapshotMissing(String),
+
+    #[cfg(any(test, feature = "scenario"))]
+    #[error(
+        "Snapshot differs from golden snapshot {path} at line {line}: \
+        expected {expected:?}, got {found:?}. \
+        Run with UPDATE_SCENARIO_SNAPSHOTS=1 to record the new snapshot."
+    )]
+    ScenarioSnapshotMismatch {
+        path: String,
+        line: usize,
+        expected: String,
+        found: String,
+    },
+
+    #[cfg(any(test, feature = "scenario"))]
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
 }
@@ -1,106 +1,163 @@
 //! This is experimental cross-platform navigation for Signer.
 //! Ideally it should replace almost everything and become the only interface
+//!
+//! There is no global navigation state. App navigation, as well as any number
+//! of independent navigation sessions run in parallel (for example in
+//! different windows of desktop frontend), is a session created with
+//! [`init_session`], returning opaque [`SessionHandle`]. Each session has its
+//! own navigation state, database and seed names. Sessions are accessed
+//! through [`do_session_action`] and [`update_session_seed_names`] and dropped
+//! with [`close_session`].
+//!
+//! Independent [`State`] instances could also be created and driven directly
+//! with [`State::perform`], for example by the scenario runner in
+//! [`scenario`] module (feature `scenario`).
 
 #![deny(unused_crate_dependencies)]
 #![deny(rustdoc::broken_intra_doc_links)]
 
 //do we support mutex?
 use lazy_static::lazy_static;
-use std::sync::{Mutex, TryLockError};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, TryLockError};
 
-use definitions::{keyring::NetworkSpecsKey, navigation::ActionResult};
+use definitions::navigation::ActionResult;
 
 mod error;
+pub use error::{Error, Result};
 
 mod actions;
 pub use actions::Action;
 pub mod alerts;
 pub mod modals;
 mod navstate;
-use navstate::{Navstate, State};
+pub use navstate::{Navstate, State};
+#[cfg(any(test, feature = "scenario"))]
+pub mod scenario;
 pub mod screens;
 #[cfg(test)]
 mod tests;
 
-//TODO: multithread here some day!
 lazy_static! {
-/// Navigation state of the app
+/// Independent navigation sessions, by handle
 ///
 /// Navigation state is unsafe either way, since it has to persist
 /// No matter if here or beyond FFI
-    pub static ref STATE: Mutex<State> = Mutex::new(
-        State{
-            navstate: Navstate::new(),
-            dbname: None,
-            seed_names: Vec::new(),
-            networks: Vec::new(),
-        }
-    );
+    static ref SESSIONS: Mutex<Sessions> = Mutex::new(Sessions::default());
 }
 
-/// User actions handler.
+/// Opaque handle of the navigation session, produced by [`init_session`].
+pub type SessionHandle = u32;
+
+#[derive(Default)]
+struct Sessions {
+    next_handle: SessionHandle,
+    states: HashMap<SessionHandle, Arc<Mutex<State>>>,
+}
+
+/// Recover navigation state after the lock was poisoned by panic in other
+/// thread.
 ///
-/// This method is called on every user [`Action`] in the UI, performs changes in backend
-/// and returns new UI information as [`ActionResult`].
-pub fn do_action(
+/// State could have been left half-updated, so the navigation falls back to
+/// the safe `Log` screen. Database, seed names and networks are kept.
+fn recover_state<'a>(
+    state: &Mutex<State>,
+    poisoned: PoisonError<MutexGuard<'a, State>>,
+) -> MutexGuard<'a, State> {
+    state.clear_poison();
+    let mut guard = poisoned.into_inner();
+    guard.navstate = Navstate::clean_screen(screens::Screen::Log);
+    guard
+}
+
+/// Lock navigation state, waiting for the lock if necessary.
+fn lock_state(state: &Mutex<State>) -> MutexGuard<State> {
+    state
+        .lock()
+        .unwrap_or_else(|poisoned| recover_state(state, poisoned))
+}
+
+/// Perform action on locked navigation state.
+///
+/// If can't lock - debounce failed, ignore action.
+fn perform_on(
+    state: &Mutex<State>,
     action: Action,
     details_str: &str,
     secret_seed_phrase: &str,
-) -> Result<Option<ActionResult>, String> {
-    //If can't lock - debounce failed, ignore action
-    //
+) -> Result<Option<ActionResult>> {
     //guard is defined here to outline lifetime properly
-    let guard = STATE.try_lock();
-    match guard {
-        Ok(mut state) => state
-            .perform(action, details_str, secret_seed_phrase)
-            .map(Some)
-            .map_err(|e| format!("{}", e)),
-        Err(TryLockError::Poisoned(_)) => {
-            //TODO: maybe more grace here?
-            //Maybe just silently restart navstate? But is it safe?
-            panic!("Concurrency error! Restart the app.");
-        }
-        Err(TryLockError::WouldBlock) => Ok(None),
-    }
+    let mut guard = match state.try_lock() {
+        Ok(guard) => guard,
+        Err(TryLockError::Poisoned(poisoned)) => recover_state(state, poisoned),
+        Err(TryLockError::WouldBlock) => return Ok(None),
+    };
+    guard
+        .perform(action, details_str, secret_seed_phrase)
+        .map(Some)
 }
 
-/// Should be called in the beginning to recall things stored only by phone
-pub fn init_navigation(dbname: &str, seed_names: Vec<String>) {
-    //This operation has to happen; lock thread and do not ignore.
-    let guard = STATE.lock();
-    match guard {
-        Ok(mut navstate) => {
-            (*navstate).dbname = Some(dbname.to_string());
-            (*navstate).seed_names = seed_names;
-            match db_handling::helpers::get_all_networks(dbname) {
-                Ok(a) => {
-                    for x in a.iter() {
-                        (*navstate)
-                            .networks
-                            .push(NetworkSpecsKey::from_parts(&x.genesis_hash, &x.encryption));
-                    }
-                }
-                Err(e) => println!("No networks could be fetched: {:?}", e),
-            };
-        }
-        Err(_) => {
-            //TODO: maybe more grace here?
-            panic!("Concurrency error! Restart the app.");
-        }
-    }
+/// Get navigation state of the session.
+fn session(handle: SessionHandle) -> Result<Arc<Mutex<State>>> {
+    SESSIONS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .states
+        .get(&handle)
+        .cloned()
+        .ok_or(Error::SessionNotFound(handle))
 }
 
-/// Should be called when seed names are modified in native to synchronize data
-pub fn update_seed_names(seed_names: Vec<String>) {
-    let guard = STATE.lock();
-    match guard {
-        Ok(mut navstate) => {
-            (*navstate).seed_names = seed_names;
-        }
-        Err(_) => {
-            //TODO: maybe more grace here?
-            panic!("Concurrency error! Restart the app.");
-        }
+/// Start new independent navigation session on the database.
+///
+/// Should be called once at start of the app and could be called on app
+/// reset, with previous session closed.
+///
+/// Returns handle to be used in all further calls for this session.
+///
+/// Fails if the database schema version is newer than supported.
+pub fn init_session(dbname: &str, seed_names: Vec<String>) -> Result<SessionHandle> {
+    let mut state = State::new();
+    state.init_navigation(dbname, seed_names)?;
+    let mut sessions = SESSIONS.lock().unwrap_or_else(PoisonError::into_inner);
+    // handles wrap around, the ones still in use are skipped
+    let mut handle = sessions.next_handle;
+    while sessions.states.contains_key(&handle) {
+        handle = handle.wrapping_add(1);
     }
+    sessions.next_handle = handle.wrapping_add(1);
+    sessions.states.insert(handle, Arc::new(Mutex::new(state)));
+    Ok(handle)
+}
+
+/// User actions handler for the navigation session.
+///
+/// This method is called on every user [`Action`] in the UI, performs changes in backend
+/// and returns new UI information as [`ActionResult`].
+pub fn do_session_action(
+    handle: SessionHandle,
+    action: Action,
+    details_str: &str,
+    secret_seed_phrase: &str,
+) -> Result<Option<ActionResult>> {
+    perform_on(&session(handle)?, action, details_str, secret_seed_phrase)
+}
+
+/// Synchronize seed names of the navigation session.
+///
+/// Should be called when seed names are modified in native.
+pub fn update_session_seed_names(handle: SessionHandle, seed_names: Vec<String>) -> Result<()> {
+    lock_state(&session(handle)?).update_seed_names(seed_names);
+    Ok(())
+}
+
+/// Drop the navigation session. Handle could not be used afterwards.
+pub fn close_session(handle: SessionHandle) -> Result<()> {
+    SESSIONS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .states
+        .remove(&handle)
+        .map(|_| ())
+        .ok_or(Error::SessionNotFound(handle))
 }
@@ -1,31 +1,34 @@
 //! Navigation state of the app
 
-use db_handling::helpers::get_danger_status;
-use db_handling::manage_history::get_history_entry_by_order;
+use db_handling::helpers::get_danger_exposures_in;
+use db_handling::manage_history::{
+    get_history_entry_by_order_in, search_history_in, HistoryFilter,
+};
+use db_handling::storage::{SledStorage, Storage};
 use definitions::navigation::{
     ActionResult, Address, AlertData, FooterButton, History, MEnterPassword, MKeyDetailsMulti,
-    MKeys, MLog, MLogDetails, MManageNetworks, MNetworkCard, MNewSeed, MPasswordConfirm,
-    MRecoverSeedName, MRecoverSeedPhrase, MSCNetworkInfo, MSeedMenu, MSeeds, MSettings,
-    MSignSufficientCrypto, MSignatureReady, MSufficientCryptoReady, MTransaction, ModalData,
-    RightButton, ScreenData, ScreenNameType, ShieldAlert, TransactionType,
+    MKeys, MLog, MLogDetails, MLogSearch, MManageNetworks, MNetworkCard, MNewSeed,
+    MPasswordConfirm, MRecoverSeedName, MRecoverSeedPhrase, MSCNetworkInfo, MSeedMenu, MSeeds,
+    MSettings, MSignSufficientCrypto, MSignatureReady, MSufficientCryptoReady, MTransaction,
+    ModalData, RightButton, ScreenData, ScreenNameType, ShieldAlert, TransactionType,
 };
 use sp_runtime::MultiSigner;
 use std::fmt::Write;
-use transaction_parsing::{entry_to_transactions_with_decoding, TransactionAction};
+use transaction_parsing::{entry_to_transactions_with_decoding_in, TransactionAction};
 use zeroize::Zeroize;
 
 use crate::actions::Action;
 use crate::alerts::Alert;
 use crate::modals::Modal;
 use crate::screens::{
-    AddressState, AddressStateMulti, DeriveState, KeysState, RecoverSeedPhraseState, Screen,
-    SpecialtyKeysState, SufficientCryptoState, TransactionState,
+    AddressState, AddressStateMulti, DeriveState, KeysState, LogSearchState,
+    RecoverSeedPhraseState, Screen, SpecialtyKeysState, SufficientCryptoState, TransactionState,
 };
-use db_handling::interface_signer::{get_all_seed_names_with_identicons, guess};
+use db_handling::interface_signer::{get_all_seed_names_with_identicons_in, guess};
 use definitions::{
     keyring::{AddressKey, NetworkSpecsKey},
     network_specs::Verifier,
-    users::AddressDetails,
+    users::{AddressDetails, KeyAnnotation},
 };
 
 use crate::error::{Error, Result};
@@ -63,7 +66,63 @@ impl Default for Navstate {
     }
 }
 
+impl Default for State {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl State {
+    /// Blank navigation state, not yet bound to any database.
+    pub fn new() -> Self {
+        Self {
+            navstate: Navstate::new(),
+            dbname: None,
+            seed_names: Vec::new(),
+            networks: Vec::new(),
+        }
+    }
+
+    /// Bind state to the database and recall seed names stored only by phone.
+    ///
+    /// Database with schema version newer than supported is refused.
+    pub fn init_navigation(&mut self, dbname: &str, seed_names: Vec<String>) -> Result<()> {
+        self.init_navigation_in(&SledStorage::open(dbname)?, seed_names)?;
+        self.dbname = Some(dbname.to_string());
+        Ok(())
+    }
+
+    /// Same as [`init_navigation`](Self::init_navigation), for the database
+    /// in given [`Storage`].
+    ///
+    /// State is not bound to any database path, all further actions go
+    /// through [`perform_in`](Self::perform_in) with the same storage.
+    pub fn init_navigation_in<S: Storage>(
+        &mut self,
+        storage: &S,
+        seed_names: Vec<String>,
+    ) -> Result<()> {
+        // database made by an older Signer is brought to the current schema
+        // before anything is read from it
+        db_handling::migrations::migrate_db_in(storage, false)?;
+        self.seed_names = seed_names;
+        match db_handling::helpers::get_all_networks_in(storage) {
+            Ok(a) => {
+                for x in a.iter() {
+                    self.networks
+                        .push(NetworkSpecsKey::from_parts(&x.genesis_hash, &x.encryption));
+                }
+            }
+            Err(e) => println!("No networks could be fetched: {:?}", e),
+        };
+        Ok(())
+    }
+
+    /// Synchronize seed names modified in native.
+    pub fn update_seed_names(&mut self, seed_names: Vec<String>) {
+        self.seed_names = seed_names;
+    }
+
     fn handle_navbar_log(&self) -> (Navstate, String) {
         let mut new_navstate = self.navstate.to_owned();
         let errorline = String::new();
@@ -101,10 +160,10 @@ impl State {
         (new_navstate, errorline)
     }
 
-    fn handle_action_start(&self, dbname: &str) -> (Navstate, String) {
+    fn handle_action_start<S: Storage>(&self, storage: &S) -> (Navstate, String) {
         let mut new_navstate = self.navstate.to_owned();
         let mut errorline = String::new();
-        match db_handling::interface_signer::purge_transactions(dbname) {
+        match db_handling::interface_signer::purge_transactions_in(storage) {
             Ok(()) => {
                 if self.seed_names.is_empty() {
                     new_navstate = self.correct_seed_selector();
@@ -121,7 +180,7 @@ impl State {
         (new_navstate, errorline)
     }
 
-    fn handle_action_go_back(&self, dbname: &str) -> (Navstate, String) {
+    fn handle_action_go_back<S: Storage>(&self, storage: &S) -> (Navstate, String) {
         let mut new_navstate = self.navstate.clone();
         let mut errorline = String::new();
 
@@ -129,11 +188,11 @@ impl State {
             Alert::Empty => {
                 if let Modal::Empty = self.navstate.modal {
                     match &self.navstate.screen {
-                        Screen::LogDetails(_) => {
+                        Screen::LogDetails(_) | Screen::LogSearch(_) => {
                             new_navstate.screen = Screen::Log;
                         }
                         Screen::Transaction(_) => {
-                            match db_handling::interface_signer::purge_transactions(dbname) {
+                            match db_handling::interface_signer::purge_transactions_in(storage) {
                                 Ok(()) => new_navstate.screen = Screen::Scan,
                                 Err(e) => {
                                     new_navstate.alert = Alert::Error;
@@ -203,7 +262,7 @@ impl State {
                 } else {
                     match &self.navstate.screen {
                         Screen::Transaction(_) => {
-                            match db_handling::interface_signer::purge_transactions(dbname) {
+                            match db_handling::interface_signer::purge_transactions_in(storage) {
                                 Ok(()) => new_navstate = Navstate::clean_screen(Screen::Log),
                                 Err(e) => {
                                     new_navstate.alert = Alert::Error;
@@ -229,9 +288,9 @@ impl State {
         (new_navstate, errorline)
     }
 
-    fn handle_action_go_forward(
+    fn handle_action_go_forward<S: Storage>(
         &self,
-        dbname: &str,
+        storage: &S,
         details_str: &str,
         secret_seed_phrase: &str,
     ) -> (Navstate, String) {
@@ -242,7 +301,10 @@ impl State {
                 match self.navstate.modal {
                     Modal::LogComment => {
                         // details_str is user entered comment
-                        match db_handling::manage_history::history_entry_user(dbname, details_str) {
+                        match db_handling::manage_history::history_entry_user_in(
+                            storage,
+                            details_str,
+                        ) {
                             Ok(()) => new_navstate = Navstate::clean_screen(Screen::Log),
                             Err(e) => {
                                 new_navstate.alert = Alert::Error;
@@ -261,13 +323,13 @@ impl State {
                     }
                     Modal::NewSeedBackup(ref seed_name) => match details_str.parse::<bool>() {
                         Ok(roots) => {
-                            match db_handling::identities::try_create_seed(
+                            match db_handling::identities::try_create_seed_in(
                                 seed_name,
                                 secret_seed_phrase,
                                 roots,
-                                dbname,
+                                storage,
                             ) {
-                                Ok(()) => match KeysState::new(seed_name, dbname) {
+                                Ok(()) => match KeysState::new(seed_name, storage) {
                                     Ok(a) => new_navstate = Navstate::clean_screen(Screen::Keys(a)),
                                     Err(e) => {
                                         new_navstate.alert = Alert::Error;
@@ -289,7 +351,7 @@ impl State {
                 }
             }
             Screen::RecoverSeedName(_) => {
-                match db_handling::identities::get_addresses_by_seed_name(dbname, details_str) {
+                match db_handling::identities::get_addresses_by_seed_name_in(storage, details_str) {
                     Ok(a) => {
                         if a.is_empty() {
                             new_navstate = Navstate::clean_screen(Screen::RecoverSeedPhrase(
@@ -313,13 +375,13 @@ impl State {
             Screen::RecoverSeedPhrase(ref recover_seed_phrase_state) => {
                 let seed_name = recover_seed_phrase_state.name();
                 match details_str.parse::<bool>() {
-                    Ok(roots) => match db_handling::identities::try_create_seed(
+                    Ok(roots) => match db_handling::identities::try_create_seed_in(
                         &seed_name,
                         secret_seed_phrase,
                         roots,
-                        dbname,
+                        storage,
                     ) {
-                        Ok(()) => match KeysState::new(&seed_name, dbname) {
+                        Ok(()) => match KeysState::new(&seed_name, storage) {
                             Ok(a) => new_navstate = Navstate::clean_screen(Screen::Keys(a)),
                             Err(e) => {
                                 new_navstate.alert = Alert::Error;
@@ -344,7 +406,7 @@ impl State {
                     secret_seed_phrase,
                     details_str,
                     &derive_state.network_specs_key(),
-                    dbname,
+                    storage,
                 ) {
                     Ok(()) => {
                         new_navstate =
@@ -386,12 +448,12 @@ impl State {
                             match self.navstate.modal {
                                 Modal::EnterPassword => {
                                     let mut seed = t.seed();
-                                    match transaction_signing::handle_sign(
+                                    match transaction_signing::handle_sign_in(
                                         checksum,
                                         &seed,
                                         details_str,
                                         &t.get_comment(),
-                                        dbname,
+                                        storage,
                                     ) {
                                         Ok(a) => {
                                             seed.zeroize();
@@ -399,7 +461,12 @@ impl State {
                                         }
                                         Err(e) => {
                                             seed.zeroize();
-                                            if let transaction_signing::Error::WrongPasswordNewChecksum(c) = e {
+                                            if let transaction_signing::Error::WrongPasswordNewChecksum(c)
+                                            | transaction_signing::Error::PolicyViolationNewChecksum {
+                                                checksum: c,
+                                                ..
+                                            } = e
+                                            {
                                                 if t.ok() {
                                                     new_navstate.screen = Screen::Transaction(
                                                         Box::new(t.update_checksum_sign(
@@ -428,17 +495,31 @@ impl State {
                                 }
                             }
                         } else {
-                            match transaction_signing::handle_sign(
+                            match transaction_signing::handle_sign_in(
                                 checksum,
                                 secret_seed_phrase,
                                 "",
                                 details_str,
-                                dbname,
+                                storage,
                             ) {
                                 Ok(a) => {
                                     new_navstate.modal = Modal::SignatureReady(a);
                                 }
                                 Err(e) => {
+                                    if let transaction_signing::Error::PolicyViolationNewChecksum {
+                                        checksum: c,
+                                        ..
+                                    } = e
+                                    {
+                                        new_navstate.screen =
+                                            Screen::Transaction(Box::new(t.update_checksum_sign(
+                                                c,
+                                                content,
+                                                has_pwd,
+                                                author_info,
+                                                network_info,
+                                            )));
+                                    }
                                     new_navstate.alert = Alert::Error;
                                     let _ = write!(&mut errorline, "{}", e);
                                 }
@@ -449,7 +530,7 @@ impl State {
                         s: _,
                         u: checksum,
                         stub: stub_nav,
-                    } => match transaction_signing::handle_stub(checksum, dbname) {
+                    } => match transaction_signing::handle_stub_in(checksum, storage) {
                         Ok(()) => match stub_nav {
                             transaction_parsing::StubNav::AddSpecs {
                                 n: network_specs_key,
@@ -468,6 +549,26 @@ impl State {
                             transaction_parsing::StubNav::LoadTypes => {
                                 new_navstate = Navstate::clean_screen(Screen::ManageNetworks);
                             }
+                            transaction_parsing::StubNav::AddContacts => {
+                                new_navstate = Navstate::clean_screen(Screen::Log);
+                            }
+                            transaction_parsing::StubNav::RotateVerifier {
+                                n: network_specs_key,
+                            } => {
+                                new_navstate = Navstate::clean_screen(Screen::NetworkDetails(
+                                    network_specs_key,
+                                ));
+                            }
+                            transaction_parsing::StubNav::SecurityPolicy => {
+                                new_navstate = Navstate::clean_screen(Screen::Settings);
+                            }
+                            transaction_parsing::StubNav::AmendSpecs {
+                                n: network_specs_key,
+                            } => {
+                                new_navstate = Navstate::clean_screen(Screen::NetworkDetails(
+                                    network_specs_key,
+                                ));
+                            }
                         },
                         Err(e) => {
                             new_navstate.alert = Alert::Error;
@@ -487,11 +588,11 @@ impl State {
                             Modal::SelectSeed => {
                                 // details_str is seed_name
                                 // `secret_seed_phrase` is `seed_phrase`
-                                match db_handling::identities::import_derivations(
+                                match db_handling::identities::import_derivations_in(
                                     checksum,
                                     details_str,
                                     secret_seed_phrase,
-                                    dbname,
+                                    storage,
                                 ) {
                                     Ok(()) => {
                                         new_navstate = Navstate::clean_screen(Screen::Keys(
@@ -537,11 +638,11 @@ impl State {
                         // details_str is password entry attempt
                         if let Modal::EnterPassword = self.navstate.modal {
                             let mut seed = s.seed();
-                            match transaction_signing::sign_content(
+                            match transaction_signing::sign_content_in(
                                 &multisigner,
                                 &address_details,
                                 s.content(),
-                                dbname,
+                                storage,
                                 &seed,
                                 details_str,
                             ) {
@@ -568,28 +669,33 @@ impl State {
                     None => {
                         // `details_str` is `hex_address_key`
                         // `secret_seed_phrase` is seed phrase
-                        match process_hex_address_key_address_details(details_str, dbname) {
-                            Ok((multisigner, address_details)) => {
+                        match process_hex_address_key_selected(details_str, storage) {
+                            Ok((multisigner, address_details, annotation)) => {
                                 if address_details.has_pwd {
                                     new_navstate.screen = Screen::SignSufficientCrypto(s.update(
                                         &multisigner,
                                         &address_details,
+                                        annotation,
                                         secret_seed_phrase,
                                     ));
                                     new_navstate.modal = Modal::EnterPassword;
                                 } else {
-                                    match transaction_signing::sign_content(
+                                    match transaction_signing::sign_content_in(
                                         &multisigner,
                                         &address_details,
                                         s.content(),
-                                        dbname,
+                                        storage,
                                         secret_seed_phrase,
                                         "",
                                     ) {
                                         Ok(a) => {
-                                            new_navstate.screen = Screen::SignSufficientCrypto(
-                                                s.update(&multisigner, &address_details, ""),
-                                            );
+                                            new_navstate.screen =
+                                                Screen::SignSufficientCrypto(s.update(
+                                                    &multisigner,
+                                                    &address_details,
+                                                    annotation,
+                                                    "",
+                                                ));
                                             new_navstate.modal = Modal::SufficientCryptoReady(a);
                                         }
                                         Err(e) => {
@@ -612,14 +718,14 @@ impl State {
         (new_navstate, errorline)
     }
 
-    fn handle_select_seed(&self, dbname: &str, details_str: &str) -> (Navstate, String) {
+    fn handle_select_seed<S: Storage>(&self, storage: &S, details_str: &str) -> (Navstate, String) {
         let mut new_navstate = self.navstate.clone();
         let mut errorline = String::new();
         match self.navstate.screen {
             Screen::SeedSelector => {
                 if !details_str.is_empty() {
                     // details_str is seed name
-                    match KeysState::new(details_str, dbname) {
+                    match KeysState::new(details_str, storage) {
                         Ok(a) => {
                             new_navstate = Navstate::clean_screen(Screen::Keys(a));
                         }
@@ -638,7 +744,7 @@ impl State {
         (new_navstate, errorline)
     }
 
-    fn handle_select_key(&self, dbname: &str, details_str: &str) -> (Navstate, String) {
+    fn handle_select_key<S: Storage>(&self, storage: &S, details_str: &str) -> (Navstate, String) {
         let mut new_navstate = self.navstate.clone();
         let mut errorline = String::new();
         match self.navstate.screen {
@@ -656,7 +762,7 @@ impl State {
                         }
                     }
                 } else {
-                    match AddressState::new(details_str, keys_state, dbname) {
+                    match AddressState::new(details_str, keys_state, storage) {
                         Ok(a) => {
                             new_navstate = Navstate::clean_screen(Screen::KeyDetails(a));
                         }
@@ -673,16 +779,16 @@ impl State {
         (new_navstate, errorline)
     }
 
-    fn handle_new_key(&self, dbname: &str, details_str: &str) -> (Navstate, String) {
+    fn handle_new_key<S: Storage>(&self, storage: &S, details_str: &str) -> (Navstate, String) {
         let mut new_navstate = self.navstate.clone();
         let errorline = String::new();
         match self.navstate.screen {
             Screen::Keys(ref keys_state) => {
-                let collision = match db_handling::identities::derivation_check(
+                let collision = match db_handling::identities::derivation_check_in(
                     &keys_state.seed_name(),
                     details_str,
                     &keys_state.network_specs_key(),
-                    dbname,
+                    storage,
                 ) {
                     Ok(db_handling::identities::DerivationCheck::NoPassword(a)) => a,
                     _ => None,
@@ -749,7 +855,7 @@ impl State {
         (new_navstate, errorline)
     }
 
-    fn handle_backup_seed(&self, dbname: &str, details_str: &str) -> (Navstate, String) {
+    fn handle_backup_seed<S: Storage>(&self, storage: &S, details_str: &str) -> (Navstate, String) {
         let mut new_navstate = self.navstate.clone();
         let mut errorline = String::new();
         if details_str.is_empty() {
@@ -765,7 +871,7 @@ impl State {
                 _ => println!("BackupSeed without seed_name does nothing here"),
             }
         } else if let Screen::SelectSeedForBackup = self.navstate.screen {
-            new_navstate = match KeysState::new(details_str, dbname) {
+            new_navstate = match KeysState::new(details_str, storage) {
                 Ok(a) => Navstate {
                     screen: Screen::Keys(a),
                     modal: Modal::Backup(details_str.to_string()),
@@ -803,15 +909,30 @@ impl State {
         (new_navstate, errorline)
     }
 
-    fn handle_next_unit(&self) -> (Navstate, String) {
+    fn handle_next_unit<S: Storage>(&self, storage: &S) -> (Navstate, String) {
         let mut new_navstate = self.navstate.clone();
-        let errorline = String::new();
+        let mut errorline = String::new();
 
         match self.navstate.screen {
             Screen::KeyDetailsMulti(ref address_state_multi) => {
                 new_navstate =
                     Navstate::clean_screen(Screen::KeyDetailsMulti(address_state_multi.next()));
             }
+            Screen::LogSearch(ref log_search_state) => {
+                match HistoryFilter::from_query(&log_search_state.query())
+                    .and_then(|filter| search_history_in(storage, &filter, log_search_state.page()))
+                {
+                    Ok(found) => {
+                        new_navstate = Navstate::clean_screen(Screen::LogSearch(
+                            log_search_state.next(found.total_pages),
+                        ))
+                    }
+                    Err(e) => {
+                        new_navstate.alert = Alert::Error;
+                        let _ = write!(&mut errorline, "{}", e);
+                    }
+                }
+            }
             _ => println!("NextUnit does nothing here"),
         }
         (new_navstate, errorline)
@@ -825,6 +946,10 @@ impl State {
                 new_navstate =
                     Navstate::clean_screen(Screen::KeyDetailsMulti(address_state_multi.previous()));
             }
+            Screen::LogSearch(ref log_search_state) => {
+                new_navstate =
+                    Navstate::clean_screen(Screen::LogSearch(log_search_state.previous()));
+            }
             _ => println!("PreviousUnit does nothing here"),
         }
         (new_navstate, errorline)
@@ -867,26 +992,30 @@ impl State {
         (new_navstate, errorline)
     }
 
-    fn handle_transaction_fetched(&self, dbname: &str, details_str: &str) -> (Navstate, String) {
+    fn handle_transaction_fetched<S: Storage>(
+        &self,
+        storage: &S,
+        details_str: &str,
+    ) -> (Navstate, String) {
         let mut new_navstate = self.navstate.clone();
         let errorline = String::new();
 
         if let Screen::Scan = self.navstate.screen {
             new_navstate = Navstate::clean_screen(Screen::Transaction(Box::new(
-                TransactionState::new(details_str, dbname),
+                TransactionState::new(details_str, storage),
             )));
         }
 
         (new_navstate, errorline)
     }
 
-    fn handle_remove_network(&self, dbname: &str) -> (Navstate, String) {
+    fn handle_remove_network<S: Storage>(&self, storage: &S) -> (Navstate, String) {
         let mut new_navstate = self.navstate.clone();
         let mut errorline = String::new();
         match self.navstate.screen {
             Screen::NetworkDetails(ref network_specs_key) => {
                 if let Modal::NetworkDetailsMenu = self.navstate.modal {
-                    match db_handling::helpers::remove_network(network_specs_key, dbname) {
+                    match db_handling::helpers::remove_network_in(network_specs_key, storage) {
                         Ok(()) => {
                             new_navstate = Navstate::clean_screen(Screen::ManageNetworks);
                         }
@@ -903,16 +1032,16 @@ impl State {
         (new_navstate, errorline)
     }
 
-    fn handle_remove_metadata(&self, dbname: &str) -> (Navstate, String) {
+    fn handle_remove_metadata<S: Storage>(&self, storage: &S) -> (Navstate, String) {
         let mut new_navstate = self.navstate.clone();
         let mut errorline = String::new();
         match self.navstate.screen {
             Screen::NetworkDetails(ref network_specs_key) => match self.navstate.modal {
                 Modal::ManageMetadata(network_version) => {
-                    match db_handling::helpers::remove_metadata(
+                    match db_handling::helpers::remove_metadata_in(
                         network_specs_key,
                         network_version,
-                        dbname,
+                        storage,
                     ) {
                         Ok(()) => {
                             new_navstate = Navstate::clean_screen(Screen::NetworkDetails(
@@ -933,12 +1062,12 @@ impl State {
         (new_navstate, errorline)
     }
 
-    fn handle_remove_types(&self, dbname: &str) -> (Navstate, String) {
+    fn handle_remove_types<S: Storage>(&self, storage: &S) -> (Navstate, String) {
         let mut new_navstate = self.navstate.clone();
         let mut errorline = String::new();
         match self.navstate.screen {
             Screen::ManageNetworks => match self.navstate.modal {
-                Modal::TypesInfo => match db_handling::helpers::remove_types_info(dbname) {
+                Modal::TypesInfo => match db_handling::helpers::remove_types_info_in(storage) {
                     Ok(()) => {
                         new_navstate = Navstate::clean_screen(Screen::Log);
                     }
@@ -1063,14 +1192,14 @@ impl State {
         (new_navstate, errorline)
     }
 
-    fn handle_remove_key(&self, dbname: &str) -> (Navstate, String) {
+    fn handle_remove_key<S: Storage>(&self, storage: &S) -> (Navstate, String) {
         let mut new_navstate = self.navstate.clone();
         let mut errorline = String::new();
         match self.navstate.screen {
             Screen::Keys(ref keys_state) => match keys_state.get_specialty() {
                 SpecialtyKeysState::Swiped(ref multisigner) => {
-                    match db_handling::identities::remove_key(
-                        dbname,
+                    match db_handling::identities::remove_key_in(
+                        storage,
                         multisigner,
                         &keys_state.network_specs_key(),
                     ) {
@@ -1088,8 +1217,8 @@ impl State {
                     }
                 }
                 SpecialtyKeysState::MultiSelect(ref multiselect) => {
-                    match db_handling::identities::remove_keys_set(
-                        dbname,
+                    match db_handling::identities::remove_keys_set_in(
+                        storage,
                         multiselect,
                         &keys_state.network_specs_key(),
                     ) {
@@ -1110,8 +1239,8 @@ impl State {
             },
             Screen::KeyDetails(ref address_state) => {
                 if let Modal::KeyDetailsAction = self.navstate.modal {
-                    match db_handling::identities::remove_key(
-                        dbname,
+                    match db_handling::identities::remove_key_in(
+                        storage,
                         &address_state.multisigner(),
                         &address_state.network_specs_key(),
                     ) {
@@ -1133,6 +1262,29 @@ impl State {
         (new_navstate, errorline)
     }
 
+    fn handle_set_key_annotation<F>(&self, set: F, action_name: &str) -> (Navstate, String)
+    where
+        F: FnOnce(&MultiSigner) -> db_handling::Result<()>,
+    {
+        let mut new_navstate = self.navstate.clone();
+        let mut errorline = String::new();
+        match self.navstate.screen {
+            Screen::KeyDetails(ref address_state) => match set(&address_state.multisigner()) {
+                Ok(()) => {
+                    new_navstate =
+                        Navstate::clean_screen(Screen::KeyDetails(address_state.to_owned()));
+                }
+                Err(e) => {
+                    new_navstate.alert = Alert::Error;
+                    let _ = write!(&mut errorline, "{}", e);
+                }
+            },
+            _ => println!("{} does nothing here", action_name),
+        }
+
+        (new_navstate, errorline)
+    }
+
     fn handle_create_log_comment(&self) -> (Navstate, String) {
         let mut new_navstate = self.navstate.clone();
         let errorline = String::new();
@@ -1150,13 +1302,14 @@ impl State {
         (new_navstate, errorline)
     }
 
-    fn handle_remove_seed(&self, dbname: &str) -> (Navstate, String) {
+    fn handle_remove_seed<S: Storage>(&self, storage: &S) -> (Navstate, String) {
         let mut new_navstate = self.navstate.clone();
         let mut errorline = String::new();
         match self.navstate.screen {
             Screen::Keys(ref keys_state) => {
                 if let Modal::SeedMenu = self.navstate.modal {
-                    match db_handling::identities::remove_seed(dbname, &keys_state.seed_name()) {
+                    match db_handling::identities::remove_seed_in(storage, &keys_state.seed_name())
+                    {
                         Ok(()) => {
                             new_navstate = Navstate::clean_screen(Screen::Log);
                         }
@@ -1175,13 +1328,13 @@ impl State {
         (new_navstate, errorline)
     }
 
-    fn handle_clear_log(&self, dbname: &str) -> (Navstate, String) {
+    fn handle_clear_log<S: Storage>(&self, storage: &S) -> (Navstate, String) {
         let mut new_navstate = self.navstate.clone();
         let mut errorline = String::new();
         match self.navstate.screen {
             Screen::Log => {
                 if let Modal::LogRight = self.navstate.modal {
-                    match db_handling::manage_history::clear_history(dbname) {
+                    match db_handling::manage_history::clear_history_in(storage) {
                         Ok(()) => {
                             new_navstate = Navstate::clean_screen(Screen::Log);
                         }
@@ -1204,7 +1357,7 @@ impl State {
         let mut errorline = String::new();
 
         match self.navstate.screen {
-            Screen::Log => {
+            Screen::Log | Screen::LogSearch(_) => {
                 // details_str is u32 order which will be shown
                 match details_str.parse::<u32>() {
                     Ok(order) => new_navstate = Navstate::clean_screen(Screen::LogDetails(order)),
@@ -1220,6 +1373,46 @@ impl State {
         (new_navstate, errorline)
     }
 
+    fn handle_search_log(&self, details_str: &str) -> (Navstate, String) {
+        let mut new_navstate = self.navstate.clone();
+        let mut errorline = String::new();
+
+        match self.navstate.screen {
+            Screen::Log | Screen::LogSearch(_) => {
+                // details_str is history search query
+                match HistoryFilter::from_query(details_str) {
+                    Ok(_) => {
+                        new_navstate = Navstate::clean_screen(Screen::LogSearch(
+                            LogSearchState::new(details_str),
+                        ))
+                    }
+                    Err(e) => {
+                        new_navstate.alert = Alert::Error;
+                        let _ = write!(&mut errorline, "{}", e);
+                    }
+                }
+            }
+            _ => println!("SearchLog does nothing here"),
+        }
+
+        (new_navstate, errorline)
+    }
+
+    fn handle_search_keys(&self, details_str: &str) -> (Navstate, String) {
+        let mut new_navstate = self.navstate.clone();
+        let errorline = String::new();
+
+        match self.navstate.screen {
+            Screen::Keys(ref keys_state) => {
+                // details_str is key search query
+                new_navstate = Navstate::clean_screen(Screen::Keys(keys_state.search(details_str)));
+            }
+            _ => println!("SearchKeys does nothing here"),
+        }
+
+        (new_navstate, errorline)
+    }
+
     fn handle_swipe(&self, details_str: &str) -> (Navstate, String) {
         let mut new_navstate = self.navstate.clone();
         let mut errorline = String::new();
@@ -1261,15 +1454,15 @@ impl State {
         (new_navstate, errorline)
     }
 
-    fn handle_select_all(&self, dbname: &str) -> (Navstate, String) {
+    fn handle_select_all<S: Storage>(&self, storage: &S) -> (Navstate, String) {
         let mut new_navstate = self.navstate.clone();
         let mut errorline = String::new();
 
         match self.navstate.screen {
             Screen::Keys(ref keys_state) => match keys_state.get_specialty() {
                 SpecialtyKeysState::MultiSelect(ref multiselect) => {
-                    match db_handling::interface_signer::addresses_set_seed_name_network(
-                        dbname,
+                    match db_handling::interface_signer::addresses_set_seed_name_network_in(
+                        storage,
                         &keys_state.seed_name(),
                         &keys_state.network_specs_key(),
                     ) {
@@ -1309,7 +1502,7 @@ impl State {
         (new_navstate, errorline)
     }
 
-    fn handle_export_multi_select(&self, dbname: &str) -> (Navstate, String) {
+    fn handle_export_multi_select<S: Storage>(&self, storage: &S) -> (Navstate, String) {
         let mut new_navstate = self.navstate.clone();
         let mut errorline = String::new();
 
@@ -1321,7 +1514,7 @@ impl State {
                         keys_state.seed_name(),
                         keys_state.network_specs_key(),
                         multiselect,
-                        dbname,
+                        storage,
                     ) {
                         Ok(a) => new_navstate = Navstate::clean_screen(Screen::KeyDetailsMulti(a)),
                         Err(e) => {
@@ -1339,10 +1532,10 @@ impl State {
         (new_navstate, errorline)
     }
 
-    fn handle_increment(
+    fn handle_increment<S: Storage>(
         &self,
         details_str: &str,
-        dbname: &str,
+        storage: &S,
         secret_seed_phrase: &str,
     ) -> (Navstate, String) {
         let mut new_navstate = self.navstate.clone();
@@ -1358,7 +1551,7 @@ impl State {
                                 &multisigner,
                                 &keys_state.network_specs_key(),
                                 secret_seed_phrase,
-                                dbname,
+                                storage,
                             ) {
                                 Ok(()) => {
                                     new_navstate = Navstate::clean_screen(Screen::Keys(
@@ -1434,15 +1627,15 @@ impl State {
         (new_navstate, errorline)
     }
 
-    fn get_screen_data(
+    fn get_screen_data<S: Storage>(
         &mut self,
         new_navstate: &Navstate,
         details_str: &str,
-        dbname: &str,
+        storage: &S,
     ) -> Result<ScreenData> {
         let sd = match new_navstate.screen {
             Screen::Log => {
-                let history = db_handling::manage_history::get_history(dbname)?;
+                let history = db_handling::manage_history::get_history_in(storage)?;
                 let log: Vec<_> = history
                     .into_iter()
                     .map(|(order, entry)| History {
@@ -1456,12 +1649,32 @@ impl State {
                 ScreenData::Log { f }
             }
             Screen::LogDetails(order) => {
-                let e = get_history_entry_by_order(order, dbname)?;
+                let e = get_history_entry_by_order_in(order, storage)?;
                 let timestamp = e.timestamp.clone();
-                let events = entry_to_transactions_with_decoding(e, dbname)?;
+                let events = entry_to_transactions_with_decoding_in(e, storage)?;
                 let f = MLogDetails { timestamp, events };
                 ScreenData::LogDetails { f }
             }
+            Screen::LogSearch(ref log_search_state) => {
+                let filter = HistoryFilter::from_query(&log_search_state.query())?;
+                let found = search_history_in(storage, &filter, log_search_state.page())?;
+                let log: Vec<_> = found
+                    .entries
+                    .into_iter()
+                    .map(|(order, entry)| History {
+                        order: order.stamp(),
+                        timestamp: entry.timestamp,
+                        events: entry.events,
+                    })
+                    .collect();
+                let f = MLogSearch {
+                    query: log_search_state.query(),
+                    page: log_search_state.page(),
+                    total_pages: found.total_pages,
+                    log,
+                };
+                ScreenData::LogSearch { f }
+            }
             Screen::Scan => ScreenData::Scan,
             Screen::Transaction(ref t) => {
                 let (content, ttype, author_info, network_info) = match t.action() {
@@ -1506,8 +1719,8 @@ impl State {
             }
             Screen::SeedSelector => {
                 let seed_name_cards =
-                    db_handling::interface_signer::get_all_seed_names_with_identicons(
-                        dbname,
+                    db_handling::interface_signer::get_all_seed_names_with_identicons_in(
+                        storage,
                         &self.seed_names,
                     )?;
                 let f = MSeeds { seed_name_cards };
@@ -1515,22 +1728,33 @@ impl State {
             }
             Screen::SelectSeedForBackup => {
                 let seed_name_cards =
-                    db_handling::interface_signer::get_all_seed_names_with_identicons(
-                        dbname,
+                    db_handling::interface_signer::get_all_seed_names_with_identicons_in(
+                        storage,
                         &self.seed_names,
                     )?;
                 let f = MSeeds { seed_name_cards };
                 ScreenData::SelectSeedForBackup { f }
             }
             Screen::Keys(ref keys_state) => {
-                let (root, set, title, logo) =
-                    db_handling::interface_signer::print_identities_for_seed_name_and_network(
-                        dbname,
+                let (root, mut set, title, logo) =
+                    db_handling::interface_signer::print_identities_for_seed_name_and_network_in(
+                        storage,
                         &keys_state.seed_name(),
                         &keys_state.network_specs_key(),
                         keys_state.get_swiped_key(),
                         keys_state.get_multiselect_keys(),
                     )?;
+                if let Some(query) = keys_state.query() {
+                    let path_query = query.to_lowercase();
+                    set.retain(|card| {
+                        card.path.to_lowercase().contains(&path_query)
+                            || card
+                                .annotation
+                                .as_ref()
+                                .map(|annotation| annotation.matches(&query))
+                                .unwrap_or(false)
+                    });
+                }
                 let multiselect_mode = keys_state.is_multiselect();
                 let multiselect_count = if let SpecialtyKeysState::MultiSelect(ref multiselect) =
                     keys_state.get_specialty()
@@ -1550,8 +1774,8 @@ impl State {
                 ScreenData::Keys { f }
             }
             Screen::KeyDetails(ref address_state) => {
-                let f = db_handling::interface_signer::export_key(
-                    dbname,
+                let f = db_handling::interface_signer::export_key_in(
+                    storage,
                     &address_state.multisigner(),
                     &address_state.seed_name(),
                     &address_state.network_specs_key(),
@@ -1559,8 +1783,8 @@ impl State {
                 ScreenData::KeyDetails { f }
             }
             Screen::KeyDetailsMulti(ref address_state_multi) => {
-                let key_details = db_handling::interface_signer::export_key(
-                    dbname,
+                let key_details = db_handling::interface_signer::export_key_in(
+                    storage,
                     &address_state_multi.multisigner(),
                     &address_state_multi.seed_name(),
                     &address_state_multi.network_specs_key(),
@@ -1605,8 +1829,8 @@ impl State {
                 ScreenData::RecoverSeedPhrase { f }
             }
             Screen::DeriveKey(ref derive_state) => {
-                let f = db_handling::interface_signer::derive_prep(
-                    dbname,
+                let f = db_handling::interface_signer::derive_prep_in(
+                    storage,
                     &derive_state.seed_name(),
                     &derive_state.network_specs_key(),
                     derive_state.collision(),
@@ -1626,7 +1850,7 @@ impl State {
                 // you have `error` field for that.
                 //
                 // Don't ignore this on refactor like everyone else before you.
-                let f = match db_handling::helpers::get_general_verifier(dbname) {
+                let f = match db_handling::helpers::get_general_verifier_in(storage) {
                     Ok(Verifier { v: Some(vv) }) => {
                         let card = vv.show_card();
                         MSettings {
@@ -1647,23 +1871,23 @@ impl State {
                 ScreenData::Settings { f }
             }
             Screen::Verifier => {
-                let f = db_handling::helpers::get_general_verifier(dbname)?;
+                let f = db_handling::helpers::get_general_verifier_in(storage)?;
                 ScreenData::VVerifier { f: f.show_card() }
             }
             Screen::ManageNetworks => {
-                let networks = db_handling::interface_signer::show_all_networks(dbname)?;
+                let networks = db_handling::interface_signer::show_all_networks_in(storage)?;
                 let f = MManageNetworks { networks };
                 ScreenData::ManageNetworks { f }
             }
             Screen::NetworkDetails(ref network_specs_key) => {
-                let f = db_handling::interface_signer::network_details_by_key(
-                    dbname,
+                let f = db_handling::interface_signer::network_details_by_key_in(
+                    storage,
                     network_specs_key,
                 )?;
                 ScreenData::NNetworkDetails { f }
             }
             Screen::SignSufficientCrypto(_) => {
-                let identities = db_handling::interface_signer::print_all_identities(dbname)?;
+                let identities = db_handling::interface_signer::print_all_identities_in(storage)?;
                 let f = MSignSufficientCrypto { identities };
                 ScreenData::SignSufficientCrypto { f }
             }
@@ -1673,14 +1897,14 @@ impl State {
         Ok(sd)
     }
 
-    fn get_modal_details(
+    fn get_modal_details<S: Storage>(
         &mut self,
         new_navstate: &mut Navstate,
-        dbname: &str,
+        storage: &S,
     ) -> Result<Option<ModalData>> {
         let modal = match new_navstate.modal {
             Modal::Backup(ref seed_name) => Some(ModalData::Backup {
-                f: db_handling::interface_signer::backup_prep(dbname, seed_name)?,
+                f: db_handling::interface_signer::backup_prep_in(storage, seed_name)?,
             }),
             Modal::SeedMenu => match new_navstate.screen {
                 Screen::Keys(ref keys_state) => {
@@ -1696,8 +1920,8 @@ impl State {
                 f: db_handling::interface_signer::print_new_seed(new_seed_name)?,
             }),
             Modal::NetworkSelector(ref network_specs_key) => Some(ModalData::NetworkSelector {
-                f: db_handling::interface_signer::show_all_networks_with_flag(
-                    dbname,
+                f: db_handling::interface_signer::show_all_networks_with_flag_in(
+                    storage,
                     network_specs_key,
                 )?,
             }),
@@ -1764,13 +1988,13 @@ impl State {
             },
             Modal::LogRight => Some(ModalData::LogRight {
                 f: definitions::navigation::MLogRight {
-                    checksum: db_handling::interface_signer::history_hex_checksum(dbname)?,
+                    checksum: db_handling::interface_signer::history_hex_checksum_in(storage)?,
                 },
             }),
             Modal::ManageMetadata(network_version) => match new_navstate.screen {
                 Screen::NetworkDetails(ref network_specs_key) => Some(ModalData::ManageMetadata {
-                    f: db_handling::interface_signer::metadata_details(
-                        dbname,
+                    f: db_handling::interface_signer::metadata_details_in(
+                        storage,
                         network_specs_key,
                         network_version,
                     )?,
@@ -1790,6 +2014,7 @@ impl State {
                                 has_pwd: author_info.has_pwd,
                                 multiselect: None,
                                 secret_exposed: author_info.secret_exposed,
+                                annotation: author_info.annotation,
                             };
                             let f = MSufficientCryptoReady {
                                 author_info,
@@ -1805,10 +2030,10 @@ impl State {
                 }
             }
             Modal::TypesInfo => Some(ModalData::TypesInfo {
-                f: db_handling::interface_signer::show_types_status(dbname)?,
+                f: db_handling::interface_signer::show_types_status_in(storage)?,
             }),
             Modal::SelectSeed => {
-                match get_all_seed_names_with_identicons(dbname, &self.seed_names) {
+                match get_all_seed_names_with_identicons_in(storage, &self.seed_names) {
                     Ok(a) => Some(ModalData::SelectSeed {
                         f: MSeeds { seed_name_cards: a },
                     }),
@@ -1837,123 +2062,159 @@ impl State {
         action: Action,
         details_str: &str,
         secret_seed_phrase: &str,
+    ) -> Result<ActionResult> {
+        if let Some(ref dbname) = self.dbname.clone() {
+            let storage = SledStorage::open(dbname)?;
+            self.perform_in(&storage, action, details_str, secret_seed_phrase)
+        } else {
+            Err(Error::DbNotInitialized)
+        }
+    }
+
+    /// Same as [`perform`](Self::perform), for the database in given
+    /// [`Storage`].
+    pub fn perform_in<S: Storage>(
+        &mut self,
+        storage: &S,
+        action: Action,
+        details_str: &str,
+        secret_seed_phrase: &str,
     ) -> Result<ActionResult> {
         let mut new_navstate = self.navstate.to_owned();
 
-        if let Some(ref dbname) = self.dbname.clone() {
-            let mut errorline;
-            //Try to perform action
-            (new_navstate, errorline) = match action {
-                //App init
-                Action::Start => self.handle_action_start(dbname),
-                //Simple navigation commands
-                Action::NavbarLog => self.handle_navbar_log(),
-                Action::NavbarScan => self.handle_navbar_scan(),
-                Action::NavbarKeys => self.handle_navbar_keys(),
-                Action::NavbarSettings => self.handle_navbar_settings(),
-                //General back action is defined here
-                Action::GoBack => self.handle_action_go_back(dbname),
-                Action::GoForward => {
-                    self.handle_action_go_forward(dbname, details_str, secret_seed_phrase)
-                }
-                Action::SelectSeed => self.handle_select_seed(dbname, details_str),
-                Action::SelectKey => self.handle_select_key(dbname, details_str),
-                Action::NewKey => self.handle_new_key(dbname, details_str),
-                Action::RightButtonAction => self.handle_right_button(),
-                Action::Shield => self.handle_shield(),
-                Action::NewSeed => self.handle_new_seed(),
-                Action::RecoverSeed => self.handle_recover_seed(),
-                Action::BackupSeed => self.handle_backup_seed(dbname, details_str),
-                Action::NetworkSelector => self.handle_network_selector(),
-                Action::NextUnit => self.handle_next_unit(),
-                Action::PreviousUnit => self.handle_previous_unit(),
-                Action::ChangeNetwork => self.handle_change_network(details_str),
-                Action::CheckPassword => self.handle_change_password(details_str),
-                Action::TransactionFetched => self.handle_transaction_fetched(dbname, details_str),
-                Action::RemoveNetwork => self.handle_remove_network(dbname),
-                Action::RemoveMetadata => self.handle_remove_metadata(dbname),
-                Action::RemoveTypes => self.handle_remove_types(dbname),
-                Action::SignNetworkSpecs => self.handle_sign_network_specs(),
-                Action::SignMetadata => self.handle_sign_metadata(),
-                Action::SignTypes => self.handle_sign_types(),
-                Action::ManageNetworks => self.handle_manage_networks(),
-                Action::ViewGeneralVerifier => self.handle_view_general_verifier(),
-                Action::ManageMetadata => self.handle_manage_metadata(details_str),
-                Action::RemoveKey => self.handle_remove_key(dbname),
-                Action::RemoveSeed => self.handle_remove_seed(dbname),
-                Action::ClearLog => self.handle_clear_log(dbname),
-                Action::CreateLogComment => self.handle_create_log_comment(),
-                Action::ShowLogDetails => self.handle_show_log_details(details_str),
-                Action::Swipe => self.handle_swipe(details_str),
-                Action::LongTap => self.handle_long_tap(details_str),
-                Action::SelectAll => self.handle_select_all(dbname),
-                Action::ExportMultiSelect => self.handle_export_multi_select(dbname),
-                Action::Increment => self.handle_increment(details_str, dbname, secret_seed_phrase),
-                Action::ShowDocuments => self.handle_show_documents(),
-                Action::TextEntry => self.handle_text_entry(details_str),
-                Action::PushWord => self.handle_push_word(details_str),
-                Action::Nothing => (new_navstate, String::new()),
-            };
+        let mut errorline;
+        //Try to perform action
+        (new_navstate, errorline) = match action {
+            //App init
+            Action::Start => self.handle_action_start(storage),
+            //Simple navigation commands
+            Action::NavbarLog => self.handle_navbar_log(),
+            Action::NavbarScan => self.handle_navbar_scan(),
+            Action::NavbarKeys => self.handle_navbar_keys(),
+            Action::NavbarSettings => self.handle_navbar_settings(),
+            //General back action is defined here
+            Action::GoBack => self.handle_action_go_back(storage),
+            Action::GoForward => {
+                self.handle_action_go_forward(storage, details_str, secret_seed_phrase)
+            }
+            Action::SelectSeed => self.handle_select_seed(storage, details_str),
+            Action::SelectKey => self.handle_select_key(storage, details_str),
+            Action::NewKey => self.handle_new_key(storage, details_str),
+            Action::RightButtonAction => self.handle_right_button(),
+            Action::Shield => self.handle_shield(),
+            Action::NewSeed => self.handle_new_seed(),
+            Action::RecoverSeed => self.handle_recover_seed(),
+            Action::BackupSeed => self.handle_backup_seed(storage, details_str),
+            Action::NetworkSelector => self.handle_network_selector(),
+            Action::NextUnit => self.handle_next_unit(storage),
+            Action::PreviousUnit => self.handle_previous_unit(),
+            Action::ChangeNetwork => self.handle_change_network(details_str),
+            Action::CheckPassword => self.handle_change_password(details_str),
+            Action::TransactionFetched => self.handle_transaction_fetched(storage, details_str),
+            Action::RemoveNetwork => self.handle_remove_network(storage),
+            Action::RemoveMetadata => self.handle_remove_metadata(storage),
+            Action::RemoveTypes => self.handle_remove_types(storage),
+            Action::SignNetworkSpecs => self.handle_sign_network_specs(),
+            Action::SignMetadata => self.handle_sign_metadata(),
+            Action::SignTypes => self.handle_sign_types(),
+            Action::ManageNetworks => self.handle_manage_networks(),
+            Action::ViewGeneralVerifier => self.handle_view_general_verifier(),
+            Action::ManageMetadata => self.handle_manage_metadata(details_str),
+            Action::RemoveKey => self.handle_remove_key(storage),
+            Action::SetKeyLabel => self.handle_set_key_annotation(
+                |multisigner| {
+                    // details_str is new key label
+                    db_handling::helpers::set_key_label_in(storage, multisigner, details_str)
+                },
+                "SetKeyLabel",
+            ),
+            Action::SetKeyTags => self.handle_set_key_annotation(
+                |multisigner| {
+                    // details_str is new key tags
+                    db_handling::helpers::set_key_tags_in(storage, multisigner, details_str)
+                },
+                "SetKeyTags",
+            ),
+            Action::SetKeyNotes => self.handle_set_key_annotation(
+                |multisigner| {
+                    // details_str is new key notes
+                    db_handling::helpers::set_key_notes_in(storage, multisigner, details_str)
+                },
+                "SetKeyNotes",
+            ),
+            Action::RemoveSeed => self.handle_remove_seed(storage),
+            Action::ClearLog => self.handle_clear_log(storage),
+            Action::CreateLogComment => self.handle_create_log_comment(),
+            Action::ShowLogDetails => self.handle_show_log_details(details_str),
+            Action::SearchLog => self.handle_search_log(details_str),
+            Action::SearchKeys => self.handle_search_keys(details_str),
+            Action::Swipe => self.handle_swipe(details_str),
+            Action::LongTap => self.handle_long_tap(details_str),
+            Action::SelectAll => self.handle_select_all(storage),
+            Action::ExportMultiSelect => self.handle_export_multi_select(storage),
+            Action::Increment => self.handle_increment(details_str, storage, secret_seed_phrase),
+            Action::ShowDocuments => self.handle_show_documents(),
+            Action::TextEntry => self.handle_text_entry(details_str),
+            Action::PushWord => self.handle_push_word(details_str),
+            Action::Nothing => (new_navstate, String::new()),
+        };
 
-            //Prepare screen details
-            let screen_data = match self.get_screen_data(&new_navstate, details_str, dbname) {
-                Ok(sd) => sd,
-                Err(e) => {
-                    let _ = write!(&mut errorline, "{}", e);
-                    //This is special error used only
-                    //here; please do not change it to
-                    //`Alert::Error` or app may get stuck
-                    new_navstate.alert = Alert::ErrorDisplay;
-                    ScreenData::Settings {
-                        f: MSettings::default(),
-                    }
+        //Prepare screen details
+        let screen_data = match self.get_screen_data(&new_navstate, details_str, storage) {
+            Ok(sd) => sd,
+            Err(e) => {
+                let _ = write!(&mut errorline, "{}", e);
+                //This is special error used only
+                //here; please do not change it to
+                //`Alert::Error` or app may get stuck
+                new_navstate.alert = Alert::ErrorDisplay;
+                ScreenData::Settings {
+                    f: MSettings::default(),
                 }
-            };
+            }
+        };
 
-            //Prepare modal details
-            let modal_data = match self.get_modal_details(&mut new_navstate, dbname) {
-                Ok(md) => md,
-                Err(e) => {
-                    let _ = write!(&mut errorline, "{}", e);
-                    new_navstate.alert = Alert::Error;
-                    None
-                }
-            };
+        //Prepare modal details
+        let modal_data = match self.get_modal_details(&mut new_navstate, storage) {
+            Ok(md) => md,
+            Err(e) => {
+                let _ = write!(&mut errorline, "{}", e);
+                new_navstate.alert = Alert::Error;
+                None
+            }
+        };
 
-            //Prepare alert details
-            //Important! No errors could be handled in this block!
-            let alert_data = match new_navstate.alert {
-                Alert::Error | Alert::ErrorDisplay => Some(AlertData::ErrorData { f: errorline }),
-                Alert::Empty => None,
-                Alert::Shield => match get_danger_status(dbname) {
-                    Ok(true) => Some(AlertData::Shield {
-                        f: Some(ShieldAlert::Past),
-                    }),
-                    Ok(false) => Some(AlertData::Shield { f: None }),
-                    Err(e) => Some(AlertData::ErrorData {
-                        f: format!("{}", e),
-                    }),
-                },
-            };
+        //Prepare alert details
+        //Important! No errors could be handled in this block!
+        let alert_data = match new_navstate.alert {
+            Alert::Error | Alert::ErrorDisplay => Some(AlertData::ErrorData { f: errorline }),
+            Alert::Empty => None,
+            Alert::Shield => match get_danger_exposures_in(storage) {
+                Ok(exposures) if exposures.is_empty() => Some(AlertData::Shield { f: None }),
+                Ok(exposures) => Some(AlertData::Shield {
+                    f: Some(ShieldAlert::Past { exposures }),
+                }),
+                Err(e) => Some(AlertData::ErrorData {
+                    f: format!("{}", e),
+                }),
+            },
+        };
 
-            self.navstate = new_navstate;
-
-            let action_result = ActionResult {
-                screen_label: self.get_screen_label(),
-                back: self.navstate.screen.has_back(),
-                footer: self.get_footer(),
-                footer_button: self.get_active_navbutton(),
-                right_button: self.get_right_button(),
-                screen_name_type: self.get_screen_name_type(),
-                screen_data,
-                modal_data,
-                alert_data,
-            };
+        self.navstate = new_navstate;
+
+        let action_result = ActionResult {
+            screen_label: self.get_screen_label(),
+            back: self.navstate.screen.has_back(),
+            footer: self.get_footer(),
+            footer_button: self.get_active_navbutton(),
+            right_button: self.get_right_button(),
+            screen_name_type: self.get_screen_name_type(),
+            screen_data,
+            modal_data,
+            alert_data,
+        };
 
-            Ok(action_result)
-        } else {
-            Err(Error::DbNotInitialized)
-        }
+        Ok(action_result)
     }
 
     fn toggle_modal(&self, modal: Modal) -> Modal {
@@ -1982,6 +2243,7 @@ impl State {
         match self.navstate.screen {
             Screen::Log => true,
             Screen::LogDetails(_) => true,
+            Screen::LogSearch(_) => true,
             Screen::Scan => true,
             Screen::Transaction(_) => false,
             Screen::SeedSelector => true,
@@ -2006,7 +2268,7 @@ impl State {
     ///Decide which footer button should shine
     fn get_active_navbutton(&self) -> Option<FooterButton> {
         match self.navstate.screen {
-            Screen::Log | Screen::LogDetails(_) => Some(FooterButton::Log),
+            Screen::Log | Screen::LogDetails(_) | Screen::LogSearch(_) => Some(FooterButton::Log),
             Screen::Scan | Screen::Transaction(_) => Some(FooterButton::Scan),
             Screen::SeedSelector
             | Screen::Keys(_)
@@ -2048,6 +2310,7 @@ impl State {
             | Screen::DeriveKey(_)
             | Screen::Settings
             | Screen::LogDetails(_)
+            | Screen::LogSearch(_)
             | Screen::Scan
             | Screen::Transaction(_)
             | Screen::Documents
@@ -2065,6 +2328,7 @@ impl State {
         match self.navstate.screen {
             Screen::Log
             | Screen::LogDetails(_)
+            | Screen::LogSearch(_)
             | Screen::Keys(_)
             | Screen::KeyDetails(_)
             | Screen::KeyDetailsMulti(_)
@@ -2118,14 +2382,17 @@ impl Navstate {
     }
 }
 
-fn process_hex_address_key_address_details(
+/// Key selected for signing, with its address details and user-defined
+/// annotation.
+fn process_hex_address_key_selected<S: Storage>(
     hex_address_key: &str,
-    dbname: &str,
-) -> Result<(MultiSigner, AddressDetails)> {
+    storage: &S,
+) -> Result<(MultiSigner, AddressDetails, Option<KeyAnnotation>)> {
     let address_key = AddressKey::from_hex(hex_address_key)?;
     let multisigner = address_key.multi_signer()?;
-    let address_details = db_handling::helpers::get_address_details(dbname, &address_key)?;
-    Ok((multisigner, address_details))
+    let address_details = db_handling::helpers::get_address_details_in(storage, &address_key)?;
+    let annotation = db_handling::helpers::try_get_key_annotation_in(storage, &address_key)?;
+    Ok((multisigner, address_details, annotation))
 }
 
 fn process_hex_address_key(hex_address_key: &str) -> Result<MultiSigner> {
@@ -0,0 +1,464 @@
+//! Headless navigator driver for scripted end-to-end scenarios
+//!
+//! Scenario is a sequence of user [`Action`]s, replayed against an independent
+//! [`State`] instance, without any device or UI involved. Each step result is
+//! recorded as a text snapshot and optionally checked against expectations.
+//!
+//! Scenarios are written in JSON:
+//!
+//! ```json
+//! {
+//!     "name": "create seed",
+//!     "seed_names": [],
+//!     "general_verifier": "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+//!     "steps": [
+//!         {"action": "Start", "expect": {"screen": "SeedSelector", "modal": "NewSeedMenu"}},
+//!         {"action": "NewSeed"},
+//!         {"action": "GoForward", "details": "Alice", "expect": {"screen_label": "New Seed"}},
+//!         {"action": "GoForward", "details": "true", "seed_phrase": "bottom drive obey lake curtain smoke basket hold race lonely fit walk", "expect": {"screen": "Keys"}},
+//!         {"action": "NavbarLog", "seed_names": ["Alice"], "expect": {"contains": ["SeedCreated"]}}
+//!     ]
+//! }
+//! ```
+//!
+//! Step fields:
+//!
+//! - `action` is [`Action`] name, as it is spelled in the enum, mandatory
+//! - `details` and `seed_phrase` are passed into [`State::perform`] as is,
+//!   empty if not set
+//! - `seed_names` replaces seed names known to navigator before the action,
+//!   similarly to [`State::update_seed_names`] called by the phone
+//! - `expect` is set of checks for the step result, all optional:
+//!   - `screen_label` is exact screen label
+//!   - `screen` is [`ScreenData`](definitions::navigation::ScreenData)
+//!     variant name
+//!   - `modal` is [`ModalData`](definitions::navigation::ModalData) variant
+//!     name, or `None`
+//!   - `alert` is [`AlertData`](definitions::navigation::AlertData) variant
+//!     name, or `None`
+//!   - `error` is exact error produced by the navigator
+//!   - `contains` is a list of substrings expected in the step snapshot
+//!
+//! Snapshots are `Debug` representation of the step result, with timestamps,
+//! checksums, generated seed phrases and long byte sequences (identicons, QR
+//! codes) blinded, so that the snapshots of the same flow are comparable
+//! between the runs.
+//!
+//! Replay snapshot is checked against golden snapshot committed next to the
+//! scenario file, `<scenario>.snap`, with
+//! [`ScenarioReport::check_snapshot`]. Golden snapshots are recorded (or
+//! re-recorded after an intended change in navigator behavior) by running the
+//! scenario tests with `UPDATE_SCENARIO_SNAPSHOTS` environment variable set:
+//!
+//! ```text
+//! UPDATE_SCENARIO_SNAPSHOTS=1 cargo test -p navigator scenario
+//! ```
+//!
+//! Changed `.snap` files are then reviewed and committed together with the
+//! change that caused them.
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_json::{Map, Value};
+use sp_runtime::MultiSigner;
+use std::{convert::TryInto, env, fs, io, path::Path, str::FromStr};
+
+use db_handling::{
+    cold_default::{init_db, init_db_in, populate_cold_nav_test, populate_cold_nav_test_in},
+    storage::{MemoryStorage, SledStorage, Storage},
+};
+use definitions::{
+    navigation::ActionResult,
+    network_specs::{Verifier, VerifierValue},
+};
+
+use crate::error::{Error, Result};
+use crate::{Action, State};
+
+lazy_static! {
+    static ref TIMESTAMP: Regex =
+        Regex::new(r#"(?P<field>timestamp|first_used|last_used): "[^"]*""#)
+            .expect("checked construction");
+    static ref SEED_PHRASE: Regex =
+        Regex::new(r#"seed_phrase: "[^"]*""#).expect("checked construction");
+    static ref CHECKSUM: Regex = Regex::new(r#"checksum: \d+"#).expect("checked construction");
+    static ref LONG_BYTES: Regex =
+        Regex::new(r#"\[(\d+, ){64,}\d+\]"#).expect("checked construction");
+}
+
+/// Environment variable that makes [`ScenarioReport::check_snapshot`] record
+/// golden snapshots instead of checking against them.
+pub const UPDATE_SNAPSHOTS: &str = "UPDATE_SCENARIO_SNAPSHOTS";
+
+/// Scripted sequence of user actions.
+#[derive(Debug)]
+pub struct Scenario {
+    /// Scenario name, for reports
+    pub name: String,
+
+    /// Seed names known to navigator at start
+    pub seed_names: Vec<String>,
+
+    /// General verifier set in temporary database
+    pub general_verifier: Verifier,
+
+    /// Actions to replay
+    pub steps: Vec<ScenarioStep>,
+}
+
+/// Single user action in the [`Scenario`].
+#[derive(Debug)]
+pub struct ScenarioStep {
+    pub action: Action,
+    pub details: String,
+    pub seed_phrase: String,
+    pub seed_names: Option<Vec<String>>,
+    pub expect: Expectation,
+}
+
+/// Checks for the [`ScenarioStep`] result. Checks not set are skipped.
+#[derive(Debug, Default)]
+pub struct Expectation {
+    pub screen_label: Option<String>,
+    pub screen: Option<String>,
+    pub modal: Option<String>,
+    pub alert: Option<String>,
+    pub error: Option<String>,
+    pub contains: Vec<String>,
+}
+
+/// Result of the single [`ScenarioStep`].
+#[derive(Debug)]
+pub struct StepReport {
+    /// Action performed, with details
+    pub action: String,
+
+    /// Blinded text representation of the step result
+    pub snapshot: String,
+
+    /// Failed expectations, empty if the step went as expected
+    pub failures: Vec<String>,
+}
+
+/// Result of the [`Scenario`] replay.
+#[derive(Debug)]
+pub struct ScenarioReport {
+    pub name: String,
+    pub steps: Vec<StepReport>,
+}
+
+impl Scenario {
+    /// Parse scenario from JSON.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let value: Value = serde_json::from_str(json)
+            .map_err(|e| Error::ScenarioFormat(format!("Not a valid JSON: {}.", e)))?;
+        let scenario = as_object(&value, "scenario")?;
+        let name = get_string(scenario, "name")?.unwrap_or_default();
+        let seed_names = get_string_list(scenario, "seed_names")?.unwrap_or_default();
+        let general_verifier = match get_string(scenario, "general_verifier")? {
+            Some(hex_public) => {
+                let public: [u8; 32] = hex::decode(&hex_public)
+                    .ok()
+                    .and_then(|public| public.try_into().ok())
+                    .ok_or_else(|| {
+                        Error::ScenarioFormat(format!(
+                            "General verifier {} is not a sr25519 public key in hex format.",
+                            hex_public
+                        ))
+                    })?;
+                Verifier {
+                    v: Some(VerifierValue::Standard {
+                        m: MultiSigner::Sr25519(sp_core::sr25519::Public::from_raw(public)),
+                    }),
+                }
+            }
+            None => Verifier { v: None },
+        };
+        let steps = match scenario.get("steps") {
+            Some(Value::Array(steps)) => steps
+                .iter()
+                .enumerate()
+                .map(|(index, step)| ScenarioStep::from_value(index, step))
+                .collect::<Result<Vec<_>>>()?,
+            _ => {
+                return Err(Error::ScenarioFormat(
+                    "Scenario has no list of steps.".to_string(),
+                ))
+            }
+        };
+        Ok(Self {
+            name,
+            seed_names,
+            general_verifier,
+            steps,
+        })
+    }
+
+    /// Replay scenario on existing initiated cold database.
+    pub fn run(&self, dbname: &str) -> Result<ScenarioReport> {
+        self.run_in(&SledStorage::open(dbname)?)
+    }
+
+    /// Same as [`run`](Self::run), for initiated cold database in given
+    /// [`Storage`].
+    pub fn run_in<S: Storage>(&self, storage: &S) -> Result<ScenarioReport> {
+        let mut state = State::new();
+        state.init_navigation_in(storage, self.seed_names.to_owned())?;
+        let steps = self
+            .steps
+            .iter()
+            .map(|step| {
+                if let Some(ref seed_names) = step.seed_names {
+                    state.update_seed_names(seed_names.to_owned());
+                }
+                step.check(state.perform_in(storage, step.action, &step.details, &step.seed_phrase))
+            })
+            .collect();
+        Ok(ScenarioReport {
+            name: self.name.to_owned(),
+            steps,
+        })
+    }
+
+    /// Replay scenario on a fresh navigator test cold database, created in a
+    /// new temporary directory and removed after the replay, whether it
+    /// succeeded or not.
+    pub fn run_in_temporary_db(&self) -> Result<ScenarioReport> {
+        let dir = tempfile::tempdir()?;
+        let dbname = dir.path().to_str().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Temporary directory path is not valid unicode.",
+            )
+        })?;
+        populate_cold_nav_test(dbname)?;
+        init_db(dbname, self.general_verifier.to_owned())?;
+        let report = self.run(dbname)?;
+        dir.close()?;
+        Ok(report)
+    }
+
+    /// Replay scenario on a fresh navigator test cold database, kept in
+    /// memory only.
+    pub fn run_in_memory_db(&self) -> Result<ScenarioReport> {
+        let storage = MemoryStorage::new();
+        populate_cold_nav_test_in(&storage)?;
+        init_db_in(&storage, self.general_verifier.to_owned())?;
+        self.run_in(&storage)
+    }
+}
+
+impl ScenarioStep {
+    fn from_value(index: usize, value: &Value) -> Result<Self> {
+        let step = as_object(value, &format!("step {}", index))?;
+        let action_name = get_string(step, "action")?
+            .ok_or_else(|| Error::ScenarioFormat(format!("Step {} has no action.", index)))?;
+        let action = Action::from_str(&action_name)?;
+        let details = get_string(step, "details")?.unwrap_or_default();
+        let seed_phrase = get_string(step, "seed_phrase")?.unwrap_or_default();
+        let seed_names = get_string_list(step, "seed_names")?;
+        let expect = match step.get("expect") {
+            Some(value) => {
+                let expect = as_object(value, &format!("step {} expectation", index))?;
+                Expectation {
+                    screen_label: get_string(expect, "screen_label")?,
+                    screen: get_string(expect, "screen")?,
+                    modal: get_string(expect, "modal")?,
+                    alert: get_string(expect, "alert")?,
+                    error: get_string(expect, "error")?,
+                    contains: get_string_list(expect, "contains")?.unwrap_or_default(),
+                }
+            }
+            None => Expectation::default(),
+        };
+        Ok(Self {
+            action,
+            details,
+            seed_phrase,
+            seed_names,
+            expect,
+        })
+    }
+
+    fn check(&self, result: Result<ActionResult>) -> StepReport {
+        let mut failures = Vec::new();
+        let snapshot = match result {
+            Ok(action_result) => {
+                if let Some(ref screen_label) = self.expect.screen_label {
+                    if screen_label != &action_result.screen_label {
+                        failures.push(format!(
+                            "Expected screen label {:?}, got {:?}.",
+                            screen_label, action_result.screen_label
+                        ))
+                    }
+                }
+                let screen = variant_name(&action_result.screen_data);
+                let modal = variant_name(&action_result.modal_data);
+                let alert = variant_name(&action_result.alert_data);
+                for (what, expected, got) in [
+                    ("screen", &self.expect.screen, screen),
+                    ("modal", &self.expect.modal, modal),
+                    ("alert", &self.expect.alert, alert),
+                ] {
+                    if let Some(expected) = expected {
+                        if expected != &got {
+                            failures.push(format!("Expected {} {}, got {}.", what, expected, got))
+                        }
+                    }
+                }
+                if let Some(ref error) = self.expect.error {
+                    failures.push(format!("Expected error {:?}, got none.", error))
+                }
+                blind(&format!("{:?}", action_result))
+            }
+            Err(e) => {
+                let error = e.to_string();
+                match self.expect.error {
+                    Some(ref expected) if expected == &error => (),
+                    _ => failures.push(format!("Unexpected error {:?}.", error)),
+                }
+                format!("Error: {}", error)
+            }
+        };
+        for part in self.expect.contains.iter() {
+            if !snapshot.contains(part) {
+                failures.push(format!("Expected snapshot to contain {:?}.", part))
+            }
+        }
+        StepReport {
+            action: format!("{:?} {:?}", self.action, self.details),
+            snapshot,
+            failures,
+        }
+    }
+}
+
+impl ScenarioReport {
+    /// All steps went as expected.
+    pub fn is_success(&self) -> bool {
+        self.steps.iter().all(|step| step.failures.is_empty())
+    }
+
+    /// Failed expectations, with step numbers.
+    pub fn failures(&self) -> Vec<String> {
+        self.steps
+            .iter()
+            .enumerate()
+            .flat_map(|(index, step)| {
+                step.failures
+                    .iter()
+                    .map(move |failure| format!("Step {}, {}: {}", index, step.action, failure))
+            })
+            .collect()
+    }
+
+    /// Printable snapshot of the whole replay, one step per paragraph, to
+    /// compare with snapshot of previous replay.
+    pub fn snapshot(&self) -> String {
+        let mut out = format!("Scenario: {}\n", self.name);
+        for (index, step) in self.steps.iter().enumerate() {
+            out.push_str(&format!(
+                "\n{}. {}\n{}\n",
+                index, step.action, step.snapshot
+            ));
+        }
+        out
+    }
+
+    /// Check replay snapshot against golden snapshot file.
+    ///
+    /// If [`UPDATE_SNAPSHOTS`] environment variable is set, golden snapshot
+    /// file is written instead.
+    pub fn check_snapshot<P: AsRef<Path>>(&self, golden: P) -> Result<()> {
+        let golden = golden.as_ref();
+        let snapshot = self.snapshot();
+        if env::var_os(UPDATE_SNAPSHOTS).is_some() {
+            fs::write(golden, snapshot)?;
+            return Ok(());
+        }
+        let path = golden.display().to_string();
+        let expected = match fs::read_to_string(golden) {
+            Ok(expected) => expected,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Err(Error::ScenarioSnapshotMissing(path))
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let mut expected_lines = expected.lines();
+        let mut found_lines = snapshot.lines();
+        for line in 1.. {
+            match (expected_lines.next(), found_lines.next()) {
+                (None, None) => break,
+                (expected, found) if expected == found => (),
+                (expected, found) => {
+                    return Err(Error::ScenarioSnapshotMismatch {
+                        path,
+                        line,
+                        expected: expected.unwrap_or_default().to_string(),
+                        found: found.unwrap_or_default().to_string(),
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Blind values that change from run to run.
+fn blind(snapshot: &str) -> String {
+    let snapshot = TIMESTAMP.replace_all(snapshot, r#"$field: "**""#);
+    let snapshot = SEED_PHRASE.replace_all(&snapshot, r#"seed_phrase: "**""#);
+    let snapshot = CHECKSUM.replace_all(&snapshot, "checksum: **");
+    LONG_BYTES.replace_all(&snapshot, "[**]").to_string()
+}
+
+/// Enum variant name from its `Debug` representation.
+fn variant_name<T: std::fmt::Debug>(value: &T) -> String {
+    let debug = format!("{:?}", value);
+    let debug = debug
+        .strip_prefix("Some(")
+        .map(|inner| inner.to_string())
+        .unwrap_or(debug);
+    debug
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn as_object<'a>(value: &'a Value, what: &str) -> Result<&'a Map<String, Value>> {
+    value
+        .as_object()
+        .ok_or_else(|| Error::ScenarioFormat(format!("Expected {} to be an object.", what)))
+}
+
+fn get_string(object: &Map<String, Value>, key: &str) -> Result<Option<String>> {
+    match object.get(key) {
+        Some(Value::String(s)) => Ok(Some(s.to_owned())),
+        Some(_) => Err(Error::ScenarioFormat(format!(
+            "Expected {} to be a string.",
+            key
+        ))),
+        None => Ok(None),
+    }
+}
+
+fn get_string_list(object: &Map<String, Value>, key: &str) -> Result<Option<Vec<String>>> {
+    match object.get(key) {
+        Some(Value::Array(list)) => list
+            .iter()
+            .map(|element| match element {
+                Value::String(s) => Ok(s.to_owned()),
+                _ => Err(Error::ScenarioFormat(format!(
+                    "Expected {} to be a list of strings.",
+                    key
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(Some),
+        Some(_) => Err(Error::ScenarioFormat(format!(
+            "Expected {} to be a list of strings.",
+            key
+        ))),
+        None => Ok(None),
+    }
+}
@@ -4,15 +4,16 @@ use zeroize::Zeroize;
 
 use crate::error::Result;
 use db_handling::{
-    helpers::get_address_details,
-    interface_signer::{first_network, SeedDraft},
+    helpers::get_address_details_in,
+    interface_signer::{first_network_in, SeedDraft},
+    storage::Storage,
 };
 use definitions::{
     helpers::{make_identicon_from_multisigner, multisigner_to_public},
     keyring::{AddressKey, NetworkSpecsKey},
     navigation::{Address, TransactionCardSet},
     network_specs::NetworkSpecs,
-    users::AddressDetails,
+    users::{AddressDetails, KeyAnnotation},
 };
 use transaction_parsing;
 use transaction_signing;
@@ -24,6 +25,7 @@ const MAX_COUNT_SET: u8 = 3;
 pub enum Screen {
     Log,
     LogDetails(u32),
+    LogSearch(LogSearchState),
     Scan,
     Transaction(Box<TransactionState>),
     SeedSelector,
@@ -57,6 +59,7 @@ pub struct KeysState {
     seed_name: String,
     network_specs_key: NetworkSpecsKey,
     specialty: SpecialtyKeysState,
+    query: Option<String>,
 }
 
 ///State of screen with 1 key
@@ -103,6 +106,13 @@ pub struct SufficientCryptoState {
     counter: u8,
 }
 
+///State of history log search screen
+#[derive(Debug, Clone)]
+pub struct LogSearchState {
+    query: String,
+    page: u32,
+}
+
 ///State of screen recover seed phrase
 #[derive(Debug, Clone)]
 pub struct RecoverSeedPhraseState {
@@ -116,8 +126,8 @@ pub struct RecoverSeedPhraseState {
 pub struct EnteredInfo(pub String);
 
 impl KeysState {
-    pub fn new(seed_name: &str, database_name: &str) -> Result<Self> {
-        let network_specs = first_network(database_name)?;
+    pub fn new<S: Storage>(seed_name: &str, storage: &S) -> Result<Self> {
+        let network_specs = first_network_in(storage)?;
         Ok(Self {
             seed_name: seed_name.to_string(),
             network_specs_key: NetworkSpecsKey::from_parts(
@@ -125,6 +135,7 @@ impl KeysState {
                 &network_specs.encryption,
             ),
             specialty: SpecialtyKeysState::None,
+            query: None,
         })
     }
     pub fn new_in_network(seed_name: &str, network_specs_key: &NetworkSpecsKey) -> Self {
@@ -132,6 +143,7 @@ impl KeysState {
             seed_name: seed_name.to_string(),
             network_specs_key: network_specs_key.to_owned(),
             specialty: SpecialtyKeysState::None,
+            query: None,
         }
     }
     pub fn change_network(&self, network_specs_key: &NetworkSpecsKey) -> Self {
@@ -139,6 +151,7 @@ impl KeysState {
             seed_name: self.seed_name(),
             network_specs_key: network_specs_key.to_owned(),
             specialty: SpecialtyKeysState::None,
+            query: None,
         }
     }
     pub fn seed_name(&self) -> String {
@@ -166,6 +179,7 @@ impl KeysState {
             seed_name: self.seed_name(),
             network_specs_key: self.network_specs_key(),
             specialty,
+            query: self.query(),
         }
     }
     pub fn select_single(&self, multisigner: &MultiSigner) -> Self {
@@ -191,6 +205,7 @@ impl KeysState {
             seed_name: self.seed_name(),
             network_specs_key: self.network_specs_key(),
             specialty,
+            query: self.query(),
         }
     }
     pub fn select_set(&self, set: Vec<MultiSigner>) -> Self {
@@ -198,6 +213,7 @@ impl KeysState {
             seed_name: self.seed_name(),
             network_specs_key: self.network_specs_key(),
             specialty: SpecialtyKeysState::MultiSelect(set),
+            query: self.query(),
         }
     }
     pub fn get_swiped_key(&self) -> Option<MultiSigner> {
@@ -222,17 +238,34 @@ impl KeysState {
             seed_name: self.seed_name(),
             network_specs_key: self.network_specs_key(),
             specialty: SpecialtyKeysState::None,
+            query: self.query(),
+        }
+    }
+    pub fn query(&self) -> Option<String> {
+        self.query.to_owned()
+    }
+    /// Filter keys by path, label, tags or notes; empty query shows all keys.
+    pub fn search(&self, query: &str) -> Self {
+        let query = query.trim();
+        Self {
+            seed_name: self.seed_name(),
+            network_specs_key: self.network_specs_key(),
+            specialty: SpecialtyKeysState::None,
+            query: (!query.is_empty()).then(|| query.to_string()),
         }
     }
 }
 
 impl AddressState {
-    pub fn new(hex_address_key: &str, keys_state: &KeysState, database_name: &str) -> Result<Self> {
+    pub fn new<S: Storage>(
+        hex_address_key: &str,
+        keys_state: &KeysState,
+        storage: &S,
+    ) -> Result<Self> {
         let address_key = AddressKey::from_hex(hex_address_key)?;
         let multisigner = address_key.multi_signer()?;
         let is_root =
-            get_address_details(database_name, &AddressKey::from_multisigner(&multisigner))?
-                .is_root();
+            get_address_details_in(storage, &AddressKey::from_multisigner(&multisigner))?.is_root();
         Ok(Self {
             seed_name: keys_state.seed_name(),
             network_specs_key: keys_state.network_specs_key(),
@@ -245,6 +278,7 @@ impl AddressState {
             seed_name: self.seed_name(),
             network_specs_key: self.network_specs_key(),
             specialty: SpecialtyKeysState::None,
+            query: None,
         }
     }
     pub fn seed_name(&self) -> String {
@@ -262,16 +296,16 @@ impl AddressState {
 }
 
 impl AddressStateMulti {
-    pub fn new(
+    pub fn new<S: Storage>(
         seed_name: String,
         network_specs_key: NetworkSpecsKey,
         multiselect: &[MultiSigner],
-        database_name: &str,
+        storage: &S,
     ) -> Result<Self> {
         let mut set: Vec<(MultiSigner, bool)> = Vec::new();
         for multisigner in multiselect.iter() {
             let address_details =
-                get_address_details(database_name, &AddressKey::from_multisigner(multisigner))?;
+                get_address_details_in(storage, &AddressKey::from_multisigner(multisigner))?;
             set.push((multisigner.to_owned(), address_details.is_root()))
         }
         Ok(Self {
@@ -286,6 +320,7 @@ impl AddressStateMulti {
             seed_name: self.seed_name(),
             network_specs_key: self.network_specs_key(),
             specialty: SpecialtyKeysState::None,
+            query: None,
         }
     }
     pub fn seed_name(&self) -> String {
@@ -358,6 +393,7 @@ impl DeriveState {
             seed_name: self.keys_state.seed_name(),
             network_specs_key: self.keys_state.network_specs_key(),
             specialty: SpecialtyKeysState::None,
+            query: None,
         }
     }
     pub fn seed_name(&self) -> String {
@@ -393,10 +429,10 @@ impl DeriveState {
 }
 
 impl TransactionState {
-    pub fn new(details_str: &str, dbname: &str) -> Self {
+    pub fn new<S: Storage>(details_str: &str, storage: &S) -> Self {
         Self {
             entered_info: EnteredInfo("".to_string()),
-            action: transaction_parsing::produce_output(details_str, dbname),
+            action: transaction_parsing::produce_output_in(details_str, storage),
             comment: "".to_string(),
             counter: 1,
         }
@@ -475,6 +511,7 @@ impl SufficientCryptoState {
         &self,
         multisigner: &MultiSigner,
         address_details: &AddressDetails,
+        annotation: Option<KeyAnnotation>,
         new_secret_string: &str,
     ) -> Self {
         let identicon = make_identicon_from_multisigner(multisigner);
@@ -486,6 +523,7 @@ impl SufficientCryptoState {
             has_pwd: address_details.has_pwd,
             multiselect: None,
             secret_exposed: address_details.secret_exposed,
+            annotation,
         };
         Self {
             key_selected: Some((
@@ -517,6 +555,38 @@ impl SufficientCryptoState {
     }
 }
 
+impl LogSearchState {
+    pub fn new(query: &str) -> Self {
+        Self {
+            query: query.to_string(),
+            page: 0,
+        }
+    }
+    pub fn query(&self) -> String {
+        self.query.to_string()
+    }
+    pub fn page(&self) -> u32 {
+        self.page
+    }
+    pub fn next(&self, total_pages: u32) -> Self {
+        let page = if self.page + 1 < total_pages {
+            self.page + 1
+        } else {
+            self.page
+        };
+        Self {
+            query: self.query(),
+            page,
+        }
+    }
+    pub fn previous(&self) -> Self {
+        Self {
+            query: self.query(),
+            page: self.page.saturating_sub(1),
+        }
+    }
+}
+
 impl RecoverSeedPhraseState {
     pub fn new(seed_name: &str) -> Self {
         Self {
@@ -544,6 +614,7 @@ impl Screen {
         match self {
             Screen::Log => Some(String::from("Log")),
             Screen::LogDetails(_) => Some(String::from("LogDetails")),
+            Screen::LogSearch(_) => Some(String::from("LogSearch")),
             Screen::Scan => Some(String::from("Scan")),
             Screen::Transaction(_) => Some(String::from("Transaction")),
             Screen::SeedSelector => Some(String::from("SeedSelector")),
@@ -569,6 +640,7 @@ impl Screen {
         match self {
             Screen::Log => "",
             Screen::LogDetails(_) => "Event details",
+            Screen::LogSearch(_) => "Search log",
             Screen::Scan => "",
             Screen::Transaction(_) => "",
             Screen::SeedSelector => "Select seed",
@@ -600,6 +672,7 @@ impl Screen {
             | Screen::Nowhere => false,
             Screen::Transaction(_)
             | Screen::LogDetails(_)
+            | Screen::LogSearch(_)
             | Screen::Keys(_)
             | Screen::KeyDetails(_)
             | Screen::KeyDetailsMulti(_)
@@ -638,6 +711,7 @@ mod tests {
             network_id: Vec::new(),
             encryption: Encryption::Sr25519,
             secret_exposed: false,
+            usage: Vec::new(),
         }
     }
 
@@ -654,6 +728,7 @@ mod tests {
             let sufficient_crypto_test_updated = sufficient_crypto_test.update(
                 &MultiSigner::Sr25519(Public::from_raw(PUBLIC)),
                 &test_address_details(),
+                None,
                 mock_seed,
             );
             secret_ptr = unsafe {
@@ -1,5 +1,6 @@
 use image::{GenericImageView, GrayImage, ImageBuffer, Pixel};
 use lazy_static::lazy_static;
+use parity_scale_codec::Encode;
 use regex::Regex;
 use sp_core::{blake2_256, Pair, H256};
 use sp_runtime::MultiSigner;
@@ -16,32 +17,46 @@ use constants::{
     },
     ALICE_SEED_PHRASE,
 };
-use db_handling::cold_default::{init_db, populate_cold_nav_test};
+use constants::{DANGER, POLICY, SCHEMA_VERSION};
+use db_handling::{
+    cold_default::{init_db, populate_cold_nav_test},
+    db_transactions::TrDbCold,
+    manage_history::get_history,
+    migrations::{get_schema_version, LIVE_SCHEMA_VERSION},
+    storage::Batch,
+};
 use definitions::{
     crypto::Encryption,
     history::{
         Event, IdentityHistory, MetaValuesDisplay, MetaValuesExport, NetworkSpecsDisplay,
-        NetworkSpecsExport, SignDisplay, SignMessageDisplay, TypesDisplay, TypesExport,
+        NetworkSpecsExport, NetworkSpecsTokensDisplay, SignDisplay, SignMessageDisplay,
+        TypesDisplay, TypesExport,
     },
+    localisation::LocalisedMessage,
     navigation::{
         ActionResult, Address, AlertData, Card, DerivationCheck, DerivationDestination,
         DerivationEntry, DerivationPack, FooterButton, History, MBackup, MDeriveKey,
         MEnterPassword, MEventMaybeDecoded, MKeyDetails, MKeyDetailsMulti, MKeys, MKeysCard, MLog,
-        MLogDetails, MLogRight, MMMNetwork, MMNetwork, MManageMetadata, MManageNetworks,
-        MMetadataRecord, MNetworkCard, MNetworkDetails, MNetworkMenu, MNewSeed, MNewSeedBackup,
-        MPasswordConfirm, MRawKey, MRecoverSeedName, MRecoverSeedPhrase, MSCCall, MSCContent,
-        MSCCurrency, MSCEnumVariantName, MSCEraMortal, MSCFieldName, MSCId, MSCNameVersion,
-        MSCNetworkInfo, MSeedKeyCard, MSeedMenu, MSeeds, MSettings, MSignSufficientCrypto,
-        MSignatureReady, MSufficientCryptoReady, MTransaction, MTypesInfo, MVerifier,
-        MVerifierDetails, ModalData, Network, NetworkSpecsToSend, RightButton, ScreenData,
-        ScreenNameType, SeedNameCard, TransactionCard, TransactionCardSet, TransactionType,
+        MLogDetails, MLogRight, MLogSearch, MMMNetwork, MMNetwork, MManageMetadata,
+        MManageNetworks, MMetadataRecord, MNetworkCard, MNetworkDetails, MNetworkMenu, MNewSeed,
+        MNewSeedBackup, MPasswordConfirm, MRawKey, MRecoverSeedName, MRecoverSeedPhrase, MSCCall,
+        MSCContent, MSCCurrency, MSCEnumVariantName, MSCEraMortal, MSCFieldName, MSCId,
+        MSCNameVersion, MSCNetworkInfo, MSeedKeyCard, MSeedMenu, MSeeds, MSettings,
+        MSignSufficientCrypto, MSignatureReady, MSufficientCryptoReady, MTransaction, MTypesInfo,
+        MVerifier, MVerifierDetails, ModalData, Network, NetworkSpecsToSend, RightButton,
+        ScreenData, ScreenNameType, SeedNameCard, TransactionCard, TransactionCardSet,
+        TransactionType,
     },
     network_specs::{NetworkSpecs, ValidCurrentVerifier, Verifier, VerifierValue},
 };
 
 use pretty_assertions::assert_eq;
 
-use crate::{do_action, init_navigation, update_seed_names, Action};
+use crate::scenario::{Scenario, UPDATE_SNAPSHOTS};
+use crate::{
+    close_session, do_session_action, init_session, session, update_session_seed_names, Action,
+    Error, SESSIONS,
+};
 
 const ALICE: [u8; 32] = [
     212, 53, 147, 199, 21, 253, 211, 28, 97, 20, 26, 189, 4, 169, 159, 214, 130, 44, 133, 88, 133,
@@ -230,9 +245,14 @@ fn erase_log_timestamps(log: &mut ScreenData) {
         ScreenData::LogDetails { f } => {
             f.timestamp = String::new();
         }
+        ScreenData::LogSearch { f } => {
+            for entry in f.log.iter_mut() {
+                entry.timestamp = String::new();
+            }
+        }
         _ => {
             panic!(
-                "expected SreenData::Log or ScreenData::LogDetails got {:?}",
+                "expected SreenData::Log, ScreenData::LogDetails or ScreenData::LogSearch got {:?}",
                 log
             );
         }
@@ -292,9 +312,11 @@ fn flow_test_1() {
     let dbname = "for_tests/flow_test_1";
     populate_cold_nav_test(dbname).unwrap();
     init_db(dbname, verifier_alice_sr25519()).unwrap();
-    init_navigation(dbname, Vec::new());
+    let app = init_session(dbname, Vec::new()).unwrap();
 
-    let action = do_action(Action::Start, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::Start, "", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "Select seed".to_string(),
         back: false,
@@ -314,7 +336,9 @@ fn flow_test_1() {
 
     let mut seed_selector_action = action;
 
-    let mut action = do_action(Action::NavbarLog, "", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::NavbarLog, "", "")
+        .unwrap()
+        .unwrap();
 
     erase_log_timestamps(&mut action.screen_data);
 
@@ -353,7 +377,9 @@ fn flow_test_1() {
 
     let mut current_log_action = action;
 
-    let mut action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
 
     erase_log_timestamps(&mut action.screen_data);
 
@@ -362,7 +388,9 @@ fn flow_test_1() {
         "GoBack on Log screen with no modals. Expected to remain where was.",
     );
 
-    let mut action = do_action(Action::GoForward, "", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::GoForward, "", "")
+        .unwrap()
+        .unwrap();
     erase_log_timestamps(&mut action.screen_data);
 
     assert_eq!(
@@ -370,7 +398,7 @@ fn flow_test_1() {
         "GoForward on Log screen with no modals. Expected to remain where was.",
     );
 
-    let mut action = do_action(Action::RightButtonAction, "", "")
+    let mut action = do_session_action(app, Action::RightButtonAction, "", "")
         .unwrap()
         .unwrap();
     erase_log_timestamps(&mut action.screen_data);
@@ -416,7 +444,9 @@ fn flow_test_1() {
         "RightButton on Log screen with no modals. Expected same Log screen with LogRight modal"
     );
 
-    let mut action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     erase_log_timestamps(&mut action.screen_data);
 
     assert_eq!(
@@ -424,11 +454,11 @@ fn flow_test_1() {
         "GoBack on Log screen with LogRight modal. Expected to get Log screen with no modals"
     );
 
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::RightButtonAction, "", "")
         .unwrap()
         .unwrap();
 
-    let mut action = do_action(Action::CreateLogComment, "", "")
+    let mut action = do_session_action(app, Action::CreateLogComment, "", "")
         .unwrap()
         .unwrap();
     erase_log_timestamps(&mut action.screen_data);
@@ -463,7 +493,9 @@ fn flow_test_1() {
 
     assert_eq!(action, expected_action,
             "CreateLogComment on Log screen with LogRight modal. Expected same Log screen with LogComment modal");
-    let mut action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     erase_log_timestamps(&mut action.screen_data);
 
     assert_eq!(
@@ -471,13 +503,13 @@ fn flow_test_1() {
         "GoBack on Log screen with LogComment modal. Expected same Log screen with no modals"
     );
 
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::RightButtonAction, "", "")
         .unwrap()
         .unwrap();
-    do_action(Action::CreateLogComment, "", "")
+    do_session_action(app, Action::CreateLogComment, "", "")
         .unwrap()
         .unwrap();
-    let mut action = do_action(Action::GoForward, "Remember this moment", "")
+    let mut action = do_session_action(app, Action::GoForward, "Remember this moment", "")
         .unwrap()
         .unwrap();
     erase_log_timestamps(&mut action.screen_data);
@@ -522,7 +554,112 @@ fn flow_test_1() {
 
     assert_eq!(action, expected_action, "GoForward on Log screen with LogComment modal. Expected updated Log screen with no modals.");
 
-    let mut action = do_action(Action::Shield, "", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::SearchLog, "text=remember", "")
+        .unwrap()
+        .unwrap();
+    erase_log_timestamps(&mut action.screen_data);
+    let log_search_action = ActionResult {
+        screen_label: "Search log".to_string(),
+        back: true,
+        footer: true,
+        footer_button: Some(FooterButton::Log),
+        right_button: None,
+        screen_name_type: ScreenNameType::H4,
+        screen_data: ScreenData::LogSearch {
+            f: MLogSearch {
+                query: "text=remember".to_string(),
+                page: 0,
+                total_pages: 1,
+                log: vec![History {
+                    order: 1,
+                    timestamp: String::new(),
+                    events: vec![Event::UserEntry {
+                        user_entry: "Remember this moment".to_string(),
+                    }],
+                }],
+            },
+        },
+        modal_data: None,
+        alert_data: None,
+    };
+    assert_eq!(
+        action, log_search_action,
+        "SearchLog on Log screen. Expected LogSearch screen with matching entries only."
+    );
+
+    let mut action = do_session_action(app, Action::NextUnit, "", "")
+        .unwrap()
+        .unwrap();
+    erase_log_timestamps(&mut action.screen_data);
+    assert_eq!(
+        action, log_search_action,
+        "NextUnit on LogSearch screen with single page. Expected to remain where was."
+    );
+
+    let mut action = do_session_action(app, Action::PreviousUnit, "", "")
+        .unwrap()
+        .unwrap();
+    erase_log_timestamps(&mut action.screen_data);
+    assert_eq!(
+        action, log_search_action,
+        "PreviousUnit on LogSearch screen first page. Expected to remain where was."
+    );
+
+    let mut action = do_session_action(app, Action::SearchLog, "kind=GeneralVerifierSet", "")
+        .unwrap()
+        .unwrap();
+    erase_log_timestamps(&mut action.screen_data);
+    let mut expected_search_action = log_search_action.clone();
+    expected_search_action.screen_data = ScreenData::LogSearch {
+        f: MLogSearch {
+            query: "kind=GeneralVerifierSet".to_string(),
+            page: 0,
+            total_pages: 1,
+            log: vec![History {
+                order: 0,
+                timestamp: String::new(),
+                events: vec![Event::GeneralVerifierSet {
+                    verifier: Verifier {
+                        v: Some(VerifierValue::Standard {
+                            m: sr_multisigner_from_hex(hex),
+                        }),
+                    },
+                }],
+            }],
+        },
+    };
+    assert_eq!(
+        action, expected_search_action,
+        "SearchLog on LogSearch screen. Expected LogSearch screen with new query results."
+    );
+
+    let mut action = do_session_action(app, Action::SearchLog, "nonsense", "")
+        .unwrap()
+        .unwrap();
+    erase_log_timestamps(&mut action.screen_data);
+    expected_search_action.alert_data = Some(AlertData::ErrorData {
+        f: "Unable to interpret history search query part nonsense.".to_string(),
+    });
+    assert_eq!(
+        action, expected_search_action,
+        "SearchLog with invalid query. Expected same LogSearch screen with error."
+    );
+
+    do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
+    let mut action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
+    erase_log_timestamps(&mut action.screen_data);
+    assert_eq!(
+        action, expected_action,
+        "GoBack on LogSearch screen. Expected Log screen with full log."
+    );
+
+    let mut action = do_session_action(app, Action::Shield, "", "")
+        .unwrap()
+        .unwrap();
     erase_log_timestamps(&mut action.screen_data);
 
     expected_action.alert_data = Some(AlertData::Shield { f: None });
@@ -532,10 +669,12 @@ fn flow_test_1() {
         "Shield on Log screen with no modal. Expected same Log screen with Shield alert.",
     );
 
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::RightButtonAction, "", "")
+        .unwrap()
+        .unwrap();
+    let mut action = do_session_action(app, Action::ClearLog, "", "")
         .unwrap()
         .unwrap();
-    let mut action = do_action(Action::ClearLog, "", "").unwrap().unwrap();
     erase_log_timestamps(&mut action.screen_data);
 
     expected_action.screen_data = ScreenData::Log {
@@ -556,7 +695,9 @@ fn flow_test_1() {
         "ClearLog on Log screen with LogRight modal. Expected updated Log screen with no modals."
     );
 
-    let action = do_action(Action::NavbarSettings, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::NavbarSettings, "", "")
+        .unwrap()
+        .unwrap();
 
     let expected_action = ActionResult {
         screen_label: String::new(),
@@ -585,7 +726,9 @@ fn flow_test_1() {
 
     let current_settings_action = action;
 
-    let action = do_action(Action::BackupSeed, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::BackupSeed, "", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "Select seed".to_string(),
         back: true,
@@ -607,13 +750,15 @@ fn flow_test_1() {
         "BackupSeed on Settings screen. Expected SelectSeedForBackup screen with no modals."
     );
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, current_settings_action,
         "GoBack on SelectSeedForBackup screen with no seeds available. Expected Settings screen with no modals."
     );
 
-    let action = do_action(Action::ViewGeneralVerifier, "", "")
+    let action = do_session_action(app, Action::ViewGeneralVerifier, "", "")
         .unwrap()
         .unwrap();
 
@@ -640,13 +785,17 @@ fn flow_test_1() {
         "ViewGeneralVerifier on Settings screen. Expected Verifier screen with no modals.",
     );
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, current_settings_action,
         "GoBack on Verifier screen. Expected Settings screen with no modals.",
     );
 
-    let action = do_action(Action::ShowDocuments, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::ShowDocuments, "", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "ABOUT".to_string(),
         back: true,
@@ -663,13 +812,17 @@ fn flow_test_1() {
         "ShowDocuments on Settings screen. Expected Documents screen with no modals.",
     );
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, current_settings_action,
         "GoBack on Documents screen. Expected Settings screen with no modals.",
     );
 
-    let action = do_action(Action::ManageNetworks, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::ManageNetworks, "", "")
+        .unwrap()
+        .unwrap();
 
     let expected_action = ActionResult {
         screen_label: "MANAGE NETWORKS".to_string(),
@@ -716,14 +869,19 @@ fn flow_test_1() {
 
     let mut manage_networks_action = action;
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, current_settings_action,
         "GoBack on ManageNetworks screen. Expected Settings screen with no modals.",
     );
 
-    do_action(Action::ManageNetworks, "", "").unwrap().unwrap();
-    let action = do_action(
+    do_session_action(app, Action::ManageNetworks, "", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(
+        app,
         Action::GoForward,
         "01b0a8d493285c2df73290dfb7e61f870f17b41801197a149ca93654499ea3dafe",
         "",
@@ -783,19 +941,22 @@ fn flow_test_1() {
 
     let kusama_action = action;
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, manage_networks_action,
         "GoBack on NetworkDetails screen. Expected ManageNetworks screen with no modals.",
     );
 
-    do_action(
+    do_session_action(
+        app,
         Action::GoForward,
         "01b0a8d493285c2df73290dfb7e61f870f17b41801197a149ca93654499ea3dafe",
         "",
     )
     .unwrap();
-    let action = do_action(Action::ManageMetadata, "9130", "")
+    let action = do_session_action(app, Action::ManageMetadata, "9130", "")
         .unwrap()
         .unwrap();
 
@@ -817,7 +978,9 @@ fn flow_test_1() {
     });
     assert_eq!(action, kusama_action_modal, "ManageMetadata on NetworkDetails screen for kusama sr25519 key. Expected NetworkDetails screen for kusama with ManageMetadata modal");
 
-    let action = do_action(Action::SignMetadata, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::SignMetadata, "", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "Sign SufficientCrypto".to_string(),
         back: true,
@@ -834,16 +997,20 @@ fn flow_test_1() {
     assert_eq!(action, expected_action, "SignMetadata on NetworkDetails screen for kusama sr25519 key with ManageMetadata modal for version 9130. Expected SignSufficientCrypto screen for kusama9130 metadata with no modals");
     let sign_sufficient_crypto_action = expected_action;
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, kusama_action,
         "GoBack on SignSufficientCrypto screen. Expected NetworkDetails screen with no modals."
     );
 
-    do_action(Action::ManageMetadata, "9130", "")
+    do_session_action(app, Action::ManageMetadata, "9130", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(app, Action::RemoveMetadata, "", "")
         .unwrap()
         .unwrap();
-    let action = do_action(Action::RemoveMetadata, "", "").unwrap().unwrap();
     let expected_action = ActionResult {
         screen_label: "Network details".to_string(),
         back: true,
@@ -888,14 +1055,14 @@ fn flow_test_1() {
 
     let kusama_action = action;
 
-    let action = do_action(Action::RightButtonAction, "", "")
+    let action = do_session_action(app, Action::RightButtonAction, "", "")
         .unwrap()
         .unwrap();
     let mut expected_action = kusama_action.clone();
     expected_action.modal_data = Some(ModalData::NetworkDetailsMenu);
     assert_eq!(action, expected_action, "RightButton on NetworkDetails screen for kusama sr25519 key. Expected NetworkDetails screen for kusama with NetworkDetailsMenu modal");
 
-    let action = do_action(Action::SignNetworkSpecs, "", "")
+    let action = do_session_action(app, Action::SignNetworkSpecs, "", "")
         .unwrap()
         .unwrap();
     let expected_action = ActionResult {
@@ -914,16 +1081,20 @@ fn flow_test_1() {
 
     assert_eq!(action, expected_action, "SignNetworkSpecs on NetworkDetails screen for kusama sr25519 key with NetworkDetailsMenu modal. Expected SignSufficientCrypto screen for kusama specs with no modals.");
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, kusama_action,
         "GoBack on SignSufficientCrypto screen. Expected NetworkDetails screen with no modals."
     );
 
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::RightButtonAction, "", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(app, Action::RemoveNetwork, "", "")
         .unwrap()
         .unwrap();
-    let action = do_action(Action::RemoveNetwork, "", "").unwrap().unwrap();
     let expected_action = ActionResult {
         screen_label: "MANAGE NETWORKS".to_string(),
         back: true,
@@ -959,7 +1130,7 @@ fn flow_test_1() {
         "RemoveNetwork on NetworkDetails screen for kusama sr25519. Expected updated ManageNetworks screen with no modals"
     );
 
-    let action = do_action(Action::RightButtonAction, "", "")
+    let action = do_session_action(app, Action::RightButtonAction, "", "")
         .unwrap()
         .unwrap();
     let mut expected_action = expected_action;
@@ -979,7 +1150,9 @@ fn flow_test_1() {
         "RightButton on ManageNetworks screen. Expected ManageNetworks screen with TypesInfo modal"
     );
 
-    let action = do_action(Action::SignTypes, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::SignTypes, "", "")
+        .unwrap()
+        .unwrap();
 
     let expected_action = sign_sufficient_crypto_action;
     assert_eq!(
@@ -987,17 +1160,23 @@ fn flow_test_1() {
         "SignTypes on ManageNetworks screen with TypesInfo modal. Expected SignSufficientCrypto screen for types with no modals."
     );
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, current_settings_action,
         "GoBack on SignSufficientCrypto screen. Expected Settings screen with no modals",
     );
 
-    do_action(Action::ManageNetworks, "", "").unwrap().unwrap();
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::ManageNetworks, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::RightButtonAction, "", "")
+        .unwrap()
+        .unwrap();
+    let mut action = do_session_action(app, Action::RemoveTypes, "", "")
         .unwrap()
         .unwrap();
-    let mut action = do_action(Action::RemoveTypes, "", "").unwrap().unwrap();
     erase_log_timestamps(&mut action.screen_data);
 
     let mut expected_action = log_action.clone();
@@ -1042,6 +1221,7 @@ fn flow_test_1() {
                                 path_id: "//kusama".to_string(),
                                 secondary_color: "#262626".to_string(),
                                 title: "Kusama".to_string(),
+                                tokens: Vec::new(),
                                 unit: "KSM".to_string(),
                             },
                             valid_current_verifier: ValidCurrentVerifier::General,
@@ -1070,7 +1250,9 @@ fn flow_test_1() {
 
     current_log_action = action;
 
-    let mut action = do_action(Action::ShowLogDetails, "2", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::ShowLogDetails, "2", "")
+        .unwrap()
+        .unwrap();
     erase_log_timestamps(&mut action.screen_data);
 
     let genesis_hash = "b0a8d493285c2df73290dfb7e61f870f17b41801197a149ca93654499ea3dafe";
@@ -1099,6 +1281,7 @@ fn flow_test_1() {
                                 path_id: "//kusama".to_string(),
                                 secondary_color: "#262626".to_string(),
                                 title: "Kusama".to_string(),
+                                tokens: Vec::new(),
                                 unit: "KSM".to_string(),
                             },
                             valid_current_verifier: ValidCurrentVerifier::General,
@@ -1123,17 +1306,21 @@ fn flow_test_1() {
         "ShowLogDetails on Log screen with order 2. Expected LogDetails screen with no modals"
     );
 
-    let mut action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     erase_log_timestamps(&mut action.screen_data);
     assert_eq!(
         action, current_log_action,
         "GoBack on ShowLogDetails screen. Expected Log screen with no modals.",
     );
 
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::RightButtonAction, "", "")
+        .unwrap()
+        .unwrap();
+    let mut action = do_session_action(app, Action::ClearLog, "", "")
         .unwrap()
         .unwrap();
-    let mut action = do_action(Action::ClearLog, "", "").unwrap().unwrap();
     erase_log_timestamps(&mut action.screen_data);
 
     expected_action.screen_label = "".to_string();
@@ -1153,7 +1340,9 @@ fn flow_test_1() {
         "ClearLog on Log screen with LogRight modal. Expected updated Log screen with no modals."
     );
 
-    let action = do_action(Action::NavbarScan, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::NavbarScan, "", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: String::new(),
         back: false,
@@ -1172,7 +1361,8 @@ fn flow_test_1() {
 
     let scan_action = action;
 
-    let action = do_action(
+    let action = do_session_action(
+        app,
         Action::TransactionFetched,
         std::fs::read_to_string("for_tests/add_specs_kusama-sr25519_Alice-sr25519.txt")
             .unwrap()
@@ -1219,6 +1409,7 @@ fn flow_test_1() {
                                 path_id: "//kusama".to_string(),
                                 secondary_color: "#262626".to_string(),
                                 title: "Kusama".to_string(),
+                                tokens: Vec::new(),
                                 unit: "KSM".to_string(),
                             },
                         },
@@ -1235,13 +1426,16 @@ fn flow_test_1() {
     };
     assert_eq!(action, expected_action, "TransactionFetched on Scan screen with add_specs info for kusama. Expected Transaction screen with no modals");
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, scan_action,
         "GoBack on Transaction screen. Expected Scan screen with no modals.",
     );
 
-    do_action(
+    do_session_action(
+        app,
         Action::TransactionFetched,
         std::fs::read_to_string("for_tests/add_specs_kusama-sr25519_Alice-sr25519.txt")
             .unwrap()
@@ -1249,7 +1443,9 @@ fn flow_test_1() {
         "",
     )
     .unwrap();
-    let action = do_action(Action::GoForward, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoForward, "", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "Network details".to_string(),
         back: true,
@@ -1296,7 +1492,9 @@ fn flow_test_1() {
         "GoForward on Transaction screen with add specs stub. Expected NetworkDetails screen for kusama sr25519, with no modals"
     );
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "MANAGE NETWORKS".to_string(),
         back: true,
@@ -1338,10 +1536,14 @@ fn flow_test_1() {
 
     manage_networks_action = action;
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(action, current_settings_action, "GoBack on ManageNetworks screen, to see footer. Expected known Settings screen with no modals.");
 
-    let mut action = do_action(Action::NavbarLog, "", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::NavbarLog, "", "")
+        .unwrap()
+        .unwrap();
     erase_log_timestamps(&mut action.screen_data);
 
     let mut expected_action = log_action.clone();
@@ -1352,28 +1554,32 @@ fn flow_test_1() {
                 History {
                     order: 1,
                     timestamp: String::new(),
-                    events: vec![Event::NetworkSpecsAdded {
-                        network_specs_display: NetworkSpecsDisplay {
-                            specs: NetworkSpecs {
-                                base58prefix: 2,
-                                color: "#000".to_string(),
-                                decimals: 12,
-                                encryption: Encryption::Sr25519,
-                                genesis_hash: H256::from_str(hhh).unwrap(),
-                                logo: "kusama".to_string(),
-                                name: "kusama".to_string(),
-                                order: 2,
-                                path_id: "//kusama".to_string(),
-                                secondary_color: "#262626".to_string(),
-                                title: "Kusama".to_string(),
-                                unit: "KSM".to_string(),
-                            },
-                            valid_current_verifier: ValidCurrentVerifier::General,
-                            general_verifier: Verifier {
-                                v: Some(VerifierValue::Standard {
-                                    m: sr_multisigner_from_hex(hex_2),
-                                }),
+                    events: vec![Event::NetworkSpecsTokensAdded {
+                        network_specs_tokens_display: NetworkSpecsTokensDisplay {
+                            network_specs_display: NetworkSpecsDisplay {
+                                specs: NetworkSpecs {
+                                    base58prefix: 2,
+                                    color: "#000".to_string(),
+                                    decimals: 12,
+                                    encryption: Encryption::Sr25519,
+                                    genesis_hash: H256::from_str(hhh).unwrap(),
+                                    logo: "kusama".to_string(),
+                                    name: "kusama".to_string(),
+                                    order: 2,
+                                    path_id: "//kusama".to_string(),
+                                    secondary_color: "#262626".to_string(),
+                                    title: "Kusama".to_string(),
+                                    tokens: Vec::new(),
+                                    unit: "KSM".to_string(),
+                                },
+                                valid_current_verifier: ValidCurrentVerifier::General,
+                                general_verifier: Verifier {
+                                    v: Some(VerifierValue::Standard {
+                                        m: sr_multisigner_from_hex(hex_2),
+                                    }),
+                                },
                             },
+                            tokens: Vec::new(),
                         },
                     }],
                 },
@@ -1391,8 +1597,11 @@ fn flow_test_1() {
         "Switched to Log from Settings. Expected updated Log screen with no modals.",
     );
 
-    do_action(Action::NavbarScan, "", "").unwrap().unwrap();
-    let action = do_action(
+    do_session_action(app, Action::NavbarScan, "", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(
+        app,
         Action::TransactionFetched,
         std::fs::read_to_string("for_tests/load_metadata_kusamaV9151_Alice-sr25519.txt")
             .unwrap()
@@ -1452,7 +1661,9 @@ fn flow_test_1() {
         "TransactionFetched on Scan screen with load_metadata for kusama9151. Expected Transaction screen with no modals"
     );
 
-    let action = do_action(Action::GoForward, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoForward, "", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "Network details".to_string(),
         back: true,
@@ -1503,9 +1714,15 @@ fn flow_test_1() {
         "GoForward on Transaction screen with load metadata stub. Expected NetworkDetails screen for kusama sr25519, updated with new metadata, with no modals"
     );
 
-    do_action(Action::GoBack, "", "").unwrap().unwrap();
-    do_action(Action::GoBack, "", "").unwrap().unwrap();
-    let mut action = do_action(Action::NavbarLog, "", "").unwrap().unwrap();
+    do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
+    let mut action = do_session_action(app, Action::NavbarLog, "", "")
+        .unwrap()
+        .unwrap();
     erase_log_timestamps(&mut action.screen_data);
     let mut expected_action = log_action.clone();
     expected_action.screen_data = ScreenData::Log {
@@ -1528,28 +1745,32 @@ fn flow_test_1() {
                 History {
                     order: 1,
                     timestamp: String::new(),
-                    events: vec![Event::NetworkSpecsAdded {
-                        network_specs_display: NetworkSpecsDisplay {
-                            specs: NetworkSpecs {
-                                base58prefix: 2,
-                                color: "#000".to_string(),
-                                decimals: 12,
-                                encryption: Encryption::Sr25519,
-                                genesis_hash: H256::from_str(hhh).unwrap(),
-                                logo: "kusama".to_string(),
-                                name: "kusama".to_string(),
-                                order: 2,
-                                path_id: "//kusama".to_string(),
-                                secondary_color: "#262626".to_string(),
-                                title: "Kusama".to_string(),
-                                unit: "KSM".to_string(),
-                            },
-                            valid_current_verifier: ValidCurrentVerifier::General,
-                            general_verifier: Verifier {
-                                v: Some(VerifierValue::Standard {
-                                    m: sr_multisigner_from_hex(hex_2),
-                                }),
+                    events: vec![Event::NetworkSpecsTokensAdded {
+                        network_specs_tokens_display: NetworkSpecsTokensDisplay {
+                            network_specs_display: NetworkSpecsDisplay {
+                                specs: NetworkSpecs {
+                                    base58prefix: 2,
+                                    color: "#000".to_string(),
+                                    decimals: 12,
+                                    encryption: Encryption::Sr25519,
+                                    genesis_hash: H256::from_str(hhh).unwrap(),
+                                    logo: "kusama".to_string(),
+                                    name: "kusama".to_string(),
+                                    order: 2,
+                                    path_id: "//kusama".to_string(),
+                                    secondary_color: "#262626".to_string(),
+                                    title: "Kusama".to_string(),
+                                    tokens: Vec::new(),
+                                    unit: "KSM".to_string(),
+                                },
+                                valid_current_verifier: ValidCurrentVerifier::General,
+                                general_verifier: Verifier {
+                                    v: Some(VerifierValue::Standard {
+                                        m: sr_multisigner_from_hex(hex_2),
+                                    }),
+                                },
                             },
+                            tokens: Vec::new(),
                         },
                     }],
                 },
@@ -1567,8 +1788,11 @@ fn flow_test_1() {
         "Switched to Log from Settings. Expected updated Log screen with no modals.",
     );
 
-    do_action(Action::NavbarScan, "", "").unwrap().unwrap();
-    let action = do_action(
+    do_session_action(app, Action::NavbarScan, "", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(
+        app,
         Action::TransactionFetched,
         std::fs::read_to_string("for_tests/load_types_Alice-sr25519.txt")
             .unwrap()
@@ -1606,6 +1830,7 @@ fn flow_test_1() {
                         indent: 0,
                         card: Card::WarningCard {
                             f: "Updating types (really rare operation).".to_string(),
+                            message: LocalisedMessage::new("warning.updating_types"),
                         },
                     }]),
                     types_info: Some(vec![TransactionCard {
@@ -1634,14 +1859,20 @@ fn flow_test_1() {
         "TransactionFetched on Scan screen with load_types. Not that we really need them anymore. Expected Transaction screen with no modals."
     );
 
-    let action = do_action(Action::GoForward, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoForward, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, manage_networks_action,
         "GoForward on Transaction screen with load types stub. Expected known ManageNetworks screen with no modals."
     );
 
-    do_action(Action::GoBack, "", "").unwrap().unwrap();
-    let mut action = do_action(Action::NavbarLog, "", "").unwrap().unwrap();
+    do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
+    let mut action = do_session_action(app, Action::NavbarLog, "", "")
+        .unwrap()
+        .unwrap();
     erase_log_timestamps(&mut action.screen_data);
     let mut expected_action = log_action;
     let hex_3 = "d091a5a24a97e18dfe298b167d8fd5a2add10098c8792cba21c39029a9ee0aeb";
@@ -1653,8 +1884,8 @@ fn flow_test_1() {
                     order: 3,
                     timestamp: String::new(),
                     events: vec![
-                        Event::Warning {
-                            warning: "Updating types (really rare operation).".to_string(),
+                        Event::WarningMessage {
+                            message: LocalisedMessage::new("warning.updating_types"),
                         },
                         Event::TypesAdded {
                             types_display: TypesDisplay {
@@ -1685,28 +1916,32 @@ fn flow_test_1() {
                 History {
                     order: 1,
                     timestamp: String::new(),
-                    events: vec![Event::NetworkSpecsAdded {
-                        network_specs_display: NetworkSpecsDisplay {
-                            specs: NetworkSpecs {
-                                base58prefix: 2,
-                                color: "#000".to_string(),
-                                decimals: 12,
-                                encryption: Encryption::Sr25519,
-                                genesis_hash: H256::from_str(hhh).unwrap(),
-                                logo: "kusama".to_string(),
-                                name: "kusama".to_string(),
-                                order: 2,
-                                path_id: "//kusama".to_string(),
-                                secondary_color: "#262626".to_string(),
-                                title: "Kusama".to_string(),
-                                unit: "KSM".to_string(),
-                            },
-                            valid_current_verifier: ValidCurrentVerifier::General,
-                            general_verifier: Verifier {
-                                v: Some(VerifierValue::Standard {
-                                    m: sr_multisigner_from_hex(hex_2),
-                                }),
+                    events: vec![Event::NetworkSpecsTokensAdded {
+                        network_specs_tokens_display: NetworkSpecsTokensDisplay {
+                            network_specs_display: NetworkSpecsDisplay {
+                                specs: NetworkSpecs {
+                                    base58prefix: 2,
+                                    color: "#000".to_string(),
+                                    decimals: 12,
+                                    encryption: Encryption::Sr25519,
+                                    genesis_hash: H256::from_str(hhh).unwrap(),
+                                    logo: "kusama".to_string(),
+                                    name: "kusama".to_string(),
+                                    order: 2,
+                                    path_id: "//kusama".to_string(),
+                                    secondary_color: "#262626".to_string(),
+                                    title: "Kusama".to_string(),
+                                    tokens: Vec::new(),
+                                    unit: "KSM".to_string(),
+                                },
+                                valid_current_verifier: ValidCurrentVerifier::General,
+                                general_verifier: Verifier {
+                                    v: Some(VerifierValue::Standard {
+                                        m: sr_multisigner_from_hex(hex_2),
+                                    }),
+                                },
                             },
+                            tokens: Vec::new(),
                         },
                     }],
                 },
@@ -1724,10 +1959,12 @@ fn flow_test_1() {
         "Switched to Log from Settings. Expected updated Log screen with no modals.",
     );
 
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::RightButtonAction, "", "")
+        .unwrap()
+        .unwrap();
+    let mut action = do_session_action(app, Action::ClearLog, "", "")
         .unwrap()
         .unwrap();
-    let mut action = do_action(Action::ClearLog, "", "").unwrap().unwrap();
     erase_log_timestamps(&mut action.screen_data);
     assert_eq!(
         action, empty_log,
@@ -1736,7 +1973,9 @@ fn flow_test_1() {
 
     current_log_action = action;
 
-    let action = do_action(Action::NavbarKeys, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::NavbarKeys, "", "")
+        .unwrap()
+        .unwrap();
 
     let expected_action = ActionResult {
         screen_label: "Select seed".to_string(),
@@ -1758,7 +1997,9 @@ fn flow_test_1() {
         "NavbarKeys on Log screen. Expected SeedSelector screen with NewSeedMenu modal",
     );
 
-    let action = do_action(Action::NewSeed, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::NewSeed, "", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "New Seed".to_string(),
         back: true,
@@ -1779,14 +2020,20 @@ fn flow_test_1() {
 
     let new_seed_action = action;
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, seed_selector_action,
         "GoBack on NewSeed screen. Expected SeedSelector screen with no modals.",
     );
 
-    do_action(Action::NewSeed, "", "").unwrap().unwrap();
-    let mut action = do_action(Action::GoForward, "Portia", "").unwrap().unwrap();
+    do_session_action(app, Action::NewSeed, "", "")
+        .unwrap()
+        .unwrap();
+    let mut action = do_session_action(app, Action::GoForward, "Portia", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "New Seed".to_string(),
         back: true,
@@ -1814,25 +2061,37 @@ fn flow_test_1() {
         "GoForward on NewSeed screen with non-empty seed name. Expected NewSeed screen with NewSeedBackup modal."
     );
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, new_seed_action,
         "GoBack on NewSeed screen with generated seed. Expected NewSeed screen with no modals."
     );
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(action, seed_selector_action, "GoBack on NewSeed screen with no modals, to see footer. Expected known SeedSelector screen with no modals");
 
-    let mut action = do_action(Action::NavbarLog, "", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::NavbarLog, "", "")
+        .unwrap()
+        .unwrap();
     erase_log_timestamps(&mut action.screen_data);
     assert_eq!(
         action, current_log_action,
         "Switched to Log from SeedSelector after cancelling seed creation. Expected known Log screen with no modals.",
     );
 
-    do_action(Action::NavbarKeys, "", "").unwrap().unwrap();
-    do_action(Action::NewSeed, "", "").unwrap().unwrap();
-    let mut action = do_action(Action::GoForward, "Portia", "").unwrap().unwrap();
+    do_session_action(app, Action::NavbarKeys, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::NewSeed, "", "")
+        .unwrap()
+        .unwrap();
+    let mut action = do_session_action(app, Action::GoForward, "Portia", "")
+        .unwrap()
+        .unwrap();
     let seed_phrase_portia = if let Some(ref mut m) = action.modal_data {
         erase_modal_seed_phrase_and_identicon(m)
     } else {
@@ -1863,7 +2122,7 @@ fn flow_test_1() {
         "GoForward on NewSeed screen with non-empty seed name. Expected NewSeed screen with NewSeedBackup modal."
     );
 
-    let mut action = do_action(Action::GoForward, "true", &seed_phrase_portia)
+    let mut action = do_session_action(app, Action::GoForward, "true", &seed_phrase_portia)
         .unwrap()
         .unwrap();
     erase_base58_address_identicon(&mut action.screen_data);
@@ -1885,6 +2144,8 @@ fn flow_test_1() {
                     swiped: false,
                     multiselect: false,
                     secret_exposed: false,
+                    usage: None,
+                    annotation: None,
                 }],
                 root: MSeedKeyCard {
                     seed_name: "Portia".to_string(),
@@ -1912,9 +2173,11 @@ fn flow_test_1() {
         "GoForward on NewSeed screen with NewSeedBackup modal active. Expected Keys screen with no modals."
     );
 
-    update_seed_names(vec![String::from("Portia")]);
+    update_session_seed_names(app, vec![String::from("Portia")]).unwrap();
 
-    let mut action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     erase_identicon(&mut action.screen_data);
 
     let expected_action = ActionResult {
@@ -1943,7 +2206,9 @@ fn flow_test_1() {
 
     seed_selector_action = action;
 
-    let mut action = do_action(Action::NavbarLog, "", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::NavbarLog, "", "")
+        .unwrap()
+        .unwrap();
     erase_public_keys(&mut action.screen_data);
     erase_log_timestamps(&mut action.screen_data);
 
@@ -1983,6 +2248,7 @@ fn flow_test_1() {
                                         network_genesis_hash_polkadot,
                                     )
                                     .unwrap(),
+                                    label: String::new(),
                                 },
                             },
                             Event::IdentityAdded {
@@ -1995,6 +2261,7 @@ fn flow_test_1() {
                                         network_genesis_hash_polkadot,
                                     )
                                     .unwrap(),
+                                    label: String::new(),
                                 },
                             },
                             Event::IdentityAdded {
@@ -2007,6 +2274,7 @@ fn flow_test_1() {
                                         network_genesis_hash_kusama,
                                     )
                                     .unwrap(),
+                                    label: String::new(),
                                 },
                             },
                             Event::IdentityAdded {
@@ -2019,6 +2287,7 @@ fn flow_test_1() {
                                         network_genesis_hash_kusama,
                                     )
                                     .unwrap(),
+                                    label: String::new(),
                                 },
                             },
                             Event::IdentityAdded {
@@ -2031,6 +2300,7 @@ fn flow_test_1() {
                                         network_genesis_hash_westend,
                                     )
                                     .unwrap(),
+                                    label: String::new(),
                                 },
                             },
                             Event::IdentityAdded {
@@ -2043,6 +2313,7 @@ fn flow_test_1() {
                                         network_genesis_hash_westend,
                                     )
                                     .unwrap(),
+                                    label: String::new(),
                                 },
                             },
                         ],
@@ -2060,10 +2331,12 @@ fn flow_test_1() {
     };
     assert_eq!(action, expected_action);
 
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::RightButtonAction, "", "")
+        .unwrap()
+        .unwrap();
+    let mut action = do_session_action(app, Action::ClearLog, "", "")
         .unwrap()
         .unwrap();
-    let mut action = do_action(Action::ClearLog, "", "").unwrap().unwrap();
     erase_log_timestamps(&mut action.screen_data);
 
     let expected_action = ActionResult {
@@ -2090,11 +2363,15 @@ fn flow_test_1() {
         "ClearLog on Log screen with LogRight modal. Expected updated Log screen with no modals"
     );
 
-    do_action(Action::NavbarKeys, "", "").unwrap().unwrap();
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::NavbarKeys, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::RightButtonAction, "", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(app, Action::RecoverSeed, "", "")
         .unwrap()
         .unwrap();
-    let action = do_action(Action::RecoverSeed, "", "").unwrap().unwrap();
     let expected_action = ActionResult {
         screen_label: "Recover Seed".to_string(),
         back: true,
@@ -2116,18 +2393,24 @@ fn flow_test_1() {
         "RecoverSeed on SeedSelector screen with NewSeedMenu modal. Expected RecoverSeedName screen with no modals"
     );
 
-    let mut action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     erase_identicon(&mut action.screen_data);
     assert_eq!(
         action, seed_selector_action,
         "GoBack on RecoverSeedName screen with no modals. Expected known SeedSelector screen"
     );
 
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::RightButtonAction, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::RecoverSeed, "", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(app, Action::GoForward, "Portia", "")
         .unwrap()
         .unwrap();
-    do_action(Action::RecoverSeed, "", "").unwrap().unwrap();
-    let action = do_action(Action::GoForward, "Portia", "").unwrap().unwrap();
     let expected_action = ActionResult {
         screen_label: "Recover Seed".to_string(),
         back: true,
@@ -2151,8 +2434,12 @@ fn flow_test_1() {
         "GoForward on RecoverSeedName screen using existing name. Expected RecoverSeedName screen with error."
     );
 
-    do_action(Action::GoBack, "", "").unwrap().unwrap();
-    let action = do_action(Action::GoForward, "Alys", "").unwrap().unwrap();
+    do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(app, Action::GoForward, "Alys", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "Recover Seed".to_string(),
         back: true,
@@ -2187,7 +2474,9 @@ fn flow_test_1() {
         "GoForward on RecoverSeedName screen using new name. Expected RecoverSeedPhrase screen with no modals."
     );
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "Recover Seed".to_string(),
         back: true,
@@ -2209,7 +2498,9 @@ fn flow_test_1() {
         "GoBack on RecoverSeedPhrase screen. Expected RecoverSeedName screen with no modals and with retained name."
     );
 
-    let action = do_action(Action::GoForward, "Alice", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoForward, "Alice", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "Recover Seed".to_string(),
         back: true,
@@ -2248,7 +2539,9 @@ fn flow_test_1() {
     );
 
     // Alice painstakingly recalls her seed phrase
-    let action = do_action(Action::TextEntry, " botto", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::TextEntry, " botto", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "Recover Seed".to_string(),
         back: true,
@@ -2278,7 +2571,7 @@ fn flow_test_1() {
         )
     );
 
-    let action = do_action(Action::TextEntry, " botto ", "")
+    let action = do_session_action(app, Action::TextEntry, " botto ", "")
         .unwrap()
         .unwrap();
     let expected_action = ActionResult {
@@ -2319,7 +2612,7 @@ fn flow_test_1() {
         )
     );
 
-    let action = do_action(Action::TextEntry, " abstract ", "")
+    let action = do_session_action(app, Action::TextEntry, " abstract ", "")
         .unwrap()
         .unwrap();
     let expected_action = ActionResult {
@@ -2360,7 +2653,9 @@ fn flow_test_1() {
         )
     );
 
-    let action = do_action(Action::TextEntry, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::TextEntry, "", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "Recover Seed".to_string(),
         back: true,
@@ -2399,10 +2694,12 @@ fn flow_test_1() {
         )
     );
 
-    do_action(Action::TextEntry, " d", "").unwrap().unwrap();
+    do_session_action(app, Action::TextEntry, " d", "")
+        .unwrap()
+        .unwrap();
 
     // a cat interfered
-    let action = do_action(Action::TextEntry, " ddddddddddddddd", "")
+    let action = do_session_action(app, Action::TextEntry, " ddddddddddddddd", "")
         .unwrap()
         .unwrap();
     let expected_action = ActionResult {
@@ -2443,7 +2740,7 @@ fn flow_test_1() {
         )
     );
 
-    let action = do_action(Action::TextEntry, " dddddddd ", "")
+    let action = do_session_action(app, Action::TextEntry, " dddddddd ", "")
         .unwrap()
         .unwrap();
     assert_eq!(
@@ -2454,11 +2751,21 @@ fn flow_test_1() {
         )
     );
 
-    do_action(Action::TextEntry, " driv ", "").unwrap().unwrap();
-    do_action(Action::TextEntry, " obe ", "").unwrap().unwrap();
-    do_action(Action::TextEntry, " lake ", "").unwrap().unwrap();
-    do_action(Action::TextEntry, " curt ", "").unwrap().unwrap();
-    let action = do_action(Action::TextEntry, " som", "").unwrap().unwrap();
+    do_session_action(app, Action::TextEntry, " driv ", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::TextEntry, " obe ", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::TextEntry, " lake ", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::TextEntry, " curt ", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(app, Action::TextEntry, " som", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "Recover Seed".to_string(),
         back: true,
@@ -2494,7 +2801,9 @@ fn flow_test_1() {
         )
     );
 
-    let action = do_action(Action::TextEntry, " smo", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::TextEntry, " smo", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "Recover Seed".to_string(),
         back: true,
@@ -2530,7 +2839,9 @@ fn flow_test_1() {
         )
     );
 
-    let action = do_action(Action::TextEntry, " smo ", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::TextEntry, " smo ", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, expected_action,
         concat!(
@@ -2539,7 +2850,9 @@ fn flow_test_1() {
         )
     );
 
-    let action = do_action(Action::PushWord, "smoke", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::PushWord, "smoke", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "Recover Seed".to_string(),
         back: true,
@@ -2585,12 +2898,24 @@ fn flow_test_1() {
         )
     );
 
-    do_action(Action::TextEntry, " bask ", "").unwrap().unwrap();
-    do_action(Action::TextEntry, " hold ", "").unwrap().unwrap();
-    do_action(Action::TextEntry, " race ", "").unwrap().unwrap();
-    do_action(Action::TextEntry, " lone ", "").unwrap().unwrap();
-    do_action(Action::TextEntry, " fit ", "").unwrap().unwrap();
-    let action = do_action(Action::TextEntry, " walk ", "").unwrap().unwrap();
+    do_session_action(app, Action::TextEntry, " bask ", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::TextEntry, " hold ", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::TextEntry, " race ", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::TextEntry, " lone ", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::TextEntry, " fit ", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(app, Action::TextEntry, " walk ", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "Recover Seed".to_string(),
         back: true,
@@ -2649,7 +2974,7 @@ fn flow_test_1() {
     // here the phone gets the finalized allowed seed, and needs to check it with strongbox, to see if the seed phrase already is known
     // can't model it here
 
-    let action = do_action(Action::GoForward, "false", ALICE_SEED_PHRASE)
+    let action = do_session_action(app, Action::GoForward, "false", ALICE_SEED_PHRASE)
         .unwrap()
         .unwrap();
 
@@ -2673,6 +2998,8 @@ fn flow_test_1() {
                     swiped: false,
                     multiselect: false,
                     secret_exposed: false,
+                    usage: None,
+                    annotation: None,
                 }],
                 // since root == 'false' in do_action above.
                 // TODO: This has to be wrapped with Option<_>.
@@ -2704,11 +3031,13 @@ fn flow_test_1() {
         )
     );
 
-    update_seed_names(vec![String::from("Portia"), String::from("Alice")]);
+    update_session_seed_names(app, vec![String::from("Portia"), String::from("Alice")]).unwrap();
 
     let mut alice_polkadot_keys_action = action;
 
-    let mut action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     erase_identicon(&mut action.screen_data);
     let expected_action = ActionResult {
         screen_label: "Select seed".to_string(),
@@ -2742,7 +3071,9 @@ fn flow_test_1() {
         "GoBack on Keys screen. Expected updated SeedSelector screen with no modals",
     );
 
-    let action = do_action(Action::SelectSeed, "Alice", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::SelectSeed, "Alice", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, alice_polkadot_keys_action,
         concat!(
@@ -2751,7 +3082,8 @@ fn flow_test_1() {
         )
     );
 
-    let action = do_action(
+    let action = do_session_action(
+        app,
         Action::SelectKey,
         "01f606519cb8726753885cd4d0f518804a69a5e0badf36fee70feadd8044081730",
         "",
@@ -2779,6 +3111,7 @@ fn flow_test_1() {
                     has_pwd: false,
                     multiselect: None,
                     secret_exposed: false,
+                    annotation: None,
                 },
                 network_info: MSCNetworkInfo {
                     network_title: "Polkadot".to_string(),
@@ -2787,6 +3120,7 @@ fn flow_test_1() {
                         "0191b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3"
                             .to_string(),
                 },
+                usage: None,
             },
         },
         modal_data: None,
@@ -2797,13 +3131,17 @@ fn flow_test_1() {
         "SelectKey on Keys screen. Expected KeyDetails screen for Alice //polkadot key.",
     );
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, alice_polkadot_keys_action,
         "GoBack on KeyDetails screen. Expected known Keys screen for Alice polkadot keys.",
     );
 
-    let action = do_action(Action::NewKey, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::NewKey, "", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "Derive Key".to_string(),
         back: true,
@@ -2835,14 +3173,18 @@ fn flow_test_1() {
         "NewKey on Keys screen. Expected DeriveKey screen",
     );
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, alice_polkadot_keys_action,
         "GoBack on DeriveKey screen. Expected known Keys screen for Alice polkadot keys",
     );
 
-    do_action(Action::NewKey, "", "").unwrap().unwrap();
-    let action = do_action(Action::CheckPassword, "//secret//path///multipass", "")
+    do_session_action(app, Action::NewKey, "", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(app, Action::CheckPassword, "//secret//path///multipass", "")
         .unwrap()
         .unwrap();
     let expected_action = ActionResult {
@@ -2888,7 +3230,8 @@ fn flow_test_1() {
 
     // Plaintext secrets in json?
 
-    let action = do_action(
+    let action = do_session_action(
+        app,
         Action::GoForward,
         "//secret//path///multipass",
         ALICE_SEED_PHRASE,
@@ -2917,6 +3260,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                     MKeysCard {
                         address_key:
@@ -2929,6 +3274,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                 ],
                 // since root == 'false' in do_action above.
@@ -2958,9 +3305,11 @@ fn flow_test_1() {
         )
     );
 
-    do_action(Action::NewKey, "", "").unwrap().unwrap();
+    do_session_action(app, Action::NewKey, "", "")
+        .unwrap()
+        .unwrap();
     // trying to create the missing root
-    let action = do_action(Action::GoForward, "", ALICE_SEED_PHRASE)
+    let action = do_session_action(app, Action::GoForward, "", ALICE_SEED_PHRASE)
         .unwrap()
         .unwrap();
 
@@ -2985,6 +3334,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                     MKeysCard {
                         address_key:
@@ -2997,6 +3348,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                 ],
                 root: MSeedKeyCard {
@@ -3029,7 +3382,9 @@ fn flow_test_1() {
 
     alice_polkadot_keys_action = action;
 
-    let mut action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     erase_identicon(&mut action.screen_data);
     let expected_action = ActionResult {
         screen_label: "Select seed".to_string(),
@@ -3066,8 +3421,10 @@ fn flow_test_1() {
         )
     );
 
-    do_action(Action::SelectSeed, "Alice", "").unwrap().unwrap();
-    let action = do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::SelectSeed, "Alice", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(app, Action::RightButtonAction, "", "")
         .unwrap()
         .unwrap();
     let mut expected_action = ActionResult {
@@ -3091,6 +3448,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                     MKeysCard {
                         address_key:
@@ -3103,6 +3462,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                 ],
                 root: MSeedKeyCard {
@@ -3137,7 +3498,9 @@ fn flow_test_1() {
         "RightButton on Keys screen. Expected SeedMenu modal to appear",
     );
 
-    let action = do_action(Action::BackupSeed, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::BackupSeed, "", "")
+        .unwrap()
+        .unwrap();
     expected_action.modal_data = Some(ModalData::Backup {
         f: MBackup {
             seed_name: "Alice".to_string(),
@@ -3190,7 +3553,9 @@ fn flow_test_1() {
     // mock signal from phone; elsewise untestable;
     db_handling::manage_history::seed_name_was_shown(dbname, String::from("Alice")).unwrap();
 
-    let mut action = do_action(Action::NavbarLog, "", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::NavbarLog, "", "")
+        .unwrap()
+        .unwrap();
     erase_log_timestamps(&mut action.screen_data);
     let expected_action = ActionResult {
         screen_label: String::new(),
@@ -3222,6 +3587,7 @@ fn flow_test_1() {
                                     network_genesis_hash_polkadot
                                 )
                                 .unwrap(),
+                                label: String::new(),
                             },
                         }],
                     },
@@ -3240,6 +3606,7 @@ fn flow_test_1() {
                                     network_genesis_hash_polkadot
                                 )
                                 .unwrap(),
+                                label: String::new(),
                             },
                         }],
                     },
@@ -3262,6 +3629,7 @@ fn flow_test_1() {
                                         network_genesis_hash_polkadot,
                                     )
                                     .unwrap(),
+                                    label: String::new(),
                                 },
                             },
                             Event::IdentityAdded {
@@ -3275,6 +3643,7 @@ fn flow_test_1() {
                                     network_genesis_hash: H256::from_str(
                                         network_genesis_hash_kusama)
                                     .unwrap(),
+                                    label: String::new(),
                                 },
                             },
                             Event::IdentityAdded {
@@ -3289,6 +3658,7 @@ fn flow_test_1() {
                                     network_genesis_hash_westend
                                     )
                                     .unwrap(),
+                                    label: String::new(),
                                 },
                             },
                         ],
@@ -3313,10 +3683,12 @@ fn flow_test_1() {
         )
     );
 
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::RightButtonAction, "", "")
+        .unwrap()
+        .unwrap();
+    let mut action = do_session_action(app, Action::ClearLog, "", "")
         .unwrap()
         .unwrap();
-    let mut action = do_action(Action::ClearLog, "", "").unwrap().unwrap();
     erase_log_timestamps(&mut action.screen_data);
     assert_eq!(
         action, empty_log,
@@ -3326,14 +3698,18 @@ fn flow_test_1() {
         )
     );
 
-    do_action(Action::NavbarKeys, "", "").unwrap().unwrap();
-    do_action(Action::SelectSeed, "Portia", "")
+    do_session_action(app, Action::NavbarKeys, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::SelectSeed, "Portia", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::RightButtonAction, "", "")
         .unwrap()
         .unwrap();
-    do_action(Action::RightButtonAction, "", "")
+    let _action = do_session_action(app, Action::RemoveSeed, "", "")
         .unwrap()
         .unwrap();
-    let _action = do_action(Action::RemoveSeed, "", "").unwrap().unwrap();
     /* TODO: this.
     let cut_real_json = cut_public_key(&timeless(&real_json));
 
@@ -3348,12 +3724,14 @@ fn flow_test_1() {
     */
     // Switching to log. Maybe we want to switch here to updated SeedSelector?
 
-    update_seed_names(vec![String::from("Alice")]);
+    update_session_seed_names(app, vec![String::from("Alice")]).unwrap();
 
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::RightButtonAction, "", "")
+        .unwrap()
+        .unwrap();
+    let mut action = do_session_action(app, Action::ClearLog, "", "")
         .unwrap()
         .unwrap();
-    let mut action = do_action(Action::ClearLog, "", "").unwrap().unwrap();
     erase_log_timestamps(&mut action.screen_data);
     assert_eq!(
         action, empty_log,
@@ -3363,7 +3741,9 @@ fn flow_test_1() {
         )
     );
 
-    let action = do_action(Action::NavbarKeys, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::NavbarKeys, "", "")
+        .unwrap()
+        .unwrap();
 
     let expected_action = ActionResult {
         screen_label: "Select seed".to_string(),
@@ -3389,8 +3769,12 @@ fn flow_test_1() {
         "NavbarKeys on Log screen. Expected updated SeedSelector screen with no modals",
     );
 
-    do_action(Action::SelectSeed, "Alice", "").unwrap().unwrap();
-    let action = do_action(Action::NetworkSelector, "", "").unwrap().unwrap();
+    do_session_action(app, Action::SelectSeed, "Alice", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(app, Action::NetworkSelector, "", "")
+        .unwrap()
+        .unwrap();
     let mut expected_action = alice_polkadot_keys_action.clone();
     expected_action.modal_data = Some(ModalData::NetworkSelector {
         f: MNetworkMenu {
@@ -3431,7 +3815,9 @@ fn flow_test_1() {
         )
     );
 
-    let action = do_action(Action::NetworkSelector, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::NetworkSelector, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, alice_polkadot_keys_action,
         concat!(
@@ -3440,8 +3826,11 @@ fn flow_test_1() {
         )
     );
 
-    do_action(Action::NetworkSelector, "", "").unwrap().unwrap();
-    let action = do_action(
+    do_session_action(app, Action::NetworkSelector, "", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(
+        app,
         Action::ChangeNetwork,
         "01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e",
         "",
@@ -3468,6 +3857,8 @@ fn flow_test_1() {
                     swiped: false,
                     multiselect: false,
                     secret_exposed: false,
+                    usage: None,
+                    annotation: None,
                 }],
                 root: MSeedKeyCard {
                     seed_name: "Alice".to_string(),
@@ -3491,8 +3882,10 @@ fn flow_test_1() {
         "ChangeNetwork on Keys screen. Expected Keys screen for Alice westend keys.",
     );
 
-    do_action(Action::NavbarScan, "", "").unwrap().unwrap();
-    let action = do_action(Action::TransactionFetched,"53ffde01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e141c2f2f416c6963653c2f2f416c6963652f77657374656e64582f2f416c6963652f7365637265742f2f7365637265740c2f2f300c2f2f31","").unwrap().unwrap();
+    do_session_action(app, Action::NavbarScan, "", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(app, Action::TransactionFetched,"53ffde01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e141c2f2f416c6963653c2f2f416c6963652f77657374656e64582f2f416c6963652f7365637265742f2f7365637265740c2f2f300c2f2f31","").unwrap().unwrap();
     let mut expected_action = ActionResult {
         screen_label: String::new(),
         back: true,
@@ -3540,7 +3933,9 @@ fn flow_test_1() {
         )
     );
 
-    let action = do_action(Action::GoForward, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoForward, "", "")
+        .unwrap()
+        .unwrap();
     expected_action.modal_data = Some(ModalData::SelectSeed {
         f: MSeeds {
             seed_name_cards: vec![SeedNameCard {
@@ -3559,7 +3954,7 @@ fn flow_test_1() {
         )
     );
 
-    let action = do_action(Action::GoForward, "Alice", ALICE_SEED_PHRASE)
+    let action = do_session_action(app, Action::GoForward, "Alice", ALICE_SEED_PHRASE)
         .unwrap()
         .unwrap();
     let expected_action = ActionResult {
@@ -3583,6 +3978,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                     MKeysCard {
                         address_key:
@@ -3595,6 +3992,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                     MKeysCard {
                         address_key:
@@ -3607,6 +4006,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                     MKeysCard {
                         address_key:
@@ -3619,6 +4020,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                     MKeysCard {
                         address_key:
@@ -3631,6 +4034,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                     MKeysCard {
                         address_key:
@@ -3643,6 +4048,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                 ],
                 root: MSeedKeyCard {
@@ -3679,8 +4086,11 @@ fn flow_test_1() {
 
     let mut alice_westend_keys_action = action;
 
-    do_action(Action::NetworkSelector, "", "").unwrap().unwrap();
-    let action = do_action(
+    do_session_action(app, Action::NetworkSelector, "", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(
+        app,
         Action::ChangeNetwork,
         "0191b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3",
         "",
@@ -3695,14 +4105,18 @@ fn flow_test_1() {
         )
     );
 
-    do_action(Action::NetworkSelector, "", "").unwrap().unwrap();
-    do_action(
+    do_session_action(app, Action::NetworkSelector, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(
+        app,
         Action::ChangeNetwork,
         "01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e",
         "",
     )
     .unwrap();
-    let action = do_action(
+    let action = do_session_action(
+        app,
         Action::Swipe,
         "013efeca331d646d8a2986374bb3bb8d6e9e3cfcdd7c45c2b69104fab5d61d3f34",
         "",
@@ -3718,7 +4132,8 @@ fn flow_test_1() {
         "Swipe on Keys screen for Alice westend keys. Expected updated Keys screen.",
     );
     // unswipe
-    let action = do_action(
+    let action = do_session_action(
+        app,
         Action::Swipe,
         "013efeca331d646d8a2986374bb3bb8d6e9e3cfcdd7c45c2b69104fab5d61d3f34",
         "",
@@ -3734,14 +4149,16 @@ fn flow_test_1() {
         )
     );
 
-    do_action(
+    do_session_action(
+        app,
         Action::Swipe,
         "013efeca331d646d8a2986374bb3bb8d6e9e3cfcdd7c45c2b69104fab5d61d3f34",
         "",
     )
     .unwrap();
     // swipe another
-    let action = do_action(
+    let action = do_session_action(
+        app,
         Action::Swipe,
         "019cd20feb68e0535a6c1cdeead4601b652cf6af6d76baf370df26ee25adde0805",
         "",
@@ -3766,7 +4183,9 @@ fn flow_test_1() {
     }
 
     // remove swiped
-    let action = do_action(Action::RemoveKey, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::RemoveKey, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, keys_westend,
         "RemoveKey on Keys screen with swiped key. Expected updated Keys screen.",
@@ -3774,14 +4193,15 @@ fn flow_test_1() {
 
     // Note: after removal, stay on the Keys screen (previously went to log).
 
-    do_action(
+    do_session_action(
+        app,
         Action::Swipe,
         "013efeca331d646d8a2986374bb3bb8d6e9e3cfcdd7c45c2b69104fab5d61d3f34",
         "",
     )
     .unwrap();
     // increment swiped `//westend`
-    let action = do_action(Action::Increment, "2", ALICE_SEED_PHRASE)
+    let action = do_session_action(app, Action::Increment, "2", ALICE_SEED_PHRASE)
         .unwrap()
         .unwrap();
     let mut expected_action = ActionResult {
@@ -3805,6 +4225,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                     MKeysCard {
                         address_key:
@@ -3817,6 +4239,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                     MKeysCard {
                         address_key:
@@ -3829,6 +4253,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                     MKeysCard {
                         address_key:
@@ -3841,6 +4267,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                     MKeysCard {
                         address_key:
@@ -3853,6 +4281,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                     MKeysCard {
                         address_key:
@@ -3865,6 +4295,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                     MKeysCard {
                         address_key:
@@ -3877,6 +4309,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                 ],
                 root: MSeedKeyCard {
@@ -3900,13 +4334,14 @@ fn flow_test_1() {
         "Increment on Keys screen with swiped key. Expected updated Keys screen",
     );
 
-    do_action(
+    do_session_action(
+        app,
         Action::Swipe,
         "013efeca331d646d8a2986374bb3bb8d6e9e3cfcdd7c45c2b69104fab5d61d3f34",
         "",
     )
     .unwrap();
-    let action = do_action(Action::Increment, "1", ALICE_SEED_PHRASE)
+    let action = do_session_action(app, Action::Increment, "1", ALICE_SEED_PHRASE)
         .unwrap()
         .unwrap();
 
@@ -3923,6 +4358,8 @@ fn flow_test_1() {
                 swiped: false,
                 multiselect: false,
                 secret_exposed: false,
+                usage: None,
+                annotation: None,
             },
         );
     }
@@ -3933,7 +4370,8 @@ fn flow_test_1() {
     );
 
     // enter multi regime with LongTap
-    let action = do_action(
+    let action = do_session_action(
+        app,
         Action::LongTap,
         "018266a693d6872d2b6437215c198ee25cabf2e4256df9ad00e979e84b00b5235e",
         "",
@@ -3954,7 +4392,8 @@ fn flow_test_1() {
     );
 
     // select by SelectKey in multi
-    let action = do_action(
+    let action = do_session_action(
+        app,
         Action::SelectKey,
         "012afba9278e30ccf6a6ceb3a8b6e336b70068f045c666f2e7f4f9cc5f47db8972",
         "",
@@ -3973,7 +4412,8 @@ fn flow_test_1() {
     );
 
     // deselect by SelectKey in multi
-    let action = do_action(
+    let action = do_session_action(
+        app,
         Action::SelectKey,
         "012afba9278e30ccf6a6ceb3a8b6e336b70068f045c666f2e7f4f9cc5f47db8972",
         "",
@@ -3991,7 +4431,8 @@ fn flow_test_1() {
     );
 
     // deselect by LongTap in multi
-    let action = do_action(
+    let action = do_session_action(
+        app,
         Action::LongTap,
         "018266a693d6872d2b6437215c198ee25cabf2e4256df9ad00e979e84b00b5235e",
         "",
@@ -4010,38 +4451,45 @@ fn flow_test_1() {
 
     // Note: although multiselect count is 0, remain in multiselect mode
 
-    do_action(
+    do_session_action(
+        app,
         Action::LongTap,
         "0120c394d410893cac63d993fa71eb8247e6af9a29cda467e836efec678b9f6b7f",
         "",
     )
     .unwrap();
-    do_action(
+    do_session_action(
+        app,
         Action::LongTap,
         "012afba9278e30ccf6a6ceb3a8b6e336b70068f045c666f2e7f4f9cc5f47db8972",
         "",
     )
     .unwrap();
-    do_action(
+    do_session_action(
+        app,
         Action::LongTap,
         "014e384fb30994d520094dce42086dbdd4977c11fb2f2cf9ca1c80056684934b08",
         "",
     )
     .unwrap();
-    do_action(
+    do_session_action(
+        app,
         Action::LongTap,
         "01b606fc73f57f03cdb4c932d475ab426043e429cecc2ffff0d2672b0df8398c48",
         "",
     )
     .unwrap();
-    do_action(
+    do_session_action(
+        app,
         Action::LongTap,
         "01e655361d12f3ccca5f128187cf3f5eea052be722746e392c8b498d0d18723470",
         "",
     )
     .unwrap();
     // remove keys in multiselect mode
-    let action = do_action(Action::RemoveKey, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::RemoveKey, "", "")
+        .unwrap()
+        .unwrap();
 
     if let ScreenData::Keys { ref mut f } = expected_action.screen_data {
         f.set.remove(0);
@@ -4058,14 +4506,17 @@ fn flow_test_1() {
         "RemoveKey on Keys screen with multiselect mode. Expected updated Keys screen.",
     );
     // enter multiselect mode
-    do_action(
+    do_session_action(
+        app,
         Action::LongTap,
         "013efeca331d646d8a2986374bb3bb8d6e9e3cfcdd7c45c2b69104fab5d61d3f34",
         "",
     )
     .unwrap();
     // select all
-    let action = do_action(Action::SelectAll, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::SelectAll, "", "")
+        .unwrap()
+        .unwrap();
 
     if let ScreenData::Keys { ref mut f } = expected_action.screen_data {
         for entry in f.set.iter_mut() {
@@ -4082,7 +4533,9 @@ fn flow_test_1() {
     );
 
     // deselect all
-    let action = do_action(Action::SelectAll, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::SelectAll, "", "")
+        .unwrap()
+        .unwrap();
 
     if let ScreenData::Keys { ref mut f } = expected_action.screen_data {
         for entry in f.set.iter_mut() {
@@ -4096,7 +4549,9 @@ fn flow_test_1() {
         "SelectAll on Keys screen with multiselect mode. Expected updated Keys screen.",
     );
     // exit multiselect mode
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     if let ScreenData::Keys { ref mut f } = expected_action.screen_data {
         f.multiselect_count = "".to_string();
         f.multiselect_mode = false;
@@ -4110,15 +4565,18 @@ fn flow_test_1() {
     alice_westend_keys_action = action;
 
     // enter multiselect mode
-    do_action(
+    do_session_action(
+        app,
         Action::LongTap,
         "013efeca331d646d8a2986374bb3bb8d6e9e3cfcdd7c45c2b69104fab5d61d3f34",
         "",
     )
     .unwrap();
     // select all
-    do_action(Action::SelectAll, "", "").unwrap().unwrap();
-    let action = do_action(Action::ExportMultiSelect, "", "")
+    do_session_action(app, Action::SelectAll, "", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(app, Action::ExportMultiSelect, "", "")
         .unwrap()
         .unwrap();
 
@@ -4143,6 +4601,7 @@ fn flow_test_1() {
                         has_pwd: false,
                         multiselect: None,
                         secret_exposed: false,
+                        annotation: None,
                     },
                     network_info: MSCNetworkInfo {
                         network_title: "Westend".to_string(),
@@ -4151,6 +4610,7 @@ fn flow_test_1() {
                             "01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"
                                 .to_string(),
                     },
+                    usage: None,
                 },
                 current_number: "1".to_string(),
                 out_of: "3".to_string(),
@@ -4169,7 +4629,9 @@ fn flow_test_1() {
 
     let unit1 = action;
 
-    let action = do_action(Action::NextUnit, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::NextUnit, "", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "Key".to_string(),
         back: true,
@@ -4191,6 +4653,7 @@ fn flow_test_1() {
                         has_pwd: false,
                         multiselect: None,
                         secret_exposed: false,
+                        annotation: None,
                     },
                     network_info: MSCNetworkInfo {
                         network_title: "Westend".to_string(),
@@ -4199,6 +4662,7 @@ fn flow_test_1() {
                             "01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"
                                 .to_string(),
                     },
+                    usage: None,
                 },
                 current_number: "2".to_string(),
                 out_of: "3".to_string(),
@@ -4218,7 +4682,9 @@ fn flow_test_1() {
 
     let unit2 = action;
 
-    let action = do_action(Action::NextUnit, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::NextUnit, "", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "Key".to_string(),
         back: true,
@@ -4240,6 +4706,7 @@ fn flow_test_1() {
                         has_pwd: false,
                         multiselect: None,
                         secret_exposed: false,
+                        annotation: None,
                     },
                     network_info: MSCNetworkInfo {
                         network_title: "Westend".to_string(),
@@ -4248,6 +4715,7 @@ fn flow_test_1() {
                             "01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"
                                 .to_string(),
                     },
+                    usage: None,
                 },
                 current_number: "3".to_string(),
                 out_of: "3".to_string(),
@@ -4266,37 +4734,49 @@ fn flow_test_1() {
 
     let unit3 = action;
 
-    let action = do_action(Action::NextUnit, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::NextUnit, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, unit1,
         "ExportMultiSelect on Keys screen with multiselect mode. Expected KeyDetailsMulti screen"
     );
 
-    let action = do_action(Action::PreviousUnit, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::PreviousUnit, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, unit3,
         "ExportMultiSelect on Keys screen with multiselect mode. Expected KeyDetailsMulti screen"
     );
 
-    let action = do_action(Action::PreviousUnit, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::PreviousUnit, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, unit2,
         "ExportMultiSelect on Keys screen with multiselect mode. Expected KeyDetailsMulti screen"
     );
 
-    let action = do_action(Action::PreviousUnit, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::PreviousUnit, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, unit1,
         "ExportMultiSelect on Keys screen with multiselect mode. Expected KeyDetailsMulti screen"
     );
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, alice_westend_keys_action,
         "GoBack on KeyDetailsMulti screen. Expected Keys screen in plain mode",
     );
 
-    let action = do_action(Action::NewKey, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::NewKey, "", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "Derive Key".to_string(),
         back: true,
@@ -4328,15 +4808,19 @@ fn flow_test_1() {
         "NewKey on Keys screen. Expected DeriveKey screen",
     );
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, alice_westend_keys_action,
         "GoBack on DeriveKey screen. Expected Keys screen in plain mode.",
     );
 
-    do_action(Action::NewKey, "", "").unwrap().unwrap();
+    do_session_action(app, Action::NewKey, "", "")
+        .unwrap()
+        .unwrap();
     // create root derivation
-    let action = do_action(Action::GoForward, "", ALICE_SEED_PHRASE)
+    let action = do_session_action(app, Action::GoForward, "", ALICE_SEED_PHRASE)
         .unwrap()
         .unwrap();
     let mut expected_action = ActionResult {
@@ -4360,6 +4844,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                     MKeysCard {
                         address_key:
@@ -4372,6 +4858,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                     MKeysCard {
                         address_key:
@@ -4384,6 +4872,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                 ],
                 root: MSeedKeyCard {
@@ -4415,14 +4905,17 @@ fn flow_test_1() {
     );
 
     // enter multiselect mode
-    do_action(
+    do_session_action(
+        app,
         Action::LongTap,
         "013efeca331d646d8a2986374bb3bb8d6e9e3cfcdd7c45c2b69104fab5d61d3f34",
         "",
     )
     .unwrap();
     // select all
-    let action = do_action(Action::SelectAll, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::SelectAll, "", "")
+        .unwrap()
+        .unwrap();
 
     if let ScreenData::Keys { ref mut f } = expected_action.screen_data {
         for entry in f.set.iter_mut() {
@@ -4442,8 +4935,11 @@ fn flow_test_1() {
         )
     );
 
-    do_action(Action::GoBack, "", "").unwrap().unwrap();
-    let action = do_action(
+    do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(
+        app,
         Action::SelectKey,
         "0146ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a",
         "",
@@ -4470,6 +4966,7 @@ fn flow_test_1() {
                     has_pwd: false,
                     multiselect: None,
                     secret_exposed: false,
+                    annotation: None,
                 },
                 network_info: MSCNetworkInfo {
                     network_title: "Westend".to_string(),
@@ -4478,6 +4975,7 @@ fn flow_test_1() {
                         "01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"
                             .to_string(),
                 },
+                usage: None,
             },
         },
         modal_data: None,
@@ -4492,9 +4990,15 @@ fn flow_test_1() {
         )
     );
 
-    do_action(Action::GoBack, "", "").unwrap().unwrap();
-    do_action(Action::NavbarSettings, "", "").unwrap().unwrap();
-    let action = do_action(Action::BackupSeed, "", "").unwrap().unwrap();
+    do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::NavbarSettings, "", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(app, Action::BackupSeed, "", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: "Select seed".to_string(),
         back: true,
@@ -4522,7 +5026,9 @@ fn flow_test_1() {
         )
     );
 
-    let action = do_action(Action::BackupSeed, "Alice", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::BackupSeed, "Alice", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: String::new(),
         back: true,
@@ -4544,6 +5050,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                     MKeysCard {
                         address_key:
@@ -4556,6 +5064,8 @@ fn flow_test_1() {
                         swiped: false,
                         multiselect: false,
                         secret_exposed: false,
+                        usage: None,
+                        annotation: None,
                     },
                 ],
                 root: MSeedKeyCard {
@@ -4648,18 +5158,23 @@ fn flow_test_1() {
     // mock signal from phone
     db_handling::manage_history::seed_name_was_shown(dbname, String::from("Alice")).unwrap();
 
-    do_action(Action::NavbarSettings, "", "").unwrap().unwrap();
-    do_action(Action::ManageNetworks, "", "").unwrap().unwrap();
-    do_action(
+    do_session_action(app, Action::NavbarSettings, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::ManageNetworks, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(
+        app,
         Action::GoForward,
         "01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e",
         "",
     )
     .unwrap();
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::RightButtonAction, "", "")
         .unwrap()
         .unwrap();
-    let action = do_action(Action::SignNetworkSpecs, "", "")
+    let action = do_session_action(app, Action::SignNetworkSpecs, "", "")
         .unwrap()
         .unwrap();
     let mut expected_action = ActionResult {
@@ -4778,7 +5293,8 @@ fn flow_test_1() {
         )
     );
 
-    let mut action = do_action(
+    let mut action = do_session_action(
+        app,
         Action::GoForward,
         "0146ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a",
         "",
@@ -4796,6 +5312,7 @@ fn flow_test_1() {
                 has_pwd: false,
                 multiselect: None,
                 secret_exposed: false,
+                annotation: None,
             },
             sufficient: vec![],
             content: MSCContent::AddSpecs {
@@ -4857,7 +5374,9 @@ fn flow_test_1() {
         std::env::set_current_dir("../../navigator").unwrap();
     }
 
-    let action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, current_settings_action,
         concat!(
@@ -4866,7 +5385,9 @@ fn flow_test_1() {
         )
     );
 
-    let mut action = do_action(Action::NavbarLog, "", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::NavbarLog, "", "")
+        .unwrap()
+        .unwrap();
     erase_log_timestamps(&mut action.screen_data);
 
     let alice_public_hex = "46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a";
@@ -4889,6 +5410,7 @@ fn flow_test_1() {
                         path_id: "//westend".to_string(),
                         secondary_color: "#262626".to_string(),
                         title: "Westend".to_string(),
+                        tokens: Vec::new(),
                         unit: "WND".to_string(),
                     },
                     signed_by: VerifierValue::Standard {
@@ -4920,10 +5442,12 @@ fn flow_test_1() {
             expected_action.screen_data
         );
     }
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::RightButtonAction, "", "")
+        .unwrap()
+        .unwrap();
+    let mut action = do_session_action(app, Action::ClearLog, "", "")
         .unwrap()
         .unwrap();
-    let mut action = do_action(Action::ClearLog, "", "").unwrap().unwrap();
     erase_log_timestamps(&mut action.screen_data);
     assert_eq!(
         action, expected_action,
@@ -4933,19 +5457,27 @@ fn flow_test_1() {
         )
     );
 
-    do_action(Action::NavbarSettings, "", "").unwrap().unwrap();
-    do_action(Action::ManageNetworks, "", "").unwrap().unwrap();
-    do_action(
+    do_session_action(app, Action::NavbarSettings, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::ManageNetworks, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(
+        app,
         Action::GoForward,
         "01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e",
         "",
     )
     .unwrap();
-    do_action(Action::ManageMetadata, "9150", "")
+    do_session_action(app, Action::ManageMetadata, "9150", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::SignMetadata, "", "")
         .unwrap()
         .unwrap();
-    do_action(Action::SignMetadata, "", "").unwrap().unwrap();
-    let mut action = do_action(
+    let mut action = do_session_action(
+        app,
         Action::GoForward,
         "0146ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a",
         "",
@@ -5067,6 +5599,7 @@ fn flow_test_1() {
                     has_pwd: false,
                     multiselect: None,
                     secret_exposed: false,
+                    annotation: None,
                 },
                 sufficient: vec![],
                 content: MSCContent::LoadMetadata {
@@ -5124,8 +5657,12 @@ fn flow_test_1() {
         std::env::set_current_dir("../../navigator").unwrap();
     }
 
-    do_action(Action::GoBack, "", "").unwrap().unwrap();
-    let action = do_action(Action::NavbarLog, "", "").unwrap().unwrap();
+    do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(app, Action::NavbarLog, "", "")
+        .unwrap()
+        .unwrap();
     if let ScreenData::Log { ref f } = action.screen_data {
         assert_eq!(
             f.log[0].events[0],
@@ -5152,18 +5689,27 @@ fn flow_test_1() {
     } else {
         panic!("Expected ScreenData::Log, got {:?}", action.screen_data);
     }
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::RightButtonAction, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::ClearLog, "", "")
         .unwrap()
         .unwrap();
-    do_action(Action::ClearLog, "", "").unwrap().unwrap();
 
-    do_action(Action::NavbarSettings, "", "").unwrap().unwrap();
-    do_action(Action::ManageNetworks, "", "").unwrap().unwrap();
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::NavbarSettings, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::ManageNetworks, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::RightButtonAction, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::SignTypes, "", "")
         .unwrap()
         .unwrap();
-    do_action(Action::SignTypes, "", "").unwrap().unwrap();
-    let mut action = do_action(
+    let mut action = do_session_action(
+        app,
         Action::GoForward,
         "0146ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a",
         "",
@@ -5193,6 +5739,7 @@ fn flow_test_1() {
                 has_pwd: false,
                 multiselect: None,
                 secret_exposed: false,
+                annotation: None,
             },
             sufficient: vec![],
             content: MSCContent::LoadTypes {
@@ -5238,8 +5785,12 @@ fn flow_test_1() {
         std::env::set_current_dir("../../navigator").unwrap();
     }
 
-    do_action(Action::GoBack, "", "").unwrap().unwrap();
-    let action = do_action(Action::NavbarLog, "", "").unwrap().unwrap();
+    do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(app, Action::NavbarLog, "", "")
+        .unwrap()
+        .unwrap();
     if let ScreenData::Log { ref f } = action.screen_data {
         assert_eq!(
             f.log[0].events[0],
@@ -5265,14 +5816,18 @@ fn flow_test_1() {
         panic!("Expected ScreenData::Log, got {:?}", action.screen_data);
     }
 
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::RightButtonAction, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::ClearLog, "", "")
         .unwrap()
         .unwrap();
-    do_action(Action::ClearLog, "", "").unwrap().unwrap();
 
     // let's scan something!!! oops wrong network version
-    do_action(Action::NavbarScan, "", "").unwrap().unwrap();
-    let action = do_action(Action::TransactionFetched,"530100d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27da40403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480700e8764817b501b8003223000005000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e538a7d7a0ac17eb6dd004578cb8e238c384a10f57c999a3fa1200409cd9b3f33e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e","").unwrap().unwrap();
+    do_session_action(app, Action::NavbarScan, "", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(app, Action::TransactionFetched,"530100d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27da40403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480700e8764817b501b8003223000005000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e538a7d7a0ac17eb6dd004578cb8e238c384a10f57c999a3fa1200409cd9b3f33e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e","").unwrap().unwrap();
     let expected_action = ActionResult {
         screen_label: String::new(),
         back: true,
@@ -5315,7 +5870,9 @@ fn flow_test_1() {
         )
     );
 
-    let action = do_action(Action::GoForward, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoForward, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, expected_action,
         concat!(
@@ -5326,9 +5883,11 @@ fn flow_test_1() {
     );
 
     // let's scan something real!!!
-    do_action(Action::GoBack, "", "").unwrap().unwrap();
+    do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     let transaction_hex = "5301008266a693d6872d2b6437215c198ee25cabf2e4256df9ad00e979e84b00b5235ea40403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480700e8764817b501b800be23000005000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e538a7d7a0ac17eb6dd004578cb8e238c384a10f57c999a3fa1200409cd9b3f33e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e";
-    let action = do_action(Action::TransactionFetched, transaction_hex, "")
+    let action = do_session_action(app, Action::TransactionFetched, transaction_hex, "")
         .unwrap()
         .unwrap();
     let docs = "53616d6520617320746865205b607472616e73666572605d2063616c6c2c206275742077697468206120636865636b207468617420746865207472616e736665722077696c6c206e6f74206b696c6c207468650a6f726967696e206163636f756e742e0a0a393925206f66207468652074696d6520796f752077616e74205b607472616e73666572605d20696e73746561642e0a0a5b607472616e73666572605d3a207374727563742e50616c6c65742e68746d6c236d6574686f642e7472616e73666572".to_string();
@@ -5393,6 +5952,7 @@ fn flow_test_1() {
                                     base58: "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"
                                         .to_string(),
                                     identicon: bob().to_vec(),
+                                    contact_name: None,
                                 },
                             },
                         },
@@ -5480,6 +6040,7 @@ fn flow_test_1() {
                     has_pwd: false,
                     multiselect: None,
                     secret_exposed: false,
+                    annotation: None,
                 }),
                 network_info: Some(MSCNetworkInfo {
                     network_title: "Westend".to_string(),
@@ -5502,7 +6063,8 @@ fn flow_test_1() {
         )
     );
 
-    let action = do_action(
+    let action = do_session_action(
+        app,
         Action::GoForward,
         "Alice sends some cash",
         ALICE_SEED_PHRASE,
@@ -5527,7 +6089,9 @@ fn flow_test_1() {
         signature_hex
     );
 
-    let mut action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     erase_log_timestamps(&mut action.screen_data);
 
     let transaction = "a40403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480700e8764817b501b800be23000005000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e538a7d7a0ac17eb6dd004578cb8e238c384a10f57c999a3fa1200409cd9b3f33".to_string();
@@ -5579,7 +6143,9 @@ fn flow_test_1() {
         "GoBack from Transaction with SignatureReady modal. Expected Log.",
     );
 
-    let mut action = do_action(Action::ShowLogDetails, "1", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::ShowLogDetails, "1", "")
+        .unwrap()
+        .unwrap();
     erase_log_timestamps(&mut action.screen_data);
 
     //r#"{"screen":"LogDetails","screenLabel":"Event details","back":true,"footer":true,"footerButton":"Log","rightButton":"None","screenNameType":"h4","modal":"Empty","alert":"Empty","screenData":{"timestamp":"**","events":[{"event":"transaction_signed","payload":{"transaction":{"method":[{"index":0,"indent":0,"type":"pallet","payload":"Balances"},{"index":1,"indent":1,"type":"method","payload":{"method_name":"transfer_keep_alive","docs":"53616d6520617320746865205b607472616e73666572605d2063616c6c2c206275742077697468206120636865636b207468617420746865207472616e736665722077696c6c206e6f74206b696c6c207468650a6f726967696e206163636f756e742e0a0a393925206f66207468652074696d6520796f752077616e74205b607472616e73666572605d20696e73746561642e0a0a5b607472616e73666572605d3a207374727563742e50616c6c65742e68746d6c236d6574686f642e7472616e73666572"}},{"index":2,"indent":2,"type":"field_name","payload":{"name":"dest","docs_field_name":"","path_type":"sp_runtime >> multiaddress >> MultiAddress","docs_type":""}},{"index":3,"indent":3,"type":"enum_variant_name","payload":{"name":"Id","docs_enum_variant":""}},{"index":4,"indent":4,"type":"Id","payload":{"base58":"5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty","identicon":"<bob>"}},{"index":5,"indent":2,"type":"field_name","payload":{"name":"value","docs_field_name":"","path_type":"","docs_type":""}},{"index":6,"indent":3,"type":"balance","payload":{"amount":"100.000000000","units":"mWND"}}],"extensions":[{"index":7,"indent":0,"type":"era","payload":{"era":"Mortal","phase":"27","period":"64"}},{"index":8,"indent":0,"type":"nonce","payload":"46"},{"index":9,"indent":0,"type":"tip","payload":{"amount":"0","units":"pWND"}},{"index":10,"indent":0,"type":"name_version","payload":{"name":"westend","version":"9150"}},{"index":11,"indent":0,"type":"tx_version","payload":"5"},{"index":12,"indent":0,"type":"block_hash","payload":"538a7d7a0ac17eb6dd004578cb8e238c384a10f57c999a3fa1200409cd9b3f33"}]},"network_name":"westend","signed_by":{"public_key":"8266a693d6872d2b6437215c198ee25cabf2e4256df9ad00e979e84b00b5235e","identicon":"<alice_sr25519_//Alice/secret//secret>","encryption":"sr25519"},"user_comment":"Alice sends some cash"}}]},"modalData":{},"alertData":{}}"#;
@@ -5593,17 +6159,23 @@ fn flow_test_1() {
     );
     */
 
-    do_action(Action::GoBack, "", "").unwrap().unwrap();
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::RightButtonAction, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::ClearLog, "", "")
         .unwrap()
         .unwrap();
-    do_action(Action::ClearLog, "", "").unwrap().unwrap();
 
     // let's scan a text message
-    do_action(Action::NavbarScan, "", "").unwrap().unwrap();
+    do_session_action(app, Action::NavbarScan, "", "")
+        .unwrap()
+        .unwrap();
     let message_hex = "5301033efeca331d646d8a2986374bb3bb8d6e9e3cfcdd7c45c2b69104fab5d61d3f34f5064c6f72656d20697073756d20646f6c6f722073697420616d65742c20636f6e73656374657475722061646970697363696e6720656c69742c2073656420646f20656975736d6f642074656d706f7220696e6369646964756e74207574206c61626f726520657420646f6c6f7265206d61676e6120616c697175612e20557420656e696d206164206d696e696d2076656e69616d2c2071756973206e6f737472756420657865726369746174696f6e20756c6c616d636f206c61626f726973206e69736920757420616c697175697020657820656120636f6d6d6f646f20636f6e7365717561742e2044756973206175746520697275726520646f6c6f7220696e20726570726568656e646572697420696e20766f6c7570746174652076656c697420657373652063696c6c756d20646f6c6f726520657520667567696174206e756c6c612070617269617475722e204578636570746575722073696e74206f6363616563617420637570696461746174206e6f6e2070726f6964656e742c2073756e7420696e2063756c706120717569206f666669636961206465736572756e74206d6f6c6c697420616e696d20696420657374206c61626f72756d2ee143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e";
     let card_text = "4c6f72656d20697073756d20646f6c6f722073697420616d65742c20636f6e73656374657475722061646970697363696e6720656c69742c2073656420646f20656975736d6f642074656d706f7220696e6369646964756e74207574206c61626f726520657420646f6c6f7265206d61676e6120616c697175612e20557420656e696d206164206d696e696d2076656e69616d2c2071756973206e6f737472756420657865726369746174696f6e20756c6c616d636f206c61626f726973206e69736920757420616c697175697020657820656120636f6d6d6f646f20636f6e7365717561742e2044756973206175746520697275726520646f6c6f7220696e20726570726568656e646572697420696e20766f6c7570746174652076656c697420657373652063696c6c756d20646f6c6f726520657520667567696174206e756c6c612070617269617475722e204578636570746575722073696e74206f6363616563617420637570696461746174206e6f6e2070726f6964656e742c2073756e7420696e2063756c706120717569206f666669636961206465736572756e74206d6f6c6c697420616e696d20696420657374206c61626f72756d2e".to_string();
-    let action = do_action(Action::TransactionFetched, message_hex, "")
+    let action = do_session_action(app, Action::TransactionFetched, message_hex, "")
         .unwrap()
         .unwrap();
     let expected_action = ActionResult {
@@ -5634,6 +6206,7 @@ fn flow_test_1() {
                     has_pwd: false,
                     multiselect: None,
                     secret_exposed: false,
+                    annotation: None,
                 }),
                 network_info: Some(MSCNetworkInfo {
                     network_title: "Westend".to_string(),
@@ -5656,7 +6229,7 @@ fn flow_test_1() {
         )
     );
 
-    let action = do_action(Action::GoForward, "text test", ALICE_SEED_PHRASE)
+    let action = do_session_action(app, Action::GoForward, "text test", ALICE_SEED_PHRASE)
         .unwrap()
         .unwrap();
     let signature_hex = if let Some(ModalData::SignatureReady {
@@ -5684,7 +6257,9 @@ fn flow_test_1() {
         signature_hex
     );
 
-    let mut action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
     erase_log_timestamps(&mut action.screen_data);
     let signed_by = "3efeca331d646d8a2986374bb3bb8d6e9e3cfcdd7c45c2b69104fab5d61d3f34";
     let expected_action = ActionResult {
@@ -5733,7 +6308,9 @@ fn flow_test_1() {
         "GoBack from Transaction with SignatureReady modal. Expected Log.",
     );
 
-    let mut action = do_action(Action::ShowLogDetails, "1", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::ShowLogDetails, "1", "")
+        .unwrap()
+        .unwrap();
     erase_log_timestamps(&mut action.screen_data);
     let expected_action = ActionResult {
         screen_label: "Event details".to_string(),
@@ -5779,18 +6356,28 @@ fn flow_test_1() {
         )
     );
 
-    do_action(Action::GoBack, "", "").unwrap().unwrap();
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::RightButtonAction, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::ClearLog, "", "")
         .unwrap()
         .unwrap();
-    do_action(Action::ClearLog, "", "").unwrap().unwrap();
 
-    do_action(Action::NavbarKeys, "", "").unwrap().unwrap();
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::NavbarKeys, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::RightButtonAction, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::NewSeed, "", "")
+        .unwrap()
+        .unwrap();
+    let mut action = do_session_action(app, Action::GoForward, "Pepper", "")
         .unwrap()
         .unwrap();
-    do_action(Action::NewSeed, "", "").unwrap().unwrap();
-    let mut action = do_action(Action::GoForward, "Pepper", "").unwrap().unwrap();
     let seed_phrase_pepper = cut_seed_remove_identicon(&mut action.modal_data);
     let expected_action = ActionResult {
         screen_label: "New Seed".to_string(),
@@ -5820,7 +6407,7 @@ fn flow_test_1() {
         )
     );
 
-    let mut action = do_action(Action::GoForward, "false", &seed_phrase_pepper)
+    let mut action = do_session_action(app, Action::GoForward, "false", &seed_phrase_pepper)
         .unwrap()
         .unwrap();
     erase_base58_address_identicon(&mut action.screen_data);
@@ -5842,6 +6429,8 @@ fn flow_test_1() {
                     swiped: false,
                     multiselect: false,
                     secret_exposed: false,
+                    usage: None,
+                    annotation: None,
                 }],
                 root: MSeedKeyCard {
                     seed_name: "Pepper".to_string(),
@@ -5872,10 +6461,13 @@ fn flow_test_1() {
         )
     );
 
-    update_seed_names(vec![String::from("Alice"), String::from("Pepper")]);
+    update_session_seed_names(app, vec![String::from("Alice"), String::from("Pepper")]).unwrap();
 
-    do_action(Action::NetworkSelector, "", "").unwrap().unwrap();
-    let mut action = do_action(
+    do_session_action(app, Action::NetworkSelector, "", "")
+        .unwrap()
+        .unwrap();
+    let mut action = do_session_action(
+        app,
         Action::ChangeNetwork,
         "01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e",
         "",
@@ -5901,6 +6493,8 @@ fn flow_test_1() {
                     swiped: false,
                     multiselect: false,
                     secret_exposed: false,
+                    usage: None,
+                    annotation: None,
                 }],
                 root: MSeedKeyCard {
                     seed_name: "Pepper".to_string(),
@@ -5934,13 +6528,15 @@ fn flow_test_1() {
         action, expected_action,
         "Changed network to westend. Expected Keys screen with no modals",
     );
-    do_action(Action::NavbarScan, "", "").unwrap().unwrap();
+    do_session_action(app, Action::NavbarScan, "", "")
+        .unwrap()
+        .unwrap();
 
     let transaction_hex_pepper = transaction_hex.replace(
         "8266a693d6872d2b6437215c198ee25cabf2e4256df9ad00e979e84b00b5235e",
         &pepper_westend_public,
     );
-    let action = do_action(Action::TransactionFetched, &transaction_hex_pepper, "")
+    let action = do_session_action(app, Action::TransactionFetched, &transaction_hex_pepper, "")
         .unwrap()
         .unwrap();
 
@@ -6004,6 +6600,7 @@ fn flow_test_1() {
                                     base58: "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"
                                         .to_string(),
                                     identicon: bob().to_vec(),
+                                    contact_name: None,
                                 },
                             },
                         },
@@ -6091,6 +6688,7 @@ fn flow_test_1() {
                     has_pwd: false,
                     multiselect: None,
                     secret_exposed: false,
+                    annotation: None,
                 }),
                 network_info: Some(MSCNetworkInfo {
                     network_title: "Westend".to_string(),
@@ -6113,7 +6711,8 @@ fn flow_test_1() {
         )
     );
 
-    let action = do_action(
+    let action = do_session_action(
+        app,
         Action::GoForward,
         "Pepper also sends some cash",
         &seed_phrase_pepper,
@@ -6149,28 +6748,46 @@ fn flow_test_1() {
         signature_hex
     );
 
-    do_action(Action::GoBack, "", "").unwrap().unwrap();
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::RightButtonAction, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::ClearLog, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::NavbarKeys, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::SelectSeed, "Pepper", "")
         .unwrap()
         .unwrap();
-    do_action(Action::ClearLog, "", "").unwrap().unwrap();
-    do_action(Action::NavbarKeys, "", "").unwrap().unwrap();
-    do_action(Action::SelectSeed, "Pepper", "")
+    do_session_action(app, Action::NetworkSelector, "", "")
         .unwrap()
         .unwrap();
-    do_action(Action::NetworkSelector, "", "").unwrap().unwrap();
-    do_action(
+    do_session_action(
+        app,
         Action::ChangeNetwork,
         "01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e",
         "",
     )
     .unwrap();
-    do_action(Action::Swipe, &format!("01{}", pepper_westend_public), "")
+    do_session_action(
+        app,
+        Action::Swipe,
+        &format!("01{}", pepper_westend_public),
+        "",
+    )
+    .unwrap()
+    .unwrap();
+    do_session_action(app, Action::RemoveKey, "", "")
         .unwrap()
         .unwrap();
-    do_action(Action::RemoveKey, "", "").unwrap().unwrap();
 
-    let action = do_action(Action::NewKey, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::NewKey, "", "")
+        .unwrap()
+        .unwrap();
     let mut expected_action = ActionResult {
         screen_label: "Derive Key".to_string(),
         back: true,
@@ -6217,7 +6834,7 @@ fn flow_test_1() {
         panic!("");
     }
 
-    let action = do_action(Action::CheckPassword, "//0///secret", "")
+    let action = do_session_action(app, Action::CheckPassword, "//0///secret", "")
         .unwrap()
         .unwrap();
     assert_eq!(
@@ -6229,7 +6846,7 @@ fn flow_test_1() {
         )
     );
 
-    let mut action = do_action(Action::GoForward, "//0///secret", &seed_phrase_pepper)
+    let mut action = do_session_action(app, Action::GoForward, "//0///secret", &seed_phrase_pepper)
         .unwrap()
         .unwrap();
     let (pepper_key0_public, pepper_key0_base58, pepper_key0_identicon) =
@@ -6262,6 +6879,8 @@ fn flow_test_1() {
                     swiped: false,
                     multiselect: false,
                     secret_exposed: false,
+                    usage: None,
+                    annotation: None,
                 }],
                 root: MSeedKeyCard {
                     seed_name: "Pepper".to_string(),
@@ -6286,12 +6905,14 @@ fn flow_test_1() {
         )
     );
 
-    do_action(Action::NavbarScan, "", "").unwrap().unwrap();
+    do_session_action(app, Action::NavbarScan, "", "")
+        .unwrap()
+        .unwrap();
     let message_hex = message_hex.replace(
         "3efeca331d646d8a2986374bb3bb8d6e9e3cfcdd7c45c2b69104fab5d61d3f34",
         &pepper_key0_public,
     );
-    let action = do_action(Action::TransactionFetched, &message_hex, "")
+    let action = do_session_action(app, Action::TransactionFetched, &message_hex, "")
         .unwrap()
         .unwrap();
     let mut expected_action = ActionResult {
@@ -6322,6 +6943,7 @@ fn flow_test_1() {
                     has_pwd: true,
                     multiselect: None,
                     secret_exposed: false,
+                    annotation: None,
                 }),
                 network_info: Some(MSCNetworkInfo {
                     network_title: "Westend".to_string(),
@@ -6344,7 +6966,8 @@ fn flow_test_1() {
         )
     );
 
-    let action = do_action(
+    let action = do_session_action(
+        app,
         Action::GoForward,
         "Pepper tries sending text from passworded account",
         &seed_phrase_pepper,
@@ -6361,6 +6984,7 @@ fn flow_test_1() {
                 has_pwd: true,
                 multiselect: None,
                 secret_exposed: false,
+                annotation: None,
             },
             counter: 1,
         },
@@ -6373,7 +6997,7 @@ fn flow_test_1() {
         )
     );
 
-    let action = do_action(Action::GoForward, "wrong_one", "")
+    let action = do_session_action(app, Action::GoForward, "wrong_one", "")
         .unwrap()
         .unwrap();
     expected_action.modal_data = Some(ModalData::EnterPassword {
@@ -6386,6 +7010,7 @@ fn flow_test_1() {
                 has_pwd: true,
                 multiselect: None,
                 secret_exposed: false,
+                annotation: None,
             },
             counter: 2,
         },
@@ -6402,8 +7027,10 @@ fn flow_test_1() {
         )
     );
 
-    do_action(Action::GoBack, "", "").unwrap().unwrap();
-    let action = do_action(Action::GoForward, "wrong_two", "")
+    do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
+    let action = do_session_action(app, Action::GoForward, "wrong_two", "")
         .unwrap()
         .unwrap();
     expected_action.modal_data = Some(ModalData::EnterPassword {
@@ -6416,6 +7043,7 @@ fn flow_test_1() {
                 has_pwd: true,
                 multiselect: None,
                 secret_exposed: false,
+                annotation: None,
             },
             counter: 3,
         },
@@ -6428,8 +7056,10 @@ fn flow_test_1() {
         )
     );
 
-    do_action(Action::GoBack, "", "").unwrap().unwrap();
-    let mut action = do_action(Action::GoForward, "wrong_three", "")
+    do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
+    let mut action = do_session_action(app, Action::GoForward, "wrong_three", "")
         .unwrap()
         .unwrap();
     erase_log_timestamps(&mut action.screen_data);
@@ -6502,6 +7132,7 @@ fn flow_test_1() {
                                 public_key: hex::decode(&pepper_key0_public).unwrap(),
                                 path: "//0".to_string(),
                                 network_genesis_hash,
+                                label: String::new(),
                             },
                         }],
                     },
@@ -6515,6 +7146,7 @@ fn flow_test_1() {
                                 public_key: hex::decode(pepper_westend_public).unwrap(),
                                 path: "//westend".to_string(),
                                 network_genesis_hash,
+                                label: String::new(),
                             },
                         }],
                     },
@@ -6540,22 +7172,29 @@ fn flow_test_1() {
         )
     );
 
-    do_action(Action::RightButtonAction, "", "")
+    do_session_action(app, Action::RightButtonAction, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::ClearLog, "", "")
         .unwrap()
         .unwrap();
-    do_action(Action::ClearLog, "", "").unwrap().unwrap();
 
-    do_action(Action::NavbarScan, "", "").unwrap().unwrap();
-    do_action(Action::TransactionFetched, &message_hex, "")
+    do_session_action(app, Action::NavbarScan, "", "")
+        .unwrap()
+        .unwrap();
+    do_session_action(app, Action::TransactionFetched, &message_hex, "")
         .unwrap()
         .unwrap();
-    do_action(
+    do_session_action(
+        app,
         Action::GoForward,
         "Pepper tries better",
         &seed_phrase_pepper,
     )
     .unwrap();
-    let action = do_action(Action::GoForward, "secret", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::GoForward, "secret", "")
+        .unwrap()
+        .unwrap();
     let signature_hex = if let Some(ModalData::SignatureReady {
         f: MSignatureReady { ref signature },
     }) = action.modal_data
@@ -6588,13 +7227,17 @@ fn flow_test_1() {
         signature_hex
     );
 
-    do_action(Action::GoBack, "", "").unwrap().unwrap();
+    do_session_action(app, Action::GoBack, "", "")
+        .unwrap()
+        .unwrap();
 
     {
         // database got unavailable for some reason
         let _database = db_handling::helpers::open_db(dbname).unwrap();
 
-        let mut action = do_action(Action::NavbarKeys, "", "").unwrap().unwrap();
+        let mut action = do_session_action(app, Action::NavbarKeys, "", "")
+            .unwrap()
+            .unwrap();
         let expected_alert = "Database error. Internal error. IO error: could not acquire lock on \"for_tests/flow_test_1/db\": Os {**}".to_string();
         let expected_action = ActionResult {
             screen_label: "Select seed".to_string(),
@@ -6622,7 +7265,9 @@ fn flow_test_1() {
             "Tried to switch from Log to Keys with unavailable database."
         );
 
-        let mut action = do_action(Action::GoBack, "", "").unwrap().unwrap();
+        let mut action = do_session_action(app, Action::GoBack, "", "")
+            .unwrap()
+            .unwrap();
 
         let expected_alert = "Database error. Internal error. IO error: could not acquire lock on \"for_tests/flow_test_1/db\": Os {**}".to_string();
         let expected_action = ActionResult {
@@ -6662,13 +7307,17 @@ fn flow_test_1() {
     }
 
     // Aaand, we are back
-    let action = do_action(Action::NavbarSettings, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::NavbarSettings, "", "")
+        .unwrap()
+        .unwrap();
     assert_eq!(
         action, current_settings_action,
         "Reload Settings. Expected known Settings screen with no errors.",
     );
 
-    let mut action = do_action(Action::NavbarLog, "", "").unwrap().unwrap();
+    let mut action = do_session_action(app, Action::NavbarLog, "", "")
+        .unwrap()
+        .unwrap();
     erase_log_timestamps(&mut action.screen_data);
     let expected_action = ActionResult {
         screen_label: String::new(),
@@ -6711,7 +7360,9 @@ fn flow_test_1() {
 
     // no init after population
     populate_cold_nav_test(dbname).unwrap();
-    let action = do_action(Action::NavbarSettings, "", "").unwrap().unwrap();
+    let action = do_session_action(app, Action::NavbarSettings, "", "")
+        .unwrap()
+        .unwrap();
     let expected_action = ActionResult {
         screen_label: String::new(),
         back: false,
@@ -6738,5 +7389,230 @@ fn flow_test_1() {
         )
     );
 
+    close_session(app).unwrap();
+    std::fs::remove_dir_all(dbname).unwrap();
+}
+
+#[test]
+fn scenario_replay() {
+    let scenario = Scenario::from_json(include_str!(
+        "../scenarios/create_seed_and_search_keys.json"
+    ))
+    .unwrap();
+    let report = scenario.run_in_temporary_db().unwrap();
+    assert!(report.is_success(), "{:#?}", report.failures());
+    assert_eq!(report.steps.len(), 7);
+
+    // replay of the same scenario results in the same snapshot
+    let report_again = scenario.run_in_temporary_db().unwrap();
+    assert_eq!(report.snapshot(), report_again.snapshot());
+
+    // and in the database kept in memory
+    let report_in_memory = scenario.run_in_memory_db().unwrap();
+    assert_eq!(report.snapshot(), report_in_memory.snapshot());
+
+    // and in the snapshot recorded earlier
+    report
+        .check_snapshot(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/scenarios/create_seed_and_search_keys.snap"
+        ))
+        .unwrap();
+
+    // changed behavior is caught by the golden snapshot check
+    if std::env::var_os(UPDATE_SNAPSHOTS).is_none() {
+        let dir = tempfile::tempdir().unwrap();
+        let golden = dir.path().join("changed.snap");
+        let changed = report.snapshot().replacen("Keys", "Log", 1);
+        std::fs::write(&golden, changed).unwrap();
+        let line = report
+            .snapshot()
+            .lines()
+            .position(|line| line.contains("Keys"))
+            .unwrap()
+            + 1;
+        match report.check_snapshot(&golden).unwrap_err() {
+            Error::ScenarioSnapshotMismatch {
+                line: mismatch_line,
+                ..
+            } => assert_eq!(mismatch_line, line),
+            e => panic!("Expected snapshot mismatch, got {:?}.", e),
+        }
+        assert!(matches!(
+            report
+                .check_snapshot(dir.path().join("missing.snap"))
+                .unwrap_err(),
+            Error::ScenarioSnapshotMissing(_)
+        ));
+    }
+
+    // failed expectations are reported, not panicked on
+    let scenario = Scenario::from_json(
+        r#"{"steps": [{"action": "Start", "expect": {"screen": "Keys", "contains": ["Bob"]}}]}"#,
+    )
+    .unwrap();
+    let report = scenario.run_in_temporary_db().unwrap();
+    assert_eq!(
+        report.failures(),
+        vec![
+            "Step 0, Start \"\": Expected screen Keys, got SeedSelector.".to_string(),
+            "Step 0, Start \"\": Expected snapshot to contain \"Bob\".".to_string(),
+        ]
+    );
+
+    if let Error::UnknownAction(name) =
+        Scenario::from_json(r#"{"steps": [{"action": "Fly"}]}"#).unwrap_err()
+    {
+        assert_eq!(name, "Fly");
+    } else {
+        panic!("Expected unknown action error.")
+    }
+    assert!(matches!(
+        Scenario::from_json(r#"{"steps": "Start"}"#).unwrap_err(),
+        Error::ScenarioFormat(_)
+    ));
+}
+
+#[test]
+fn independent_sessions() {
+    let dbname_1 = "for_tests/independent_sessions_1";
+    let dbname_2 = "for_tests/independent_sessions_2";
+    for dbname in [dbname_1, dbname_2] {
+        populate_cold_nav_test(dbname).unwrap();
+        init_db(dbname, verifier_alice_sr25519()).unwrap();
+    }
+    let session_1 = init_session(dbname_1, Vec::new()).unwrap();
+    let session_2 = init_session(dbname_2, vec!["Alice".to_string()]).unwrap();
+    assert_ne!(session_1, session_2);
+
+    // sessions are driven in parallel and do not interfere
+    let threads: Vec<_> = [
+        (session_1, Action::NavbarSettings),
+        (session_2, Action::NavbarLog),
+    ]
+    .into_iter()
+    .map(|(handle, action)| {
+        std::thread::spawn(move || {
+            do_session_action(handle, Action::Start, "", "").unwrap();
+            do_session_action(handle, action, "", "").unwrap().unwrap()
+        })
+    })
+    .collect();
+    let results: Vec<ActionResult> = threads
+        .into_iter()
+        .map(|thread| thread.join().unwrap())
+        .collect();
+    assert!(matches!(
+        results[0].screen_data,
+        ScreenData::Settings { .. }
+    ));
+    assert!(matches!(results[1].screen_data, ScreenData::Log { .. }));
+
+    update_session_seed_names(session_1, vec!["Alice".to_string()]).unwrap();
+    let action = do_session_action(session_1, Action::NavbarKeys, "", "")
+        .unwrap()
+        .unwrap();
+    if let ScreenData::SeedSelector { f } = action.screen_data {
+        assert_eq!(f.seed_name_cards.len(), 1);
+    } else {
+        panic!("Expected seed selector screen.")
+    }
+
+    // panic while holding the session lock resets the session to log screen
+    let state = session(session_1).unwrap();
+    let _ = std::thread::spawn(move || {
+        let _guard = state.lock().unwrap();
+        panic!("Panic while holding navigation state lock.");
+    })
+    .join();
+    assert!(session(session_1).unwrap().is_poisoned());
+    let action = do_session_action(session_1, Action::Nothing, "", "")
+        .unwrap()
+        .unwrap();
+    assert!(matches!(action.screen_data, ScreenData::Log { .. }));
+    assert!(!session(session_1).unwrap().is_poisoned());
+    let action = do_session_action(session_1, Action::NavbarSettings, "", "")
+        .unwrap()
+        .unwrap();
+    assert!(matches!(action.screen_data, ScreenData::Settings { .. }));
+    let action = do_session_action(session_1, Action::Nothing, "", "")
+        .unwrap()
+        .unwrap();
+    assert!(matches!(action.screen_data, ScreenData::Settings { .. }));
+
+    // handles wrap around, the ones still in use are skipped
+    SESSIONS.lock().unwrap().next_handle = session_1;
+    let session_3 = init_session(dbname_1, Vec::new()).unwrap();
+    assert_ne!(session_3, session_1);
+    assert_ne!(session_3, session_2);
+    close_session(session_3).unwrap();
+
+    close_session(session_1).unwrap();
+    close_session(session_2).unwrap();
+    assert!(matches!(
+        do_session_action(session_1, Action::Start, "", "").unwrap_err(),
+        Error::SessionNotFound(handle) if handle == session_1
+    ));
+    assert!(update_session_seed_names(session_2, Vec::new()).is_err());
+    assert!(close_session(session_2).is_err());
+
+    for dbname in [dbname_1, dbname_2] {
+        std::fs::remove_dir_all(dbname).unwrap();
+    }
+}
+
+#[test]
+fn session_migrates_database() {
+    let dbname = "for_tests/session_migrates_database";
+    populate_cold_nav_test(dbname).unwrap();
+    init_db(dbname, verifier_alice_sr25519()).unwrap();
+
+    // database as it was before the schema versioning
+    let mut settings_batch = Batch::default();
+    settings_batch.remove(SCHEMA_VERSION);
+    settings_batch.remove(POLICY);
+    settings_batch.insert(DANGER, false.encode());
+    TrDbCold::new()
+        .set_settings(settings_batch)
+        .apply(dbname)
+        .unwrap();
+    assert_eq!(get_schema_version(dbname).unwrap(), 0);
+
+    let migrations_recorded = || {
+        get_history(dbname)
+            .unwrap()
+            .into_iter()
+            .flat_map(|(_, entry)| entry.events)
+            .filter(|event| matches!(event, Event::DatabaseMigrated { .. }))
+            .count()
+    };
+
+    // pending migrations are applied when the session starts
+    let handle = init_session(dbname, Vec::new()).unwrap();
+    assert_eq!(get_schema_version(dbname).unwrap(), LIVE_SCHEMA_VERSION);
+    assert_eq!(migrations_recorded(), LIVE_SCHEMA_VERSION as usize);
+    let action = do_session_action(handle, Action::Start, "", "")
+        .unwrap()
+        .unwrap();
+    assert!(matches!(
+        action.screen_data,
+        ScreenData::SeedSelector { .. }
+    ));
+    close_session(handle).unwrap();
+
+    // and only once
+    let handle = init_session(dbname, Vec::new()).unwrap();
+    assert_eq!(migrations_recorded(), LIVE_SCHEMA_VERSION as usize);
+    close_session(handle).unwrap();
+
+    // database from a newer Signer is refused
+    let mut settings_batch = Batch::default();
+    settings_batch.insert(SCHEMA_VERSION, (LIVE_SCHEMA_VERSION + 1).encode());
+    TrDbCold::new()
+        .set_settings(settings_batch)
+        .apply(dbname)
+        .unwrap();
+    assert!(init_session(dbname, Vec::new()).is_err());
+
     std::fs::remove_dir_all(dbname).unwrap();
 }
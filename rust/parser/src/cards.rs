@@ -4,6 +4,8 @@ use sp_core::{
 };
 use sp_runtime::generic::Era;
 
+use definitions::localisation::LocalisedMessage;
+
 #[derive(Clone)]
 pub enum ParserCard {
     Pallet(String), // pallet name
@@ -57,70 +59,79 @@ pub enum ParserCard {
 
 impl ParserCard {
     pub fn show_no_docs(&self, indent: u32) -> String {
+        format!("{}{}", "  ".repeat(indent as usize), self.message().show())
+    }
+
+    /// Card contents without docs, as localisation-ready message
+    pub fn message(&self) -> LocalisedMessage {
         match &self {
-            ParserCard::Pallet(pallet_name) => readable(indent, "pallet", pallet_name),
+            ParserCard::Pallet(pallet_name) => {
+                LocalisedMessage::new("card.pallet").with_text("pallet", pallet_name)
+            }
             ParserCard::Method {
                 method_name,
                 docs: _,
-            } => readable(indent, "method", method_name),
-            ParserCard::Varname(varname) => readable(indent, "varname", varname),
-            ParserCard::Default(decoded_string) => readable(indent, "default", decoded_string),
-            ParserCard::Text(decoded_text) => readable(indent, "text", decoded_text),
-            ParserCard::Id { id, base58prefix } => readable(
-                indent,
-                "Id",
+            } => LocalisedMessage::new("card.method").with_text("method", method_name),
+            ParserCard::Varname(varname) => {
+                LocalisedMessage::new("card.varname").with_text("varname", varname)
+            }
+            ParserCard::Default(decoded_string) => {
+                LocalisedMessage::new("card.default").with_text("value", decoded_string)
+            }
+            ParserCard::Text(decoded_text) => {
+                LocalisedMessage::new("card.text").with_text("text", decoded_text)
+            }
+            ParserCard::Id { id, base58prefix } => LocalisedMessage::new("card.id").with_text(
+                "address",
                 &id.to_ss58check_with_version(Ss58AddressFormat::custom(*base58prefix)),
             ),
-            ParserCard::None => readable(indent, "none", ""),
-            ParserCard::IdentityField(variant) => readable(indent, "identity_field", variant),
-            ParserCard::BitVec(bv) => readable(indent, "bitvec", bv),
-            ParserCard::Balance { number, units } => {
-                readable(indent, "balance", &format!("{} {}", number, units))
+            ParserCard::None => LocalisedMessage::new("card.none"),
+            ParserCard::IdentityField(variant) => {
+                LocalisedMessage::new("card.identity_field").with_text("field", variant)
             }
+            ParserCard::BitVec(bv) => LocalisedMessage::new("card.bitvec").with_text("bitvec", bv),
+            ParserCard::Balance { number, units } => LocalisedMessage::new("card.balance")
+                .with_text("number", number)
+                .with_text("units", units),
             ParserCard::FieldName {
                 name,
                 docs_field_name: _,
                 path_type: _,
                 docs_type: _,
-            } => readable(indent, "field_name", name),
+            } => LocalisedMessage::new("card.field_name").with_text("name", name),
             ParserCard::FieldNumber {
                 number,
                 docs_field_number: _,
                 path_type: _,
                 docs_type: _,
-            } => readable(indent, "field_number", &number.to_string()),
+            } => LocalisedMessage::new("card.field_number").with_number("number", *number as u64),
             ParserCard::EnumVariantName {
                 name,
                 docs_enum_variant: _,
-            } => readable(indent, "enum_variant_name", name),
+            } => LocalisedMessage::new("card.enum_variant_name").with_text("name", name),
             ParserCard::Era(era) => match era {
-                Era::Immortal => readable(indent, "era", "Immortal"),
-                Era::Mortal(period, phase) => readable(
-                    indent,
-                    "era",
-                    &format!("Mortal, phase: {}, period: {}", phase, period),
-                ),
+                Era::Immortal => LocalisedMessage::new("card.era_immortal"),
+                Era::Mortal(period, phase) => LocalisedMessage::new("card.era_mortal")
+                    .with_number("phase", *phase)
+                    .with_number("period", *period),
             },
-            ParserCard::Nonce(nonce) => readable(indent, "nonce", nonce),
-            ParserCard::BlockHash(block_hash) => {
-                readable(indent, "block_hash", &hex::encode(block_hash))
+            ParserCard::Nonce(nonce) => {
+                LocalisedMessage::new("card.nonce").with_text("nonce", nonce)
             }
-            ParserCard::Tip { number, units } => {
-                readable(indent, "tip", &format!("{} {}", number, units))
+            ParserCard::BlockHash(block_hash) => {
+                LocalisedMessage::new("card.block_hash").with_hex("block_hash", block_hash)
             }
+            ParserCard::Tip { number, units } => LocalisedMessage::new("card.tip")
+                .with_text("number", number)
+                .with_text("units", units),
             ParserCard::NetworkNameVersion { name, version } => {
-                readable(indent, "network", &format!("{}{}", name, version))
+                LocalisedMessage::new("card.network")
+                    .with_text("name", name)
+                    .with_text("version", version)
+            }
+            ParserCard::TxVersion(x) => {
+                LocalisedMessage::new("card.tx_version").with_text("tx_version", x)
             }
-            ParserCard::TxVersion(x) => readable(indent, "tx_version", x),
         }
     }
 }
-
-fn readable(indent: u32, card_type: &str, card_payload: &str) -> String {
-    format!(
-        "{}{}: {}",
-        "  ".repeat(indent as usize),
-        card_type,
-        card_payload
-    )
-}
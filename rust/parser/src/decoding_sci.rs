@@ -13,13 +13,19 @@ use scale_info::{
     TypeDefPrimitive, TypeDefVariant,
 };
 
-use definitions::network_specs::ShortSpecs;
+use definitions::{
+    metadata_proof::is_omitted_type,
+    network_specs::{ShortSpecs, TokenSpecs},
+};
 
 use crate::decoding_commons::{
     decode_known_length, decode_primitive_with_flags, get_compact, special_case_account_id,
     DecodedOut, OutputCard,
 };
-use crate::decoding_sci_ext::{special_case_era, special_case_hash, Ext, Hash, SpecialExt};
+use crate::decoding_sci_ext::{
+    special_case_asset_tip, special_case_era, special_case_hash, special_case_metadata_hash, Ext,
+    Hash, SpecialExt,
+};
 use crate::error::{ParserDecodingError, ParserMetadataError, Result};
 use crate::{cards::ParserCard, Error};
 
@@ -322,6 +328,56 @@ fn field_type_name_is_balance(type_name: &str) -> bool {
         || (type_name == "PalletBalanceOf<T>")
 }
 
+/// Check if the field type name is a currency id of `orml_tokens` or an asset
+/// id of `pallet_assets`.
+fn field_type_name_is_currency_id(type_name: &str) -> bool {
+    (type_name == "CurrencyIdOf<T>")
+        || (type_name == "T::CurrencyId")
+        || (type_name == "T::AssetId")
+        || (type_name == "T::AssetIdParameter")
+}
+
+/// Select network specs to display balance-related values of the call, after
+/// the call currency id was decoded.
+///
+/// Currency of `orml_tokens` is found by enum variant name matching the token
+/// unit, asset of `pallet_assets` is found by asset id. Balance-related values
+/// in unknown currency are displayed with decimals `0` and unit `UNIT`.
+pub(crate) fn currency_specs(
+    short_specs: &ShortSpecs,
+    currency_cards: &[OutputCard],
+) -> ShortSpecs {
+    for x in currency_cards.iter() {
+        match &x.card {
+            ParserCard::EnumVariantName { name, .. } => {
+                if name == &short_specs.unit {
+                    return short_specs.to_owned();
+                }
+                if let Some(token) = short_specs.tokens.iter().find(|t| &t.unit == name) {
+                    return short_specs.with_token(token);
+                }
+            }
+            ParserCard::Default(number) => {
+                if let Ok(asset_id) = number.parse::<u32>() {
+                    if let Some(token) = short_specs
+                        .tokens
+                        .iter()
+                        .find(|t| t.asset_id == Some(asset_id))
+                    {
+                        return short_specs.with_token(token);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    short_specs.with_token(&TokenSpecs {
+        asset_id: None,
+        decimals: 0,
+        unit: String::from("UNIT"),
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn decoding_sci_complete(
     current_type: &Type<PortableForm>,
@@ -334,6 +390,9 @@ pub(crate) fn decoding_sci_complete(
     indent: u32,
     short_specs: &ShortSpecs,
 ) -> Result<DecodedOut> {
+    if is_omitted_type(current_type) {
+        return Err(ParserDecodingError::TypeOmittedInProof.into());
+    }
     if let Some(ext) = possible_ext {
         ext.check_special(current_type)
     }
@@ -358,18 +417,34 @@ pub(crate) fn decoding_sci_complete(
                         return special_case_era(data, &mut ext.found_ext, indent);
                     }
                 }
+                if let SpecialExt::MetadataHash = ext.specialty {
+                    if current_type.path().ident().as_deref() == Some("Option") {
+                        return special_case_metadata_hash(data, &mut ext.found_ext);
+                    }
+                }
             }
             match current_type.type_def() {
-                TypeDef::Composite(x) => decode_type_def_composite(
-                    x,
-                    possible_ext,
-                    compact_flag,
-                    balance_flag,
-                    data,
-                    meta_v14,
-                    indent,
-                    short_specs,
-                ),
+                TypeDef::Composite(x) => {
+                    if let Some(ext) = possible_ext {
+                        if let SpecialExt::AssetTip = ext.specialty {
+                            if let Some(decoded_out) =
+                                special_case_asset_tip(x, &data, meta_v14, indent, short_specs)?
+                            {
+                                return Ok(decoded_out);
+                            }
+                        }
+                    }
+                    decode_type_def_composite(
+                        x,
+                        possible_ext,
+                        compact_flag,
+                        balance_flag,
+                        data,
+                        meta_v14,
+                        indent,
+                        short_specs,
+                    )
+                }
                 TypeDef::Variant(x) => {
                     reject_flags(compact_flag, balance_flag)?;
                     decode_type_def_variant(
@@ -887,7 +962,12 @@ fn process_fields(
     let mut indent_skipped = false;
     let mut field_is_str = false;
     let mut fancy_out: Vec<OutputCard> = Vec::new();
+
+    // network specs for balance-related values, changed for the remaining
+    // call arguments after the currency id, if the network has several tokens
+    let mut call_specs: Option<ShortSpecs> = None;
     for (i, x) in fields.iter().enumerate() {
+        let field_specs = call_specs.as_ref().unwrap_or(short_specs);
         let mut field_docs = String::new();
         for (j, y) in x.docs().iter().enumerate() {
             if j > 0 {
@@ -951,10 +1031,17 @@ fn process_fields(
                     data,
                     meta_v14,
                     indent,
-                    short_specs,
+                    field_specs,
                 )?
             }
         };
+        // currency id is matched against the network own specs, not the
+        // token selected by the previous currency id
+        if let (CallExpectation::Method, Some(type_name)) = (call_expectation, x.type_name()) {
+            if !short_specs.tokens.is_empty() && field_type_name_is_currency_id(type_name) {
+                call_specs = Some(currency_specs(short_specs, &after_run.fancy_out));
+            }
+        }
         fancy_out.extend_from_slice(&after_run.fancy_out);
         data = after_run.remaining_vector;
     }
@@ -1117,7 +1204,7 @@ fn ugly_patch_u64<O: BitOrder>(into_bv_decode: Vec<u8>) -> Result<String> {
     Ok(out)
 }
 
-fn type_path_docs(
+pub(crate) fn type_path_docs(
     meta_v14: &RuntimeMetadataV14,
     type_id: u32,
 ) -> Result<(Type<PortableForm>, String, String)> {
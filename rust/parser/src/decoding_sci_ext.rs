@@ -1,15 +1,19 @@
 use frame_metadata::v14::RuntimeMetadataV14;
 use parity_scale_codec::Decode;
-use scale_info::{form::PortableForm, Type};
+use printing_balance::convert_balance_pretty;
+use scale_info::{form::PortableForm, Type, TypeDef, TypeDefComposite};
 use sp_core::H256;
 use sp_runtime::generic::Era;
 
-use definitions::network_specs::ShortSpecs;
+use definitions::{
+    metadata::{extension_rule, ExtensionRule},
+    network_specs::ShortSpecs,
+};
 
 use crate::cards::ParserCard;
-use crate::decoding_commons::{DecodedOut, OutputCard};
-use crate::decoding_sci::{decoding_sci_complete, CallExpectation};
-use crate::error::{Error, ParserDecodingError, Result};
+use crate::decoding_commons::{get_compact, DecodedOut, OutputCard};
+use crate::decoding_sci::{currency_specs, decoding_sci_complete, type_path_docs, CallExpectation};
+use crate::error::{Error, ParserDecodingError, ParserMetadataError, Result};
 
 pub(crate) fn decode_ext_attempt(
     data: &[u8],
@@ -86,19 +90,22 @@ impl Ext {
                 "Era" => SpecialExt::Era,
                 "CheckNonce" => SpecialExt::Nonce,
                 "ChargeTransactionPayment" => SpecialExt::Tip,
+                "ChargeAssetTxPayment" => SpecialExt::AssetTip,
                 _ => SpecialExt::None,
             },
             None => SpecialExt::None,
         };
         if let SpecialExt::None = self.specialty {
-            self.specialty = match self.identifier.as_str() {
-                "CheckSpecVersion" => SpecialExt::SpecVersion,
-                "CheckTxVersion" => SpecialExt::TxVersion,
-                "CheckGenesis" => SpecialExt::Hash(Hash::GenesisHash),
-                "CheckMortality" => SpecialExt::Hash(Hash::BlockHash),
-                "CheckNonce" => SpecialExt::Nonce,
-                "ChargeTransactionPayment" => SpecialExt::Tip,
-                _ => SpecialExt::None,
+            self.specialty = match extension_rule(&self.identifier) {
+                Some(ExtensionRule::SpecVersion) => SpecialExt::SpecVersion,
+                Some(ExtensionRule::TxVersion) => SpecialExt::TxVersion,
+                Some(ExtensionRule::GenesisHash) => SpecialExt::Hash(Hash::GenesisHash),
+                Some(ExtensionRule::BlockHash) => SpecialExt::Hash(Hash::BlockHash),
+                Some(ExtensionRule::Nonce) => SpecialExt::Nonce,
+                Some(ExtensionRule::Tip) => SpecialExt::Tip,
+                Some(ExtensionRule::AssetTip) => SpecialExt::AssetTip,
+                Some(ExtensionRule::MetadataHash) => SpecialExt::MetadataHash,
+                None => SpecialExt::None,
             };
         }
     }
@@ -109,6 +116,7 @@ pub(crate) struct FoundExt {
     pub(crate) genesis_hash: Option<H256>,
     pub(crate) block_hash: Option<H256>,
     pub(crate) network_version_printed: Option<String>,
+    pub(crate) metadata_hash: Option<H256>,
 }
 
 impl FoundExt {
@@ -118,6 +126,7 @@ impl FoundExt {
             genesis_hash: None,
             block_hash: None,
             network_version_printed: None,
+            metadata_hash: None,
         }
     }
 }
@@ -133,6 +142,8 @@ pub(crate) enum SpecialExt {
     Era,
     Nonce,
     Tip,
+    AssetTip,
+    MetadataHash,
     SpecVersion,
     TxVersion,
     Hash(Hash),
@@ -175,6 +186,36 @@ pub(crate) fn special_case_hash(
     }
 }
 
+/// Decode metadata hash from `Option<[u8; 32]>` additional signed data of
+/// `CheckMetadataHash` extension.
+///
+/// Metadata hash is not displayed, it is only recorded to check the metadata
+/// proof, if the transaction has one.
+pub(crate) fn special_case_metadata_hash(
+    data: Vec<u8>,
+    found_ext: &mut FoundExt,
+) -> Result<DecodedOut> {
+    let remaining_vector = match data.first() {
+        Some(0) => data[1..].to_vec(),
+        Some(1) => match data.get(1..33) {
+            Some(a) => {
+                if found_ext.metadata_hash.is_some() {
+                    return Err(ParserMetadataError::MetadataHashTwice.into());
+                }
+                found_ext.metadata_hash = Some(H256::from_slice(a));
+                data[33..].to_vec()
+            }
+            None => return Err(ParserDecodingError::DataTooShort.into()),
+        },
+        Some(_) => return Err(ParserDecodingError::UnexpectedOptionVariant.into()),
+        None => return Err(ParserDecodingError::DataTooShort.into()),
+    };
+    Ok(DecodedOut {
+        remaining_vector,
+        fancy_out: Vec::new(),
+    })
+}
+
 pub(crate) fn special_case_era(
     data: Vec<u8>,
     found_ext: &mut FoundExt,
@@ -184,9 +225,9 @@ pub(crate) fn special_case_era(
         Some(0) => (data[0..1].to_vec(), data[1..].to_vec()),
         Some(_) => match data.get(0..2) {
             Some(a) => (a.to_vec(), data[2..].to_vec()),
-            None => return Err(Error::Decoding(ParserDecodingError::DataTooShort)),
+            None => return Err(ParserDecodingError::DataTooShort.into()),
         },
-        None => return Err(Error::Decoding(ParserDecodingError::DataTooShort)),
+        None => return Err(ParserDecodingError::DataTooShort.into()),
     };
     match Era::decode(&mut &era_data[..]) {
         Ok(a) => {
@@ -202,3 +243,97 @@ pub(crate) fn special_case_era(
         Err(_) => Err(Error::Decoding(ParserDecodingError::Era)),
     }
 }
+
+/// Decode `ChargeAssetTxPayment` signed extension.
+///
+/// Extension has compact `tip` and optional `asset_id` fields. Tip is paid in
+/// the asset, if one is selected, and is displayed with the asset decimals and
+/// unit from network specs, followed by the asset id. If no asset is selected,
+/// tip is paid in the network main token.
+///
+/// Outputs `None` if the extension has unexpected structure, so that it could
+/// be decoded as a generic type.
+pub(crate) fn special_case_asset_tip(
+    composite_ty: &TypeDefComposite<PortableForm>,
+    data: &[u8],
+    meta_v14: &RuntimeMetadataV14,
+    indent: u32,
+    short_specs: &ShortSpecs,
+) -> Result<Option<DecodedOut>> {
+    let (tip_field, asset_field) = match composite_ty.fields() {
+        [tip_field, asset_field] => (tip_field, asset_field),
+        _ => return Ok(None),
+    };
+    let (tip_type, _, _) = type_path_docs(meta_v14, tip_field.ty().id())?;
+    if !matches!(tip_type.type_def(), TypeDef::Compact(_)) {
+        return Ok(None);
+    }
+    let (asset_type, path_type, docs_type) = type_path_docs(meta_v14, asset_field.ty().id())?;
+    if asset_type.path().ident().as_deref() != Some("Option") {
+        return Ok(None);
+    }
+
+    let tip = get_compact::<u128>(data)?;
+    let data = match tip.start_next_unit {
+        Some(start) => data[start..].to_vec(),
+        None => return Err(ParserDecodingError::DataTooShort.into()),
+    };
+    let asset_decoded = decoding_sci_complete(
+        &asset_type,
+        &mut None,
+        false,
+        false,
+        &CallExpectation::None,
+        data,
+        meta_v14,
+        indent + 1,
+        short_specs,
+    )?;
+    let asset_is_selected = !matches!(
+        asset_decoded.fancy_out.first().map(|x| &x.card),
+        Some(ParserCard::None)
+    );
+    let tip_specs = if asset_is_selected {
+        currency_specs(short_specs, &asset_decoded.fancy_out)
+    } else {
+        short_specs.to_owned()
+    };
+    let tip_output = convert_balance_pretty(
+        &tip.compact_found.to_string(),
+        tip_specs.decimals,
+        &tip_specs.unit,
+    );
+    let mut fancy_out = vec![OutputCard {
+        card: ParserCard::Tip {
+            number: tip_output.number,
+            units: tip_output.units,
+        },
+        indent,
+    }];
+    if asset_is_selected {
+        let mut docs_field_name = String::new();
+        for (i, x) in asset_field.docs().iter().enumerate() {
+            if i > 0 {
+                docs_field_name.push('\n');
+            }
+            docs_field_name.push_str(x);
+        }
+        fancy_out.push(OutputCard {
+            card: ParserCard::FieldName {
+                name: asset_field
+                    .name()
+                    .map(|x| x.to_string())
+                    .unwrap_or_else(|| String::from("asset_id")),
+                docs_field_name,
+                path_type,
+                docs_type,
+            },
+            indent,
+        });
+        fancy_out.extend_from_slice(&asset_decoded.fancy_out);
+    }
+    Ok(Some(DecodedOut {
+        remaining_vector: asset_decoded.remaining_vector,
+        fancy_out,
+    }))
+}
@@ -100,6 +100,9 @@ pub enum ParserMetadataError {
 
     #[error("metadata extensions have more than one network metadata version")]
     SpecVersionTwice,
+
+    #[error("metadata extensions have more than one metadata hash")]
+    MetadataHashTwice,
 }
 
 /// Errors directly related to transaction parsing
@@ -109,6 +112,7 @@ pub enum ParserMetadataError {
 /// - `53xx00` mortal transactions
 /// - `53xx02` immortal transactions
 /// - `53xx03` text message transactions
+/// - `53xx06` transactions with partial metadata proof
 ///
 /// `53xx00` and `53xx02` transactions contain encoded transaction data, and
 /// are parsed prior to signing using the network metadata. Transaction is
@@ -177,6 +181,15 @@ pub enum ParserMetadataError {
 /// nested calls are processed through the type resolver, i.e. the pallet index
 /// is processed independently only on the start of the decoding.
 ///
+/// `53xx06` transaction has a SCALE-encoded
+/// [`MetadataProof`](definitions::metadata_proof::MetadataProof) right after
+/// the public key, and otherwise is same as `53xx00` transaction. Call is
+/// decoded with `RuntimeMetadataV14` restored from the proof, not with the
+/// metadata from the database. Extensions are decoded with the network metadata
+/// from the database that has `CheckMetadataHash` extension, and the metadata
+/// digest restored from the proof must match the metadata hash in the
+/// transaction extensions.
+///
 /// `53xx03` transaction consists of:
 ///
 /// - prelude `53xx03`, where `xx` stands for the encryption algorithm
@@ -214,6 +227,21 @@ pub enum ParserDecodingError {
     #[error("Block hash for immortal transaction not matching genesis hash for the network.")]
     ImmortalHashMismatch,
 
+    /// Transaction decoded with the metadata from a proof has no metadata hash
+    /// in the extensions, and thus the proof could not be checked.
+    #[error("Transaction extensions have no metadata hash to check the metadata proof against.")]
+    NoMetadataHash,
+
+    /// Metadata digest restored from the proof does not match the metadata
+    /// hash in the transaction extensions.
+    #[error("Metadata proof does not match the metadata hash from the transaction extensions.")]
+    MetadataHashMismatch,
+
+    /// Only for `RuntimeMetadataV14` restored from a metadata proof. Decoding
+    /// needs a type that was not included in the proof.
+    #[error("Type needed for decoding is not in the metadata proof.")]
+    TypeOmittedInProof,
+
     /// Error decoding the extensions using metadata with `RuntimeMetadataV12`
     /// or `RuntimeMetadataV13`, with default extensions set.
     #[error("Unable to decode extensions for V12/V13 metadata using standard extensions set.")]
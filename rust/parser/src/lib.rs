@@ -53,6 +53,12 @@ pub fn parse_method(
         MetadataBundle::Sci {
             meta_v14,
             network_version: _,
+        }
+        | MetadataBundle::Proof {
+            meta_v14,
+            extensions_meta_v14: _,
+            network_version: _,
+            metadata_hash: _,
         } => decoding_sci_entry_point(method_data, meta_v14, start_indent, short_specs)?,
     };
     if !method_decoded.remaining_vector.is_empty() {
@@ -143,6 +149,12 @@ pub fn parse_extensions(
         MetadataBundle::Sci {
             meta_v14,
             network_version,
+        }
+        | MetadataBundle::Proof {
+            meta_v14: _,
+            extensions_meta_v14: meta_v14,
+            network_version,
+            metadata_hash: _,
         } => {
             let mut ext = Ext::init();
             let extensions_decoded =
@@ -188,6 +200,16 @@ pub fn parse_extensions(
                     ParserDecodingError::SomeDataNotUsedExtensions,
                 ));
             }
+            if let MetadataBundle::Proof { metadata_hash, .. } = metadata_bundle {
+                match ext.found_ext.metadata_hash {
+                    Some(a) => {
+                        if a != *metadata_hash {
+                            return Err(Error::Decoding(ParserDecodingError::MetadataHashMismatch));
+                        }
+                    }
+                    None => return Err(Error::Decoding(ParserDecodingError::NoMetadataHash)),
+                }
+            }
             (era, block_hash, extensions_decoded.fancy_out)
         }
     };
@@ -329,4 +351,34 @@ pub enum MetadataBundle<'a> {
         meta_v14: &'a RuntimeMetadataV14,
         network_version: u32,
     },
+
+    /// Partial metadata restored from
+    /// [`MetadataProof`](definitions::metadata_proof::MetadataProof), used
+    /// for the call.
+    ///
+    /// Extensions are decoded with `extensions_meta_v14`, the network
+    /// metadata from the database with `CheckMetadataHash` extension, and
+    /// must have metadata hash matching the proof digest.
+    Proof {
+        meta_v14: &'a RuntimeMetadataV14,
+        extensions_meta_v14: &'a RuntimeMetadataV14,
+        network_version: u32,
+        metadata_hash: H256,
+    },
+}
+
+impl<'a> MetadataBundle<'a> {
+    pub fn network_version(&self) -> u32 {
+        match self {
+            MetadataBundle::Older {
+                network_version, ..
+            }
+            | MetadataBundle::Sci {
+                network_version, ..
+            }
+            | MetadataBundle::Proof {
+                network_version, ..
+            } => *network_version,
+        }
+    }
 }
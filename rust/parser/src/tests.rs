@@ -1,7 +1,26 @@
-use crate::{parse_and_display_set, Error};
-use definitions::network_specs::ShortSpecs;
-use frame_metadata::RuntimeMetadata;
-use parity_scale_codec::Decode;
+use crate::cards::ParserCard;
+use crate::decoding_commons::OutputCard;
+use crate::decoding_sci_ext::{decode_ext_attempt, Ext};
+use crate::error::ParserDecodingError;
+use crate::{
+    cut_method_extensions, parse_and_display_set, parse_extensions, parse_method, Error,
+    MetadataBundle,
+};
+use definitions::{
+    metadata_proof::{ExtraInfo, MetadataProof},
+    network_specs::{ShortSpecs, TokenSpecs},
+};
+use frame_metadata::{
+    v14::{
+        ExtrinsicMetadata, PalletCallMetadata, PalletMetadata, RuntimeMetadataV14,
+        SignedExtensionMetadata,
+    },
+    RuntimeMetadata,
+};
+use parity_scale_codec::{Compact, Decode, Encode};
+use printing_balance::convert_balance_pretty;
+use scale_info::{meta_type, Registry, TypeInfo};
+use sp_core::H256;
 
 fn metadata(filename: &str) -> RuntimeMetadata {
     let metadata_hex = std::fs::read_to_string(&filename).unwrap();
@@ -19,6 +38,7 @@ fn specs() -> ShortSpecs {
         ]
         .into(),
         name: "westend".to_string(),
+        tokens: Vec::new(),
         unit: "WND".to_string(),
     }
 }
@@ -191,6 +211,7 @@ fn tr_6() {
         ]
         .into(),
         name: "acala".to_string(),
+        tokens: Vec::new(),
         unit: "ACA".to_string(),
     };
     let reply =
@@ -222,3 +243,440 @@ block_hash: 5cfeb3e46c080274613bdb80809a3e84fe782ac31ea91e2c778de996f738e620"#;
         reply
     );
 }
+
+#[test]
+fn tr_7() {
+    let data = hex::decode("b00c0000dc621b10081b4b51335553ef8df227feb0327649d00beab6e09c10a1dce9735900020b00407a10f35a24010000dc07000001000000fc41b9bd8ef8fe53d58c7ea67c794c7ec9a73daf05e6d54b14ff6342c99ba64c5cfeb3e46c080274613bdb80809a3e84fe782ac31ea91e2c778de996f738e620").unwrap();
+    let specs_acala = ShortSpecs {
+        base58prefix: 10,
+        decimals: 12,
+        genesis_hash: [
+            252, 65, 185, 189, 142, 248, 254, 83, 213, 140, 126, 166, 124, 121, 76, 126, 201, 167,
+            61, 175, 5, 230, 213, 75, 20, 255, 99, 66, 201, 155, 166, 76,
+        ]
+        .into(),
+        name: "acala".to_string(),
+        tokens: vec![
+            TokenSpecs {
+                asset_id: None,
+                decimals: 12,
+                unit: "AUSD".to_string(),
+            },
+            TokenSpecs {
+                asset_id: None,
+                decimals: 10,
+                unit: "DOT".to_string(),
+            },
+        ],
+        unit: "ACA".to_string(),
+    };
+    let reply =
+        parse_and_display_set(&data, &metadata("for_tests/acala2012"), &specs_acala).unwrap();
+    let reply_known = r#"
+Method:
+
+pallet: Currencies,
+  method: transfer,
+    field_name: dest,
+      enum_variant_name: Id,
+        Id: 25rZGFcFEWz1d81xB98PJN8LQu5cCwjyazAerGkng5NDuk9C,
+    field_name: currency_id,
+      enum_variant_name: Token,
+        enum_variant_name: DOT,
+    field_name: amount,
+      balance: 10.0000000000000 kDOT
+
+
+Extensions:
+
+era: Mortal, phase: 18, period: 32,
+nonce: 0,
+tip: 0 pACA,
+network: acala2012,
+tx_version: 1,
+block_hash: 5cfeb3e46c080274613bdb80809a3e84fe782ac31ea91e2c778de996f738e620"#;
+    assert!(
+        reply == reply_known,
+        "Expected: {}\nReceived: {}",
+        reply_known,
+        reply
+    );
+}
+
+fn show(cards: &[OutputCard]) -> String {
+    cards
+        .iter()
+        .map(|x| x.card.show_no_docs(x.indent))
+        .collect::<Vec<String>>()
+        .join(",\n")
+}
+
+#[test]
+fn tr_8() {
+    let data = hex::decode("4d0210020806000046ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a07001b2c3ef70006050c0008264834504a64ace1373f0c8ed5d57381ddf54a2f67a318fa42b1352681606d00aebb0211dbb07b4d335a657257b8ac5e53794c901e4f616d4a254f2490c43934009ae581fef1fc06828723715731adcf810e42ce4dadad629b1b7fa5c3c144a81d550008009723000007000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e5b1d91c89d3de85a4d6eee76ecf3a303cf38b59e7d81522eb7cd24b02eb161ff").unwrap();
+    let meta_v14 = match metadata("for_tests/westend9111") {
+        RuntimeMetadata::V14(a) => a,
+        _ => panic!("expected V14 metadata"),
+    };
+    let extra = ExtraInfo {
+        spec_version: 9111,
+        spec_name: "westend".to_string(),
+        base58prefix: 42,
+        decimals: 12,
+        unit: "WND".to_string(),
+    };
+    let (method_data, extensions_data) = cut_method_extensions(&data).unwrap();
+    let metadata_bundle = MetadataBundle::Sci {
+        meta_v14: &meta_v14,
+        network_version: 9111,
+    };
+    let method_known = parse_method(method_data.to_vec(), &metadata_bundle, &specs()).unwrap();
+
+    // `Utility` pallet `batch_all` call
+    let proof = MetadataProof::for_call(&meta_v14, extra.to_owned(), 16, 2).unwrap();
+    let proof_meta_v14 = proof.runtime_metadata().unwrap();
+    let proof_bundle = MetadataBundle::Proof {
+        meta_v14: &proof_meta_v14,
+        extensions_meta_v14: &meta_v14,
+        network_version: 9111,
+        metadata_hash: proof.digest().unwrap(),
+    };
+    let method = parse_method(method_data.to_vec(), &proof_bundle, &specs()).unwrap();
+    assert_eq!(show(&method), show(&method_known));
+
+    // network metadata has no `CheckMetadataHash` extension, transaction
+    // extensions do not commit to metadata hash
+    let reply = parse_extensions(extensions_data, &proof_bundle, &specs(), None).unwrap_err();
+    assert!(
+        matches!(reply, Error::Decoding(ParserDecodingError::NoMetadataHash)),
+        "Received: {:?}",
+        reply
+    );
+
+    // `Balances` pallet `transfer` call
+    let proof = MetadataProof::for_call(&meta_v14, extra, 4, 0).unwrap();
+    let proof_meta_v14 = proof.runtime_metadata().unwrap();
+    let proof_bundle = MetadataBundle::Proof {
+        meta_v14: &proof_meta_v14,
+        extensions_meta_v14: &meta_v14,
+        network_version: 9111,
+        metadata_hash: proof.digest().unwrap(),
+    };
+    let reply = parse_method(method_data, &proof_bundle, &specs()).unwrap_err();
+    assert!(
+        matches!(
+            reply,
+            Error::Decoding(ParserDecodingError::TypeOmittedInProof)
+        ),
+        "Received: {:?}",
+        reply
+    );
+}
+
+/// Transaction mortality, as in runtime, immortal only
+#[allow(dead_code)]
+#[derive(TypeInfo)]
+enum Era {
+    Immortal,
+}
+
+/// `CheckMetadataHash` signed extension mode
+#[allow(dead_code)]
+#[derive(TypeInfo)]
+enum Mode {
+    Disabled,
+    Enabled,
+}
+
+/// Metadata with signed extensions needed for transaction with metadata
+/// proof, as it would be loaded into Signer
+fn metadata_hash_metadata() -> RuntimeMetadataV14 {
+    let mut registry = Registry::new();
+    let empty = registry.register_type(&meta_type::<()>());
+    let era = registry.register_type(&meta_type::<Era>());
+    let version = registry.register_type(&meta_type::<u32>());
+    let hash = registry.register_type(&meta_type::<[u8; 32]>());
+    let mode = registry.register_type(&meta_type::<Mode>());
+    let metadata_hash = registry.register_type(&meta_type::<Option<[u8; 32]>>());
+    let extension = |identifier: &str, ty, additional_signed| SignedExtensionMetadata {
+        identifier: identifier.to_string(),
+        ty,
+        additional_signed,
+    };
+    RuntimeMetadataV14 {
+        types: registry.into(),
+        pallets: Vec::new(),
+        extrinsic: ExtrinsicMetadata {
+            ty: empty,
+            version: 4,
+            signed_extensions: vec![
+                extension("CheckSpecVersion", empty, version),
+                extension("CheckGenesis", empty, hash),
+                extension("CheckMortality", era, hash),
+                extension("CheckMetadataHash", mode, metadata_hash),
+            ],
+        },
+        ty: empty,
+    }
+}
+
+#[test]
+fn metadata_hash_in_extensions() {
+    let meta_v14 = match metadata("for_tests/westend9111") {
+        RuntimeMetadata::V14(a) => a,
+        _ => panic!("expected V14 metadata"),
+    };
+    let extra = ExtraInfo {
+        spec_version: 9111,
+        spec_name: "westend".to_string(),
+        base58prefix: 42,
+        decimals: 12,
+        unit: "WND".to_string(),
+    };
+    let proof = MetadataProof::for_call(&meta_v14, extra, 4, 0).unwrap();
+    let proof_meta_v14 = proof.runtime_metadata().unwrap();
+    let extensions_meta_v14 = metadata_hash_metadata();
+    let proof_bundle = MetadataBundle::Proof {
+        meta_v14: &proof_meta_v14,
+        extensions_meta_v14: &extensions_meta_v14,
+        network_version: 9111,
+        metadata_hash: proof.digest().unwrap(),
+    };
+
+    // immortal era, metadata hash enabled, then additional signed data
+    let extensions_data = |metadata_hash: H256| {
+        let mut data = vec![0, 1];
+        data.extend_from_slice(&9111u32.encode());
+        data.extend_from_slice(specs().genesis_hash.as_bytes());
+        data.extend_from_slice(specs().genesis_hash.as_bytes());
+        data.extend_from_slice(&Some(metadata_hash.to_fixed_bytes()).encode());
+        data
+    };
+    parse_extensions(
+        extensions_data(proof.digest().unwrap()),
+        &proof_bundle,
+        &specs(),
+        None,
+    )
+    .unwrap();
+
+    let reply =
+        parse_extensions(extensions_data(H256::zero()), &proof_bundle, &specs(), None).unwrap_err();
+    assert!(
+        matches!(
+            reply,
+            Error::Decoding(ParserDecodingError::MetadataHashMismatch)
+        ),
+        "Received: {:?}",
+        reply
+    );
+}
+
+/// `ChargeAssetTxPayment` signed extension, as in Asset Hub runtime
+#[derive(Encode, TypeInfo)]
+struct ChargeAssetTxPayment {
+    #[codec(compact)]
+    tip: u128,
+    asset_id: Option<u32>,
+}
+
+/// Metadata with Asset Hub signed extension only
+fn asset_hub_metadata() -> RuntimeMetadataV14 {
+    let mut registry = Registry::new();
+    let ty = registry.register_type(&meta_type::<ChargeAssetTxPayment>());
+    let empty = registry.register_type(&meta_type::<()>());
+    RuntimeMetadataV14 {
+        types: registry.into(),
+        pallets: Vec::new(),
+        extrinsic: ExtrinsicMetadata {
+            ty: empty,
+            version: 4,
+            signed_extensions: vec![SignedExtensionMetadata {
+                identifier: "ChargeAssetTxPayment".to_string(),
+                ty,
+                additional_signed: empty,
+            }],
+        },
+        ty: empty,
+    }
+}
+
+fn asset_hub_specs() -> ShortSpecs {
+    ShortSpecs {
+        base58prefix: 0,
+        decimals: 10,
+        genesis_hash: H256::from_slice(
+            &hex::decode("68d56f15f85d3136970ec16946040bc1752654e906147f7e43e9d539d7c3de2f")
+                .unwrap(),
+        ),
+        name: "statemint".to_string(),
+        tokens: vec![TokenSpecs {
+            asset_id: Some(1984),
+            decimals: 6,
+            unit: "USDt".to_string(),
+        }],
+        unit: "DOT".to_string(),
+    }
+}
+
+#[test]
+fn asset_tip_in_known_asset() {
+    let data = ChargeAssetTxPayment {
+        tip: 1_500_000,
+        asset_id: Some(1984),
+    }
+    .encode();
+    let decoded = decode_ext_attempt(
+        &data,
+        &mut Ext::init(),
+        &asset_hub_metadata(),
+        0,
+        &asset_hub_specs(),
+    )
+    .unwrap();
+    assert!(decoded.remaining_vector.is_empty());
+
+    // tip is in the asset decimals and unit, followed by the asset id
+    let tip = convert_balance_pretty("1500000", 6, "USDt");
+    assert!(matches!(
+        &decoded.fancy_out[0].card,
+        ParserCard::Tip { number, units } if number == &tip.number && units == &tip.units
+    ));
+    assert!(matches!(
+        &decoded.fancy_out[1].card,
+        ParserCard::FieldName { name, .. } if name == "asset_id"
+    ));
+    assert!(decoded
+        .fancy_out
+        .iter()
+        .any(|x| matches!(&x.card, ParserCard::Default(asset_id) if asset_id == "1984")));
+}
+
+#[test]
+fn asset_tip_no_asset_selected() {
+    let data = ChargeAssetTxPayment {
+        tip: 1_500_000,
+        asset_id: None,
+    }
+    .encode();
+    let decoded = decode_ext_attempt(
+        &data,
+        &mut Ext::init(),
+        &asset_hub_metadata(),
+        0,
+        &asset_hub_specs(),
+    )
+    .unwrap();
+    assert!(decoded.remaining_vector.is_empty());
+
+    // tip is in the network main token, no asset card
+    let tip = convert_balance_pretty("1500000", 10, "DOT");
+    assert_eq!(decoded.fancy_out.len(), 1);
+    assert!(matches!(
+        &decoded.fancy_out[0].card,
+        ParserCard::Tip { number, units } if number == &tip.number && units == &tip.units
+    ));
+}
+
+/// Runtime types used in calls, as in `orml_tokens` based runtimes
+trait Config: 'static {
+    type Balance: TypeInfo + 'static;
+    type CurrencyId: TypeInfo + 'static;
+}
+
+/// Currency id with the network main token and one more token
+#[derive(Encode, TypeInfo)]
+enum CurrencyId {
+    ACA,
+    DOT,
+}
+
+impl Config for () {
+    type Balance = u128;
+    type CurrencyId = CurrencyId;
+}
+
+/// Call with two currency ids, as `Dex` pallet swap in Acala runtime
+#[allow(dead_code, non_camel_case_types)]
+#[derive(TypeInfo)]
+#[scale_info(skip_type_params(T))]
+enum Call<T: Config> {
+    swap_with_exact_supply {
+        supply_currency_id: T::CurrencyId,
+        #[codec(compact)]
+        supply_amount: T::Balance,
+        target_currency_id: T::CurrencyId,
+        #[codec(compact)]
+        min_target_amount: T::Balance,
+    },
+}
+
+#[test]
+fn two_currency_ids_in_call() {
+    let mut registry = Registry::new();
+    let call_ty = registry.register_type(&meta_type::<Call<()>>());
+    let empty = registry.register_type(&meta_type::<()>());
+    let meta_v14 = RuntimeMetadataV14 {
+        types: registry.into(),
+        pallets: vec![PalletMetadata {
+            name: "Dex".to_string(),
+            storage: None,
+            calls: Some(PalletCallMetadata { ty: call_ty }),
+            event: None,
+            constants: Vec::new(),
+            error: None,
+            index: 91,
+        }],
+        extrinsic: ExtrinsicMetadata {
+            ty: empty,
+            version: 4,
+            signed_extensions: Vec::new(),
+        },
+        ty: empty,
+    };
+    let specs_acala = ShortSpecs {
+        base58prefix: 10,
+        decimals: 12,
+        genesis_hash: H256::from_slice(
+            &hex::decode("fc41b9bd8ef8fe53d58c7ea67c794c7ec9a73daf05e6d54b14ff6342c99ba64c")
+                .unwrap(),
+        ),
+        name: "acala".to_string(),
+        tokens: vec![TokenSpecs {
+            asset_id: None,
+            decimals: 10,
+            unit: "DOT".to_string(),
+        }],
+        unit: "ACA".to_string(),
+    };
+
+    // DOT supplied, main token ACA expected in return
+    let mut method_data = vec![91, 0];
+    method_data.extend(CurrencyId::DOT.encode());
+    method_data.extend(Compact(10_000_000_000u128).encode());
+    method_data.extend(CurrencyId::ACA.encode());
+    method_data.extend(Compact(2_000_000_000_000u128).encode());
+    let metadata_bundle = MetadataBundle::Sci {
+        meta_v14: &meta_v14,
+        network_version: 2012,
+    };
+    let method = parse_method(method_data, &metadata_bundle, &specs_acala).unwrap();
+
+    // each amount is in the currency given before it
+    let balances: Vec<(String, String)> = method
+        .iter()
+        .filter_map(|x| match &x.card {
+            ParserCard::Balance { number, units } => Some((number.to_owned(), units.to_owned())),
+            _ => None,
+        })
+        .collect();
+    let supply_amount = convert_balance_pretty("10000000000", 10, "DOT");
+    let min_target_amount = convert_balance_pretty("2000000000000", 12, "ACA");
+    assert_eq!(
+        balances,
+        vec![
+            (supply_amount.number, supply_amount.units),
+            (min_target_amount.number, min_target_amount.units),
+        ]
+    );
+}
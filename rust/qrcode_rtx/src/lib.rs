@@ -120,3 +120,19 @@ where
         transform_into_qr_apng(input, output_name)
     }
 }
+
+/// Function to make appropriately sized qr code frames, as separate png images
+///
+/// Input that fits into single qr code produces single static qr code frame,
+/// longer input produces fountain qr code frames, to be shown one by one.
+pub fn make_pretty_qr_frames(input: &[u8]) -> Result<Vec<Vec<u8>>, Box<dyn std::error::Error>> {
+    if input.len() <= 2953 {
+        Ok(vec![png_qr(input, DataType::Regular)?])
+    } else {
+        let mut out: Vec<Vec<u8>> = Vec::new();
+        for data_pack in make_data_packs(input)?.iter() {
+            out.push(png_qr(data_pack, DataType::Regular)?);
+        }
+        Ok(out)
+    }
+}
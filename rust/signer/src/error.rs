@@ -0,0 +1,880 @@
+//! Errors passed over FFI
+//!
+//! Typed errors from the Rust crates are converted into
+//! [`ErrorDisplayed::Typed`], with stable [`ErrorCode`] that frontend could
+//! react to, and with named parameters, so that the message could be localised
+//! by the frontend. English message is always provided as well.
+//!
+//! Error codes and parameter names are part of the FFI, and must not be
+//! changed or reused once released. New codes are added when needed.
+
+use std::{fmt::Display, str::FromStr};
+
+use definitions::{
+    crypto::Encryption,
+    helpers::multisigner_to_public,
+    keyring::{AddressKey, NetworkSpecsKey, VerifierKey},
+    network_specs::VerifierValue,
+};
+use sp_core::H256;
+
+/// Error passed to frontend
+#[derive(Debug)]
+pub enum ErrorDisplayed {
+    /// Error with no defined type, only text description
+    Str {
+        /// Error description
+        s: String,
+    },
+
+    /// Typed error
+    Typed {
+        /// Stable error code
+        code: ErrorCode,
+
+        /// Named error parameters
+        params: Vec<ErrorParam>,
+
+        /// Error description in English
+        message: String,
+    },
+}
+
+/// Stable error code
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// Unexpected error, not expected to be handled by user
+    Internal,
+
+    /// Database could not be accessed
+    DatabaseInternal,
+
+    /// Database contents are inconsistent
+    DatabaseCorrupted,
+
+    /// Database is not initialized
+    DatabaseNotInitialized,
+
+    /// Database schema version is newer than supported, params `found`,
+    /// `supported`
+    DatabaseSchemaTooNew,
+
+    /// Database was changed since it was checked, params `expected`,
+    /// `actual` are not available
+    ChecksumMismatch,
+
+    /// User has entered a wrong password; `checksum` param is set if the
+    /// database checksum was changed by the log record
+    WrongPassword,
+
+    /// Seed phrase could not be used
+    InvalidSeedPhrase,
+
+    /// Seed phrase and derivation could not be combined into secret
+    InvalidSecretString,
+
+    /// Seed phrase is empty
+    EmptySeedPhrase,
+
+    /// Seed name is empty
+    EmptySeedName,
+
+    /// No seeds known to Signer
+    NoKnownSeeds,
+
+    /// Seed name of the key does not match the expected one, params
+    /// `address_key`, `expected_seed_name`, `seed_name`
+    SeedNameNotMatching,
+
+    /// Public key could not be derived directly from seed, param `public_key`
+    NoSeedForKeyPair,
+
+    /// Key is not in the database, param `address_key`
+    AddressNotFound,
+
+    /// Key has no derivation in the network, params `address_key`,
+    /// `network_specs_key`
+    AddressNotInNetwork,
+
+    /// Same public key was produced for different seed or derivation, param
+    /// `seed_name`
+    KeyCollision,
+
+    /// Derivation already exists, params `seed_name`, `path`,
+    /// `network_specs_key`
+    DerivationExists,
+
+    /// Derivation is invalid, param `path` is not always available
+    InvalidDerivation,
+
+    /// Password was lost while creating the derivation
+    PasswordLost,
+
+    /// Derivations could not be imported
+    DerivationsImportInvalid,
+
+    /// No networks known to Signer
+    NoNetworksAvailable,
+
+    /// Network is disabled until Signer is wiped, param `genesis_hash`
+    NetworkDisabled,
+
+    /// Network is not known to Signer, params `genesis_hash`, `encryption`
+    UnknownNetwork,
+
+    /// Network specs are not in the database, params `network_specs_key` or
+    /// `name`, `encryption`
+    NetworkSpecsNotFound,
+
+    /// Network specs must be added before the metadata, param `name`
+    NetworkSpecsMissing,
+
+    /// Received network specs differ from known ones in unchangeable values,
+    /// params vary
+    NetworkSpecsChanged,
+
+    /// Received network specs are already known, params `name`, `encryption`
+    NetworkSpecsKnown,
+
+    /// No metadata for the network, param `name`
+    MetadataMissing,
+
+    /// No metadata for the network version, params `name`, `version`
+    MetadataNotFound,
+
+    /// Metadata could not be used
+    MetadataInvalid,
+
+    /// Received metadata is already known, params `name`, `version`
+    MetadataKnown,
+
+    /// Received metadata differs from known metadata with the same version,
+    /// params `name`, `version`
+    MetadataChanged,
+
+    /// Received metadata is for other network than the genesis hash, params
+    /// `name_metadata`, `name_specs`, `genesis_hash`
+    MetadataWrongGenesisHash,
+
+    /// Network version in transaction does not match metadata, params
+    /// `as_decoded`, `in_metadata`
+    NetworkVersionMismatch,
+
+    /// Network name in metadata does not match network specs, params
+    /// `name_metadata`, `name_network_specs`
+    NetworkNameMismatch,
+
+    /// Types information is missing
+    TypesNotFound,
+
+    /// Received types information is already known
+    TypesKnown,
+
+    /// Received contacts are already known
+    ContactsKnown,
+
+    /// Received update is not signed, but must be, params `name` (absent for
+    /// general verifier content), `expected_verifier`
+    VerifierMissing,
+
+    /// Received update is signed by other verifier than expected, params
+    /// `name`, `expected_verifier`, `received_verifier`
+    WrongVerifier,
+
+    /// Network has no verifier to accept signed metadata yet, params `name`,
+    /// `received_verifier`
+    VerifierNotSetUp,
+
+    /// Network has no custom verifier to rotate, param `name`
+    VerifierNotCustom,
+
+    /// Received verifier rotation sets the verifier already in use, param
+    /// `name`
+    VerifierKnown,
+
+    /// Received verifier rotation sets the general verifier as a custom one,
+    /// param `name`
+    VerifierRotationToGeneral,
+
+    /// Received verifier rotation is replayed or out of order, params `name`,
+    /// `expected_index`, `received_index`
+    VerifierRotationOutOfOrder,
+
+    /// Received security policy is already in force
+    SecurityPolicyKnown,
+
+    /// Received security policy is replayed or outdated, params
+    /// `stored_index`, `received_index`
+    SecurityPolicyOutdated,
+
+    /// Action is forbidden by the security policy, param `rule`; `checksum`
+    /// param is set if the database checksum was changed by the log record
+    PolicyViolation,
+
+    /// Update signature is invalid
+    BadSignature,
+
+    /// Payload is too short
+    PayloadTooShort,
+
+    /// Payload is not a Substrate one, param `prefix`
+    PayloadNotSubstrate,
+
+    /// Payload type is not supported, param `payload_code`
+    PayloadNotSupported,
+
+    /// Encryption is not supported, param `encryption_code`
+    EncryptionNotSupported,
+
+    /// Payload could not be decoded
+    PayloadUnreadable,
+
+    /// Compressed payload has other content than expected, params
+    /// `expected`, `found`
+    PayloadContentMismatch,
+
+    /// Transaction could not be parsed
+    TransactionParsingFailed,
+
+    /// Transaction extensions could not be parsed with any metadata, param
+    /// `name`
+    ExtensionsParsingFailed,
+
+    /// History entry could not be found, param `order`
+    HistoryEntryNotFound,
+
+    /// History log hash chain is broken, param `order`
+    HistoryChainBroken,
+
+    /// History search query is invalid, param `part`
+    HistoryQueryInvalid,
+
+    /// No history entries to export, params `first_order`, `last_order`
+    HistoryAuditRangeEmpty,
+
+    /// Contact name is empty
+    ContactNameEmpty,
+
+    /// Contact address is invalid, param `address`
+    ContactAddressInvalid,
+
+    /// Contact is not in the database, param `account_id`
+    ContactNotFound,
+
+    /// QR code could not be generated
+    QrGeneration,
+
+    /// Navigation session is not known, param `session`
+    NavigationSessionNotFound,
+
+    /// Action is not known, param `action`
+    UnknownAction,
+}
+
+/// Named error parameter
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ErrorParam {
+    pub name: String,
+    pub value: String,
+}
+
+impl ErrorDisplayed {
+    fn typed(code: ErrorCode, params: Vec<(&str, String)>, message: impl Display) -> Self {
+        Self::Typed {
+            code,
+            params: params
+                .into_iter()
+                .map(|(name, value)| ErrorParam {
+                    name: name.to_string(),
+                    value,
+                })
+                .collect(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Display for ErrorDisplayed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Str { s } => f.write_str(s),
+            Self::Typed { message, .. } => f.write_str(message),
+        }
+    }
+}
+
+impl From<anyhow::Error> for ErrorDisplayed {
+    fn from(e: anyhow::Error) -> Self {
+        Self::Str {
+            s: format!("error on signer side: {}", e),
+        }
+    }
+}
+
+impl FromStr for ErrorDisplayed {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(ErrorDisplayed::Str { s: s.to_string() })
+    }
+}
+
+impl From<String> for ErrorDisplayed {
+    fn from(s: String) -> Self {
+        ErrorDisplayed::Str { s }
+    }
+}
+
+impl From<db_handling::Error> for ErrorDisplayed {
+    fn from(e: db_handling::Error) -> Self {
+        use db_handling::Error;
+        let (code, params) = match e {
+            Error::DbTransactionError(_) | Error::DbError(_) => {
+                (ErrorCode::DatabaseInternal, Vec::new())
+            }
+            Error::Defaults(_)
+            | Error::Other(_)
+            | Error::TimeFormat(_)
+            | Error::DefinitionsError(_) => (ErrorCode::Internal, Vec::new()),
+            Error::Codec(_)
+            | Error::Stub
+            | Error::Sign
+            | Error::DerivationsNotFound
+            | Error::CustomVerifierIsGeneral(_)
+            | Error::UnexpectedGenesisHash { .. }
+            | Error::TwoRootKeys { .. }
+            | Error::NoValidCurrentVerifier(_)
+            | Error::DifferentNamesSameGenesisHash { .. }
+            | Error::DifferentBase58Specs { .. }
+            | Error::GeneralVerifierNotFound
+            | Error::DangerStatusNotFound
+            | Error::SecretExposedMismatch { .. } => (ErrorCode::DatabaseCorrupted, Vec::new()),
+            Error::Bip39MnemonicType(_) => (ErrorCode::InvalidSeedPhrase, Vec::new()),
+            Error::DeadVerifier(ref verifier_key) => (
+                ErrorCode::NetworkDisabled,
+                vec![("genesis_hash", verifier_key_param(verifier_key))],
+            ),
+            Error::HistoryEntryNotFound(order) => (
+                ErrorCode::HistoryEntryNotFound,
+                vec![("order", order.to_string())],
+            ),
+            Error::HistoryChainBroken { order } => (
+                ErrorCode::HistoryChainBroken,
+                vec![("order", order.to_string())],
+            ),
+            Error::HistoryQueryInvalid { ref part } => (
+                ErrorCode::HistoryQueryInvalid,
+                vec![("part", part.to_owned())],
+            ),
+            Error::HistoryAuditRangeEmpty {
+                first_order,
+                last_order,
+            } => (
+                ErrorCode::HistoryAuditRangeEmpty,
+                vec![
+                    ("first_order", first_order.to_string()),
+                    ("last_order", last_order.to_string()),
+                ],
+            ),
+            Error::ContactNameEmpty => (ErrorCode::ContactNameEmpty, Vec::new()),
+            Error::ContactAddressInvalid { ref address } => (
+                ErrorCode::ContactAddressInvalid,
+                vec![("address", address.to_owned())],
+            ),
+            Error::ContactNotFound { account_id } => (
+                ErrorCode::ContactNotFound,
+                vec![("account_id", hex::encode(account_id))],
+            ),
+            Error::NoNetworksAvailable => (ErrorCode::NoNetworksAvailable, Vec::new()),
+            Error::SeedNameNotMatching {
+                ref address_key,
+                ref expected_seed_name,
+                ref real_seed_name,
+            } => (
+                ErrorCode::SeedNameNotMatching,
+                vec![
+                    ("address_key", address_key_param(address_key)),
+                    ("expected_seed_name", expected_seed_name.to_owned()),
+                    ("seed_name", real_seed_name.to_owned()),
+                ],
+            ),
+            Error::Qr(_) => (ErrorCode::QrGeneration, Vec::new()),
+            Error::NetworkSpecsKeyForAddressNotFound {
+                ref network_specs_key,
+                ref address_key,
+            } => (
+                ErrorCode::AddressNotInNetwork,
+                vec![
+                    ("address_key", address_key_param(address_key)),
+                    (
+                        "network_specs_key",
+                        network_specs_key_param(network_specs_key),
+                    ),
+                ],
+            ),
+            Error::EmptySeed => (ErrorCode::EmptySeedPhrase, Vec::new()),
+            Error::EmptySeedName => (ErrorCode::EmptySeedName, Vec::new()),
+            Error::SecretStringError(_) => (ErrorCode::InvalidSecretString, Vec::new()),
+            Error::KeyCollisionBatch {
+                ref seed_name_new, ..
+            } => (
+                ErrorCode::KeyCollision,
+                vec![("seed_name", seed_name_new.to_owned())],
+            ),
+            Error::KeyCollision { ref seed_name } => (
+                ErrorCode::KeyCollision,
+                vec![("seed_name", seed_name.to_owned())],
+            ),
+            Error::DerivationExists {
+                ref address_details,
+                ref network_specs_key,
+                ..
+            } => (
+                ErrorCode::DerivationExists,
+                vec![
+                    ("seed_name", address_details.seed_name.to_owned()),
+                    ("path", address_details.path.to_owned()),
+                    (
+                        "network_specs_key",
+                        network_specs_key_param(network_specs_key),
+                    ),
+                ],
+            ),
+            Error::InvalidDerivation(ref path) => (
+                ErrorCode::InvalidDerivation,
+                vec![("path", path.to_owned())],
+            ),
+            Error::LostPwd => (ErrorCode::PasswordLost, Vec::new()),
+            Error::NoValidDerivationToExport | Error::DerivationsImportNotReadable => {
+                (ErrorCode::DerivationsImportInvalid, Vec::new())
+            }
+            Error::TypesNotFound => (ErrorCode::TypesNotFound, Vec::new()),
+            Error::NetworkSpecsNotFound(ref network_specs_key) => (
+                ErrorCode::NetworkSpecsNotFound,
+                vec![(
+                    "network_specs_key",
+                    network_specs_key_param(network_specs_key),
+                )],
+            ),
+            Error::AddressNotFound(ref address_key) => (
+                ErrorCode::AddressNotFound,
+                vec![("address_key", address_key_param(address_key))],
+            ),
+            Error::MetaValuesNotFound { ref name, version } => (
+                ErrorCode::MetadataNotFound,
+                vec![("name", name.to_owned()), ("version", version.to_string())],
+            ),
+            Error::ChecksumMismatch => (ErrorCode::ChecksumMismatch, Vec::new()),
+            Error::SchemaVersionTooNew { found, supported } => (
+                ErrorCode::DatabaseSchemaTooNew,
+                vec![
+                    ("found", found.to_string()),
+                    ("supported", supported.to_string()),
+                ],
+            ),
+            Error::NoKnownSeeds => (ErrorCode::NoKnownSeeds, Vec::new()),
+            Error::WrongPassword => (ErrorCode::WrongPassword, Vec::new()),
+            Error::PolicyViolation(rule) => (
+                ErrorCode::PolicyViolation,
+                vec![("rule", rule.id().to_string())],
+            ),
+            Error::NoSeedForKeyPair { ref multisigner } => (
+                ErrorCode::NoSeedForKeyPair,
+                vec![(
+                    "public_key",
+                    hex::encode(multisigner_to_public(multisigner)),
+                )],
+            ),
+        };
+        Self::typed(code, params, e)
+    }
+}
+
+impl From<transaction_parsing::Error> for ErrorDisplayed {
+    fn from(e: transaction_parsing::Error) -> Self {
+        use transaction_parsing::Error;
+        let (code, params) = match e {
+            Error::DbError(db_handling_error) => return db_handling_error.into(),
+            Error::Parser(parser_error) => return parser_error.into(),
+            Error::Definitions(_) => (ErrorCode::Internal, Vec::new()),
+            Error::Sled(_) => (ErrorCode::DatabaseInternal, Vec::new()),
+            Error::Metadata(_) => (ErrorCode::MetadataInvalid, Vec::new()),
+            Error::TooShort => (ErrorCode::PayloadTooShort, Vec::new()),
+            Error::NotSubstrate(ref prefix) => (
+                ErrorCode::PayloadNotSubstrate,
+                vec![("prefix", prefix.to_owned())],
+            ),
+            Error::BadSignature => (ErrorCode::BadSignature, Vec::new()),
+            Error::PayloadNotSupported(ref payload_code) => (
+                ErrorCode::PayloadNotSupported,
+                vec![("payload_code", payload_code.to_owned())],
+            ),
+            Error::HistoricalMetadata { ref name } | Error::NoMetadata { ref name } => {
+                (ErrorCode::MetadataMissing, vec![("name", name.to_owned())])
+            }
+            Error::SpecsCollision { .. } => (ErrorCode::DatabaseCorrupted, Vec::new()),
+            Error::HistoryNetworkSpecs {
+                ref name,
+                ref encryption,
+            } => (
+                ErrorCode::NetworkSpecsNotFound,
+                vec![
+                    ("name", name.to_owned()),
+                    ("encryption", encryption_param(encryption)),
+                ],
+            ),
+            Error::ImportantSpecsChanged(ref network_specs_key) => (
+                ErrorCode::NetworkSpecsChanged,
+                vec![(
+                    "network_specs_key",
+                    network_specs_key_param(network_specs_key),
+                )],
+            ),
+            Error::SameNameVersionDifferentMeta { ref name, version } => (
+                ErrorCode::MetadataChanged,
+                vec![("name", name.to_owned()), ("version", version.to_string())],
+            ),
+            Error::EncryptionNotSupported(ref encryption_code) => (
+                ErrorCode::EncryptionNotSupported,
+                vec![("encryption_code", encryption_code.to_owned())],
+            ),
+            Error::AllExtensionsParsingFailed {
+                ref network_name, ..
+            } => (
+                ErrorCode::ExtensionsParsingFailed,
+                vec![("name", network_name.to_owned())],
+            ),
+            Error::SeparateMethodExtensions => (ErrorCode::TransactionParsingFailed, Vec::new()),
+            Error::UnknownNetwork {
+                ref genesis_hash,
+                ref encryption,
+            }
+            | Error::NetworkForDerivationsImport {
+                ref genesis_hash,
+                ref encryption,
+            } => (
+                ErrorCode::UnknownNetwork,
+                vec![
+                    ("genesis_hash", genesis_hash_param(genesis_hash)),
+                    ("encryption", encryption_param(encryption)),
+                ],
+            ),
+            Error::Codec(_) => (ErrorCode::PayloadUnreadable, Vec::new()),
+            Error::CompressedContentMismatch {
+                ref expected,
+                ref found,
+            } => (
+                ErrorCode::PayloadContentMismatch,
+                vec![
+                    ("expected", format!("{:?}", expected)),
+                    ("found", format!("{:?}", found)),
+                ],
+            ),
+            Error::AddSpecsDifferentBase58 {
+                ref genesis_hash,
+                ref name,
+                base58_database,
+                base58_input,
+            } => (
+                ErrorCode::NetworkSpecsChanged,
+                vec![
+                    ("genesis_hash", genesis_hash_param(genesis_hash)),
+                    ("name", name.to_owned()),
+                    ("base58_database", base58_database.to_string()),
+                    ("base58_input", base58_input.to_string()),
+                ],
+            ),
+            Error::AddSpecsDifferentName {
+                ref genesis_hash,
+                ref name_database,
+                ref name_input,
+            } => (
+                ErrorCode::NetworkSpecsChanged,
+                vec![
+                    ("genesis_hash", genesis_hash_param(genesis_hash)),
+                    ("name_database", name_database.to_owned()),
+                    ("name_input", name_input.to_owned()),
+                ],
+            ),
+            Error::SpecsKnown {
+                ref name,
+                ref encryption,
+            } => (
+                ErrorCode::NetworkSpecsKnown,
+                vec![
+                    ("name", name.to_owned()),
+                    ("encryption", encryption_param(encryption)),
+                ],
+            ),
+            Error::NeedVerifier {
+                ref name,
+                ref verifier_value,
+            } => (
+                ErrorCode::VerifierMissing,
+                vec![
+                    ("name", name.to_owned()),
+                    ("expected_verifier", verifier_value_param(verifier_value)),
+                ],
+            ),
+            Error::NeedGeneralVerifier {
+                ref verifier_value, ..
+            } => (
+                ErrorCode::VerifierMissing,
+                vec![("expected_verifier", verifier_value_param(verifier_value))],
+            ),
+            Error::AddSpecsVerifierChanged {
+                ref name,
+                old_verifier_value: ref expected,
+                new_verifier_value: ref received,
+            }
+            | Error::LoadMetaVerifierChanged {
+                ref name,
+                old_verifier_value: ref expected,
+                new_verifier_value: ref received,
+            }
+            | Error::LoadMetaGeneralVerifierChanged {
+                ref name,
+                old_general_verifier_value: ref expected,
+                new_general_verifier_value: ref received,
+            }
+            | Error::RotateVerifierNotEndorsed {
+                ref name,
+                current_verifier_value: ref expected,
+                received_verifier_value: ref received,
+            }
+            | Error::RotateVerifierOutdated {
+                ref name,
+                current_verifier_value: ref expected,
+                outgoing_verifier_value: ref received,
+            }
+            | Error::AmendSpecsNotEndorsed {
+                ref name,
+                current_verifier_value: ref expected,
+                received_verifier_value: ref received,
+            } => (
+                ErrorCode::WrongVerifier,
+                vec![
+                    ("name", name.to_owned()),
+                    ("expected_verifier", verifier_value_param(expected)),
+                    ("received_verifier", verifier_value_param(received)),
+                ],
+            ),
+            Error::GeneralVerifierChanged {
+                old_general_verifier_value: ref expected,
+                new_general_verifier_value: ref received,
+                ..
+            } => (
+                ErrorCode::WrongVerifier,
+                vec![
+                    ("expected_verifier", verifier_value_param(expected)),
+                    ("received_verifier", verifier_value_param(received)),
+                ],
+            ),
+            Error::LoadMetaSetVerifier {
+                ref name,
+                new_verifier_value: ref received,
+            }
+            | Error::LoadMetaSetGeneralVerifier {
+                ref name,
+                new_general_verifier_value: ref received,
+            } => (
+                ErrorCode::VerifierNotSetUp,
+                vec![
+                    ("name", name.to_owned()),
+                    ("received_verifier", verifier_value_param(received)),
+                ],
+            ),
+            Error::TypesKnown => (ErrorCode::TypesKnown, Vec::new()),
+            Error::ContactsKnown => (ErrorCode::ContactsKnown, Vec::new()),
+            Error::RotateVerifierUnknownNetwork { ref genesis_hash } => (
+                ErrorCode::UnknownNetwork,
+                vec![("genesis_hash", genesis_hash_param(genesis_hash))],
+            ),
+            Error::RotateVerifierNotCustom { ref name } => (
+                ErrorCode::VerifierNotCustom,
+                vec![("name", name.to_owned())],
+            ),
+            Error::RotateVerifierSame { ref name } => {
+                (ErrorCode::VerifierKnown, vec![("name", name.to_owned())])
+            }
+            Error::RotateVerifierToGeneral { ref name } => (
+                ErrorCode::VerifierRotationToGeneral,
+                vec![("name", name.to_owned())],
+            ),
+            Error::RotateVerifierOutOfOrder {
+                ref name,
+                expected_rotation_index,
+                received_rotation_index,
+            } => (
+                ErrorCode::VerifierRotationOutOfOrder,
+                vec![
+                    ("name", name.to_owned()),
+                    ("expected_index", expected_rotation_index.to_string()),
+                    ("received_index", received_rotation_index.to_string()),
+                ],
+            ),
+            Error::SecurityPolicyNoGeneralVerifier => (ErrorCode::VerifierNotSetUp, Vec::new()),
+            Error::SecurityPolicyKnown => (ErrorCode::SecurityPolicyKnown, Vec::new()),
+            Error::SecurityPolicyOutdated {
+                stored_index,
+                received_index,
+            } => (
+                ErrorCode::SecurityPolicyOutdated,
+                vec![
+                    ("stored_index", stored_index.to_string()),
+                    ("received_index", received_index.to_string()),
+                ],
+            ),
+            Error::AmendSpecsUnknownNetwork {
+                ref network_specs_key,
+            } => (
+                ErrorCode::NetworkSpecsNotFound,
+                vec![(
+                    "network_specs_key",
+                    network_specs_key_param(network_specs_key),
+                )],
+            ),
+            Error::AmendSpecsNotAllowed {
+                ref name,
+                ref field,
+            } => (
+                ErrorCode::NetworkSpecsChanged,
+                vec![("name", name.to_owned()), ("field", field.to_owned())],
+            ),
+            Error::AmendSpecsNotVerified { ref name } => {
+                (ErrorCode::VerifierNotSetUp, vec![("name", name.to_owned())])
+            }
+            Error::LoadMetaUnknownNetwork { ref name }
+            | Error::LoadMetaNoSpecs { ref name, .. } => (
+                ErrorCode::NetworkSpecsMissing,
+                vec![("name", name.to_owned())],
+            ),
+            Error::LoadMetaWrongGenesisHash {
+                ref name_metadata,
+                ref name_specs,
+                ref genesis_hash,
+            } => (
+                ErrorCode::MetadataWrongGenesisHash,
+                vec![
+                    ("name_metadata", name_metadata.to_owned()),
+                    ("name_specs", name_specs.to_owned()),
+                    ("genesis_hash", genesis_hash_param(genesis_hash)),
+                ],
+            ),
+            Error::MetadataKnown { ref name, version } => (
+                ErrorCode::MetadataKnown,
+                vec![("name", name.to_owned()), ("version", version.to_string())],
+            ),
+        };
+        Self::typed(code, params, e)
+    }
+}
+
+impl From<transaction_signing::Error> for ErrorDisplayed {
+    fn from(e: transaction_signing::Error) -> Self {
+        use transaction_signing::Error;
+        let (code, params) = match e {
+            Error::DbHandling(db_handling_error) => return db_handling_error.into(),
+            Error::Other(_) => (ErrorCode::Internal, Vec::new()),
+            Error::WrongPassword => (ErrorCode::WrongPassword, Vec::new()),
+            Error::WrongPasswordNewChecksum(checksum) => (
+                ErrorCode::WrongPassword,
+                vec![("checksum", checksum.to_string())],
+            ),
+            Error::PolicyViolationNewChecksum { rule, checksum } => (
+                ErrorCode::PolicyViolation,
+                vec![
+                    ("rule", rule.id().to_string()),
+                    ("checksum", checksum.to_string()),
+                ],
+            ),
+            Error::CryptoError(_) => (ErrorCode::InvalidSecretString, Vec::new()),
+        };
+        Self::typed(code, params, e)
+    }
+}
+
+impl From<parser::Error> for ErrorDisplayed {
+    fn from(e: parser::Error) -> Self {
+        use parser::Error;
+        let (code, params) = match e {
+            Error::Metadata(_) | Error::FundamentallyBadV14Metadata(_) => {
+                (ErrorCode::MetadataInvalid, Vec::new())
+            }
+            Error::DefinitionsError(_) => (ErrorCode::Internal, Vec::new()),
+            Error::Codec(_) => (ErrorCode::PayloadUnreadable, Vec::new()),
+            Error::SeparateMethodExtensions | Error::Decoding(_) => {
+                (ErrorCode::TransactionParsingFailed, Vec::new())
+            }
+            Error::WrongNetworkVersion {
+                ref as_decoded,
+                in_metadata,
+            } => (
+                ErrorCode::NetworkVersionMismatch,
+                vec![
+                    ("as_decoded", as_decoded.to_owned()),
+                    ("in_metadata", in_metadata.to_string()),
+                ],
+            ),
+            Error::NetworkNameMismatch {
+                ref name_metadata,
+                ref name_network_specs,
+            } => (
+                ErrorCode::NetworkNameMismatch,
+                vec![
+                    ("name_metadata", name_metadata.to_owned()),
+                    ("name_network_specs", name_network_specs.to_owned()),
+                ],
+            ),
+            Error::NoTypes | Error::DefaultTypes => (ErrorCode::TypesNotFound, Vec::new()),
+        };
+        Self::typed(code, params, e)
+    }
+}
+
+impl From<navigator::Error> for ErrorDisplayed {
+    fn from(e: navigator::Error) -> Self {
+        use navigator::Error;
+        let (code, params) = match e {
+            Error::DbHandling(db_handling_error) => return db_handling_error.into(),
+            Error::TransactionParsing(transaction_parsing_error) => {
+                return transaction_parsing_error.into()
+            }
+            Error::DbNotInitialized => (ErrorCode::DatabaseNotInitialized, Vec::new()),
+            Error::SessionNotFound(session) => (
+                ErrorCode::NavigationSessionNotFound,
+                vec![("session", session.to_string())],
+            ),
+            Error::UnknownAction(ref action) => (
+                ErrorCode::UnknownAction,
+                vec![("action", action.to_owned())],
+            ),
+            _ => (ErrorCode::Internal, Vec::new()),
+        };
+        Self::typed(code, params, e)
+    }
+}
+
+fn address_key_param(address_key: &AddressKey) -> String {
+    hex::encode(address_key.key())
+}
+
+fn network_specs_key_param(network_specs_key: &NetworkSpecsKey) -> String {
+    hex::encode(network_specs_key.key())
+}
+
+fn verifier_key_param(verifier_key: &VerifierKey) -> String {
+    hex::encode(verifier_key.genesis_hash())
+}
+
+fn genesis_hash_param(genesis_hash: &H256) -> String {
+    hex::encode(genesis_hash)
+}
+
+fn encryption_param(encryption: &Encryption) -> String {
+    encryption.show()
+}
+
+fn verifier_value_param(verifier_value: &VerifierValue) -> String {
+    verifier_value.show_error()
+}
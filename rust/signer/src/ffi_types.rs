@@ -1,17 +1,24 @@
 use crate::UniffiCustomTypeConverter;
+pub use db_handling::{fsck::DbIssueDisplay, migrations::MigrationReport};
 use definitions::helpers::{multisigner_to_encryption, multisigner_to_public};
 pub use definitions::{
     crypto::Encryption,
+    danger::{Exposure, ExposureRecord},
     history::{
-        Event, IdentityHistory, MetaValuesDisplay, MetaValuesExport, NetworkSpecsDisplay,
-        NetworkSpecsExport, NetworkVerifierDisplay, SignDisplay, SignMessageDisplay, TypesDisplay,
-        TypesExport,
+        AuditFormat, ContactDisplay, Event, HistoryAuditExport, IdentityHistory,
+        KeyAnnotationHistory, MetaValuesDisplay, MetaValuesExport, NetworkSpecsAmendmentDisplay,
+        NetworkSpecsAmendmentTokensDisplay, NetworkSpecsDisplay, NetworkSpecsExport,
+        NetworkSpecsTokensDisplay, NetworkVerifierDisplay, NetworkVerifierRotationDisplay,
+        SignDisplay, SignMessageDisplay, TypesDisplay, TypesExport,
     },
+    localisation::{LocalisedMessage, MessageArg, MessageArgValue, MessageTemplate},
     metadata::MetaValues,
     navigation::*,
     network_specs::{
-        NetworkSpecs, NetworkSpecsToSend, ValidCurrentVerifier, Verifier, VerifierValue,
+        NetworkSpecs, NetworkSpecsToSend, TokenSpecs, ValidCurrentVerifier, Verifier, VerifierValue,
     },
+    policy::{PolicyRule, SecurityPolicy},
+    users::KeyAnnotation,
 };
 pub use navigator::Action;
 
@@ -22,50 +22,11 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 #![allow(clippy::let_unit_value)]
 
+mod error;
 mod ffi_types;
 
+pub use crate::error::{ErrorCode, ErrorDisplayed, ErrorParam};
 use crate::ffi_types::*;
-use std::{fmt::Display, str::FromStr};
-
-/// Container for severe error message
-///
-/// TODO: implement properly or remove completely
-#[derive(Debug)]
-pub enum ErrorDisplayed {
-    /// String description of error
-    Str {
-        /// Error description
-        s: String,
-    },
-}
-
-impl From<anyhow::Error> for ErrorDisplayed {
-    fn from(e: anyhow::Error) -> Self {
-        Self::Str {
-            s: format!("error on signer side: {}", e),
-        }
-    }
-}
-
-impl FromStr for ErrorDisplayed {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(ErrorDisplayed::Str { s: s.to_string() })
-    }
-}
-
-impl From<String> for ErrorDisplayed {
-    fn from(s: String) -> Self {
-        ErrorDisplayed::Str { s }
-    }
-}
-
-impl Display for ErrorDisplayed {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("TODO")
-    }
-}
 
 include!(concat!(env!("OUT_DIR"), "/signer.uniffi.rs"));
 
@@ -80,36 +41,49 @@ fn action_get_name(action: &Action) -> Option<FooterButton> {
     }
 }
 
-/// Perform action in frontend.
+/// Start navigation session of the app; should be called once at start of the app and could be
+/// called on app reset, after previous session was closed with [`close_navigation_session`]
+///
+/// Accepts list of seed names to avoid calling [`update_session_seed_names`] every time
+///
+/// Returns opaque session handle, to be used in all other session calls. Independent sessions
+/// could run in parallel, for example for additional window of desktop frontend
+///
+/// Fails if the database was made by a newer Signer, see [`migrate_database`]
+fn init_navigation_session(dbname: &str, seed_names: Vec<String>) -> Result<u32, ErrorDisplayed> {
+    navigator::init_session(dbname, seed_names).map_err(Into::into)
+}
+
+/// Perform action in frontend, in navigation session.
 ///
 /// This call should be debounced.
 ///
-/// Action tries to acquire lock on app state mutex and is ignored on failure.
+/// Action tries to acquire lock on session state mutex and is ignored on failure. If other
+/// thread panicked while holding the lock, navigation is reset to log screen.
 ///
 /// `seed_phrase` field is zeroized, it is expected to be used for secrets only.
 ///
 /// `details` field is not always zeroized.
 ///
 /// App view contents are returned as result, this should be sufficient to render view.
-fn backend_action(
+fn backend_session_action(
+    session: u32,
     action: Action,
     details: &str,
     seed_phrase: &str,
 ) -> Result<Option<ActionResult>, ErrorDisplayed> {
-    navigator::do_action(action, details, seed_phrase).map_err(|s| ErrorDisplayed::Str { s })
+    navigator::do_session_action(session, action, details, seed_phrase).map_err(Into::into)
 }
 
-/// Should be called once at start of the app and could be called on app reset
-///
-/// Accepts list of seed names to avoid calling [`update_seed_names`] every time
-fn init_navigation(dbname: &str, seed_names: Vec<String>) {
-    navigator::init_navigation(dbname, seed_names)
+/// Should be called every time any change could have been done to seeds. Accepts updated list of
+/// seeds for navigation session, completely disregards previously known list
+fn update_session_seed_names(session: u32, seed_names: Vec<String>) -> Result<(), ErrorDisplayed> {
+    navigator::update_session_seed_names(session, seed_names).map_err(Into::into)
 }
 
-/// Should be called every time any change could have been done to seeds. Accepts updated list of
-/// seeds, completely disregards previously known list
-fn update_seed_names(seed_names: Vec<String>) {
-    navigator::update_seed_names(seed_names)
+/// Close navigation session, session handle is invalid afterwards
+fn close_navigation_session(session: u32) -> Result<(), ErrorDisplayed> {
+    navigator::close_session(session).map_err(Into::into)
 }
 
 /// Determines estimated required number of multiframe QR that should be gathered before decoding
@@ -141,7 +115,7 @@ fn generate_secret_key_qr(
     network_specs_key: &str,
     seed_phrase: &str,
     key_password: Option<String>,
-) -> Result<MKeyDetails, anyhow::Error> {
+) -> Result<MKeyDetails, ErrorDisplayed> {
     db_handling::identities::export_secret_key(
         dbname,
         public_key,
@@ -170,13 +144,22 @@ fn substrate_path_check(
 
 /// Must be called once on normal first start of the app upon accepting conditions; relies on old
 /// data being already removed
-fn history_init_history_with_cert(dbname: &str) -> anyhow::Result<(), String> {
-    db_handling::cold_default::signer_init_with_cert(dbname).map_err(|e| format!("{}", e))
+fn history_init_history_with_cert(dbname: &str) -> Result<(), ErrorDisplayed> {
+    db_handling::cold_default::signer_init_with_cert(dbname).map_err(Into::into)
+}
+
+/// Same as [`history_init_history_with_cert`], for devices provisioned with security policy;
+/// relies on old data being already removed
+fn history_init_history_with_policy(
+    policy: SecurityPolicy,
+    dbname: &str,
+) -> Result<(), ErrorDisplayed> {
+    db_handling::cold_default::signer_init_with_policy(dbname, policy).map_err(Into::into)
 }
 
 /// Must be called once upon jailbreak (removal of general verifier) after all old data was removed
-fn history_init_history_no_cert(dbname: &str) -> anyhow::Result<(), String> {
-    db_handling::cold_default::signer_init_no_cert(dbname).map_err(|e| format!("{}", e))
+fn history_init_history_no_cert(dbname: &str) -> Result<(), ErrorDisplayed> {
+    db_handling::cold_default::signer_init_no_cert(dbname).map_err(Into::into)
 }
 
 /// Must be called every time network detector detects network. Sets alert flag in database that could
@@ -184,31 +167,162 @@ fn history_init_history_no_cert(dbname: &str) -> anyhow::Result<(), String> {
 ///
 /// This changes log, so it is expected to fail all operations that check that database remained
 /// intact
-fn history_device_was_online(dbname: &str) -> anyhow::Result<(), String> {
-    db_handling::manage_history::device_was_online(dbname).map_err(|e| format!("{}", e))
+fn history_device_was_online(dbname: &str) -> Result<(), ErrorDisplayed> {
+    db_handling::manage_history::device_was_online(dbname).map_err(Into::into)
+}
+
+/// Must be called every time USB debugging is detected; same as [`history_device_was_online`]
+fn history_usb_debugging_was_enabled(dbname: &str) -> Result<(), ErrorDisplayed> {
+    db_handling::manage_history::device_exposed(dbname, Exposure::UsbDebugging).map_err(Into::into)
+}
+
+/// Must be called every time enabled Wi-Fi radio is detected; same as
+/// [`history_device_was_online`]
+fn history_wifi_was_enabled(dbname: &str) -> Result<(), ErrorDisplayed> {
+    db_handling::manage_history::device_exposed(dbname, Exposure::Wifi).map_err(Into::into)
+}
+
+/// Must be called every time enabled Bluetooth radio is detected; same as
+/// [`history_device_was_online`]
+fn history_bluetooth_was_enabled(dbname: &str) -> Result<(), ErrorDisplayed> {
+    db_handling::manage_history::device_exposed(dbname, Exposure::Bluetooth).map_err(Into::into)
+}
+
+/// Must be called every time screen recording is detected; same as
+/// [`history_device_was_online`]
+fn history_screen_was_recorded(dbname: &str) -> Result<(), ErrorDisplayed> {
+    db_handling::manage_history::device_exposed(dbname, Exposure::ScreenRecording)
+        .map_err(Into::into)
+}
+
+/// Must be called every time unlocked bootloader is detected; same as
+/// [`history_device_was_online`]
+fn history_bootloader_was_unlocked(dbname: &str) -> Result<(), ErrorDisplayed> {
+    db_handling::manage_history::device_exposed(dbname, Exposure::BootloaderUnlocked)
+        .map_err(Into::into)
+}
+
+/// Gets security policy currently in force
+fn get_security_policy(dbname: &str) -> Result<SecurityPolicy, ErrorDisplayed> {
+    db_handling::helpers::get_security_policy(dbname).map_err(Into::into)
+}
+
+/// Gets cold database schema version
+fn get_schema_version(dbname: &str) -> Result<u32, ErrorDisplayed> {
+    db_handling::migrations::get_schema_version(dbname).map_err(Into::into)
+}
+
+/// Brings cold database to the schema version of this Signer. [`init_navigation_session`] applies
+/// pending migrations itself, this is for reports; with `dry_run` only reports the pending migrations
+fn migrate_database(dbname: &str, dry_run: bool) -> Result<Vec<MigrationReport>, ErrorDisplayed> {
+    db_handling::migrations::migrate_db(dbname, dry_run).map_err(Into::into)
+}
+
+/// Checks cold database consistency; returns found issues, empty if there are none
+fn check_database(dbname: &str) -> Result<Vec<DbIssueDisplay>, ErrorDisplayed> {
+    db_handling::fsck::check_db(dbname)
+        .map(|issues| issues.iter().map(|issue| issue.display()).collect())
+        .map_err(Into::into)
+}
+
+/// Applies safe repairs to the cold database; returns repaired issues
+///
+/// Makes record in log if anything was repaired
+fn repair_database(dbname: &str) -> Result<Vec<DbIssueDisplay>, ErrorDisplayed> {
+    db_handling::fsck::repair_db(dbname)
+        .map(|issues| issues.iter().map(|issue| issue.display()).collect())
+        .map_err(Into::into)
 }
 
 /// Checks if network alert flag was set
-fn history_get_warnings(dbname: &str) -> anyhow::Result<bool, String> {
-    db_handling::helpers::get_danger_status(dbname).map_err(|e| format!("{}", e))
+fn history_get_warnings(dbname: &str) -> Result<bool, ErrorDisplayed> {
+    db_handling::helpers::get_danger_status(dbname).map_err(Into::into)
+}
+
+/// Gets all exposures recorded since the last alert flag reset
+fn history_get_exposures(dbname: &str) -> Result<Vec<ExposureRecord>, ErrorDisplayed> {
+    db_handling::helpers::get_danger_exposures(dbname).map_err(Into::into)
 }
 
 /// Resets network alert flag; makes record of reset in log
-fn history_acknowledge_warnings(dbname: &str) -> anyhow::Result<(), String> {
-    db_handling::manage_history::reset_danger_status_to_safe(dbname).map_err(|e| format!("{}", e))
+fn history_acknowledge_warnings(dbname: &str) -> Result<(), ErrorDisplayed> {
+    db_handling::manage_history::reset_danger_status_to_safe(dbname).map_err(Into::into)
 }
 
 /// Allows frontend to send events into log; TODO: maybe this is not needed
-fn history_entry_system(event: Event, dbname: &str) -> anyhow::Result<(), String> {
-    db_handling::manage_history::history_entry_system(dbname, event).map_err(|e| format!("{}", e))
+fn history_entry_system(event: Event, dbname: &str) -> Result<(), ErrorDisplayed> {
+    db_handling::manage_history::history_entry_system(dbname, event).map_err(Into::into)
 }
 
 /// Must be called every time seed backup shows seed to user
 ///
 /// Makes record in log
-fn history_seed_name_was_shown(seed_name: &str, dbname: &str) -> anyhow::Result<(), String> {
+fn history_seed_name_was_shown(seed_name: &str, dbname: &str) -> Result<(), ErrorDisplayed> {
     db_handling::manage_history::seed_name_was_shown(dbname, seed_name.to_string())
-        .map_err(|e| format!("{}", e))
+        .map_err(Into::into)
+}
+
+/// Exports history log entries from `first_order` to `last_order` as audit report signed with
+/// given key, as multiframe QR code
+///
+/// `public_key` is hex-encoded public key of the signing key. Can be taken from [`MKeyDetails`]
+/// `network_specs_key` is hex-encoded [`NetworkSpecsKey`]. Can be taken from [`MSCNetworkInfo`]
+///
+/// Returns QR code frames as png images, to be shown one after another
+#[allow(clippy::too_many_arguments)]
+fn history_export_audit(
+    dbname: &str,
+    public_key: &str,
+    expected_seed_name: &str,
+    network_specs_key: &str,
+    seed_phrase: &str,
+    key_password: Option<String>,
+    first_order: u32,
+    last_order: u32,
+    format: AuditFormat,
+) -> Result<Vec<Vec<u8>>, ErrorDisplayed> {
+    transaction_signing::export_history_audit(
+        dbname,
+        public_key,
+        expected_seed_name,
+        network_specs_key,
+        seed_phrase,
+        key_password.as_deref().unwrap_or_default(),
+        first_order,
+        last_order,
+        format,
+    )
+    .map_err(Into::into)
+}
+
+/// Returns all named contacts, sorted by name
+fn contacts_get_all(dbname: &str) -> Result<Vec<MSCContact>, ErrorDisplayed> {
+    db_handling::interface_signer::show_contacts(dbname).map_err(Into::into)
+}
+
+/// Adds named contact entered manually, or renames existing one
+///
+/// `address` is SS58 address of the contact, in any network format
+///
+/// Makes record in log
+fn contacts_add(name: &str, address: &str, dbname: &str) -> Result<(), ErrorDisplayed> {
+    db_handling::helpers::add_contact(dbname, name, address).map_err(Into::into)
+}
+
+/// Removes named contact
+///
+/// `address` is SS58 address of the contact, in any network format
+///
+/// Makes record in log
+fn contacts_remove(address: &str, dbname: &str) -> Result<(), ErrorDisplayed> {
+    db_handling::helpers::remove_contact(dbname, address).map_err(Into::into)
+}
+
+/// Returns English templates of all messages, to be used as a reference for translations
+///
+/// Message arguments are referred to by name in curly brackets
+fn english_message_catalog() -> Vec<MessageTemplate> {
+    definitions::localisation::english_catalog()
 }
 
 /// Must be called once to initialize logging from Rust in development mode.
@@ -241,5 +355,53 @@ ffi_support::define_string_destructor!(signer_destroy_string);
 
 #[cfg(test)]
 mod tests {
-    //use super::*;
+    use super::*;
+
+    fn param(error: &ErrorDisplayed, name: &str) -> Option<String> {
+        match error {
+            ErrorDisplayed::Typed { params, .. } => params
+                .iter()
+                .find(|param| param.name == name)
+                .map(|param| param.value.to_owned()),
+            ErrorDisplayed::Str { .. } => None,
+        }
+    }
+
+    #[test]
+    fn typed_errors() {
+        let error: ErrorDisplayed = transaction_signing::Error::WrongPasswordNewChecksum(42).into();
+        assert!(matches!(
+            error,
+            ErrorDisplayed::Typed {
+                code: ErrorCode::WrongPassword,
+                ..
+            }
+        ));
+        assert_eq!(param(&error, "checksum").as_deref(), Some("42"));
+        assert_eq!(error.to_string(), "Wrong password.");
+
+        let error: ErrorDisplayed =
+            transaction_parsing::Error::DbError(db_handling::Error::HistoryEntryNotFound(7)).into();
+        assert!(matches!(
+            error,
+            ErrorDisplayed::Typed {
+                code: ErrorCode::HistoryEntryNotFound,
+                ..
+            }
+        ));
+        assert_eq!(param(&error, "order").as_deref(), Some("7"));
+
+        let error: ErrorDisplayed = navigator::Error::SessionNotFound(3).into();
+        assert!(matches!(
+            error,
+            ErrorDisplayed::Typed {
+                code: ErrorCode::NavigationSessionNotFound,
+                ..
+            }
+        ));
+
+        let error: ErrorDisplayed = String::from("untyped").into();
+        assert_eq!(param(&error, "order"), None);
+        assert_eq!(error.to_string(), "untyped");
+    }
 }
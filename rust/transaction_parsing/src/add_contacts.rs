@@ -0,0 +1,132 @@
+use db_handling::{
+    db_transactions::TrDbColdStub,
+    helpers::{enforce_policy_in, get_contacts_in, get_general_verifier_in},
+    storage::Storage,
+};
+use definitions::{
+    error::TransferContent, error_signer::GeneralVerifierForContent, history::Event,
+    navigation::TransactionCardSet, network_specs::Verifier, policy::PolicyRule,
+    qr_transfers::ContentAddContacts, users::ContactEntry,
+};
+
+use crate::cards::{Card, Warning};
+use crate::check_signature::pass_crypto;
+use crate::error::{Error, Result};
+use crate::holds::GeneralHold;
+use crate::{StubNav, TransactionAction};
+
+pub fn add_contacts<S>(data_hex: &str, storage: &S) -> Result<TransactionAction>
+where
+    S: Storage,
+{
+    let checked_info = pass_crypto(data_hex, TransferContent::AddContacts)?;
+    if checked_info.verifier.v.is_none() {
+        enforce_policy_in(PolicyRule::UnsignedUpdate, storage)?;
+    }
+    let received_contacts = ContentAddContacts::from_slice(&checked_info.message).contacts()?;
+    let known_contacts = get_contacts_in(storage)?;
+
+    // only new contacts and renamed contacts are added
+    let new_contacts: Vec<ContactEntry> = received_contacts
+        .into_iter()
+        .filter(|contact| {
+            !known_contacts.iter().any(|(account_id, contact_details)| {
+                (account_id == &contact.account_id) && (contact_details.name == contact.name)
+            })
+        })
+        .collect();
+    if new_contacts.is_empty() {
+        return Err(Error::ContactsKnown);
+    }
+
+    let general_verifier = get_general_verifier_in(storage)?;
+    let mut stub = TrDbColdStub::new();
+    let mut index = 0;
+    match checked_info.verifier {
+        Verifier { v: None } => match general_verifier {
+            Verifier { v: None } => {
+                stub = stub.new_history_entry(Event::WarningMessage {
+                    message: Warning::ContactsNotVerified.message(),
+                });
+                stub = stub.add_contacts(&new_contacts, &checked_info.verifier);
+                let checksum = stub.store_and_get_checksum_in(storage)?;
+                let warning_card = Card::Warning(Warning::ContactsNotVerified).card(&mut index, 0);
+                let contacts_cards = new_contacts
+                    .iter()
+                    .map(|contact| Card::Contact(contact).card(&mut index, 0))
+                    .collect();
+                Ok(TransactionAction::Stub {
+                    s: TransactionCardSet {
+                        warning: Some(vec![warning_card]),
+                        contacts: Some(contacts_cards),
+                        ..Default::default()
+                    },
+                    u: checksum,
+                    stub: StubNav::AddContacts,
+                })
+            }
+            Verifier {
+                v: Some(old_general_verifier_value),
+            } => Err(Error::NeedGeneralVerifier {
+                content: GeneralVerifierForContent::Contacts,
+                verifier_value: old_general_verifier_value,
+            }),
+        },
+        Verifier {
+            v: Some(ref new_general_verifier_value),
+        } => {
+            let verifier_card = Card::Verifier(new_general_verifier_value).card(&mut index, 0);
+            if general_verifier == checked_info.verifier {
+                stub = stub.add_contacts(&new_contacts, &checked_info.verifier);
+                let checksum = stub.store_and_get_checksum_in(storage)?;
+                let contacts_cards = new_contacts
+                    .iter()
+                    .map(|contact| Card::Contact(contact).card(&mut index, 0))
+                    .collect();
+                Ok(TransactionAction::Stub {
+                    s: TransactionCardSet {
+                        verifier: Some(vec![verifier_card]),
+                        contacts: Some(contacts_cards),
+                        ..Default::default()
+                    },
+                    u: checksum,
+                    stub: StubNav::AddContacts,
+                })
+            } else {
+                match general_verifier {
+                    Verifier { v: None } => {
+                        let new_general_verifier = checked_info.verifier;
+                        let general_hold = GeneralHold::get(storage)?;
+                        stub = general_hold.upd_stub(stub, &new_general_verifier, storage)?;
+                        stub = stub.add_contacts(&new_contacts, &new_general_verifier);
+                        let warning_card =
+                            Card::Warning(Warning::GeneralVerifierAppeared(&general_hold))
+                                .card(&mut index, 0);
+                        let contacts_cards = new_contacts
+                            .iter()
+                            .map(|contact| Card::Contact(contact).card(&mut index, 0))
+                            .collect();
+                        let checksum = stub.store_and_get_checksum_in(storage)?;
+                        Ok(TransactionAction::Stub {
+                            s: TransactionCardSet {
+                                verifier: Some(vec![verifier_card]),
+                                warning: Some(vec![warning_card]),
+                                contacts: Some(contacts_cards),
+                                ..Default::default()
+                            },
+                            u: checksum,
+                            stub: StubNav::AddContacts,
+                        })
+                    }
+                    Verifier {
+                        v: Some(old_general_verifier_value),
+                    } => Err(Error::GeneralVerifierChanged {
+                        content: GeneralVerifierForContent::Contacts,
+                        old_general_verifier_value,
+                        new_general_verifier_value: new_general_verifier_value.to_owned(),
+                    }),
+                }
+            }
+        }
+    }
+}
@@ -1,8 +1,10 @@
 use db_handling::{
     db_transactions::TrDbColdStub,
     helpers::{
-        genesis_hash_in_specs, get_general_verifier, open_db, try_get_valid_current_verifier,
+        enforce_policy_in, genesis_hash_in_specs, get_general_verifier_in,
+        try_get_valid_current_verifier_in,
     },
+    storage::Storage,
 };
 use definitions::{
     error::TransferContent,
@@ -11,9 +13,9 @@ use definitions::{
     keyring::{NetworkSpecsKey, VerifierKey},
     navigation::TransactionCardSet,
     network_specs::{ValidCurrentVerifier, Verifier},
+    policy::PolicyRule,
     qr_transfers::ContentAddSpecs,
 };
-use std::path::Path;
 
 use crate::cards::{Card, Warning};
 use crate::check_signature::pass_crypto;
@@ -23,18 +25,21 @@ use crate::{StubNav, TransactionAction};
 
 use crate::holds::{GeneralHold, Hold, HoldRelease};
 
-pub fn add_specs<P>(data_hex: &str, db_path: P) -> Result<TransactionAction>
+pub fn add_specs<S>(data_hex: &str, storage: &S) -> Result<TransactionAction>
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
     let checked_info = pass_crypto(data_hex, TransferContent::AddSpecs)?;
+    if checked_info.verifier.v.is_none() {
+        enforce_policy_in(PolicyRule::UnsignedUpdate, storage)?;
+    }
     let specs = ContentAddSpecs::from_slice(&checked_info.message).specs()?;
     let network_specs_key = NetworkSpecsKey::from_parts(&specs.genesis_hash, &specs.encryption);
     let verifier_key = VerifierKey::from_parts(specs.genesis_hash);
-    let possible_valid_current_verifier = try_get_valid_current_verifier(&verifier_key, &db_path)?;
-    let general_verifier = get_general_verifier(&db_path)?;
-    if let Some(specs_invariants) = genesis_hash_in_specs(specs.genesis_hash, &open_db(&db_path)?)?
-    {
+    let possible_valid_current_verifier =
+        try_get_valid_current_verifier_in(&verifier_key, storage)?;
+    let general_verifier = get_general_verifier_in(storage)?;
+    if let Some(specs_invariants) = genesis_hash_in_specs(specs.genesis_hash, storage)? {
         if specs.name != specs_invariants.name {
             return Err(Error::AddSpecsDifferentName {
                 genesis_hash: specs_invariants.genesis_hash,
@@ -57,16 +62,16 @@ where
     match possible_valid_current_verifier {
         None => match checked_info.verifier {
             Verifier { v: None } => {
-                stub = stub.new_history_entry(Event::Warning {
-                    warning: Warning::NotVerified.show(),
+                stub = stub.new_history_entry(Event::WarningMessage {
+                    message: Warning::NotVerified.message(),
                 });
-                stub = stub.add_network_specs(
+                stub = stub.add_network_specs_in(
                     &specs,
                     &ValidCurrentVerifier::Custom {
                         v: Verifier { v: None },
                     },
                     &general_verifier,
-                    &db_path,
+                    storage,
                 )?;
                 stub = stub.new_network_verifier(
                     &verifier_key,
@@ -75,7 +80,7 @@ where
                     },
                     &general_verifier,
                 );
-                let checksum = stub.store_and_get_checksum(&db_path)?;
+                let checksum = stub.store_and_get_checksum_in(storage)?;
                 let warning_card = Card::Warning(Warning::NotVerified).card(&mut index, 0);
                 let specs_card = Card::NewSpecs(&specs).card(&mut index, 0);
                 Ok(TransactionAction::Stub {
@@ -97,13 +102,13 @@ where
                 match general_verifier {
                     Verifier { v: None } => {
                         let new_general_verifier = checked_info.verifier;
-                        let general_hold = GeneralHold::get(&db_path)?;
-                        stub = general_hold.upd_stub(stub, &new_general_verifier, &db_path)?;
-                        stub = stub.add_network_specs(
+                        let general_hold = GeneralHold::get(storage)?;
+                        stub = general_hold.upd_stub(stub, &new_general_verifier, storage)?;
+                        stub = stub.add_network_specs_in(
                             &specs,
                             &ValidCurrentVerifier::General,
                             &new_general_verifier,
-                            &db_path,
+                            storage,
                         )?;
                         stub = stub.new_network_verifier(
                             &verifier_key,
@@ -114,7 +119,7 @@ where
                             Card::Warning(Warning::GeneralVerifierAppeared(&general_hold))
                                 .card(&mut index, 0);
                         let specs_card = Card::NewSpecs(&specs).card(&mut index, 0);
-                        let checksum = stub.store_and_get_checksum(db_path)?;
+                        let checksum = stub.store_and_get_checksum_in(storage)?;
                         Ok(TransactionAction::Stub {
                             s: TransactionCardSet {
                                 verifier: Some(vec![verifier_card]),
@@ -130,11 +135,11 @@ where
                     }
                     _ => {
                         if checked_info.verifier == general_verifier {
-                            stub = stub.add_network_specs(
+                            stub = stub.add_network_specs_in(
                                 &specs,
                                 &ValidCurrentVerifier::General,
                                 &general_verifier,
-                                &db_path,
+                                storage,
                             )?;
                             stub = stub.new_network_verifier(
                                 &verifier_key,
@@ -142,7 +147,7 @@ where
                                 &general_verifier,
                             );
                             let specs_card = Card::NewSpecs(&specs).card(&mut index, 0);
-                            let checksum = stub.store_and_get_checksum(&db_path)?;
+                            let checksum = stub.store_and_get_checksum_in(storage)?;
                             Ok(TransactionAction::Stub {
                                 s: TransactionCardSet {
                                     verifier: Some(vec![verifier_card]),
@@ -155,13 +160,13 @@ where
                                 },
                             })
                         } else {
-                            stub = stub.add_network_specs(
+                            stub = stub.add_network_specs_in(
                                 &specs,
                                 &ValidCurrentVerifier::Custom {
                                     v: checked_info.verifier.to_owned(),
                                 },
                                 &general_verifier,
-                                &db_path,
+                                storage,
                             )?;
                             stub = stub.new_network_verifier(
                                 &verifier_key,
@@ -171,7 +176,7 @@ where
                                 &general_verifier,
                             );
                             let specs_card = Card::NewSpecs(&specs).card(&mut index, 0);
-                            let checksum = stub.store_and_get_checksum(&db_path)?;
+                            let checksum = stub.store_and_get_checksum_in(storage)?;
                             Ok(TransactionAction::Stub {
                                 s: TransactionCardSet {
                                     verifier: Some(vec![verifier_card]),
@@ -191,20 +196,20 @@ where
         Some(ValidCurrentVerifier::Custom { v: custom_verifier }) => match custom_verifier {
             Verifier { v: None } => match checked_info.verifier {
                 Verifier { v: None } => {
-                    stub = stub.new_history_entry(Event::Warning {
-                        warning: Warning::NotVerified.show(),
+                    stub = stub.new_history_entry(Event::WarningMessage {
+                        message: Warning::NotVerified.message(),
                     });
                     let warning_card = Card::Warning(Warning::NotVerified).card(&mut index, 0);
-                    if specs_are_new(&specs, &db_path)? {
-                        stub = stub.add_network_specs(
+                    if specs_are_new(&specs, storage)? {
+                        stub = stub.add_network_specs_in(
                             &specs,
                             &ValidCurrentVerifier::Custom {
                                 v: Verifier { v: None },
                             },
                             &general_verifier,
-                            &db_path,
+                            storage,
                         )?;
-                        let checksum = stub.store_and_get_checksum(&db_path)?;
+                        let checksum = stub.store_and_get_checksum_in(storage)?;
                         let specs_card = Card::NewSpecs(&specs).card(&mut index, 0);
                         Ok(TransactionAction::Stub {
                             s: TransactionCardSet {
@@ -228,7 +233,7 @@ where
                     v: Some(ref new_verifier_value),
                 } => {
                     let verifier_card = Card::Verifier(new_verifier_value).card(&mut index, 0);
-                    let hold = Hold::get(&verifier_key, &db_path)?;
+                    let hold = Hold::get(&verifier_key, storage)?;
                     if checked_info.verifier == general_verifier {
                         stub = hold.upd_stub(
                             stub,
@@ -236,7 +241,7 @@ where
                             &custom_verifier,
                             &ValidCurrentVerifier::General,
                             HoldRelease::General,
-                            &db_path,
+                            storage,
                         )?;
                         let warning_card_1 = Card::Warning(Warning::VerifierChangingToGeneral {
                             verifier_key: &verifier_key,
@@ -244,23 +249,23 @@ where
                         })
                         .card(&mut index, 0);
                         let mut possible_warning = None;
-                        if !specs_are_new(&specs, &db_path)? {
+                        if !specs_are_new(&specs, storage)? {
                             possible_warning = Some(
                                 Card::Warning(Warning::NetworkSpecsAlreadyThere(&specs.title))
                                     .card(&mut index, 0),
                             );
-                            stub = stub.new_history_entry(Event::Warning {
-                                warning: Warning::NetworkSpecsAlreadyThere(&specs.title).show(),
+                            stub = stub.new_history_entry(Event::WarningMessage {
+                                message: Warning::NetworkSpecsAlreadyThere(&specs.title).message(),
                             });
                         };
-                        stub = stub.add_network_specs(
+                        stub = stub.add_network_specs_in(
                             &specs,
                             &ValidCurrentVerifier::General,
                             &general_verifier,
-                            &db_path,
+                            storage,
                         )?;
                         let specs_card = Card::NewSpecs(&specs).card(&mut index, 0);
-                        let checksum = stub.store_and_get_checksum(&db_path)?;
+                        let checksum = stub.store_and_get_checksum_in(storage)?;
                         match possible_warning {
                             None => Ok(TransactionAction::Stub {
                                 s: TransactionCardSet {
@@ -295,36 +300,36 @@ where
                             &custom_verifier,
                             &ValidCurrentVerifier::General,
                             HoldRelease::GeneralSuper,
-                            &db_path,
+                            storage,
                         )?;
                         let warning_card_1 = Card::Warning(Warning::VerifierGeneralSuper {
                             verifier_key: &verifier_key,
                             hold: &hold,
                         })
                         .card(&mut index, 0);
-                        let general_hold = GeneralHold::get(&db_path)?;
-                        stub = general_hold.upd_stub(stub, &new_general_verifier, &db_path)?;
+                        let general_hold = GeneralHold::get(storage)?;
+                        stub = general_hold.upd_stub(stub, &new_general_verifier, storage)?;
                         let warning_card_2 =
                             Card::Warning(Warning::GeneralVerifierAppeared(&general_hold))
                                 .card(&mut index, 0);
                         let mut possible_warning = None;
-                        if !specs_are_new(&specs, &db_path)? {
+                        if !specs_are_new(&specs, storage)? {
                             possible_warning = Some(
                                 Card::Warning(Warning::NetworkSpecsAlreadyThere(&specs.title))
                                     .card(&mut index, 0),
                             );
-                            stub = stub.new_history_entry(Event::Warning {
-                                warning: Warning::NetworkSpecsAlreadyThere(&specs.title).show(),
+                            stub = stub.new_history_entry(Event::WarningMessage {
+                                message: Warning::NetworkSpecsAlreadyThere(&specs.title).message(),
                             });
                         };
-                        stub = stub.add_network_specs(
+                        stub = stub.add_network_specs_in(
                             &specs,
                             &ValidCurrentVerifier::General,
                             &general_verifier,
-                            &db_path,
+                            storage,
                         )?;
                         let specs_card = Card::NewSpecs(&specs).card(&mut index, 0);
-                        let checksum = stub.store_and_get_checksum(&db_path)?;
+                        let checksum = stub.store_and_get_checksum_in(storage)?;
                         match possible_warning {
                             None => Ok(TransactionAction::Stub {
                                 s: TransactionCardSet {
@@ -364,7 +369,7 @@ where
                                 v: checked_info.verifier.to_owned(),
                             },
                             HoldRelease::Custom,
-                            &db_path,
+                            storage,
                         )?;
                         let warning_card_1 = Card::Warning(Warning::VerifierChangingToCustom {
                             verifier_key: &verifier_key,
@@ -372,25 +377,25 @@ where
                         })
                         .card(&mut index, 0);
                         let mut possible_warning = None;
-                        if !specs_are_new(&specs, &db_path)? {
+                        if !specs_are_new(&specs, storage)? {
                             possible_warning = Some(
                                 Card::Warning(Warning::NetworkSpecsAlreadyThere(&specs.title))
                                     .card(&mut index, 0),
                             );
-                            stub = stub.new_history_entry(Event::Warning {
-                                warning: Warning::NetworkSpecsAlreadyThere(&specs.title).show(),
+                            stub = stub.new_history_entry(Event::WarningMessage {
+                                message: Warning::NetworkSpecsAlreadyThere(&specs.title).message(),
                             });
                         };
-                        stub = stub.add_network_specs(
+                        stub = stub.add_network_specs_in(
                             &specs,
                             &ValidCurrentVerifier::Custom {
                                 v: checked_info.verifier.to_owned(),
                             },
                             &general_verifier,
-                            &db_path,
+                            storage,
                         )?;
                         let specs_card = Card::NewSpecs(&specs).card(&mut index, 0);
-                        let checksum = stub.store_and_get_checksum(&db_path)?;
+                        let checksum = stub.store_and_get_checksum_in(storage)?;
                         match possible_warning {
                             None => Ok(TransactionAction::Stub {
                                 s: TransactionCardSet {
@@ -432,14 +437,14 @@ where
                 } => {
                     let verifier_card = Card::Verifier(new_verifier_value).card(&mut index, 0);
                     if checked_info.verifier == general_verifier {
-                        let hold = Hold::get(&verifier_key, &db_path)?;
+                        let hold = Hold::get(&verifier_key, storage)?;
                         stub = hold.upd_stub(
                             stub,
                             &verifier_key,
                             &custom_verifier,
                             &ValidCurrentVerifier::General,
                             HoldRelease::General,
-                            &db_path,
+                            storage,
                         )?;
                         let warning_card_1 = Card::Warning(Warning::VerifierChangingToGeneral {
                             verifier_key: &verifier_key,
@@ -447,23 +452,23 @@ where
                         })
                         .card(&mut index, 0);
                         let mut possible_warning = None;
-                        if !specs_are_new(&specs, &db_path)? {
+                        if !specs_are_new(&specs, storage)? {
                             possible_warning = Some(
                                 Card::Warning(Warning::NetworkSpecsAlreadyThere(&specs.title))
                                     .card(&mut index, 0),
                             );
-                            stub = stub.new_history_entry(Event::Warning {
-                                warning: Warning::NetworkSpecsAlreadyThere(&specs.title).show(),
+                            stub = stub.new_history_entry(Event::WarningMessage {
+                                message: Warning::NetworkSpecsAlreadyThere(&specs.title).message(),
                             });
                         };
-                        stub = stub.add_network_specs(
+                        stub = stub.add_network_specs_in(
                             &specs,
                             &ValidCurrentVerifier::General,
                             &general_verifier,
-                            &db_path,
+                            storage,
                         )?;
                         let specs_card = Card::NewSpecs(&specs).card(&mut index, 0);
-                        let checksum = stub.store_and_get_checksum(&db_path)?;
+                        let checksum = stub.store_and_get_checksum_in(storage)?;
                         match possible_warning {
                             None => Ok(TransactionAction::Stub {
                                 s: TransactionCardSet {
@@ -491,15 +496,15 @@ where
                             }),
                         }
                     } else if new_verifier_value == old_verifier_value {
-                        if specs_are_new(&specs, &db_path)? {
-                            stub = stub.add_network_specs(
+                        if specs_are_new(&specs, storage)? {
+                            stub = stub.add_network_specs_in(
                                 &specs,
                                 &ValidCurrentVerifier::Custom { v: custom_verifier },
                                 &general_verifier,
-                                &db_path,
+                                storage,
                             )?;
                             let specs_card = Card::NewSpecs(&specs).card(&mut index, 0);
-                            let checksum = stub.store_and_get_checksum(&db_path)?;
+                            let checksum = stub.store_and_get_checksum_in(storage)?;
                             Ok(TransactionAction::Stub {
                                 s: TransactionCardSet {
                                     verifier: Some(vec![verifier_card]),
@@ -531,18 +536,18 @@ where
             Verifier { v: None } => match checked_info.verifier {
                 Verifier { v: None } => {
                     let warning_card = Card::Warning(Warning::NotVerified).card(&mut index, 0);
-                    stub = stub.new_history_entry(Event::Warning {
-                        warning: Warning::NotVerified.show(),
+                    stub = stub.new_history_entry(Event::WarningMessage {
+                        message: Warning::NotVerified.message(),
                     });
-                    if specs_are_new(&specs, &db_path)? {
-                        stub = stub.add_network_specs(
+                    if specs_are_new(&specs, storage)? {
+                        stub = stub.add_network_specs_in(
                             &specs,
                             &ValidCurrentVerifier::General,
                             &general_verifier,
-                            &db_path,
+                            storage,
                         )?;
                         let specs_card = Card::NewSpecs(&specs).card(&mut index, 0);
-                        let checksum = stub.store_and_get_checksum(&db_path)?;
+                        let checksum = stub.store_and_get_checksum_in(storage)?;
                         Ok(TransactionAction::Stub {
                             s: TransactionCardSet {
                                 warning: Some(vec![warning_card]),
@@ -566,29 +571,29 @@ where
                 } => {
                     let verifier_card = Card::Verifier(new_verifier_value).card(&mut index, 0);
                     let new_general_verifier = checked_info.verifier;
-                    let general_hold = GeneralHold::get(&db_path)?;
-                    stub = general_hold.upd_stub(stub, &new_general_verifier, &db_path)?;
+                    let general_hold = GeneralHold::get(storage)?;
+                    stub = general_hold.upd_stub(stub, &new_general_verifier, storage)?;
                     let warning_card_1 =
                         Card::Warning(Warning::GeneralVerifierAppeared(&general_hold))
                             .card(&mut index, 0);
                     let mut possible_warning = None;
-                    if !specs_are_new(&specs, &db_path)? {
+                    if !specs_are_new(&specs, storage)? {
                         possible_warning = Some(
                             Card::Warning(Warning::NetworkSpecsAlreadyThere(&specs.title))
                                 .card(&mut index, 0),
                         );
-                        stub = stub.new_history_entry(Event::Warning {
-                            warning: Warning::NetworkSpecsAlreadyThere(&specs.title).show(),
+                        stub = stub.new_history_entry(Event::WarningMessage {
+                            message: Warning::NetworkSpecsAlreadyThere(&specs.title).message(),
                         });
                     };
-                    stub = stub.add_network_specs(
+                    stub = stub.add_network_specs_in(
                         &specs,
                         &ValidCurrentVerifier::General,
                         &new_general_verifier,
-                        &db_path,
+                        storage,
                     )?;
                     let specs_card = Card::NewSpecs(&specs).card(&mut index, 0);
-                    let checksum = stub.store_and_get_checksum(&db_path)?;
+                    let checksum = stub.store_and_get_checksum_in(storage)?;
                     match possible_warning {
                         None => Ok(TransactionAction::Stub {
                             s: TransactionCardSet {
@@ -621,17 +626,17 @@ where
                 v: Some(ref old_general_verifier_value),
             } => {
                 if checked_info.verifier == general_verifier {
-                    if specs_are_new(&specs, &db_path)? {
+                    if specs_are_new(&specs, storage)? {
                         let verifier_card =
                             Card::Verifier(old_general_verifier_value).card(&mut index, 0);
-                        stub = stub.add_network_specs(
+                        stub = stub.add_network_specs_in(
                             &specs,
                             &ValidCurrentVerifier::General,
                             &general_verifier,
-                            &db_path,
+                            storage,
                         )?;
                         let specs_card = Card::NewSpecs(&specs).card(&mut index, 0);
-                        let checksum = stub.store_and_get_checksum(&db_path)?;
+                        let checksum = stub.store_and_get_checksum_in(storage)?;
                         Ok(TransactionAction::Stub {
                             s: TransactionCardSet {
                                 verifier: Some(vec![verifier_card]),
@@ -0,0 +1,123 @@
+use db_handling::{
+    db_transactions::TrDbColdStub,
+    helpers::{
+        get_general_verifier_in, try_get_network_specs_in, try_get_valid_current_verifier_in,
+    },
+    storage::Storage,
+};
+use definitions::{
+    error::TransferContent,
+    error_signer::GeneralVerifierForContent,
+    keyring::{NetworkSpecsKey, VerifierKey},
+    navigation::TransactionCardSet,
+    network_specs::{ValidCurrentVerifier, Verifier},
+    qr_transfers::ContentAmendSpecs,
+};
+
+use crate::cards::{Card, Warning};
+use crate::check_signature::pass_crypto;
+use crate::error::{Error, Result};
+use crate::{StubNav, TransactionAction};
+
+pub fn amend_specs<S>(data_hex: &str, storage: &S) -> Result<TransactionAction>
+where
+    S: Storage,
+{
+    let checked_info = pass_crypto(data_hex, TransferContent::AmendSpecs)?;
+    let new_specs = ContentAmendSpecs::from_slice(&checked_info.message).specs()?;
+    let network_specs_key =
+        NetworkSpecsKey::from_parts(&new_specs.genesis_hash, &new_specs.encryption);
+    let old_network_specs =
+        try_get_network_specs_in(storage, &network_specs_key)?.ok_or_else(|| {
+            Error::AmendSpecsUnknownNetwork {
+                network_specs_key: network_specs_key.to_owned(),
+            }
+        })?;
+    let verifier_key = VerifierKey::from_parts(new_specs.genesis_hash);
+    let valid_current_verifier = try_get_valid_current_verifier_in(&verifier_key, storage)?
+        .ok_or_else(|| Error::AmendSpecsUnknownNetwork {
+            network_specs_key: network_specs_key.to_owned(),
+        })?;
+    let old_specs = old_network_specs.to_send();
+    let name = old_specs.name.to_string();
+
+    if let Some(field) = old_specs.fixed_field_changed(&new_specs) {
+        return Err(Error::AmendSpecsNotAllowed {
+            name,
+            field: field.to_string(),
+        });
+    }
+
+    // amendment must be signed by the current network verifier, be it custom
+    // or general one; unverified networks could get new specs only through
+    // `add_specs` update
+    let is_general = matches!(valid_current_verifier, ValidCurrentVerifier::General);
+    let current_verifier = match valid_current_verifier {
+        ValidCurrentVerifier::General => get_general_verifier_in(storage)?,
+        ValidCurrentVerifier::Custom { v } => v,
+    };
+    let current_verifier_value = match current_verifier {
+        Verifier {
+            v: Some(current_verifier_value),
+        } => current_verifier_value,
+        Verifier { v: None } => return Err(Error::AmendSpecsNotVerified { name }),
+    };
+    match checked_info.verifier {
+        Verifier { v: None } => {
+            if is_general {
+                return Err(Error::NeedGeneralVerifier {
+                    content: GeneralVerifierForContent::Network { name },
+                    verifier_value: current_verifier_value,
+                });
+            } else {
+                return Err(Error::NeedVerifier {
+                    name,
+                    verifier_value: current_verifier_value,
+                });
+            }
+        }
+        Verifier {
+            v: Some(received_verifier_value),
+        } => {
+            if received_verifier_value != current_verifier_value {
+                return Err(Error::AmendSpecsNotEndorsed {
+                    name,
+                    current_verifier_value,
+                    received_verifier_value,
+                });
+            }
+        }
+    }
+    if old_specs.display_changes(&new_specs).is_empty() {
+        return Err(Error::SpecsKnown {
+            name,
+            encryption: new_specs.encryption,
+        });
+    }
+
+    let mut index = 0;
+    let verifier_card = Card::Verifier(&current_verifier_value).card(&mut index, 0);
+    let warning_card = Card::Warning(Warning::SpecsAmendment {
+        old_specs: &old_specs,
+        new_specs: &new_specs,
+    })
+    .card(&mut index, 0);
+    let old_specs_card = Card::NewSpecs(&old_specs).card(&mut index, 0);
+    let new_specs_card = Card::NewSpecs(&new_specs).card(&mut index, 0);
+    let checksum = TrDbColdStub::new()
+        .amend_network_specs(&old_network_specs, &new_specs)
+        .store_and_get_checksum_in(storage)?;
+    Ok(TransactionAction::Stub {
+        s: TransactionCardSet {
+            verifier: Some(vec![verifier_card]),
+            warning: Some(vec![warning_card]),
+            old_specs: Some(vec![old_specs_card]),
+            new_specs: Some(vec![new_specs_card]),
+            ..Default::default()
+        },
+        u: checksum,
+        stub: StubNav::AmendSpecs {
+            n: network_specs_key,
+        },
+    })
+}
@@ -1,4 +1,4 @@
-use sp_core::crypto::{Ss58AddressFormat, Ss58Codec};
+use sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58Codec};
 use sp_runtime::{generic::Era, MultiSigner};
 
 use definitions::helpers::make_identicon_from_account;
@@ -8,14 +8,16 @@ use definitions::{
     helpers::{make_identicon_from_multisigner, pic_meta, print_multisigner_as_base58},
     history::MetaValuesDisplay,
     keyring::VerifierKey,
+    localisation::LocalisedMessage,
     navigation::{
-        Address, Card as NavCard, MMetadataRecord, MSCAuthorPlain, MSCCall, MSCCurrency,
-        MSCEnumVariantName, MSCEraMortal, MSCFieldName, MSCFieldNumber, MSCId, MSCNameVersion,
-        MSCNetworkInfo, MTypesInfo, MVerifierDetails, TransactionCard,
+        Address, Card as NavCard, MMetadataRecord, MSCAuthorPlain, MSCCall, MSCContact,
+        MSCCurrency, MSCEnumVariantName, MSCEraMortal, MSCFieldName, MSCFieldNumber, MSCId,
+        MSCNameVersion, MSCNetworkInfo, MTypesInfo, MVerifierDetails, TransactionCard,
     },
     network_specs::{NetworkSpecs, NetworkSpecsToSend, VerifierValue},
+    policy::SecurityPolicy,
     qr_transfers::ContentLoadTypes,
-    users::AddressDetails,
+    users::{AddressDetails, ContactEntry, KeyAnnotation},
 };
 use parser::cards::ParserCard;
 
@@ -25,10 +27,17 @@ use crate::holds::{GeneralHold, Hold};
 #[allow(clippy::enum_variant_names)]
 pub(crate) enum Card<'a> {
     ParserCard(&'a ParserCard),
+    Id {
+        id: &'a AccountId32,
+        base58prefix: u16,
+        contact_name: Option<&'a str>,
+    },
+    Contact(&'a ContactEntry),
     Author {
         author: &'a MultiSigner,
         base58prefix: u16,
         address_details: &'a AddressDetails,
+        annotation: Option<&'a KeyAnnotation>,
     },
     AuthorPlain {
         author: &'a MultiSigner,
@@ -72,24 +81,104 @@ pub(crate) enum Warning<'a> {
     TypesAlreadyThere,
     NetworkSpecsAlreadyThere(&'a str), // network title
     MetadataExtensionsIncomplete,
+    ContactsNotVerified,
+    ContactLookalike {
+        base58: String,
+        contact_name: &'a str,
+    },
+    NonceReplay {
+        nonce: u64,
+        last_nonce: u64,
+    },
+    VerifierRotation {
+        name: &'a str,
+        verifier_key: &'a VerifierKey,
+    },
+    SecurityPolicy(&'a SecurityPolicy),
+    SpecsAmendment {
+        old_specs: &'a NetworkSpecsToSend,
+        new_specs: &'a NetworkSpecsToSend,
+    },
 }
 
 impl<'a> Warning<'a> {
-    pub(crate) fn show(&self) -> String {
+    pub(crate) fn message(&self) -> LocalisedMessage {
         match &self {
-            Warning::AuthorNotFound => String::from("Transaction author public key not found."),
-            Warning::NewerVersion {used_version, latest_version} => format!("Transaction uses outdated runtime version {}. Latest known available version is {}.", used_version, latest_version),
-            Warning::NoNetworkID => String::from("Public key is on record, but not associated with the network used."),
-            Warning::NotVerified => String::from("Received network information is not verified."),
-            Warning::UpdatingTypes => String::from("Updating types (really rare operation)."),
-            Warning::TypesNotVerified => String::from("Received types information is not verified."),
-            Warning::GeneralVerifierAppeared(x) => format!("Received message is verified by a new general verifier. Currently no general verifier is set, and proceeding will update the general verifier to the received value. All previously acquired information associated with general verifier will be purged. {}", x.show()),
-            Warning::VerifierChangingToGeneral{verifier_key, hold} => format!("Received message is verified by the general verifier. Current verifier for network with genesis hash {} is a custom one, and proceeding will update the network verifier to general. All previously acquired information associated with former custom verifier will be purged. {}", hex::encode(verifier_key.genesis_hash()), hold.show()),
-            Warning::VerifierChangingToCustom{verifier_key, hold} => format!("Received message is verified. Currently no verifier is set for network with genesis hash {}. Proceeding will update the network verifier to custom verifier. All previously acquired network information that was received unverified will be purged. {}", hex::encode(verifier_key.genesis_hash()), hold.show()),
-            Warning::VerifierGeneralSuper{verifier_key, hold} => format!("Received message is verified. Currently no verifier is set for network with genesis hash {} and no general verifier is set. Proceeding will update the network verifier to general. All previously acquired network information that was received unverified will be purged. {}", hex::encode(verifier_key.genesis_hash()), hold.show()),
-            Warning::TypesAlreadyThere => String::from("Received types information is identical to the one that was in the database."),
-            Warning::NetworkSpecsAlreadyThere (x) => format!("Received network specs information for {} is same as the one already in the database.", x),
-            Warning::MetadataExtensionsIncomplete => String::from("Received metadata has incomplete set of signed extensions. As a result, Signer may be unable to parse signable transactions using this metadata."),
+            Warning::AuthorNotFound => LocalisedMessage::new("warning.author_not_found"),
+            Warning::NewerVersion {
+                used_version,
+                latest_version,
+            } => LocalisedMessage::new("warning.newer_version")
+                .with_number("used_version", *used_version)
+                .with_number("latest_version", *latest_version),
+            Warning::NoNetworkID => LocalisedMessage::new("warning.no_network_id"),
+            Warning::NotVerified => LocalisedMessage::new("warning.not_verified"),
+            Warning::UpdatingTypes => LocalisedMessage::new("warning.updating_types"),
+            Warning::TypesNotVerified => LocalisedMessage::new("warning.types_not_verified"),
+            Warning::GeneralVerifierAppeared(x) => x.affected(LocalisedMessage::new(if x.types {
+                "warning.general_verifier_appeared_types"
+            } else {
+                "warning.general_verifier_appeared"
+            })),
+            Warning::VerifierChangingToGeneral { verifier_key, hold } => hold.affected(
+                LocalisedMessage::new("warning.verifier_changing_to_general")
+                    .with_hex("genesis_hash", verifier_key.genesis_hash()),
+            ),
+            Warning::VerifierChangingToCustom { verifier_key, hold } => hold.affected(
+                LocalisedMessage::new("warning.verifier_changing_to_custom")
+                    .with_hex("genesis_hash", verifier_key.genesis_hash()),
+            ),
+            Warning::VerifierGeneralSuper { verifier_key, hold } => hold.affected(
+                LocalisedMessage::new("warning.verifier_general_super")
+                    .with_hex("genesis_hash", verifier_key.genesis_hash()),
+            ),
+            Warning::TypesAlreadyThere => LocalisedMessage::new("warning.types_already_there"),
+            Warning::NetworkSpecsAlreadyThere(x) => {
+                LocalisedMessage::new("warning.network_specs_already_there").with_text("title", x)
+            }
+            Warning::MetadataExtensionsIncomplete => {
+                LocalisedMessage::new("warning.metadata_extensions_incomplete")
+            }
+            Warning::ContactsNotVerified => LocalisedMessage::new("warning.contacts_not_verified"),
+            Warning::ContactLookalike {
+                base58,
+                contact_name,
+            } => LocalisedMessage::new("warning.contact_lookalike")
+                .with_text("address", base58)
+                .with_text("contact_name", contact_name),
+            Warning::NonceReplay { nonce, last_nonce } => {
+                LocalisedMessage::new("warning.nonce_replay")
+                    .with_number("nonce", *nonce)
+                    .with_number("last_nonce", *last_nonce)
+            }
+            Warning::VerifierRotation { name, verifier_key } => {
+                LocalisedMessage::new("warning.verifier_rotation")
+                    .with_text("name", name)
+                    .with_hex("genesis_hash", verifier_key.genesis_hash())
+            }
+            Warning::SecurityPolicy(security_policy) => {
+                LocalisedMessage::new("warning.security_policy").with_list(
+                    "rules",
+                    security_policy
+                        .rules()
+                        .iter()
+                        .map(|rule| rule.id().to_string())
+                        .collect(),
+                )
+            }
+            Warning::SpecsAmendment {
+                old_specs,
+                new_specs,
+            } => LocalisedMessage::new("warning.specs_amendment")
+                .with_text("name", &new_specs.name)
+                .with_list(
+                    "changes",
+                    old_specs
+                        .display_changes(new_specs)
+                        .into_iter()
+                        .map(|(field, old, new)| format!("{}: {} -> {}", field, old, new))
+                        .collect(),
+                ),
         }
     }
 }
@@ -112,13 +201,7 @@ impl<'a> Card<'a> {
                 ParserCard::Text(decoded_text) => NavCard::TextCard {
                     f: hex::encode(decoded_text.as_bytes()),
                 },
-                ParserCard::Id { id, base58prefix } => NavCard::IdCard {
-                    f: MSCId {
-                        base58: id
-                            .to_ss58check_with_version(Ss58AddressFormat::custom(*base58prefix)),
-                        identicon: make_identicon_from_account(id.to_owned()),
-                    },
-                },
+                ParserCard::Id { id, base58prefix } => id_card(id, *base58prefix, None),
                 ParserCard::None => NavCard::NoneCard,
                 ParserCard::IdentityField(variant) => {
                     NavCard::IdentityFieldCard { f: variant.clone() }
@@ -193,12 +276,28 @@ impl<'a> Card<'a> {
                 },
                 ParserCard::TxVersion(x) => NavCard::TxSpecCard { f: x.clone() },
             },
+            Card::Id {
+                id,
+                base58prefix,
+                contact_name,
+            } => id_card(id, *base58prefix, *contact_name),
+            Card::Contact(x) => {
+                let account_id = AccountId32::new(x.account_id);
+                NavCard::ContactCard {
+                    f: MSCContact {
+                        name: x.name.to_owned(),
+                        base58: account_id.to_ss58check(),
+                        identicon: make_identicon_from_account(account_id),
+                    },
+                }
+            }
             Card::Author {
                 author,
                 base58prefix,
                 address_details,
+                annotation,
             } => NavCard::AuthorCard {
-                f: make_author_info(author, *base58prefix, address_details),
+                f: make_author_info(author, *base58prefix, address_details, *annotation),
             },
             Card::AuthorPlain {
                 author,
@@ -270,7 +369,13 @@ impl<'a> Card<'a> {
             },
             Card::NetworkGenesisHash(x) => NavCard::NetworkGenesisHashCard { f: hex::encode(x) },
             Card::Derivations(x) => NavCard::DerivationsCard { f: x.to_vec() },
-            Card::Warning(warn) => NavCard::WarningCard { f: warn.show() },
+            Card::Warning(warn) => {
+                let message = warn.message();
+                NavCard::WarningCard {
+                    f: message.show(),
+                    message,
+                }
+            }
             Card::Error(err) => NavCard::ErrorCard {
                 f: format!("Bad input data. {}", err),
             },
@@ -286,10 +391,21 @@ impl<'a> Card<'a> {
     }
 }
 
+fn id_card(id: &AccountId32, base58prefix: u16, contact_name: Option<&str>) -> NavCard {
+    NavCard::IdCard {
+        f: MSCId {
+            base58: id.to_ss58check_with_version(Ss58AddressFormat::custom(base58prefix)),
+            identicon: make_identicon_from_account(id.to_owned()),
+            contact_name: contact_name.map(|name| name.to_string()),
+        },
+    }
+}
+
 pub(crate) fn make_author_info(
     author: &MultiSigner,
     base58prefix: u16,
     address_details: &AddressDetails,
+    annotation: Option<&KeyAnnotation>,
 ) -> Address {
     Address {
         base58: print_multisigner_as_base58(author, Some(base58prefix)),
@@ -299,5 +415,6 @@ pub(crate) fn make_author_info(
         has_pwd: address_details.has_pwd,
         multiselect: None,
         secret_exposed: address_details.secret_exposed,
+        annotation: annotation.cloned(),
     }
 }
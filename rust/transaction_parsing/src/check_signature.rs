@@ -1,8 +1,10 @@
 use crate::error::{Error, Result};
+use constants::MAX_DECOMPRESSED_SIZE;
 use definitions::{
     error::TransferContent,
     helpers::unhex,
     network_specs::{Verifier, VerifierValue},
+    qr_transfers::ContentCompressed,
 };
 use parser::decoding_commons::get_compact;
 use sp_core::{ecdsa, ed25519, sr25519, Pair};
@@ -17,6 +19,7 @@ pub struct InfoPassedCrypto {
 
 pub fn pass_crypto(data_hex: &str, content: TransferContent) -> Result<InfoPassedCrypto> {
     let data = unhex(data_hex)?;
+    let compressed = is_compressed(data_hex);
 
     match &data_hex[2..4] {
         "00" => {
@@ -24,7 +27,7 @@ pub fn pass_crypto(data_hex: &str, content: TransferContent) -> Result<InfoPasse
             let a = data.get(3..35).ok_or(Error::TooShort)?;
             let into_pubkey: [u8; 32] = a.try_into().expect("fixed size should fit in array");
             let (pubkey, data) = (ed25519::Public::from_raw(into_pubkey), &data[35..]);
-            let (message, tail) = cut_data(data, content)?;
+            let (message, tail) = cut_data(data, content, compressed)?;
             let a = tail.get(..64).ok_or(Error::TooShort)?;
             let into_signature: [u8; 64] = a.try_into().expect("fixed size should fit in array");
             let (signature, tail) = (
@@ -50,7 +53,7 @@ pub fn pass_crypto(data_hex: &str, content: TransferContent) -> Result<InfoPasse
             let a = data.get(3..35).ok_or(Error::TooShort)?;
             let into_pubkey: [u8; 32] = a.try_into().expect("fixed size should fit in array");
             let (pubkey, data) = (sr25519::Public::from_raw(into_pubkey), &data[35..]);
-            let (message, tail) = cut_data(data, content)?;
+            let (message, tail) = cut_data(data, content, compressed)?;
             let a = tail.get(..64).ok_or(Error::TooShort)?;
             let into_signature: [u8; 64] = a.try_into().expect("fixed size should fit in array");
             let (signature, tail) = (
@@ -76,7 +79,7 @@ pub fn pass_crypto(data_hex: &str, content: TransferContent) -> Result<InfoPasse
             let a = data.get(3..36).ok_or(Error::TooShort)?;
             let into_pubkey: [u8; 33] = a.try_into().expect("fixed size should fit in array");
             let (pubkey, data) = (ecdsa::Public::from_raw(into_pubkey), &data[36..]);
-            let (message, tail) = cut_data(data, content)?;
+            let (message, tail) = cut_data(data, content, compressed)?;
             let a = tail.get(..65).ok_or(Error::TooShort)?;
             let into_signature: [u8; 65] = a.try_into().expect("fixed size should fit in array");
             let (signature, tail) = (
@@ -100,7 +103,7 @@ pub fn pass_crypto(data_hex: &str, content: TransferContent) -> Result<InfoPasse
         "ff" => {
             // Received info was not signed
             let data = data.get(3..).ok_or(Error::TooShort)?;
-            let (message, tail) = cut_data(data, content)?;
+            let (message, tail) = cut_data(data, content, compressed)?;
             let verifier = Verifier { v: None };
             Ok(InfoPassedCrypto {
                 verifier,
@@ -112,17 +115,68 @@ pub fn pass_crypto(data_hex: &str, content: TransferContent) -> Result<InfoPasse
     }
 }
 
-fn cut_data(data: &[u8], content: TransferContent) -> Result<(Vec<u8>, Vec<u8>)> {
+/// Get update content type from compressed update, without checking the
+/// signature.
+///
+/// Compressed update (prelude `53xx82`) could contain `add_specs`,
+/// `load_metadata`, `load_types`, `add_contacts`, `rotate_verifier`,
+/// `security_policy` or `amend_specs` content. The content is
+/// processed with corresponding update handler, that uses [`pass_crypto`] to
+/// decompress the content and verify the signature.
+pub fn compressed_content(data_hex: &str) -> Result<TransferContent> {
+    let data = unhex(data_hex)?;
+    let start = match &data_hex[2..4] {
+        "00" | "01" => 35,
+        "02" => 36,
+        "ff" => 3,
+        _ => return Err(Error::EncryptionNotSupported(data_hex[2..4].to_string())),
+    };
+    let data = data.get(start..).ok_or(Error::TooShort)?;
+    let (content_compressed, _) = cut_opaque(data)?;
+    Ok(ContentCompressed::from_slice(&content_compressed)
+        .content_type()?
+        .into())
+}
+
+fn is_compressed(data_hex: &str) -> bool {
+    data_hex.get(4..6) == Some("82")
+}
+
+fn cut_opaque(data: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+    let pre_data = get_compact::<u32>(data)?;
+    let data_length = pre_data.compact_found as usize;
+    let start = pre_data.start_next_unit.ok_or(Error::TooShort)?;
+    let a = data
+        .get(start..start + data_length)
+        .ok_or(Error::TooShort)?;
+    Ok((a.to_vec(), data[start + data_length..].to_vec()))
+}
+
+fn cut_data(data: &[u8], content: TransferContent, compressed: bool) -> Result<(Vec<u8>, Vec<u8>)> {
+    if compressed {
+        // Compressed payload consists of SCALE encoded `Vec<u8>` of `ContentCompressed`. Signature verifies the decompressed content, i.e. exactly the same data as in uncompressed update, so the verifiers could sign the update once for both forms.
+        let (content_compressed, tail) = cut_opaque(data)?;
+        let (content_type, message) =
+            ContentCompressed::from_slice(&content_compressed).decompress(MAX_DECOMPRESSED_SIZE)?;
+        let found = TransferContent::from(content_type);
+        if found != content {
+            return Err(Error::CompressedContentMismatch {
+                expected: content,
+                found,
+            });
+        }
+        return Ok((message, tail));
+    }
     let pre_data = get_compact::<u32>(data)?;
     match content {
-        TransferContent::AddSpecs | TransferContent::LoadTypes => {
-            // `AddSpecs` and `LoadTypes` payloads consist of SCALE encoded `Vec<u8>` of `ContentAddSpecs` or `ContentLoadTypes` correspondingly. Encoding of contents is done to have exact length of data easily accessible (to cut data correctly in case multisignatures are implemented). Signature verifies `ContentAddSpecs` or `ContentLoadTypes` correspondingly, WITHOUT the length piece from encoding
-            let data_length = pre_data.compact_found as usize;
-            let start = pre_data.start_next_unit.ok_or(Error::TooShort)?;
-            let a = data
-                .get(start..start + data_length)
-                .ok_or(Error::TooShort)?;
-            Ok((a.to_vec(), data[start + data_length..].to_vec()))
+        TransferContent::AddSpecs
+        | TransferContent::LoadTypes
+        | TransferContent::AddContacts
+        | TransferContent::RotateVerifier
+        | TransferContent::SecurityPolicy
+        | TransferContent::AmendSpecs => {
+            // `AddSpecs`, `LoadTypes`, `AddContacts`, `RotateVerifier`, `SecurityPolicy` and `AmendSpecs` payloads consist of SCALE encoded `Vec<u8>` of `ContentAddSpecs`, `ContentLoadTypes`, `ContentAddContacts`, `ContentRotateVerifier`, `ContentSecurityPolicy` or `ContentAmendSpecs` correspondingly. Encoding of contents is done to have exact length of data easily accessible (to cut data correctly in case multisignatures are implemented). Signature verifies the content, WITHOUT the length piece from encoding
+            cut_opaque(data)
         }
         TransferContent::LoadMeta => {
             // LoadMeta payload consists of SCALE encoded `Vec<u8>` of metadata and `[u8;32]` genesis hash; compact announces length of metadata vector, 32 is added to include the genesis hash
@@ -1,27 +1,26 @@
 use db_handling::{
-    db_transactions::TrDbColdDerivations, helpers::try_get_network_specs,
-    identities::check_derivation_set,
+    db_transactions::TrDbColdDerivations, helpers::try_get_network_specs_in,
+    identities::check_derivation_set, storage::Storage,
 };
 use definitions::{
     helpers::unhex, keyring::NetworkSpecsKey, navigation::TransactionCardSet,
     qr_transfers::ContentDerivations,
 };
-use std::path::Path;
 
 use crate::cards::Card;
 use crate::error::{Error, Result};
 use crate::TransactionAction;
 
-pub fn process_derivations<P>(data_hex: &str, db_path: P) -> Result<TransactionAction>
+pub fn process_derivations<S>(data_hex: &str, storage: &S) -> Result<TransactionAction>
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
     let data = unhex(data_hex)?;
     let content_derivations = ContentDerivations::from_slice(&data[3..]);
     let (encryption, genesis_hash, derivations) =
         content_derivations.encryption_genhash_derivations()?;
     let network_specs_key = NetworkSpecsKey::from_parts(&genesis_hash, &encryption);
-    let network_specs = try_get_network_specs(&db_path, &network_specs_key)?.ok_or(
+    let network_specs = try_get_network_specs_in(storage, &network_specs_key)?.ok_or(
         Error::NetworkForDerivationsImport {
             genesis_hash,
             encryption,
@@ -29,7 +28,7 @@ where
     )?;
     check_derivation_set(&derivations)?;
     let checksum = TrDbColdDerivations::generate(&derivations, &network_specs)
-        .store_and_get_checksum(&db_path)?;
+        .store_and_get_checksum_in(storage)?;
     let derivations_card = Card::Derivations(&derivations).card(&mut 0, 0);
     let network_info = network_specs;
     Ok(TransactionAction::Derivations {
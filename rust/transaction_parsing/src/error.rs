@@ -2,7 +2,7 @@ use std::fmt::Write;
 
 use definitions::{
     crypto::Encryption,
-    error::MetadataError,
+    error::{MetadataError, TransferContent},
     error_signer::GeneralVerifierForContent,
     keyring::NetworkSpecsKey,
     network_specs::{ValidCurrentVerifier, Verifier, VerifierValue},
@@ -57,6 +57,7 @@ pub enum Error {
     /// - `03` text message
     /// - `80` `load_metadata` update
     /// - `81` `load_types` update
+    /// - `82` compressed `load_metadata`, `load_types` or `add_specs` update
     /// - `c1` `add_specs` update
     /// - `de` `derivations` update
     /// - `f0` print all available cards (testing tool)
@@ -116,11 +117,16 @@ pub enum Error {
     /// - `base58prefix`, network-associated base58 prefix  
     /// - `decimals`  
     /// - `name`, network name, as it appears in the network metadata  
+    /// - `tokens`, network tokens other than the main one  
     /// - `unit`
+    ///
+    /// Changes of `decimals`, `tokens` and `unit`, and of the display-only
+    /// specs fields, could be made with verified `amend_specs` update, that
+    /// keeps the network addresses and metadata.
     #[error(
         "Similar network specs are already stored in the database under key {}. \
         Network specs in received payload have different unchangeable values \
-        (base58 prefix, decimals, encryption, network name, unit).",
+        (base58 prefix, decimals, encryption, network name, tokens, unit).",
         hex::encode(.0.key())
     )]
     ImportantSpecsChanged(NetworkSpecsKey),
@@ -197,6 +203,47 @@ pub enum Error {
     )]
     NoMetadata { name: String },
 
+    /// Received transaction with metadata proof (with prelude `53xx06`) has
+    /// network information in the proof different from the network specs in
+    /// the database.
+    ///
+    /// Transaction could not be decoded, as the proof does not correspond to
+    /// the network.
+    #[error(
+        "Metadata proof in the transaction has {field} different from the network \
+        specs of {name} network."
+    )]
+    MetadataProofSpecsMismatch {
+        /// network name
+        name: String,
+
+        /// name of the network specs field that differs
+        field: String,
+    },
+
+    /// Received transaction with metadata proof (with prelude `53xx06`) is
+    /// generated in the network that has no metadata entries with
+    /// `CheckMetadataHash` signed extension in the `METATREE` tree of the
+    /// database.
+    ///
+    /// Position of the metadata hash in transaction extensions is known only
+    /// from the metadata loaded into Signer, transaction is not accepted.
+    #[error(
+        "Metadata proof in the transaction could not be accepted. No metadata \
+        of {name} network in the database has CheckMetadataHash signed extension. \
+        Add network metadata."
+    )]
+    MetadataProofNotAccepted { name: String },
+
+    /// Received transaction with metadata proof (with prelude `53xx06`) has
+    /// signed extensions in the proof different from the signed extensions in
+    /// all metadata entries of the network in the database.
+    #[error(
+        "Metadata proof in the transaction has signed extensions different from \
+        the metadata of {name} network in the database."
+    )]
+    MetadataProofExtensionsMismatch { name: String },
+
     /// Received signable transaction (with prelude `53xx00`, `53xx02` or
     /// `53xx03`) is generated in the network that has no corresponding
     /// [`NetworkSpecs`](definitions::network_specs::NetworkSpecs) entry in the
@@ -216,6 +263,20 @@ pub enum Error {
     #[error("Received message could not be read. {0}")]
     Codec(#[from] parity_scale_codec::Error),
 
+    /// Compressed update payload (with prelude `53xx82`) contains update
+    /// content of type different from the one expected by update processing.
+    #[error(
+        "Compressed update payload contains content of unexpected type. \
+        Expected {expected:?}, found {found:?}."
+    )]
+    CompressedContentMismatch {
+        /// content type expected by update processing
+        expected: TransferContent,
+
+        /// content type declared in the compressed payload
+        found: TransferContent,
+    },
+
     /// [`NetworkSpecsToSend`](definitions::network_specs::NetworkSpecsToSend)
     /// received in `add_specs` payload are for a network that already has
     /// [`NetworkSpecs`](definitions::network_specs::NetworkSpecs) entry in
@@ -374,6 +435,237 @@ pub enum Error {
     #[error("Exactly same types information is already in the database.")]
     TypesKnown,
 
+    /// Received `add_contacts` update contains only contacts that are
+    /// already in the database under exactly same names.
+    ///
+    /// Not exactly an error, but Signer can't do anything and complains.
+    #[error("All received contacts are already in the database.")]
+    ContactsKnown,
+
+    /// User attempted to rotate verifier for the network that has no
+    /// [`CurrentVerifier`](definitions::network_specs::CurrentVerifier) entry
+    /// in the `VERIFIERS` tree or no associated network specs in the
+    /// `SPECSTREE` tree of the Signer database.
+    #[error(
+        "Network with genesis hash {} is not in the database. \
+        Verifier could be rotated only for a known network.",
+        hex::encode(genesis_hash)
+    )]
+    RotateVerifierUnknownNetwork {
+        /// network genesis hash from the received `rotate_verifier` payload
+        genesis_hash: H256,
+    },
+
+    /// User attempted to rotate verifier for the network that has no custom
+    /// verifier set up, i.e. the network is not verified or is verified by the
+    /// general verifier.
+    ///
+    /// Only custom network verifier could be rotated, all other verifier
+    /// changes go through the network specs update and purge the network data.
+    #[error(
+        "Network {name} has no custom verifier. \
+        Only custom network verifier could be rotated."
+    )]
+    RotateVerifierNotCustom {
+        /// network name
+        name: String,
+    },
+
+    /// Received `rotate_verifier` payload is signed by
+    /// `received_verifier_value`, that is not the current custom verifier of
+    /// the network.
+    ///
+    /// Rotation must be endorsed by the current network verifier, so that
+    /// a chain of rotations could be applied only in order, each link signed
+    /// by the verifier set up by the previous one.
+    #[error(
+        "Network {name} current verifier is {}. Received rotate_verifier message \
+        is signed by {}. Verifier rotation must be endorsed by the current network \
+        verifier.",
+        current_verifier_value.show_error(),
+        received_verifier_value.show_error(),
+    )]
+    RotateVerifierNotEndorsed {
+        /// network name
+        name: String,
+
+        /// [`VerifierValue`] for the network in the database
+        current_verifier_value: VerifierValue,
+
+        /// [`VerifierValue`] that was used to sign the payload
+        received_verifier_value: VerifierValue,
+    },
+
+    /// Received `rotate_verifier` payload names as the outgoing verifier
+    /// `outgoing_verifier_value`, that is not the current custom verifier of
+    /// the network.
+    ///
+    /// Rotation was made for other verifier chain state, and could not be
+    /// applied.
+    #[error(
+        "Network {name} current verifier is {}. Received rotate_verifier message \
+        rotates out {}. Verifier rotation is outdated.",
+        current_verifier_value.show_error(),
+        outgoing_verifier_value.show_error(),
+    )]
+    RotateVerifierOutdated {
+        /// network name
+        name: String,
+
+        /// [`VerifierValue`] for the network in the database
+        current_verifier_value: VerifierValue,
+
+        /// [`VerifierValue`] named as outgoing in the payload
+        outgoing_verifier_value: VerifierValue,
+    },
+
+    /// Received `rotate_verifier` payload has rotation index other than the
+    /// one following the last rotation accepted for the network.
+    ///
+    /// Rotations are applied only in order, each exactly once, so that the
+    /// rotation could not be replayed after the verifier was rotated back.
+    #[error(
+        "Network {name} expects verifier rotation with index {expected_rotation_index}. \
+        Received rotate_verifier message has index {received_rotation_index}, and is \
+        either replayed or out of order."
+    )]
+    RotateVerifierOutOfOrder {
+        /// network name
+        name: String,
+
+        /// rotation index expected by the Signer
+        expected_rotation_index: u32,
+
+        /// rotation index from the received payload
+        received_rotation_index: u32,
+    },
+
+    /// Received `rotate_verifier` payload sets as a new network verifier the
+    /// verifier already set up for the network.
+    ///
+    /// Not exactly an error, but Signer can't do anything and complains.
+    #[error(
+        "Verifier for network {name} is already the one in the received \
+        rotate_verifier message."
+    )]
+    RotateVerifierSame {
+        /// network name
+        name: String,
+    },
+
+    /// Received `rotate_verifier` payload sets the general verifier as a
+    /// custom network verifier.
+    ///
+    /// Network verifier could be upgraded to general only with an update
+    /// signed by the general verifier, see [here](definitions::network_specs).
+    #[error(
+        "Received rotate_verifier message sets the general verifier as custom verifier \
+        for network {name}. Network verifier could be changed to general only by an \
+        update signed by the general verifier."
+    )]
+    RotateVerifierToGeneral {
+        /// network name
+        name: String,
+    },
+
+    /// Received `security_policy` payload could not be accepted, because the
+    /// Signer has no general verifier set up.
+    ///
+    /// Security policy could be changed only by the general verifier, and
+    /// could not be used to set up one.
+    #[error(
+        "No general verifier is set up in the database. Received security_policy \
+        message could be accepted only if signed by the general verifier."
+    )]
+    SecurityPolicyNoGeneralVerifier,
+
+    /// Received `security_policy` payload contains exactly same security
+    /// policy as the one already stored in the `SETTREE` tree of the database
+    /// under the key `POLICY`.
+    ///
+    /// Not exactly an error, but Signer can't do anything and complains.
+    #[error("Exactly same security policy is already in the database.")]
+    SecurityPolicyKnown,
+
+    /// Received `security_policy` payload has index not greater than the index
+    /// of the security policy in force.
+    ///
+    /// Policy updates are accepted only with increasing indices, so that older
+    /// signed policy could not be replayed.
+    #[error(
+        "Security policy in force has index {stored_index}. Received security_policy \
+        message has index {received_index}, and is either replayed or outdated."
+    )]
+    SecurityPolicyOutdated {
+        /// index of the security policy in force
+        stored_index: u32,
+
+        /// index of the security policy in received message
+        received_index: u32,
+    },
+
+    /// User attempted to amend network specs for the network that has no
+    /// entry in the `SPECSTREE` tree of the Signer database.
+    #[error(
+        "Network with network specs key {} is not in the database. \
+        Network specs could be amended only for a known network.",
+        hex::encode(network_specs_key.key())
+    )]
+    AmendSpecsUnknownNetwork {
+        /// network specs key from the received `amend_specs` payload
+        network_specs_key: NetworkSpecsKey,
+    },
+
+    /// Received `amend_specs` payload changes network specs field that could
+    /// not be changed without removing the network, i.e. `name`,
+    /// `base58prefix` or `path_id`.
+    #[error(
+        "Received amend_specs message changes {field} of network {name}. \
+        Only unit, decimals, title, logo and colors could be amended."
+    )]
+    AmendSpecsNotAllowed {
+        /// network name
+        name: String,
+
+        /// name of the network specs field that was changed
+        field: String,
+    },
+
+    /// User attempted to amend network specs for the network that is not
+    /// verified, or is verified by the general verifier while no general
+    /// verifier is set up.
+    ///
+    /// Amendment must be signed by the current network verifier, unverified
+    /// network specs could only be updated with `add_specs` update.
+    #[error(
+        "Network {name} is not verified. \
+        Network specs could be amended only for a verified network."
+    )]
+    AmendSpecsNotVerified {
+        /// network name
+        name: String,
+    },
+
+    /// Received `amend_specs` payload is signed by `received_verifier_value`,
+    /// that is not the current network verifier.
+    #[error(
+        "Network {name} current verifier is {}. Received amend_specs message \
+        is signed by {}. Network specs amendment must be signed by the current \
+        network verifier.",
+        current_verifier_value.show_error(),
+        received_verifier_value.show_error(),
+    )]
+    AmendSpecsNotEndorsed {
+        /// network name
+        name: String,
+
+        /// [`VerifierValue`] for the network in the database
+        current_verifier_value: VerifierValue,
+
+        /// [`VerifierValue`] that was used to sign the payload
+        received_verifier_value: VerifierValue,
+    },
+
     /// User attempted to load into Signer the metadata for the network that
     /// has no [`CurrentVerifier`](definitions::network_specs::CurrentVerifier) entry
     /// in the `VERIFIERS` tree of the Signer database.
@@ -599,6 +891,8 @@ fn display_general_verifier_for_content(g: &GeneralVerifierForContent) -> String
     match g {
         GeneralVerifierForContent::Network { name } => format!("{} network information", name),
         GeneralVerifierForContent::Types => String::from("types information"),
+        GeneralVerifierForContent::Contacts => String::from("contacts"),
+        GeneralVerifierForContent::SecurityPolicy => String::from("security policy"),
     }
 }
 
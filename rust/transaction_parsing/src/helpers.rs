@@ -1,37 +1,51 @@
 use constants::{METATREE, SPECSTREE};
-use db_handling::helpers::{get_types, open_db, open_tree};
+use db_handling::{
+    helpers::{get_contacts_in, get_types_in},
+    storage::Storage,
+};
 use definitions::{
     crypto::Encryption,
     error::MetadataError,
     helpers::unhex,
     keyring::{MetaKey, MetaKeyPrefix, NetworkSpecsKey},
     metadata::{MetaSetElement, MetaValues},
+    metadata_proof::{ExtraInfo, MetadataProof},
     network_specs::{NetworkSpecs, NetworkSpecsToSend, ShortSpecs},
+    users::ContactDetails,
+};
+use frame_metadata::{v14::RuntimeMetadataV14, RuntimeMetadata};
+use parser::{cards::ParserCard, decoding_commons::OutputCard, method::OlderMeta, MetadataBundle};
+use sp_core::{
+    crypto::{AccountId32, Ss58AddressFormat, Ss58Codec},
+    ecdsa, ed25519, sr25519, H256,
 };
-use frame_metadata::RuntimeMetadata;
-use parser::{method::OlderMeta, MetadataBundle};
-use sp_core::{ecdsa, ed25519, sr25519, H256};
 use sp_runtime::MultiSigner;
 use std::convert::TryInto;
-use std::path::Path;
 
+use crate::cards::Warning;
 use crate::error::{Error, Result};
 
+/// Number of leading and of trailing characters of base58 address that are
+/// compared with known contacts to detect lookalike addresses.
+///
+/// Leading characters are counted past the characters fixed by the network
+/// prefix, see [`prefix_chars`]. Address poisoning relies on the users
+/// checking only the beginning and the end of the address.
+const LOOKALIKE_CHARS: usize = 5;
+
 /// Function to get the network specs from the database
 /// by network name and encryption
-pub(crate) fn specs_by_name<P>(
+pub(crate) fn specs_by_name<S>(
     network_name: &str,
     encryption: &Encryption,
-    db_path: P,
+    storage: &S,
 ) -> Result<NetworkSpecs>
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
-    let database = open_db(db_path)?;
-    let chainspecs = open_tree(&database, SPECSTREE)?;
     let mut found_network_specs = None;
-    for x in chainspecs.iter().flatten() {
-        let network_specs = NetworkSpecs::from_entry_checked(x)?;
+    for (key, value) in storage.iter(SPECSTREE)? {
+        let network_specs = NetworkSpecs::from_entry_checked((key.into(), value.into()))?;
         if (network_specs.name == network_name) && (&network_specs.encryption == encryption) {
             match found_network_specs {
                 Some(_) => {
@@ -55,16 +69,14 @@ where
     }
 }
 
-pub fn find_meta_set<P>(short_specs: &ShortSpecs, db_path: P) -> Result<Vec<MetaSetElement>>
+pub fn find_meta_set<S>(short_specs: &ShortSpecs, storage: &S) -> Result<Vec<MetaSetElement>>
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
-    let database = open_db(db_path)?;
-    let metadata = open_tree(&database, METATREE)?;
     let mut out: Vec<MetaSetElement> = Vec::new();
     let meta_key_prefix = MetaKeyPrefix::from_name(&short_specs.name);
-    for x in metadata.scan_prefix(meta_key_prefix.prefix()).flatten() {
-        let new_element = MetaSetElement::from_entry(x)?;
+    for (key, value) in storage.scan_prefix(METATREE, &meta_key_prefix.prefix())? {
+        let new_element = MetaSetElement::from_entry((key.into(), value.into()))?;
         if let Some(found_now) = new_element.optional_base58prefix() {
             if found_now != short_specs.base58prefix {
                 return Err(MetadataError::Base58PrefixSpecsMismatch {
@@ -80,22 +92,22 @@ where
     Ok(out)
 }
 
-pub fn bundle_from_meta_set_element<P>(
+pub fn bundle_from_meta_set_element<S>(
     meta_set_element: &MetaSetElement,
-    db_path: P,
+    storage: &S,
 ) -> Result<MetadataBundle>
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
     match meta_set_element.runtime_metadata() {
         RuntimeMetadata::V12(ref meta_v12) => Ok(MetadataBundle::Older {
             older_meta: OlderMeta::V12(meta_v12),
-            types: get_types(db_path)?,
+            types: get_types_in(storage)?,
             network_version: meta_set_element.version(),
         }),
         RuntimeMetadata::V13(ref meta_v13) => Ok(MetadataBundle::Older {
             older_meta: OlderMeta::V13(meta_v13),
-            types: get_types(db_path)?,
+            types: get_types_in(storage)?,
             network_version: meta_set_element.version(),
         }),
         RuntimeMetadata::V14(ref meta_v14) => Ok(MetadataBundle::Sci {
@@ -106,14 +118,12 @@ where
     }
 }
 
-pub fn accept_meta_values<P>(meta_values: &MetaValues, db_path: P) -> Result<bool>
+pub fn accept_meta_values<S>(meta_values: &MetaValues, storage: &S) -> Result<bool>
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
     let meta_key = MetaKey::from_parts(&meta_values.name, meta_values.version);
-    let database = open_db(db_path)?;
-    let metadata = open_tree(&database, METATREE)?;
-    match metadata.get(meta_key.key())? {
+    match storage.get(METATREE, &meta_key.key())? {
         Some(a) => {
             if a == meta_values.meta {
                 Ok(false)
@@ -129,23 +139,22 @@ where
 }
 
 /// Function to check if the chain specs are already in the database
-pub fn specs_are_new<P>(new: &NetworkSpecsToSend, db_path: P) -> Result<bool>
+pub fn specs_are_new<S>(new: &NetworkSpecsToSend, storage: &S) -> Result<bool>
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
     let network_specs_key = NetworkSpecsKey::from_parts(&new.genesis_hash, &new.encryption);
-    let database = open_db(db_path)?;
-    let chainspecs = open_tree(&database, SPECSTREE)?;
-    match chainspecs.get(network_specs_key.key())? {
+    match storage.get(SPECSTREE, &network_specs_key.key())? {
         Some(encoded_known_network_specs) => {
             let old = NetworkSpecs::from_entry_with_key_checked(
                 &network_specs_key,
-                encoded_known_network_specs,
+                encoded_known_network_specs.into(),
             )?;
             if (old.base58prefix != new.base58prefix)
                 | (old.decimals != new.decimals)
                 | (old.encryption != new.encryption)
                 | (old.name != new.name)
+                | (old.tokens != new.tokens)
                 | (old.unit != new.unit)
             {
                 return Err(Error::ImportantSpecsChanged(network_specs_key));
@@ -212,3 +221,150 @@ pub fn multisigner_msg_genesis_encryption(
     let msg = data[..data.len() - 32].to_vec();
     Ok((multi_signer, msg, genesis_hash_vec, encryption))
 }
+
+/// Check that network information committed in metadata proof digest matches
+/// the network specs used for parsing.
+pub(crate) fn check_proof_specs(extra: &ExtraInfo, network_specs: &NetworkSpecs) -> Result<()> {
+    let field = if extra.spec_name != network_specs.name {
+        "name"
+    } else if extra.base58prefix != network_specs.base58prefix {
+        "base58 prefix"
+    } else if extra.decimals != network_specs.decimals {
+        "decimals"
+    } else if extra.unit != network_specs.unit {
+        "unit"
+    } else {
+        return Ok(());
+    };
+    Err(Error::MetadataProofSpecsMismatch {
+        name: network_specs.name.to_string(),
+        field: field.to_string(),
+    })
+}
+
+/// Find the network metadata from the database to decode the extensions of the
+/// transaction with metadata proof.
+///
+/// Proof itself comes with the transaction and could not tell where the
+/// metadata hash is in the extensions. Only `v14` metadata with
+/// `CheckMetadataHash` signed extension and with the same signed extensions as
+/// in the proof is used, newest version first.
+pub(crate) fn proof_extensions_metadata<'a>(
+    meta_set: &'a [MetaSetElement],
+    metadata_proof: &MetadataProof,
+    name: &str,
+) -> Result<&'a RuntimeMetadataV14> {
+    let mut found_metadata_hash = false;
+    for meta_set_element in meta_set.iter() {
+        if let RuntimeMetadata::V14(meta_v14) = meta_set_element.runtime_metadata() {
+            let signed_extensions = &meta_v14.extrinsic.signed_extensions;
+            if !signed_extensions
+                .iter()
+                .any(|x| x.identifier == "CheckMetadataHash")
+            {
+                continue;
+            }
+            found_metadata_hash = true;
+            if signed_extensions
+                .iter()
+                .map(|x| &x.identifier)
+                .eq(metadata_proof
+                    .extrinsic
+                    .signed_extensions
+                    .iter()
+                    .map(|x| &x.identifier))
+            {
+                return Ok(meta_v14);
+            }
+        }
+    }
+    if found_metadata_hash {
+        Err(Error::MetadataProofExtensionsMismatch {
+            name: name.to_string(),
+        })
+    } else {
+        Err(Error::MetadataProofNotAccepted {
+            name: name.to_string(),
+        })
+    }
+}
+
+/// Named contacts known to the Signer, used to annotate the addresses in
+/// parsed transactions.
+pub(crate) struct KnownContacts(Vec<([u8; 32], ContactDetails)>);
+
+impl KnownContacts {
+    /// Get all named contacts from the database.
+    pub(crate) fn get<S>(storage: &S) -> Result<Self>
+    where
+        S: Storage,
+    {
+        Ok(Self(get_contacts_in(storage)?))
+    }
+
+    /// Name of the contact with given account id, if the contact is known.
+    pub(crate) fn name(&self, id: &AccountId32) -> Option<&str> {
+        let id: &[u8] = id.as_ref();
+        self.0
+            .iter()
+            .find(|(account_id, _)| account_id[..] == *id)
+            .map(|(_, contact_details)| contact_details.name.as_str())
+    }
+
+    /// Warnings for addresses in parsed cards that are not known contacts,
+    /// but look similar to one, i.e. have same leading or trailing base58
+    /// characters in same base58 format.
+    pub(crate) fn lookalikes(&self, set: &[OutputCard]) -> Vec<Warning<'_>> {
+        let mut out = Vec::new();
+        for card in set.iter() {
+            if let ParserCard::Id { id, base58prefix } = &card.card {
+                if self.name(id).is_some() {
+                    continue;
+                }
+                let format = Ss58AddressFormat::custom(*base58prefix);
+                let base58 = id.to_ss58check_with_version(format);
+                let skip = prefix_chars(format);
+                for (account_id, contact_details) in self.0.iter() {
+                    let contact_base58 =
+                        AccountId32::new(*account_id).to_ss58check_with_version(format);
+                    if lookalike(&base58, &contact_base58, skip) {
+                        out.push(Warning::ContactLookalike {
+                            base58: base58.to_owned(),
+                            contact_name: &contact_details.name,
+                        });
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Number of leading base58 characters that are same for all addresses in
+/// given base58 format.
+///
+/// These characters are determined by the network prefix bytes and not by the
+/// account id, and are not compared when searching for lookalike addresses.
+pub(crate) fn prefix_chars(format: Ss58AddressFormat) -> usize {
+    let lowest = AccountId32::new([0; 32]).to_ss58check_with_version(format);
+    let highest = AccountId32::new([0xff; 32]).to_ss58check_with_version(format);
+    lowest
+        .chars()
+        .zip(highest.chars())
+        .take_while(|(a, b)| a == b)
+        .count()
+}
+
+/// Check if two different base58 addresses have same leading characters past
+/// first `skip` characters, or same trailing characters.
+pub(crate) fn lookalike(base58: &str, contact_base58: &str, skip: usize) -> bool {
+    if base58 == contact_base58
+        || base58.len() != contact_base58.len()
+        || base58.len() < skip + 2 * LOOKALIKE_CHARS
+    {
+        return false;
+    }
+    let head = skip..skip + LOOKALIKE_CHARS;
+    let tail = base58.len() - LOOKALIKE_CHARS;
+    base58[head.clone()] == contact_base58[head] || base58[tail..] == contact_base58[tail..]
+}
@@ -1,61 +1,59 @@
-use std::fmt::Write;
-use std::path::Path;
-
 use constants::{METATREE, SETTREE, SPECSTREE, TYPES, VERIFIERS};
 use db_handling::{
     db_transactions::TrDbColdStub,
-    helpers::{get_general_verifier, open_db, open_tree, prep_types},
+    helpers::{enforce_policy_in, get_general_verifier_in, prep_types_in},
+    storage::Storage,
 };
 use definitions::{
     history::Event,
     keyring::{MetaKeyPrefix, VerifierKey},
+    localisation::LocalisedMessage,
     metadata::MetaValues,
     network_specs::{CurrentVerifier, NetworkSpecs, ValidCurrentVerifier, Verifier},
+    policy::PolicyRule,
 };
 use parity_scale_codec::Decode;
-use sled::Tree;
+use sled::IVec;
 
 use crate::cards::Warning;
 use crate::error::Result;
 
-fn print_affected(metadata_set: &[MetaValues], network_specs_set: &[NetworkSpecs]) -> String {
-    let mut out_metadata = String::new();
-    let mut out_network_specs = String::new();
-    for (i, x) in metadata_set.iter().enumerate() {
-        if i > 0 {
-            out_metadata.push_str(", ");
-        }
-        let _ = write!(&mut out_metadata, "{}{}", x.name, x.version);
-    }
-    for (i, x) in network_specs_set.iter().enumerate() {
-        if i > 0 {
-            out_network_specs.push_str(", ");
-        }
-        out_network_specs.push_str(&x.title);
-    }
-    if out_network_specs.is_empty() {
-        out_network_specs = String::from("none");
-    }
-    if out_metadata.is_empty() {
-        out_metadata = String::from("none");
-    }
-    format!(
-        "Affected network specs entries: {}; affected metadata entries: {}.",
-        out_network_specs, out_metadata
-    )
+/// Add affected entries to the warning message
+fn with_affected(
+    message: LocalisedMessage,
+    metadata_set: &[MetaValues],
+    network_specs_set: &[NetworkSpecs],
+) -> LocalisedMessage {
+    message
+        .with_list(
+            "network_specs",
+            network_specs_set
+                .iter()
+                .map(|x| x.title.to_string())
+                .collect(),
+        )
+        .with_list(
+            "metadata",
+            metadata_set
+                .iter()
+                .map(|x| format!("{}{}", x.name, x.version))
+                .collect(),
+        )
 }
 
-fn collect_set(
+fn collect_set<S>(
     verifier_key: &VerifierKey,
-    chainspecs: &Tree,
-    metadata: &Tree,
-) -> Result<(Vec<MetaValues>, Vec<NetworkSpecs>)> {
+    storage: &S,
+) -> Result<(Vec<MetaValues>, Vec<NetworkSpecs>)>
+where
+    S: Storage,
+{
     let mut metadata_set = Vec::new();
     let mut network_specs_set = Vec::new();
     let genesis_hash = verifier_key.genesis_hash();
     let mut name_found: Option<String> = None;
-    for x in chainspecs.iter().flatten() {
-        let network_specs = NetworkSpecs::from_entry_checked(x)?;
+    for (key, value) in storage.iter(SPECSTREE)? {
+        let network_specs = NetworkSpecs::from_entry_checked((key.into(), value.into()))?;
         if network_specs.genesis_hash.as_bytes() == &genesis_hash[..] {
             name_found = match name_found {
                 Some(n) => {
@@ -76,8 +74,8 @@ fn collect_set(
     }
     if let Some(name) = name_found {
         let meta_key_prefix = MetaKeyPrefix::from_name(&name);
-        for y in metadata.scan_prefix(meta_key_prefix.prefix()).flatten() {
-            metadata_set.push(MetaValues::from_entry_checked(y)?)
+        for (key, value) in storage.scan_prefix(METATREE, &meta_key_prefix.prefix())? {
+            metadata_set.push(MetaValues::from_entry_checked((key.into(), value.into()))?)
         }
     }
     metadata_set.sort_by(|a, b| a.version.cmp(&b.version));
@@ -92,43 +90,32 @@ pub(crate) struct GeneralHold {
 }
 
 impl GeneralHold {
-    /// function to show entries depending on general verifier
-    pub(crate) fn show(&self) -> String {
-        let part = print_affected(&self.metadata_set, &self.network_specs_set);
-        if self.types {
-            format!("{} Types information is purged.", part)
-        } else {
-            part
-        }
+    /// function to add entries depending on general verifier to the warning
+    pub(crate) fn affected(&self, message: LocalisedMessage) -> LocalisedMessage {
+        with_affected(message, &self.metadata_set, &self.network_specs_set)
     }
     /// function to find all entries in the database that were verified by general verifier
-    pub(crate) fn get<P>(db_path: P) -> Result<Self>
+    pub(crate) fn get<S>(storage: &S) -> Result<Self>
     where
-        P: AsRef<Path>,
+        S: Storage,
     {
         let mut metadata_set = Vec::new();
         let mut network_specs_set = Vec::new(); // all are verified by general_verifier
         let mut verifier_set = Vec::new();
 
-        let database = open_db(&db_path)?;
-        let metadata = open_tree(&database, METATREE)?;
-        let chainspecs = open_tree(&database, SPECSTREE)?;
-        let settings = open_tree(&database, SETTREE)?;
-        let verifiers = open_tree(&database, VERIFIERS)?;
-        for (verifier_key_vec, current_verifier_encoded) in verifiers.iter().flatten() {
-            let verifier_key = VerifierKey::from_ivec(&verifier_key_vec)?;
+        for (verifier_key_vec, current_verifier_encoded) in storage.iter(VERIFIERS)? {
+            let verifier_key = VerifierKey::from_ivec(&IVec::from(verifier_key_vec))?;
             let current_verifier = <CurrentVerifier>::decode(&mut &current_verifier_encoded[..])?;
             if let CurrentVerifier::Valid(ValidCurrentVerifier::General) = current_verifier {
                 verifier_set.push(verifier_key)
             }
         }
         for verifier_key in verifier_set.iter() {
-            let (new_metadata_set, new_network_specs_set) =
-                collect_set(verifier_key, &chainspecs, &metadata)?;
+            let (new_metadata_set, new_network_specs_set) = collect_set(verifier_key, storage)?;
             metadata_set.extend_from_slice(&new_metadata_set);
             network_specs_set.extend_from_slice(&new_network_specs_set);
         }
-        let types = settings.contains_key(TYPES)?;
+        let types = storage.get(SETTREE, TYPES)?.is_some();
         metadata_set.sort_by(|a, b| a.name.cmp(&b.name));
         network_specs_set.sort_by(|a, b| a.title.cmp(&b.title));
         Ok(Self {
@@ -137,19 +124,20 @@ impl GeneralHold {
             types,
         })
     }
-    pub(crate) fn upd_stub<P>(
+    pub(crate) fn upd_stub<S>(
         &self,
         stub: TrDbColdStub,
         new_general_verifier: &Verifier,
-        db_path: P,
+        storage: &S,
     ) -> Result<TrDbColdStub>
     where
-        P: AsRef<Path>,
+        S: Storage,
     {
-        let former_general_verifier = get_general_verifier(&db_path)?;
+        enforce_policy_in(PolicyRule::VerifierChange, storage)?;
+        let former_general_verifier = get_general_verifier_in(storage)?;
         let mut out = stub;
-        out = out.new_history_entry(Event::Warning {
-            warning: Warning::GeneralVerifierAppeared(self).show(),
+        out = out.new_history_entry(Event::WarningMessage {
+            message: Warning::GeneralVerifierAppeared(self).message(),
         });
         for x in self.metadata_set.iter() {
             out = out.remove_metadata(x)
@@ -162,7 +150,7 @@ impl GeneralHold {
             )
         }
         if self.types {
-            out = out.remove_types(&prep_types(&db_path)?, &former_general_verifier)
+            out = out.remove_types(&prep_types_in(storage)?, &former_general_verifier)
         }
         out = out.new_general_verifier(new_general_verifier);
         Ok(out)
@@ -175,56 +163,54 @@ pub(crate) struct Hold {
 }
 
 impl Hold {
-    /// function to show entries depending on former verifier
-    pub(crate) fn show(&self) -> String {
-        print_affected(&self.metadata_set, &self.network_specs_set)
+    /// function to add entries depending on former verifier to the warning
+    pub(crate) fn affected(&self, message: LocalisedMessage) -> LocalisedMessage {
+        with_affected(message, &self.metadata_set, &self.network_specs_set)
     }
     /// function to find all entries in the database corresponding to given `verifier_key`, that was used to store the former verifier
-    pub(crate) fn get<P>(verifier_key: &VerifierKey, db_path: P) -> Result<Self>
+    pub(crate) fn get<S>(verifier_key: &VerifierKey, storage: &S) -> Result<Self>
     where
-        P: AsRef<Path>,
+        S: Storage,
     {
-        let database = open_db(&db_path)?;
-        let metadata = open_tree(&database, METATREE)?;
-        let chainspecs = open_tree(&database, SPECSTREE)?;
-        let (metadata_set, network_specs_set) = collect_set(verifier_key, &chainspecs, &metadata)?;
+        let (metadata_set, network_specs_set) = collect_set(verifier_key, storage)?;
         Ok(Self {
             metadata_set,
             network_specs_set,
         })
     }
-    pub(crate) fn upd_stub<P>(
+    pub(crate) fn upd_stub<S>(
         &self,
         stub: TrDbColdStub,
         verifier_key: &VerifierKey,
         former_verifier: &Verifier,
         new_verifier: &ValidCurrentVerifier,
         hold_release: HoldRelease,
-        db_path: P,
+        storage: &S,
     ) -> Result<TrDbColdStub>
     where
-        P: AsRef<Path>,
+        S: Storage,
     {
-        let general_verifier = get_general_verifier(&db_path)?;
+        enforce_policy_in(PolicyRule::VerifierChange, storage)?;
+        let general_verifier = get_general_verifier_in(storage)?;
         let mut out = stub;
         let warning = match hold_release {
             HoldRelease::General => Warning::VerifierChangingToGeneral {
                 verifier_key,
                 hold: self,
             }
-            .show(),
+            .message(),
             HoldRelease::Custom => Warning::VerifierChangingToCustom {
                 verifier_key,
                 hold: self,
             }
-            .show(),
+            .message(),
             HoldRelease::GeneralSuper => Warning::VerifierGeneralSuper {
                 verifier_key,
                 hold: self,
             }
-            .show(),
+            .message(),
         };
-        out = out.new_history_entry(Event::Warning { warning });
+        out = out.new_history_entry(Event::WarningMessage { message: warning });
         for x in self.metadata_set.iter() {
             out = out.remove_metadata(x)
         }
@@ -1,15 +1,21 @@
 #![deny(unused_crate_dependencies)]
 #![deny(rustdoc::broken_intra_doc_links)]
 
-use definitions::navigation::TransactionCardSet;
+use db_handling::storage::{SledStorage, Storage};
+use definitions::{error::TransferContent, navigation::TransactionCardSet};
 use std::path::Path;
 
 pub use definitions::navigation::{StubNav, TransactionAction};
+mod add_contacts;
+use add_contacts::add_contacts;
 mod add_specs;
 use add_specs::add_specs;
+mod amend_specs;
+use amend_specs::amend_specs;
 pub mod cards;
 use cards::Card;
 pub mod check_signature;
+use check_signature::compressed_content;
 mod derivations;
 use derivations::process_derivations;
 mod helpers;
@@ -21,8 +27,14 @@ use load_types::load_types;
 mod message;
 use message::process_message;
 mod parse_transaction;
-pub use parse_transaction::entry_to_transactions_with_decoding;
 use parse_transaction::parse_transaction;
+pub use parse_transaction::{
+    entry_to_transactions_with_decoding, entry_to_transactions_with_decoding_in,
+};
+mod rotate_verifier;
+use rotate_verifier::rotate_verifier;
+mod security_policy;
+use security_policy::security_policy;
 mod error;
 #[cfg(test)]
 mod tests;
@@ -35,9 +47,9 @@ pub use crate::error::{Error, Result};
 /// - actual content (differs between transaction types, could be even empty)
 /// actual content is handled individually depending on prelude
 
-fn handle_scanner_input<P>(payload: &str, db_path: P) -> Result<TransactionAction>
+fn handle_scanner_input<S>(payload: &str, storage: &S) -> Result<TransactionAction>
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
     let data_hex = {
         if let Some(a) = payload.strip_prefix("0x") {
@@ -56,12 +68,25 @@ where
     }
 
     match &data_hex[4..6] {
-        "00" | "02" => parse_transaction(data_hex, db_path),
-        "03" => process_message(data_hex, db_path),
-        "80" => load_metadata(data_hex, db_path),
-        "81" => load_types(data_hex, db_path),
-        "c1" => add_specs(data_hex, db_path),
-        "de" => process_derivations(data_hex, db_path),
+        "00" | "02" | "06" => parse_transaction(data_hex, storage),
+        "03" => process_message(data_hex, storage),
+        "80" => load_metadata(data_hex, storage),
+        "81" => load_types(data_hex, storage),
+        "82" => match compressed_content(data_hex)? {
+            TransferContent::AddSpecs => add_specs(data_hex, storage),
+            TransferContent::LoadMeta => load_metadata(data_hex, storage),
+            TransferContent::LoadTypes => load_types(data_hex, storage),
+            TransferContent::AddContacts => add_contacts(data_hex, storage),
+            TransferContent::RotateVerifier => rotate_verifier(data_hex, storage),
+            TransferContent::SecurityPolicy => security_policy(data_hex, storage),
+            TransferContent::AmendSpecs => amend_specs(data_hex, storage),
+        },
+        "c1" => add_specs(data_hex, storage),
+        "c2" => add_contacts(data_hex, storage),
+        "c3" => rotate_verifier(data_hex, storage),
+        "c4" => security_policy(data_hex, storage),
+        "c5" => amend_specs(data_hex, storage),
+        "de" => process_derivations(data_hex, storage),
         _ => Err(Error::PayloadNotSupported(data_hex[4..6].to_string())),
     }
 }
@@ -70,13 +95,28 @@ pub fn produce_output<P>(payload: &str, dbname: P) -> TransactionAction
 where
     P: AsRef<Path>,
 {
-    match handle_scanner_input(payload, dbname) {
+    match SledStorage::open(dbname) {
+        Ok(storage) => produce_output_in(payload, &storage),
+        Err(e) => error_output(e.into()),
+    }
+}
+
+/// Same as [`produce_output`], for the database in given [`Storage`].
+pub fn produce_output_in<S>(payload: &str, storage: &S) -> TransactionAction
+where
+    S: Storage,
+{
+    match handle_scanner_input(payload, storage) {
         Ok(out) => out,
-        Err(e) => TransactionAction::Read {
-            r: TransactionCardSet {
-                error: Some(vec![Card::Error(e).card(&mut 0, 0)]),
-                ..Default::default()
-            },
+        Err(e) => error_output(e),
+    }
+}
+
+fn error_output(e: Error) -> TransactionAction {
+    TransactionAction::Read {
+        r: TransactionCardSet {
+            error: Some(vec![Card::Error(e).card(&mut 0, 0)]),
+            ..Default::default()
         },
     }
 }
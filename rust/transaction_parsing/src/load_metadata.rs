@@ -1,8 +1,10 @@
 use db_handling::{
     db_transactions::TrDbColdStub,
     helpers::{
-        genesis_hash_in_specs, get_general_verifier, open_db, try_get_valid_current_verifier,
+        enforce_policy_in, genesis_hash_in_specs, get_general_verifier_in,
+        try_get_valid_current_verifier_in,
     },
+    storage::Storage,
 };
 use definitions::{
     error::TransferContent,
@@ -12,9 +14,9 @@ use definitions::{
     metadata::MetaValues,
     navigation::{TransactionCard, TransactionCardSet},
     network_specs::{ValidCurrentVerifier, Verifier},
+    policy::PolicyRule,
     qr_transfers::ContentLoadMeta,
 };
-use std::path::Path;
 
 use crate::cards::{Card, Warning};
 use crate::check_signature::pass_crypto;
@@ -27,27 +29,29 @@ enum FirstCard {
     VerifierCard(TransactionCard),
 }
 
-pub fn load_metadata<P>(data_hex: &str, db_path: P) -> Result<TransactionAction>
+pub fn load_metadata<S>(data_hex: &str, storage: &S) -> Result<TransactionAction>
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
     let checked_info = pass_crypto(data_hex, TransferContent::LoadMeta)?;
+    if checked_info.verifier.v.is_none() {
+        enforce_policy_in(PolicyRule::UnsignedUpdate, storage)?;
+    }
     let (meta, genesis_hash) = ContentLoadMeta::from_slice(&checked_info.message).meta_genhash()?;
     let meta_values = MetaValues::from_slice_metadata(&meta)?;
-    let general_verifier = get_general_verifier(&db_path)?;
+    let general_verifier = get_general_verifier_in(storage)?;
     let verifier_key = VerifierKey::from_parts(genesis_hash);
-    let valid_current_verifier = try_get_valid_current_verifier(&verifier_key, &db_path)?.ok_or(
+    let valid_current_verifier = try_get_valid_current_verifier_in(&verifier_key, storage)?.ok_or(
         Error::LoadMetaUnknownNetwork {
             name: meta_values.name.clone(),
         },
     )?;
-    let specs_invariants = genesis_hash_in_specs(genesis_hash, &open_db(&db_path)?)?.ok_or(
-        Error::LoadMetaNoSpecs {
+    let specs_invariants =
+        genesis_hash_in_specs(genesis_hash, storage)?.ok_or(Error::LoadMetaNoSpecs {
             name: meta_values.name.clone(),
             valid_current_verifier: valid_current_verifier.clone(),
             general_verifier: general_verifier.clone(),
-        },
-    )?;
+        })?;
     if meta_values.name != specs_invariants.name {
         return Err(Error::LoadMetaWrongGenesisHash {
             name_metadata: meta_values.name,
@@ -70,8 +74,8 @@ where
     let mut index = 0;
     let optional_ext_warning = {
         if meta_values.warn_incomplete_extensions {
-            stub = stub.new_history_entry(Event::Warning {
-                warning: Warning::MetadataExtensionsIncomplete.show(),
+            stub = stub.new_history_entry(Event::WarningMessage {
+                message: Warning::MetadataExtensionsIncomplete.message(),
             });
             Some(Card::Warning(Warning::MetadataExtensionsIncomplete).card(&mut index, 0))
         } else {
@@ -81,8 +85,8 @@ where
 
     let first_card = match checked_info.verifier {
         Verifier { v: None } => {
-            stub = stub.new_history_entry(Event::Warning {
-                warning: Warning::NotVerified.show(),
+            stub = stub.new_history_entry(Event::WarningMessage {
+                message: Warning::NotVerified.message(),
             });
             match valid_current_verifier {
                 ValidCurrentVerifier::Custom {
@@ -165,9 +169,9 @@ where
             FirstCard::VerifierCard(Card::Verifier(new_verifier_value).card(&mut index, 0))
         }
     };
-    if accept_meta_values(&meta_values, &db_path)? {
+    if accept_meta_values(&meta_values, storage)? {
         stub = stub.add_metadata(&meta_values);
-        let checksum = stub.store_and_get_checksum(&db_path)?;
+        let checksum = stub.store_and_get_checksum_in(storage)?;
         let meta_display = MetaValuesDisplay::get(&meta_values);
         let meta_card = Card::Meta(meta_display).card(&mut index, 0);
         match first_card {
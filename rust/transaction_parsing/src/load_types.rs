@@ -1,13 +1,13 @@
 use db_handling::{
     db_transactions::TrDbColdStub,
-    helpers::{get_general_verifier, try_get_types},
+    helpers::{enforce_policy_in, get_general_verifier_in, try_get_types_in},
+    storage::Storage,
 };
 use definitions::{
     error::TransferContent, error_signer::GeneralVerifierForContent, history::Event,
-    navigation::TransactionCardSet, network_specs::Verifier, qr_transfers::ContentLoadTypes,
-    types::TypeEntry,
+    navigation::TransactionCardSet, network_specs::Verifier, policy::PolicyRule,
+    qr_transfers::ContentLoadTypes, types::TypeEntry,
 };
-use std::path::Path;
 
 use crate::cards::{Card, Warning};
 use crate::check_signature::pass_crypto;
@@ -15,15 +15,18 @@ use crate::error::{Error, Result};
 use crate::holds::GeneralHold;
 use crate::{StubNav, TransactionAction};
 
-pub fn load_types<P>(data_hex: &str, db_path: P) -> Result<TransactionAction>
+pub fn load_types<S>(data_hex: &str, storage: &S) -> Result<TransactionAction>
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
     let checked_info = pass_crypto(data_hex, TransferContent::LoadTypes)?;
+    if checked_info.verifier.v.is_none() {
+        enforce_policy_in(PolicyRule::UnsignedUpdate, storage)?;
+    }
     let content_new_types = ContentLoadTypes::from_slice(&checked_info.message);
     let new_types = content_new_types.types()?;
-    let old_types: Vec<TypeEntry> = try_get_types(&db_path)?.unwrap_or_default();
-    let general_verifier = get_general_verifier(&db_path)?;
+    let old_types: Vec<TypeEntry> = try_get_types_in(storage)?.unwrap_or_default();
+    let general_verifier = get_general_verifier_in(storage)?;
     let mut stub = TrDbColdStub::new();
     let mut index = 0;
     match checked_info.verifier {
@@ -32,14 +35,14 @@ where
                 if new_types == old_types {
                     Err(Error::TypesKnown)
                 } else {
-                    stub = stub.new_history_entry(Event::Warning {
-                        warning: Warning::TypesNotVerified.show(),
+                    stub = stub.new_history_entry(Event::WarningMessage {
+                        message: Warning::TypesNotVerified.message(),
                     });
-                    stub = stub.new_history_entry(Event::Warning {
-                        warning: Warning::UpdatingTypes.show(),
+                    stub = stub.new_history_entry(Event::WarningMessage {
+                        message: Warning::UpdatingTypes.message(),
                     });
                     stub = stub.add_types(&content_new_types, &checked_info.verifier);
-                    let checksum = stub.store_and_get_checksum(&db_path)?;
+                    let checksum = stub.store_and_get_checksum_in(storage)?;
                     let warning_card_1 =
                         Card::Warning(Warning::TypesNotVerified).card(&mut index, 0);
                     let warning_card_2 = Card::Warning(Warning::UpdatingTypes).card(&mut index, 0);
@@ -70,11 +73,11 @@ where
                 if new_types == old_types {
                     Err(Error::TypesKnown)
                 } else {
-                    stub = stub.new_history_entry(Event::Warning {
-                        warning: Warning::UpdatingTypes.show(),
+                    stub = stub.new_history_entry(Event::WarningMessage {
+                        message: Warning::UpdatingTypes.message(),
                     });
                     stub = stub.add_types(&content_new_types, &checked_info.verifier);
-                    let checksum = stub.store_and_get_checksum(&db_path)?;
+                    let checksum = stub.store_and_get_checksum_in(storage)?;
                     let warning_card = Card::Warning(Warning::UpdatingTypes).card(&mut index, 0);
                     let types_card = Card::TypesInfo(content_new_types).card(&mut index, 0);
                     Ok(TransactionAction::Stub {
@@ -92,27 +95,27 @@ where
                 match general_verifier {
                     Verifier { v: None } => {
                         let new_general_verifier = checked_info.verifier;
-                        let general_hold = GeneralHold::get(&db_path)?;
-                        stub = general_hold.upd_stub(stub, &new_general_verifier, &db_path)?;
+                        let general_hold = GeneralHold::get(storage)?;
+                        stub = general_hold.upd_stub(stub, &new_general_verifier, storage)?;
                         stub = stub.add_types(&content_new_types, &new_general_verifier);
                         let warning_card_1 =
                             Card::Warning(Warning::GeneralVerifierAppeared(&general_hold))
                                 .card(&mut index, 0);
                         let warning_card_2 = {
                             if new_types == old_types {
-                                stub = stub.new_history_entry(Event::Warning {
-                                    warning: Warning::TypesAlreadyThere.show(),
+                                stub = stub.new_history_entry(Event::WarningMessage {
+                                    message: Warning::TypesAlreadyThere.message(),
                                 });
                                 Card::Warning(Warning::TypesAlreadyThere).card(&mut index, 0)
                             } else {
-                                stub = stub.new_history_entry(Event::Warning {
-                                    warning: Warning::UpdatingTypes.show(),
+                                stub = stub.new_history_entry(Event::WarningMessage {
+                                    message: Warning::UpdatingTypes.message(),
                                 });
                                 Card::Warning(Warning::UpdatingTypes).card(&mut index, 0)
                             }
                         };
                         let types_card = Card::TypesInfo(content_new_types).card(&mut index, 0);
-                        let checksum = stub.store_and_get_checksum(&db_path)?;
+                        let checksum = stub.store_and_get_checksum_in(storage)?;
                         Ok(TransactionAction::Stub {
                             s: TransactionCardSet {
                                 verifier: Some(vec![verifier_card]),
@@ -1,6 +1,7 @@
 use db_handling::{
     db_transactions::{SignContent, TrDbColdSign},
-    helpers::{try_get_address_details, try_get_network_specs},
+    helpers::{try_get_address_details_in, try_get_key_annotation_in, try_get_network_specs_in},
+    storage::Storage,
 };
 use definitions::{
     keyring::{AddressKey, NetworkSpecsKey},
@@ -8,16 +9,15 @@ use definitions::{
 };
 use parity_scale_codec::DecodeAll;
 use parser::cards::ParserCard;
-use std::path::Path;
 
 use crate::cards::{make_author_info, Card, Warning};
 use crate::error::{Error, Result};
 use crate::helpers::multisigner_msg_genesis_encryption;
 use crate::TransactionAction;
 
-pub fn process_message<P>(data_hex: &str, db_path: P) -> Result<TransactionAction>
+pub fn process_message<S>(data_hex: &str, storage: &S) -> Result<TransactionAction>
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
     let (author_multi_signer, message_vec, genesis_hash, encryption) =
         multisigner_msg_genesis_encryption(data_hex)?;
@@ -33,10 +33,11 @@ where
     let mut index: u32 = 0;
     let indent: u32 = 0;
 
-    match try_get_network_specs(&db_path, &network_specs_key)? {
+    match try_get_network_specs_in(storage, &network_specs_key)? {
         Some(network_specs) => {
             let address_key = AddressKey::from_multisigner(&author_multi_signer);
-            match try_get_address_details(&db_path, &address_key)? {
+            let annotation = try_get_key_annotation_in(storage, &address_key)?;
+            match try_get_address_details_in(storage, &address_key)? {
                 Some(address_details) => {
                     if address_details.network_id.contains(&network_specs_key) {
                         let message_card = Card::ParserCard(&ParserCard::Text(message.to_string()))
@@ -44,16 +45,18 @@ where
                         let sign = TrDbColdSign::generate(
                             SignContent::Message(message),
                             &network_specs.name,
+                            &network_specs_key,
                             &address_details.path,
                             address_details.has_pwd,
                             &author_multi_signer,
                             Vec::new(),
                         );
-                        let checksum = sign.store_and_get_checksum(&db_path)?;
+                        let checksum = sign.store_and_get_checksum_in(storage)?;
                         let author_info = make_author_info(
                             &author_multi_signer,
                             network_specs.base58prefix,
                             &address_details,
+                            annotation.as_ref(),
                         );
                         let network_info = network_specs;
                         Ok(TransactionAction::Sign {
@@ -71,6 +74,7 @@ where
                             author: &author_multi_signer,
                             base58prefix: network_specs.base58prefix,
                             address_details: &address_details,
+                            annotation: annotation.as_ref(),
                         }
                         .card(&mut index, indent);
                         let warning_card =
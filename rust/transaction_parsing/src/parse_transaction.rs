@@ -1,28 +1,40 @@
 use db_handling::{
     db_transactions::{SignContent, TrDbColdSign},
-    helpers::{try_get_address_details, try_get_network_specs},
+    helpers::{
+        enforce_policy_in, try_get_address_details_in, try_get_key_annotation_in,
+        try_get_network_specs_in,
+    },
+    storage::{SledStorage, Storage},
 };
 use definitions::{
     history::{Entry, Event, SignDisplay},
     keyring::{AddressKey, NetworkSpecsKey},
+    metadata_proof::MetadataProof,
     navigation::{MEventMaybeDecoded, TransactionCard, TransactionCardSet},
     network_specs::VerifierValue,
+    policy::PolicyRule,
     users::AddressDetails,
 };
-use parser::{cut_method_extensions, decoding_commons::OutputCard, parse_extensions, parse_method};
+use parity_scale_codec::Decode;
+use parser::{
+    cards::ParserCard, cut_method_extensions, decoding_commons::OutputCard, parse_extensions,
+    parse_method, MetadataBundle,
+};
 use std::path::Path;
 
 use crate::cards::{make_author_info, Card, Warning};
 use crate::error::{Error, Result};
 use crate::helpers::{
-    bundle_from_meta_set_element, find_meta_set, multisigner_msg_genesis_encryption, specs_by_name,
+    bundle_from_meta_set_element, check_proof_specs, find_meta_set,
+    multisigner_msg_genesis_encryption, proof_extensions_metadata, specs_by_name, KnownContacts,
 };
 use crate::TransactionAction;
 
 /// Transaction payload in hex format as it arrives into parsing program contains following elements:
-/// - prelude, length 6 symbols ("53" stands for substrate, ** - crypto type, 00 or 02 - transaction type),
+/// - prelude, length 6 symbols ("53" stands for substrate, ** - crypto type, 00, 02 or 06 - transaction type),
 /// see the standard for details,
 /// - author public key (length depends on cryptography used),
+/// - for transaction type 06 only, SCALE-encoded metadata proof,
 /// - method, extensions, network genesis hash
 
 /// Enum to move around cards in preparatory stage (author details or author card, and warning card)
@@ -40,9 +52,9 @@ enum CardsPrep<'a> {
 /// i.e. it starts with 53****, followed by author address, followed by actual transaction piece,
 /// followed by extrinsics, concluded with chain genesis hash
 
-pub(crate) fn parse_transaction<P>(data_hex: &str, db_path: P) -> Result<TransactionAction>
+pub(crate) fn parse_transaction<S>(data_hex: &str, storage: &S) -> Result<TransactionAction>
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
     let (author_multi_signer, parser_data, genesis_hash, encryption) =
         multisigner_msg_genesis_encryption(data_hex)?;
@@ -59,12 +71,13 @@ where
     let mut index: u32 = 0;
     let indent: u32 = 0;
 
-    match try_get_network_specs(&db_path, &network_specs_key)? {
+    match try_get_network_specs_in(storage, &network_specs_key)? {
         Some(network_specs) => {
             let address_key = AddressKey::from_multisigner(&author_multi_signer);
+            let annotation = try_get_key_annotation_in(storage, &address_key)?;
             let mut history: Vec<Event> = Vec::new();
 
-            let mut cards_prep = match try_get_address_details(&db_path, &address_key)? {
+            let mut cards_prep = match try_get_address_details_in(storage, &address_key)? {
                 Some(address_details) => {
                     if address_details.network_id.contains(&network_specs_key) {
                         CardsPrep::SignProceed(address_details, None)
@@ -73,6 +86,7 @@ where
                             author: &author_multi_signer,
                             base58prefix: network_specs.base58prefix,
                             address_details: &address_details,
+                            annotation: annotation.as_ref(),
                         })
                         .card(&mut index, indent);
                         CardsPrep::ShowOnly(
@@ -92,37 +106,79 @@ where
             };
 
             let short_specs = network_specs.short();
+
+            // call in transaction with metadata proof is decoded only with the
+            // metadata restored from the proof, extensions are decoded with
+            // the network metadata from the database
+            let (metadata_proof, parser_data) = match &data_hex[4..6] {
+                "06" => {
+                    let mut data = &parser_data[..];
+                    let metadata_proof = MetadataProof::decode(&mut data)?;
+                    check_proof_specs(&metadata_proof.extra, &network_specs)?;
+                    (Some(metadata_proof), data.to_vec())
+                }
+                _ => (None, parser_data),
+            };
             let (method_data, extensions_data) = match cut_method_extensions(&parser_data) {
                 Ok(a) => a,
                 Err(_) => return Err(Error::SeparateMethodExtensions),
             };
 
-            let meta_set = find_meta_set(&short_specs, &db_path)?;
-            if meta_set.is_empty() {
+            let meta_set = find_meta_set(&short_specs, storage)?;
+            let proof_meta_v14 = match metadata_proof {
+                Some(ref a) => Some((
+                    a.runtime_metadata()?,
+                    proof_extensions_metadata(&meta_set, a, &network_specs.name)?,
+                    a.extra.spec_version,
+                    a.digest()?,
+                )),
+                None => None,
+            };
+            let metadata_bundles = match proof_meta_v14 {
+                Some((ref meta_v14, extensions_meta_v14, network_version, metadata_hash)) => {
+                    vec![MetadataBundle::Proof {
+                        meta_v14,
+                        extensions_meta_v14,
+                        network_version,
+                        metadata_hash,
+                    }]
+                }
+                None => meta_set
+                    .iter()
+                    .map(|x| bundle_from_meta_set_element(x, storage))
+                    .collect::<Result<Vec<MetadataBundle>>>()?,
+            };
+            if metadata_bundles.is_empty() {
                 return Err(Error::NoMetadata {
                     name: network_specs.name,
                 });
             }
+            let contacts = KnownContacts::get(storage)?;
             let mut found_solution = None;
             let mut error_collection = Vec::new();
-            let latest_version = meta_set[0].version();
-            for (i, x) in meta_set.iter().enumerate() {
-                let used_version = x.version();
-                let metadata_bundle = bundle_from_meta_set_element(x, &db_path)?;
+            let latest_version = metadata_bundles[0].network_version();
+            for (i, metadata_bundle) in metadata_bundles.iter().enumerate() {
+                let used_version = metadata_bundle.network_version();
                 match parse_extensions(
                     extensions_data.to_vec(),
-                    &metadata_bundle,
+                    metadata_bundle,
                     &short_specs,
                     optional_mortal_flag,
                 ) {
                     Ok(extensions_cards) => {
                         if i > 0 {
-                            history.push(Event::Warning {
-                                warning: Warning::NewerVersion {
+                            // signing with outdated metadata could be
+                            // forbidden by the security policy; transactions
+                            // that could not be signed anyway are shown as is
+                            if matches!(cards_prep, CardsPrep::SignProceed(..)) {
+                                enforce_policy_in(PolicyRule::OutdatedMetadata, storage)?;
+                            }
+                            history.push(Event::WarningMessage {
+                                message: Warning::NewerVersion {
                                     used_version,
                                     latest_version,
                                 }
-                                .show(),
+                                .message(),
                             });
                             cards_prep = match cards_prep {
                                 CardsPrep::SignProceed(address_details, _) => {
@@ -149,32 +205,57 @@ where
                                 }
                             };
                         }
-                        match parse_method(method_data.to_vec(), &metadata_bundle, &short_specs) {
+                        match parse_method(method_data.to_vec(), metadata_bundle, &short_specs) {
                             Ok(a) => {
+                                let lookalikes = contacts.lookalikes(&a);
                                 found_solution = match cards_prep {
                                     CardsPrep::SignProceed(address_details, possible_warning) => {
+                                        let nonce = find_nonce(&extensions_cards);
+                                        let replay = replay_warning(
+                                            &address_details,
+                                            &network_specs_key,
+                                            nonce,
+                                        );
+                                        for w in replay.iter().chain(lookalikes.iter()) {
+                                            history.push(Event::WarningMessage {
+                                                message: w.message(),
+                                            });
+                                        }
                                         let sign = TrDbColdSign::generate(
                                             SignContent::Transaction {
                                                 method: method_data,
                                                 extensions: extensions_data,
+                                                nonce,
+                                                metadata_proof: metadata_proof.to_owned(),
                                             },
                                             &network_specs.name,
+                                            &network_specs_key,
                                             &address_details.path,
                                             address_details.has_pwd,
                                             &author_multi_signer,
                                             history,
                                         );
-                                        let checksum = sign.store_and_get_checksum(&db_path)?;
+                                        let checksum = sign.store_and_get_checksum_in(storage)?;
                                         let author_info = make_author_info(
                                             &author_multi_signer,
                                             network_specs.base58prefix,
                                             &address_details,
+                                            annotation.as_ref(),
                                         );
-                                        let warning = possible_warning
+                                        let warning: Vec<TransactionCard> = possible_warning
+                                            .into_iter()
+                                            .chain(replay)
+                                            .chain(lookalikes)
                                             .map(|w| Card::Warning(w).card(&mut index, indent))
-                                            .map(|w| vec![w]);
-                                        let method = into_cards(&a, &mut index);
-                                        let extensions = into_cards(&extensions_cards, &mut index);
+                                            .collect();
+                                        let method = into_cards(&a, &contacts, &mut index);
+                                        let extensions =
+                                            into_cards(&extensions_cards, &contacts, &mut index);
+                                        let warning = if warning.is_empty() {
+                                            None
+                                        } else {
+                                            Some(warning)
+                                        };
                                         let content = TransactionCardSet {
                                             warning,
                                             method: Some(method),
@@ -191,10 +272,17 @@ where
                                     }
                                     CardsPrep::ShowOnly(author_card, warning_card) => {
                                         let author = Some(vec![author_card]);
-                                        let warning = Some(vec![*warning_card]);
-                                        let method = Some(into_cards(&a, &mut index));
-                                        let extensions =
-                                            Some(into_cards(&extensions_cards, &mut index));
+                                        let mut warning = vec![*warning_card];
+                                        for w in lookalikes.into_iter() {
+                                            warning.push(Card::Warning(w).card(&mut index, indent));
+                                        }
+                                        let warning = Some(warning);
+                                        let method = Some(into_cards(&a, &contacts, &mut index));
+                                        let extensions = Some(into_cards(
+                                            &extensions_cards,
+                                            &contacts,
+                                            &mut index,
+                                        ));
                                         let r = TransactionCardSet {
                                             author,
                                             warning,
@@ -216,10 +304,12 @@ where
                                             author: &author_multi_signer,
                                             base58prefix: network_specs.base58prefix,
                                             address_details: &address_details,
+                                            annotation: annotation.as_ref(),
                                         }
                                         .card(&mut index, indent);
                                         let error = Card::Error(e.into()).card(&mut index, indent);
-                                        let extensions = into_cards(&extensions_cards, &mut index);
+                                        let extensions =
+                                            into_cards(&extensions_cards, &contacts, &mut index);
                                         let r = TransactionCardSet {
                                             author: Some(vec![author]),
                                             error: Some(vec![error]),
@@ -235,8 +325,11 @@ where
                                         let error = Some(vec![
                                             Card::Error(e.into()).card(&mut index, indent)
                                         ]);
-                                        let extensions =
-                                            Some(into_cards(&extensions_cards, &mut index));
+                                        let extensions = Some(into_cards(
+                                            &extensions_cards,
+                                            &contacts,
+                                            &mut index,
+                                        ));
                                         let r = TransactionCardSet {
                                             author,
                                             warning,
@@ -284,9 +377,47 @@ where
     }
 }
 
-fn into_cards(set: &[OutputCard], index: &mut u32) -> Vec<TransactionCard> {
+/// Find transaction nonce in parsed extensions cards.
+fn find_nonce(extensions_cards: &[OutputCard]) -> Option<u64> {
+    extensions_cards.iter().find_map(|card| match &card.card {
+        ParserCard::Nonce(nonce) => nonce.parse().ok(),
+        _ => None,
+    })
+}
+
+/// Make replay warning if the address key has already signed in this network
+/// a transaction with same or higher nonce.
+fn replay_warning<'a>(
+    address_details: &AddressDetails,
+    network_specs_key: &NetworkSpecsKey,
+    nonce: Option<u64>,
+) -> Option<Warning<'a>> {
+    let nonce = nonce?;
+    let last_nonce = address_details.usage(network_specs_key)?.last_nonce?;
+    if nonce <= last_nonce {
+        Some(Warning::NonceReplay { nonce, last_nonce })
+    } else {
+        None
+    }
+}
+
+/// Transform parsed cards into transaction cards, with addresses of known
+/// contacts annotated with contact names.
+fn into_cards(
+    set: &[OutputCard],
+    contacts: &KnownContacts,
+    index: &mut u32,
+) -> Vec<TransactionCard> {
     set.iter()
-        .map(|card| Card::ParserCard(&card.card).card(index, card.indent))
+        .map(|card| match &card.card {
+            ParserCard::Id { id, base58prefix } => Card::Id {
+                id,
+                base58prefix: *base58prefix,
+                contact_name: contacts.name(id),
+            }
+            .card(index, card.indent),
+            _ => Card::ParserCard(&card.card).card(index, card.indent),
+        })
         .collect()
 }
 
@@ -296,11 +427,24 @@ pub fn entry_to_transactions_with_decoding<P>(
 ) -> Result<Vec<MEventMaybeDecoded>>
 where
     P: AsRef<Path>,
+{
+    entry_to_transactions_with_decoding_in(entry, &SledStorage::open(db_path)?)
+}
+
+/// Same as [`entry_to_transactions_with_decoding`], for the database in given
+/// [`Storage`].
+pub fn entry_to_transactions_with_decoding_in<S>(
+    entry: Entry,
+    storage: &S,
+) -> Result<Vec<MEventMaybeDecoded>>
+where
+    S: Storage,
 {
     let mut res = Vec::new();
+    let metadata_proofs = entry.metadata_proofs;
 
     // TODO: insanely bad code.
-    for event in entry.events.into_iter() {
+    for (event_index, event) in entry.events.into_iter().enumerate() {
         let (verifier_details, signed_by, decoded) = match event {
             Event::TransactionSigned { ref sign_display }
             | Event::TransactionSignError { ref sign_display } => {
@@ -308,10 +452,10 @@ where
                 let address_key = AddressKey::from_multisigner(m);
                 let verifier_details = Some(sign_display.signed_by.show_card());
 
-                if let Some(address_details) = try_get_address_details(&db_path, &address_key)? {
+                if let Some(address_details) = try_get_address_details_in(storage, &address_key)? {
                     let mut specs_found = None;
                     for id in &address_details.network_id {
-                        let specs = try_get_network_specs(&db_path, id)?;
+                        let specs = try_get_network_specs_in(storage, id)?;
                         if let Some(specs) = specs {
                             if specs.name == sign_display.network_name {
                                 specs_found = Some(specs);
@@ -320,14 +464,23 @@ where
                     }
 
                     if let Some(specs_found) = specs_found {
+                        let annotation = try_get_key_annotation_in(storage, &address_key)?;
                         (
                             verifier_details,
                             Some(make_author_info(
                                 m,
                                 specs_found.base58prefix,
                                 &address_details,
+                                annotation.as_ref(),
                             )),
-                            Some(decode_signable_from_history(sign_display, &db_path)?),
+                            Some(decode_signable_from_history(
+                                sign_display,
+                                metadata_proofs
+                                    .iter()
+                                    .find(|x| x.event_index as usize == event_index)
+                                    .map(|x| &x.metadata_proof),
+                                storage,
+                            )?),
                         )
                     } else {
                         (verifier_details, None, None)
@@ -349,43 +502,73 @@ where
     Ok(res)
 }
 
-pub(crate) fn decode_signable_from_history<P>(
+/// Decode transaction from the history log.
+///
+/// Call in transaction signed with metadata proof is decoded only with the
+/// metadata restored from the proof stored in the history log, its extensions
+/// and other transactions are decoded with the network metadata in the
+/// database.
+pub(crate) fn decode_signable_from_history<S>(
     found_signable: &SignDisplay,
-    db_path: P,
+    metadata_proof: Option<&MetadataProof>,
+    storage: &S,
 ) -> Result<TransactionCardSet>
 where
-    P: AsRef<Path>,
+    S: Storage,
 {
     let (parser_data, network_name, encryption) = found_signable.transaction_network_encryption();
 
-    let short_specs = specs_by_name(&network_name, &encryption, &db_path)?.short();
-    let meta_set = find_meta_set(&short_specs, &db_path)?;
-    if meta_set.is_empty() {
+    let short_specs = specs_by_name(&network_name, &encryption, storage)?.short();
+    let meta_set = find_meta_set(&short_specs, storage)?;
+    let proof_meta_v14 = match metadata_proof {
+        Some(a) => Some((
+            a.runtime_metadata()?,
+            proof_extensions_metadata(&meta_set, a, &network_name)?,
+            a.extra.spec_version,
+            a.digest()?,
+        )),
+        None => None,
+    };
+    let metadata_bundles = match proof_meta_v14 {
+        Some((ref meta_v14, extensions_meta_v14, network_version, metadata_hash)) => {
+            vec![MetadataBundle::Proof {
+                meta_v14,
+                extensions_meta_v14,
+                network_version,
+                metadata_hash,
+            }]
+        }
+        None => meta_set
+            .iter()
+            .map(|x| bundle_from_meta_set_element(x, storage))
+            .collect::<Result<Vec<MetadataBundle>>>()?,
+    };
+    if metadata_bundles.is_empty() {
         return Err(Error::HistoricalMetadata { name: network_name });
     }
 
     let (method_data, extensions_data) = cut_method_extensions(&parser_data)?;
+    let contacts = KnownContacts::get(storage)?;
 
     let mut found_solution = None;
     let mut error_collection = Vec::new();
     let mut index = 0;
     let indent = 0;
 
-    for x in meta_set.iter() {
-        let used_version = x.version();
-        let metadata_bundle = bundle_from_meta_set_element(x, &db_path)?;
+    for metadata_bundle in metadata_bundles.iter() {
+        let used_version = metadata_bundle.network_version();
 
         match parse_extensions(
             extensions_data.to_vec(),
-            &metadata_bundle,
+            metadata_bundle,
             &short_specs,
             None,
         ) {
             Ok(extensions_cards) => {
-                match parse_method(method_data, &metadata_bundle, &short_specs) {
+                match parse_method(method_data, metadata_bundle, &short_specs) {
                     Ok(a) => {
-                        let method = into_cards(&a, &mut index);
-                        let extensions = into_cards(&extensions_cards, &mut index);
+                        let method = into_cards(&a, &contacts, &mut index);
+                        let extensions = into_cards(&extensions_cards, &contacts, &mut index);
                         found_solution = Some(TransactionCardSet {
                             method: Some(method),
                             extensions: Some(extensions),
@@ -394,7 +577,7 @@ where
                     }
                     Err(e) => {
                         let error = Card::Error(e.into()).card(&mut index, indent);
-                        let extensions = Some(into_cards(&extensions_cards, &mut index));
+                        let extensions = Some(into_cards(&extensions_cards, &contacts, &mut index));
                         found_solution = Some(TransactionCardSet {
                             error: Some(vec![error]),
                             extensions,
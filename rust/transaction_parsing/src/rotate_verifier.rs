@@ -0,0 +1,126 @@
+use db_handling::{
+    db_transactions::TrDbColdStub,
+    helpers::{
+        enforce_policy_in, genesis_hash_in_specs, get_general_verifier_in,
+        get_verifier_rotation_index_in, try_get_valid_current_verifier_in,
+    },
+    storage::Storage,
+};
+use definitions::{
+    error::TransferContent,
+    keyring::VerifierKey,
+    navigation::TransactionCardSet,
+    network_specs::{ValidCurrentVerifier, Verifier},
+    policy::PolicyRule,
+    qr_transfers::{ContentRotateVerifier, VerifierRotation},
+};
+
+use crate::cards::{Card, Warning};
+use crate::check_signature::pass_crypto;
+use crate::error::{Error, Result};
+use crate::{StubNav, TransactionAction};
+
+pub fn rotate_verifier<S>(data_hex: &str, storage: &S) -> Result<TransactionAction>
+where
+    S: Storage,
+{
+    let checked_info = pass_crypto(data_hex, TransferContent::RotateVerifier)?;
+    let VerifierRotation {
+        genesis_hash,
+        old_verifier_value: outgoing_verifier_value,
+        new_verifier_value,
+        rotation_index,
+    } = ContentRotateVerifier::from_slice(&checked_info.message).verifier_rotation()?;
+    let verifier_key = VerifierKey::from_parts(genesis_hash);
+    let valid_current_verifier = try_get_valid_current_verifier_in(&verifier_key, storage)?
+        .ok_or(Error::RotateVerifierUnknownNetwork { genesis_hash })?;
+    let specs_invariants = genesis_hash_in_specs(genesis_hash, storage)?
+        .ok_or(Error::RotateVerifierUnknownNetwork { genesis_hash })?;
+    let name = specs_invariants.name;
+
+    let old_verifier_value = match valid_current_verifier {
+        ValidCurrentVerifier::Custom {
+            v: Verifier {
+                v: Some(old_verifier_value),
+            },
+        } => old_verifier_value,
+        _ => return Err(Error::RotateVerifierNotCustom { name }),
+    };
+
+    // rotation must be signed by the current network verifier; rotations
+    // signed by any other key, including the verifiers that were rotated out
+    // or were never set up in this Signer, are not accepted
+    match checked_info.verifier {
+        Verifier { v: None } => {
+            return Err(Error::NeedVerifier {
+                name,
+                verifier_value: old_verifier_value,
+            })
+        }
+        Verifier {
+            v: Some(received_verifier_value),
+        } => {
+            if received_verifier_value != old_verifier_value {
+                return Err(Error::RotateVerifierNotEndorsed {
+                    name,
+                    current_verifier_value: old_verifier_value,
+                    received_verifier_value,
+                });
+            }
+        }
+    }
+    // rotation must name the current verifier as the outgoing one and follow
+    // the last accepted rotation, so that old rotations could not be replayed
+    // after the verifier was rotated back
+    if outgoing_verifier_value != old_verifier_value {
+        return Err(Error::RotateVerifierOutdated {
+            name,
+            current_verifier_value: old_verifier_value,
+            outgoing_verifier_value,
+        });
+    }
+    let expected_rotation_index = get_verifier_rotation_index_in(&verifier_key, storage)? + 1;
+    if rotation_index != expected_rotation_index {
+        return Err(Error::RotateVerifierOutOfOrder {
+            name,
+            expected_rotation_index,
+            received_rotation_index: rotation_index,
+        });
+    }
+    if new_verifier_value == old_verifier_value {
+        return Err(Error::RotateVerifierSame { name });
+    }
+    if get_general_verifier_in(storage)?.v.as_ref() == Some(&new_verifier_value) {
+        return Err(Error::RotateVerifierToGeneral { name });
+    }
+    enforce_policy_in(PolicyRule::VerifierChange, storage)?;
+
+    let mut index = 0;
+    let warning_card = Card::Warning(Warning::VerifierRotation {
+        name: &name,
+        verifier_key: &verifier_key,
+    })
+    .card(&mut index, 0);
+    let verifier_card = Card::Verifier(&old_verifier_value).card(&mut index, 0);
+    let new_verifier_card = Card::Verifier(&new_verifier_value).card(&mut index, 0);
+    let checksum = TrDbColdStub::new()
+        .rotate_network_verifier(
+            &verifier_key,
+            &old_verifier_value,
+            &new_verifier_value,
+            rotation_index,
+        )
+        .store_and_get_checksum_in(storage)?;
+    Ok(TransactionAction::Stub {
+        s: TransactionCardSet {
+            verifier: Some(vec![verifier_card]),
+            warning: Some(vec![warning_card]),
+            new_verifier: Some(vec![new_verifier_card]),
+            ..Default::default()
+        },
+        u: checksum,
+        stub: StubNav::RotateVerifier {
+            n: specs_invariants.first_network_specs_key,
+        },
+    })
+}
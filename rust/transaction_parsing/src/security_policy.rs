@@ -0,0 +1,84 @@
+use db_handling::{
+    db_transactions::TrDbColdStub,
+    helpers::{get_general_verifier_in, get_security_policy_in},
+    storage::Storage,
+};
+use definitions::{
+    error::TransferContent, error_signer::GeneralVerifierForContent,
+    navigation::TransactionCardSet, network_specs::Verifier, qr_transfers::ContentSecurityPolicy,
+};
+
+use crate::cards::{Card, Warning};
+use crate::check_signature::pass_crypto;
+use crate::error::{Error, Result};
+use crate::{StubNav, TransactionAction};
+
+pub fn security_policy<S>(data_hex: &str, storage: &S) -> Result<TransactionAction>
+where
+    S: Storage,
+{
+    let checked_info = pass_crypto(data_hex, TransferContent::SecurityPolicy)?;
+    let security_policy =
+        ContentSecurityPolicy::from_slice(&checked_info.message).security_policy()?;
+    let general_verifier = get_general_verifier_in(storage)?;
+
+    // security policy is accepted only if signed by the general verifier
+    // already set up in this Signer; it can not be used to set up a new one
+    let verifier_value = match (checked_info.verifier, general_verifier) {
+        (
+            Verifier {
+                v: Some(new_general_verifier_value),
+            },
+            Verifier {
+                v: Some(old_general_verifier_value),
+            },
+        ) => {
+            if new_general_verifier_value != old_general_verifier_value {
+                return Err(Error::GeneralVerifierChanged {
+                    content: GeneralVerifierForContent::SecurityPolicy,
+                    old_general_verifier_value,
+                    new_general_verifier_value,
+                });
+            }
+            new_general_verifier_value
+        }
+        (
+            Verifier { v: None },
+            Verifier {
+                v: Some(old_general_verifier_value),
+            },
+        ) => {
+            return Err(Error::NeedGeneralVerifier {
+                content: GeneralVerifierForContent::SecurityPolicy,
+                verifier_value: old_general_verifier_value,
+            })
+        }
+        (_, Verifier { v: None }) => return Err(Error::SecurityPolicyNoGeneralVerifier),
+    };
+    let stored_policy = get_security_policy_in(storage)?;
+    if stored_policy == security_policy {
+        return Err(Error::SecurityPolicyKnown);
+    }
+    if security_policy.index <= stored_policy.index {
+        return Err(Error::SecurityPolicyOutdated {
+            stored_index: stored_policy.index,
+            received_index: security_policy.index,
+        });
+    }
+
+    let mut index = 0;
+    let verifier_card = Card::Verifier(&verifier_value).card(&mut index, 0);
+    let warning_card = Card::Warning(Warning::SecurityPolicy(&security_policy)).card(&mut index, 0);
+    let checksum = TrDbColdStub::new()
+        .new_security_policy(&security_policy)
+        .store_and_get_checksum_in(storage)?;
+    Ok(TransactionAction::Stub {
+        s: TransactionCardSet {
+            verifier: Some(vec![verifier_card]),
+            warning: Some(vec![warning_card]),
+            ..Default::default()
+        },
+        u: checksum,
+        stub: StubNav::SecurityPolicy,
+    })
+}
@@ -1,24 +1,41 @@
-use crate::{produce_output, StubNav};
+use crate::check_signature::pass_crypto;
+use crate::{produce_output, produce_output_in, Error, StubNav};
 use constants::test_values::{
     alice_sr_alice, bob, ed, id_01, id_02, id_03, types_known, types_unknown, westend_9070,
 };
 use db_handling::{
-    cold_default::{populate_cold, populate_cold_no_metadata, populate_cold_no_networks},
+    cold_default::{
+        populate_cold, populate_cold_in, populate_cold_no_metadata, populate_cold_no_networks,
+    },
+    db_transactions::TrDbColdStub,
+    helpers::{
+        add_contact, get_contacts, get_security_policy, get_verifier_rotation_index,
+        try_get_network_specs, try_get_valid_current_verifier,
+    },
     manage_history::get_history,
+    storage::MemoryStorage,
 };
 use definitions::{
     crypto::Encryption,
-    history::{Entry, Event},
-    keyring::NetworkSpecsKey,
+    error::TransferContent,
+    history::{Entry, Event, NetworkSpecsAmendmentTokensDisplay, NetworkVerifierRotationDisplay},
+    keyring::{NetworkSpecsKey, VerifierKey},
+    localisation::LocalisedMessage,
     navigation::{
-        Address, Card, MMetadataRecord, MSCAuthorPlain, MSCCall, MSCCurrency, MSCEnumVariantName,
-        MSCEraMortal, MSCId, MSCNameVersion, MTypesInfo, MVerifierDetails, NetworkSpecsToSend,
-        TransactionAction, TransactionCard, TransactionCardSet,
+        Address, Card, MMetadataRecord, MSCAuthorPlain, MSCCall, MSCContact, MSCCurrency,
+        MSCEnumVariantName, MSCEraMortal, MSCId, MSCNameVersion, MTypesInfo, MVerifierDetails,
+        NetworkSpecsToSend, TransactionAction, TransactionCard, TransactionCardSet,
     },
-    network_specs::{NetworkSpecs, Verifier, VerifierValue},
+    network_specs::{NetworkSpecs, ValidCurrentVerifier, Verifier, VerifierValue},
+    policy::{PolicyRule, SecurityPolicy},
+    qr_transfers::{
+        CompressedContentType, Compression, ContentAddContacts, ContentAmendSpecs,
+        ContentCompressed, ContentRotateVerifier, ContentSecurityPolicy, VerifierRotation,
+    },
+    users::ContactEntry,
 };
 use pretty_assertions::assert_eq;
-use sp_core::H256;
+use sp_core::{sr25519, Pair, H256};
 use sp_runtime::MultiSigner;
 use std::{fs, str::FromStr};
 
@@ -43,6 +60,11 @@ fn verifier_alice_ed25519() -> Verifier {
     }
 }
 
+const BOB: [u8; 32] = [
+    142, 175, 4, 21, 22, 135, 115, 99, 38, 201, 254, 161, 126, 37, 252, 82, 135, 97, 54, 147, 201,
+    18, 144, 156, 178, 38, 170, 71, 148, 242, 106, 72,
+];
+
 fn entries_contain_event(entries: &[Entry], event: &Event) -> bool {
     entries.iter().flat_map(|e| &e.events).any(|e| e == event)
 }
@@ -63,6 +85,7 @@ fn westend_spec() -> NetworkSpecs {
         path_id: "//westend".to_string(),
         secondary_color: "#262626".to_string(),
         title: "Westend".to_string(),
+        tokens: Vec::new(),
         unit: "WND".to_string(),
     }
 }
@@ -113,6 +136,7 @@ fn add_specs_westend_no_network_info_not_signed() {
                     path_id: "//westend".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "Westend".to_string(),
+                    tokens: Vec::new(),
                     unit: "WND".to_string(),
                 },
             },
@@ -122,6 +146,7 @@ fn add_specs_westend_no_network_info_not_signed() {
             indent: 0,
             card: Card::WarningCard {
                 f: "Received network information is not verified.".to_string(),
+                message: LocalisedMessage::new("warning.not_verified"),
             },
         }]),
         ..Default::default()
@@ -159,6 +184,25 @@ fn add_specs_westend_not_signed() {
     fs::remove_dir_all(dbname).unwrap();
 }
 
+#[test]
+fn add_specs_westend_not_signed_in_memory() {
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let line = fs::read_to_string("for_tests/add_specs_westend_unverified.txt").unwrap();
+    let action = produce_output_in(line.trim(), &storage);
+    let expected_action = TransactionAction::Read {
+        r: TransactionCardSet {
+            error: Some(vec![TransactionCard {
+                index: 0,
+                indent: 0,
+                card: Card::ErrorCard { f:  "Bad input data. Exactly same network specs for network westend with encryption sr25519 are already in the database.".to_string()},
+            }]),
+            ..Default::default()
+        }
+    };
+    assert_eq!(action, expected_action);
+}
+
 #[test]
 fn add_specs_westend_not_signed_general_verifier_disappear() {
     let dbname = "for_tests/add_specs_westend_not_signed_general_verifier_disappear";
@@ -233,14 +277,24 @@ fn load_types_known_alice_signed() {
     populate_cold_no_metadata(dbname, Verifier { v: None }).unwrap();
     let line = fs::read_to_string("for_tests/types_info_Alice.txt").unwrap();
 
-    let expected_warning_1 =  "Received message is verified by a new general verifier. Currently no general verifier is set, and proceeding will update the general verifier to the received value. All previously acquired information associated with general verifier will be purged. Affected network specs entries: Kusama, Polkadot, Westend; affected metadata entries: none. Types information is purged.".to_string();
+    let expected_warning_1 = LocalisedMessage::new("warning.general_verifier_appeared_types")
+        .with_list(
+            "network_specs",
+            vec![
+                "Kusama".to_string(),
+                "Polkadot".to_string(),
+                "Westend".to_string(),
+            ],
+        )
+        .with_list("metadata", Vec::new());
 
     let warning = Some(vec![
         TransactionCard {
             index: 1,
             indent: 0,
             card: Card::WarningCard {
-                f: expected_warning_1,
+                f: expected_warning_1.show(),
+                message: expected_warning_1,
             },
         },
         TransactionCard {
@@ -249,6 +303,7 @@ fn load_types_known_alice_signed() {
             card: Card::WarningCard {
                 f: "Received types information is identical to the one that was in the database."
                     .to_string(),
+                message: LocalisedMessage::new("warning.types_already_there"),
             },
         },
     ]);
@@ -369,12 +424,16 @@ fn load_types_known_alice_signed_metadata_hold() {
         warning: Some(vec![TransactionCard {
             index: 1,
             indent: 0,
-            card: Card::WarningCard { f: "Received message is verified by a new general verifier. Currently no general verifier is set, and proceeding will update the general verifier to the received value. All previously acquired information associated with general verifier will be purged. Affected network specs entries: Kusama, Polkadot, Westend; affected metadata entries: kusama2030, polkadot30, westend9000, westend9010. Types information is purged.".to_string() },
+            card: Card::WarningCard {
+ f: "Received message is verified by a new general verifier. Currently no general verifier is set, and proceeding will update the general verifier to the received value. All previously acquired information associated with general verifier will be purged. Affected network specs entries: Kusama, Polkadot, Westend; affected metadata entries: kusama2030, polkadot30, westend9000, westend9010. Types information is purged.".to_string(), message: LocalisedMessage::new("warning.general_verifier_appeared_types")
+.with_list("network_specs", vec!["Kusama".to_string(), "Polkadot".to_string(), "Westend".to_string()])
+.with_list("metadata", vec!["kusama2030".to_string(), "polkadot30".to_string(), "westend9000".to_string(), "westend9010".to_string()]) },
         },
         TransactionCard {
             index: 2,
             indent: 0,
-            card: Card::WarningCard { f: "Received types information is identical to the one that was in the database.".to_string() },
+            card: Card::WarningCard {
+ f: "Received types information is identical to the one that was in the database.".to_string(), message: LocalisedMessage::new("warning.types_already_there") },
         }
         ]),
         types_info: Some(vec![TransactionCard {
@@ -412,6 +471,7 @@ fn load_types_unknown_not_signed() {
                 indent: 0,
                 card: Card::WarningCard {
                     f: "Received types information is not verified.".to_string(),
+                    message: LocalisedMessage::new("warning.types_not_verified"),
                 },
             },
             TransactionCard {
@@ -419,6 +479,7 @@ fn load_types_unknown_not_signed() {
                 indent: 0,
                 card: Card::WarningCard {
                     f: "Updating types (really rare operation).".to_string(),
+                    message: LocalisedMessage::new("warning.updating_types"),
                 },
             },
         ]),
@@ -470,11 +531,15 @@ fn load_types_unknown_alice_signed() {
         warning: Some(vec![TransactionCard {
             index: 1,
             indent: 0,
-            card: Card::WarningCard { f: "Received message is verified by a new general verifier. Currently no general verifier is set, and proceeding will update the general verifier to the received value. All previously acquired information associated with general verifier will be purged. Affected network specs entries: Kusama, Polkadot, Westend; affected metadata entries: none. Types information is purged.".to_string() },
+            card: Card::WarningCard {
+ f: "Received message is verified by a new general verifier. Currently no general verifier is set, and proceeding will update the general verifier to the received value. All previously acquired information associated with general verifier will be purged. Affected network specs entries: Kusama, Polkadot, Westend; affected metadata entries: none. Types information is purged.".to_string(), message: LocalisedMessage::new("warning.general_verifier_appeared_types")
+.with_list("network_specs", vec!["Kusama".to_string(), "Polkadot".to_string(), "Westend".to_string()])
+.with_list("metadata", Vec::new()) },
         }, TransactionCard {
             index: 2,
             indent: 0,
-            card: Card::WarningCard { f: "Updating types (really rare operation).".to_string() },
+            card: Card::WarningCard {
+ f: "Updating types (really rare operation).".to_string(), message: LocalisedMessage::new("warning.updating_types") },
         }]),
         types_info: Some(vec![TransactionCard {
             index: 3,
@@ -572,6 +637,7 @@ fn parse_transaction_1() {
                     f: MSCId {
                         base58: "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
                         identicon: bob().to_vec(),
+                        contact_name: None,
                     },
                 },
             },
@@ -657,6 +723,7 @@ fn parse_transaction_1() {
         has_pwd: false,
         multiselect: None,
         secret_exposed: false,
+        annotation: None,
     };
     let network_info_known = NetworkSpecs {
         base58prefix: 42,
@@ -673,6 +740,7 @@ fn parse_transaction_1() {
         path_id: "//westend".to_string(),
         secondary_color: "#262626".to_string(),
         title: "Westend".to_string(),
+        tokens: Vec::new(),
         unit: "WND".to_string(),
     };
     let output = produce_output(line, dbname);
@@ -774,6 +842,7 @@ fn parse_transaction_2() {
                     f: MSCId {
                         base58: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
                         identicon: alice_sr_alice().to_vec(),
+                        contact_name: None,
                     },
                 },
             },
@@ -852,6 +921,7 @@ fn parse_transaction_2() {
                     f: MSCId {
                         base58: "5G1ojzh47Yt8KoYhuAjXpHcazvsoCXe3G8LZchKDvumozJJJ".to_string(),
                         identicon: id_01().to_vec(),
+                        contact_name: None,
                     },
                 },
             },
@@ -872,6 +942,7 @@ fn parse_transaction_2() {
                     f: MSCId {
                         base58: "5FZoQhgUCmqBxnkHX7jCqThScS2xQWiwiF61msg63CFL3Y8f".to_string(),
                         identicon: id_02().to_vec(),
+                        contact_name: None,
                     },
                 },
             },
@@ -916,6 +987,7 @@ fn parse_transaction_2() {
                     f: MSCId {
                         base58: "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
                         identicon: bob().to_vec(),
+                        contact_name: None,
                     },
                 },
             },
@@ -984,6 +1056,7 @@ fn parse_transaction_2() {
         has_pwd: false,
         multiselect: None,
         secret_exposed: false,
+        annotation: None,
     };
     let network_info_known = westend_spec();
 
@@ -1057,6 +1130,7 @@ fn parse_transaction_3() {
                     f: MSCId {
                         base58: "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
                         identicon: bob().to_vec(),
+                        contact_name: None,
                     },
                 },
             },
@@ -1142,6 +1216,7 @@ fn parse_transaction_3() {
         has_pwd: false,
         multiselect: None,
         secret_exposed: false,
+        annotation: None,
     };
     let network_info_known = westend_spec();
     let output = produce_output(line, dbname);
@@ -1174,6 +1249,7 @@ fn load_westend9070_not_signed() {
             indent: 0,
             card: Card::WarningCard {
                 f: "Received network information is not verified.".to_string(),
+                message: LocalisedMessage::new("warning.not_verified"),
             },
         }]),
         meta: Some(vec![TransactionCard {
@@ -1209,6 +1285,138 @@ fn load_westend9070_not_signed() {
     fs::remove_dir_all(dbname).unwrap();
 }
 
+/// Repack unsigned update from test file into compressed update.
+fn compress_unsigned_update(line: &str, content_type: CompressedContentType) -> String {
+    let data = hex::decode(line.trim()).unwrap();
+    assert_eq!(&data[..2], &[0x53, 0xff]);
+    let compressed =
+        ContentCompressed::generate(content_type, Compression::Zstd, &data[3..]).unwrap();
+    format!("53ff82{}", hex::encode(compressed.to_transfer()))
+}
+
+#[test]
+fn load_westend9070_not_signed_compressed() {
+    let dbname = "for_tests/load_westend9070_not_signed_compressed";
+    populate_cold_no_metadata(dbname, Verifier { v: None }).unwrap();
+    let line = fs::read_to_string("for_tests/network_metadata_westendV9070_None.txt").unwrap();
+    let line = compress_unsigned_update(&line, CompressedContentType::LoadMeta);
+    let set_expected = TransactionCardSet {
+        warning: Some(vec![TransactionCard {
+            index: 0,
+            indent: 0,
+            card: Card::WarningCard {
+                f: "Received network information is not verified.".to_string(),
+                message: LocalisedMessage::new("warning.not_verified"),
+            },
+        }]),
+        meta: Some(vec![TransactionCard {
+            index: 1,
+            indent: 0,
+            card: Card::MetaCard {
+                f: MMetadataRecord {
+                    specname: "westend".to_string(),
+                    specs_version: "9070".to_string(),
+                    meta_hash: "e281fbc53168a6b87d1ea212923811f4c083e7be7d18df4b8527b9532e5f5fec"
+                        .to_string(),
+                    meta_id_pic: westend_9070().to_vec(),
+                },
+            },
+        }]),
+        ..Default::default()
+    };
+    let action = produce_output(&line, dbname);
+    if let TransactionAction::Stub { s: set, u: _, stub } = action {
+        assert_eq!(set, set_expected);
+        assert!(matches!(stub, StubNav::LoadMeta { .. }));
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+    fs::remove_dir_all(dbname).unwrap();
+}
+
+#[test]
+fn load_westend9070_compressed_declared_as_load_types() {
+    let dbname = "for_tests/load_westend9070_compressed_declared_as_load_types";
+    populate_cold_no_metadata(dbname, Verifier { v: None }).unwrap();
+    let line = fs::read_to_string("for_tests/network_metadata_westendV9070_None.txt").unwrap();
+    let line = compress_unsigned_update(&line, CompressedContentType::LoadTypes);
+    let action = produce_output(&line, dbname);
+    if let TransactionAction::Read { r: set } = action {
+        assert!(set.error.is_some());
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+    fs::remove_dir_all(dbname).unwrap();
+}
+
+#[test]
+fn compressed_content_type_mismatch() {
+    let line = fs::read_to_string("for_tests/network_metadata_westendV9070_None.txt").unwrap();
+    let line = compress_unsigned_update(&line, CompressedContentType::LoadTypes);
+    let error = pass_crypto(&line, TransferContent::LoadMeta)
+        .err()
+        .expect("content type declared in compressed payload is not `load_metadata`");
+    if let Error::CompressedContentMismatch { expected, found } = error {
+        assert_eq!(expected, TransferContent::LoadMeta);
+        assert_eq!(found, TransferContent::LoadTypes);
+    } else {
+        panic!("Wrong error {:?}", error)
+    }
+}
+
+/// Pack unsigned update from test file into compressed update signed by
+/// Alice over the uncompressed content, optionally compressing a tampered
+/// content instead.
+fn compress_alice_signed_update(
+    line: &str,
+    content_type: CompressedContentType,
+    tamper: bool,
+) -> String {
+    let data = hex::decode(line.trim()).unwrap();
+    assert_eq!(&data[..2], &[0x53, 0xff]);
+    let content = data[3..].to_vec();
+    let pair = sr25519::Pair::from_string("//Alice", None).unwrap();
+    let signature = pair.sign(&content);
+    let mut compressed_content = content;
+    if tamper {
+        let last = compressed_content.len() - 1;
+        compressed_content[last] ^= 1;
+    }
+    let compressed =
+        ContentCompressed::generate(content_type, Compression::Zstd, &compressed_content).unwrap();
+    format!(
+        "530182{}{}{}",
+        hex::encode(pair.public()),
+        hex::encode(compressed.to_transfer()),
+        hex::encode(signature)
+    )
+}
+
+#[test]
+fn compressed_alice_signed_verifies_uncompressed_content() {
+    let line = fs::read_to_string("for_tests/network_metadata_westendV9070_None.txt").unwrap();
+    let content = hex::decode(line.trim()).unwrap()[3..].to_vec();
+    let line = compress_alice_signed_update(&line, CompressedContentType::LoadMeta, false);
+    let info = pass_crypto(&line, TransferContent::LoadMeta).unwrap();
+    assert_eq!(info.verifier, verifier_alice_sr25519());
+    assert_eq!(info.message, content);
+    assert!(info.tail.is_empty());
+}
+
+#[test]
+fn compressed_alice_signed_tampered_rejected() {
+    let line = fs::read_to_string("for_tests/network_metadata_westendV9070_None.txt").unwrap();
+    let line = compress_alice_signed_update(&line, CompressedContentType::LoadMeta, true);
+    let error = pass_crypto(&line, TransferContent::LoadMeta)
+        .err()
+        .expect("signature was produced for content before tampering");
+    assert!(
+        matches!(error, Error::BadSignature),
+        "Wrong error {:?}",
+        error
+    );
+}
+
 #[test]
 fn load_westend9070_alice_signed() {
     let dbname = "for_tests/load_westend9070_alice_signed";
@@ -1367,6 +1575,7 @@ fn add_specs_dock_not_verified_db_not_verified() {
             indent: 0,
             card: Card::WarningCard {
                 f: "Received network information is not verified.".to_string(),
+                message: LocalisedMessage::new("warning.not_verified"),
             },
         }]),
         new_specs: Some(vec![TransactionCard {
@@ -1387,6 +1596,7 @@ fn add_specs_dock_not_verified_db_not_verified() {
                     path_id: "//dock-pos-main-runtime".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "dock-pos-main-runtime-sr25519".to_string(),
+                    tokens: Vec::new(),
                     unit: "DOCK".to_string(),
                 },
             },
@@ -1435,7 +1645,10 @@ fn add_specs_dock_alice_verified_db_not_verified() {
         warning: Some(vec![TransactionCard {
             index: 1,
             indent: 0,
-            card: Card::WarningCard { f: "Received message is verified by a new general verifier. Currently no general verifier is set, and proceeding will update the general verifier to the received value. All previously acquired information associated with general verifier will be purged. Affected network specs entries: Kusama, Polkadot, Westend; affected metadata entries: kusama2030, polkadot30, westend9000, westend9010. Types information is purged.".to_string() },
+            card: Card::WarningCard {
+ f: "Received message is verified by a new general verifier. Currently no general verifier is set, and proceeding will update the general verifier to the received value. All previously acquired information associated with general verifier will be purged. Affected network specs entries: Kusama, Polkadot, Westend; affected metadata entries: kusama2030, polkadot30, westend9000, westend9010. Types information is purged.".to_string(), message: LocalisedMessage::new("warning.general_verifier_appeared_types")
+.with_list("network_specs", vec!["Kusama".to_string(), "Polkadot".to_string(), "Westend".to_string()])
+.with_list("metadata", vec!["kusama2030".to_string(), "polkadot30".to_string(), "westend9000".to_string(), "westend9010".to_string()]) },
         }]),
         new_specs: Some(vec![TransactionCard {
             index: 2,
@@ -1452,6 +1665,7 @@ fn add_specs_dock_alice_verified_db_not_verified() {
                     path_id: "//dock-pos-main-runtime".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "dock-pos-main-runtime-sr25519".to_string(),
+                    tokens: Vec::new(),
                     unit: "DOCK".to_string(),
                 },
             },
@@ -1489,6 +1703,7 @@ fn add_specs_dock_not_verified_db_alice_verified() {
             indent: 0,
             card: Card::WarningCard {
                 f: "Received network information is not verified.".to_string(),
+                message: LocalisedMessage::new("warning.not_verified"),
             },
         }]),
         new_specs: Some(vec![TransactionCard {
@@ -1509,6 +1724,7 @@ fn add_specs_dock_not_verified_db_alice_verified() {
                     path_id: "//dock-pos-main-runtime".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "dock-pos-main-runtime-sr25519".to_string(),
+                    tokens: Vec::new(),
                     unit: "DOCK".to_string(),
                 },
             },
@@ -1571,6 +1787,7 @@ fn add_specs_dock_both_verified_same() {
                     path_id: "//dock-pos-main-runtime".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "dock-pos-main-runtime-sr25519".to_string(),
+                    tokens: Vec::new(),
                     unit: "DOCK".to_string(),
                 },
             },
@@ -1633,6 +1850,7 @@ fn add_specs_dock_both_verified_different() {
                     path_id: "//dock-pos-main-runtime".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "dock-pos-main-runtime-sr25519".to_string(),
+                    tokens: Vec::new(),
                     unit: "DOCK".to_string(),
                 },
             },
@@ -1668,6 +1886,7 @@ fn add_specs_westend_ed25519_not_signed() {
             indent: 0,
             card: Card::WarningCard {
                 f: "Received network information is not verified.".to_string(),
+                message: LocalisedMessage::new("warning.not_verified"),
             },
         }]),
         new_specs: Some(vec![TransactionCard {
@@ -1688,6 +1907,7 @@ fn add_specs_westend_ed25519_not_signed() {
                     path_id: "//westend".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "westend-ed25519".to_string(),
+                    tokens: Vec::new(),
                     unit: "WND".to_string(),
                 },
             },
@@ -1740,7 +1960,24 @@ fn add_specs_westend_ed25519_alice_signed_db_not_verified() {
     let dbname = "for_tests/add_specs_westend_ed25519_alice_signed_db_not_verified";
     populate_cold(dbname, Verifier { v: None }).unwrap();
     let line = fs::read_to_string("for_tests/add_specs_westend-ed25519_Alice-sr25519.txt").unwrap();
-    let warning_str = "Received message is verified by a new general verifier. Currently no general verifier is set, and proceeding will update the general verifier to the received value. All previously acquired information associated with general verifier will be purged. Affected network specs entries: Kusama, Polkadot, Westend; affected metadata entries: kusama2030, polkadot30, westend9000, westend9010. Types information is purged.".to_string();
+    let warning_str = LocalisedMessage::new("warning.general_verifier_appeared_types")
+        .with_list(
+            "network_specs",
+            vec![
+                "Kusama".to_string(),
+                "Polkadot".to_string(),
+                "Westend".to_string(),
+            ],
+        )
+        .with_list(
+            "metadata",
+            vec![
+                "kusama2030".to_string(),
+                "polkadot30".to_string(),
+                "westend9000".to_string(),
+                "westend9010".to_string(),
+            ],
+        );
 
     let set_expected = TransactionCardSet {
         verifier: Some(vec![TransactionCard {
@@ -1758,7 +1995,10 @@ fn add_specs_westend_ed25519_alice_signed_db_not_verified() {
         warning: Some(vec![TransactionCard {
             index: 1,
             indent: 0,
-            card: Card::WarningCard { f: warning_str },
+            card: Card::WarningCard {
+                f: warning_str.show(),
+                message: warning_str,
+            },
         }]),
         new_specs: Some(vec![TransactionCard {
             index: 2,
@@ -1778,6 +2018,7 @@ fn add_specs_westend_ed25519_alice_signed_db_not_verified() {
                     path_id: "//westend".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "westend-ed25519".to_string(),
+                    tokens: Vec::new(),
                     unit: "WND".to_string(),
                 },
             },
@@ -1860,6 +2101,7 @@ fn add_specs_westend_ed25519_both_verified_same() {
                     path_id: "//westend".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "westend-ed25519".to_string(),
+                    tokens: Vec::new(),
                     unit: "WND".to_string(),
                 },
             },
@@ -1931,6 +2173,7 @@ fn parse_transaction_4_unknown_author() {
             indent: 0,
             card: Card::WarningCard {
                 f: "Transaction author public key not found.".to_string(),
+                message: LocalisedMessage::new("warning.author_not_found"),
             },
         }]),
         method: Some(vec![
@@ -1975,6 +2218,7 @@ fn parse_transaction_4_unknown_author() {
                     f: MSCId {
                         base58: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
                         identicon: alice_sr_alice().to_vec(),
+                        contact_name: None,
                     },
                 },
             },
@@ -2115,6 +2359,7 @@ fn parse_transaction_6_error_on_parsing() {
                     has_pwd: false,
                     multiselect: None,
                     secret_exposed: false,
+                    annotation: None,
                 },
             },
         }]),
@@ -2209,6 +2454,7 @@ fn parse_transaction_7_error_on_parsing() {
                     has_pwd: false,
                     multiselect: None,
                     secret_exposed: false,
+                    annotation: None,
                 },
             },
         }]),
@@ -2304,6 +2550,7 @@ fn parse_transaction_8_error_on_parsing() {
                     has_pwd: false,
                     multiselect: None,
                     secret_exposed: false,
+                    annotation: None,
                 },
             },
         }]),
@@ -2402,6 +2649,7 @@ fn parse_msg_1() {
         has_pwd: false,
         multiselect: None,
         secret_exposed: false,
+        annotation: None,
     };
 
     let network_info_known = westend_spec();
@@ -2529,3 +2777,743 @@ fn import_derivations_some_passworded() {
     }
     fs::remove_dir_all(dbname).unwrap();
 }
+
+fn add_contacts_bob_not_signed() -> String {
+    let content = ContentAddContacts::generate(&[ContactEntry {
+        account_id: BOB,
+        name: "Bob".to_string(),
+    }]);
+    format!("53ffc2{}", hex::encode(content.to_transfer()))
+}
+
+#[test]
+fn add_contacts_not_signed() {
+    let dbname = "for_tests/add_contacts_not_signed";
+    populate_cold(dbname, Verifier { v: None }).unwrap();
+    let line = add_contacts_bob_not_signed();
+    let expected_set = TransactionCardSet {
+        warning: Some(vec![TransactionCard {
+            index: 0,
+            indent: 0,
+            card: Card::WarningCard {
+                f: "Received contacts are not verified.".to_string(),
+                message: LocalisedMessage::new("warning.contacts_not_verified"),
+            },
+        }]),
+        contacts: Some(vec![TransactionCard {
+            index: 1,
+            indent: 0,
+            card: Card::ContactCard {
+                f: MSCContact {
+                    name: "Bob".to_string(),
+                    base58: "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
+                    identicon: bob().to_vec(),
+                },
+            },
+        }]),
+        ..Default::default()
+    };
+
+    let action = produce_output(&line, dbname);
+    if let TransactionAction::Stub { s: set, u: _, stub } = action {
+        assert_eq!(stub, StubNav::AddContacts);
+        assert_eq!(set, expected_set);
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+    // nothing is added until the stub is accepted
+    assert!(get_contacts(dbname).unwrap().is_empty());
+    fs::remove_dir_all(dbname).unwrap();
+}
+
+#[test]
+fn add_contacts_known_not_signed() {
+    let dbname = "for_tests/add_contacts_known_not_signed";
+    populate_cold(dbname, Verifier { v: None }).unwrap();
+    add_contact(
+        dbname,
+        "Bob",
+        "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty",
+    )
+    .unwrap();
+    let line = add_contacts_bob_not_signed();
+    let expected_set = TransactionCardSet {
+        error: Some(vec![TransactionCard {
+            index: 0,
+            indent: 0,
+            card: Card::ErrorCard {
+                f: "Bad input data. All received contacts are already in the database.".to_string(),
+            },
+        }]),
+        ..Default::default()
+    };
+
+    let action = produce_output(&line, dbname);
+    if let TransactionAction::Read { r: set } = action {
+        assert_eq!(set, expected_set);
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+    fs::remove_dir_all(dbname).unwrap();
+}
+
+#[test]
+fn parse_transaction_1_recipient_contact() {
+    let dbname = "for_tests/parse_transaction_1_recipient_contact";
+    populate_cold(dbname, Verifier { v: None }).unwrap();
+    add_contact(
+        dbname,
+        "Bob",
+        "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty",
+    )
+    .unwrap();
+    let line = "530100d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27da40403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480700e8764817b501b8003223000005000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e538a7d7a0ac17eb6dd004578cb8e238c384a10f57c999a3fa1200409cd9b3f33e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e";
+    let id_card_known = TransactionCard {
+        index: 4,
+        indent: 4,
+        card: Card::IdCard {
+            f: MSCId {
+                base58: "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
+                identicon: bob().to_vec(),
+                contact_name: Some("Bob".to_string()),
+            },
+        },
+    };
+
+    let output = produce_output(line, dbname);
+    if let TransactionAction::Sign { content, .. } = output {
+        assert_eq!(content.method.unwrap()[4], id_card_known);
+        assert!(content.warning.is_none());
+    } else {
+        panic!("Wrong action {:?}", output)
+    }
+    fs::remove_dir_all(dbname).unwrap();
+}
+
+fn rotate_verifier_westend(
+    old_verifier_value: &VerifierValue,
+    new_verifier_value: &VerifierValue,
+    rotation_index: u32,
+    signer: &str,
+) -> String {
+    let content = ContentRotateVerifier::generate(&VerifierRotation {
+        genesis_hash: westend_spec().genesis_hash,
+        old_verifier_value: old_verifier_value.to_owned(),
+        new_verifier_value: new_verifier_value.to_owned(),
+        rotation_index,
+    });
+    let pair = sr25519::Pair::from_string(signer, None).unwrap();
+    format!(
+        "5301c3{}{}{}",
+        hex::encode(pair.public()),
+        hex::encode(content.to_transfer()),
+        hex::encode(pair.sign(&content.to_sign()))
+    )
+}
+
+fn verifier_value_bob_sr25519() -> VerifierValue {
+    VerifierValue::Standard {
+        m: MultiSigner::Sr25519(sr25519::Public::from_raw(BOB)),
+    }
+}
+
+#[test]
+fn rotate_verifier_westend_alice_to_bob() {
+    let dbname = "for_tests/rotate_verifier_westend_alice_to_bob";
+    populate_cold(dbname, Verifier { v: None }).unwrap();
+    let verifier_key = VerifierKey::from_parts(westend_spec().genesis_hash);
+    TrDbColdStub::new()
+        .new_network_verifier(
+            &verifier_key,
+            &ValidCurrentVerifier::Custom {
+                v: verifier_alice_sr25519(),
+            },
+            &Verifier { v: None },
+        )
+        .apply(dbname)
+        .unwrap();
+
+    // unsigned rotation is not accepted
+    let content = ContentRotateVerifier::generate(&VerifierRotation {
+        genesis_hash: westend_spec().genesis_hash,
+        old_verifier_value: verifier_alice_sr25519().v.unwrap(),
+        new_verifier_value: verifier_value_bob_sr25519(),
+        rotation_index: 1,
+    });
+    let line = format!("53ffc3{}", hex::encode(content.to_transfer()));
+    let action = produce_output(&line, dbname);
+    if let TransactionAction::Read { r: set } = action {
+        assert!(set.error.is_some());
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+
+    // rotation index must follow the last accepted one, first one is 1
+    let line = rotate_verifier_westend(
+        verifier_alice_sr25519().v.as_ref().unwrap(),
+        &verifier_value_bob_sr25519(),
+        2,
+        "//Alice",
+    );
+    let action = produce_output(&line, dbname);
+    if let TransactionAction::Read { r: set } = action {
+        assert_eq!(
+            set.error.unwrap()[0].card,
+            Card::ErrorCard {
+                f: "Bad input data. Network westend expects verifier rotation with index 1. Received rotate_verifier message has index 2, and is either replayed or out of order.".to_string(),
+            }
+        );
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+
+    let alice_to_bob = rotate_verifier_westend(
+        verifier_alice_sr25519().v.as_ref().unwrap(),
+        &verifier_value_bob_sr25519(),
+        1,
+        "//Alice",
+    );
+    let line = alice_to_bob.to_owned();
+    let expected_set = TransactionCardSet {
+        warning: Some(vec![TransactionCard {
+            index: 0,
+            indent: 0,
+            card: Card::WarningCard {
+                f: "Received message replaces the custom verifier of network westend with genesis hash e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e, and is endorsed by the current network verifier. Network specs and metadata are kept. After proceeding, updates for this network will be accepted only if signed by the new verifier.".to_string(),
+                message: LocalisedMessage::new("warning.verifier_rotation")
+                    .with_text("name", "westend")
+                    .with_hex("genesis_hash", westend_spec().genesis_hash),
+            },
+        }]),
+        verifier: Some(vec![TransactionCard {
+            index: 1,
+            indent: 0,
+            card: Card::VerifierCard {
+                f: MVerifierDetails {
+                    public_key: "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+                        .to_string(),
+                    identicon: alice_sr_alice().to_vec(),
+                    encryption: "sr25519".to_string(),
+                },
+            },
+        }]),
+        new_verifier: Some(vec![TransactionCard {
+            index: 2,
+            indent: 0,
+            card: Card::VerifierCard {
+                f: MVerifierDetails {
+                    public_key: "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"
+                        .to_string(),
+                    identicon: bob().to_vec(),
+                    encryption: "sr25519".to_string(),
+                },
+            },
+        }]),
+        ..Default::default()
+    };
+    let action = produce_output(&line, dbname);
+    if let TransactionAction::Stub { s: set, u, stub } = action {
+        assert_eq!(set, expected_set);
+        assert_eq!(
+            stub,
+            StubNav::RotateVerifier {
+                n: NetworkSpecsKey::from_parts(&westend_spec().genesis_hash, &Encryption::Sr25519),
+            }
+        );
+        TrDbColdStub::from_storage(dbname, u)
+            .unwrap()
+            .apply(dbname)
+            .unwrap();
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+
+    assert_eq!(
+        try_get_valid_current_verifier(&verifier_key, dbname).unwrap(),
+        Some(ValidCurrentVerifier::Custom {
+            v: Verifier {
+                v: Some(verifier_value_bob_sr25519()),
+            },
+        })
+    );
+    let current_history: Vec<_> = get_history(dbname)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.1)
+        .collect();
+    assert!(entries_contain_event(
+        &current_history,
+        &Event::NetworkVerifierRotated {
+            network_verifier_rotation_display: NetworkVerifierRotationDisplay::get(
+                &verifier_key,
+                verifier_alice_sr25519().v.as_ref().unwrap(),
+                &verifier_value_bob_sr25519(),
+            ),
+        }
+    ));
+
+    // Alice was rotated out, and could not endorse further rotations
+    let line = rotate_verifier_westend(
+        verifier_alice_sr25519().v.as_ref().unwrap(),
+        verifier_alice_sr25519().v.as_ref().unwrap(),
+        2,
+        "//Alice",
+    );
+    let expected_set = TransactionCardSet {
+        error: Some(vec![TransactionCard {
+            index: 0,
+            indent: 0,
+            card: Card::ErrorCard {
+                f: "Bad input data. Network westend current verifier is public key: 8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48, encryption: sr25519. Received rotate_verifier message is signed by public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d, encryption: sr25519. Verifier rotation must be endorsed by the current network verifier.".to_string(),
+            },
+        }]),
+        ..Default::default()
+    };
+    let action = produce_output(&line, dbname);
+    if let TransactionAction::Read { r: set } = action {
+        assert_eq!(set, expected_set);
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+
+    // Bob must name himself as the outgoing verifier
+    let line = rotate_verifier_westend(
+        verifier_alice_sr25519().v.as_ref().unwrap(),
+        verifier_alice_sr25519().v.as_ref().unwrap(),
+        2,
+        "//Bob",
+    );
+    let action = produce_output(&line, dbname);
+    if let TransactionAction::Read { r: set } = action {
+        assert_eq!(
+            set.error.unwrap()[0].card,
+            Card::ErrorCard {
+                f: "Bad input data. Network westend current verifier is public key: 8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48, encryption: sr25519. Received rotate_verifier message rotates out public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d, encryption: sr25519. Verifier rotation is outdated.".to_string(),
+            }
+        );
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+
+    // Bob rotates the verifier back to Alice
+    let line = rotate_verifier_westend(
+        &verifier_value_bob_sr25519(),
+        verifier_alice_sr25519().v.as_ref().unwrap(),
+        2,
+        "//Bob",
+    );
+    let action = produce_output(&line, dbname);
+    if let TransactionAction::Stub { u, .. } = action {
+        TrDbColdStub::from_storage(dbname, u)
+            .unwrap()
+            .apply(dbname)
+            .unwrap();
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+    assert_eq!(
+        try_get_valid_current_verifier(&verifier_key, dbname).unwrap(),
+        Some(ValidCurrentVerifier::Custom {
+            v: verifier_alice_sr25519(),
+        })
+    );
+    assert_eq!(
+        get_verifier_rotation_index(&verifier_key, dbname).unwrap(),
+        2
+    );
+
+    // first rotation, signed by Alice, could not be replayed
+    let action = produce_output(&alice_to_bob, dbname);
+    if let TransactionAction::Read { r: set } = action {
+        assert_eq!(
+            set.error.unwrap()[0].card,
+            Card::ErrorCard {
+                f: "Bad input data. Network westend expects verifier rotation with index 3. Received rotate_verifier message has index 1, and is either replayed or out of order.".to_string(),
+            }
+        );
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+    assert_eq!(
+        try_get_valid_current_verifier(&verifier_key, dbname).unwrap(),
+        Some(ValidCurrentVerifier::Custom {
+            v: verifier_alice_sr25519(),
+        })
+    );
+    fs::remove_dir_all(dbname).unwrap();
+}
+
+fn security_policy_alice(security_policy: &SecurityPolicy) -> String {
+    let content = ContentSecurityPolicy::generate(security_policy);
+    let pair = sr25519::Pair::from_string("//Alice", None).unwrap();
+    format!(
+        "5301c4{}{}{}",
+        hex::encode(pair.public()),
+        hex::encode(content.to_transfer()),
+        hex::encode(pair.sign(&content.to_sign()))
+    )
+}
+
+#[test]
+fn security_policy_reject_unsigned_updates() {
+    let dbname = "for_tests/security_policy_reject_unsigned_updates";
+    populate_cold(dbname, verifier_alice_sr25519()).unwrap();
+    let security_policy = SecurityPolicy {
+        index: 1,
+        reject_unsigned_updates: true,
+        ..Default::default()
+    };
+
+    // unsigned security policy is not accepted
+    let content = ContentSecurityPolicy::generate(&security_policy);
+    let line = format!("53ffc4{}", hex::encode(content.to_transfer()));
+    let action = produce_output(&line, dbname);
+    if let TransactionAction::Read { r: set } = action {
+        assert!(set.error.is_some());
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+
+    let line = security_policy_alice(&security_policy);
+    let expected_set = TransactionCardSet {
+        verifier: Some(vec![TransactionCard {
+            index: 0,
+            indent: 0,
+            card: Card::VerifierCard {
+                f: MVerifierDetails {
+                    public_key: "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+                        .to_string(),
+                    identicon: alice_sr_alice().to_vec(),
+                    encryption: "sr25519".to_string(),
+                },
+            },
+        }]),
+        warning: Some(vec![TransactionCard {
+            index: 1,
+            indent: 0,
+            card: Card::WarningCard {
+                f: "Received message replaces Signer security policy, and is signed by the general verifier. After proceeding, following rules will be enforced: reject_unsigned_updates.".to_string(),
+                message: LocalisedMessage::new("warning.security_policy")
+                    .with_list("rules", vec!["reject_unsigned_updates".to_string()]),
+            },
+        }]),
+        ..Default::default()
+    };
+    let action = produce_output(&line, dbname);
+    if let TransactionAction::Stub { s: set, u, stub } = action {
+        assert_eq!(set, expected_set);
+        assert_eq!(stub, StubNav::SecurityPolicy);
+        TrDbColdStub::from_storage(dbname, u)
+            .unwrap()
+            .apply(dbname)
+            .unwrap();
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+    assert_eq!(get_security_policy(dbname).unwrap(), security_policy);
+
+    // same policy is not accepted again
+    let action = produce_output(&security_policy_alice(&security_policy), dbname);
+    if let TransactionAction::Read { r: set } = action {
+        assert!(set.error.is_some());
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+
+    let line = add_contacts_bob_not_signed();
+    let expected_set = TransactionCardSet {
+        error: Some(vec![TransactionCard {
+            index: 0,
+            indent: 0,
+            card: Card::ErrorCard {
+                f: "Bad input data. Database error. Internal error. Forbidden by Signer security policy: unsigned updates are rejected.".to_string(),
+            },
+        }]),
+        ..Default::default()
+    };
+    let action = produce_output(&line, dbname);
+    if let TransactionAction::Read { r: set } = action {
+        assert_eq!(set, expected_set);
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+    let current_history: Vec<_> = get_history(dbname)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.1)
+        .collect();
+    assert!(entries_contain_event(
+        &current_history,
+        &Event::SecurityPolicySet {
+            security_policy: security_policy.to_owned(),
+        }
+    ));
+    assert!(entries_contain_event(
+        &current_history,
+        &Event::SecurityPolicyViolation {
+            rule: PolicyRule::UnsignedUpdate,
+        }
+    ));
+    fs::remove_dir_all(dbname).unwrap();
+}
+
+fn accept_security_policy(security_policy: &SecurityPolicy, dbname: &str) {
+    let action = produce_output(&security_policy_alice(security_policy), dbname);
+    if let TransactionAction::Stub { u, .. } = action {
+        TrDbColdStub::from_storage(dbname, u)
+            .unwrap()
+            .apply(dbname)
+            .unwrap();
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+}
+
+#[test]
+fn security_policy_replay_rejected() {
+    let dbname = "for_tests/security_policy_replay_rejected";
+    populate_cold(dbname, verifier_alice_sr25519()).unwrap();
+    let strict_policy = SecurityPolicy {
+        index: 1,
+        reject_unsigned_updates: true,
+        forbid_secret_export: true,
+        ..Default::default()
+    };
+    let relaxed_policy = SecurityPolicy {
+        index: 2,
+        ..Default::default()
+    };
+    accept_security_policy(&strict_policy, dbname);
+    accept_security_policy(&relaxed_policy, dbname);
+    assert_eq!(get_security_policy(dbname).unwrap(), relaxed_policy);
+
+    // earlier signed policy is not accepted again
+    let expected_set = TransactionCardSet {
+        error: Some(vec![TransactionCard {
+            index: 0,
+            indent: 0,
+            card: Card::ErrorCard {
+                f: "Bad input data. Security policy in force has index 2. Received security_policy message has index 1, and is either replayed or outdated.".to_string(),
+            },
+        }]),
+        ..Default::default()
+    };
+    let action = produce_output(&security_policy_alice(&strict_policy), dbname);
+    if let TransactionAction::Read { r: set } = action {
+        assert_eq!(set, expected_set);
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+
+    // new policy with the index already used is not accepted either
+    let same_index_policy = SecurityPolicy {
+        index: 2,
+        forbid_secret_export: true,
+        ..Default::default()
+    };
+    let action = produce_output(&security_policy_alice(&same_index_policy), dbname);
+    if let TransactionAction::Read { r: set } = action {
+        assert!(set.error.is_some());
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+    assert_eq!(get_security_policy(dbname).unwrap(), relaxed_policy);
+    fs::remove_dir_all(dbname).unwrap();
+}
+
+fn amend_specs_westend(specs: &NetworkSpecsToSend, signer: &str) -> String {
+    let content = ContentAmendSpecs::generate(specs);
+    let pair = sr25519::Pair::from_string(signer, None).unwrap();
+    format!(
+        "5301c5{}{}{}",
+        hex::encode(pair.public()),
+        hex::encode(content.to_transfer()),
+        hex::encode(pair.sign(&content.to_sign()))
+    )
+}
+
+#[test]
+fn amend_specs_westend_unit_decimals() {
+    let dbname = "for_tests/amend_specs_westend_unit_decimals";
+    populate_cold(dbname, verifier_alice_sr25519()).unwrap();
+    let network_specs_key =
+        NetworkSpecsKey::from_parts(&westend_spec().genesis_hash, &Encryption::Sr25519);
+    let old_specs = westend_spec().to_send();
+    let new_specs = NetworkSpecsToSend {
+        decimals: 10,
+        unit: "WUND".to_string(),
+        ..old_specs.to_owned()
+    };
+
+    // unsigned amendment is not accepted
+    let content = ContentAmendSpecs::generate(&new_specs);
+    let line = format!("53ffc5{}", hex::encode(content.to_transfer()));
+    let action = produce_output(&line, dbname);
+    if let TransactionAction::Read { r: set } = action {
+        assert!(set.error.is_some());
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+
+    // amendment must be signed by the current network verifier
+    let line = amend_specs_westend(&new_specs, "//Bob");
+    let expected_set = TransactionCardSet {
+        error: Some(vec![TransactionCard {
+            index: 0,
+            indent: 0,
+            card: Card::ErrorCard {
+                f: "Bad input data. Network westend current verifier is public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d, encryption: sr25519. Received amend_specs message is signed by public key: 8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48, encryption: sr25519. Network specs amendment must be signed by the current network verifier.".to_string(),
+            },
+        }]),
+        ..Default::default()
+    };
+    let action = produce_output(&line, dbname);
+    if let TransactionAction::Read { r: set } = action {
+        assert_eq!(set, expected_set);
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+
+    // base58 prefix could not be amended
+    let line = amend_specs_westend(
+        &NetworkSpecsToSend {
+            base58prefix: 0,
+            ..new_specs.to_owned()
+        },
+        "//Alice",
+    );
+    let expected_set = TransactionCardSet {
+        error: Some(vec![TransactionCard {
+            index: 0,
+            indent: 0,
+            card: Card::ErrorCard {
+                f: "Bad input data. Received amend_specs message changes base58prefix of network westend. Only unit, decimals, title, logo and colors could be amended.".to_string(),
+            },
+        }]),
+        ..Default::default()
+    };
+    let action = produce_output(&line, dbname);
+    if let TransactionAction::Read { r: set } = action {
+        assert_eq!(set, expected_set);
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+
+    let line = amend_specs_westend(&new_specs, "//Alice");
+    let expected_set = TransactionCardSet {
+        verifier: Some(vec![TransactionCard {
+            index: 0,
+            indent: 0,
+            card: Card::VerifierCard {
+                f: MVerifierDetails {
+                    public_key: "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+                        .to_string(),
+                    identicon: alice_sr_alice().to_vec(),
+                    encryption: "sr25519".to_string(),
+                },
+            },
+        }]),
+        warning: Some(vec![TransactionCard {
+            index: 1,
+            indent: 0,
+            card: Card::WarningCard {
+                f: "Received message amends network specs of westend, and is signed by the current network verifier. Following fields will be changed: unit: WND -> WUND, decimals: 12 -> 10. Addresses and metadata of the network are kept.".to_string(),
+                message: LocalisedMessage::new("warning.specs_amendment")
+                    .with_text("name", "westend")
+                    .with_list(
+                        "changes",
+                        vec![
+                            "unit: WND -> WUND".to_string(),
+                            "decimals: 12 -> 10".to_string(),
+                        ],
+                    ),
+            },
+        }]),
+        old_specs: Some(vec![TransactionCard {
+            index: 2,
+            indent: 0,
+            card: Card::NewSpecsCard {
+                f: old_specs.to_owned(),
+            },
+        }]),
+        new_specs: Some(vec![TransactionCard {
+            index: 3,
+            indent: 0,
+            card: Card::NewSpecsCard {
+                f: new_specs.to_owned(),
+            },
+        }]),
+        ..Default::default()
+    };
+    let action = produce_output(&line, dbname);
+    if let TransactionAction::Stub { s: set, u, stub } = action {
+        assert_eq!(set, expected_set);
+        assert_eq!(
+            stub,
+            StubNav::AmendSpecs {
+                n: network_specs_key.to_owned(),
+            }
+        );
+        TrDbColdStub::from_storage(dbname, u)
+            .unwrap()
+            .apply(dbname)
+            .unwrap();
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+
+    let amended = try_get_network_specs(dbname, &network_specs_key)
+        .unwrap()
+        .unwrap();
+    assert_eq!(amended, new_specs.to_store(westend_spec().order));
+    let current_history: Vec<_> = get_history(dbname)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.1)
+        .collect();
+    assert!(entries_contain_event(
+        &current_history,
+        &Event::NetworkSpecsTokensAmended {
+            network_specs_amendment_tokens_display: NetworkSpecsAmendmentTokensDisplay::get(
+                &westend_spec(),
+                &amended,
+            ),
+        }
+    ));
+
+    // same specs are not accepted again
+    let action = produce_output(&amend_specs_westend(&new_specs, "//Alice"), dbname);
+    if let TransactionAction::Read { r: set } = action {
+        assert!(set.error.is_some());
+    } else {
+        panic!("Wrong action {:?}", action)
+    }
+    fs::remove_dir_all(dbname).unwrap();
+}
+
+#[test]
+fn contact_lookalike_skips_network_prefix_chars() {
+    use crate::helpers::{lookalike, prefix_chars};
+    use sp_core::crypto::Ss58AddressFormat;
+
+    // all westend addresses start with `5`, this character carries no
+    // information about the account id
+    let skip = prefix_chars(Ss58AddressFormat::custom(42));
+    assert_eq!(skip, 1);
+
+    let contact = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    // same first 5 characters, only 4 of them come from the account id
+    let not_lookalike = "5GrwvbZ3k8UPnkWbM2sCZ1tEWf8qJ2VDjDc4hRZsGnYhmo1x";
+    assert!(!lookalike(not_lookalike, contact, skip));
+
+    // same 5 characters past the network prefix character
+    let lookalike_head = "5GrwvaZ3k8UPnkWbM2sCZ1tEWf8qJ2VDjDc4hRZsGnYhmo1x";
+    assert!(lookalike(lookalike_head, contact, skip));
+
+    // same 5 trailing characters
+    let lookalike_tail = "5DAAnrj7VHTznn2AWBemMuyBwZWs6FNFjdyVXUeYum3KutQY";
+    assert!(lookalike(lookalike_tail, contact, skip));
+
+    // address is not a lookalike of itself
+    assert!(!lookalike(contact, contact, skip));
+}
@@ -1,3 +1,5 @@
+use definitions::policy::PolicyRule;
+
 /// Transaction signing result.
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -37,4 +39,18 @@ pub enum Error {
     /// Associated data is the new checksum.
     #[error("Wrong password.")]
     WrongPasswordNewChecksum(u32),
+
+    /// Signing is forbidden by the Signer
+    /// [`SecurityPolicy`](definitions::policy::SecurityPolicy).
+    ///
+    /// Signer log records the policy violation. This changes the database
+    /// checksum, and for the next attempt it must be updated.
+    #[error("Forbidden by Signer security policy: {}.", .rule.show())]
+    PolicyViolationNewChecksum {
+        /// violated rule
+        rule: PolicyRule,
+
+        /// new database checksum
+        checksum: u32,
+    },
 }
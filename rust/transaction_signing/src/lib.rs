@@ -3,12 +3,18 @@
 
 use sp_runtime::MultiSigner;
 
-use db_handling::db_transactions::TrDbColdStub;
-use definitions::{keyring::NetworkSpecsKey, navigation::MSCContent, users::AddressDetails};
+use db_handling::{
+    db_transactions::TrDbColdStub,
+    storage::{SledStorage, Storage},
+};
+use definitions::{
+    history::AuditFormat, keyring::NetworkSpecsKey, navigation::MSCContent, users::AddressDetails,
+};
 
 mod sign_message;
 use sign_message::{
-    sufficient_crypto_add_specs, sufficient_crypto_load_metadata, sufficient_crypto_load_types,
+    sign_history_audit, sufficient_crypto_add_specs, sufficient_crypto_load_metadata,
+    sufficient_crypto_load_types,
 };
 mod sign_transaction;
 use sign_transaction::create_signature_png;
@@ -19,7 +25,15 @@ mod error;
 pub use error::{Error, Result};
 
 pub fn handle_stub(checksum: u32, database_name: &str) -> Result<()> {
-    Ok(TrDbColdStub::from_storage(database_name, checksum)?.apply(database_name)?)
+    handle_stub_in(checksum, &SledStorage::open(database_name)?)
+}
+
+/// Same as [`handle_stub`], for the database in given [`Storage`].
+pub fn handle_stub_in<S>(checksum: u32, storage: &S) -> Result<()>
+where
+    S: Storage,
+{
+    Ok(TrDbColdStub::from_storage_in(storage, checksum)?.apply_in(storage)?)
 }
 
 pub fn handle_sign(
@@ -29,15 +43,29 @@ pub fn handle_sign(
     user_comment: &str,
     database_name: &str,
 ) -> Result<Vec<u8>> {
-    create_signature_png(
+    handle_sign_in(
+        checksum,
         seed_phrase,
         pwd_entry,
         user_comment,
-        database_name,
-        checksum,
+        &SledStorage::open(database_name)?,
     )
 }
 
+/// Same as [`handle_sign`], for the database in given [`Storage`].
+pub fn handle_sign_in<S>(
+    checksum: u32,
+    seed_phrase: &str,
+    pwd_entry: &str,
+    user_comment: &str,
+    storage: &S,
+) -> Result<Vec<u8>>
+where
+    S: Storage,
+{
+    create_signature_png(seed_phrase, pwd_entry, user_comment, storage, checksum)
+}
+
 ///Possible content to generate sufficient crypto for
 #[derive(Debug, Clone)]
 pub enum SufficientContent {
@@ -54,12 +82,34 @@ pub fn sign_content(
     seed_phrase: &str,
     pwd_entry: &str,
 ) -> Result<(Vec<u8>, MSCContent)> {
+    sign_content_in(
+        multisigner,
+        address_details,
+        content,
+        &SledStorage::open(database_name)?,
+        seed_phrase,
+        pwd_entry,
+    )
+}
+
+/// Same as [`sign_content`], for the database in given [`Storage`].
+pub fn sign_content_in<S>(
+    multisigner: &MultiSigner,
+    address_details: &AddressDetails,
+    content: SufficientContent,
+    storage: &S,
+    seed_phrase: &str,
+    pwd_entry: &str,
+) -> Result<(Vec<u8>, MSCContent)>
+where
+    S: Storage,
+{
     match content {
         SufficientContent::AddSpecs(network_specs_key) => sufficient_crypto_add_specs(
             &network_specs_key,
             multisigner,
             address_details,
-            database_name,
+            storage,
             seed_phrase,
             pwd_entry,
         ),
@@ -68,16 +118,75 @@ pub fn sign_content(
             version,
             multisigner,
             address_details,
-            database_name,
+            storage,
             seed_phrase,
             pwd_entry,
         ),
         SufficientContent::LoadTypes => sufficient_crypto_load_types(
             multisigner,
             address_details,
-            database_name,
+            storage,
             seed_phrase,
             pwd_entry,
         ),
     }
 }
+
+/// Export history log entries from `first_order` to `last_order` as audit
+/// report, signed with address identified by hexadecimal public key and
+/// network specs key.
+///
+/// Output is a set of qr code frames in png format.
+#[allow(clippy::too_many_arguments)]
+pub fn export_history_audit(
+    database_name: &str,
+    public_key: &str,
+    expected_seed_name: &str,
+    network_specs_key: &str,
+    seed_phrase: &str,
+    pwd_entry: &str,
+    first_order: u32,
+    last_order: u32,
+    format: AuditFormat,
+) -> Result<Vec<Vec<u8>>> {
+    export_history_audit_in(
+        &SledStorage::open(database_name)?,
+        public_key,
+        expected_seed_name,
+        network_specs_key,
+        seed_phrase,
+        pwd_entry,
+        first_order,
+        last_order,
+        format,
+    )
+}
+
+/// Same as [`export_history_audit`], for the database in given [`Storage`].
+#[allow(clippy::too_many_arguments)]
+pub fn export_history_audit_in<S>(
+    storage: &S,
+    public_key: &str,
+    expected_seed_name: &str,
+    network_specs_key: &str,
+    seed_phrase: &str,
+    pwd_entry: &str,
+    first_order: u32,
+    last_order: u32,
+    format: AuditFormat,
+) -> Result<Vec<Vec<u8>>>
+where
+    S: Storage,
+{
+    sign_history_audit(
+        storage,
+        public_key,
+        expected_seed_name,
+        network_specs_key,
+        seed_phrase,
+        pwd_entry,
+        first_order,
+        last_order,
+        format,
+    )
+}
@@ -6,17 +6,26 @@ use zeroize::Zeroize;
 use crate::{Error, Result};
 use db_handling::{
     db_transactions::TrDbCold,
-    helpers::{get_meta_values_by_name_version, get_network_specs, prep_types},
-    manage_history::events_to_batch,
+    helpers::{
+        get_address_details_in, get_meta_values_by_name_version_in, get_network_specs_in,
+        prep_types_in,
+    },
+    manage_history::{events_to_batch_in, history_audit_report_in},
+    storage::Storage,
 };
 use definitions::{
     crypto::SufficientCrypto,
-    history::{Event, MetaValuesExport, NetworkSpecsExport, TypesExport},
-    keyring::NetworkSpecsKey,
+    helpers::{get_multisigner, unhex},
+    history::{
+        AuditFormat, Event, HistoryAuditExport, MetaValuesExport, NetworkSpecsExport,
+        SignedHistoryAudit, TypesExport,
+    },
+    keyring::{AddressKey, NetworkSpecsKey},
     navigation::{MSCContent, MSCNetworkInfo},
     qr_transfers::{ContentAddSpecs, ContentLoadMeta},
     users::AddressDetails,
 };
+use qrcode_rtx::make_pretty_qr_frames;
 use qrcode_static::{png_qr, DataType};
 
 pub(crate) fn sign_as_address_key(
@@ -96,14 +105,17 @@ fn sufficient_crypto(
 }
 
 /// Function to generate hex line of qr data corresponding to `sufficient_crypto` for `load_types` message
-pub(crate) fn sufficient_crypto_load_types(
+pub(crate) fn sufficient_crypto_load_types<S>(
     multisigner: &MultiSigner,
     address_details: &AddressDetails,
-    database_name: &str,
+    storage: &S,
     seed_phrase: &str,
     pwd_entry: &str,
-) -> Result<(Vec<u8>, MSCContent)> {
-    let types_content = prep_types(database_name)?;
+) -> Result<(Vec<u8>, MSCContent)>
+where
+    S: Storage,
+{
+    let types_content = prep_types_in(storage)?;
     let sufficient = match sufficient_crypto(
         multisigner,
         address_details,
@@ -113,20 +125,20 @@ pub(crate) fn sufficient_crypto_load_types(
     ) {
         Ok(s) => {
             TrDbCold::new()
-                .set_history(events_to_batch(
-                    database_name,
+                .set_history(events_to_batch_in(
+                    storage,
                     vec![Event::TypesSigned {
                         types_export: TypesExport::get(&types_content, &s.verifier_value()),
                     }],
                 )?)
-                .apply(database_name)?;
+                .apply_in(storage)?;
             qr_from_sufficient(s)?
         }
         Err(e) => {
             if let Error::WrongPassword = e {
                 TrDbCold::new()
-                    .set_history(events_to_batch(database_name, vec![Event::WrongPassword])?)
-                    .apply(database_name)?;
+                    .set_history(events_to_batch_in(storage, vec![Event::WrongPassword])?)
+                    .apply_in(storage)?;
             }
             return Err(e);
         }
@@ -136,18 +148,21 @@ pub(crate) fn sufficient_crypto_load_types(
 }
 
 /// Function to generate hex line of qr data corresponding to `sufficient_crypto` for `load_metadata` message
-pub(crate) fn sufficient_crypto_load_metadata(
+pub(crate) fn sufficient_crypto_load_metadata<S>(
     network_specs_key: &NetworkSpecsKey,
     network_version: u32,
     multisigner: &MultiSigner,
     address_details: &AddressDetails,
-    database_name: &str,
+    storage: &S,
     seed_phrase: &str,
     pwd_entry: &str,
-) -> Result<(Vec<u8>, MSCContent)> {
-    let network_specs = get_network_specs(database_name, network_specs_key)?;
+) -> Result<(Vec<u8>, MSCContent)>
+where
+    S: Storage,
+{
+    let network_specs = get_network_specs_in(storage, network_specs_key)?;
     let meta_values =
-        get_meta_values_by_name_version(database_name, &network_specs.name, network_version)?;
+        get_meta_values_by_name_version_in(storage, &network_specs.name, network_version)?;
     let load_meta_content =
         ContentLoadMeta::generate(&meta_values.meta, &network_specs.genesis_hash);
     let sufficient = match sufficient_crypto(
@@ -159,8 +174,8 @@ pub(crate) fn sufficient_crypto_load_metadata(
     ) {
         Ok(s) => {
             TrDbCold::new()
-                .set_history(events_to_batch(
-                    database_name,
+                .set_history(events_to_batch_in(
+                    storage,
                     vec![Event::MetadataSigned {
                         meta_values_export: MetaValuesExport::get(
                             &meta_values,
@@ -168,14 +183,14 @@ pub(crate) fn sufficient_crypto_load_metadata(
                         ),
                     }],
                 )?)
-                .apply(database_name)?;
+                .apply_in(storage)?;
             qr_from_sufficient(s)?
         }
         Err(e) => {
             if let Error::WrongPassword = e {
                 TrDbCold::new()
-                    .set_history(events_to_batch(database_name, vec![Event::WrongPassword])?)
-                    .apply(database_name)?;
+                    .set_history(events_to_batch_in(storage, vec![Event::WrongPassword])?)
+                    .apply_in(storage)?;
             }
             return Err(e);
         }
@@ -190,15 +205,18 @@ pub(crate) fn sufficient_crypto_load_metadata(
 }
 
 /// Function to generate hex line of qr data corresponding to `sufficient_crypto` for `add_specs` message
-pub(crate) fn sufficient_crypto_add_specs(
+pub(crate) fn sufficient_crypto_add_specs<S>(
     network_specs_key: &NetworkSpecsKey,
     multisigner: &MultiSigner,
     address_details: &AddressDetails,
-    database_name: &str,
+    storage: &S,
     seed_phrase: &str,
     pwd_entry: &str,
-) -> Result<(Vec<u8>, MSCContent)> {
-    let network_specs_to_send = get_network_specs(database_name, network_specs_key)?.to_send();
+) -> Result<(Vec<u8>, MSCContent)>
+where
+    S: Storage,
+{
+    let network_specs_to_send = get_network_specs_in(storage, network_specs_key)?.to_send();
     let add_specs_content = ContentAddSpecs::generate(&network_specs_to_send);
     let sufficient = match sufficient_crypto(
         multisigner,
@@ -209,8 +227,8 @@ pub(crate) fn sufficient_crypto_add_specs(
     ) {
         Ok(s) => {
             TrDbCold::new()
-                .set_history(events_to_batch(
-                    database_name,
+                .set_history(events_to_batch_in(
+                    storage,
                     vec![Event::NetworkSpecsSigned {
                         network_specs_export: NetworkSpecsExport::get(
                             &network_specs_to_send,
@@ -218,14 +236,14 @@ pub(crate) fn sufficient_crypto_add_specs(
                         ),
                     }],
                 )?)
-                .apply(database_name)?;
+                .apply_in(storage)?;
             qr_from_sufficient(s)?
         }
         Err(e) => {
             if let Error::WrongPassword = e {
                 TrDbCold::new()
-                    .set_history(events_to_batch(database_name, vec![Event::WrongPassword])?)
-                    .apply(database_name)?;
+                    .set_history(events_to_batch_in(storage, vec![Event::WrongPassword])?)
+                    .apply_in(storage)?;
             }
             return Err(e);
         }
@@ -242,6 +260,93 @@ pub(crate) fn sufficient_crypto_add_specs(
     ))
 }
 
+/// Function to generate multiframe qr code frames with history log audit
+/// report, signed by given address
+///
+/// Address is found by hexadecimal public key and [`NetworkSpecsKey`], and must
+/// belong to `expected_seed_name`. Output is a set of png images, to be shown
+/// one by one, with SCALE-encoded [`SignedHistoryAudit`].
+///
+/// The export is recorded in the history log, after the reported range.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn sign_history_audit<S>(
+    storage: &S,
+    public_key: &str,
+    expected_seed_name: &str,
+    network_specs_key_hex: &str,
+    seed_phrase: &str,
+    pwd_entry: &str,
+    first_order: u32,
+    last_order: u32,
+    format: AuditFormat,
+) -> Result<Vec<Vec<u8>>>
+where
+    S: Storage,
+{
+    let public_key = unhex(public_key).map_err(db_handling::Error::from)?;
+    let network_specs_key =
+        NetworkSpecsKey::from_hex(network_specs_key_hex).map_err(db_handling::Error::from)?;
+    let network_specs = get_network_specs_in(storage, &network_specs_key)?;
+    let multisigner = get_multisigner(&public_key, &network_specs.encryption)
+        .map_err(db_handling::Error::from)?;
+    let address_key = AddressKey::from_multisigner(&multisigner);
+    let address_details = get_address_details_in(storage, &address_key)?;
+    if address_details.seed_name != expected_seed_name {
+        return Err(db_handling::Error::SeedNameNotMatching {
+            address_key,
+            expected_seed_name: expected_seed_name.to_string(),
+            real_seed_name: address_details.seed_name,
+        }
+        .into());
+    }
+    if !address_details.network_id.contains(&network_specs_key) {
+        return Err(db_handling::Error::NetworkSpecsKeyForAddressNotFound {
+            network_specs_key,
+            address_key,
+        }
+        .into());
+    }
+
+    let report = history_audit_report_in(storage, first_order, last_order, format)?;
+    let sufficient_crypto = match sufficient_crypto(
+        &multisigner,
+        &address_details,
+        &SignedHistoryAudit::signed_content(format, &report),
+        seed_phrase,
+        pwd_entry,
+    ) {
+        Ok(s) => s,
+        Err(e) => {
+            if let Error::WrongPassword = e {
+                TrDbCold::new()
+                    .set_history(events_to_batch_in(storage, vec![Event::WrongPassword])?)
+                    .apply_in(storage)?;
+            }
+            return Err(e);
+        }
+    };
+    let signed_history_audit = SignedHistoryAudit {
+        format,
+        report,
+        sufficient_crypto,
+    };
+    let frames = make_pretty_qr_frames(&signed_history_audit.encode())
+        .map_err(|e| Error::Other(anyhow::anyhow!("{}", e)))?;
+    TrDbCold::new()
+        .set_history(events_to_batch_in(
+            storage,
+            vec![Event::HistoryAuditExported {
+                history_audit_export: HistoryAuditExport::get(
+                    first_order,
+                    last_order,
+                    &signed_history_audit,
+                ),
+            }],
+        )?)
+        .apply_in(storage)?;
+    Ok(frames)
+}
+
 fn qr_from_sufficient(sufficient: SufficientCrypto) -> Result<Vec<u8>> {
     Ok(png_qr(&sufficient.encode(), DataType::Regular)?)
 }
@@ -3,7 +3,12 @@ use sp_core::blake2_256;
 use sp_runtime::MultiSignature;
 use zeroize::Zeroize;
 
-use db_handling::db_transactions::{SignContent, TrDbColdSign};
+use db_handling::{
+    db_transactions::{SignContent, TrDbColdSign},
+    helpers::enforce_policy_in,
+    storage::Storage,
+};
+use definitions::policy::PolicyRule;
 use qrcode_static::{png_qr_from_string, DataType};
 
 use crate::sign_message::sign_as_address_key;
@@ -12,14 +17,31 @@ use crate::{Error, Result};
 /// Function to create signatures using RN output action line, and user entered pin and password.
 /// Also needs database name to fetch saved transaction and key.
 
-pub(crate) fn create_signature(
+pub(crate) fn create_signature<S>(
     seed_phrase: &str,
     pwd_entry: &str,
     user_comment: &str,
-    database_name: &str,
+    storage: &S,
     checksum: u32,
-) -> Result<MultiSignature> {
-    let sign = TrDbColdSign::from_storage(database_name, checksum)?;
+) -> Result<MultiSignature>
+where
+    S: Storage,
+{
+    let sign = TrDbColdSign::from_storage_in(storage, checksum)?;
+
+    // security policy could require a comment on every signature; violation is
+    // recorded in the history log, changing the database checksum
+    if user_comment.trim().is_empty() {
+        if let Err(e) = enforce_policy_in(PolicyRule::UserComment, storage) {
+            return match e {
+                db_handling::Error::PolicyViolation(rule) => {
+                    let checksum = sign.store_and_get_checksum_in(storage)?;
+                    Err(Error::PolicyViolationNewChecksum { rule, checksum })
+                }
+                e => Err(e.into()),
+            };
+        }
+    }
     let pwd = {
         if sign.has_pwd() {
             Some(pwd_entry)
@@ -28,9 +50,9 @@ pub(crate) fn create_signature(
         }
     };
     let content_vec = match sign.content() {
-        SignContent::Transaction { method, extensions } => {
-            [method.to_vec(), extensions.to_vec()].concat()
-        }
+        SignContent::Transaction {
+            method, extensions, ..
+        } => [method.to_vec(), extensions.to_vec()].concat(),
         SignContent::Message(a) => a.encode(),
     };
 
@@ -47,13 +69,13 @@ pub(crate) fn create_signature(
     match sign_as_address_key(&content_vec, &sign.multisigner(), &full_address, pwd) {
         Ok(s) => {
             full_address.zeroize();
-            sign.apply(false, user_comment, database_name)?;
+            sign.apply_in(false, user_comment, storage)?;
             Ok(s.multi_signature())
         }
         Err(e) => {
             full_address.zeroize();
             if let Error::WrongPassword = e {
-                let checksum = sign.apply(true, user_comment, database_name)?;
+                let checksum = sign.apply_in(true, user_comment, storage)?;
                 Err(Error::WrongPasswordNewChecksum(checksum))
             } else {
                 Err(e)
@@ -62,22 +84,18 @@ pub(crate) fn create_signature(
     }
 }
 
-pub fn create_signature_png(
+pub fn create_signature_png<S>(
     seed_phrase: &str,
     pwd_entry: &str,
     user_comment: &str,
-    database_name: &str,
+    storage: &S,
     checksum: u32,
-) -> Result<Vec<u8>> {
+) -> Result<Vec<u8>>
+where
+    S: Storage,
+{
     let hex_result = hex::encode(
-        create_signature(
-            seed_phrase,
-            pwd_entry,
-            user_comment,
-            database_name,
-            checksum,
-        )?
-        .encode(),
+        create_signature(seed_phrase, pwd_entry, user_comment, storage, checksum)?.encode(),
     );
     let qr_data = png_qr_from_string(&hex_result, DataType::Regular)?;
     Ok(qr_data)
@@ -1,7 +1,8 @@
 use parity_scale_codec::{Decode, Encode};
 use pretty_assertions::assert_eq;
+use qr_reader_phone::process_payload::{process_decoded_payload, InProgress, Ready};
 use sled::{open, Db, Tree};
-use sp_core::H256;
+use sp_core::{Pair, H256};
 use sp_runtime::MultiSigner;
 use std::{fmt::Write as _, fs, io::Write, str::FromStr};
 
@@ -13,15 +14,20 @@ use constants::{
     ADDRTREE, ALICE_SEED_PHRASE, GENERALVERIFIER, METATREE, SETTREE, SPECSTREE, VERIFIERS,
 };
 use db_handling::{
-    cold_default::{populate_cold, populate_cold_no_networks},
-    helpers::remove_network,
+    cold_default::{populate_cold, populate_cold_in, populate_cold_no_networks},
+    helpers::{get_address_details, get_address_details_in, remove_network},
     identities::{remove_seed, try_create_address, try_create_seed},
-    manage_history::{get_history, get_history_entry_by_order},
+    manage_history::{get_history, get_history_entry_by_order, history_audit_report},
+    storage::{MemoryStorage, SledStorage},
 };
 use definitions::{
-    crypto::Encryption,
-    history::{Entry, Event, SignDisplay, SignMessageDisplay},
+    crypto::{Encryption, SufficientCrypto},
+    history::{
+        AuditFormat, Entry, Event, HistoryAuditExport, SignDisplay, SignMessageDisplay,
+        SignedHistoryAudit,
+    },
     keyring::{AddressKey, MetaKey, NetworkSpecsKey, VerifierKey},
+    localisation::LocalisedMessage,
     navigation::{
         Address, Card, MMetadataRecord, MSCAuthorPlain, MSCCall, MSCCurrency, MSCEnumVariantName,
         MSCEraMortal, MSCFieldName, MSCId, MSCNameVersion, MTypesInfo, MVerifierDetails,
@@ -31,10 +37,11 @@ use definitions::{
     users::AddressDetails,
 };
 use transaction_parsing::{
-    entry_to_transactions_with_decoding, produce_output, StubNav, TransactionAction,
+    entry_to_transactions_with_decoding, produce_output, produce_output_in, StubNav,
+    TransactionAction,
 };
 
-use crate::{handle_stub, sign_transaction::create_signature, Error, Result};
+use crate::{export_history_audit, handle_stub, sign_transaction::create_signature, Error, Result};
 
 const PWD: &str = "";
 const USER_COMMENT: &str = "";
@@ -59,7 +66,14 @@ fn sign_action_test(
     dbname: &str,
 ) -> Result<String> {
     Ok(hex::encode(
-        create_signature(seed_phrase, pwd_entry, user_comment, dbname, checksum)?.encode(),
+        create_signature(
+            seed_phrase,
+            pwd_entry,
+            user_comment,
+            &SledStorage::open(dbname)?,
+            checksum,
+        )?
+        .encode(),
     ))
 }
 
@@ -176,7 +190,7 @@ fn print_db_content(dbname: &str) -> String {
     let identities: Tree = database.open_tree(ADDRTREE).unwrap();
     for (address_key_vec, address_details_encoded) in identities.iter().flatten() {
         let address_key = AddressKey::from_ivec(&address_key_vec);
-        let address_details = AddressDetails::decode(&mut &address_details_encoded[..]).unwrap();
+        let address_details = AddressDetails::from_stored(&address_details_encoded).unwrap();
         let (public_key, encryption) = address_key.public_key_encryption().unwrap();
 
         let mut networks_set: Vec<String> = Vec::new();
@@ -262,6 +276,7 @@ fn can_sign_transaction_1() {
                     f: MSCId {
                         base58: "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
                         identicon: bob().to_vec(),
+                        contact_name: None,
                     },
                 },
             },
@@ -346,6 +361,7 @@ fn can_sign_transaction_1() {
         has_pwd: false,
         multiselect: None,
         secret_exposed: false,
+        annotation: None,
     };
 
     let network_info_known = NetworkSpecs {
@@ -363,6 +379,7 @@ fn can_sign_transaction_1() {
         path_id: "//westend".to_string(),
         secondary_color: "#262626".to_string(),
         title: "Westend".to_string(),
+        tokens: Vec::new(),
         unit: "WND".to_string(),
     };
 
@@ -475,6 +492,7 @@ fn can_sign_transaction_1() {
                         f: MSCId {
                             base58: "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
                             identicon: bob().to_vec(),
+                            contact_name: None,
                         },
                     },
                 },
@@ -587,6 +605,7 @@ fn can_sign_message_1() {
         has_pwd: false,
         multiselect: None,
         secret_exposed: false,
+        annotation: None,
     };
 
     let network_info_known = NetworkSpecs {
@@ -604,6 +623,7 @@ fn can_sign_message_1() {
         path_id: "//westend".to_string(),
         secondary_color: "#262626".to_string(),
         title: "Westend".to_string(),
+        tokens: Vec::new(),
         unit: "WND".to_string(),
     };
 
@@ -678,6 +698,68 @@ fn can_sign_message_1() {
     fs::remove_dir_all(dbname).unwrap();
 }
 
+#[test]
+fn key_usage_recorded_and_replay_warned() {
+    let storage = MemoryStorage::new();
+    populate_cold_in(&storage, Verifier { v: None }).unwrap();
+    let line = "530100d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27da40403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480700e8764817b501b8003223000005000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e538a7d7a0ac17eb6dd004578cb8e238c384a10f57c999a3fa1200409cd9b3f33e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e";
+    // message `usage`
+    let message_line = "530103d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d147573616765e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e";
+
+    let address_key = AddressKey::from_multisigner(&MultiSigner::Sr25519(
+        sp_core::sr25519::Public::from_raw(ALICE),
+    ));
+    let network_specs_key = NetworkSpecsKey::from_parts(
+        &H256::from_str("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e")
+            .unwrap(),
+        &Encryption::Sr25519,
+    );
+    let address_details = get_address_details_in(&storage, &address_key).unwrap();
+    assert!(address_details.usage(&network_specs_key).is_none());
+
+    let output = produce_output_in(line, &storage);
+    if let TransactionAction::Sign {
+        content, checksum, ..
+    } = output
+    {
+        assert_eq!(content.warning, None);
+        create_signature(ALICE_SEED_PHRASE, PWD, USER_COMMENT, &storage, checksum).unwrap();
+    } else {
+        panic!("Wrong action: {:?}", output)
+    }
+
+    let output = produce_output_in(message_line, &storage);
+    if let TransactionAction::Sign { checksum, .. } = output {
+        create_signature(ALICE_SEED_PHRASE, PWD, USER_COMMENT, &storage, checksum).unwrap();
+    } else {
+        panic!("Wrong action: {:?}", output)
+    }
+
+    let address_details = get_address_details_in(&storage, &address_key).unwrap();
+    let key_usage = address_details.usage(&network_specs_key).unwrap();
+    assert_eq!(key_usage.transactions_signed, 1);
+    assert_eq!(key_usage.messages_signed, 1);
+    assert_eq!(key_usage.last_nonce, Some(46));
+    assert!(key_usage.first_used <= key_usage.last_used);
+
+    let output = produce_output_in(line, &storage);
+    if let TransactionAction::Sign { content, .. } = output {
+        let warning_known = vec![TransactionCard {
+            index: 0,
+            indent: 0,
+            card: Card::WarningCard {
+                f: "Transaction nonce 46 is not higher than nonce 46 of a transaction already signed with this key in this network. This could be a replay or a duplicate of an already signed transaction.".to_string(),
+                message: LocalisedMessage::new("warning.nonce_replay")
+                    .with_number("nonce", 46u64)
+                    .with_number("last_nonce", 46u64),
+            },
+        }];
+        assert_eq!(content.warning, Some(warning_known));
+    } else {
+        panic!("Wrong action: {:?}", output)
+    }
+}
+
 #[test]
 fn add_specs_westend_no_network_info_not_signed() {
     let dbname = "for_tests/add_specs_westend_no_network_info_not_signed";
@@ -690,6 +772,7 @@ fn add_specs_westend_no_network_info_not_signed() {
             indent: 0,
             card: Card::WarningCard {
                 f: "Received network information is not verified.".to_string(),
+                message: LocalisedMessage::new("warning.not_verified"),
             },
         }]),
         new_specs: Some(vec![TransactionCard {
@@ -710,6 +793,7 @@ fn add_specs_westend_no_network_info_not_signed() {
                     path_id: "//westend".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "Westend".to_string(),
+                    tokens: Vec::new(),
                     unit: "WND".to_string(),
                 },
             },
@@ -772,6 +856,7 @@ fn add_specs_westend_ed25519_not_signed() {
             indent: 0,
             card: Card::WarningCard {
                 f: "Received network information is not verified.".to_string(),
+                message: LocalisedMessage::new("warning.not_verified"),
             },
         }]),
         new_specs: Some(vec![TransactionCard {
@@ -792,6 +877,7 @@ fn add_specs_westend_ed25519_not_signed() {
                     path_id: "//westend".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "westend-ed25519".to_string(),
+                    tokens: Vec::new(),
                     unit: "WND".to_string(),
                 },
             },
@@ -1017,6 +1103,7 @@ fn load_westend9070() {
             indent: 0,
             card: Card::WarningCard {
                 f: "Received network information is not verified.".to_string(),
+                message: LocalisedMessage::new("warning.not_verified"),
             },
         }]),
         meta: Some(vec![TransactionCard {
@@ -1128,10 +1215,26 @@ fn load_known_types_upd_general_verifier() {
     populate_cold(dbname, Verifier { v: None }).unwrap();
     let line = fs::read_to_string("for_tests/types_info_Alice.txt").unwrap();
     let output = produce_output(line.trim(), dbname);
-    let warning = "Received message is verified by a new general verifier. Currently no general verifier is set, and proceeding will update the general verifier to the received value. All previously acquired information associated with general verifier will be purged. Affected network specs entries: Kusama, Polkadot, Westend; affected metadata entries: kusama2030, polkadot30, westend9000, westend9010. Types information is purged.".to_string();
+    let warning = LocalisedMessage::new("warning.general_verifier_appeared_types")
+        .with_list(
+            "network_specs",
+            vec![
+                "Kusama".to_string(),
+                "Polkadot".to_string(),
+                "Westend".to_string(),
+            ],
+        )
+        .with_list(
+            "metadata",
+            vec![
+                "kusama2030".to_string(),
+                "polkadot30".to_string(),
+                "westend9000".to_string(),
+                "westend9010".to_string(),
+            ],
+        );
 
-    let warning2 =
-        "Received types information is identical to the one that was in the database.".to_string();
+    let warning2 = LocalisedMessage::new("warning.types_already_there");
 
     let reply_known = TransactionCardSet {
         verifier: Some(vec![TransactionCard {
@@ -1150,12 +1253,18 @@ fn load_known_types_upd_general_verifier() {
             TransactionCard {
                 index: 1,
                 indent: 0,
-                card: Card::WarningCard { f: warning },
+                card: Card::WarningCard {
+                    f: warning.show(),
+                    message: warning,
+                },
             },
             TransactionCard {
                 index: 2,
                 indent: 0,
-                card: Card::WarningCard { f: warning2 },
+                card: Card::WarningCard {
+                    f: warning2.show(),
+                    message: warning2,
+                },
             },
         ]),
         types_info: Some(vec![TransactionCard {
@@ -1274,6 +1383,7 @@ fn load_new_types_verified() {
             indent: 0,
             card: Card::WarningCard {
                 f: "Updating types (really rare operation).".to_string(),
+                message: LocalisedMessage::new("warning.updating_types"),
             },
         }]),
         types_info: Some(vec![TransactionCard {
@@ -1397,6 +1507,7 @@ fn dock_adventures_1() {
             indent: 0,
             card: Card::WarningCard {
                 f: "Received network information is not verified.".to_string(),
+                message: LocalisedMessage::new("warning.not_verified"),
             },
         }]),
         new_specs: Some(vec![TransactionCard {
@@ -1417,6 +1528,7 @@ fn dock_adventures_1() {
                     path_id: "//dock-pos-main-runtime".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "dock-pos-main-runtime-sr25519".to_string(),
+                    tokens: Vec::new(),
                     unit: "DOCK".to_string(),
                 },
             },
@@ -1530,6 +1642,7 @@ Identities:
             indent: 0,
             card: Card::WarningCard {
                 f: "Received network information is not verified.".to_string(),
+                message: LocalisedMessage::new("warning.not_verified"),
             },
         }]),
         meta: Some(vec![TransactionCard {
@@ -1612,9 +1725,37 @@ Identities:
         fs::read_to_string("for_tests/add_specs_dock-pos-main-runtime-sr25519_Alice-sr25519.txt")
             .unwrap();
     let output = produce_output(line.trim(), dbname);
-    let warning_1 = "Received message is verified. Currently no verifier is set for network with genesis hash 6bfe24dca2a3be10f22212678ac13a6446ec764103c0f3471c71609eac384aae and no general verifier is set. Proceeding will update the network verifier to general. All previously acquired network information that was received unverified will be purged. Affected network specs entries: dock-pos-main-runtime-sr25519; affected metadata entries: dock-pos-main-runtime31.".to_string();
-    let warning_2 = "Received message is verified by a new general verifier. Currently no general verifier is set, and proceeding will update the general verifier to the received value. All previously acquired information associated with general verifier will be purged. Affected network specs entries: Kusama, Polkadot, Westend; affected metadata entries: kusama2030, polkadot30, westend9000, westend9010. Types information is purged.".to_string();
-    let warning_3 = "Received network specs information for dock-pos-main-runtime-sr25519 is same as the one already in the database.".to_string();
+    let warning_1 = LocalisedMessage::new("warning.verifier_general_super")
+        .with_hex(
+            "genesis_hash",
+            hex::decode("6bfe24dca2a3be10f22212678ac13a6446ec764103c0f3471c71609eac384aae")
+                .unwrap(),
+        )
+        .with_list(
+            "network_specs",
+            vec!["dock-pos-main-runtime-sr25519".to_string()],
+        )
+        .with_list("metadata", vec!["dock-pos-main-runtime31".to_string()]);
+    let warning_2 = LocalisedMessage::new("warning.general_verifier_appeared_types")
+        .with_list(
+            "network_specs",
+            vec![
+                "Kusama".to_string(),
+                "Polkadot".to_string(),
+                "Westend".to_string(),
+            ],
+        )
+        .with_list(
+            "metadata",
+            vec![
+                "kusama2030".to_string(),
+                "polkadot30".to_string(),
+                "westend9000".to_string(),
+                "westend9010".to_string(),
+            ],
+        );
+    let warning_3 = LocalisedMessage::new("warning.network_specs_already_there")
+        .with_text("title", "dock-pos-main-runtime-sr25519");
 
     let reply_known = TransactionCardSet {
         verifier: Some(vec![TransactionCard {
@@ -1633,17 +1774,26 @@ Identities:
             TransactionCard {
                 index: 1,
                 indent: 0,
-                card: Card::WarningCard { f: warning_1 },
+                card: Card::WarningCard {
+                    f: warning_1.show(),
+                    message: warning_1,
+                },
             },
             TransactionCard {
                 index: 2,
                 indent: 0,
-                card: Card::WarningCard { f: warning_2 },
+                card: Card::WarningCard {
+                    f: warning_2.show(),
+                    message: warning_2,
+                },
             },
             TransactionCard {
                 index: 3,
                 indent: 0,
-                card: Card::WarningCard { f: warning_3 },
+                card: Card::WarningCard {
+                    f: warning_3.show(),
+                    message: warning_3,
+                },
             },
         ]),
         new_specs: Some(vec![TransactionCard {
@@ -1664,6 +1814,7 @@ Identities:
                     path_id: "//dock-pos-main-runtime".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "dock-pos-main-runtime-sr25519".to_string(),
+                    tokens: Vec::new(),
                     unit: "DOCK".to_string(),
                 },
             },
@@ -1742,6 +1893,7 @@ fn dock_adventures_2() {
             indent: 0,
             card: Card::WarningCard {
                 f: "Received network information is not verified.".to_string(),
+                message: LocalisedMessage::new("warning.not_verified"),
             },
         }]),
         new_specs: Some(vec![TransactionCard {
@@ -1762,6 +1914,7 @@ fn dock_adventures_2() {
                     path_id: "//dock-pos-main-runtime".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "dock-pos-main-runtime-sr25519".to_string(),
+                    tokens: Vec::new(),
                     unit: "DOCK".to_string(),
                 },
             },
@@ -1877,6 +2030,7 @@ Identities:
             indent: 0,
             card: Card::WarningCard {
                 f: "Received network information is not verified.".to_string(),
+                message: LocalisedMessage::new("warning.not_verified"),
             },
         }]),
         meta: Some(vec![TransactionCard {
@@ -1961,9 +2115,20 @@ Identities:
         fs::read_to_string("for_tests/add_specs_dock-pos-main-runtime-sr25519_Alice-ed25519.txt")
             .unwrap();
     let output = produce_output(line.trim(), dbname);
-    let warning_1 = "Received message is verified. Currently no verifier is set for network with genesis hash 6bfe24dca2a3be10f22212678ac13a6446ec764103c0f3471c71609eac384aae. Proceeding will update the network verifier to custom verifier. All previously acquired network information that was received unverified will be purged. Affected network specs entries: dock-pos-main-runtime-sr25519; affected metadata entries: dock-pos-main-runtime31.".to_string();
+    let warning_1 = LocalisedMessage::new("warning.verifier_changing_to_custom")
+        .with_hex(
+            "genesis_hash",
+            hex::decode("6bfe24dca2a3be10f22212678ac13a6446ec764103c0f3471c71609eac384aae")
+                .unwrap(),
+        )
+        .with_list(
+            "network_specs",
+            vec!["dock-pos-main-runtime-sr25519".to_string()],
+        )
+        .with_list("metadata", vec!["dock-pos-main-runtime31".to_string()]);
 
-    let warning_2 = "Received network specs information for dock-pos-main-runtime-sr25519 is same as the one already in the database.".to_string();
+    let warning_2 = LocalisedMessage::new("warning.network_specs_already_there")
+        .with_text("title", "dock-pos-main-runtime-sr25519");
 
     let reply_known = TransactionCardSet {
         verifier: Some(vec![TransactionCard {
@@ -1982,12 +2147,18 @@ Identities:
             TransactionCard {
                 index: 1,
                 indent: 0,
-                card: Card::WarningCard { f: warning_1 },
+                card: Card::WarningCard {
+                    f: warning_1.show(),
+                    message: warning_1,
+                },
             },
             TransactionCard {
                 index: 2,
                 indent: 0,
-                card: Card::WarningCard { f: warning_2 },
+                card: Card::WarningCard {
+                    f: warning_2.show(),
+                    message: warning_2.clone(),
+                },
             },
         ]),
         new_specs: Some(vec![TransactionCard {
@@ -2008,6 +2179,7 @@ Identities:
                     path_id: "//dock-pos-main-runtime".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "dock-pos-main-runtime-sr25519".to_string(),
+                    tokens: Vec::new(),
                     unit: "DOCK".to_string(),
                 },
             },
@@ -2078,8 +2250,19 @@ Identities:
         panic!("Wrong action: {:?}", output)
     }
 
-    let warning = "Received message is verified by the general verifier. Current verifier for network with genesis hash 6bfe24dca2a3be10f22212678ac13a6446ec764103c0f3471c71609eac384aae is a custom one, and proceeding will update the network verifier to general. All previously acquired information associated with former custom verifier will be purged. Affected network specs entries: dock-pos-main-runtime-sr25519; affected metadata entries: none.".to_string();
-    let warning_2 = "Received network specs information for dock-pos-main-runtime-sr25519 is same as the one already in the database.".to_string();
+    let warning = LocalisedMessage::new("warning.verifier_changing_to_general")
+        .with_hex(
+            "genesis_hash",
+            hex::decode("6bfe24dca2a3be10f22212678ac13a6446ec764103c0f3471c71609eac384aae")
+                .unwrap(),
+        )
+        .with_list(
+            "network_specs",
+            vec!["dock-pos-main-runtime-sr25519".to_string()],
+        )
+        .with_list("metadata", Vec::new());
+    let warning_2 = LocalisedMessage::new("warning.network_specs_already_there")
+        .with_text("title", "dock-pos-main-runtime-sr25519");
     let line =
         fs::read_to_string("for_tests/add_specs_dock-pos-main-runtime-sr25519_Alice-sr25519.txt")
             .unwrap();
@@ -2101,12 +2284,18 @@ Identities:
             TransactionCard {
                 index: 1,
                 indent: 0,
-                card: Card::WarningCard { f: warning },
+                card: Card::WarningCard {
+                    f: warning.show(),
+                    message: warning,
+                },
             },
             TransactionCard {
                 index: 2,
                 indent: 0,
-                card: Card::WarningCard { f: warning_2 },
+                card: Card::WarningCard {
+                    f: warning_2.show(),
+                    message: warning_2,
+                },
             },
         ]),
         new_specs: Some(vec![TransactionCard {
@@ -2127,6 +2316,7 @@ Identities:
                     path_id: "//dock-pos-main-runtime".to_string(),
                     secondary_color: "#262626".to_string(),
                     title: "dock-pos-main-runtime-sr25519".to_string(),
+                    tokens: Vec::new(),
                     unit: "DOCK".to_string(),
                 },
             },
@@ -2210,6 +2400,7 @@ fn can_parse_westend_with_v14() {
             indent: 0,
             card: Card::WarningCard {
                 f: "Received network information is not verified.".to_string(),
+                message: LocalisedMessage::new("warning.not_verified"),
             },
         }]),
         meta: Some(vec![TransactionCard {
@@ -2374,6 +2565,7 @@ Identities:
                     f: MSCId {
                         base58: "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
                         identicon: bob().to_vec(),
+                        contact_name: None,
                     },
                 },
             },
@@ -2464,6 +2656,7 @@ Identities:
         has_pwd: false,
         multiselect: None,
         secret_exposed: false,
+        annotation: None,
     };
     // TODO: let network_info_known = r#""network_title":"Westend","network_logo":"westend""#;
 
@@ -2568,6 +2761,7 @@ Identities:
                     f: MSCId {
                         base58: "5DfhGyQdFobKM8NsWvEeAKk5EQQgYe9AydgJ7rMB6E1EqRzV".to_string(),
                         identicon: alice_sr_root().to_vec(),
+                        contact_name: None,
                     },
                 },
             },
@@ -2661,6 +2855,7 @@ Identities:
                     f: MSCId {
                         base58: "5CFPcUJgYgWryPaV1aYjSbTpbTLu42V32Ytw1L9rfoMAsfGh".to_string(),
                         identicon: id_04().to_vec(),
+                        contact_name: None,
                     },
                 },
             },
@@ -2681,6 +2876,7 @@ Identities:
                     f: MSCId {
                         base58: "5G1ojzh47Yt8KoYhuAjXpHcazvsoCXe3G8LZchKDvumozJJJ".to_string(),
                         identicon: id_01().to_vec(),
+                        contact_name: None,
                     },
                 },
             },
@@ -2701,6 +2897,7 @@ Identities:
                     f: MSCId {
                         base58: "5FZoQhgUCmqBxnkHX7jCqThScS2xQWiwiF61msg63CFL3Y8f".to_string(),
                         identicon: id_02().to_vec(),
+                        contact_name: None,
                     },
                 },
             },
@@ -2767,6 +2964,7 @@ Identities:
         has_pwd: false,
         multiselect: None,
         secret_exposed: false,
+        annotation: None,
     };
     // TODO let network_info_known = r#""network_title":"Westend","network_logo":"westend""#;
 
@@ -2828,6 +3026,7 @@ fn parse_transaction_alice_remarks_westend9122() {
             indent: 0,
             card: Card::WarningCard {
                 f: "Received network information is not verified.".to_string(),
+                message: LocalisedMessage::new("warning.not_verified"),
             },
         }]),
         meta: Some(vec![TransactionCard {
@@ -2973,6 +3172,7 @@ fn parse_transaction_alice_remarks_westend9122() {
         has_pwd: false,
         multiselect: None,
         secret_exposed: false,
+        annotation: None,
     };
     // TODO let network_info_known = r#""network_title":"Westend","network_logo":"westend""#;
 
@@ -3014,9 +3214,26 @@ fn proper_hold_display() {
     }
 
     let line = fs::read_to_string("for_tests/types_info_Alice.txt").unwrap();
-    let warning_1 = "Received message is verified by a new general verifier. Currently no general verifier is set, and proceeding will update the general verifier to the received value. All previously acquired information associated with general verifier will be purged. Affected network specs entries: Kusama, Polkadot, Westend, westend-ed25519; affected metadata entries: kusama2030, polkadot30, westend9000, westend9010. Types information is purged.".to_string();
-    let warning_2 =
-        "Received types information is identical to the one that was in the database.".to_string();
+    let warning_1 = LocalisedMessage::new("warning.general_verifier_appeared_types")
+        .with_list(
+            "network_specs",
+            vec![
+                "Kusama".to_string(),
+                "Polkadot".to_string(),
+                "Westend".to_string(),
+                "westend-ed25519".to_string(),
+            ],
+        )
+        .with_list(
+            "metadata",
+            vec![
+                "kusama2030".to_string(),
+                "polkadot30".to_string(),
+                "westend9000".to_string(),
+                "westend9010".to_string(),
+            ],
+        );
+    let warning_2 = LocalisedMessage::new("warning.types_already_there");
 
     let output = produce_output(line.trim(), dbname);
     let reply_known = TransactionCardSet {
@@ -3036,12 +3253,18 @@ fn proper_hold_display() {
             TransactionCard {
                 index: 1,
                 indent: 0,
-                card: Card::WarningCard { f: warning_1 },
+                card: Card::WarningCard {
+                    f: warning_1.show(),
+                    message: warning_1,
+                },
             },
             TransactionCard {
                 index: 2,
                 indent: 0,
-                card: Card::WarningCard { f: warning_2 },
+                card: Card::WarningCard {
+                    f: warning_2.show(),
+                    message: warning_2,
+                },
             },
         ]),
         types_info: Some(vec![TransactionCard {
@@ -3369,6 +3592,7 @@ Identities:"#;
             indent: 0,
             card: Card::WarningCard {
                 f: "Transaction author public key not found.".to_string(),
+                message: LocalisedMessage::new("warning.author_not_found"),
             },
         }]),
         method: Some(vec![
@@ -3418,6 +3642,7 @@ Identities:"#;
                     f: MSCId {
                         base58: "25rZGFcFEWz1d81xB98PJN8LQu5cCwjyazAerGkng5NDuk9C".to_string(),
                         identicon: id_05().to_vec(),
+                        contact_name: None,
                     },
                 },
             },
@@ -3528,20 +3753,26 @@ fn shell_no_token_warning_on_metadata() {
     let line = fs::read_to_string("for_tests/load_metadata_shellV200_unverified.txt").unwrap();
     let output = produce_output(line.trim(), dbname);
 
-    let warning_1 = "Received metadata has incomplete set of signed extensions. As a result, Signer may be unable to parse signable transactions using this metadata.".to_string();
-    let warning_2 = "Received network information is not verified.".to_string();
+    let warning_1 = LocalisedMessage::new("warning.metadata_extensions_incomplete");
+    let warning_2 = LocalisedMessage::new("warning.not_verified");
 
     let reply_known = TransactionCardSet {
         warning: Some(vec![
             TransactionCard {
                 index: 0,
                 indent: 0,
-                card: Card::WarningCard { f: warning_1 },
+                card: Card::WarningCard {
+                    f: warning_1.show(),
+                    message: warning_1,
+                },
             },
             TransactionCard {
                 index: 1,
                 indent: 0,
-                card: Card::WarningCard { f: warning_2 },
+                card: Card::WarningCard {
+                    f: warning_2.show(),
+                    message: warning_2,
+                },
             },
         ]),
         meta: Some(vec![TransactionCard {
@@ -3879,3 +4110,93 @@ Identities:"#;
 
     fs::remove_dir_all(dbname).unwrap();
 }
+
+/// Read payload from png qr code frames, as the hot side would.
+fn payload_from_frames(frames: &[Vec<u8>]) -> Vec<u8> {
+    let mut decoding = InProgress::None;
+    for frame in frames.iter() {
+        let image = image::load_from_memory(frame).unwrap().to_luma8();
+        let mut qr_decoder = quircs::Quirc::new();
+        let code = qr_decoder
+            .identify(image.width() as usize, image.height() as usize, &image)
+            .next()
+            .unwrap()
+            .unwrap();
+        match process_decoded_payload(code.decode().unwrap().payload, decoding).unwrap() {
+            Ready::Yes(payload) => return payload,
+            Ready::NotYet(in_progress) => decoding = in_progress,
+        }
+    }
+    panic!("Frames do not contain complete payload.")
+}
+
+/// Sign data with Alice root sr25519 key.
+fn sign_as_alice_root(data: &[u8]) -> SufficientCrypto {
+    let pair = sp_core::sr25519::Pair::from_string(ALICE_SEED_PHRASE, None).unwrap();
+    SufficientCrypto::Sr25519 {
+        public: pair.public(),
+        signature: pair.sign(data),
+    }
+}
+
+#[test]
+fn export_history_audit_alice() {
+    let dbname = "for_tests/export_history_audit_alice";
+    populate_cold(dbname, Verifier { v: None }).unwrap();
+    let alice_root = "46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a";
+    let westend_sr25519 = "01e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e";
+
+    let frames = export_history_audit(
+        dbname,
+        alice_root,
+        "Alice",
+        westend_sr25519,
+        ALICE_SEED_PHRASE,
+        PWD,
+        0,
+        1,
+        AuditFormat::Csv,
+    )
+    .unwrap();
+    let signed_history_audit =
+        <SignedHistoryAudit>::decode(&mut &payload_from_frames(&frames)[..]).unwrap();
+    assert!(signed_history_audit.is_valid());
+    assert_eq!(signed_history_audit.format, AuditFormat::Csv);
+    let report = history_audit_report(dbname, 0, 1, AuditFormat::Csv).unwrap();
+    assert_eq!(signed_history_audit.report, report);
+
+    // signature is made for the wrapped report only
+    let unwrapped = SignedHistoryAudit {
+        format: AuditFormat::Csv,
+        report: report.to_vec(),
+        sufficient_crypto: sign_as_alice_root(&report),
+    };
+    assert!(!unwrapped.is_valid());
+
+    // export is recorded in the history log
+    let history = get_history(dbname).unwrap();
+    assert_eq!(
+        history[0].1.events,
+        vec![Event::HistoryAuditExported {
+            history_audit_export: HistoryAuditExport::get(0, 1, &signed_history_audit),
+        }]
+    );
+
+    let result = export_history_audit(
+        dbname,
+        alice_root,
+        "Bob",
+        westend_sr25519,
+        ALICE_SEED_PHRASE,
+        PWD,
+        0,
+        1,
+        AuditFormat::Csv,
+    );
+    if let Err(Error::DbHandling(db_handling::Error::SeedNameNotMatching { .. })) = result {
+    } else {
+        panic!("Expected seed name mismatch. Got: {:?}", result)
+    }
+
+    fs::remove_dir_all(dbname).unwrap();
+}